@@ -9,6 +9,9 @@ fn evm(c: &mut Criterion) {
     bench::transfer_multi::run(c);
     bench::evm_build::run(c);
     bench::gas_cost_estimator::run(c);
+    bench::storage_loop::run(c);
+    bench::keccak_cache::run(c);
+    bench::workloads::run(c);
 }
 criterion_group!(benches, evm);
 criterion_main!(benches);
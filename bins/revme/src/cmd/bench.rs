@@ -2,9 +2,12 @@ pub mod analysis;
 pub mod burntpix;
 pub mod evm_build;
 pub mod gas_cost_estimator;
+pub mod keccak_cache;
 pub mod snailtracer;
+pub mod storage_loop;
 pub mod transfer;
 pub mod transfer_multi;
+pub mod workloads;
 
 use clap::{Parser, ValueEnum};
 
@@ -17,6 +20,9 @@ pub enum BenchName {
     EvmBuild,
     TransferMulti,
     GasCostEstimator,
+    StorageLoop,
+    KeccakCache,
+    Workloads,
 }
 
 impl BenchName {
@@ -28,6 +34,9 @@ impl BenchName {
         BenchName::TransferMulti,
         BenchName::EvmBuild,
         BenchName::GasCostEstimator,
+        BenchName::StorageLoop,
+        BenchName::KeccakCache,
+        BenchName::Workloads,
     ];
 
     pub fn as_str(self) -> &'static str {
@@ -39,6 +48,9 @@ impl BenchName {
             BenchName::EvmBuild => "evm-build",
             BenchName::TransferMulti => "transfer-multi",
             BenchName::GasCostEstimator => "gas-cost-estimator",
+            BenchName::StorageLoop => "storage-loop",
+            BenchName::KeccakCache => "keccak-cache",
+            BenchName::Workloads => "workloads",
         }
     }
 }
@@ -92,6 +104,15 @@ impl Cmd {
             BenchName::GasCostEstimator => {
                 gas_cost_estimator::run(&mut criterion);
             }
+            BenchName::StorageLoop => {
+                storage_loop::run(&mut criterion);
+            }
+            BenchName::KeccakCache => {
+                keccak_cache::run(&mut criterion);
+            }
+            BenchName::Workloads => {
+                workloads::run(&mut criterion);
+            }
         }
     }
 }
@@ -0,0 +1,84 @@
+use bench_workloads::keccak_loop::{self, ITERATIONS};
+use context::TxEnv;
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+use revm::{
+    bytecode::opcode, primitives::TxKind, state::Bytecode, Context, ExecuteEvm, MainBuilder,
+    MainContext,
+};
+
+/// Cache size used for the cached runs.
+const CACHE_SIZE: usize = 1024;
+
+/// Returns code that hashes the same two words of memory [`ITERATIONS`] times.
+fn repeated_bytecode() -> Bytecode {
+    let [high, low] = ITERATIONS.to_be_bytes();
+    Bytecode::new_legacy(
+        vec![
+            opcode::PUSH2,
+            high,
+            low,
+            opcode::JUMPDEST,
+            // pop(keccak256(0, 64))
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            opcode::POP,
+            // loop while the counter is not zero
+            opcode::PUSH1,
+            1,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP1,
+            opcode::PUSH1,
+            3,
+            opcode::JUMPI,
+            opcode::STOP,
+        ]
+        .into(),
+    )
+}
+
+/// Compares KECCAK256 with and without the keccak cache, for inputs that always miss and
+/// for inputs that always hit.
+pub fn run(criterion: &mut Criterion) {
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .gas_limit(30_000_000)
+        .build()
+        .unwrap();
+
+    let mut group = criterion.benchmark_group("keccak_cache");
+    for (name, bytecode) in [
+        ("miss", keccak_loop::bytecode(ITERATIONS, 2)),
+        ("hit", repeated_bytecode()),
+    ] {
+        for cache_size in [0, CACHE_SIZE] {
+            let mut evm = Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
+                .modify_cfg_chained(|cfg| {
+                    cfg.disable_nonce_check = true;
+                    cfg.keccak_cache_size = cache_size;
+                })
+                .build_mainnet();
+            let name = if cache_size == 0 {
+                format!("{name}_uncached")
+            } else {
+                format!("{name}_cached")
+            };
+            group.bench_function(name, |b| {
+                b.iter_batched(
+                    || tx.clone(),
+                    |input| {
+                        let output = evm.transact(input).unwrap();
+                        assert!(output.result.is_success());
+                    },
+                    criterion::BatchSize::SmallInput,
+                );
+            });
+        }
+    }
+    group.finish();
+}
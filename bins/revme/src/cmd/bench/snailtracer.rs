@@ -3,13 +3,12 @@ use criterion::Criterion;
 use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
 use inspector::CountInspector;
 use revm::{
-    bytecode::Bytecode,
-    primitives::{bytes, hex, Bytes, TxKind},
+    primitives::{bytes, TxKind},
     Context, ExecuteEvm, InspectEvm, MainBuilder, MainContext,
 };
 
 pub fn run(criterion: &mut Criterion) {
-    let bytecode = Bytecode::new_raw(Bytes::from(hex::decode(BYTES).unwrap()));
+    let bytecode = bench_workloads::snailtracer::bytecode();
 
     let mut evm = Context::mainnet()
         .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
@@ -51,5 +50,3 @@ pub fn run(criterion: &mut Criterion) {
         );
     });
 }
-
-const BYTES: &str = include_str!("snailtracer.hex");
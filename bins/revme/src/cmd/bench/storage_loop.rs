@@ -0,0 +1,64 @@
+//! Storage heavy loops, run with and without the `slot_cache` feature to compare the cache of
+//! hot storage slots against plain journal lookups:
+//!
+//! ```sh
+//! cargo run --release -p revme -- bench storage-loop
+//! cargo run --release -p revme --features slot_cache -- bench storage-loop
+//! ```
+use bench_workloads::{
+    storage_loop::{bytecode, ITERATIONS},
+    swap,
+};
+use context::TxEnv;
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+use revm::{
+    primitives::{TxKind, U256},
+    Context, ExecuteEvm, MainBuilder, MainContext,
+};
+
+pub fn run(criterion: &mut Criterion) {
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(bytecode()))
+        .modify_cfg_chained(|cfg| cfg.disable_nonce_check = true)
+        .build_mainnet();
+
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .gas_limit(10_000_000)
+        .build()
+        .unwrap();
+
+    let suffix = if cfg!(feature = "slot_cache") {
+        "_slot_cache"
+    } else {
+        ""
+    };
+    criterion.bench_function(&format!("storage_loop{suffix}"), |b| {
+        b.iter_batched(
+            || tx.clone(),
+            |input| {
+                let output = evm.transact(input).unwrap();
+                assert!(output.result.is_success());
+                assert_eq!(
+                    output.state[&BENCH_TARGET].storage[&U256::ZERO].present_value,
+                    U256::from(ITERATIONS)
+                );
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    let workload = swap::workload();
+    criterion.bench_function(&format!("swaps{suffix}"), |b| {
+        b.iter_batched(
+            || workload.evm(),
+            |mut evm| {
+                let report = workload.run_with(&mut evm).unwrap();
+                assert!(report.is_success());
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
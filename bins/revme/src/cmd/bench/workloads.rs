@@ -0,0 +1,19 @@
+use criterion::Criterion;
+
+/// Runs every workload of the `revm-bench-workloads` library.
+pub fn run(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("workloads");
+    for workload in bench_workloads::all() {
+        group.bench_function(workload.name, |b| {
+            b.iter_batched(
+                || workload.evm(),
+                |mut evm| {
+                    let report = workload.run_with(&mut evm).unwrap();
+                    assert!(report.is_success());
+                },
+                criterion::BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
@@ -0,0 +1,103 @@
+//! Call trees, a contract that calls itself recursively.
+use crate::{call, db_with_target, Workload, BENCH_TARGET};
+use revm::{
+    bytecode::opcode,
+    primitives::{Bytes, U256},
+    state::Bytecode,
+};
+
+/// Depth of the call chain.
+pub const CHAIN_DEPTH: u8 = 255;
+
+/// Depth of the call tree, the tree has `2^(depth + 1) - 1` frames.
+pub const TREE_DEPTH: u8 = 9;
+
+/// Returns code that calls itself `fanout` times while the depth in the calldata is not zero,
+/// every call passes the depth decremented by one.
+pub fn bytecode(fanout: u8) -> Bytecode {
+    let call = [
+        // call(gas(), address(), 0, 0, 0x20, 0, 0)
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::ADDRESS,
+        opcode::GAS,
+        opcode::CALL,
+        opcode::POP,
+    ];
+    let mut code = vec![
+        // stop if the depth is zero
+        opcode::PUSH0,
+        opcode::CALLDATALOAD,
+        opcode::DUP1,
+        opcode::ISZERO,
+        opcode::PUSH2,
+        // Offset of the jump destination, set below.
+        0,
+        0,
+        opcode::JUMPI,
+        // mstore(0, depth - 1)
+        opcode::PUSH1,
+        1,
+        opcode::SWAP1,
+        opcode::SUB,
+        opcode::PUSH0,
+        opcode::MSTORE,
+    ];
+    for _ in 0..fanout {
+        code.extend(call);
+    }
+    let [high, low] = u16::try_from(code.len() + 1)
+        .expect("fanout fits the code")
+        .to_be_bytes();
+    code[5] = high;
+    code[6] = low;
+    code.extend([opcode::STOP, opcode::JUMPDEST, opcode::STOP]);
+    Bytecode::new_legacy(code.into())
+}
+
+/// Returns the workload with the call chain of [`CHAIN_DEPTH`] frames.
+pub fn chain() -> Workload {
+    workload_with("call_chain", CHAIN_DEPTH, 1)
+}
+
+/// Returns the workload with the binary call tree of [`TREE_DEPTH`] levels.
+pub fn tree() -> Workload {
+    workload_with("call_tree", TREE_DEPTH, 2)
+}
+
+/// Returns the workload with the call tree of the given depth where every frame calls
+/// `fanout` frames.
+pub fn workload_with(name: &'static str, depth: u8, fanout: u8) -> Workload {
+    let tx = call(BENCH_TARGET)
+        .data(Bytes::from(U256::from(depth).to_be_bytes::<32>()))
+        .gas_limit(30_000_000)
+        .build()
+        .unwrap();
+    Workload::new(name, db_with_target(bytecode(fanout)), vec![tx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{inspector::CountInspector, InspectEvm};
+
+    fn frames(workload: Workload) -> u64 {
+        let mut evm = workload.evm().with_inspector(CountInspector::new());
+        let result = evm.inspect_one_tx(workload.txs[0].clone()).unwrap();
+        assert!(result.is_success());
+        evm.inspector.call_count()
+    }
+
+    #[test]
+    fn calls_itself() {
+        assert_eq!(frames(workload_with("call_tree", 0, 2)), 1);
+        assert_eq!(frames(workload_with("call_tree", 3, 2)), 15);
+        assert_eq!(frames(workload_with("call_tree", 2, 3)), 13);
+        assert_eq!(frames(chain()), u64::from(CHAIN_DEPTH) + 1);
+        assert_eq!(frames(tree()), (1 << (TREE_DEPTH + 1)) - 1);
+    }
+}
@@ -0,0 +1,154 @@
+//! ERC-20 transfer storm, many token transfers from one holder to distinct recipients.
+use crate::{call, db_with_target, Workload, BENCH_CALLER, BENCH_TARGET};
+use revm::{
+    bytecode::opcode,
+    primitives::{keccak256, Address, Bytes, StorageKey, StorageValue, U256},
+    state::Bytecode,
+};
+
+/// Number of transfers of the default workload.
+pub const TRANSFERS: usize = 1000;
+
+/// Token balance of [`BENCH_CALLER`] before the first transfer.
+pub const CALLER_BALANCE: u64 = 1_000_000_000;
+
+/// First recipient, recipients are consecutive addresses.
+const FIRST_RECIPIENT: u64 = 0x1000;
+
+/// Returns code of a minimal token with the balance mapping at slot `0`, like the Solidity
+/// `mapping(address => uint256)`.
+///
+/// Calldata is the recipient and the amount as two words, without a selector. Transfer
+/// reverts if the caller balance is smaller than the amount.
+pub fn bytecode() -> Bytecode {
+    Bytecode::new_legacy(
+        vec![
+            // from = keccak256(caller, 0)
+            opcode::CALLER,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            // [from, amount, balance]
+            opcode::PUSH1,
+            0x20,
+            opcode::CALLDATALOAD,
+            opcode::DUP2,
+            opcode::SLOAD,
+            // revert if balance < amount
+            opcode::DUP2,
+            opcode::DUP2,
+            opcode::LT,
+            opcode::PUSH1,
+            38,
+            opcode::JUMPI,
+            // sstore(from, balance - amount)
+            opcode::DUP2,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP3,
+            opcode::SSTORE,
+            // to = keccak256(recipient, 0)
+            opcode::PUSH0,
+            opcode::CALLDATALOAD,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            // sstore(to, sload(to) + amount)
+            opcode::DUP1,
+            opcode::SLOAD,
+            opcode::DUP3,
+            opcode::ADD,
+            opcode::SWAP1,
+            opcode::SSTORE,
+            opcode::STOP,
+            // revert
+            opcode::JUMPDEST,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::REVERT,
+        ]
+        .into(),
+    )
+}
+
+/// Returns the storage slot of the token balance of the `holder`.
+pub fn balance_slot(holder: Address) -> StorageKey {
+    let mut preimage = [0; 64];
+    preimage[..32].copy_from_slice(holder.into_word().as_slice());
+    keccak256(preimage).into()
+}
+
+/// Returns the calldata of the transfer of `amount` tokens to the `recipient`.
+pub fn transfer_input(recipient: Address, amount: U256) -> Bytes {
+    let mut input = Vec::with_capacity(64);
+    input.extend_from_slice(recipient.into_word().as_slice());
+    input.extend_from_slice(&amount.to_be_bytes::<32>());
+    input.into()
+}
+
+/// Returns the `index`-th recipient of the transfer storm.
+pub fn recipient(index: usize) -> Address {
+    Address::from_word(U256::from(FIRST_RECIPIENT + index as u64).into())
+}
+
+/// Returns the workload with [`TRANSFERS`] transfers.
+pub fn workload() -> Workload {
+    workload_with(TRANSFERS)
+}
+
+/// Returns the workload with `transfers` transfers of one token, each to a new recipient.
+pub fn workload_with(transfers: usize) -> Workload {
+    let mut db = db_with_target(bytecode());
+    db.insert_account_storage(
+        BENCH_TARGET,
+        balance_slot(BENCH_CALLER),
+        StorageValue::from(CALLER_BALANCE),
+    )
+    .unwrap();
+    let txs = (0..transfers)
+        .map(|index| {
+            call(BENCH_TARGET)
+                .data(transfer_input(recipient(index), U256::from(1)))
+                .gas_limit(100_000)
+                .build()
+                .unwrap()
+        })
+        .collect();
+    Workload::new("erc20_transfers", db, txs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::DatabaseRef;
+
+    #[test]
+    fn transfers_tokens() {
+        let workload = workload_with(10);
+        let mut evm = workload.evm();
+        let report = workload.run_with(&mut evm).unwrap();
+        assert!(report.is_success());
+
+        let db = &evm.ctx.journaled_state.database;
+        let balance = |holder| db.storage_ref(BENCH_TARGET, balance_slot(holder)).unwrap();
+        assert_eq!(balance(BENCH_CALLER), U256::from(CALLER_BALANCE - 10));
+        assert_eq!(balance(recipient(0)), U256::from(1));
+        assert_eq!(balance(recipient(9)), U256::from(1));
+        assert_eq!(balance(recipient(10)), U256::ZERO);
+
+        // Transfer of more tokens than the balance reverts.
+        let tx = call(BENCH_TARGET)
+            .data(transfer_input(recipient(0), U256::from(CALLER_BALANCE + 1)))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+        let overdraft = Workload::new("erc20_overdraft", workload.db, vec![tx]);
+        assert_eq!(overdraft.run().successful, 0);
+    }
+}
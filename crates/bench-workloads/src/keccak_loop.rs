@@ -0,0 +1,56 @@
+//! Keccak loop, hashes memory in a loop and stores the hash back into memory.
+use crate::{call, db_with_target, Workload, BENCH_TARGET};
+use revm::{bytecode::opcode, state::Bytecode};
+
+/// Number of iterations of the default workload.
+pub const ITERATIONS: u16 = 10_000;
+
+/// Returns code that hashes the first `words` words of memory `iterations` times, every hash
+/// overwrites the first word.
+pub fn bytecode(iterations: u16, words: u8) -> Bytecode {
+    let [high, low] = iterations.to_be_bytes();
+    let size = u16::from(words) * 32;
+    let [size_high, size_low] = size.to_be_bytes();
+    Bytecode::new_legacy(
+        vec![
+            opcode::PUSH2,
+            high,
+            low,
+            opcode::JUMPDEST,
+            // mstore(0, keccak256(0, size))
+            opcode::PUSH2,
+            size_high,
+            size_low,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            // loop while the counter is not zero
+            opcode::PUSH1,
+            1,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP1,
+            opcode::PUSH1,
+            3,
+            opcode::JUMPI,
+            opcode::STOP,
+        ]
+        .into(),
+    )
+}
+
+/// Returns the workload that hashes two words [`ITERATIONS`] times.
+pub fn workload() -> Workload {
+    workload_with(ITERATIONS, 2)
+}
+
+/// Returns the workload that hashes `words` words `iterations` times.
+pub fn workload_with(iterations: u16, words: u8) -> Workload {
+    let tx = call(BENCH_TARGET).gas_limit(30_000_000).build().unwrap();
+    Workload::new(
+        "keccak_loop",
+        db_with_target(bytecode(iterations, words)),
+        vec![tx],
+    )
+}
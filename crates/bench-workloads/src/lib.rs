@@ -0,0 +1,172 @@
+//! # revm-bench-workloads
+//!
+//! Standard benchmark workloads used to measure revm, packaged with runners so that forks
+//! and downstream EVMs can measure their modifications against the same baselines.
+//!
+//! Every [`Workload`] is a prefilled in-memory database and a list of transactions. It can be
+//! run on the mainnet EVM with [`Workload::run`] or on any EVM that executes [`TxEnv`] with
+//! [`Workload::run_with`].
+//!
+//! ```
+//! let report = revm_bench_workloads::keccak_loop::workload().run();
+//! assert!(report.is_success());
+//! ```
+
+pub mod call_tree;
+pub mod erc20;
+pub mod keccak_loop;
+pub mod snailtracer;
+pub mod storage_loop;
+pub mod swap;
+pub mod transfer;
+
+pub use revm::database::{BENCH_CALLER, BENCH_TARGET};
+
+use revm::{
+    context::{tx::TxEnvBuilder, TxEnv},
+    context_interface::result::ExecutionResult,
+    database::{CacheDB, EmptyDB, BENCH_CALLER_BALANCE},
+    handler::MainnetContext,
+    primitives::{Address, TxKind},
+    state::{AccountInfo, Bytecode},
+    Context, ExecuteCommitEvm, MainBuilder, MainContext, MainnetEvm,
+};
+use std::time::{Duration, Instant};
+
+/// Database of the workloads.
+pub type WorkloadDB = CacheDB<EmptyDB>;
+
+/// Benchmark workload, state and the transactions that are executed on top of it.
+#[derive(Clone, Debug)]
+pub struct Workload {
+    /// Name of the workload.
+    pub name: &'static str,
+    /// State before the first transaction.
+    pub db: WorkloadDB,
+    /// Transactions, executed and committed in order.
+    pub txs: Vec<TxEnv>,
+}
+
+impl Workload {
+    /// Creates a new workload.
+    pub fn new(name: &'static str, db: WorkloadDB, txs: Vec<TxEnv>) -> Self {
+        Self { name, db, txs }
+    }
+
+    /// Returns the mainnet EVM with a copy of the workload state.
+    ///
+    /// Nonce check is disabled so the transactions can be executed repeatedly.
+    pub fn evm(&self) -> MainnetEvm<MainnetContext<WorkloadDB>> {
+        Context::mainnet()
+            .with_db(self.db.clone())
+            .modify_cfg_chained(|cfg| cfg.disable_nonce_check = true)
+            .build_mainnet()
+    }
+
+    /// Runs the workload on the mainnet EVM created by [`Workload::evm`].
+    pub fn run(&self) -> WorkloadReport {
+        self.run_with(&mut self.evm())
+            .expect("workload database is infallible")
+    }
+
+    /// Runs the workload on the given EVM.
+    ///
+    /// EVM is expected to contain the state of [`Workload::db`], transactions are committed to
+    /// it one by one. Failed and reverted transactions are reported, not returned as errors.
+    pub fn run_with<EVM, HALT>(&self, evm: &mut EVM) -> Result<WorkloadReport, EVM::Error>
+    where
+        EVM: ExecuteCommitEvm<Tx = TxEnv, ExecutionResult = ExecutionResult<HALT>>,
+    {
+        let mut report = WorkloadReport::default();
+        let start = Instant::now();
+        for tx in &self.txs {
+            let result = evm.transact_commit(tx.clone())?;
+            report.txs += 1;
+            report.successful += usize::from(result.is_success());
+            report.gas_used += result.gas_used();
+        }
+        report.elapsed = start.elapsed();
+        Ok(report)
+    }
+}
+
+/// Outcome of a workload run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkloadReport {
+    /// Number of executed transactions.
+    pub txs: usize,
+    /// Number of transactions that succeeded.
+    pub successful: usize,
+    /// Gas used by all transactions.
+    pub gas_used: u64,
+    /// Time spent executing the transactions.
+    pub elapsed: Duration,
+}
+
+impl WorkloadReport {
+    /// Returns `true` if all transactions succeeded.
+    pub fn is_success(&self) -> bool {
+        self.successful == self.txs
+    }
+
+    /// Returns gas executed per second.
+    pub fn gas_per_second(&self) -> f64 {
+        self.gas_used as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Returns all standard workloads.
+pub fn all() -> Vec<Workload> {
+    vec![
+        snailtracer::workload(),
+        erc20::workload(),
+        keccak_loop::workload(),
+        call_tree::chain(),
+        call_tree::tree(),
+        storage_loop::workload(),
+        swap::workload(),
+        transfer::workload(),
+    ]
+}
+
+/// Returns the database with the funded [`BENCH_CALLER`] and the `code` at [`BENCH_TARGET`].
+fn db_with_target(code: Bytecode) -> WorkloadDB {
+    let mut db = db();
+    db.insert_account_info(BENCH_TARGET, AccountInfo::from_bytecode(code));
+    db
+}
+
+/// Returns the database with the funded [`BENCH_CALLER`].
+fn db() -> WorkloadDB {
+    let mut db = WorkloadDB::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+    );
+    db
+}
+
+/// Returns the transaction from [`BENCH_CALLER`] to the `target`, without gas price so that
+/// the caller balance doesn't limit the number of runs.
+fn call(target: Address) -> TxEnvBuilder {
+    TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(target))
+        .gas_price(0)
+        .gas_priority_fee(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workloads_succeed() {
+        for workload in all() {
+            let report = workload.run();
+            assert_eq!(report.txs, workload.txs.len(), "{}", workload.name);
+            assert!(report.is_success(), "{}", workload.name);
+            assert!(report.gas_used > 0, "{}", workload.name);
+        }
+    }
+}
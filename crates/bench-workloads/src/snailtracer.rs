@@ -0,0 +1,24 @@
+//! Snailtracer, a ray tracer contract that renders a single pixel.
+use crate::{call, db_with_target, Workload, BENCH_TARGET};
+use revm::{
+    primitives::{bytes, hex, Bytes},
+    state::Bytecode,
+};
+
+/// Runtime code of the snailtracer contract.
+const CODE: &str = include_str!("snailtracer.hex");
+
+/// Returns the snailtracer runtime code.
+pub fn bytecode() -> Bytecode {
+    Bytecode::new_raw(Bytes::from(hex::decode(CODE).unwrap()))
+}
+
+/// Returns the workload that calls the `Benchmark()` function of the snailtracer.
+pub fn workload() -> Workload {
+    let tx = call(BENCH_TARGET)
+        .data(bytes!("30627b7c"))
+        .gas_limit(1_000_000_000)
+        .build()
+        .unwrap();
+    Workload::new("snailtracer", db_with_target(bytecode()), vec![tx])
+}
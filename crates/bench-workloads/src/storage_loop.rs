@@ -0,0 +1,63 @@
+//! Storage loop, updates two reserves and a hashed balance slot like repeated swaps of a pool.
+use crate::{call, db_with_target, Workload, BENCH_CALLER, BENCH_TARGET};
+use revm::{bytecode::opcode, primitives::keccak256, state::Bytecode};
+
+/// Number of iterations of the loop.
+pub const ITERATIONS: u16 = 1000;
+
+/// Returns code that updates two reserves and a hashed balance slot in a loop.
+///
+/// Slot `0` is incremented by one and slot `1` by two in every iteration.
+pub fn bytecode() -> Bytecode {
+    let [high, low] = ITERATIONS.to_be_bytes();
+    let mut code = vec![opcode::PUSH2, high, low, opcode::JUMPDEST];
+    code.extend([
+        // reserve0 += 1
+        opcode::PUSH0,
+        opcode::SLOAD,
+        opcode::PUSH1,
+        1,
+        opcode::ADD,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        // reserve1 += 2
+        opcode::PUSH1,
+        1,
+        opcode::SLOAD,
+        opcode::PUSH1,
+        2,
+        opcode::ADD,
+        opcode::PUSH1,
+        1,
+        opcode::SSTORE,
+        // balance += 1
+        opcode::PUSH32,
+    ]);
+    code.extend(keccak256(BENCH_CALLER.into_word()));
+    code.extend([
+        opcode::DUP1,
+        opcode::SLOAD,
+        opcode::PUSH1,
+        1,
+        opcode::ADD,
+        opcode::SWAP1,
+        opcode::SSTORE,
+        // loop while the counter is not zero
+        opcode::PUSH1,
+        1,
+        opcode::SWAP1,
+        opcode::SUB,
+        opcode::DUP1,
+        opcode::PUSH1,
+        3,
+        opcode::JUMPI,
+        opcode::STOP,
+    ]);
+    Bytecode::new_legacy(code.into())
+}
+
+/// Returns the workload that runs the storage loop once.
+pub fn workload() -> Workload {
+    let tx = call(BENCH_TARGET).gas_limit(10_000_000).build().unwrap();
+    Workload::new("storage_loop", db_with_target(bytecode()), vec![tx])
+}
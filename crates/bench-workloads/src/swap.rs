@@ -0,0 +1,137 @@
+//! Uniswap-style swaps, a pool that updates its reserves and pays out a token in every swap.
+use crate::{call, db_with_target, erc20, Workload, BENCH_TARGET};
+use revm::{
+    bytecode::opcode,
+    primitives::{address, Address, StorageValue, U256},
+    state::{AccountInfo, Bytecode},
+};
+
+/// Number of swaps executed by the transaction.
+pub const SWAPS: u16 = 200;
+
+/// Address of the token paid out by the pool, the pool is at [`BENCH_TARGET`].
+pub const TOKEN: Address = address!("0x2000000000000000000000000000000000000000");
+
+/// Reserves of the pool and its token balance before the first swap.
+pub const INITIAL_RESERVE: u64 = 1_000_000_000;
+
+/// Returns code of the pool that swaps [`SWAPS`] times.
+///
+/// Every swap increments reserve `0` at slot `0`, decrements reserve `1` at slot `1` and
+/// transfers one [`TOKEN`] to the caller. Reverts if the transfer fails.
+pub fn bytecode() -> Bytecode {
+    let [high, low] = SWAPS.to_be_bytes();
+    let mut code = vec![opcode::PUSH2, high, low, opcode::JUMPDEST];
+    code.extend([
+        // reserve0 += 1
+        opcode::PUSH0,
+        opcode::SLOAD,
+        opcode::PUSH1,
+        1,
+        opcode::ADD,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        // reserve1 -= 1
+        opcode::PUSH1,
+        1,
+        opcode::DUP1,
+        opcode::SLOAD,
+        opcode::SUB,
+        opcode::PUSH1,
+        1,
+        opcode::SSTORE,
+        // call(gas(), TOKEN, 0, 0, 0x40, 0, 0) with the transfer of one token to the caller
+        opcode::CALLER,
+        opcode::PUSH0,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        1,
+        opcode::PUSH1,
+        0x20,
+        opcode::MSTORE,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::PUSH1,
+        0x40,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::PUSH20,
+    ]);
+    code.extend(TOKEN);
+    code.extend([
+        opcode::GAS,
+        opcode::CALL,
+        // revert if the transfer failed
+        opcode::ISZERO,
+        opcode::PUSH2,
+    ]);
+    // Offset of the jump destination, set below.
+    let revert_offset = code.len();
+    code.extend([
+        0,
+        0,
+        opcode::JUMPI,
+        // loop while the counter is not zero
+        opcode::PUSH1,
+        1,
+        opcode::SWAP1,
+        opcode::SUB,
+        opcode::DUP1,
+        opcode::PUSH1,
+        3,
+        opcode::JUMPI,
+        opcode::STOP,
+    ]);
+    let [high, low] = u16::try_from(code.len()).unwrap().to_be_bytes();
+    code[revert_offset] = high;
+    code[revert_offset + 1] = low;
+    code.extend([
+        opcode::JUMPDEST,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::REVERT,
+    ]);
+    Bytecode::new_legacy(code.into())
+}
+
+/// Returns the workload that swaps [`SWAPS`] times in one transaction.
+pub fn workload() -> Workload {
+    let mut db = db_with_target(bytecode());
+    for slot in [U256::ZERO, U256::from(1)] {
+        db.insert_account_storage(BENCH_TARGET, slot, StorageValue::from(INITIAL_RESERVE))
+            .unwrap();
+    }
+    db.insert_account_info(TOKEN, AccountInfo::from_bytecode(erc20::bytecode()));
+    db.insert_account_storage(
+        TOKEN,
+        erc20::balance_slot(BENCH_TARGET),
+        StorageValue::from(INITIAL_RESERVE),
+    )
+    .unwrap();
+    let tx = call(BENCH_TARGET).gas_limit(10_000_000).build().unwrap();
+    Workload::new("swaps", db, vec![tx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BENCH_CALLER;
+    use revm::DatabaseRef;
+
+    #[test]
+    fn swaps() {
+        let workload = workload();
+        let mut evm = workload.evm();
+        let report = workload.run_with(&mut evm).unwrap();
+        assert!(report.is_success());
+
+        let db = &evm.ctx.journaled_state.database;
+        let swaps = u64::from(SWAPS);
+        let reserve = |slot: u64| db.storage_ref(BENCH_TARGET, U256::from(slot)).unwrap();
+        assert_eq!(reserve(0), U256::from(INITIAL_RESERVE + swaps));
+        assert_eq!(reserve(1), U256::from(INITIAL_RESERVE - swaps));
+        let balance = |holder| db.storage_ref(TOKEN, erc20::balance_slot(holder)).unwrap();
+        assert_eq!(balance(BENCH_TARGET), U256::from(INITIAL_RESERVE - swaps));
+        assert_eq!(balance(BENCH_CALLER), U256::from(swaps));
+    }
+}
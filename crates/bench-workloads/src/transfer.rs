@@ -0,0 +1,33 @@
+//! Ether transfer storm, value transfers from one account to distinct recipients.
+use crate::{call, db, Workload};
+use revm::primitives::{Address, U256};
+
+/// Number of transfers of the default workload.
+pub const TRANSFERS: usize = 1000;
+
+/// First recipient, recipients are consecutive addresses.
+const FIRST_RECIPIENT: u64 = 0x2000;
+
+/// Returns the `index`-th recipient of the transfer storm.
+pub fn recipient(index: usize) -> Address {
+    Address::from_word(U256::from(FIRST_RECIPIENT + index as u64).into())
+}
+
+/// Returns the workload with [`TRANSFERS`] transfers.
+pub fn workload() -> Workload {
+    workload_with(TRANSFERS)
+}
+
+/// Returns the workload with `transfers` transfers of one wei, each to a new account.
+pub fn workload_with(transfers: usize) -> Workload {
+    let txs = (0..transfers)
+        .map(|index| {
+            call(recipient(index))
+                .value(U256::from(1))
+                .gas_limit(30_000)
+                .build()
+                .unwrap()
+        })
+        .collect();
+    Workload::new("transfers", db(), txs)
+}
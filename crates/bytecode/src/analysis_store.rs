@@ -0,0 +1,248 @@
+//! External store of bytecode analysis artifacts.
+//!
+//! Analysis of legacy bytecode is linear in the code size and is repeated every time a node
+//! decodes code from its database. An [`AnalysisStore`] lets nodes persist the serialized
+//! artifacts keyed by the code hash and share them across restarts and processes, the analysis
+//! is skipped when a valid artifact is found.
+use crate::{opcode, JumpTable, LegacyAnalyzedBytecode};
+use core::fmt::Debug;
+use primitives::{Bytes, B256};
+use std::{sync::Arc, vec::Vec};
+
+/// Kind of the artifact produced by the analysis of a bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ArtifactKind {
+    /// Jump table and padding of legacy bytecode, see [`LegacyAnalyzedBytecode`].
+    JumpTable,
+}
+
+/// Store of serialized analysis artifacts keyed by the code hash.
+///
+/// Loaded artifacts are validated against the code before use, a missing or invalid artifact
+/// makes the bytecode analyzed again and the new artifact stored. Validation keeps the execution
+/// memory safe for any artifact, but a `JUMPDEST` inside push data marked as a jump destination
+/// is not detected, so the store has to be trusted to return artifacts produced for the code
+/// hash.
+/// Implementations can be shared between threads, so methods take `&self`.
+pub trait AnalysisStore: Debug + Send + Sync {
+    /// Returns the artifact of the kind for the code hash.
+    fn load(&self, code_hash: B256, kind: ArtifactKind) -> Option<Bytes>;
+
+    /// Stores the artifact of the kind for the code hash.
+    fn store(&self, code_hash: B256, kind: ArtifactKind, artifact: Bytes);
+}
+
+impl<T: AnalysisStore + ?Sized> AnalysisStore for Arc<T> {
+    fn load(&self, code_hash: B256, kind: ArtifactKind) -> Option<Bytes> {
+        (**self).load(code_hash, kind)
+    }
+
+    fn store(&self, code_hash: B256, kind: ArtifactKind, artifact: Bytes) {
+        (**self).store(code_hash, kind, artifact)
+    }
+}
+
+/// Version of the serialized [`ArtifactKind::JumpTable`] format.
+const JUMP_TABLE_VERSION: u8 = 2;
+
+/// Padding of bytecode decoded from an artifact, enough for a `PUSH32` at the last byte followed
+/// by a STOP.
+const MAX_PADDING: usize = 33;
+
+impl LegacyAnalyzedBytecode {
+    /// Serializes the jump table of the bytecode as an [`ArtifactKind::JumpTable`] artifact.
+    pub fn encode_artifact(&self) -> Bytes {
+        let jump_table = self.jump_table().as_slice();
+        let mut artifact = Vec::with_capacity(1 + jump_table.len());
+        artifact.push(JUMP_TABLE_VERSION);
+        artifact.extend_from_slice(jump_table);
+        artifact.into()
+    }
+
+    /// Creates analyzed bytecode from the original bytecode and its [`ArtifactKind::JumpTable`]
+    /// artifact.
+    ///
+    /// The bytecode is always padded with the maximum padding, so immediates of the last
+    /// instruction are in bounds whatever the artifact contains.
+    ///
+    /// Returns `None` if the artifact has a different version, does not match the length of the
+    /// bytecode or marks a byte that is not a `JUMPDEST` as a jump destination.
+    pub fn decode_artifact(bytecode: Bytes, artifact: &[u8]) -> Option<Self> {
+        let [JUMP_TABLE_VERSION, jump_table @ ..] = artifact else {
+            return None;
+        };
+        let original_len = bytecode.len();
+        if jump_table.len() != original_len.div_ceil(8) {
+            return None;
+        }
+        for (i, byte) in jump_table.iter().enumerate() {
+            let mut bits = *byte;
+            while bits != 0 {
+                let pc = i * 8 + bits.trailing_zeros() as usize;
+                if bytecode.get(pc) != Some(&opcode::JUMPDEST) {
+                    return None;
+                }
+                bits &= bits - 1;
+            }
+        }
+        let jump_table = JumpTable::from_slice(jump_table, original_len);
+        Some(Self::with_max_padding(&bytecode, jump_table))
+    }
+
+    /// Creates analyzed bytecode padded with [`MAX_PADDING`] zero bytes.
+    fn with_max_padding(bytecode: &[u8], jump_table: JumpTable) -> Self {
+        let original_len = bytecode.len();
+        let mut padded = Vec::with_capacity(original_len + MAX_PADDING);
+        padded.extend_from_slice(bytecode);
+        padded.resize(original_len + MAX_PADDING, 0);
+        Self::new(padded.into(), original_len, jump_table)
+    }
+
+    /// Analyzes the bytecode like [`LegacyAnalyzedBytecode::analyze`], reusing the artifact from
+    /// the store if it is valid.
+    ///
+    /// `code_hash` has to be the hash of `bytecode`, new artifacts are stored under it. The
+    /// bytecode is padded like in [`LegacyAnalyzedBytecode::decode_artifact`] whether the artifact
+    /// is found or not, so the result does not depend on the content of the store.
+    pub fn analyze_with_store(bytecode: Bytes, code_hash: B256, store: &dyn AnalysisStore) -> Self {
+        if let Some(analyzed) = store
+            .load(code_hash, ArtifactKind::JumpTable)
+            .and_then(|artifact| Self::decode_artifact(bytecode.clone(), &artifact))
+        {
+            return analyzed;
+        }
+        let analyzed = Self::analyze(bytecode);
+        store.store(
+            code_hash,
+            ArtifactKind::JumpTable,
+            analyzed.encode_artifact(),
+        );
+        Self::with_max_padding(
+            analyzed.original_byte_slice(),
+            analyzed.jump_table().clone(),
+        )
+    }
+}
+
+/// [`AnalysisStore`] that keeps artifacts in memory.
+///
+/// Useful to share artifacts between EVM instances of a process and as a reference for
+/// persistent implementations.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct InMemoryAnalysisStore {
+    artifacts: std::sync::RwLock<primitives::HashMap<(B256, ArtifactKind), Bytes>>,
+}
+
+#[cfg(feature = "std")]
+impl InMemoryAnalysisStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of stored artifacts.
+    pub fn len(&self) -> usize {
+        self.artifacts.read().unwrap().len()
+    }
+
+    /// Returns `true` if no artifact is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl AnalysisStore for InMemoryAnalysisStore {
+    fn load(&self, code_hash: B256, kind: ArtifactKind) -> Option<Bytes> {
+        self.artifacts
+            .read()
+            .unwrap()
+            .get(&(code_hash, kind))
+            .cloned()
+    }
+
+    fn store(&self, code_hash: B256, kind: ArtifactKind, artifact: Bytes) {
+        self.artifacts
+            .write()
+            .unwrap()
+            .insert((code_hash, kind), artifact);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use primitives::keccak256;
+
+    #[test]
+    fn test_artifact_roundtrip() {
+        for code in [
+            &[][..],
+            &[opcode::PUSH1, 0x01, opcode::JUMPDEST, opcode::STOP],
+            &[opcode::JUMPDEST, opcode::PUSH32, 0x01],
+            &[opcode::JUMPDEST; 17],
+        ] {
+            let code = Bytes::copy_from_slice(code);
+            let analyzed = LegacyAnalyzedBytecode::analyze(code.clone());
+            let decoded =
+                LegacyAnalyzedBytecode::decode_artifact(code, &analyzed.encode_artifact()).unwrap();
+            assert_eq!(decoded.original_bytes(), analyzed.original_bytes());
+            assert_eq!(decoded.jump_table(), analyzed.jump_table());
+            assert_eq!(
+                decoded.bytecode().len(),
+                decoded.original_len() + MAX_PADDING
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_artifact() {
+        let code = Bytes::from_static(&[opcode::JUMPDEST, opcode::PUSH32, 0x5b]);
+        let decode =
+            |artifact: &[u8]| LegacyAnalyzedBytecode::decode_artifact(code.clone(), artifact);
+        assert!(decode(&[JUMP_TABLE_VERSION, 0b001]).is_some());
+        // old version with the padding byte.
+        assert!(decode(&[1, 1, 0b001]).is_none());
+        // jump destination on PUSH32 and out of the code.
+        assert!(decode(&[JUMP_TABLE_VERSION, 0b011]).is_none());
+        assert!(decode(&[JUMP_TABLE_VERSION, 0b1001]).is_none());
+        // wrong jump table length.
+        assert!(decode(&[JUMP_TABLE_VERSION, 0b001, 0]).is_none());
+
+        // immediates of the truncated PUSH32 are in bounds.
+        let decoded = decode(&[JUMP_TABLE_VERSION, 0b001]).unwrap();
+        assert!(decoded.bytecode().len() > 2 + 32);
+    }
+
+    #[test]
+    fn test_analyze_with_store() {
+        let store = InMemoryAnalysisStore::new();
+        let code = Bytes::from_static(&[opcode::JUMPDEST, opcode::PUSH1, 0x5b]);
+        let code_hash = keccak256(&code);
+
+        let analyzed = LegacyAnalyzedBytecode::analyze_with_store(code.clone(), code_hash, &store);
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            LegacyAnalyzedBytecode::analyze_with_store(code.clone(), code_hash, &store),
+            analyzed
+        );
+
+        // invalid artifact is replaced.
+        store.store(
+            code_hash,
+            ArtifactKind::JumpTable,
+            Bytes::from_static(&[JUMP_TABLE_VERSION, 0b10]),
+        );
+        assert_eq!(
+            LegacyAnalyzedBytecode::analyze_with_store(code, code_hash, &store),
+            analyzed
+        );
+        assert_eq!(
+            store.load(code_hash, ArtifactKind::JumpTable),
+            Some(analyzed.encode_artifact())
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! - EIP-7702 bytecode, introduces in Prague and contains address to delegated account.
 
 use crate::{
+    analysis_store::AnalysisStore,
     eip7702::{Eip7702Bytecode, EIP7702_MAGIC_BYTES},
     BytecodeDecodeError, JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode,
 };
@@ -64,6 +65,16 @@ impl Bytecode {
         Self::LegacyAnalyzed(LegacyRawBytecode(raw).into_analyzed())
     }
 
+    /// Creates a new legacy [`Bytecode`], reusing the analysis from the store if possible.
+    ///
+    /// See [`LegacyAnalyzedBytecode::analyze_with_store`].
+    #[inline]
+    pub fn new_legacy_with_store(raw: Bytes, code_hash: B256, store: &dyn AnalysisStore) -> Self {
+        Self::LegacyAnalyzed(LegacyAnalyzedBytecode::analyze_with_store(
+            raw, code_hash, store,
+        ))
+    }
+
     /// Creates a new raw [`Bytecode`].
     ///
     /// # Panics
@@ -95,6 +106,26 @@ impl Bytecode {
         }
     }
 
+    /// Creates a new raw [`Bytecode`] like [`Self::new_raw_checked`], reusing the analysis of
+    /// legacy bytecode from the store if possible.
+    ///
+    /// Used to decode code loaded from a database, see [`LegacyAnalyzedBytecode::analyze_with_store`].
+    #[inline]
+    pub fn new_raw_checked_with_store(
+        bytes: Bytes,
+        code_hash: B256,
+        store: &dyn AnalysisStore,
+    ) -> Result<Self, BytecodeDecodeError> {
+        let prefix = bytes.get(..2);
+        match prefix {
+            Some(prefix) if prefix == &EIP7702_MAGIC_BYTES => {
+                let eip7702 = Eip7702Bytecode::new_raw(bytes)?;
+                Ok(Self::Eip7702(eip7702))
+            }
+            _ => Ok(Self::new_legacy_with_store(bytes, code_hash, store)),
+        }
+    }
+
     /// Create new checked bytecode.
     ///
     /// # Panics
@@ -10,6 +10,15 @@ pub enum BytecodeDecodeError {
     Eip7702(Eip7702DecodeError),
 }
 
+impl BytecodeDecodeError {
+    /// Returns stable numeric code of the error, in `5000..6000` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Eip7702(e) => e.code(),
+        }
+    }
+}
+
 impl From<Eip7702DecodeError> for BytecodeDecodeError {
     fn from(error: Eip7702DecodeError) -> Self {
         Self::Eip7702(error)
@@ -101,6 +101,17 @@ pub enum Eip7702DecodeError {
     UnsupportedVersion,
 }
 
+impl Eip7702DecodeError {
+    /// Returns stable numeric code of the error.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::InvalidLength => 5001,
+            Self::InvalidMagic => 5002,
+            Self::UnsupportedVersion => 5003,
+        }
+    }
+}
+
 impl fmt::Display for Eip7702DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -154,4 +165,16 @@ mod tests {
             bytes!("ef01000101010101010101010101010101010101010101")
         );
     }
+
+    #[test]
+    fn decode_error_codes_are_stable() {
+        for (error, code) in [
+            (Eip7702DecodeError::InvalidLength, 5001),
+            (Eip7702DecodeError::InvalidMagic, 5002),
+            (Eip7702DecodeError::UnsupportedVersion, 5003),
+        ] {
+            assert_eq!(error.code(), code);
+            assert_eq!(crate::BytecodeDecodeError::Eip7702(error).code(), code);
+        }
+    }
 }
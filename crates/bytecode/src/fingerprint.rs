@@ -0,0 +1,175 @@
+//! Fingerprints of legacy bytecode to find identical or near-identical deployments.
+//!
+//! Compilers append metadata, such as the hash of the source, to the deployed code. Contracts
+//! compiled from the same source with different settings, or deployed with different
+//! immutables, have different code hashes but share a [`Fingerprint::stripped_hash`] or a
+//! [`Fingerprint::opcode_hash`].
+use crate::{Bytecode, BytecodeIterator};
+use primitives::{keccak256, HashSet, B256, KECCAK_EMPTY};
+use std::vec::Vec;
+
+/// Number of opcodes in a shingle compared by [`similarity`].
+pub const SHINGLE_LEN: usize = 4;
+
+/// Fingerprint of legacy bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingerprint {
+    /// Hash of the code.
+    pub code_hash: B256,
+    /// Hash of the code without the trailing compiler metadata.
+    pub stripped_hash: B256,
+    /// Hash of the opcodes of the code without metadata, immediates of `PUSH` opcodes are
+    /// skipped so that embedded addresses and constants do not change it.
+    pub opcode_hash: B256,
+    /// Length of the code without the trailing compiler metadata.
+    pub stripped_len: usize,
+}
+
+impl Fingerprint {
+    /// Computes the fingerprint of the code.
+    pub fn new(code: &[u8]) -> Self {
+        let stripped = strip_metadata(code);
+        Self {
+            code_hash: hash(code),
+            stripped_hash: hash(stripped),
+            opcode_hash: hash(&opcodes(stripped)),
+            stripped_len: stripped.len(),
+        }
+    }
+
+    /// Returns `true` if the code is the same apart from the compiler metadata.
+    pub fn matches_stripped(&self, other: &Self) -> bool {
+        self.stripped_hash == other.stripped_hash
+    }
+
+    /// Returns `true` if the opcodes are the same apart from `PUSH` immediates.
+    pub fn matches_opcodes(&self, other: &Self) -> bool {
+        self.opcode_hash == other.opcode_hash
+    }
+}
+
+impl Bytecode {
+    /// Returns the fingerprint of the bytecode, `None` for EIP-7702 bytecode.
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        (!self.is_eip7702()).then(|| Fingerprint::new(self.original_byte_slice()))
+    }
+}
+
+/// Returns the code without the CBOR encoded metadata that Solidity and Vyper append.
+///
+/// The last two bytes are the big-endian length of the metadata, which has to be a CBOR map.
+/// The code is returned unchanged if it does not end with metadata.
+pub fn strip_metadata(code: &[u8]) -> &[u8] {
+    let Some((len, rest)) = code
+        .split_last_chunk::<2>()
+        .map(|(rest, len)| (u16::from_be_bytes(*len) as usize, rest))
+    else {
+        return code;
+    };
+    if len == 0 || len > rest.len() {
+        return code;
+    }
+    let (stripped, metadata) = rest.split_at(rest.len() - len);
+    // CBOR map with up to 23 entries.
+    if matches!(metadata[0], 0xa1..=0xb7) {
+        stripped
+    } else {
+        code
+    }
+}
+
+/// Returns the similarity of the opcodes of two codes, from `0.0` for no shared sequences to
+/// `1.0` for the same sequences.
+///
+/// It is the Jaccard index of the sets of [`SHINGLE_LEN`] consecutive opcodes, metadata and
+/// `PUSH` immediates are ignored.
+pub fn similarity(a: &[u8], b: &[u8]) -> f64 {
+    let (a, b) = (opcodes(strip_metadata(a)), opcodes(strip_metadata(b)));
+    let (a, b) = (shingles(&a), shingles(&b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let shared = a.intersection(&b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+fn hash(bytes: &[u8]) -> B256 {
+    if bytes.is_empty() {
+        KECCAK_EMPTY
+    } else {
+        keccak256(bytes)
+    }
+}
+
+fn opcodes(code: &[u8]) -> Vec<u8> {
+    BytecodeIterator::from_slice(code).collect()
+}
+
+fn shingles(opcodes: &[u8]) -> HashSet<&[u8]> {
+    if opcodes.len() < SHINGLE_LEN {
+        return HashSet::from_iter((!opcodes.is_empty()).then_some(opcodes));
+    }
+    opcodes.windows(SHINGLE_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode;
+    use primitives::hex;
+
+    // `mstore(0x40, 0x80) revert(0, 0)` and a CBOR map `{"ipfs": 0x010203}`.
+    const CODE: [u8; 12] = hex!("6080604052600080fd5b0000");
+    const METADATA: [u8; 10] = hex!("a1646970667341010203");
+
+    fn with_metadata(code: &[u8], metadata: &[u8]) -> Vec<u8> {
+        let mut out = code.to_vec();
+        out.extend_from_slice(metadata);
+        out.extend_from_slice(&(metadata.len() as u16).to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn strip() {
+        let code = with_metadata(&CODE, &METADATA);
+        assert_eq!(strip_metadata(&code), CODE);
+        // Not a CBOR map.
+        let code = with_metadata(&CODE, &[0x60; 4]);
+        assert_eq!(strip_metadata(&code), code);
+        // Length longer than the code.
+        assert_eq!(strip_metadata(&[0x00, 0xff]), [0x00, 0xff]);
+        assert_eq!(strip_metadata(&[]), [0u8; 0]);
+    }
+
+    #[test]
+    fn fingerprints() {
+        let a = Fingerprint::new(&with_metadata(&CODE, &METADATA));
+        let b = Fingerprint::new(&with_metadata(&CODE, &[0xa1, 0x00, 0x00]));
+        assert_ne!(a.code_hash, b.code_hash);
+        assert!(a.matches_stripped(&b));
+        assert_eq!(a.stripped_len, CODE.len());
+
+        // Different constant, same opcodes.
+        let mut code = CODE;
+        code[1] = 0x81;
+        let c = Fingerprint::new(&code);
+        assert!(!a.matches_stripped(&c));
+        assert!(a.matches_opcodes(&c));
+
+        let bytecode = Bytecode::new_raw(code.into());
+        assert_eq!(bytecode.fingerprint(), Some(c));
+    }
+
+    #[test]
+    fn similar() {
+        assert_eq!(similarity(&CODE, &with_metadata(&CODE, &METADATA)), 1.0);
+
+        let mut code = CODE.to_vec();
+        code.extend_from_slice(&[opcode::ADD, opcode::MUL, opcode::SUB, opcode::DIV]);
+        let value = similarity(&CODE, &code);
+        assert!(value > 0.0 && value < 1.0, "{value}");
+
+        assert_eq!(similarity(&CODE, &[opcode::ADD; 8]), 0.0);
+    }
+}
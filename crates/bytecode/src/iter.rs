@@ -20,6 +20,12 @@ impl<'a> BytecodeIterator<'a> {
             Bytecode::LegacyAnalyzed(_) => &bytecode.bytecode()[..],
             Bytecode::Eip7702(_) => &[],
         };
+        Self::from_slice(bytes)
+    }
+
+    /// Creates a new iterator over raw legacy bytecode.
+    #[inline]
+    pub fn from_slice(bytes: &'a [u8]) -> Self {
         Self {
             start: bytes.as_ptr(),
             bytes: bytes.iter(),
@@ -9,10 +9,12 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+pub mod analysis_store;
 pub mod bytecode;
 mod decode_errors;
 /// EIP-7702 bytecode.
 pub mod eip7702;
+pub mod fingerprint;
 /// Iterator for the bytecode.
 mod iter;
 /// Legacy bytecode.
@@ -20,10 +22,14 @@ pub mod legacy;
 pub mod opcode;
 pub mod utils;
 
+#[cfg(feature = "std")]
+pub use analysis_store::InMemoryAnalysisStore;
 /// Re-export of bitvec crate, used to store legacy bytecode jump table.
+pub use analysis_store::{AnalysisStore, ArtifactKind};
 pub use bitvec;
 pub use bytecode::Bytecode;
 pub use decode_errors::BytecodeDecodeError;
+pub use fingerprint::Fingerprint;
 pub use iter::BytecodeIterator;
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
 pub use opcode::OpCode;
@@ -1,8 +1,18 @@
 //! Configuration for the EVM. Containing [`SpecId`].
+pub mod blob_schedule;
+pub mod create_address;
+pub mod gas_schedule;
+
+pub use blob_schedule::BlobSchedule;
+pub use create_address::{CreateAddressPolicy, StandardCreateAddress};
+pub use gas_schedule::{CallGasPolicy, GasSchedule};
+
 use auto_impl::auto_impl;
 use core::fmt::Debug;
 use core::hash::Hash;
 use primitives::{hardfork::SpecId, Address, TxKind, U256};
+use state::bytecode::AnalysisStore;
+use std::sync::Arc;
 
 /// Configuration for the EVM.
 #[auto_impl(&, &mut, Box, Arc)]
@@ -17,6 +27,14 @@ pub trait Cfg {
     /// Returns whether the transaction's chain ID check is enabled.
     fn tx_chain_id_check(&self) -> bool;
 
+    /// Returns whether a transaction with the given chain ID is accepted when the chain ID check
+    /// is enabled.
+    ///
+    /// Defaults to accepting only [`Cfg::chain_id`].
+    fn is_chain_id_accepted(&self, chain_id: u64) -> bool {
+        chain_id == self.chain_id()
+    }
+
     /// Returns the gas limit cap for the transaction.
     ///
     /// Cap is introduced in [`EIP-7825: Transaction Gas Limit Cap`](https://eips.ethereum.org/EIPS/eip-7825)
@@ -53,6 +71,147 @@ pub trait Cfg {
     /// Returns whether the nonce check is disabled.
     fn is_nonce_check_disabled(&self) -> bool;
 
+    /// Returns how nonces of the transaction and of EIP-7702 authorizations are handled.
+    ///
+    /// Defaults to [`NonceCheckMode::AutoFill`] if [`Cfg::is_nonce_check_disabled`] is set and to
+    /// [`NonceCheckMode::Strict`] otherwise.
+    fn nonce_check_mode(&self) -> NonceCheckMode {
+        if self.is_nonce_check_disabled() {
+            NonceCheckMode::AutoFill
+        } else {
+            NonceCheckMode::Strict
+        }
+    }
+
+    /// Returns how blobs of EIP-4844 transactions are validated.
+    ///
+    /// Defaults to [`BlobSidecarMode::HashesOnly`].
+    fn blob_sidecar_mode(&self) -> BlobSidecarMode {
+        BlobSidecarMode::HashesOnly
+    }
+
+    /// Returns which transaction senders with code are accepted, see EIP-3607.
+    ///
+    /// Defaults to `CallerCodePolicy::Disabled` if [`Cfg::is_eip3607_disabled`] is set and the
+    /// `optional_eip3607` feature is enabled, and to [`CallerCodePolicy::AllowDelegated`] otherwise.
+    fn caller_code_policy(&self) -> CallerCodePolicy {
+        #[cfg(feature = "optional_eip3607")]
+        if self.is_eip3607_disabled() {
+            return CallerCodePolicy::Disabled;
+        }
+        CallerCodePolicy::AllowDelegated
+    }
+
+    /// Returns whether accounts from the transaction access list are loaded from the database
+    /// before execution.
+    ///
+    /// Access list slots are always loaded, accounts without slots are only marked as warm
+    /// unless this is enabled.
+    fn is_access_list_prefetch_enabled(&self) -> bool {
+        false
+    }
+
+    /// Returns the maximum number of hashes cached by the KECCAK256 instruction within a
+    /// transaction, see [`KeccakCache`][crate::local::KeccakCache].
+    ///
+    /// Defaults to `0`, the cache is disabled.
+    fn keccak_cache_size(&self) -> usize {
+        0
+    }
+
+    /// Returns the ceiling of the gas limit of simulated calls, applied by
+    /// `ExecuteGasCapEvm::transact_with_gas_cap` in the handler crate.
+    ///
+    /// Defaults to `None`, calls are only capped by the block and transaction gas limit caps.
+    fn call_gas_cap(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the behaviour of the SELFDESTRUCT instruction.
+    ///
+    /// Defaults to the behaviour of the spec, see [`SelfdestructMode::from_spec`].
+    fn selfdestruct_mode(&self) -> SelfdestructMode {
+        SelfdestructMode::from_spec(self.spec().into())
+    }
+
+    /// Returns which accounts are included in the state returned when the journal is finalized.
+    ///
+    /// Defaults to [`FinalizedStateMode::Loaded`], all loaded accounts are included.
+    fn finalized_state_mode(&self) -> FinalizedStateMode {
+        FinalizedStateMode::Loaded
+    }
+
+    /// Returns the opcodes that halt with [`HaltReason::OpcodeDisabled`] instead of executing.
+    ///
+    /// Defaults to no disabled opcodes.
+    ///
+    /// [`HaltReason::OpcodeDisabled`]: crate::result::HaltReason::OpcodeDisabled
+    fn disabled_opcodes(&self) -> DisabledOpcodes {
+        DisabledOpcodes::new()
+    }
+
+    /// Returns the gas schedule read by instructions.
+    ///
+    /// Defaults to the schedule of the spec, see [`GasSchedule::for_spec`].
+    fn gas_schedule(&self) -> &GasSchedule {
+        GasSchedule::for_spec(self.spec().into())
+    }
+
+    /// Returns the gas refund rules.
+    ///
+    /// Defaults to the rules of the spec, see [`RefundPolicy::from_spec`].
+    fn refund_policy(&self) -> RefundPolicy {
+        RefundPolicy::from_spec(self.spec().into())
+    }
+
+    /// Returns the recipients of the base fee and the priority fee.
+    ///
+    /// Defaults to [`FeePolicy::MAINNET`].
+    fn fee_policy(&self) -> FeePolicy {
+        FeePolicy::MAINNET
+    }
+
+    /// Returns the blob parameters of the chain.
+    ///
+    /// Defaults to the parameters of the spec, see [`BlobSchedule::from_spec`].
+    fn blob_schedule(&self) -> BlobSchedule {
+        BlobSchedule::from_spec(self.spec().into())
+    }
+
+    /// Returns the code observed by EXTCODESIZE, EXTCODECOPY and EXTCODEHASH for accounts
+    /// delegated with EIP-7702.
+    ///
+    /// Defaults to [`Eip7702CodePolicy::Designator`] as specified by EIP-7702.
+    fn eip7702_code_policy(&self) -> Eip7702CodePolicy {
+        Eip7702CodePolicy::Designator
+    }
+
+    /// Returns the policy that derives addresses of created contracts.
+    ///
+    /// Defaults to `None`, addresses are derived as specified by the yellow paper and EIP-1014.
+    fn create_address_policy(&self) -> Option<&dyn CreateAddressPolicy> {
+        None
+    }
+
+    /// Returns the store of bytecode analysis artifacts.
+    ///
+    /// Consulted before the code of created contracts is analyzed, new artifacts are stored in
+    /// it. Code loaded from the database is analyzed by the database, which can be given the same
+    /// store. Defaults to `None`, code is always analyzed.
+    fn analysis_store(&self) -> Option<Arc<dyn AnalysisStore>> {
+        None
+    }
+
+    /// Selects the spec active at the block with the given number and timestamp.
+    ///
+    /// Called by the context when the block is set. Returns `true` if the spec was selected,
+    /// defaults to a fixed spec and returns `false`.
+    #[auto_impl(keep_default_for(&, Arc))]
+    fn select_spec_for_block(&mut self, number: U256, timestamp: U256) -> bool {
+        let _ = (number, timestamp);
+        false
+    }
+
     /// Returns whether the base fee check is disabled.
     fn is_base_fee_check_disabled(&self) -> bool;
 
@@ -60,6 +219,411 @@ pub trait Cfg {
     fn is_priority_fee_check_disabled(&self) -> bool;
 }
 
+/// Nonce handling policy for transactions and EIP-7702 authorizations.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonceCheckMode {
+    /// Nonces must match the account nonce, mismatched transaction is rejected and
+    /// mismatched authorization is skipped.
+    #[default]
+    Strict,
+    /// Provided nonces are ignored and the current account nonce is used instead.
+    ///
+    /// Nonces of the caller and of the authorities are incremented as if they matched.
+    AutoFill,
+    /// Nonces are neither checked nor incremented.
+    ///
+    /// Caller nonce is still incremented for create transactions as it is used to derive
+    /// the created address.
+    Ignore,
+}
+
+impl NonceCheckMode {
+    /// Returns `true` if nonces need to match the account nonce.
+    #[inline]
+    pub const fn is_strict(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
+
+    /// Returns `true` if account nonces are incremented.
+    #[inline]
+    pub const fn bumps_nonce(&self) -> bool {
+        !matches!(self, Self::Ignore)
+    }
+}
+
+/// Behaviour of the SELFDESTRUCT instruction.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfdestructMode {
+    /// Account is destroyed and its balance is moved to the target, behaviour before Cancun.
+    Destroy,
+    /// [EIP-6780](https://eips.ethereum.org/EIPS/eip-6780): account is destroyed only if it was
+    /// created in the same transaction, otherwise only its balance is moved.
+    ///
+    /// Behaviour since Cancun.
+    #[default]
+    SameTxOnly,
+    /// SELFDESTRUCT halts execution as an instruction that is not activated.
+    Disabled,
+}
+
+impl SelfdestructMode {
+    /// Returns the mode used by the given spec.
+    #[inline]
+    pub const fn from_spec(spec: SpecId) -> Self {
+        if spec.is_enabled_in(SpecId::CANCUN) {
+            Self::SameTxOnly
+        } else {
+            Self::Destroy
+        }
+    }
+
+    /// Returns `true` if the account is destroyed even if it was not created in the same
+    /// transaction.
+    #[inline]
+    pub const fn is_destroy(&self) -> bool {
+        matches!(self, Self::Destroy)
+    }
+
+    /// Returns `true` if SELFDESTRUCT is disabled.
+    #[inline]
+    pub const fn is_disabled(&self) -> bool {
+        matches!(self, Self::Disabled)
+    }
+}
+
+/// Accounts included in the state returned when the journal is finalized.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FinalizedStateMode {
+    /// Every loaded account, including accounts that were only read.
+    #[default]
+    Loaded,
+    /// Every touched account, including touched accounts that are not changed, for example the
+    /// target of a call without value. Suitable for witnesses.
+    Touched,
+    /// Only accounts whose balance, nonce, code or storage changed, or that were created,
+    /// selfdestructed or cleared as touched empty accounts by EIP-161. Minimal state diff.
+    Changed,
+}
+
+/// Set of opcodes that halt with [`HaltReason::OpcodeDisabled`] instead of executing.
+///
+/// Disabled opcodes are replaced in the instruction table, so execution of other opcodes is not
+/// slowed down by checks.
+///
+/// [`HaltReason::OpcodeDisabled`]: crate::result::HaltReason::OpcodeDisabled
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisabledOpcodes([u64; 4]);
+
+impl DisabledOpcodes {
+    /// Returns an empty set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self([0; 4])
+    }
+
+    /// Returns the set of the given opcodes.
+    pub const fn from_opcodes(opcodes: &[u8]) -> Self {
+        let mut set = Self::new();
+        let mut i = 0;
+        while i < opcodes.len() {
+            set = set.with(opcodes[i]);
+            i += 1;
+        }
+        set
+    }
+
+    /// Returns the set with the opcode added.
+    #[inline]
+    pub const fn with(mut self, opcode: u8) -> Self {
+        self.0[opcode as usize / 64] |= 1 << (opcode % 64);
+        self
+    }
+
+    /// Adds the opcode to the set.
+    #[inline]
+    pub fn insert(&mut self, opcode: u8) {
+        *self = self.with(opcode);
+    }
+
+    /// Removes the opcode from the set.
+    #[inline]
+    pub fn remove(&mut self, opcode: u8) {
+        self.0[opcode as usize / 64] &= !(1 << (opcode % 64));
+    }
+
+    /// Returns `true` if the opcode is in the set.
+    #[inline]
+    pub const fn contains(&self, opcode: u8) -> bool {
+        self.0[opcode as usize / 64] & (1 << (opcode % 64)) != 0
+    }
+
+    /// Returns `true` if no opcode is disabled.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0[0] == 0 && self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0
+    }
+
+    /// Returns an iterator over the opcodes in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|opcode| self.contains(*opcode))
+    }
+}
+
+impl FromIterator<u8> for DisabledOpcodes {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|opcode| set.insert(opcode));
+        set
+    }
+}
+
+/// Gas refund rules applied when the transaction gas is finalized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefundPolicy {
+    /// Maximum refund as a fraction of the gas spent.
+    pub cap: RefundCap,
+    /// Whether SELFDESTRUCT records a refund, removed in London by EIP-3529.
+    pub selfdestruct_refund: bool,
+}
+
+impl Default for RefundPolicy {
+    fn default() -> Self {
+        Self::from_spec(SpecId::default())
+    }
+}
+
+impl RefundPolicy {
+    /// Returns the refund rules of the given spec.
+    #[inline]
+    pub const fn from_spec(spec: SpecId) -> Self {
+        let is_london = spec.is_enabled_in(SpecId::LONDON);
+        Self {
+            cap: if is_london {
+                RefundCap::Eip3529
+            } else {
+                RefundCap::PreLondon
+            },
+            selfdestruct_refund: !is_london,
+        }
+    }
+
+    /// Sets the refund cap.
+    #[inline]
+    pub const fn with_cap(mut self, cap: RefundCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets whether SELFDESTRUCT records a refund.
+    #[inline]
+    pub const fn with_selfdestruct_refund(mut self, enabled: bool) -> Self {
+        self.selfdestruct_refund = enabled;
+        self
+    }
+}
+
+/// Maximum gas refund as a fraction of the gas spent by the transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefundCap {
+    /// [EIP-3529](https://eips.ethereum.org/EIPS/eip-3529): refund is capped to a fifth of
+    /// the gas spent.
+    Eip3529,
+    /// Refund is capped to a half of the gas spent, rule before London.
+    PreLondon,
+    /// Refund is capped to the gas spent divided by the given quotient.
+    ///
+    /// Quotient of zero disables refunds.
+    Quotient(u64),
+}
+
+impl RefundCap {
+    /// Returns the quotient the gas spent is divided by.
+    #[inline]
+    pub const fn quotient(&self) -> u64 {
+        match self {
+            Self::Eip3529 => 5,
+            Self::PreLondon => 2,
+            Self::Quotient(quotient) => *quotient,
+        }
+    }
+}
+
+/// Recipients of the transaction fees paid when the transaction gas is settled.
+///
+/// Fee of a transaction is split into the base fee, `basefee * gas_used` since London, and the
+/// priority fee, the rest of `effective_gas_price * gas_used`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeePolicy {
+    /// Destination of the priority fee.
+    pub priority_fee: FeeDestination,
+    /// Destination of the base fee.
+    pub base_fee: FeeDestination,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+impl FeePolicy {
+    /// Priority fee is paid to the block beneficiary and the base fee is burned.
+    pub const MAINNET: Self = Self {
+        priority_fee: FeeDestination::to(FeeRecipient::Beneficiary),
+        base_fee: FeeDestination::to(FeeRecipient::Burn),
+    };
+
+    /// Sets the destination of the priority fee.
+    #[inline]
+    pub const fn with_priority_fee(mut self, destination: FeeDestination) -> Self {
+        self.priority_fee = destination;
+        self
+    }
+
+    /// Sets the destination of the base fee.
+    #[inline]
+    pub const fn with_base_fee(mut self, destination: FeeDestination) -> Self {
+        self.base_fee = destination;
+        self
+    }
+}
+
+/// Destination of a part of the transaction fee, optionally split between two recipients.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeDestination {
+    /// Recipient of the fee that is not paid to the `secondary` recipient.
+    pub primary: FeeRecipient,
+    /// Recipient of the `secondary_percent` of the fee.
+    pub secondary: FeeRecipient,
+    /// Percentage of the fee paid to the `secondary` recipient, capped at 100.
+    pub secondary_percent: u8,
+}
+
+impl FeeDestination {
+    /// Whole fee is paid to the recipient.
+    #[inline]
+    pub const fn to(recipient: FeeRecipient) -> Self {
+        Self {
+            primary: recipient,
+            secondary: recipient,
+            secondary_percent: 0,
+        }
+    }
+
+    /// Fee is split, `secondary_percent` of it is paid to the `secondary` recipient and the rest
+    /// to the `primary` recipient.
+    #[inline]
+    pub const fn split(
+        primary: FeeRecipient,
+        secondary: FeeRecipient,
+        secondary_percent: u8,
+    ) -> Self {
+        Self {
+            primary,
+            secondary,
+            secondary_percent,
+        }
+    }
+
+    /// Splits the fee between the primary and the secondary recipient.
+    ///
+    /// Rounding remainder is paid to the primary recipient.
+    #[inline]
+    pub fn distribute(&self, fee: U256) -> [(FeeRecipient, U256); 2] {
+        let percent = self.secondary_percent.min(100);
+        let secondary = fee / U256::from(100) * U256::from(percent)
+            + fee % U256::from(100) * U256::from(percent) / U256::from(100);
+        [(self.primary, fee - secondary), (self.secondary, secondary)]
+    }
+}
+
+/// Recipient of a part of the transaction fee.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeRecipient {
+    /// Fee is paid to the block beneficiary.
+    #[default]
+    Beneficiary,
+    /// Fee is removed from circulation.
+    Burn,
+    /// Fee is paid to the address, like a fee vault.
+    Address(Address),
+}
+
+/// Code observed by EXTCODESIZE, EXTCODECOPY and EXTCODEHASH for accounts delegated with
+/// EIP-7702.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Eip7702CodePolicy {
+    /// Instructions observe the 23 bytes delegation designator, `0xef0100 ++ address`.
+    #[default]
+    Designator,
+    /// Instructions observe the code of the delegate account.
+    ///
+    /// Delegate is loaded as an additional account access and the access is cold if either
+    /// the delegated account or the delegate is cold.
+    DelegateCode,
+}
+
+/// Validation of blobs of EIP-4844 transactions.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlobSidecarMode {
+    /// Only versioned hashes of the transaction are validated, blob sidecar is ignored.
+    #[default]
+    HashesOnly,
+    /// Blob sidecar is required and verified against the versioned hashes with the KZG backend.
+    ///
+    /// Useful for builders validating full blob transactions.
+    Verify,
+}
+
+impl BlobSidecarMode {
+    /// Returns `true` if the blob sidecar is verified.
+    #[inline]
+    pub const fn is_verify(&self) -> bool {
+        matches!(self, Self::Verify)
+    }
+}
+
+/// Policy of the EIP-3607 check rejecting transactions from senders with deployed code.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallerCodePolicy {
+    /// Senders with any code are rejected, including EIP-7702 delegated accounts.
+    Strict,
+    /// Senders with code are rejected unless the code is an EIP-7702 delegation designator.
+    ///
+    /// This is the behaviour of EIP-3607 amended by EIP-7702.
+    #[default]
+    AllowDelegated,
+    /// Senders with code are accepted.
+    ///
+    /// Useful for simulations that impersonate contract accounts.
+    #[cfg(feature = "optional_eip3607")]
+    Disabled,
+}
+
+impl CallerCodePolicy {
+    /// Returns `true` if senders with code are accepted.
+    #[inline]
+    pub const fn is_disabled(&self) -> bool {
+        match self {
+            #[cfg(feature = "optional_eip3607")]
+            Self::Disabled => true,
+            _ => false,
+        }
+    }
+}
+
 /// What bytecode analysis to perform
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -92,3 +656,52 @@ pub enum CreateScheme {
         address: Address,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn disabled_opcodes() {
+        let mut set = DisabledOpcodes::from_opcodes(&[0xff, 0xf0, 0x00]);
+        assert!(set.contains(0xff) && set.contains(0xf0) && set.contains(0x00));
+        assert!(!set.contains(0x01));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0x00, 0xf0, 0xff]);
+
+        set.remove(0xff);
+        set.remove(0x00);
+        set.insert(0x40);
+        assert_eq!(set, [0xf0, 0x40].into_iter().collect());
+        set.remove(0xf0);
+        set.remove(0x40);
+        assert!(set.is_empty());
+        assert_eq!(set, DisabledOpcodes::default());
+    }
+
+    #[test]
+    fn fee_destination_split() {
+        let vault = FeeRecipient::Address(Address::with_last_byte(1));
+        let fee = U256::from(1_001);
+        assert_eq!(
+            FeeDestination::to(FeeRecipient::Burn).distribute(fee),
+            [(FeeRecipient::Burn, fee), (FeeRecipient::Burn, U256::ZERO)]
+        );
+        assert_eq!(
+            FeeDestination::split(FeeRecipient::Beneficiary, vault, 30).distribute(fee),
+            [
+                (FeeRecipient::Beneficiary, U256::from(701)),
+                (vault, U256::from(300))
+            ]
+        );
+        // Percentage is capped at 100.
+        assert_eq!(
+            FeeDestination::split(FeeRecipient::Beneficiary, vault, 200).distribute(fee),
+            [(FeeRecipient::Beneficiary, U256::ZERO), (vault, fee)]
+        );
+        assert_eq!(
+            FeeDestination::split(FeeRecipient::Beneficiary, vault, 50).distribute(U256::MAX)[1].1,
+            U256::MAX / U256::from(2)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Blob parameters of hardforks, [EIP-7840](https://eips.ethereum.org/EIPS/eip-7840).
+use crate::block::blob::{calc_excess_blob_gas_osaka, BlobExcessGasAndPrice};
+use primitives::{eip4844, hardfork::SpecId};
+
+/// Blob parameters of a hardfork.
+///
+/// Schedule of a spec is returned by [`BlobSchedule::from_spec`], chains and devnets running
+/// other blob parameters can override it with [`Cfg::blob_schedule`](crate::Cfg::blob_schedule).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobSchedule {
+    /// Target number of blobs per block.
+    pub target_blob_count: u64,
+    /// Maximum number of blobs per block.
+    pub max_blob_count: u64,
+    /// Maximum number of blobs per transaction.
+    pub max_blobs_per_tx: u64,
+    /// Controls the maximum rate of change of the blob base fee.
+    pub base_fee_update_fraction: u64,
+    /// Whether the blob base fee is bounded by the execution cost,
+    /// [EIP-7918](https://eips.ethereum.org/EIPS/eip-7918).
+    pub base_fee_bounded_by_execution_cost: bool,
+}
+
+impl BlobSchedule {
+    /// Blob parameters of Cancun.
+    pub const CANCUN: Self = Self {
+        target_blob_count: eip4844::TARGET_BLOB_NUMBER_PER_BLOCK_CANCUN,
+        max_blob_count: eip4844::MAX_BLOB_NUMBER_PER_BLOCK_CANCUN,
+        max_blobs_per_tx: eip4844::MAX_BLOB_NUMBER_PER_BLOCK_CANCUN,
+        base_fee_update_fraction: eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN,
+        base_fee_bounded_by_execution_cost: false,
+    };
+
+    /// Blob parameters of Prague, [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691).
+    pub const PRAGUE: Self = Self {
+        target_blob_count: eip4844::TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE,
+        max_blob_count: eip4844::MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE,
+        max_blobs_per_tx: eip4844::MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE,
+        base_fee_update_fraction: eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE,
+        base_fee_bounded_by_execution_cost: false,
+    };
+
+    /// Blob parameters of Osaka.
+    ///
+    /// Block parameters are the same as in Prague, transactions are limited by
+    /// [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594) and the base fee is bounded by
+    /// [EIP-7918](https://eips.ethereum.org/EIPS/eip-7918).
+    pub const OSAKA: Self = Self {
+        max_blobs_per_tx: eip4844::MAX_BLOBS_PER_TX_OSAKA,
+        base_fee_bounded_by_execution_cost: true,
+        ..Self::PRAGUE
+    };
+
+    /// Returns the blob parameters of the spec.
+    ///
+    /// Specs before Cancun don't have blobs and return the Cancun parameters.
+    pub const fn from_spec(spec: SpecId) -> Self {
+        if spec.is_enabled_in(SpecId::OSAKA) {
+            Self::OSAKA
+        } else if spec.is_enabled_in(SpecId::PRAGUE) {
+            Self::PRAGUE
+        } else {
+            Self::CANCUN
+        }
+    }
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blob_count * eip4844::GAS_PER_BLOB
+    }
+
+    /// Returns the maximum blob gas per block.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blob_count * eip4844::GAS_PER_BLOB
+    }
+
+    /// Returns the blob gas price of a block with the given excess blob gas.
+    pub fn blob_excess_gas_and_price(&self, excess_blob_gas: u64) -> BlobExcessGasAndPrice {
+        BlobExcessGasAndPrice::new(excess_blob_gas, self.base_fee_update_fraction)
+    }
+
+    /// Returns the excess blob gas of the block following the parent block, if the parent block
+    /// uses this schedule.
+    pub fn next_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+        parent_base_fee_per_gas: u64,
+    ) -> u64 {
+        calc_excess_blob_gas_osaka(
+            parent_excess_blob_gas,
+            parent_blob_gas_used,
+            self.target_blob_gas_per_block(),
+            self.base_fee_bounded_by_execution_cost,
+            parent_base_fee_per_gas,
+            parent_excess_blob_gas,
+            self.base_fee_update_fraction,
+            self.max_blob_count,
+            self.target_blob_count,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        assert_eq!(
+            BlobSchedule::from_spec(SpecId::SHANGHAI),
+            BlobSchedule::CANCUN
+        );
+        assert_eq!(
+            BlobSchedule::from_spec(SpecId::PRAGUE),
+            BlobSchedule::PRAGUE
+        );
+        assert_eq!(
+            BlobSchedule::CANCUN.target_blob_gas_per_block(),
+            eip4844::TARGET_BLOB_GAS_PER_BLOCK_CANCUN
+        );
+        assert_eq!(
+            BlobSchedule::PRAGUE.max_blob_gas_per_block(),
+            eip4844::MAX_BLOB_GAS_PER_BLOCK_PRAGUE
+        );
+        let osaka = BlobSchedule::from_spec(SpecId::OSAKA);
+        assert_eq!(osaka.max_blob_count, 9);
+        assert_eq!(osaka.max_blobs_per_tx, 6);
+    }
+
+    #[test]
+    fn next_excess_blob_gas() {
+        let schedule = BlobSchedule::PRAGUE;
+        let target = schedule.target_blob_gas_per_block();
+        assert_eq!(schedule.next_excess_blob_gas(0, target, 0), 0);
+        assert_eq!(
+            schedule.next_excess_blob_gas(0, schedule.max_blob_gas_per_block(), 0),
+            3 * eip4844::GAS_PER_BLOB
+        );
+
+        // Blobs that are cheap compared to execution grow the excess faster in Osaka.
+        let used = schedule.max_blob_gas_per_block();
+        assert!(
+            BlobSchedule::OSAKA.next_excess_blob_gas(0, used, 1_000_000_000)
+                > schedule.next_excess_blob_gas(0, used, 1_000_000_000)
+        );
+    }
+}
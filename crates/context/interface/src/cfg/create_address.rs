@@ -0,0 +1,52 @@
+//! Derivation of addresses of contracts created by `CREATE`, `CREATE2` and creation transactions.
+use auto_impl::auto_impl;
+use core::fmt::Debug;
+use primitives::{create_address::create2_address_from_hash, Address, B256, U256};
+
+/// Policy that derives addresses of created contracts.
+///
+/// Chains that namespace deployments or mix additional data, like the chain ID, into the address
+/// can set the policy with [`Cfg::create_address_policy`](crate::Cfg::create_address_policy).
+/// Default methods use the standard derivation, so a policy only overrides the schemes it changes.
+/// Init code of `CREATE2` is always hashed with [`keccak256`](primitives::keccak256). Addresses of [`CreateScheme::Custom`](crate::CreateScheme::Custom) are not derived.
+#[auto_impl(&, Box, Arc)]
+pub trait CreateAddressPolicy: Debug + Send + Sync {
+    /// Returns the address created by `CREATE` or a creation transaction, `nonce` is the nonce
+    /// of the caller before the creation.
+    fn create_address(&self, caller: Address, nonce: u64) -> Address {
+        caller.create(nonce)
+    }
+
+    /// Returns the address created by `CREATE2`.
+    fn create2_address(&self, caller: Address, salt: U256, init_code_hash: B256) -> Address {
+        create2_address_from_hash(caller, salt.into(), init_code_hash)
+    }
+}
+
+/// Standard address derivation of [EIP-1014](https://eips.ethereum.org/EIPS/eip-1014) and the
+/// yellow paper.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardCreateAddress;
+
+impl CreateAddressPolicy for StandardCreateAddress {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, create_address::create2_address_from_hash, keccak256};
+
+    #[test]
+    fn standard_create_address() {
+        let caller = address!("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        assert_eq!(
+            StandardCreateAddress.create_address(caller, 0),
+            address!("0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        let hash = keccak256([0x00]);
+        assert_eq!(
+            StandardCreateAddress.create2_address(caller, U256::from(1), hash),
+            create2_address_from_hash(caller, B256::with_last_byte(1), hash)
+        );
+    }
+}
@@ -0,0 +1,615 @@
+//! Gas costs of instructions that were repriced by hardforks or are commonly changed by chains.
+use crate::{
+    context::{SStoreResult, SelfDestructResult},
+    journaled_state::{AccountLoad, StateLoad},
+};
+use primitives::hardfork::SpecId;
+
+/// Gas schedule read by instructions.
+///
+/// Contains costs of state access and other instructions with dynamic or fork dependent cost.
+/// Schedule of a spec is returned by [`GasSchedule::from_spec`] and [`GasSchedule::for_spec`],
+/// chains with a custom schedule can override it with [`Cfg::gas_schedule`](crate::Cfg::gas_schedule).
+///
+/// Costs of a custom schedule can be arbitrary, cost functions saturate or return `None` on
+/// overflow, which runs the instruction out of gas.
+///
+/// Static costs of arithmetic, stack, memory and environment instructions never changed and are
+/// not part of the schedule.
+///
+/// Since [EIP-2929](https://eips.ethereum.org/EIPS/eip-2929) account and storage access costs
+/// depend on whether the access is warm or cold. Before it, warm and cold access costs are zero
+/// and instructions are priced with their flat costs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasSchedule {
+    /// Cost of a warm account access, added to BALANCE, EXTCODE* and CALL* costs.
+    pub warm_account_access: u64,
+    /// Cost of a cold account access, added to BALANCE, EXTCODE*, CALL* and SELFDESTRUCT costs.
+    pub cold_account_access: u64,
+    /// Cost of a warm storage slot access by SLOAD and SSTORE.
+    pub warm_storage_read: u64,
+    /// Cost of a cold storage slot access by SLOAD and SSTORE.
+    pub cold_sload: u64,
+    /// Flat cost of BALANCE.
+    pub balance: u64,
+    /// Flat cost of EXTCODESIZE.
+    pub extcodesize: u64,
+    /// Flat cost of EXTCODECOPY.
+    pub extcodecopy: u64,
+    /// Flat cost of EXTCODEHASH.
+    pub extcodehash: u64,
+    /// Flat cost of SLOAD.
+    pub sload: u64,
+    /// Cost of SSTORE setting a zero slot to a non-zero value.
+    pub sstore_set: u64,
+    /// Cost of SSTORE changing a non-zero slot.
+    pub sstore_reset: u64,
+    /// Refund of SSTORE clearing a slot.
+    pub sstore_clears_refund: i64,
+    /// Whether SSTORE uses net gas metering of [EIP-2200](https://eips.ethereum.org/EIPS/eip-2200).
+    pub sstore_net_metering: bool,
+    /// Flat cost of CALL, CALLCODE, DELEGATECALL and STATICCALL.
+    pub call: u64,
+    /// Additional cost of a call transferring value.
+    pub call_value: u64,
+    /// Gas given to the callee of a call transferring value.
+    pub call_stipend: u64,
+    /// Additional cost of a call creating a new account.
+    pub new_account: u64,
+    /// Whether a new account is charged by calls and SELFDESTRUCT only when value is transferred,
+    /// [EIP-161](https://eips.ethereum.org/EIPS/eip-161).
+    pub new_account_requires_value: bool,
+    /// Flat cost of SELFDESTRUCT.
+    pub selfdestruct: u64,
+    /// Additional cost of SELFDESTRUCT sending balance to a new account.
+    pub selfdestruct_new_account: u64,
+    /// Refund of SELFDESTRUCT, when enabled by the refund policy.
+    pub selfdestruct_refund: i64,
+    /// Base cost of EXP.
+    pub exp: u64,
+    /// Cost of EXP per byte of the exponent.
+    pub exp_byte: u64,
+    /// Base cost of LOG*.
+    pub log: u64,
+    /// Cost of LOG* per byte of data.
+    pub log_data: u64,
+    /// Cost of LOG* per topic.
+    pub log_topic: u64,
+    /// Base cost of KECCAK256.
+    pub keccak256: u64,
+    /// Cost of KECCAK256 per word of input, also charged by CREATE2.
+    pub keccak256_word: u64,
+    /// Cost of *COPY instructions per copied word.
+    pub copy: u64,
+    /// Cost of BLOCKHASH.
+    pub blockhash: u64,
+    /// Base cost of CREATE and CREATE2.
+    pub create: u64,
+    /// Cost of CREATE and CREATE2 per word of initcode,
+    /// [EIP-3860](https://eips.ethereum.org/EIPS/eip-3860).
+    pub initcode_word: u64,
+    /// Gas forwarded to child frames by calls and creations.
+    pub call_gas: CallGasPolicy,
+}
+
+/// Limits the gas calls and creations forward to child frames.
+///
+/// Calls forward the requested gas and creations forward all remaining gas, limited by the
+/// policy. The stipend of calls transferring value is [`GasSchedule::call_stipend`] and is added
+/// on top of the forwarded gas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallGasPolicy {
+    /// Part of the remaining gas retained by the parent frame is `1 / retained_divisor`.
+    ///
+    /// Zero retains nothing and does not limit the requested gas to the remaining gas, so the
+    /// call runs out of gas if it requests more than remaining, as before
+    /// [EIP-150](https://eips.ethereum.org/EIPS/eip-150) that retains `1/64`.
+    pub retained_divisor: u64,
+    /// Maximum gas forwarded to a child frame, stipend excluded.
+    pub max_forwarded: u64,
+}
+
+impl Default for CallGasPolicy {
+    fn default() -> Self {
+        Self::from_spec(SpecId::default())
+    }
+}
+
+impl CallGasPolicy {
+    /// Returns the policy of the given spec.
+    pub const fn from_spec(spec: SpecId) -> Self {
+        Self {
+            // EIP-150: Gas cost changes for IO-heavy operations
+            retained_divisor: if spec.is_enabled_in(SpecId::TANGERINE) {
+                64
+            } else {
+                0
+            },
+            max_forwarded: u64::MAX,
+        }
+    }
+
+    /// Returns the policy that forwards at most `max_forwarded` gas.
+    pub const fn with_max_forwarded(mut self, max_forwarded: u64) -> Self {
+        self.max_forwarded = max_forwarded;
+        self
+    }
+
+    /// Returns the gas forwarded to a child frame that requested `requested` gas while the parent
+    /// frame has `remaining` gas.
+    #[inline]
+    pub const fn forwarded_gas(&self, remaining: u64, requested: u64) -> u64 {
+        let mut gas = requested;
+        if let Some(retained) = remaining.checked_div(self.retained_divisor) {
+            let available = remaining - retained;
+            if available < gas {
+                gas = available;
+            }
+        }
+        if self.max_forwarded < gas {
+            gas = self.max_forwarded;
+        }
+        gas
+    }
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self::from_spec(SpecId::default())
+    }
+}
+
+impl GasSchedule {
+    /// Returns the gas schedule of the given spec.
+    pub const fn from_spec(spec: SpecId) -> Self {
+        let is_tangerine = spec.is_enabled_in(SpecId::TANGERINE);
+        let is_istanbul = spec.is_enabled_in(SpecId::ISTANBUL);
+        let is_berlin = spec.is_enabled_in(SpecId::BERLIN);
+
+        // EIP-2929: Gas cost increases for state access opcodes
+        let (warm_account_access, cold_account_access, warm_storage_read, cold_sload) = if is_berlin
+        {
+            (100, 2600, 100, 2100)
+        } else {
+            (0, 0, 0, 0)
+        };
+
+        // EIP-150: Gas cost changes for IO-heavy operations
+        // EIP-1884: Repricing for trie-size-dependent opcodes
+        let (balance, extcodesize, extcodehash, sload, call) = if is_berlin {
+            (0, 0, 0, 0, 0)
+        } else if is_istanbul {
+            (700, 700, 700, 800, 700)
+        } else if is_tangerine {
+            (400, 700, 400, 200, 700)
+        } else {
+            (20, 20, 400, 50, 40)
+        };
+
+        Self {
+            warm_account_access,
+            cold_account_access,
+            warm_storage_read,
+            cold_sload,
+            balance,
+            extcodesize,
+            extcodecopy: extcodesize,
+            extcodehash,
+            sload,
+            sstore_set: 20000,
+            sstore_reset: 5000,
+            // EIP-3529: Reduction in refunds
+            sstore_clears_refund: if spec.is_enabled_in(SpecId::LONDON) {
+                4800
+            } else {
+                15000
+            },
+            // EIP-2200: Structured Definitions for Net Gas Metering
+            sstore_net_metering: is_istanbul,
+            call,
+            call_value: 9000,
+            call_stipend: 2300,
+            new_account: 25000,
+            // EIP-161: State trie clearing (invariant-preserving alternative)
+            new_account_requires_value: spec.is_enabled_in(SpecId::SPURIOUS_DRAGON),
+            selfdestruct: if is_tangerine { 5000 } else { 0 },
+            selfdestruct_new_account: if is_tangerine { 25000 } else { 0 },
+            selfdestruct_refund: 24000,
+            exp: 10,
+            // EIP-160: EXP cost increase
+            exp_byte: if spec.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
+                50
+            } else {
+                10
+            },
+            log: 375,
+            log_data: 8,
+            log_topic: 375,
+            keccak256: 30,
+            keccak256_word: 6,
+            copy: 3,
+            blockhash: 20,
+            create: 32000,
+            initcode_word: 2,
+            call_gas: CallGasPolicy::from_spec(spec),
+        }
+    }
+
+    /// Returns a reference to the gas schedule of the given spec, see [`GasSchedule::from_spec`].
+    ///
+    /// The schedules are constants, so they are not rebuilt on every access.
+    pub const fn for_spec(spec: SpecId) -> &'static Self {
+        const FRONTIER: GasSchedule = GasSchedule::from_spec(SpecId::FRONTIER);
+        const TANGERINE: GasSchedule = GasSchedule::from_spec(SpecId::TANGERINE);
+        const SPURIOUS_DRAGON: GasSchedule = GasSchedule::from_spec(SpecId::SPURIOUS_DRAGON);
+        const ISTANBUL: GasSchedule = GasSchedule::from_spec(SpecId::ISTANBUL);
+        const BERLIN: GasSchedule = GasSchedule::from_spec(SpecId::BERLIN);
+        const LONDON: GasSchedule = GasSchedule::from_spec(SpecId::LONDON);
+
+        if spec.is_enabled_in(SpecId::LONDON) {
+            &LONDON
+        } else if spec.is_enabled_in(SpecId::BERLIN) {
+            &BERLIN
+        } else if spec.is_enabled_in(SpecId::ISTANBUL) {
+            &ISTANBUL
+        } else if spec.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
+            &SPURIOUS_DRAGON
+        } else if spec.is_enabled_in(SpecId::TANGERINE) {
+            &TANGERINE
+        } else {
+            &FRONTIER
+        }
+    }
+
+    /// Returns the cost of a warm or cold account access.
+    #[inline]
+    pub const fn account_access_cost(&self, is_cold: bool) -> u64 {
+        if is_cold {
+            self.cold_account_access
+        } else {
+            self.warm_account_access
+        }
+    }
+
+    /// Returns the account access cost including the access of the EIP-7702 delegate.
+    #[inline]
+    pub const fn account_access_cost_with_delegation(&self, load: &StateLoad<AccountLoad>) -> u64 {
+        let gas = self.account_access_cost(load.is_cold);
+        match load.data.is_delegate_account_cold {
+            Some(is_cold) => gas.saturating_add(self.account_access_cost(is_cold)),
+            None => gas,
+        }
+    }
+
+    /// Returns the cost of BALANCE.
+    #[inline]
+    pub const fn balance_cost(&self, is_cold: bool) -> u64 {
+        self.balance
+            .saturating_add(self.account_access_cost(is_cold))
+    }
+
+    /// Returns the cost of EXTCODESIZE.
+    #[inline]
+    pub const fn extcodesize_cost(&self, is_cold: bool) -> u64 {
+        self.extcodesize
+            .saturating_add(self.account_access_cost(is_cold))
+    }
+
+    /// Returns the cost of EXTCODEHASH.
+    #[inline]
+    pub const fn extcodehash_cost(&self, is_cold: bool) -> u64 {
+        self.extcodehash
+            .saturating_add(self.account_access_cost(is_cold))
+    }
+
+    /// Returns the cost of EXTCODECOPY copying `len` bytes.
+    #[inline]
+    pub const fn extcodecopy_cost(&self, len: usize, is_cold: bool) -> Option<u64> {
+        self.copy_cost(
+            self.extcodecopy
+                .saturating_add(self.account_access_cost(is_cold)),
+            len,
+        )
+    }
+
+    /// Returns `base_cost` increased by the cost of copying `len` bytes.
+    #[inline]
+    pub const fn copy_cost(&self, base_cost: u64, len: usize) -> Option<u64> {
+        match self.per_word_cost(len, self.copy) {
+            Some(cost) => base_cost.checked_add(cost),
+            None => None,
+        }
+    }
+
+    /// Returns the cost of SLOAD.
+    #[inline]
+    pub const fn sload_cost(&self, is_cold: bool) -> u64 {
+        self.sload.saturating_add(if is_cold {
+            self.cold_sload
+        } else {
+            self.warm_storage_read
+        })
+    }
+
+    /// Returns the cost of SSTORE.
+    #[inline]
+    pub fn sstore_cost(&self, vals: &SStoreResult, is_cold: bool) -> u64 {
+        if !self.sstore_net_metering {
+            return if vals.is_present_zero() && !vals.is_new_zero() {
+                self.sstore_set
+            } else {
+                self.sstore_reset
+            };
+        }
+
+        // EIP-2200: Structured Definitions for Net Gas Metering
+        let sload_gas = self.sload_cost(false);
+        let mut gas = if vals.is_new_eq_present() {
+            sload_gas
+        } else if vals.is_original_eq_present() && vals.is_original_zero() {
+            self.sstore_set
+        } else if vals.is_original_eq_present() {
+            self.sstore_reset.saturating_sub(self.cold_sload)
+        } else {
+            sload_gas
+        };
+        if is_cold {
+            gas = gas.saturating_add(self.cold_sload);
+        }
+        gas
+    }
+
+    /// Returns the refund of SSTORE, can be negative.
+    #[inline]
+    pub fn sstore_refund(&self, vals: &SStoreResult) -> i64 {
+        let clears = self.sstore_clears_refund;
+        let refund_of = |gas: u64| i64::try_from(gas).unwrap_or(i64::MAX);
+        if !self.sstore_net_metering {
+            return if !vals.is_present_zero() && vals.is_new_zero() {
+                clears
+            } else {
+                0
+            };
+        }
+
+        // EIP-2200: Structured Definitions for Net Gas Metering
+        if vals.is_new_eq_present() {
+            return 0;
+        }
+        if vals.is_original_eq_present() && vals.is_new_zero() {
+            return clears;
+        }
+
+        let mut refund: i64 = 0;
+        if !vals.is_original_zero() {
+            if vals.is_present_zero() {
+                refund = refund.saturating_sub(clears);
+            } else if vals.is_new_zero() {
+                refund = refund.saturating_add(clears);
+            }
+        }
+        if vals.is_original_eq_new() {
+            let sload_gas = self.sload_cost(false);
+            let restored = if vals.is_original_zero() {
+                self.sstore_set.saturating_sub(sload_gas)
+            } else {
+                self.sstore_reset
+                    .saturating_sub(self.cold_sload)
+                    .saturating_sub(sload_gas)
+            };
+            refund = refund.saturating_add(refund_of(restored));
+        }
+        refund
+    }
+
+    /// Returns the cost of a call, without the gas given to the callee.
+    ///
+    /// `account_load.is_empty` should be `false` for instructions that do not transfer value
+    /// and existed before EIP-161.
+    #[inline]
+    pub const fn call_cost(
+        &self,
+        transfers_value: bool,
+        account_load: &StateLoad<AccountLoad>,
+    ) -> u64 {
+        let mut gas = self
+            .call
+            .saturating_add(self.account_access_cost_with_delegation(account_load));
+        if transfers_value {
+            gas = gas.saturating_add(self.call_value);
+        }
+        if account_load.data.is_empty && (transfers_value || !self.new_account_requires_value) {
+            gas = gas.saturating_add(self.new_account);
+        }
+        gas
+    }
+
+    /// Returns the gas forwarded to a child frame, see [`CallGasPolicy::forwarded_gas`].
+    #[inline]
+    pub const fn call_gas_limit(&self, remaining: u64, requested: u64) -> u64 {
+        self.call_gas.forwarded_gas(remaining, requested)
+    }
+
+    /// Returns the cost of SELFDESTRUCT.
+    #[inline]
+    pub const fn selfdestruct_cost(&self, res: &StateLoad<SelfDestructResult>) -> u64 {
+        let creates_account =
+            !res.data.target_exists && (res.data.had_value || !self.new_account_requires_value);
+        let mut gas = self.selfdestruct;
+        if creates_account {
+            gas = gas.saturating_add(self.selfdestruct_new_account);
+        }
+        if res.is_cold {
+            gas = gas.saturating_add(self.cold_account_access);
+        }
+        gas
+    }
+
+    /// Returns the cost of EXP with the given exponent byte length.
+    #[inline]
+    pub const fn exp_cost(&self, exponent_bytes: u64) -> Option<u64> {
+        match self.exp_byte.checked_mul(exponent_bytes) {
+            Some(cost) => self.exp.checked_add(cost),
+            None => None,
+        }
+    }
+
+    /// Returns the cost of LOG with `topics` topics and `len` bytes of data.
+    #[inline]
+    pub const fn log_cost(&self, topics: u8, len: u64) -> Option<u64> {
+        let (Some(data), Some(topics)) = (
+            self.log_data.checked_mul(len),
+            self.log_topic.checked_mul(topics as u64),
+        ) else {
+            return None;
+        };
+        match self.log.checked_add(data) {
+            Some(cost) => cost.checked_add(topics),
+            None => None,
+        }
+    }
+
+    /// Returns the cost of KECCAK256 hashing `len` bytes.
+    #[inline]
+    pub const fn keccak256_cost(&self, len: usize) -> Option<u64> {
+        match self.per_word_cost(len, self.keccak256_word) {
+            Some(cost) => self.keccak256.checked_add(cost),
+            None => None,
+        }
+    }
+
+    /// Returns the cost of CREATE2 hashing `len` bytes of initcode, without initcode cost.
+    #[inline]
+    pub const fn create2_cost(&self, len: usize) -> Option<u64> {
+        match self.per_word_cost(len, self.keccak256_word) {
+            Some(cost) => self.create.checked_add(cost),
+            None => None,
+        }
+    }
+
+    /// Returns the cost of `len` bytes of initcode.
+    #[inline]
+    pub const fn initcode_cost(&self, len: usize) -> Option<u64> {
+        self.per_word_cost(len, self.initcode_word)
+    }
+
+    #[inline]
+    const fn per_word_cost(&self, len: usize, multiple: u64) -> Option<u64> {
+        multiple.checked_mul(len.div_ceil(32) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::U256;
+
+    #[test]
+    fn sstore_schedule() {
+        let clear = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::from(1),
+            new_value: U256::ZERO,
+        };
+
+        let frontier = GasSchedule::from_spec(SpecId::FRONTIER);
+        assert_eq!(frontier.sstore_cost(&clear, false), 5000);
+        assert_eq!(frontier.sstore_refund(&clear), 15000);
+
+        let istanbul = GasSchedule::from_spec(SpecId::ISTANBUL);
+        assert_eq!(istanbul.sload_cost(false), 800);
+        assert_eq!(istanbul.sstore_cost(&clear, true), 5000);
+
+        let london = GasSchedule::from_spec(SpecId::LONDON);
+        assert_eq!(london.sload_cost(true), 2100);
+        assert_eq!(london.sstore_cost(&clear, true), 5000);
+        assert_eq!(london.sstore_cost(&clear, false), 2900);
+        assert_eq!(london.sstore_refund(&clear), 4800);
+
+        // Restoring the original value refunds the reset cost minus the warm read.
+        let restore = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::from(2),
+            new_value: U256::from(1),
+        };
+        assert_eq!(london.sstore_cost(&restore, false), 100);
+        assert_eq!(london.sstore_refund(&restore), 2800);
+    }
+
+    #[test]
+    fn call_gas_policy() {
+        let frontier = GasSchedule::from_spec(SpecId::FRONTIER);
+        assert_eq!(frontier.call_gas_limit(6400, 10_000), 10_000);
+
+        let london = GasSchedule::from_spec(SpecId::LONDON);
+        assert_eq!(london.call_gas_limit(6400, 10_000), 6300);
+        assert_eq!(london.call_gas_limit(6400, 100), 100);
+
+        let capped = GasSchedule {
+            call_gas: CallGasPolicy {
+                retained_divisor: 2,
+                max_forwarded: 3000,
+            },
+            ..london
+        };
+        assert_eq!(capped.call_gas_limit(4000, 10_000), 2000);
+        assert_eq!(capped.call_gas_limit(64_000, 10_000), 3000);
+    }
+
+    #[test]
+    fn for_spec_matches_from_spec() {
+        for spec in (0..=u8::MAX).map_while(SpecId::try_from_u8) {
+            assert_eq!(*GasSchedule::for_spec(spec), GasSchedule::from_spec(spec));
+        }
+    }
+
+    #[test]
+    fn custom_schedule_overflow() {
+        let schedule = GasSchedule {
+            log_topic: u64::MAX,
+            initcode_word: u64::MAX,
+            cold_account_access: u64::MAX,
+            call_value: u64::MAX,
+            sstore_reset: u64::MAX,
+            sstore_clears_refund: i64::MAX,
+            ..GasSchedule::from_spec(SpecId::LONDON)
+        };
+        assert_eq!(schedule.log_cost(2, 0), None);
+        assert_eq!(schedule.initcode_cost(64), None);
+        assert_eq!(schedule.balance_cost(true), u64::MAX);
+
+        let load = StateLoad::new(
+            AccountLoad {
+                is_delegate_account_cold: Some(true),
+                is_empty: true,
+            },
+            true,
+        );
+        assert_eq!(schedule.call_cost(true, &load), u64::MAX);
+
+        // Restored slot refund does not fit `i64` and saturates.
+        let restore = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::from(2),
+            new_value: U256::from(1),
+        };
+        assert_eq!(schedule.sstore_refund(&restore), i64::MAX);
+
+        // Removed clear refund and saturated restore refund cancel out.
+        let restore_cleared = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::ZERO,
+            new_value: U256::from(1),
+        };
+        assert_eq!(schedule.sstore_refund(&restore_cleared), 0);
+
+        let recreate = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::ZERO,
+            new_value: U256::from(2),
+        };
+        assert_eq!(schedule.sstore_refund(&recreate), -i64::MAX);
+    }
+}
@@ -0,0 +1,25 @@
+//! Typed data attached to the chain field of the context.
+
+/// Access to data of type `T` stored in the chain field of the context.
+///
+/// Handlers and precompiles that need chain specific data, for example oracle prices or data
+/// availability parameters, can bound `CTX: ContextTr<Chain: ChainData<T>>` instead of
+/// requiring a concrete chain type.
+pub trait ChainData<T> {
+    /// Returns the data, `None` if it is not set.
+    fn chain_data(&self) -> Option<&T>;
+
+    /// Returns the data mutably, `None` if it is not set.
+    fn chain_data_mut(&mut self) -> Option<&mut T>;
+}
+
+/// Mainnet chain has no data.
+impl<T> ChainData<T> for () {
+    fn chain_data(&self) -> Option<&T> {
+        None
+    }
+
+    fn chain_data_mut(&mut self) -> Option<&mut T> {
+        None
+    }
+}
@@ -1,6 +1,7 @@
 //! Host interface for external blockchain state access.
 
 use crate::{
+    cfg::{GasSchedule, RefundPolicy, SelfdestructMode},
     context::{SStoreResult, SelfDestructResult, StateLoad},
     journaled_state::AccountLoad,
 };
@@ -48,6 +49,22 @@ pub trait Host {
 
     /// Max initcode size, calls `ContextTr::cfg().max_code_size().saturating_mul(2)`
     fn max_initcode_size(&self) -> usize;
+    /// Selfdestruct mode, calls `ContextTr::cfg().selfdestruct_mode()`
+    fn selfdestruct_mode(&self) -> SelfdestructMode;
+    /// Gas refund rules, calls `ContextTr::cfg().refund_policy()`
+    fn refund_policy(&self) -> RefundPolicy;
+    /// Gas schedule, calls `ContextTr::cfg().gas_schedule()`
+    fn gas_schedule(&self) -> &GasSchedule;
+
+    /* Hashing */
+
+    /// Keccak256 hash used by the KECCAK256 instruction, cached by `ContextTr::local_mut().keccak_cache()`
+    ///
+    /// Defaults to uncached [`primitives::keccak256`]. The opcode is protocol mandated keccak and
+    /// does not follow a custom precompile `Crypto` implementation.
+    fn keccak256(&mut self, input: &[u8]) -> B256 {
+        primitives::keccak256(input)
+    }
 
     /* Database */
 
@@ -146,6 +163,18 @@ impl Host for DummyHost {
         0
     }
 
+    fn selfdestruct_mode(&self) -> SelfdestructMode {
+        SelfdestructMode::default()
+    }
+
+    fn refund_policy(&self) -> RefundPolicy {
+        RefundPolicy::default()
+    }
+
+    fn gas_schedule(&self) -> &GasSchedule {
+        GasSchedule::for_spec(Default::default())
+    }
+
     fn block_hash(&mut self, _number: u64) -> Option<B256> {
         None
     }
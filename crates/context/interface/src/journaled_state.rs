@@ -1,11 +1,15 @@
 //! Journaled state trait [`JournalTr`] and related types.
-use crate::context::{SStoreResult, SelfDestructResult};
+use crate::{
+    cfg::{FinalizedStateMode, SelfdestructMode},
+    context::{SStoreResult, SelfDestructResult},
+    result::DestructedAccount,
+};
 use core::ops::{Deref, DerefMut};
 use database_interface::Database;
 use primitives::{
     hardfork::SpecId, Address, Bytes, HashSet, Log, StorageKey, StorageValue, B256, U256,
 };
-use state::{Account, Bytecode};
+use state::{Account, Bytecode, EvmStorageSlot};
 use std::vec::Vec;
 
 /// Trait that contains database and journal of all changes that were made to the state.
@@ -59,6 +63,21 @@ pub trait JournalTr {
         target: Address,
     ) -> Result<StateLoad<SelfDestructResult>, <Self::Database as Database>::Error>;
 
+    /// Marks the account for selfdestruction with the given behaviour, see
+    /// [`JournalTr::selfdestruct`].
+    ///
+    /// [`SelfdestructMode::Disabled`] is handled by the instruction before the journal is called.
+    /// Defaults to [`JournalTr::selfdestruct`] with the behaviour of the spec.
+    fn selfdestruct_with_mode(
+        &mut self,
+        address: Address,
+        target: Address,
+        mode: SelfdestructMode,
+    ) -> Result<StateLoad<SelfDestructResult>, <Self::Database as Database>::Error> {
+        let _ = mode;
+        self.selfdestruct(address, target)
+    }
+
     /// Warms the account and storage.
     fn warm_account_and_storage(
         &mut self,
@@ -66,6 +85,18 @@ pub trait JournalTr {
         storage_keys: impl IntoIterator<Item = StorageKey>,
     ) -> Result<(), <Self::Database as Database>::Error>;
 
+    /// Loads accounts that are not loaded yet, so databases can fetch them in one batch.
+    ///
+    /// Accounts stay cold. Default implementation does nothing and accounts are loaded one by one
+    /// when they are warmed.
+    #[inline]
+    fn prefetch_accounts(
+        &mut self,
+        _addresses: &[Address],
+    ) -> Result<(), <Self::Database as Database>::Error> {
+        Ok(())
+    }
+
     /// Warms the account.
     fn warm_account(&mut self, address: Address);
 
@@ -81,6 +112,13 @@ pub trait JournalTr {
     /// Sets the spec id.
     fn set_spec_id(&mut self, spec_id: SpecId);
 
+    /// Sets which accounts are included in the state returned by [`JournalTr::finalize`].
+    ///
+    /// Defaults to ignoring the mode, all loaded accounts are returned.
+    fn set_finalized_state_mode(&mut self, mode: FinalizedStateMode) {
+        let _ = mode;
+    }
+
     /// Touches the account.
     fn touch_account(&mut self, address: Address);
 
@@ -128,6 +166,24 @@ pub trait JournalTr {
         address: Address,
     ) -> Result<StateLoad<AccountLoad>, <Self::Database as Database>::Error>;
 
+    /// Inserts the storage slot into the account as if the database returned `value` for it.
+    ///
+    /// Slot is unchanged and cold, and the insertion is not journaled. Journals that cache
+    /// storage slots have to invalidate them.
+    #[inline]
+    fn insert_storage_slot(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), <Self::Database as Database>::Error> {
+        let account = self.load_account(address)?.data;
+        let mut slot = EvmStorageSlot::new(value, account.transaction_id);
+        slot.mark_cold();
+        account.storage.insert(key, slot);
+        Ok(())
+    }
+
     /// Sets bytecode with hash. Assume that account is warm.
     fn set_code_with_hash(&mut self, address: Address, code: Bytecode, hash: B256);
 
@@ -198,9 +254,72 @@ pub trait JournalTr {
     /// Returns the depth of the journal.
     fn depth(&self) -> usize;
 
+    /// Returns `true` if the account existed in the database the journal reads from.
+    ///
+    /// Returns `None` if the account is not loaded into the journal. Accounts are not loaded by
+    /// this call, so it is safe to use from inspectors.
+    fn account_existed(&self, address: Address) -> Option<bool> {
+        let _ = address;
+        None
+    }
+
+    /// Returns `true` if the account was created in the current transaction and the creation
+    /// was not reverted.
+    ///
+    /// Returns `None` if the account is not loaded into the journal.
+    fn is_account_created(&self, address: Address) -> Option<bool> {
+        let _ = address;
+        None
+    }
+
+    /// Returns `true` if the account is touched and empty, these accounts are removed from the
+    /// state after the state clear ([EIP-161](https://eips.ethereum.org/EIPS/eip-161)).
+    ///
+    /// Returns `None` if the account is not loaded into the journal.
+    fn is_account_touched_and_empty(&self, address: Address) -> Option<bool> {
+        let _ = address;
+        None
+    }
+
     /// Take logs from journal.
     fn take_logs(&mut self) -> Vec<Log>;
 
+    /// Takes the accounts destructed by transactions committed since the journal was finalized
+    /// or the accounts were taken, in the order of destruction.
+    ///
+    /// Journals that don't track destructed accounts return an empty list.
+    fn take_destructed_accounts(&mut self) -> Vec<DestructedAccount> {
+        Vec::new()
+    }
+
+    /// Records the delegation applied by an EIP-7702 authorization of the current transaction.
+    ///
+    /// Journals that don't track delegations ignore it.
+    fn record_delegation(&mut self, delegation: AppliedDelegation) {
+        let _ = delegation;
+    }
+
+    /// Takes the delegations applied by transactions committed since the journal was finalized
+    /// or the delegations were taken, in the order of application.
+    ///
+    /// Journals that don't track delegations return an empty list.
+    fn take_applied_delegations(&mut self) -> Vec<AppliedDelegation> {
+        Vec::new()
+    }
+
+    /// Starts recording an [`AccessLog`] of every transaction committed from now on.
+    ///
+    /// Journals that don't record access logs ignore it.
+    fn start_access_log(&mut self) {}
+
+    /// Takes the access logs of transactions committed since the recording was started or the
+    /// logs were taken, in the order of the transactions.
+    ///
+    /// Journals that don't record access logs return an empty list.
+    fn take_access_logs(&mut self) -> Vec<AccessLog> {
+        Vec::new()
+    }
+
     /// Commit current transaction journal and returns transaction logs.
     fn commit_tx(&mut self);
 
@@ -276,6 +395,112 @@ impl<T> StateLoad<T> {
     }
 }
 
+/// Delegation applied by an EIP-7702 authorization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AppliedDelegation {
+    /// Account that signed the authorization.
+    pub authority: Address,
+    /// Account the code is delegated to, zero address if the delegation is cleared.
+    pub delegate: Address,
+}
+
+impl AppliedDelegation {
+    /// Returns `true` if the authorization cleared the delegation of the authority.
+    pub fn is_cleared(&self) -> bool {
+        self.delegate.is_zero()
+    }
+}
+
+/// Accounts and storage slots accessed by a transaction, in the order of the first access.
+///
+/// Every account and slot is listed once, with the cold flag of its first access and whether
+/// the transaction wrote it. Accesses of reverted calls are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessLog {
+    /// Accessed accounts.
+    pub accounts: Vec<AccountAccess>,
+}
+
+impl AccessLog {
+    /// Returns `true` if no account was accessed.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Returns the access of the account.
+    pub fn account(&self, address: Address) -> Option<&AccountAccess> {
+        self.accounts
+            .iter()
+            .find(|account| account.address == address)
+    }
+
+    /// Returns the number of accessed storage slots.
+    pub fn slot_count(&self) -> usize {
+        self.accounts
+            .iter()
+            .map(|account| account.slots.len())
+            .sum()
+    }
+
+    /// Returns `true` if one of the transactions writes an account or slot that the other one
+    /// accesses, so their order matters.
+    pub fn conflicts_with(&self, other: &AccessLog) -> bool {
+        self.writes_accessed_by(other) || other.writes_accessed_by(self)
+    }
+
+    fn writes_accessed_by(&self, other: &AccessLog) -> bool {
+        let mut written_accounts: HashSet<Address> = HashSet::default();
+        let mut written_slots: HashSet<(Address, StorageKey)> = HashSet::default();
+        for account in &self.accounts {
+            if account.written {
+                written_accounts.insert(account.address);
+            }
+            written_slots.extend(
+                account
+                    .slots
+                    .iter()
+                    .filter(|slot| slot.written)
+                    .map(|slot| (account.address, slot.key)),
+            );
+        }
+        other.accounts.iter().any(|account| {
+            written_accounts.contains(&account.address)
+                || account
+                    .slots
+                    .iter()
+                    .any(|slot| written_slots.contains(&(account.address, slot.key)))
+        })
+    }
+}
+
+/// Access of a transaction to an account, see [`AccessLog`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountAccess {
+    /// Address of the account.
+    pub address: Address,
+    /// Whether the first access was cold.
+    pub cold: bool,
+    /// Whether the balance, nonce or code of the account was changed.
+    pub written: bool,
+    /// Accessed storage slots of the account, in the order of the first access.
+    pub slots: Vec<SlotAccess>,
+}
+
+/// Access of a transaction to a storage slot, see [`AccessLog`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotAccess {
+    /// Key of the slot.
+    pub key: StorageKey,
+    /// Whether the first access was cold.
+    pub cold: bool,
+    /// Whether the value of the slot was changed.
+    pub written: bool,
+}
+
 /// Result of the account load from Journal state
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
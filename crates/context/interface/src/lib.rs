@@ -7,6 +7,7 @@ extern crate alloc as std;
 
 pub mod block;
 pub mod cfg;
+pub mod chain;
 pub mod context;
 pub mod host;
 pub mod journaled_state;
@@ -16,10 +17,11 @@ pub mod transaction;
 
 pub use block::Block;
 pub use cfg::{Cfg, CreateScheme, TransactTo};
+pub use chain::ChainData;
 pub use context::{ContextError, ContextSetters, ContextTr};
 pub use database_interface::{DBErrorMarker, Database};
 pub use either;
 pub use host::{DummyHost, Host};
 pub use journaled_state::JournalTr;
-pub use local::{FrameStack, FrameToken, LocalContextTr, OutFrame};
+pub use local::{CallFrameInfo, FrameStack, FrameToken, KeccakCache, LocalContextTr, OutFrame};
 pub use transaction::{Transaction, TransactionType};
@@ -1,8 +1,11 @@
 //! Local context trait [`LocalContextTr`] and related types.
+use crate::result::CreateFailure;
 use core::{
     cell::{Ref, RefCell},
+    hash::{Hash, Hasher},
     ops::Range,
 };
+use primitives::{keccak256, Address, HashMap, B256};
 use std::{rc::Rc, vec::Vec};
 
 /// Non-empty, item-pooling Vec.
@@ -85,6 +88,15 @@ impl<T> FrameStack<T> {
         unsafe { &mut *self.stack.as_mut_ptr().add(self.index.unwrap()) }
     }
 
+    /// Returns the current item and the initialized item after it.
+    ///
+    /// After [`pop`][FrameStack::pop] the item after the current one is the popped item.
+    #[inline]
+    pub fn get_with_next(&mut self) -> (&mut T, Option<&mut T>) {
+        let (current, next) = self.stack.split_at_mut(self.index.unwrap() + 1);
+        (current.last_mut().unwrap(), next.first_mut())
+    }
+
     /// Get next uninitialized item.
     #[inline]
     pub fn get_next(&mut self) -> OutFrame<'_, T> {
@@ -177,6 +189,132 @@ impl FrameToken {
     }
 }
 
+/// Frame of the running call stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallFrameInfo {
+    /// Address whose state is used by the frame, created address for create frames.
+    pub address: Address,
+    /// Caller of the frame.
+    pub caller: Address,
+    /// Gas limit of the frame.
+    pub gas_limit: u64,
+    /// Remaining gas of the frame.
+    ///
+    /// Equal to the gas limit while the frame has not called other frames. Afterwards it is
+    /// the gas remaining after the gas of the last call or create was deducted.
+    pub remaining_gas: u64,
+}
+
+impl CallFrameInfo {
+    /// Creates a new frame info with all gas remaining.
+    pub const fn new(address: Address, caller: Address, gas_limit: u64) -> Self {
+        Self {
+            address,
+            caller,
+            gas_limit,
+            remaining_gas: gas_limit,
+        }
+    }
+}
+
+/// Cache of hashes computed by the KECCAK256 instruction, keyed by the hashed bytes.
+///
+/// Loops that compute mapping slots often hash the same key and slot many times. Only inputs
+/// up to [`KeccakCache::MAX_INPUT_LEN`] bytes are cached, the cache is emptied when it is full.
+///
+/// Hashes are computed with [`keccak256`], the `asm-keccak` feature enables the assembly
+/// implementation on supported targets. Keys are stored inline, a miss does not allocate once the map has grown to its capacity.
+#[derive(Clone, Debug, Default)]
+pub struct KeccakCache {
+    hashes: HashMap<KeccakCacheKey, B256>,
+}
+
+impl KeccakCache {
+    /// Maximum length of a cached input, longer inputs are always hashed.
+    pub const MAX_INPUT_LEN: usize = 128;
+
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached hashes.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if no hash is cached.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Removes all cached hashes.
+    pub fn clear(&mut self) {
+        self.hashes.clear();
+    }
+
+    /// Returns the keccak256 hash of the input, caching at most `capacity` hashes.
+    pub fn hash(&mut self, input: &[u8], capacity: usize) -> B256 {
+        if input.len() > Self::MAX_INPUT_LEN || capacity == 0 {
+            return keccak256(input);
+        }
+        if let Some(hash) = self.hashes.get(input) {
+            return *hash;
+        }
+        if self.hashes.len() >= capacity {
+            self.hashes.clear();
+        }
+        let hash = keccak256(input);
+        self.hashes.insert(KeccakCacheKey::new(input), hash);
+        hash
+    }
+}
+
+/// Input of a cached hash, stored inline up to [`KeccakCache::MAX_INPUT_LEN`] bytes.
+///
+/// Hashes and compares as the input slice so the map can be queried with `&[u8]`.
+#[derive(Clone, Debug)]
+struct KeccakCacheKey {
+    len: u8,
+    bytes: [u8; KeccakCache::MAX_INPUT_LEN],
+}
+
+impl KeccakCacheKey {
+    fn new(input: &[u8]) -> Self {
+        let mut bytes = [0; KeccakCache::MAX_INPUT_LEN];
+        bytes[..input.len()].copy_from_slice(input);
+        Self {
+            len: input.len() as u8,
+            bytes,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl PartialEq for KeccakCacheKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for KeccakCacheKey {}
+
+impl Hash for KeccakCacheKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl core::borrow::Borrow<[u8]> for KeccakCacheKey {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 /// Local context used for caching initcode from Initcode transactions.
 pub trait LocalContextTr {
     /// Interpreter shared memory buffer. A reused memory buffer for calls.
@@ -193,12 +331,99 @@ pub trait LocalContextTr {
 
     /// Clear the local context.
     fn clear(&mut self);
+
+    /// Returns frames of the running call stack, the last one is the active frame.
+    ///
+    /// Precompiles are pushed as frames while they run. Returns empty slice if the call
+    /// stack is not recorded.
+    fn call_stack(&self) -> &[CallFrameInfo] {
+        &[]
+    }
+
+    /// Pushes a frame to the call stack.
+    fn push_call_frame(&mut self, frame: CallFrameInfo) {
+        let _ = frame;
+    }
+
+    /// Pops the active frame from the call stack.
+    fn pop_call_frame(&mut self) {}
+
+    /// Sets remaining gas of the active frame.
+    fn set_remaining_gas(&mut self, remaining_gas: u64) {
+        let _ = remaining_gas;
+    }
+
+    /// Returns the current call depth, the transaction frame has depth one.
+    fn call_depth(&self) -> usize {
+        self.call_stack().len()
+    }
+
+    /// Returns the active frame.
+    fn active_frame(&self) -> Option<&CallFrameInfo> {
+        self.call_stack().last()
+    }
+
+    /// Returns addresses of the running call stack, starting from the transaction target.
+    fn call_stack_addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        self.call_stack().iter().map(|frame| frame.address)
+    }
+
+    /// Returns the cache of the KECCAK256 instruction, `None` if hashes are not cached.
+    fn keccak_cache(&mut self) -> Option<&mut KeccakCache> {
+        None
+    }
+
+    /// Starts or stops recording contract creations that failed, stopping drops the failures
+    /// that were not taken.
+    fn set_record_create_failures(&mut self, record: bool) {
+        let _ = record;
+    }
+
+    /// Returns `true` if contract creations that failed are recorded.
+    fn records_create_failures(&self) -> bool {
+        false
+    }
+
+    /// Records a contract creation that failed, ignored if recording is not enabled.
+    fn record_create_failure(&mut self, failure: CreateFailure) {
+        let _ = failure;
+    }
+
+    /// Returns the contract creations that failed in the transaction, empty if they are not
+    /// recorded.
+    fn take_create_failures(&mut self) -> Vec<CreateFailure> {
+        Vec::new()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn keccak_cache() {
+        let mut cache = KeccakCache::new();
+        assert_eq!(cache.hash(&[1], 2), keccak256([1]));
+        assert_eq!(cache.hash(&[1], 2), keccak256([1]));
+        assert_eq!(cache.hash(&[2], 2), keccak256([2]));
+        assert_eq!(cache.len(), 2);
+        // Full cache is emptied.
+        assert_eq!(cache.hash(&[3], 2), keccak256([3]));
+        assert_eq!(cache.len(), 1);
+        // Long inputs and disabled cache are not cached.
+        let long = [0u8; KeccakCache::MAX_INPUT_LEN + 1];
+        assert_eq!(cache.hash(&long, 2), keccak256(long));
+        assert_eq!(cache.hash(&[4], 0), keccak256([4]));
+        assert_eq!(cache.len(), 1);
+        // Inputs that differ only in length are different keys.
+        assert_eq!(cache.hash(&[3, 0], 4), keccak256([3, 0]));
+        assert_eq!(cache.len(), 2);
+        // Input of the maximum length is cached.
+        let max = [5u8; KeccakCache::MAX_INPUT_LEN];
+        assert_eq!(cache.hash(&max, 4), keccak256(max));
+        assert_eq!(cache.len(), 3);
+    }
+
     #[test]
     fn frame_stack() {
         let mut stack = FrameStack::new();
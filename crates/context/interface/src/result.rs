@@ -7,10 +7,25 @@
 //! [`InvalidHeader`] is the error that is returned when the header is invalid.
 //!
 //! [`SuccessReason`] is the reason that the transaction successfully completed.
+//!
+//! All errors have stable numeric codes that don't change between releases and can be used
+//! instead of matching on `Display` output. Codes are grouped in ranges:
+//! * `1000..2000` [`InvalidTransaction`],
+//! * `2000..3000` [`InvalidHeader`],
+//! * `3000..4000` [`HaltReason`],
+//! * `4000..5000` precompile errors,
+//! * `5000..6000` bytecode decode errors,
+//! * `6000..7000` database and custom [`EVMError`]s.
+//!
+//! [`diff_results`] compares two results and their state.
+pub mod diff;
+
+pub use diff::{diff_results, ResultDiff};
+
 use crate::{context::ContextError, transaction::TransactionError};
 use core::fmt::{self, Debug};
-use database_interface::DBErrorMarker;
-use primitives::{Address, Bytes, Log, U256};
+use database_interface::{DBErrorMarker, SlowRead};
+use primitives::{eip7702, Address, Bytes, Log, B256, I256, U256};
 use state::EvmState;
 use std::{boxed::Box, string::String, vec::Vec};
 
@@ -27,6 +42,21 @@ pub struct ExecResultAndState<R, S = EvmState> {
     pub result: R,
     /// Output State.
     pub state: S,
+    /// Balance changes caused by the transaction, only set if requested.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub balance_changes: Option<BalanceChanges>,
+    /// Breakdown of the gas used by the transaction, only set if requested.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_breakdown: Option<GasBreakdown>,
+    /// Accounts destructed by the transaction, only set if requested.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub destructed_accounts: Option<Vec<DestructedAccount>>,
+    /// Contract creations that failed in the transaction, only set if requested.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub create_failures: Option<Vec<CreateFailure>>,
+    /// Slowest database reads of the transaction, only set if requested.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slow_reads: Option<Vec<SlowRead>>,
 }
 
 /// Type alias for backwards compatibility.
@@ -38,7 +68,286 @@ pub type ResultVecAndState<R, S> = ExecResultAndState<Vec<R>, S>;
 impl<R, S> ExecResultAndState<R, S> {
     /// Creates new ResultAndState.
     pub fn new(result: R, state: S) -> Self {
-        Self { result, state }
+        Self {
+            result,
+            state,
+            balance_changes: None,
+            gas_breakdown: None,
+            destructed_accounts: None,
+            create_failures: None,
+            slow_reads: None,
+        }
+    }
+
+    /// Sets the balance changes.
+    pub fn with_balance_changes(mut self, balance_changes: BalanceChanges) -> Self {
+        self.balance_changes = Some(balance_changes);
+        self
+    }
+
+    /// Sets the gas breakdown.
+    pub fn with_gas_breakdown(mut self, gas_breakdown: GasBreakdown) -> Self {
+        self.gas_breakdown = Some(gas_breakdown);
+        self
+    }
+
+    /// Sets the destructed accounts.
+    pub fn with_destructed_accounts(mut self, destructed_accounts: Vec<DestructedAccount>) -> Self {
+        self.destructed_accounts = Some(destructed_accounts);
+        self
+    }
+
+    /// Sets the failed contract creations.
+    pub fn with_create_failures(mut self, create_failures: Vec<CreateFailure>) -> Self {
+        self.create_failures = Some(create_failures);
+        self
+    }
+
+    /// Sets the slowest database reads.
+    pub fn with_slow_reads(mut self, slow_reads: Vec<SlowRead>) -> Self {
+        self.slow_reads = Some(slow_reads);
+        self
+    }
+}
+
+/// Contract creation by a transaction or a `CREATE`/`CREATE2` instruction that failed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateFailure {
+    /// Account that created the contract.
+    pub caller: Address,
+    /// Address of the contract, `None` if the creation failed before it was derived.
+    pub address: Option<Address>,
+    /// Depth of the creation frame, the transaction frame has depth zero.
+    pub depth: usize,
+    /// Rule that failed.
+    pub reason: CreateFailureReason,
+}
+
+/// Rule that failed a contract creation, see [`CreateFailure`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CreateFailureReason {
+    /// Call depth limit is reached.
+    CallTooDeep,
+    /// Balance of the caller is lower than the value sent to the contract.
+    InsufficientBalance {
+        /// Balance of the caller.
+        balance: U256,
+        /// Value sent to the contract.
+        value: U256,
+    },
+    /// Nonce of the caller can't be increased.
+    NonceOverflow,
+    /// Account at the address already has code or a nonce.
+    Collision {
+        /// Nonce of the existing account.
+        nonce: u64,
+        /// Code hash of the existing account.
+        code_hash: B256,
+    },
+    /// Initcode reverted.
+    Reverted {
+        /// Output of the initcode.
+        output: Bytes,
+    },
+    /// Initcode halted.
+    Halted {
+        /// Reason of the halt.
+        reason: HaltReason,
+    },
+    /// Deployed code starts with the `0xEF` byte,
+    /// [EIP-3541](https://eips.ethereum.org/EIPS/eip-3541).
+    StartsWithEF,
+    /// Deployed code is larger than the code size limit,
+    /// [EIP-170](https://eips.ethereum.org/EIPS/eip-170).
+    CodeSizeLimit {
+        /// Size of the deployed code.
+        size: usize,
+        /// Maximum code size.
+        limit: usize,
+    },
+    /// Remaining gas does not cover the code deposit cost.
+    CodeDepositOutOfGas {
+        /// Code deposit cost.
+        cost: u64,
+        /// Gas remaining after the initcode.
+        remaining: u64,
+    },
+}
+
+impl fmt::Display for CreateFailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CallTooDeep => write!(f, "call depth limit reached"),
+            Self::InsufficientBalance { balance, value } => {
+                write!(f, "caller balance {balance} is lower than value {value}")
+            }
+            Self::NonceOverflow => write!(f, "caller nonce overflow"),
+            Self::Collision { nonce, code_hash } => write!(
+                f,
+                "address is used by an account with nonce {nonce} and code hash {code_hash}"
+            ),
+            Self::Reverted { output } => write!(f, "initcode reverted with {output}"),
+            Self::Halted { reason } => write!(f, "initcode halted: {reason:?}"),
+            Self::StartsWithEF => write!(f, "deployed code starts with 0xEF"),
+            Self::CodeSizeLimit { size, limit } => {
+                write!(f, "deployed code size {size} exceeds limit {limit}")
+            }
+            Self::CodeDepositOutOfGas { cost, remaining } => write!(
+                f,
+                "code deposit cost {cost} exceeds remaining gas {remaining}"
+            ),
+        }
+    }
+}
+
+/// Account destructed by `SELFDESTRUCT`.
+///
+/// After Cancun ([EIP-6780](https://eips.ethereum.org/EIPS/eip-6780)) only accounts created in
+/// the same transaction are destructed, `SELFDESTRUCT` of other accounts only moves the balance
+/// and is not reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DestructedAccount {
+    /// Address of the destructed account.
+    pub address: Address,
+    /// Account that received the balance.
+    pub target: Address,
+    /// Balance of the account when it was destructed.
+    pub balance: U256,
+}
+
+impl DestructedAccount {
+    /// Returns `true` if the balance was moved to another account.
+    pub fn is_swept(&self) -> bool {
+        self.address != self.target && !self.balance.is_zero()
+    }
+
+    /// Returns `true` if the balance was burned because the account was its own target.
+    pub fn is_burned(&self) -> bool {
+        self.address == self.target && !self.balance.is_zero()
+    }
+}
+
+/// Parts of the gas used by a transaction.
+///
+/// Gas used is `intrinsic_gas + execution_gas - refunded_gas + floor_gas_adjustment`, blob gas is
+/// paid with the blob gas price and is not part of it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasBreakdown {
+    /// Intrinsic gas charged before execution, including the calldata, access list and
+    /// authorization list costs.
+    pub intrinsic_gas: u64,
+    /// Gas spent by the execution, all remaining gas if the transaction halted.
+    pub execution_gas: u64,
+    /// Gas refunded after the refund cap, including the refund of EIP-7702 authorizations.
+    pub refunded_gas: u64,
+    /// Gas added to reach the calldata floor cost of
+    /// [EIP-7623](https://eips.ethereum.org/EIPS/eip-7623), refunds are dropped if it is
+    /// applied.
+    pub floor_gas_adjustment: u64,
+    /// Blob gas of the transaction, [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+    pub blob_gas: u64,
+}
+
+impl GasBreakdown {
+    /// Returns the gas used by the transaction.
+    pub const fn gas_used(&self) -> u64 {
+        self.intrinsic_gas + self.execution_gas - self.refunded_gas + self.floor_gas_adjustment
+    }
+}
+
+/// Balance of an account before and after a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BalanceChange {
+    /// Address of the account.
+    pub address: Address,
+    /// Balance before the transaction.
+    pub before: U256,
+    /// Balance after the transaction.
+    pub after: U256,
+}
+
+impl BalanceChange {
+    /// Returns the signed balance difference.
+    pub fn delta(&self) -> I256 {
+        I256::from_raw(self.after.wrapping_sub(self.before))
+    }
+
+    /// Returns `true` if the balance did not change.
+    pub fn is_unchanged(&self) -> bool {
+        self.before == self.after
+    }
+}
+
+/// Summary of balance changes caused by a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BalanceChanges {
+    /// Caller of the transaction, `None` if it is not in the state.
+    pub sender: Option<BalanceChange>,
+    /// Called or created account, `None` if it is not in the state.
+    pub recipient: Option<BalanceChange>,
+    /// Block beneficiary, `None` if it is not in the state.
+    pub coinbase: Option<BalanceChange>,
+    /// Other accounts with changed balance, sorted by address.
+    pub others: Vec<BalanceChange>,
+}
+
+impl BalanceChanges {
+    /// Creates the summary from the changed state.
+    ///
+    /// `balance_before` returns the balance of the account before the transaction.
+    pub fn new<E>(
+        state: &EvmState,
+        sender: Address,
+        recipient: Option<Address>,
+        coinbase: Address,
+        mut balance_before: impl FnMut(Address) -> Result<U256, E>,
+    ) -> Result<Self, E> {
+        let mut change = |address: Address| -> Result<Option<BalanceChange>, E> {
+            let Some(account) = state.get(&address) else {
+                return Ok(None);
+            };
+            Ok(Some(BalanceChange {
+                address,
+                before: balance_before(address)?,
+                after: account.info.balance,
+            }))
+        };
+        let mut changes = Self {
+            sender: change(sender)?,
+            recipient: recipient.map(&mut change).transpose()?.flatten(),
+            coinbase: change(coinbase)?,
+            others: Vec::new(),
+        };
+        for address in state.keys() {
+            if *address == sender || Some(*address) == recipient || *address == coinbase {
+                continue;
+            }
+            if let Some(change) = change(*address)?.filter(|change| !change.is_unchanged()) {
+                changes.others.push(change);
+            }
+        }
+        changes.others.sort_by_key(|change| change.address);
+        Ok(changes)
+    }
+
+    /// Returns the change of the account.
+    pub fn get(&self, address: Address) -> Option<&BalanceChange> {
+        self.iter().find(|change| change.address == address)
+    }
+
+    /// Returns all changes, starting with sender, recipient and coinbase.
+    pub fn iter(&self) -> impl Iterator<Item = &BalanceChange> {
+        self.sender
+            .iter()
+            .chain(self.recipient.iter())
+            .chain(self.coinbase.iter())
+            .chain(self.others.iter())
     }
 }
 
@@ -278,6 +587,22 @@ impl<DBError, TransactionValidationErrorT> EVMError<DBError, TransactionValidati
     }
 }
 
+impl<DBError, TransactionValidationErrorT: TransactionError>
+    EVMError<DBError, TransactionValidationErrorT>
+{
+    /// Returns stable numeric code of the error.
+    ///
+    /// Transaction and header errors return the code of the inner error.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::Transaction(e) => e.code(),
+            Self::Header(e) => e.code(),
+            Self::Database(_) => 6001,
+            Self::Custom(_) => 6002,
+        }
+    }
+}
+
 impl<DBError, TransactionValidationErrorT> core::error::Error
     for EVMError<DBError, TransactionValidationErrorT>
 where
@@ -353,8 +678,40 @@ pub enum InvalidTransaction {
         /// Gas limit for the transaction.
         gas_limit: u64,
     },
+    /// Initial gas without the EIP-7702 authorization list fits in the gas limit, but the
+    /// authorizations don't.
+    ///
+    /// Returned instead of [`InvalidTransaction::CallGasCostMoreThanGasLimit`] so that the
+    /// authorization that exceeded the limit is known.
+    AuthorizationListGasMoreThanGasLimit {
+        /// Initial gas for a Call, including the authorization list.
+        initial_gas: u64,
+        /// Gas limit for the transaction.
+        gas_limit: u64,
+        /// Gas accounting of the authorization list.
+        accounting: Box<AuthorizationListGas>,
+    },
     /// EIP-3607 Reject transactions from senders with deployed code
     RejectCallerWithCode,
+    /// Sender is an EIP-7702 delegated account, rejected by [`CallerCodePolicy::Strict`].
+    ///
+    /// [`CallerCodePolicy::Strict`]: crate::cfg::CallerCodePolicy::Strict
+    RejectDelegatedCaller,
+    /// Blob sidecar is required by [`BlobSidecarMode::Verify`] but was not provided.
+    ///
+    /// [`BlobSidecarMode::Verify`]: crate::cfg::BlobSidecarMode::Verify
+    MissingBlobSidecar,
+    /// Number of blobs, commitments and proofs in the sidecar does not match the number of
+    /// versioned hashes, or a commitment does not match its versioned hash.
+    BlobSidecarMismatch {
+        /// Index of the mismatched blob, `None` if the counts do not match.
+        index: Option<usize>,
+    },
+    /// KZG proof of a blob in the sidecar is invalid.
+    InvalidBlobKzgProof {
+        /// Index of the blob.
+        index: usize,
+    },
     /// Transaction account does not have enough amount of ether to cover transferred value and gas_limit*gas_price.
     LackOfFundForMaxFee {
         /// Fee for the transaction.
@@ -436,7 +793,56 @@ pub enum InvalidTransaction {
     Eip7873MissingTarget,
 }
 
-impl TransactionError for InvalidTransaction {}
+impl InvalidTransaction {
+    /// Returns stable numeric code of the error, in `1000..2000` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::PriorityFeeGreaterThanMaxFee => 1001,
+            Self::GasPriceLessThanBasefee => 1002,
+            Self::CallerGasLimitMoreThanBlock => 1003,
+            Self::CallGasCostMoreThanGasLimit { .. } => 1004,
+            Self::GasFloorMoreThanGasLimit { .. } => 1005,
+            Self::RejectCallerWithCode => 1006,
+            Self::LackOfFundForMaxFee { .. } => 1007,
+            Self::OverflowPaymentInTransaction => 1008,
+            Self::NonceOverflowInTransaction => 1009,
+            Self::NonceTooHigh { .. } => 1010,
+            Self::NonceTooLow { .. } => 1011,
+            Self::CreateInitCodeSizeLimit => 1012,
+            Self::InvalidChainId => 1013,
+            Self::MissingChainId => 1014,
+            Self::TxGasLimitGreaterThanCap { .. } => 1015,
+            Self::AccessListNotSupported => 1016,
+            Self::MaxFeePerBlobGasNotSupported => 1017,
+            Self::BlobVersionedHashesNotSupported => 1018,
+            Self::BlobGasPriceGreaterThanMax => 1019,
+            Self::EmptyBlobs => 1020,
+            Self::BlobCreateTransaction => 1021,
+            Self::TooManyBlobs { .. } => 1022,
+            Self::BlobVersionNotSupported => 1023,
+            Self::AuthorizationListNotSupported => 1024,
+            Self::AuthorizationListInvalidFields => 1025,
+            Self::EmptyAuthorizationList => 1026,
+            Self::Eip2930NotSupported => 1027,
+            Self::Eip1559NotSupported => 1028,
+            Self::Eip4844NotSupported => 1029,
+            Self::Eip7702NotSupported => 1030,
+            Self::Eip7873NotSupported => 1031,
+            Self::Eip7873MissingTarget => 1032,
+            Self::RejectDelegatedCaller => 1033,
+            Self::MissingBlobSidecar => 1034,
+            Self::BlobSidecarMismatch { .. } => 1035,
+            Self::InvalidBlobKzgProof { .. } => 1036,
+            Self::AuthorizationListGasMoreThanGasLimit { .. } => 1037,
+        }
+    }
+}
+
+impl TransactionError for InvalidTransaction {
+    fn code(&self) -> u32 {
+        InvalidTransaction::code(self)
+    }
+}
 
 impl core::error::Error for InvalidTransaction {}
 
@@ -476,9 +882,42 @@ impl fmt::Display for InvalidTransaction {
                     "gas floor ({gas_floor}) exceeds the gas limit ({gas_limit})"
                 )
             }
+            Self::AuthorizationListGasMoreThanGasLimit {
+                initial_gas,
+                gas_limit,
+                accounting,
+            } => {
+                write!(
+                    f,
+                    "call gas cost ({initial_gas}) exceeds the gas limit ({gas_limit}) at authorization {} of {}",
+                    accounting.exceeding_index, accounting.authorizations
+                )
+            }
             Self::RejectCallerWithCode => {
                 write!(f, "reject transactions from senders with deployed code")
             }
+            Self::MissingBlobSidecar => write!(f, "blob sidecar is required but not provided"),
+            Self::BlobSidecarMismatch { index: None } => {
+                write!(
+                    f,
+                    "blob sidecar does not match the number of versioned hashes"
+                )
+            }
+            Self::BlobSidecarMismatch { index: Some(index) } => {
+                write!(
+                    f,
+                    "commitment of blob {index} does not match its versioned hash"
+                )
+            }
+            Self::InvalidBlobKzgProof { index } => {
+                write!(f, "invalid KZG proof of blob {index}")
+            }
+            Self::RejectDelegatedCaller => {
+                write!(
+                    f,
+                    "reject transactions from EIP-7702 delegated senders by strict caller code policy"
+                )
+            }
             Self::LackOfFundForMaxFee { fee, balance } => {
                 write!(f, "lack of funds ({balance}) for max fee ({fee})")
             }
@@ -532,6 +971,49 @@ impl fmt::Display for InvalidTransaction {
     }
 }
 
+/// Intrinsic gas accounting of the EIP-7702 authorization list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorizationListGas {
+    /// Initial gas of the transaction without the authorization list.
+    pub base_gas: u64,
+    /// Number of authorizations.
+    pub authorizations: u64,
+    /// Gas charged for every authorization, [`eip7702::PER_EMPTY_ACCOUNT_COST`].
+    pub cost_per_authorization: u64,
+    /// Gas refunded for every authorization whose authority account already exists.
+    ///
+    /// Refund is known only when the authorization is applied and doesn't lower the initial gas.
+    pub existing_authority_refund: u64,
+    /// Index of the first authorization that doesn't fit in the gas limit.
+    pub exceeding_index: u64,
+}
+
+impl AuthorizationListGas {
+    /// Creates the accounting of `authorizations` authorizations on top of the `base_gas`.
+    pub const fn new(base_gas: u64, authorizations: u64, gas_limit: u64) -> Self {
+        let cost_per_authorization = eip7702::PER_EMPTY_ACCOUNT_COST;
+        Self {
+            base_gas,
+            authorizations,
+            cost_per_authorization,
+            existing_authority_refund: eip7702::PER_EMPTY_ACCOUNT_COST
+                - eip7702::PER_AUTH_BASE_COST,
+            exceeding_index: gas_limit.saturating_sub(base_gas) / cost_per_authorization,
+        }
+    }
+
+    /// Returns the gas charged for all authorizations.
+    pub const fn authorization_gas(&self) -> u64 {
+        self.authorizations * self.cost_per_authorization
+    }
+
+    /// Returns the maximum refund, when the authority accounts of all authorizations exist.
+    pub const fn max_refund(&self) -> u64 {
+        self.authorizations * self.existing_authority_refund
+    }
+}
+
 /// Errors related to misconfiguration of a [`crate::Block`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -542,6 +1024,16 @@ pub enum InvalidHeader {
     ExcessBlobGasNotSet,
 }
 
+impl InvalidHeader {
+    /// Returns stable numeric code of the error, in `2000..3000` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::PrevrandaoNotSet => 2001,
+            Self::ExcessBlobGasNotSet => 2002,
+        }
+    }
+}
+
 impl core::error::Error for InvalidHeader {}
 
 impl fmt::Display for InvalidHeader {
@@ -611,6 +1103,10 @@ pub enum HaltReason {
     OutOfFunds,
     /// Call is too deep.
     CallTooDeep,
+
+    // New variants are added at the end to keep the variant indices stable.
+    /// Opcode is disabled by [`Cfg::disabled_opcodes`](crate::Cfg::disabled_opcodes).
+    OpcodeDisabled,
 }
 
 /// Out of gas errors.
@@ -631,3 +1127,213 @@ pub enum OutOfGasError {
     /// When performing SSTORE the gasleft is less than or equal to 2300
     ReentrancySentry,
 }
+
+impl HaltReason {
+    /// Returns stable numeric code of the halt reason, in `3000..4000` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::OutOfGas(e) => e.code(),
+            Self::OpcodeNotFound => 3002,
+            Self::InvalidFEOpcode => 3003,
+            Self::InvalidJump => 3004,
+            Self::NotActivated => 3005,
+            Self::StackUnderflow => 3006,
+            Self::StackOverflow => 3007,
+            Self::OutOfOffset => 3008,
+            Self::CreateCollision => 3009,
+            Self::PrecompileError => 3010,
+            Self::NonceOverflow => 3011,
+            Self::CreateContractSizeLimit => 3012,
+            Self::CreateContractStartingWithEF => 3013,
+            Self::CreateInitCodeSizeLimit => 3014,
+            Self::OverflowPayment => 3015,
+            Self::StateChangeDuringStaticCall => 3016,
+            Self::CallNotAllowedInsideStatic => 3017,
+            Self::OutOfFunds => 3018,
+            Self::CallTooDeep => 3019,
+            Self::OpcodeDisabled => 3020,
+        }
+    }
+}
+
+impl OutOfGasError {
+    /// Returns stable numeric code of the out of gas error, in `3100..3200` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Basic => 3101,
+            Self::MemoryLimit => 3102,
+            Self::Memory => 3103,
+            Self::Precompile => 3104,
+            Self::InvalidOperand => 3105,
+            Self::ReentrancySentry => 3106,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_transactions() -> [(InvalidTransaction, u32); 37] {
+        use InvalidTransaction::*;
+        [
+            (PriorityFeeGreaterThanMaxFee, 1001),
+            (GasPriceLessThanBasefee, 1002),
+            (CallerGasLimitMoreThanBlock, 1003),
+            (
+                CallGasCostMoreThanGasLimit {
+                    initial_gas: 2,
+                    gas_limit: 1,
+                },
+                1004,
+            ),
+            (
+                GasFloorMoreThanGasLimit {
+                    gas_floor: 2,
+                    gas_limit: 1,
+                },
+                1005,
+            ),
+            (RejectCallerWithCode, 1006),
+            (
+                LackOfFundForMaxFee {
+                    fee: Box::new(U256::from(2)),
+                    balance: Box::new(U256::from(1)),
+                },
+                1007,
+            ),
+            (OverflowPaymentInTransaction, 1008),
+            (NonceOverflowInTransaction, 1009),
+            (NonceTooHigh { tx: 2, state: 1 }, 1010),
+            (NonceTooLow { tx: 1, state: 2 }, 1011),
+            (CreateInitCodeSizeLimit, 1012),
+            (InvalidChainId, 1013),
+            (MissingChainId, 1014),
+            (
+                TxGasLimitGreaterThanCap {
+                    gas_limit: 2,
+                    cap: 1,
+                },
+                1015,
+            ),
+            (AccessListNotSupported, 1016),
+            (MaxFeePerBlobGasNotSupported, 1017),
+            (BlobVersionedHashesNotSupported, 1018),
+            (BlobGasPriceGreaterThanMax, 1019),
+            (EmptyBlobs, 1020),
+            (BlobCreateTransaction, 1021),
+            (TooManyBlobs { max: 1, have: 2 }, 1022),
+            (BlobVersionNotSupported, 1023),
+            (AuthorizationListNotSupported, 1024),
+            (AuthorizationListInvalidFields, 1025),
+            (EmptyAuthorizationList, 1026),
+            (Eip2930NotSupported, 1027),
+            (Eip1559NotSupported, 1028),
+            (Eip4844NotSupported, 1029),
+            (Eip7702NotSupported, 1030),
+            (Eip7873NotSupported, 1031),
+            (Eip7873MissingTarget, 1032),
+            (RejectDelegatedCaller, 1033),
+            (MissingBlobSidecar, 1034),
+            (BlobSidecarMismatch { index: Some(1) }, 1035),
+            (InvalidBlobKzgProof { index: 1 }, 1036),
+            (
+                AuthorizationListGasMoreThanGasLimit {
+                    initial_gas: 2,
+                    gas_limit: 1,
+                    accounting: Box::new(AuthorizationListGas::new(1, 1, 1)),
+                },
+                1037,
+            ),
+        ]
+    }
+
+    fn halt_reasons() -> [(HaltReason, u32); 25] {
+        use HaltReason::*;
+        [
+            (OutOfGas(OutOfGasError::Basic), 3101),
+            (OutOfGas(OutOfGasError::MemoryLimit), 3102),
+            (OutOfGas(OutOfGasError::Memory), 3103),
+            (OutOfGas(OutOfGasError::Precompile), 3104),
+            (OutOfGas(OutOfGasError::InvalidOperand), 3105),
+            (OutOfGas(OutOfGasError::ReentrancySentry), 3106),
+            (OpcodeNotFound, 3002),
+            (InvalidFEOpcode, 3003),
+            (InvalidJump, 3004),
+            (NotActivated, 3005),
+            (StackUnderflow, 3006),
+            (StackOverflow, 3007),
+            (OutOfOffset, 3008),
+            (CreateCollision, 3009),
+            (PrecompileError, 3010),
+            (NonceOverflow, 3011),
+            (CreateContractSizeLimit, 3012),
+            (CreateContractStartingWithEF, 3013),
+            (CreateInitCodeSizeLimit, 3014),
+            (OverflowPayment, 3015),
+            (StateChangeDuringStaticCall, 3016),
+            (CallNotAllowedInsideStatic, 3017),
+            (OutOfFunds, 3018),
+            (CallTooDeep, 3019),
+            (OpcodeDisabled, 3020),
+        ]
+    }
+
+    #[test]
+    fn error_codes_are_stable() {
+        let invalid_transactions = invalid_transactions();
+        for (error, code) in &invalid_transactions {
+            assert_eq!(error.code(), *code, "{error:?}");
+            assert_eq!(TransactionError::code(error), *code, "{error:?}");
+            assert_eq!(
+                EVMError::<(), InvalidTransaction>::Transaction(error.clone()).code(),
+                *code
+            );
+        }
+        for (i, (a, _)) in invalid_transactions.iter().enumerate() {
+            for (b, _) in &invalid_transactions[i + 1..] {
+                assert_ne!(a.code(), b.code(), "{a:?} and {b:?} share a code");
+            }
+        }
+
+        for (reason, code) in halt_reasons() {
+            assert_eq!(reason.code(), code, "{reason:?}");
+        }
+
+        assert_eq!(InvalidHeader::PrevrandaoNotSet.code(), 2001);
+        assert_eq!(InvalidHeader::ExcessBlobGasNotSet.code(), 2002);
+        assert_eq!(
+            EVMError::<(), InvalidTransaction>::Header(InvalidHeader::ExcessBlobGasNotSet).code(),
+            2002
+        );
+        assert_eq!(
+            EVMError::<(), InvalidTransaction>::Database(()).code(),
+            6001
+        );
+        assert_eq!(
+            EVMError::<(), InvalidTransaction>::Custom(String::new()).code(),
+            6002
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn errors_serde_roundtrip() {
+        for (error, code) in invalid_transactions() {
+            let json = serde_json::to_string(&error).unwrap();
+            let decoded: InvalidTransaction = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, error);
+            assert_eq!(decoded.code(), code);
+        }
+        for (reason, code) in halt_reasons() {
+            let json = serde_json::to_string(&reason).unwrap();
+            let decoded: HaltReason = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, reason);
+            assert_eq!(decoded.code(), code);
+        }
+        let error = EVMError::<String, InvalidTransaction>::Header(InvalidHeader::PrevrandaoNotSet);
+        let json = serde_json::to_string(&error).unwrap();
+        let decoded: EVMError<String, InvalidTransaction> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, error);
+    }
+}
@@ -0,0 +1,403 @@
+//! Differences between two execution results and their state, for differential testing.
+use super::{ExecResultAndState, ExecutionResult, HaltReason, HaltReasonTr, SuccessReason};
+use core::fmt;
+use primitives::{Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
+use state::{Account, EvmState};
+use std::{collections::BTreeSet, vec::Vec};
+
+/// Value that differs between the left and the right result.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change<T> {
+    /// Value of the left result.
+    pub left: T,
+    /// Value of the right result.
+    pub right: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// Returns the change if the values differ.
+    pub fn new(left: T, right: T) -> Option<Self> {
+        (left != right).then_some(Self { left, right })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for Change<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} != {:?}", self.left, self.right)
+    }
+}
+
+/// Outcome of an execution without its gas, output and logs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResultStatus<H = HaltReason> {
+    /// Execution succeeded.
+    Success(SuccessReason),
+    /// Execution reverted.
+    Revert,
+    /// Execution halted.
+    Halt(H),
+}
+
+impl<H: Clone> From<&ExecutionResult<H>> for ResultStatus<H> {
+    fn from(result: &ExecutionResult<H>) -> Self {
+        match result {
+            ExecutionResult::Success { reason, .. } => Self::Success(*reason),
+            ExecutionResult::Revert { .. } => Self::Revert,
+            ExecutionResult::Halt { reason, .. } => Self::Halt(reason.clone()),
+        }
+    }
+}
+
+/// Log that differs at the same position, `None` if the result has fewer logs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogDiff {
+    /// Position of the log.
+    pub index: usize,
+    /// Log of the left and the right result.
+    pub log: Change<Option<Log>>,
+}
+
+/// Storage slot changed to different values, `None` if the result did not change the slot.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotDiff {
+    /// Key of the slot.
+    pub key: StorageKey,
+    /// Value the slot was changed to by the left and the right result.
+    pub value: Change<Option<StorageValue>>,
+}
+
+/// Account touched by the results with different fields after execution.
+///
+/// Fields are `None` if the result did not touch the account.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDiff {
+    /// Address of the account.
+    pub address: Address,
+    /// Balance after execution.
+    pub balance: Option<Change<Option<U256>>>,
+    /// Nonce after execution.
+    pub nonce: Option<Change<Option<u64>>>,
+    /// Code hash after execution.
+    pub code_hash: Option<Change<Option<B256>>>,
+    /// Whether the account was selfdestructed.
+    pub selfdestructed: Option<Change<Option<bool>>>,
+    /// Slots changed to different values, ordered by key.
+    pub storage: Vec<SlotDiff>,
+}
+
+/// Differences between two execution results and their state.
+///
+/// Created by [`diff_results`], fields are `None` or empty if the results agree on them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResultDiff<H = HaltReason> {
+    /// Outcome of the execution.
+    pub status: Option<Change<ResultStatus<H>>>,
+    /// Gas used.
+    pub gas_used: Option<Change<u64>>,
+    /// Gas refunded, zero if the execution did not succeed.
+    pub gas_refunded: Option<Change<u64>>,
+    /// Output, `None` if the execution halted.
+    pub output: Option<Change<Option<Bytes>>>,
+    /// Logs that differ, ordered by position.
+    pub logs: Vec<LogDiff>,
+    /// Accounts that differ, ordered by address.
+    pub accounts: Vec<AccountDiff>,
+}
+
+impl<H> ResultDiff<H> {
+    /// Returns `true` if the results and their state are equal.
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.gas_used.is_none()
+            && self.gas_refunded.is_none()
+            && self.output.is_none()
+            && self.logs.is_empty()
+            && self.accounts.is_empty()
+    }
+
+    /// Returns `true` if only the gas used or refunded differs.
+    pub fn is_gas_only(&self) -> bool {
+        (self.gas_used.is_some() || self.gas_refunded.is_some())
+            && self.status.is_none()
+            && self.output.is_none()
+            && self.logs.is_empty()
+            && self.accounts.is_empty()
+    }
+}
+
+impl<H: fmt::Debug> fmt::Display for ResultDiff<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "results are equal");
+        }
+        if let Some(status) = &self.status {
+            writeln!(f, "status: {status}")?;
+        }
+        if let Some(gas_used) = &self.gas_used {
+            writeln!(f, "gas used: {gas_used}")?;
+        }
+        if let Some(gas_refunded) = &self.gas_refunded {
+            writeln!(f, "gas refunded: {gas_refunded}")?;
+        }
+        if let Some(output) = &self.output {
+            writeln!(f, "output: {output}")?;
+        }
+        for log in &self.logs {
+            writeln!(f, "log {}: {}", log.index, log.log)?;
+        }
+        for account in &self.accounts {
+            writeln!(f, "account {}:", account.address)?;
+            if let Some(balance) = &account.balance {
+                writeln!(f, "  balance: {balance}")?;
+            }
+            if let Some(nonce) = &account.nonce {
+                writeln!(f, "  nonce: {nonce}")?;
+            }
+            if let Some(code_hash) = &account.code_hash {
+                writeln!(f, "  code hash: {code_hash}")?;
+            }
+            if let Some(selfdestructed) = &account.selfdestructed {
+                writeln!(f, "  selfdestructed: {selfdestructed}")?;
+            }
+            for slot in &account.storage {
+                writeln!(f, "  slot {}: {}", slot.key, slot.value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares two execution results and the state they changed.
+///
+/// Only accounts touched by the execution and slots whose value changed are compared, accounts
+/// and slots that were only read are ignored.
+pub fn diff_results<H: HaltReasonTr>(
+    left: &ExecResultAndState<ExecutionResult<H>, EvmState>,
+    right: &ExecResultAndState<ExecutionResult<H>, EvmState>,
+) -> ResultDiff<H> {
+    let (left_result, right_result) = (&left.result, &right.result);
+    let gas_refunded = |result: &ExecutionResult<H>| match result {
+        ExecutionResult::Success { gas_refunded, .. } => *gas_refunded,
+        _ => 0,
+    };
+
+    let (left_logs, right_logs) = (left_result.logs(), right_result.logs());
+    let logs = (0..left_logs.len().max(right_logs.len()))
+        .filter_map(|index| {
+            Change::new(
+                left_logs.get(index).cloned(),
+                right_logs.get(index).cloned(),
+            )
+            .map(|log| LogDiff { index, log })
+        })
+        .collect();
+
+    let touched = |state: &EvmState| {
+        state
+            .iter()
+            .filter(|(_, account)| account.is_touched())
+            .map(|(address, _)| *address)
+            .collect::<BTreeSet<_>>()
+    };
+    let mut addresses = touched(&left.state);
+    addresses.extend(touched(&right.state));
+    let accounts = addresses
+        .into_iter()
+        .filter_map(|address| {
+            diff_accounts(
+                address,
+                touched_account(&left.state, &address),
+                touched_account(&right.state, &address),
+            )
+        })
+        .collect();
+
+    ResultDiff {
+        status: Change::new(left_result.into(), right_result.into()),
+        gas_used: Change::new(left_result.gas_used(), right_result.gas_used()),
+        gas_refunded: Change::new(gas_refunded(left_result), gas_refunded(right_result)),
+        output: Change::new(
+            left_result.output().cloned(),
+            right_result.output().cloned(),
+        ),
+        logs,
+        accounts,
+    }
+}
+
+fn touched_account<'a>(state: &'a EvmState, address: &Address) -> Option<&'a Account> {
+    state.get(address).filter(|account| account.is_touched())
+}
+
+fn field<T: PartialEq>(
+    left: Option<&Account>,
+    right: Option<&Account>,
+    get: impl Fn(&Account) -> T,
+) -> Option<Change<Option<T>>> {
+    Change::new(left.map(&get), right.map(&get))
+}
+
+fn diff_accounts(
+    address: Address,
+    left: Option<&Account>,
+    right: Option<&Account>,
+) -> Option<AccountDiff> {
+    let changed_slots = |account: Option<&Account>| {
+        account
+            .into_iter()
+            .flat_map(|account| account.changed_storage_slots())
+            .map(|(key, slot)| (*key, slot.present_value))
+            .collect::<Vec<_>>()
+    };
+    let (left_slots, right_slots) = (changed_slots(left), changed_slots(right));
+    let keys = left_slots
+        .iter()
+        .chain(&right_slots)
+        .map(|(key, _)| *key)
+        .collect::<BTreeSet<_>>();
+    let value = |slots: &[(StorageKey, StorageValue)], key: StorageKey| {
+        slots
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    };
+    let storage = keys
+        .into_iter()
+        .filter_map(|key| {
+            Change::new(value(&left_slots, key), value(&right_slots, key))
+                .map(|value| SlotDiff { key, value })
+        })
+        .collect::<Vec<_>>();
+
+    let diff = AccountDiff {
+        address,
+        balance: field(left, right, |account| account.info.balance),
+        nonce: field(left, right, |account| account.info.nonce),
+        code_hash: field(left, right, |account| account.info.code_hash),
+        selfdestructed: field(left, right, Account::is_selfdestructed),
+        storage,
+    };
+    (diff.balance.is_some()
+        || diff.nonce.is_some()
+        || diff.code_hash.is_some()
+        || diff.selfdestructed.is_some()
+        || !diff.storage.is_empty())
+    .then_some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Output;
+    use primitives::{address, LogData};
+    use state::{AccountInfo, EvmStorageSlot};
+
+    const ACCOUNT: Address = address!("0x0000000000000000000000000000000000000001");
+
+    fn result_and_state(
+        gas_used: u64,
+        log: u8,
+        slot: (u64, u64),
+    ) -> ExecResultAndState<ExecutionResult> {
+        let mut account = Account::from(AccountInfo::from_balance(U256::from(10)));
+        account.mark_touch();
+        account.storage.insert(
+            StorageKey::from(slot.0),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(slot.1), 0),
+        );
+        // Slot that was only read is ignored.
+        account.storage.insert(
+            StorageKey::from(100 + log),
+            EvmStorageSlot::new(U256::from(1), 0),
+        );
+        let mut state = EvmState::default();
+        state.insert(ACCOUNT, account);
+        ExecResultAndState::new(
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used,
+                gas_refunded: 0,
+                logs: vec![Log::new_unchecked(ACCOUNT, Vec::new(), vec![log].into())],
+                output: Output::Call(Bytes::new()),
+            },
+            state,
+        )
+    }
+
+    #[test]
+    fn diff_equal_results() {
+        let result = result_and_state(21_000, 1, (1, 1));
+        let diff = diff_results(&result, &result);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "results are equal");
+    }
+
+    #[test]
+    fn diff_results_and_state() {
+        let left = result_and_state(21_000, 1, (1, 1));
+        let mut right = result_and_state(22_000, 2, (2, 1));
+        right.state.get_mut(&ACCOUNT).unwrap().info.nonce = 1;
+
+        let diff = diff_results(&left, &right);
+        assert!(!diff.is_gas_only());
+        assert_eq!(diff.status, None);
+        assert_eq!(diff.gas_used, Change::new(21_000, 22_000));
+        assert_eq!(diff.logs.len(), 1);
+        assert_eq!(
+            diff.logs[0].log.right.as_ref().map(|log| &log.data),
+            Some(&LogData::new_unchecked(Vec::new(), vec![2].into()))
+        );
+
+        let account = &diff.accounts[0];
+        assert_eq!(account.address, ACCOUNT);
+        assert_eq!(account.balance, None);
+        assert_eq!(account.nonce, Change::new(Some(0), Some(1)));
+        assert_eq!(
+            account.storage,
+            vec![
+                SlotDiff {
+                    key: StorageKey::from(1),
+                    value: Change {
+                        left: Some(U256::ONE),
+                        right: None
+                    },
+                },
+                SlotDiff {
+                    key: StorageKey::from(2),
+                    value: Change {
+                        left: None,
+                        right: Some(U256::ONE)
+                    },
+                },
+            ]
+        );
+        assert!(diff.to_string().contains("gas used: 21000 != 22000"));
+
+        let mut halted = result_and_state(21_000, 1, (1, 1));
+        halted.result = ExecutionResult::Halt {
+            reason: HaltReason::OutOfGas(crate::result::OutOfGasError::Basic),
+            gas_used: 21_000,
+        };
+        let diff = diff_results(&left, &halted);
+        assert_eq!(
+            diff.status.map(|status| status.right),
+            Some(ResultStatus::Halt(HaltReason::OutOfGas(
+                crate::result::OutOfGasError::Basic
+            )))
+        );
+        assert_eq!(diff.logs.len(), 1);
+        assert_eq!(
+            diff.output,
+            Some(Change {
+                left: Some(Bytes::new()),
+                right: None
+            })
+        );
+    }
+}
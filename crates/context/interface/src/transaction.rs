@@ -1,6 +1,7 @@
 //! Transaction trait [`Transaction`] and associated types.
 mod alloy_types;
 pub mod eip2930;
+pub mod eip4844;
 pub mod eip7702;
 mod either;
 pub mod transaction_type;
@@ -10,6 +11,7 @@ pub use alloy_types::{
     SignedAuthorization,
 };
 pub use eip2930::AccessListItemTr;
+pub use eip4844::BlobSidecar;
 pub use eip7702::AuthorizationTr;
 pub use transaction_type::TransactionType;
 
@@ -20,7 +22,14 @@ use core::fmt::Debug;
 use primitives::{eip4844::GAS_PER_BLOB, Address, Bytes, TxKind, B256, U256};
 
 /// Transaction validity error types.
-pub trait TransactionError: Debug + core::error::Error {}
+pub trait TransactionError: Debug + core::error::Error {
+    /// Returns stable numeric code of the error.
+    ///
+    /// See [`crate::result`] for code ranges. Chain specific errors should wrap
+    /// [`InvalidTransaction`](crate::result::InvalidTransaction) codes and use an unused part of
+    /// the `1000..2000` range for their own variants.
+    fn code(&self) -> u32;
+}
 
 /// Main Transaction trait that abstracts and specifies all transaction currently supported by Ethereum
 ///
@@ -101,6 +110,13 @@ pub trait Transaction {
     /// Note : EIP-4844 transaction field.
     fn max_fee_per_blob_gas(&self) -> u128;
 
+    /// Returns the blobs with commitments and proofs, if provided with the transaction.
+    ///
+    /// Note : EIP-4844 transaction field, not part of the signed payload.
+    fn blob_sidecar(&self) -> Option<&BlobSidecar> {
+        None
+    }
+
     /// Total gas for all blobs. Max number of blocks is already checked
     /// so we dont need to check for overflow.
     fn total_blob_gas(&self) -> u64 {
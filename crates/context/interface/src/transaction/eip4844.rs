@@ -0,0 +1,61 @@
+//! EIP-4844 blob transaction sidecar.
+use primitives::{Bytes, FixedBytes};
+use std::vec::Vec;
+
+/// Blobs of an EIP-4844 transaction with their KZG commitments and proofs.
+///
+/// Sidecar is not part of the transaction payload, it is verified against the versioned hashes
+/// of the transaction if enabled by [`BlobSidecarMode::Verify`](crate::cfg::BlobSidecarMode::Verify).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobSidecar {
+    /// Blobs, each of them 131072 bytes long.
+    pub blobs: Vec<Bytes>,
+    /// KZG commitments of the blobs.
+    pub commitments: Vec<FixedBytes<48>>,
+    /// KZG proofs of the blobs.
+    pub proofs: Vec<FixedBytes<48>>,
+}
+
+impl BlobSidecar {
+    /// Creates a new sidecar.
+    pub fn new(
+        blobs: Vec<Bytes>,
+        commitments: Vec<FixedBytes<48>>,
+        proofs: Vec<FixedBytes<48>>,
+    ) -> Self {
+        Self {
+            blobs,
+            commitments,
+            proofs,
+        }
+    }
+
+    /// Returns the number of blobs.
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    /// Returns `true` if the sidecar contains no blobs.
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+
+    /// Iterates over blobs with their commitments and proofs.
+    ///
+    /// Returns `None` if the number of commitments or proofs does not match the number of blobs.
+    pub fn iter(
+        &self,
+    ) -> Option<impl Iterator<Item = (&Bytes, &FixedBytes<48>, &FixedBytes<48>)> + '_> {
+        if self.commitments.len() != self.blobs.len() || self.proofs.len() != self.blobs.len() {
+            return None;
+        }
+        Some(
+            self.blobs
+                .iter()
+                .zip(&self.commitments)
+                .zip(&self.proofs)
+                .map(|((blob, commitment), proof)| (blob, commitment, proof)),
+        )
+    }
+}
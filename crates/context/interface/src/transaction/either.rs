@@ -1,4 +1,4 @@
-use super::Transaction;
+use super::{BlobSidecar, Transaction};
 use either::Either;
 use primitives::{Address, Bytes, TxKind, B256, U256};
 
@@ -97,6 +97,13 @@ where
         }
     }
 
+    fn blob_sidecar(&self) -> Option<&BlobSidecar> {
+        match self {
+            Either::Left(l) => l.blob_sidecar(),
+            Either::Right(r) => r.blob_sidecar(),
+        }
+    }
+
     fn max_fee_per_blob_gas(&self) -> u128 {
         match self {
             Either::Left(l) => l.max_fee_per_blob_gas(),
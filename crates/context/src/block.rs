@@ -1,4 +1,6 @@
 //! This module contains [`BlockEnv`] and it implements [`Block`] trait.
+pub mod pending;
+
 use context_interface::block::{BlobExcessGasAndPrice, Block};
 use primitives::{eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE, Address, B256, U256};
 
@@ -0,0 +1,355 @@
+//! Construction of the [`BlockEnv`] of the block following a parent header.
+use super::BlockEnv;
+use context_interface::cfg::BlobSchedule;
+use primitives::{hardfork::SpecId, keccak256, Address, B256, U256};
+
+/// Base fee of the first EIP-1559 block.
+pub const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+
+/// Parameters of the EIP-1559 base fee update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaseFeeParams {
+    /// Bounds the change of the base fee between blocks to `1 / max_change_denominator`.
+    pub max_change_denominator: u64,
+    /// Gas target of the block is the gas limit divided by the elasticity multiplier.
+    pub elasticity_multiplier: u64,
+}
+
+impl BaseFeeParams {
+    /// Parameters of Ethereum mainnet.
+    pub const ETHEREUM: Self = Self {
+        max_change_denominator: 8,
+        elasticity_multiplier: 2,
+    };
+}
+
+impl Default for BaseFeeParams {
+    fn default() -> Self {
+        Self::ETHEREUM
+    }
+}
+
+/// Returns the base fee of the block following the parent block, as specified by
+/// [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+pub fn calc_next_block_base_fee(
+    parent_gas_used: u64,
+    parent_gas_limit: u64,
+    parent_base_fee: u64,
+    params: BaseFeeParams,
+) -> u64 {
+    let gas_target = parent_gas_limit / params.elasticity_multiplier.max(1);
+    if gas_target == 0 || parent_gas_used == gas_target {
+        return parent_base_fee;
+    }
+    let base_fee = parent_base_fee as u128;
+    let denominator = gas_target as u128 * params.max_change_denominator.max(1) as u128;
+    if parent_gas_used > gas_target {
+        let delta = base_fee * (parent_gas_used - gas_target) as u128 / denominator;
+        parent_base_fee.saturating_add(delta.max(1) as u64)
+    } else {
+        let delta = base_fee * (gas_target - parent_gas_used) as u128 / denominator;
+        parent_base_fee.saturating_sub(delta as u64)
+    }
+}
+
+/// Header fields of the parent block used to construct the next block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParentHeader {
+    /// Number of the parent block.
+    pub number: U256,
+    /// Timestamp of the parent block.
+    pub timestamp: U256,
+    /// Gas limit of the parent block.
+    pub gas_limit: u64,
+    /// Gas used by the parent block.
+    pub gas_used: u64,
+    /// Base fee of the parent block, `None` before London.
+    pub base_fee: Option<u64>,
+    /// Difficulty of the parent block.
+    pub difficulty: U256,
+    /// Mix hash of the parent block, the randomness beacon output after the merge.
+    pub mix_hash: B256,
+    /// Excess blob gas of the parent block, `None` before Cancun.
+    pub excess_blob_gas: Option<u64>,
+    /// Blob gas used by the parent block, `None` before Cancun.
+    pub blob_gas_used: Option<u64>,
+}
+
+impl ParentHeader {
+    /// Returns the header of the executed block, with the gas used by its transactions.
+    pub fn from_block(block: &BlockEnv, gas_used: u64, blob_gas_used: u64) -> Self {
+        Self {
+            number: block.number,
+            timestamp: block.timestamp,
+            gas_limit: block.gas_limit,
+            gas_used,
+            base_fee: Some(block.basefee),
+            difficulty: block.difficulty,
+            mix_hash: block.prevrandao.unwrap_or_default(),
+            excess_blob_gas: block
+                .blob_excess_gas_and_price
+                .map(|blob| blob.excess_blob_gas),
+            blob_gas_used: block.blob_excess_gas_and_price.map(|_| blob_gas_used),
+        }
+    }
+}
+
+/// Timestamp of the pending block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimestampPolicy {
+    /// Parent timestamp increased by the number of seconds.
+    Increment(u64),
+    /// Given timestamp, raised to one second after the parent if it is not later.
+    AtLeast(U256),
+}
+
+impl Default for TimestampPolicy {
+    fn default() -> Self {
+        Self::Increment(12)
+    }
+}
+
+/// Source of the `prevrandao` of the pending block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrevrandaoSource {
+    /// Mix hash of the parent block.
+    #[default]
+    Parent,
+    /// Hash of the parent mix hash and the number of the pending block, so consecutive
+    /// simulated blocks get different values.
+    Derived,
+    /// Given value.
+    Value(B256),
+}
+
+/// Builder of the [`BlockEnv`] of the block following a [`ParentHeader`].
+///
+/// Number is the parent number plus one. Base fee follows EIP-1559 from London, starting at
+/// [`INITIAL_BASE_FEE`] in the first London block. Excess blob gas and the blob gas price
+/// follow the [`BlobSchedule`] from Cancun, which is assumed to be used by the parent block
+/// too. Gas limit is the parent gas limit unless set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingBlockBuilder {
+    parent: ParentHeader,
+    spec: SpecId,
+    beneficiary: Address,
+    gas_limit: Option<u64>,
+    timestamp: TimestampPolicy,
+    prevrandao: PrevrandaoSource,
+    base_fee_params: BaseFeeParams,
+    blob_schedule: Option<BlobSchedule>,
+}
+
+impl PendingBlockBuilder {
+    /// Creates the builder of the block following `parent`, with the default spec.
+    pub fn new(parent: ParentHeader) -> Self {
+        Self {
+            parent,
+            spec: SpecId::default(),
+            beneficiary: Address::ZERO,
+            gas_limit: None,
+            timestamp: TimestampPolicy::default(),
+            prevrandao: PrevrandaoSource::default(),
+            base_fee_params: BaseFeeParams::ETHEREUM,
+            blob_schedule: None,
+        }
+    }
+
+    /// Sets the spec of the pending block.
+    pub fn spec(mut self, spec: SpecId) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    /// Sets the beneficiary, zero address by default.
+    pub fn beneficiary(mut self, beneficiary: Address) -> Self {
+        self.beneficiary = beneficiary;
+        self
+    }
+
+    /// Sets the gas limit, the parent gas limit by default.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the timestamp policy, twelve seconds after the parent by default.
+    pub fn timestamp(mut self, timestamp: TimestampPolicy) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the source of `prevrandao`, the parent mix hash by default.
+    pub fn prevrandao(mut self, prevrandao: PrevrandaoSource) -> Self {
+        self.prevrandao = prevrandao;
+        self
+    }
+
+    /// Sets the parameters of the base fee update, [`BaseFeeParams::ETHEREUM`] by default.
+    pub fn base_fee_params(mut self, params: BaseFeeParams) -> Self {
+        self.base_fee_params = params;
+        self
+    }
+
+    /// Sets the blob parameters, the parameters of the spec by default.
+    pub fn blob_schedule(mut self, schedule: BlobSchedule) -> Self {
+        self.blob_schedule = Some(schedule);
+        self
+    }
+
+    /// Builds the block environment.
+    pub fn build(&self) -> BlockEnv {
+        let parent = &self.parent;
+        let number = parent.number.saturating_add(U256::ONE);
+        let timestamp = match self.timestamp {
+            TimestampPolicy::Increment(seconds) => {
+                parent.timestamp.saturating_add(U256::from(seconds))
+            }
+            TimestampPolicy::AtLeast(timestamp) => {
+                timestamp.max(parent.timestamp.saturating_add(U256::ONE))
+            }
+        };
+
+        let basefee = if !self.spec.is_enabled_in(SpecId::LONDON) {
+            0
+        } else if let Some(base_fee) = parent.base_fee {
+            calc_next_block_base_fee(
+                parent.gas_used,
+                parent.gas_limit,
+                base_fee,
+                self.base_fee_params,
+            )
+        } else {
+            INITIAL_BASE_FEE
+        };
+
+        let (difficulty, prevrandao) = if self.spec.is_enabled_in(SpecId::MERGE) {
+            let prevrandao = match self.prevrandao {
+                PrevrandaoSource::Parent => parent.mix_hash,
+                PrevrandaoSource::Derived => {
+                    keccak256([parent.mix_hash.0, number.to_be_bytes()].concat())
+                }
+                PrevrandaoSource::Value(value) => value,
+            };
+            (U256::ZERO, Some(prevrandao))
+        } else {
+            (parent.difficulty, None)
+        };
+
+        let blob_excess_gas_and_price = self.spec.is_enabled_in(SpecId::CANCUN).then(|| {
+            let schedule = self
+                .blob_schedule
+                .unwrap_or_else(|| BlobSchedule::from_spec(self.spec));
+            let excess_blob_gas = schedule.next_excess_blob_gas(
+                parent.excess_blob_gas.unwrap_or_default(),
+                parent.blob_gas_used.unwrap_or_default(),
+                parent.base_fee.unwrap_or_default(),
+            );
+            schedule.blob_excess_gas_and_price(excess_blob_gas)
+        });
+
+        BlockEnv {
+            number,
+            beneficiary: self.beneficiary,
+            timestamp,
+            gas_limit: self.gas_limit.unwrap_or(parent.gas_limit),
+            basefee,
+            difficulty,
+            prevrandao,
+            blob_excess_gas_and_price,
+        }
+    }
+}
+
+impl BlockEnv {
+    /// Returns the builder of the block following `parent`.
+    pub fn pending(parent: ParentHeader) -> PendingBlockBuilder {
+        PendingBlockBuilder::new(parent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::eip4844::GAS_PER_BLOB;
+
+    #[test]
+    fn next_block_base_fee() {
+        let params = BaseFeeParams::ETHEREUM;
+        // Gas used at the target keeps the base fee.
+        assert_eq!(
+            calc_next_block_base_fee(15_000_000, 30_000_000, 1_000, params),
+            1_000
+        );
+        // Full block raises the base fee by an eighth.
+        assert_eq!(
+            calc_next_block_base_fee(30_000_000, 30_000_000, 1_000, params),
+            1_125
+        );
+        // Empty block lowers the base fee by an eighth.
+        assert_eq!(calc_next_block_base_fee(0, 30_000_000, 1_000, params), 875);
+        // Increase is at least one.
+        assert_eq!(
+            calc_next_block_base_fee(15_000_001, 30_000_000, 7, params),
+            8
+        );
+        assert_eq!(calc_next_block_base_fee(0, 30_000_000, 7, params), 7);
+    }
+
+    #[test]
+    fn pending_block() {
+        let parent = ParentHeader {
+            number: U256::from(10),
+            timestamp: U256::from(1_000),
+            gas_limit: 30_000_000,
+            gas_used: 30_000_000,
+            base_fee: Some(8),
+            mix_hash: B256::with_last_byte(1),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(9 * GAS_PER_BLOB),
+            ..Default::default()
+        };
+        let block = BlockEnv::pending(parent.clone())
+            .spec(SpecId::PRAGUE)
+            .beneficiary(Address::with_last_byte(2))
+            .build();
+        assert_eq!(block.number, U256::from(11));
+        assert_eq!(block.timestamp, U256::from(1_012));
+        assert_eq!(block.gas_limit, 30_000_000);
+        assert_eq!(block.basefee, 9);
+        assert_eq!(block.prevrandao, Some(parent.mix_hash));
+        assert_eq!(block.beneficiary, Address::with_last_byte(2));
+        let blob = block.blob_excess_gas_and_price.unwrap();
+        assert_eq!(blob.excess_blob_gas, 3 * GAS_PER_BLOB);
+
+        let derived = BlockEnv::pending(parent.clone())
+            .prevrandao(PrevrandaoSource::Derived)
+            .timestamp(TimestampPolicy::AtLeast(U256::from(500)))
+            .build();
+        assert_ne!(derived.prevrandao, Some(parent.mix_hash));
+        assert_eq!(derived.timestamp, U256::from(1_001));
+
+        // Next block of the pending block.
+        let next = BlockEnv::pending(ParentHeader::from_block(&block, 0, 0)).build();
+        assert_eq!(next.number, U256::from(12));
+        assert_eq!(next.basefee, 8);
+        assert_eq!(next.blob_excess_gas_and_price.unwrap().excess_blob_gas, 0);
+
+        // First London block and blocks before Cancun and the merge.
+        let london = BlockEnv::pending(ParentHeader {
+            base_fee: None,
+            difficulty: U256::from(3),
+            ..parent
+        })
+        .spec(SpecId::LONDON)
+        .build();
+        assert_eq!(london.basefee, INITIAL_BASE_FEE);
+        assert_eq!(london.prevrandao, None);
+        assert_eq!(london.difficulty, U256::from(3));
+        assert_eq!(london.blob_excess_gas_and_price, None);
+    }
+}
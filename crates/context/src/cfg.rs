@@ -1,7 +1,185 @@
 //! This module contains [`CfgEnv`] and implements [`Cfg`] trait for it.
-pub use context_interface::Cfg;
+pub use context_interface::{
+    cfg::{
+        BlobSchedule, BlobSidecarMode, CallGasPolicy, CallerCodePolicy, CreateAddressPolicy,
+        DisabledOpcodes, Eip7702CodePolicy, FeeDestination, FeePolicy, FeeRecipient,
+        FinalizedStateMode, GasSchedule, NonceCheckMode, RefundCap, RefundPolicy, SelfdestructMode,
+        StandardCreateAddress,
+    },
+    Cfg,
+};
+use state::bytecode::AnalysisStore;
+
+use core::fmt;
+use primitives::{eip170, eip3860, eip7825, hardfork::SpecId, U256};
+use std::{sync::Arc, vec::Vec};
+
+/// Shared [`CreateAddressPolicy`] of [`CfgEnv`].
+///
+/// Policies are compared by pointer, clones of the same policy are equal.
+#[derive(Clone)]
+pub struct SharedCreateAddressPolicy(pub Arc<dyn CreateAddressPolicy>);
+
+impl SharedCreateAddressPolicy {
+    /// Creates the shared policy.
+    pub fn new(policy: impl CreateAddressPolicy + 'static) -> Self {
+        Self(Arc::new(policy))
+    }
+}
+
+impl fmt::Debug for SharedCreateAddressPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for SharedCreateAddressPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+    }
+}
+
+impl Eq for SharedCreateAddressPolicy {}
+
+/// Shared [`AnalysisStore`] of [`CfgEnv`].
+///
+/// Stores are compared by pointer, clones of the same store are equal.
+#[derive(Clone)]
+pub struct SharedAnalysisStore(pub Arc<dyn AnalysisStore>);
+
+impl SharedAnalysisStore {
+    /// Creates the shared store.
+    pub fn new(store: impl AnalysisStore + 'static) -> Self {
+        Self(Arc::new(store))
+    }
+}
+
+impl fmt::Debug for SharedAnalysisStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for SharedAnalysisStore {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+    }
+}
+
+impl Eq for SharedAnalysisStore {}
+
+/// Policy for the EIP-155 transaction chain ID check.
+///
+/// EIP-7702 authorizations are not affected and are always checked against [`CfgEnv::chain_id`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainIdPolicy {
+    /// Chain ID of the transaction is not checked.
+    #[default]
+    Disabled,
+    /// Transaction chain ID must be equal to [`CfgEnv::chain_id`].
+    Strict,
+    /// Transaction chain ID must be equal to [`CfgEnv::chain_id`] or to one of the given IDs.
+    ///
+    /// Useful for replaying transactions from several chains through one EVM.
+    AnyOf(Vec<u64>),
+}
+
+/// Condition activating a fork of the [`ForkSchedule`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForkActivation {
+    /// Fork is active from the block with this number.
+    Block(u64),
+    /// Fork is active from the first block with this or a later timestamp.
+    Timestamp(u64),
+}
+
+impl ForkActivation {
+    /// Returns `true` if the fork is active at the block with the given number and timestamp.
+    pub fn is_active(&self, number: U256, timestamp: U256) -> bool {
+        match *self {
+            Self::Block(block) => number >= U256::from(block),
+            Self::Timestamp(time) => timestamp >= U256::from(time),
+        }
+    }
+}
+
+/// Forks of a chain with their activation, used to select the spec of executed blocks.
+///
+/// Forks are added in activation order, the spec of a block is the spec of the last active fork.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForkSchedule<SPEC = SpecId> {
+    forks: Vec<(ForkActivation, SPEC)>,
+}
+
+impl<SPEC> Default for ForkSchedule<SPEC> {
+    fn default() -> Self {
+        Self { forks: Vec::new() }
+    }
+}
+
+impl<SPEC> ForkSchedule<SPEC> {
+    /// Creates an empty schedule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumes `self` and returns the schedule with the fork added after the existing forks.
+    pub fn with_fork(mut self, activation: ForkActivation, spec: SPEC) -> Self {
+        self.forks.push((activation, spec));
+        self
+    }
+
+    /// Returns the forks in activation order.
+    pub fn forks(&self) -> &[(ForkActivation, SPEC)] {
+        &self.forks
+    }
+}
+
+impl<SPEC: Copy> ForkSchedule<SPEC> {
+    /// Returns the spec active at the block, `None` if no fork is active.
+    pub fn spec_at(&self, number: U256, timestamp: U256) -> Option<SPEC> {
+        self.forks
+            .iter()
+            .rev()
+            .find(|(activation, _)| activation.is_active(number, timestamp))
+            .map(|(_, spec)| *spec)
+    }
+}
+
+impl ForkSchedule {
+    /// Returns the schedule of Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        use ForkActivation::{Block, Timestamp};
+        [
+            (Block(0), SpecId::FRONTIER),
+            (Block(200_000), SpecId::FRONTIER_THAWING),
+            (Block(1_150_000), SpecId::HOMESTEAD),
+            (Block(1_920_000), SpecId::DAO_FORK),
+            (Block(2_463_000), SpecId::TANGERINE),
+            (Block(2_675_000), SpecId::SPURIOUS_DRAGON),
+            (Block(4_370_000), SpecId::BYZANTIUM),
+            (Block(7_280_000), SpecId::PETERSBURG),
+            (Block(9_069_000), SpecId::ISTANBUL),
+            (Block(9_200_000), SpecId::MUIR_GLACIER),
+            (Block(12_244_000), SpecId::BERLIN),
+            (Block(12_965_000), SpecId::LONDON),
+            (Block(13_773_000), SpecId::ARROW_GLACIER),
+            (Block(15_050_000), SpecId::GRAY_GLACIER),
+            (Block(15_537_394), SpecId::MERGE),
+            (Timestamp(1_681_338_455), SpecId::SHANGHAI),
+            (Timestamp(1_710_338_135), SpecId::CANCUN),
+            (Timestamp(1_746_612_311), SpecId::PRAGUE),
+        ]
+        .into_iter()
+        .fold(Self::new(), |schedule, (activation, spec)| {
+            schedule.with_fork(activation, spec)
+        })
+    }
+}
 
-use primitives::{eip170, eip3860, eip7825, hardfork::SpecId};
 /// EVM configuration
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -17,8 +195,18 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// If set to `false`, the transaction's chain ID check will be skipped.
     pub tx_chain_id_check: bool,
 
+    /// Chain IDs accepted in addition to [`CfgEnv::chain_id`] when the transaction's chain ID
+    /// check is enabled.
+    ///
+    /// By default, it is empty. See [`CfgEnv::with_chain_id_policy`].
+    pub accepted_chain_ids: Vec<u64>,
+
     /// Specification for EVM represent the hardfork
     pub spec: SPEC,
+    /// Forks used to select [`CfgEnv::spec`] when the block of the context is set.
+    ///
+    /// By default, it is `None` and the spec is fixed.
+    pub fork_schedule: Option<ForkSchedule<SPEC>>,
     /// Contract code size limit override.
     ///
     /// If None, the limit will be determined by the SpecId (EIP-170 or EIP-7907) at runtime.
@@ -35,15 +223,91 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// Useful to increase this because of tests.
     pub limit_contract_initcode_size: Option<usize>,
     /// Skips the nonce validation against the account's nonce
+    ///
+    /// Same as setting [`CfgEnv::nonce_check_mode`] to [`NonceCheckMode::AutoFill`].
     pub disable_nonce_check: bool,
+    /// Nonce handling policy for transactions and EIP-7702 authorizations.
+    ///
+    /// By default, it is set to [`NonceCheckMode::Strict`].
+    pub nonce_check_mode: NonceCheckMode,
+    /// Policy of the EIP-3607 check of the transaction sender code.
+    ///
+    /// By default, it is set to [`CallerCodePolicy::AllowDelegated`].
+    ///
+    /// `CallerCodePolicy::Disabled` is only available with the `optional_eip3607` feature, in
+    /// development it can be desirable to simulate calls from contracts.
+    pub caller_code_policy: CallerCodePolicy,
+    /// Validation of blobs of EIP-4844 transactions.
+    ///
+    /// By default, it is set to [`BlobSidecarMode::HashesOnly`].
+    pub blob_sidecar_mode: BlobSidecarMode,
+    /// Behaviour of the SELFDESTRUCT instruction.
+    ///
+    /// If `None`, behaviour of the spec is used, see [`SelfdestructMode::from_spec`].
+    pub selfdestruct_mode: Option<SelfdestructMode>,
+    /// Accounts included in the state returned when the journal is finalized.
+    ///
+    /// By default, it is set to [`FinalizedStateMode::Loaded`].
+    pub finalized_state_mode: FinalizedStateMode,
+    /// Opcodes that halt with [`HaltReason::OpcodeDisabled`] instead of executing.
+    ///
+    /// By default, no opcode is disabled.
+    ///
+    /// [`HaltReason::OpcodeDisabled`]: context_interface::result::HaltReason::OpcodeDisabled
+    pub disabled_opcodes: DisabledOpcodes,
+    /// Gas schedule read by instructions.
+    ///
+    /// If `None`, schedule of the spec is used, see [`GasSchedule::for_spec`].
+    pub gas_schedule: Option<GasSchedule>,
+    /// Gas refund rules.
+    ///
+    /// If `None`, rules of the spec are used, see [`RefundPolicy::from_spec`].
+    pub refund_policy: Option<RefundPolicy>,
+    /// Recipients of the transaction fees.
+    ///
+    /// By default, it is set to [`FeePolicy::MAINNET`].
+    pub fee_policy: FeePolicy,
+    /// Code observed by EXTCODE* instructions for EIP-7702 delegated accounts.
+    ///
+    /// By default, it is set to [`Eip7702CodePolicy::Designator`].
+    pub eip7702_code_policy: Eip7702CodePolicy,
+    /// Derivation of addresses of created contracts.
+    ///
+    /// If `None`, addresses are derived as with [`StandardCreateAddress`]. The policy is not
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub create_address_policy: Option<SharedCreateAddressPolicy>,
+    /// Store of bytecode analysis artifacts consulted before created code is analyzed.
+    ///
+    /// If `None`, code is always analyzed. The store is not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub analysis_store: Option<SharedAnalysisStore>,
+    /// Loads all accounts from the transaction access list before execution.
+    ///
+    /// By default, it is set to `false` and only access list storage slots are loaded.
+    pub prefetch_access_list: bool,
+    /// Maximum number of hashes cached by the KECCAK256 instruction within a transaction.
+    ///
+    /// By default, it is set to `0` and the cache is disabled.
+    pub keccak_cache_size: usize,
+    /// Ceiling of the gas limit of simulated calls, see [`Cfg::call_gas_cap`].
+    ///
+    /// By default, it is set to `None` and no ceiling is configured.
+    pub call_gas_cap: Option<u64>,
+    /// Blob parameters of the chain. EIP-7840 Add blob schedule to EL config files.
+    ///
+    /// If `None`, parameters of the spec are used, see [`BlobSchedule::from_spec`], and the
+    /// check for max blobs per transaction is skipped unless `max_blobs_per_tx` is set.
+    pub blob_schedule: Option<BlobSchedule>,
     /// Blob max count. EIP-7840 Add blob schedule to EL config files.
     ///
-    /// If this config is not set, the check for max blobs will be skipped.
+    /// If this config is not set, the check for max blobs will be skipped unless
+    /// `blob_schedule` is set. Overrides the count of `blob_schedule`.
     pub max_blobs_per_tx: Option<u64>,
     /// Blob base fee update fraction. EIP-4844 Blob base fee update fraction.
     ///
     /// If this config is not set, the blob base fee update fraction will be set to the default value.
-    /// See also [CfgEnv::blob_base_fee_update_fraction].
+    /// Overrides the fraction of `blob_schedule`. See also [CfgEnv::blob_base_fee_update_fraction].
     ///
     /// Default values for Cancun is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN`]
     /// and for Prague is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE`].
@@ -85,12 +349,11 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_eip3541")]
     pub disable_eip3541: bool,
-    /// EIP-3607 rejects transactions from senders with deployed code
-    ///
-    /// In development, it can be desirable to simulate calls from contracts, which this setting allows.
+    /// Skips the EIP-3607 check of the transaction sender code.
     ///
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_eip3607")]
+    #[deprecated(note = "Use `caller_code_policy` with `CallerCodePolicy::Disabled` instead.")]
     pub disable_eip3607: bool,
     /// Disables base fee checks for EIP-1559 transactions
     ///
@@ -116,19 +379,13 @@ impl CfgEnv {
 impl<SPEC: Into<SpecId> + Copy> CfgEnv<SPEC> {
     /// Returns the blob base fee update fraction from [CfgEnv::blob_base_fee_update_fraction].
     ///
-    /// If this field is not set, return the default value for the spec.
+    /// If this field is not set, return the value of [CfgEnv::blob_schedule] or the default value
+    /// for the spec.
     ///
     /// Default values for Cancun is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN`]
     /// and for Prague is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE`].
     pub fn blob_base_fee_update_fraction(&mut self) -> u64 {
-        self.blob_base_fee_update_fraction.unwrap_or_else(|| {
-            let spec: SpecId = self.spec.into();
-            if spec.is_enabled_in(SpecId::PRAGUE) {
-                primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE
-            } else {
-                primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN
-            }
-        })
+        self.blob_schedule().base_fee_update_fraction
     }
 }
 
@@ -138,10 +395,28 @@ impl<SPEC> CfgEnv<SPEC> {
         Self {
             chain_id: 1,
             tx_chain_id_check: false,
+            accepted_chain_ids: Vec::new(),
             limit_contract_code_size: None,
             limit_contract_initcode_size: None,
             spec,
+            fork_schedule: None,
             disable_nonce_check: false,
+            nonce_check_mode: NonceCheckMode::Strict,
+            caller_code_policy: CallerCodePolicy::AllowDelegated,
+            blob_sidecar_mode: BlobSidecarMode::HashesOnly,
+            selfdestruct_mode: None,
+            finalized_state_mode: FinalizedStateMode::Loaded,
+            disabled_opcodes: DisabledOpcodes::new(),
+            gas_schedule: None,
+            refund_policy: None,
+            fee_policy: FeePolicy::MAINNET,
+            eip7702_code_policy: Eip7702CodePolicy::Designator,
+            create_address_policy: None,
+            analysis_store: None,
+            prefetch_access_list: false,
+            keccak_cache_size: 0,
+            call_gas_cap: None,
+            blob_schedule: None,
             max_blobs_per_tx: None,
             tx_gas_limit_cap: None,
             blob_base_fee_update_fraction: None,
@@ -154,6 +429,7 @@ impl<SPEC> CfgEnv<SPEC> {
             #[cfg(feature = "optional_eip3541")]
             disable_eip3541: false,
             #[cfg(feature = "optional_eip3607")]
+            #[allow(deprecated)]
             disable_eip3607: false,
             #[cfg(feature = "optional_no_base_fee")]
             disable_base_fee: false,
@@ -180,16 +456,57 @@ impl<SPEC> CfgEnv<SPEC> {
         self
     }
 
+    /// Sets the transaction's chain ID check policy.
+    pub fn with_chain_id_policy(mut self, policy: ChainIdPolicy) -> Self {
+        self.tx_chain_id_check = policy != ChainIdPolicy::Disabled;
+        self.accepted_chain_ids = match policy {
+            ChainIdPolicy::AnyOf(ids) => ids,
+            _ => Vec::new(),
+        };
+        self
+    }
+
+    /// Returns the transaction's chain ID check policy.
+    pub fn chain_id_policy(&self) -> ChainIdPolicy {
+        if !self.tx_chain_id_check {
+            ChainIdPolicy::Disabled
+        } else if self.accepted_chain_ids.is_empty() {
+            ChainIdPolicy::Strict
+        } else {
+            ChainIdPolicy::AnyOf(self.accepted_chain_ids.clone())
+        }
+    }
+
     /// Consumes `self` and returns a new `CfgEnv` with the specified spec.
+    ///
+    /// Fork schedule is cleared.
     pub fn with_spec<OSPEC: Into<SpecId>>(self, spec: OSPEC) -> CfgEnv<OSPEC> {
         CfgEnv {
             chain_id: self.chain_id,
             tx_chain_id_check: self.tx_chain_id_check,
+            accepted_chain_ids: self.accepted_chain_ids,
             limit_contract_code_size: self.limit_contract_code_size,
             limit_contract_initcode_size: self.limit_contract_initcode_size,
             spec,
+            fork_schedule: None,
             disable_nonce_check: self.disable_nonce_check,
+            nonce_check_mode: self.nonce_check_mode,
+            caller_code_policy: self.caller_code_policy,
+            blob_sidecar_mode: self.blob_sidecar_mode,
+            selfdestruct_mode: self.selfdestruct_mode,
+            finalized_state_mode: self.finalized_state_mode,
+            disabled_opcodes: self.disabled_opcodes,
+            gas_schedule: self.gas_schedule,
+            refund_policy: self.refund_policy,
+            fee_policy: self.fee_policy,
+            eip7702_code_policy: self.eip7702_code_policy,
+            create_address_policy: self.create_address_policy,
+            analysis_store: self.analysis_store,
+            prefetch_access_list: self.prefetch_access_list,
+            keccak_cache_size: self.keccak_cache_size,
+            call_gas_cap: self.call_gas_cap,
             tx_gas_limit_cap: self.tx_gas_limit_cap,
+            blob_schedule: self.blob_schedule,
             max_blobs_per_tx: self.max_blobs_per_tx,
             blob_base_fee_update_fraction: self.blob_base_fee_update_fraction,
             #[cfg(feature = "memory_limit")]
@@ -201,6 +518,7 @@ impl<SPEC> CfgEnv<SPEC> {
             #[cfg(feature = "optional_eip3541")]
             disable_eip3541: self.disable_eip3541,
             #[cfg(feature = "optional_eip3607")]
+            #[allow(deprecated)]
             disable_eip3607: self.disable_eip3607,
             #[cfg(feature = "optional_no_base_fee")]
             disable_base_fee: self.disable_base_fee,
@@ -209,6 +527,118 @@ impl<SPEC> CfgEnv<SPEC> {
         }
     }
 
+    /// Sets the nonce handling policy.
+    pub fn with_nonce_check_mode(mut self, mode: NonceCheckMode) -> Self {
+        self.nonce_check_mode = mode;
+        self
+    }
+
+    /// Sets the policy of the EIP-3607 check of the transaction sender code.
+    pub fn with_caller_code_policy(mut self, policy: CallerCodePolicy) -> Self {
+        self.caller_code_policy = policy;
+        self
+    }
+
+    /// Sets the validation of blobs of EIP-4844 transactions.
+    pub fn with_blob_sidecar_mode(mut self, mode: BlobSidecarMode) -> Self {
+        self.blob_sidecar_mode = mode;
+        self
+    }
+
+    /// Sets the behaviour of the SELFDESTRUCT instruction, independent of the spec.
+    pub fn with_selfdestruct_mode(mut self, mode: SelfdestructMode) -> Self {
+        self.selfdestruct_mode = Some(mode);
+        self
+    }
+
+    /// Sets the gas schedule, independent of the spec.
+    pub fn with_gas_schedule(mut self, schedule: GasSchedule) -> Self {
+        self.gas_schedule = Some(schedule);
+        self
+    }
+
+    /// Sets the gas refund rules, independent of the spec.
+    pub fn with_refund_policy(mut self, policy: RefundPolicy) -> Self {
+        self.refund_policy = Some(policy);
+        self
+    }
+
+    /// Sets the recipients of the transaction fees.
+    pub fn with_fee_policy(mut self, policy: FeePolicy) -> Self {
+        self.fee_policy = policy;
+        self
+    }
+
+    /// Sets the derivation of addresses of created contracts.
+    pub fn with_create_address_policy(
+        mut self,
+        policy: impl CreateAddressPolicy + 'static,
+    ) -> Self {
+        self.create_address_policy = Some(SharedCreateAddressPolicy::new(policy));
+        self
+    }
+
+    /// Sets the store of bytecode analysis artifacts.
+    ///
+    /// Pass an `Arc` of the store to share it between multiple configurations.
+    pub fn with_analysis_store(mut self, store: impl AnalysisStore + 'static) -> Self {
+        self.analysis_store = Some(SharedAnalysisStore::new(store));
+        self
+    }
+
+    /// Sets which accounts are included in the state returned when the journal is finalized.
+    pub fn with_finalized_state_mode(mut self, mode: FinalizedStateMode) -> Self {
+        self.finalized_state_mode = mode;
+        self
+    }
+
+    /// Sets the opcodes that halt with [`HaltReason::OpcodeDisabled`] instead of executing.
+    ///
+    /// [`HaltReason::OpcodeDisabled`]: context_interface::result::HaltReason::OpcodeDisabled
+    pub fn with_disabled_opcodes(mut self, opcodes: DisabledOpcodes) -> Self {
+        self.disabled_opcodes = opcodes;
+        self
+    }
+
+    /// Sets the code observed by EXTCODE* instructions for EIP-7702 delegated accounts.
+    pub fn with_eip7702_code_policy(mut self, policy: Eip7702CodePolicy) -> Self {
+        self.eip7702_code_policy = policy;
+        self
+    }
+
+    /// Enables or disables loading of access list accounts before execution.
+    pub fn with_prefetch_access_list(mut self, prefetch: bool) -> Self {
+        self.prefetch_access_list = prefetch;
+        self
+    }
+
+    /// Sets the maximum number of hashes cached by the KECCAK256 instruction, `0` disables the cache.
+    pub fn with_keccak_cache_size(mut self, size: usize) -> Self {
+        self.keccak_cache_size = size;
+        self
+    }
+
+    /// Sets the ceiling of the gas limit of simulated calls.
+    pub fn with_call_gas_cap(mut self, cap: u64) -> Self {
+        self.call_gas_cap = Some(cap);
+        self
+    }
+
+    /// Sets the fork schedule that selects the spec of each block set in the context.
+    pub fn with_fork_schedule(mut self, schedule: ForkSchedule<SPEC>) -> Self {
+        self.fork_schedule = Some(schedule);
+        self
+    }
+
+    /// Sets the blob parameters, independent of the spec.
+    ///
+    /// Presets of Ethereum hardforks are [`BlobSchedule::CANCUN`], [`BlobSchedule::PRAGUE`] and
+    /// [`BlobSchedule::OSAKA`].
+    pub fn with_blob_schedule(mut self, schedule: BlobSchedule) -> Self {
+        self.blob_schedule = Some(schedule);
+        self
+    }
+
     /// Sets the blob target
     pub fn with_max_blobs_per_tx(mut self, max_blobs_per_tx: u64) -> Self {
         self.set_max_blobs_per_tx(max_blobs_per_tx);
@@ -251,6 +681,11 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
         self.tx_chain_id_check
     }
 
+    #[inline]
+    fn is_chain_id_accepted(&self, chain_id: u64) -> bool {
+        chain_id == self.chain_id || self.accepted_chain_ids.contains(&chain_id)
+    }
+
     #[inline]
     fn tx_gas_limit_cap(&self) -> u64 {
         self.tx_gas_limit_cap
@@ -264,6 +699,7 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
     #[inline]
     fn max_blobs_per_tx(&self) -> Option<u64> {
         self.max_blobs_per_tx
+            .or_else(|| self.blob_schedule.map(|schedule| schedule.max_blobs_per_tx))
     }
 
     fn max_code_size(&self) -> usize {
@@ -291,13 +727,7 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
     }
 
     fn is_eip3607_disabled(&self) -> bool {
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "optional_eip3607")] {
-                self.disable_eip3607
-            } else {
-                false
-            }
-        }
+        self.caller_code_policy().is_disabled()
     }
 
     fn is_balance_check_disabled(&self) -> bool {
@@ -322,7 +752,108 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
     }
 
     fn is_nonce_check_disabled(&self) -> bool {
-        self.disable_nonce_check
+        self.disable_nonce_check || !self.nonce_check_mode.is_strict()
+    }
+
+    fn is_access_list_prefetch_enabled(&self) -> bool {
+        self.prefetch_access_list
+    }
+
+    fn keccak_cache_size(&self) -> usize {
+        self.keccak_cache_size
+    }
+
+    fn call_gas_cap(&self) -> Option<u64> {
+        self.call_gas_cap
+    }
+
+    fn select_spec_for_block(&mut self, number: U256, timestamp: U256) -> bool {
+        let Some(spec) = self
+            .fork_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.spec_at(number, timestamp))
+        else {
+            return false;
+        };
+        self.spec = spec;
+        true
+    }
+
+    fn nonce_check_mode(&self) -> NonceCheckMode {
+        if self.disable_nonce_check && self.nonce_check_mode.is_strict() {
+            NonceCheckMode::AutoFill
+        } else {
+            self.nonce_check_mode
+        }
+    }
+
+    fn blob_sidecar_mode(&self) -> BlobSidecarMode {
+        self.blob_sidecar_mode
+    }
+
+    fn caller_code_policy(&self) -> CallerCodePolicy {
+        #[cfg(feature = "optional_eip3607")]
+        #[allow(deprecated)]
+        if self.disable_eip3607 {
+            return CallerCodePolicy::Disabled;
+        }
+        self.caller_code_policy
+    }
+
+    fn selfdestruct_mode(&self) -> SelfdestructMode {
+        self.selfdestruct_mode
+            .unwrap_or_else(|| SelfdestructMode::from_spec(self.spec.into()))
+    }
+
+    fn finalized_state_mode(&self) -> FinalizedStateMode {
+        self.finalized_state_mode
+    }
+
+    fn disabled_opcodes(&self) -> DisabledOpcodes {
+        self.disabled_opcodes
+    }
+
+    fn gas_schedule(&self) -> &GasSchedule {
+        match &self.gas_schedule {
+            Some(schedule) => schedule,
+            None => GasSchedule::for_spec(self.spec.into()),
+        }
+    }
+
+    fn refund_policy(&self) -> RefundPolicy {
+        self.refund_policy
+            .unwrap_or_else(|| RefundPolicy::from_spec(self.spec.into()))
+    }
+
+    fn fee_policy(&self) -> FeePolicy {
+        self.fee_policy
+    }
+
+    fn blob_schedule(&self) -> BlobSchedule {
+        let mut schedule = self
+            .blob_schedule
+            .unwrap_or_else(|| BlobSchedule::from_spec(self.spec.into()));
+        if let Some(max_blobs_per_tx) = self.max_blobs_per_tx {
+            schedule.max_blobs_per_tx = max_blobs_per_tx;
+        }
+        if let Some(fraction) = self.blob_base_fee_update_fraction {
+            schedule.base_fee_update_fraction = fraction;
+        }
+        schedule
+    }
+
+    fn eip7702_code_policy(&self) -> Eip7702CodePolicy {
+        self.eip7702_code_policy
+    }
+
+    fn create_address_policy(&self) -> Option<&dyn CreateAddressPolicy> {
+        self.create_address_policy
+            .as_ref()
+            .map(|policy| policy.0.as_ref())
+    }
+
+    fn analysis_store(&self) -> Option<Arc<dyn AnalysisStore>> {
+        self.analysis_store.as_ref().map(|store| store.0.clone())
     }
 
     fn is_base_fee_check_disabled(&self) -> bool {
@@ -355,10 +886,118 @@ impl<SPEC: Default> Default for CfgEnv<SPEC> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::vec;
 
     #[test]
     fn blob_max_and_target_count() {
         let cfg: CfgEnv = Default::default();
         assert_eq!(cfg.max_blobs_per_tx(), None);
     }
+
+    #[test]
+    fn blob_schedule() {
+        let mut cfg = CfgEnv::new_with_spec(SpecId::CANCUN);
+        assert_eq!(cfg.blob_schedule(), BlobSchedule::CANCUN);
+        assert_eq!(
+            cfg.blob_base_fee_update_fraction(),
+            primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN
+        );
+
+        let devnet = BlobSchedule {
+            target_blob_count: 10,
+            max_blob_count: 15,
+            max_blobs_per_tx: 12,
+            base_fee_update_fraction: 8_346_193,
+            ..BlobSchedule::OSAKA
+        };
+        let mut cfg = cfg.with_blob_schedule(devnet);
+        assert_eq!(cfg.blob_schedule(), devnet);
+        assert_eq!(cfg.max_blobs_per_tx(), Some(12));
+        assert_eq!(cfg.blob_base_fee_update_fraction(), 8_346_193);
+
+        // Explicit limits override the schedule.
+        cfg.set_max_blobs_per_tx(3);
+        assert_eq!(cfg.max_blobs_per_tx(), Some(3));
+        assert_eq!(cfg.blob_schedule().max_blobs_per_tx, 3);
+        assert_eq!(cfg.blob_schedule().max_blob_count, 15);
+    }
+
+    #[test]
+    fn caller_code_policy() {
+        let cfg = CfgEnv::new();
+        assert_eq!(cfg.caller_code_policy(), CallerCodePolicy::AllowDelegated);
+        let cfg = CfgEnv::new().with_caller_code_policy(CallerCodePolicy::Strict);
+        assert_eq!(cfg.caller_code_policy(), CallerCodePolicy::Strict);
+        assert!(!cfg.is_eip3607_disabled());
+
+        #[cfg(feature = "optional_eip3607")]
+        {
+            let cfg = CfgEnv::new().with_caller_code_policy(CallerCodePolicy::Disabled);
+            assert_eq!(cfg.caller_code_policy(), CallerCodePolicy::Disabled);
+            assert!(cfg.is_eip3607_disabled());
+
+            let mut cfg = CfgEnv::new();
+            #[allow(deprecated)]
+            {
+                cfg.disable_eip3607 = true;
+            }
+            assert_eq!(cfg.caller_code_policy(), CallerCodePolicy::Disabled);
+            assert!(cfg.is_eip3607_disabled());
+        }
+    }
+
+    #[test]
+    fn nonce_check_mode() {
+        let mut cfg: CfgEnv = Default::default();
+        assert_eq!(cfg.nonce_check_mode(), NonceCheckMode::Strict);
+        cfg.disable_nonce_check = true;
+        assert_eq!(cfg.nonce_check_mode(), NonceCheckMode::AutoFill);
+        let cfg = CfgEnv::new().with_nonce_check_mode(NonceCheckMode::Ignore);
+        assert!(cfg.is_nonce_check_disabled());
+        assert_eq!(cfg.nonce_check_mode(), NonceCheckMode::Ignore);
+    }
+
+    #[test]
+    fn refund_policy() {
+        let cfg = CfgEnv::new_with_spec(SpecId::BERLIN);
+        assert_eq!(
+            cfg.refund_policy(),
+            RefundPolicy {
+                cap: RefundCap::PreLondon,
+                selfdestruct_refund: true,
+            }
+        );
+        let cfg = CfgEnv::new_with_spec(SpecId::LONDON);
+        assert_eq!(cfg.refund_policy().cap, RefundCap::Eip3529);
+        assert!(!cfg.refund_policy().selfdestruct_refund);
+        let policy = RefundPolicy::default().with_cap(RefundCap::Quotient(10));
+        let cfg = cfg.with_refund_policy(policy);
+        assert_eq!(cfg.refund_policy(), policy);
+        assert_eq!(cfg.refund_policy().cap.quotient(), 10);
+    }
+
+    #[test]
+    fn selfdestruct_mode() {
+        let cfg = CfgEnv::new_with_spec(SpecId::SHANGHAI);
+        assert_eq!(cfg.selfdestruct_mode(), SelfdestructMode::Destroy);
+        let cfg = CfgEnv::new_with_spec(SpecId::CANCUN);
+        assert_eq!(cfg.selfdestruct_mode(), SelfdestructMode::SameTxOnly);
+        let cfg = cfg.with_selfdestruct_mode(SelfdestructMode::Disabled);
+        assert_eq!(cfg.selfdestruct_mode(), SelfdestructMode::Disabled);
+    }
+
+    #[test]
+    fn chain_id_policy() {
+        let cfg: CfgEnv = Default::default();
+        assert_eq!(cfg.chain_id_policy(), ChainIdPolicy::Disabled);
+        let cfg = cfg.with_chain_id_policy(ChainIdPolicy::Strict);
+        assert!(cfg.tx_chain_id_check());
+        assert!(cfg.is_chain_id_accepted(1));
+        assert!(!cfg.is_chain_id_accepted(10));
+        let cfg = cfg.with_chain_id_policy(ChainIdPolicy::AnyOf(vec![10, 8453]));
+        assert!(cfg.is_chain_id_accepted(1));
+        assert!(cfg.is_chain_id_accepted(8453));
+        assert!(!cfg.is_chain_id_accepted(56));
+        assert_eq!(cfg.chain_id_policy(), ChainIdPolicy::AnyOf(vec![10, 8453]));
+    }
 }
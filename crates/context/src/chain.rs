@@ -0,0 +1,237 @@
+//! This module contains [`ChainExtensions`] and implements [`ChainData`] trait for it.
+pub use context_interface::ChainData;
+
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+use primitives::{HashMap, U256};
+use std::boxed::Box;
+
+/// Value stored in [`ChainExtensions`].
+trait ExtensionValue: Any {
+    fn clone_box(&self) -> Box<dyn ExtensionValue>;
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<T: Any + Clone + fmt::Debug> ExtensionValue for T {
+    fn clone_box(&self) -> Box<dyn ExtensionValue> {
+        Box::new(self.clone())
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+struct Extension {
+    /// Block the value was loaded for, `None` if it is not bound to a block.
+    block_number: Option<U256>,
+    value: Box<dyn ExtensionValue>,
+}
+
+impl Clone for Extension {
+    fn clone(&self) -> Self {
+        Self {
+            block_number: self.block_number,
+            value: self.value.clone_box(),
+        }
+    }
+}
+
+impl fmt::Debug for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Value<'a>(&'a dyn ExtensionValue);
+        impl fmt::Debug for Value<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.debug(f)
+            }
+        }
+        f.debug_struct("Extension")
+            .field("block_number", &self.block_number)
+            .field("value", &Value(self.value.as_ref()))
+            .finish()
+    }
+}
+
+/// Chain field of the context that holds typed data, at most one value per type.
+///
+/// Chains that need auxiliary data next to the block, like oracle prices or data availability
+/// parameters, can use it instead of defining their own chain type. Values can be bound to a
+/// block number, so data read from the state is loaded once per block:
+///
+/// ```
+/// use revm_context::{Block, BlockEnv, ChainData, ChainExtensions};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct OraclePrice(u64);
+///
+/// let block = BlockEnv::default();
+/// let mut chain = ChainExtensions::new();
+/// if !chain.is_loaded_for_block::<OraclePrice>(block.number()) {
+///     // Read the price from the state of the block.
+///     chain.insert_for_block(block.number(), OraclePrice(100));
+/// }
+/// assert_eq!(chain.chain_data(), Some(&OraclePrice(100)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ChainExtensions {
+    extensions: HashMap<TypeId, Extension>,
+}
+
+impl ChainExtensions {
+    /// Creates an empty set of extensions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the set with the value inserted.
+    pub fn with<T: Any + Clone + fmt::Debug>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Returns the number of stored values.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// Returns `true` if no value is stored.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Inserts the value that is not bound to a block, returns the previous value of the type.
+    pub fn insert<T: Any + Clone + fmt::Debug>(&mut self, value: T) -> Option<T> {
+        self.insert_entry(None, value)
+    }
+
+    /// Inserts the value loaded for the block, returns the previous value of the type.
+    pub fn insert_for_block<T: Any + Clone + fmt::Debug>(
+        &mut self,
+        block_number: U256,
+        value: T,
+    ) -> Option<T> {
+        self.insert_entry(Some(block_number), value)
+    }
+
+    fn insert_entry<T: Any + Clone + fmt::Debug>(
+        &mut self,
+        block_number: Option<U256>,
+        value: T,
+    ) -> Option<T> {
+        let previous = self.extensions.insert(
+            TypeId::of::<T>(),
+            Extension {
+                block_number,
+                value: Box::new(value),
+            },
+        )?;
+        let value: Box<dyn Any> = previous.value;
+        value.downcast().ok().map(|value| *value)
+    }
+
+    /// Returns the value of the type.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        let value: &dyn Any = self.extensions.get(&TypeId::of::<T>())?.value.as_ref();
+        value.downcast_ref()
+    }
+
+    /// Returns the value of the type mutably.
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        let value: &mut dyn Any = self.extensions.get_mut(&TypeId::of::<T>())?.value.as_mut();
+        value.downcast_mut()
+    }
+
+    /// Removes the value of the type.
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        let value: Box<dyn Any> = self.extensions.remove(&TypeId::of::<T>())?.value;
+        value.downcast().ok().map(|value| *value)
+    }
+
+    /// Returns the block the value of the type was loaded for.
+    ///
+    /// Returns `None` if the value is not set or is not bound to a block.
+    pub fn block_number<T: Any>(&self) -> Option<U256> {
+        self.extensions.get(&TypeId::of::<T>())?.block_number
+    }
+
+    /// Returns `true` if the value of the type was loaded for the block.
+    pub fn is_loaded_for_block<T: Any>(&self, block_number: U256) -> bool {
+        self.block_number::<T>() == Some(block_number)
+    }
+
+    /// Returns the value loaded for the block, calling `load` if it was loaded for another block.
+    pub fn get_or_try_load_for_block<T, E>(
+        &mut self,
+        block_number: U256,
+        load: impl FnOnce() -> Result<T, E>,
+    ) -> Result<&mut T, E>
+    where
+        T: Any + Clone + fmt::Debug,
+    {
+        if !self.is_loaded_for_block::<T>(block_number) {
+            self.insert_for_block(block_number, load()?);
+        }
+        Ok(self.get_mut().expect("value is inserted"))
+    }
+}
+
+impl<T: Any> ChainData<T> for ChainExtensions {
+    fn chain_data(&self) -> Option<&T> {
+        self.get()
+    }
+
+    fn chain_data_mut(&mut self) -> Option<&mut T> {
+        self.get_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::format;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Price(u64);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct DaParams {
+        blob_fee: u64,
+    }
+
+    #[test]
+    fn chain_extensions() {
+        let mut chain = ChainExtensions::new().with(DaParams { blob_fee: 1 });
+        assert_eq!(chain.get::<Price>(), None);
+        assert_eq!(chain.insert_for_block(U256::from(1), Price(10)), None);
+        assert_eq!(chain.len(), 2);
+        assert!(chain.is_loaded_for_block::<Price>(U256::from(1)));
+        assert_eq!(chain.block_number::<DaParams>(), None);
+
+        let mut loads = 0;
+        let mut load = |block: u64| {
+            chain
+                .get_or_try_load_for_block(U256::from(block), || {
+                    loads += 1;
+                    Ok::<_, ()>(Price(block * 10))
+                })
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(load(1), Price(10));
+        assert_eq!(load(2), Price(20));
+        assert_eq!(loads, 1);
+
+        let cloned = chain.clone();
+        chain.get_mut::<DaParams>().unwrap().blob_fee = 2;
+        assert_eq!(
+            ChainData::<DaParams>::chain_data(&cloned),
+            Some(&DaParams { blob_fee: 1 })
+        );
+        assert_eq!(chain.remove::<DaParams>(), Some(DaParams { blob_fee: 2 }));
+        assert_eq!(chain.remove::<DaParams>(), None);
+        assert!(format!("{chain:?}").contains("Price(20)"));
+    }
+}
@@ -1,13 +1,19 @@
 //! This module contains [`Context`] struct and implements [`ContextTr`] trait for it.
-use crate::{block::BlockEnv, cfg::CfgEnv, journal::Journal, tx::TxEnv, LocalContext};
+use crate::{
+    block::BlockEnv, cfg::CfgEnv, chain::ChainExtensions, journal::Journal, tx::TxEnv, LocalContext,
+};
+use bytecode::Bytecode;
 use context_interface::{
+    cfg::{Eip7702CodePolicy, GasSchedule, RefundPolicy, SelfdestructMode},
     context::{ContextError, ContextSetters, SStoreResult, SelfDestructResult, StateLoad},
     journaled_state::AccountLoad,
     Block, Cfg, ContextTr, Host, JournalTr, LocalContextTr, Transaction, TransactionType,
 };
+use core::{any::Any, fmt};
 use database_interface::{Database, DatabaseRef, EmptyDB, WrapDatabaseRef};
 use derive_where::derive_where;
 use primitives::{hardfork::SpecId, Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
+use state::Account;
 
 /// EVM context contains data that EVM needs for execution.
 #[derive_where(Clone, Debug; BLOCK, CFG, CHAIN, TX, DB, JOURNAL, <DB as Database>::Error, LOCAL)]
@@ -146,6 +152,7 @@ impl<
 
     fn set_block(&mut self, block: Self::Block) {
         self.block = block;
+        self.select_spec_for_block();
     }
 }
 
@@ -251,7 +258,7 @@ where
         self,
         block: OB,
     ) -> Context<OB, TX, CFG, DB, JOURNAL, CHAIN, LOCAL> {
-        Context {
+        let mut context = Context {
             tx: self.tx,
             block,
             cfg: self.cfg,
@@ -259,7 +266,9 @@ where
             local: self.local,
             chain: self.chain,
             error: Ok(()),
-        }
+        };
+        context.select_spec_for_block();
+        context
     }
     /// Creates a new context with a new transaction type.
     pub fn with_tx<OTX: Transaction>(
@@ -296,7 +305,7 @@ where
         cfg: OCFG,
     ) -> Context<BLOCK, TX, OCFG, DB, JOURNAL, CHAIN, LOCAL> {
         self.journaled_state.set_spec_id(cfg.spec().into());
-        Context {
+        let mut context = Context {
             tx: self.tx,
             block: self.block,
             cfg,
@@ -304,7 +313,9 @@ where
             local: self.local,
             chain: self.chain,
             error: Ok(()),
-        }
+        };
+        context.select_spec_for_block();
+        context
     }
 
     /// Creates a new context with a new local context type.
@@ -329,8 +340,7 @@ where
     where
         F: FnOnce(&mut CFG),
     {
-        f(&mut self.cfg);
-        self.journaled_state.set_spec_id(self.cfg.spec().into());
+        self.modify_cfg(f);
         self
     }
 
@@ -390,6 +400,7 @@ where
         F: FnOnce(&mut BLOCK),
     {
         f(&mut self.block);
+        self.select_spec_for_block();
     }
 
     /// Modifies the context transaction.
@@ -407,6 +418,17 @@ where
     {
         f(&mut self.cfg);
         self.journaled_state.set_spec_id(self.cfg.spec().into());
+        self.select_spec_for_block();
+    }
+
+    /// Selects the spec of the current block if the configuration has a fork schedule.
+    fn select_spec_for_block(&mut self) {
+        if self
+            .cfg
+            .select_spec_for_block(self.block.number(), self.block.timestamp())
+        {
+            self.journaled_state.set_spec_id(self.cfg.spec().into());
+        }
     }
 
     /// Modifies the context chain.
@@ -442,6 +464,132 @@ where
     }
 }
 
+impl<BLOCK, TX, CFG, DB, JOURNAL, CHAIN, LOCAL> Context<BLOCK, TX, CFG, DB, JOURNAL, CHAIN, LOCAL>
+where
+    DB: Database,
+    JOURNAL: JournalTr<Database = DB>,
+    LOCAL: LocalContextTr,
+{
+    /// Sets the code of the account, the code hash is calculated from the code.
+    ///
+    /// Account changes made with `set_account_*` methods are written to the journal state before
+    /// execution, so they don't require a database with inserts like `CacheDB`. They are not
+    /// reverted when the next transaction fails and are part of its finalized state. Accounts
+    /// stay cold for the transaction.
+    pub fn set_account_code(&mut self, address: Address, code: Bytecode) -> Result<(), DB::Error> {
+        let account = self.load_account_for_change(address)?;
+        account.info.code_hash = code.hash_slow();
+        account.info.code = Some(code);
+        Ok(())
+    }
+
+    /// Sets the balance of the account.
+    ///
+    /// See [`Context::set_account_code`] for how the change is applied.
+    pub fn set_account_balance(
+        &mut self,
+        address: Address,
+        balance: U256,
+    ) -> Result<(), DB::Error> {
+        self.load_account_for_change(address)?.info.balance = balance;
+        Ok(())
+    }
+
+    /// Sets the nonce of the account.
+    ///
+    /// See [`Context::set_account_code`] for how the change is applied.
+    pub fn set_account_nonce(&mut self, address: Address, nonce: u64) -> Result<(), DB::Error> {
+        self.load_account_for_change(address)?.info.nonce = nonce;
+        Ok(())
+    }
+
+    /// Sets the value of the storage slot of the account.
+    ///
+    /// Slot is inserted as if the database returned the value, so it is unchanged and the gas
+    /// and refunds of `SSTORE` are the same as for a slot seeded in the database. Databases that
+    /// commit only changed slots don't persist it. See [`Context::set_account_code`] for how the
+    /// change is applied.
+    pub fn set_account_storage(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), DB::Error> {
+        self.load_account_for_change(address)?;
+        self.journaled_state
+            .insert_storage_slot(address, key, value)
+    }
+
+    /// Loads the account, marks it as touched so it is part of the finalized state and keeps it
+    /// cold for the transaction.
+    fn load_account_for_change(&mut self, address: Address) -> Result<&mut Account, DB::Error> {
+        let account = self.journaled_state.load_account(address)?.data;
+        account.mark_touch();
+        account.mark_cold();
+        Ok(account)
+    }
+}
+
+/// Resolves the account whose code is observed by EXTCODE* instructions under
+/// [`Eip7702CodePolicy::DelegateCode`].
+///
+/// Returns the delegate of an EIP-7702 delegated account and the address itself otherwise.
+/// Account is cold if the delegated account was cold.
+fn load_extcode_address<JOURNAL: JournalTr>(
+    journal: &mut JOURNAL,
+    address: Address,
+) -> Result<StateLoad<Address>, <JOURNAL::Database as Database>::Error> {
+    let account = journal.load_account_code(address)?;
+    let target = match &account.info.code {
+        Some(Bytecode::Eip7702(code)) => code.address(),
+        _ => address,
+    };
+    Ok(StateLoad::new(target, account.is_cold))
+}
+
+/// Loads the code observed by EXTCODESIZE and EXTCODECOPY with the given policy.
+fn load_extcode<JOURNAL: JournalTr>(
+    journal: &mut JOURNAL,
+    address: Address,
+    policy: Eip7702CodePolicy,
+) -> Result<StateLoad<Bytes>, <JOURNAL::Database as Database>::Error> {
+    if policy == Eip7702CodePolicy::Designator {
+        return journal.code(address);
+    }
+    let target = load_extcode_address(journal, address)?;
+    let mut code = journal.code(target.data)?;
+    code.is_cold |= target.is_cold;
+    Ok(code)
+}
+
+/// Loads the code hash observed by EXTCODEHASH with the given policy.
+fn load_extcode_hash<JOURNAL: JournalTr>(
+    journal: &mut JOURNAL,
+    address: Address,
+    policy: Eip7702CodePolicy,
+) -> Result<StateLoad<B256>, <JOURNAL::Database as Database>::Error> {
+    if policy == Eip7702CodePolicy::Designator {
+        return journal.code_hash(address);
+    }
+    let target = load_extcode_address(journal, address)?;
+    let mut code_hash = journal.code_hash(target.data)?;
+    code_hash.is_cold |= target.is_cold;
+    Ok(code_hash)
+}
+
+impl<BLOCK, TX, CFG, DB, JOURNAL, LOCAL>
+    Context<BLOCK, TX, CFG, DB, JOURNAL, ChainExtensions, LOCAL>
+where
+    DB: Database,
+    JOURNAL: JournalTr<Database = DB>,
+    LOCAL: LocalContextTr,
+{
+    /// Inserts the value into the chain extensions.
+    pub fn with_chain_data<T: Any + Clone + fmt::Debug>(mut self, value: T) -> Self {
+        self.chain.insert(value);
+        self
+    }
+}
 impl<
         BLOCK: Block,
         TX: Transaction,
@@ -517,6 +665,28 @@ impl<
         self.cfg().max_initcode_size()
     }
 
+    fn selfdestruct_mode(&self) -> SelfdestructMode {
+        self.cfg().selfdestruct_mode()
+    }
+
+    fn refund_policy(&self) -> RefundPolicy {
+        self.cfg().refund_policy()
+    }
+
+    fn gas_schedule(&self) -> &GasSchedule {
+        self.cfg().gas_schedule()
+    }
+
+    /* Hashing */
+
+    fn keccak256(&mut self, input: &[u8]) -> B256 {
+        let capacity = self.cfg.keccak_cache_size();
+        match self.local.keccak_cache() {
+            Some(cache) => cache.hash(input, capacity),
+            None => primitives::keccak256(input),
+        }
+    }
+
     /* Database */
 
     fn block_hash(&mut self, requested_number: u64) -> Option<B256> {
@@ -552,8 +722,8 @@ impl<
 
     /// Gets code of `address` and if the account is cold.
     fn load_account_code(&mut self, address: Address) -> Option<StateLoad<Bytes>> {
-        self.journal_mut()
-            .code(address)
+        let policy = self.cfg().eip7702_code_policy();
+        load_extcode(self.journal_mut(), address, policy)
             .map_err(|e| {
                 *self.error() = Err(e.into());
             })
@@ -562,8 +732,8 @@ impl<
 
     /// Gets code hash of `address` and if the account is cold.
     fn load_account_code_hash(&mut self, address: Address) -> Option<StateLoad<B256>> {
-        self.journal_mut()
-            .code_hash(address)
+        let policy = self.cfg().eip7702_code_policy();
+        load_extcode_hash(self.journal_mut(), address, policy)
             .map_err(|e| {
                 *self.error() = Err(e.into());
             })
@@ -618,8 +788,9 @@ impl<
         address: Address,
         target: Address,
     ) -> Option<StateLoad<SelfDestructResult>> {
+        let mode = self.cfg().selfdestruct_mode();
         self.journal_mut()
-            .selfdestruct(address, target)
+            .selfdestruct_with_mode(address, target, mode)
             .map_err(|e| {
                 *self.error() = Err(e.into());
             })
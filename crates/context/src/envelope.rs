@@ -0,0 +1,221 @@
+//! Decoding of signed EIP-2718 transaction envelopes into [`TxEnv`].
+use crate::{tx::TxEnvBuilder, TxEnv};
+use alloy_consensus::{
+    crypto::RecoveryError, transaction::SignerRecoverable, Transaction as AlloyTransaction,
+    TxEip4844Variant, TxEnvelope,
+};
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error};
+use context_interface::{either::Either, transaction::BlobSidecar};
+use core::fmt;
+use primitives::{Address, Bytes};
+
+/// Error returned by [`TxEnv::decode_2718`].
+#[derive(Debug)]
+pub enum TxDecodeError {
+    /// Envelope is not a valid EIP-2718 or legacy RLP transaction.
+    Decode(Eip2718Error),
+    /// Signer could not be recovered from the signature.
+    Recovery(RecoveryError),
+}
+
+impl From<Eip2718Error> for TxDecodeError {
+    fn from(value: Eip2718Error) -> Self {
+        Self::Decode(value)
+    }
+}
+
+impl From<RecoveryError> for TxDecodeError {
+    fn from(value: RecoveryError) -> Self {
+        Self::Recovery(value)
+    }
+}
+
+impl core::error::Error for TxDecodeError {}
+
+impl fmt::Display for TxDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(e) => write!(f, "invalid transaction envelope: {e}"),
+            Self::Recovery(e) => write!(f, "invalid transaction signature: {e}"),
+        }
+    }
+}
+
+impl TxEnv {
+    /// Decodes the signed EIP-2718 envelope or legacy RLP transaction and recovers its signer.
+    ///
+    /// EIP-4844 transactions in network encoding keep their blobs in
+    /// [`TxEnv::blob_sidecar`].
+    pub fn decode_2718(mut bytes: &[u8]) -> Result<Self, TxDecodeError> {
+        let envelope = TxEnvelope::decode_2718(&mut bytes)?;
+        Self::from_envelope(&envelope)
+    }
+
+    /// Recovers the signer of the signed transaction and converts it.
+    pub fn from_envelope(envelope: &TxEnvelope) -> Result<Self, TxDecodeError> {
+        let caller = envelope.recover_signer()?;
+        Ok(Self::from_recovered_envelope(envelope, caller))
+    }
+
+    /// Converts the signed transaction whose signer was already recovered.
+    ///
+    /// Unlike [`TxEnv::from_recovered_tx`], blobs of EIP-4844 transactions in network encoding
+    /// are kept in [`TxEnv::blob_sidecar`].
+    pub fn from_recovered_envelope(envelope: &TxEnvelope, caller: Address) -> Self {
+        let mut tx = Self::from_recovered_tx(envelope, caller);
+        if let TxEnvelope::Eip4844(signed) = envelope {
+            if let TxEip4844Variant::TxEip4844WithSidecar(tx_with_sidecar) = signed.tx() {
+                let sidecar = &tx_with_sidecar.sidecar;
+                tx.blob_sidecar = Some(BlobSidecar::new(
+                    sidecar
+                        .blobs
+                        .iter()
+                        .map(|blob| Bytes::copy_from_slice(blob.as_slice()))
+                        .collect(),
+                    sidecar.commitments.clone(),
+                    sidecar.proofs.clone(),
+                ));
+            }
+        }
+        tx
+    }
+
+    /// Converts the transaction signed by `caller`.
+    ///
+    /// For EIP-1559 and later transactions [`TxEnv::gas_price`] is the max fee per gas.
+    pub fn from_recovered_tx(tx: &impl AlloyTransaction, caller: Address) -> Self {
+        TxEnvBuilder::new()
+            .tx_type(Some(tx.ty()))
+            .caller(caller)
+            .gas_limit(tx.gas_limit())
+            .gas_price(tx.max_fee_per_gas())
+            .gas_priority_fee(tx.max_priority_fee_per_gas())
+            .kind(tx.kind())
+            .value(tx.value())
+            .data(tx.input().clone())
+            .nonce(tx.nonce())
+            .chain_id(tx.chain_id())
+            .access_list(tx.access_list().cloned().unwrap_or_default())
+            .blob_hashes(tx.blob_versioned_hashes().unwrap_or_default().to_vec())
+            .max_fee_per_blob_gas(tx.max_fee_per_blob_gas().unwrap_or_default())
+            .authorization_list(
+                tx.authorization_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .cloned()
+                    .map(Either::Left)
+                    .collect(),
+            )
+            .build_fill()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxEip7702, TxLegacy};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_signer::{Signature, SignerSync};
+    use alloy_signer_local::PrivateKeySigner;
+    use context_interface::transaction::{
+        AccessList, AccessListItem, Authorization, SignedAuthorization, Transaction,
+        TransactionType,
+    };
+    use primitives::{address, TxKind, B256, U256};
+
+    fn sign<T: SignableTransaction<Signature>>(signer: &PrivateKeySigner, tx: T) -> Vec<u8>
+    where
+        TxEnvelope: From<alloy_consensus::Signed<T>>,
+    {
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TxEnvelope::from(tx.into_signed(signature)).encoded_2718()
+    }
+
+    #[test]
+    fn decode_2718() {
+        let signer = PrivateKeySigner::random();
+        let to = address!("0x00000000000000000000000000000000000000aa");
+
+        let legacy = TxEnv::decode_2718(&sign(
+            &signer,
+            TxLegacy {
+                chain_id: Some(1),
+                nonce: 2,
+                gas_price: 10,
+                gas_limit: 50_000,
+                to: TxKind::Call(to),
+                value: U256::from(3),
+                input: Bytes::from_static(&[1, 2]),
+            },
+        ))
+        .unwrap();
+        assert_eq!(legacy.tx_type, TransactionType::Legacy as u8);
+        assert_eq!(legacy.caller, signer.address());
+        assert_eq!(legacy.gas_price, 10);
+        assert_eq!(legacy.gas_priority_fee, None);
+        assert_eq!(legacy.kind, TxKind::Call(to));
+        assert_eq!(legacy.input(), &Bytes::from_static(&[1, 2]));
+
+        let access_list = AccessList(vec![AccessListItem {
+            address: to,
+            storage_keys: vec![B256::ZERO],
+        }]);
+        let eip1559 = TxEnv::decode_2718(&sign(
+            &signer,
+            TxEip1559 {
+                chain_id: 1,
+                nonce: 3,
+                gas_limit: 50_000,
+                max_fee_per_gas: 20,
+                max_priority_fee_per_gas: 1,
+                to: TxKind::Create,
+                access_list: access_list.clone(),
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        assert_eq!(eip1559.tx_type, TransactionType::Eip1559 as u8);
+        assert_eq!(eip1559.gas_price, 20);
+        assert_eq!(eip1559.gas_priority_fee, Some(1));
+        assert_eq!(eip1559.kind, TxKind::Create);
+        assert_eq!(eip1559.access_list, access_list);
+
+        let authorization = Authorization {
+            chain_id: U256::from(1),
+            address: to,
+            nonce: 0,
+        };
+        let signature = signer
+            .sign_hash_sync(&authorization.signature_hash())
+            .unwrap();
+        let eip7702 = TxEnv::decode_2718(&sign(
+            &signer,
+            TxEip7702 {
+                chain_id: 1,
+                to,
+                authorization_list: vec![authorization.clone().into_signed(signature)],
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        assert_eq!(eip7702.tx_type, TransactionType::Eip7702 as u8);
+        assert_eq!(eip7702.authorization_list_len(), 1);
+        let Either::Left(signed) = &eip7702.authorization_list[0] else {
+            panic!("authorization is not signed");
+        };
+        assert_eq!(
+            signed,
+            &SignedAuthorization::new_unchecked(
+                authorization,
+                signature.v() as u8,
+                signature.r(),
+                signature.s()
+            )
+        );
+
+        assert!(matches!(
+            TxEnv::decode_2718(&[0x02, 0xc0]),
+            Err(TxDecodeError::Decode(_))
+        ));
+    }
+}
@@ -2,21 +2,31 @@
 //!
 //! Entry submodule contains [`JournalEntry`] and [`JournalEntryTr`] traits.
 //! and inner submodule contains [`JournalInner`] struct that contains state.
+//! Access log submodule records accounts and slots accessed by transactions.
+//! Slot cache submodule contains the cache of hot storage slots, enabled with the `slot_cache`
+//! feature.
+pub mod access_log;
 pub mod entry;
 pub mod inner;
+#[cfg(feature = "slot_cache")]
+pub mod slot_cache;
 
 pub use entry::{JournalEntry, JournalEntryTr};
 pub use inner::JournalInner;
 
 use bytecode::Bytecode;
 use context_interface::{
+    cfg::{FinalizedStateMode, SelfdestructMode},
     context::{SStoreResult, SelfDestructResult, StateLoad},
-    journaled_state::{AccountLoad, JournalCheckpoint, JournalTr, TransferError},
+    journaled_state::{
+        AccessLog, AccountLoad, AppliedDelegation, JournalCheckpoint, JournalTr, TransferError,
+    },
+    result::DestructedAccount,
 };
 use core::ops::{Deref, DerefMut};
 use database_interface::Database;
 use primitives::{hardfork::SpecId, Address, HashSet, Log, StorageKey, StorageValue, B256, U256};
-use state::{Account, EvmState};
+use state::{Account, EvmState, EvmStorageSlot};
 use std::vec::Vec;
 
 /// A journal of state changes internal to the EVM
@@ -140,6 +150,16 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.selfdestruct(&mut self.database, address, target)
     }
 
+    fn selfdestruct_with_mode(
+        &mut self,
+        address: Address,
+        target: Address,
+        mode: SelfdestructMode,
+    ) -> Result<StateLoad<SelfDestructResult>, DB::Error> {
+        self.inner
+            .selfdestruct_with_mode(&mut self.database, address, target, mode)
+    }
+
     fn warm_account(&mut self, address: Address) {
         self.inner.warm_preloaded_addresses.insert(address);
     }
@@ -166,6 +186,21 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.depth
     }
 
+    #[inline]
+    fn account_existed(&self, address: Address) -> Option<bool> {
+        self.inner.account_existed(address)
+    }
+
+    #[inline]
+    fn is_account_created(&self, address: Address) -> Option<bool> {
+        self.inner.is_account_created(address)
+    }
+
+    #[inline]
+    fn is_account_touched_and_empty(&self, address: Address) -> Option<bool> {
+        self.inner.is_account_touched_and_empty(address)
+    }
+
     #[inline]
     fn warm_account_and_storage(
         &mut self,
@@ -177,11 +212,21 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         Ok(())
     }
 
+    #[inline]
+    fn prefetch_accounts(&mut self, addresses: &[Address]) -> Result<(), DB::Error> {
+        self.inner.prefetch_accounts(&mut self.database, addresses)
+    }
+
     #[inline]
     fn set_spec_id(&mut self, spec_id: SpecId) {
         self.inner.spec = spec_id;
     }
 
+    #[inline]
+    fn set_finalized_state_mode(&mut self, mode: FinalizedStateMode) {
+        self.inner.set_finalized_state_mode(mode);
+    }
+
     #[inline]
     fn transfer(
         &mut self,
@@ -230,6 +275,22 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.load_account(&mut self.database, address)
     }
 
+    #[inline]
+    fn insert_storage_slot(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), DB::Error> {
+        let account = self.inner.load_account(&mut self.database, address)?.data;
+        let mut slot = EvmStorageSlot::new(value, account.transaction_id);
+        slot.mark_cold();
+        account.storage.insert(key, slot);
+        #[cfg(feature = "slot_cache")]
+        self.inner.slot_cache.invalidate();
+        Ok(())
+    }
+
     #[inline]
     fn load_account_code(
         &mut self,
@@ -285,6 +346,31 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.take_logs()
     }
 
+    #[inline]
+    fn take_destructed_accounts(&mut self) -> Vec<DestructedAccount> {
+        self.inner.take_destructed_accounts()
+    }
+
+    #[inline]
+    fn record_delegation(&mut self, delegation: AppliedDelegation) {
+        self.inner.record_delegation(delegation);
+    }
+
+    #[inline]
+    fn take_applied_delegations(&mut self) -> Vec<AppliedDelegation> {
+        self.inner.take_applied_delegations()
+    }
+
+    #[inline]
+    fn start_access_log(&mut self) {
+        self.inner.start_access_log();
+    }
+
+    #[inline]
+    fn take_access_logs(&mut self) -> Vec<AccessLog> {
+        self.inner.take_access_logs()
+    }
+
     #[inline]
     fn commit_tx(&mut self) {
         self.inner.commit_tx()
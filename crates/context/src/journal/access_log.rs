@@ -0,0 +1,65 @@
+//! Recording of the [`AccessLog`] of the current transaction.
+use context_interface::journaled_state::{AccessLog, AccountAccess, SlotAccess};
+use core::mem;
+use primitives::{Address, HashMap, StorageKey};
+use std::vec::Vec;
+
+/// Builds the [`AccessLog`] of the current transaction.
+///
+/// Indexes of accounts and slots are kept next to the log so repeated accesses are found in
+/// constant time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessLogRecorder {
+    log: AccessLog,
+    accounts: HashMap<Address, usize>,
+    slots: HashMap<(Address, StorageKey), usize>,
+}
+
+impl AccessLogRecorder {
+    /// Records access to the account, `cold` is used only if this is the first access.
+    pub fn account(&mut self, address: Address, cold: bool) -> &mut AccountAccess {
+        let index = self.account_index(address, cold);
+        &mut self.log.accounts[index]
+    }
+
+    /// Records a change of the balance, nonce or code of the account.
+    pub fn account_written(&mut self, address: Address) {
+        self.account(address, false).written = true;
+    }
+
+    /// Records access to the storage slot, `cold` is used only if this is the first access.
+    pub fn slot(&mut self, address: Address, key: StorageKey, cold: bool) -> &mut SlotAccess {
+        let index = self.account_index(address, false);
+        let account = &mut self.log.accounts[index];
+        let index = *self.slots.entry((address, key)).or_insert_with(|| {
+            account.slots.push(SlotAccess {
+                key,
+                cold,
+                written: false,
+            });
+            account.slots.len() - 1
+        });
+        &mut account.slots[index]
+    }
+
+    /// Returns the log of the transaction and clears the recorder for the next transaction.
+    pub fn take(&mut self) -> AccessLog {
+        self.accounts.clear();
+        self.slots.clear();
+        mem::take(&mut self.log)
+    }
+
+    fn account_index(&mut self, address: Address, cold: bool) -> usize {
+        let accounts = &mut self.log.accounts;
+        *self.accounts.entry(address).or_insert_with(|| {
+            accounts.push(AccountAccess {
+                address,
+                cold,
+                written: false,
+                slots: Vec::new(),
+            });
+            accounts.len() - 1
+        })
+    }
+}
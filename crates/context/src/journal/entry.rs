@@ -5,6 +5,7 @@
 //! They are created when there is change to the state from loading (making it warm), changes to the balance,
 //! or removal of the storage slot. Check [`JournalEntryTr`] for more details.
 
+use context_interface::result::DestructedAccount;
 use primitives::{Address, StorageKey, StorageValue, KECCAK_EMPTY, PRECOMPILE3, U256};
 use state::{EvmState, TransientStorage};
 
@@ -60,6 +61,11 @@ pub trait JournalEntryTr {
     /// Creates a journal entry for when an account's code is modified
     fn code_changed(address: Address) -> Self;
 
+    /// Returns the destructed account if the entry records a destruction by `SELFDESTRUCT`.
+    fn destructed_account(&self) -> Option<DestructedAccount> {
+        None
+    }
+
     /// Reverts the state change recorded by this journal entry
     ///
     /// More information on what is reverted can be found in [`JournalEntry`] enum.
@@ -287,6 +293,22 @@ impl JournalEntryTr for JournalEntry {
         JournalEntry::CodeChange { address }
     }
 
+    fn destructed_account(&self) -> Option<DestructedAccount> {
+        match *self {
+            JournalEntry::AccountDestroyed {
+                had_balance,
+                address,
+                target,
+                ..
+            } => Some(DestructedAccount {
+                address,
+                target,
+                balance: had_balance,
+            }),
+            _ => None,
+        }
+    }
+
     fn revert(
         self,
         state: &mut EvmState,
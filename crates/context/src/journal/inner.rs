@@ -1,11 +1,18 @@
 //! Module containing the [`JournalInner`] that is part of [`crate::Journal`].
+use super::access_log::AccessLogRecorder;
+#[cfg(feature = "slot_cache")]
+use super::slot_cache::SlotCache;
 use crate::entry::SelfdestructionRevertStatus;
 
 use super::JournalEntryTr;
 use bytecode::Bytecode;
 use context_interface::{
+    cfg::{FinalizedStateMode, SelfdestructMode},
     context::{SStoreResult, SelfDestructResult, StateLoad},
-    journaled_state::{AccountLoad, JournalCheckpoint, TransferError},
+    journaled_state::{
+        AccessLog, AccountLoad, AppliedDelegation, JournalCheckpoint, TransferError,
+    },
+    result::DestructedAccount,
 };
 use core::mem;
 use database_interface::Database;
@@ -14,7 +21,7 @@ use primitives::{
     hash_map::Entry,
     Address, HashMap, HashSet, Log, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
-use state::{Account, EvmState, EvmStorageSlot, TransientStorage};
+use state::{Account, AccountInfo, EvmState, EvmStorageSlot, TransientStorage};
 use std::vec::Vec;
 /// Inner journal state that contains journal and state changes.
 ///
@@ -53,6 +60,14 @@ pub struct JournalInner<ENTRY> {
     /// [EIP-161]: https://eips.ethereum.org/EIPS/eip-161
     /// [EIP-6780]: https://eips.ethereum.org/EIPS/eip-6780
     pub spec: SpecId,
+    /// Accounts included in the state returned by [`JournalInner::finalize`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub finalized_state_mode: FinalizedStateMode,
+    /// Account information at the time the account was loaded, without code.
+    ///
+    /// Recorded only with [`FinalizedStateMode::Changed`] to find changed accounts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub original_infos: HashMap<Address, AccountInfo>,
     /// Warm loaded addresses are used to check if loaded address
     /// should be considered cold or warm loaded when the account
     /// is first accessed.
@@ -64,6 +79,26 @@ pub struct JournalInner<ENTRY> {
     pub warm_coinbase_address: Option<Address>,
     /// Precompile addresses
     pub precompiles: HashSet<Address>,
+    /// Accounts destructed by transactions committed since the journal was finalized.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub destructed_accounts: Vec<DestructedAccount>,
+    /// Delegations applied by EIP-7702 authorizations of the current transaction.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tx_delegations: Vec<AppliedDelegation>,
+    /// Delegations applied by transactions committed since the journal was finalized.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub applied_delegations: Vec<AppliedDelegation>,
+    /// Access log of the current transaction, recorded only if it is started with
+    /// [`JournalInner::start_access_log`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub access_log: Option<AccessLogRecorder>,
+    /// Access logs of transactions committed since the logs were taken.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub access_logs: Vec<AccessLog>,
+    /// Cache of hot storage slots of the current transaction.
+    #[cfg(feature = "slot_cache")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub slot_cache: SlotCache,
 }
 
 impl<ENTRY: JournalEntryTr> Default for JournalInner<ENTRY> {
@@ -86,9 +121,46 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             transaction_id: 0,
             depth: 0,
             spec: SpecId::default(),
+            finalized_state_mode: FinalizedStateMode::Loaded,
+            original_infos: HashMap::default(),
             warm_preloaded_addresses: HashSet::default(),
             precompiles: HashSet::default(),
             warm_coinbase_address: None,
+            destructed_accounts: Vec::new(),
+            tx_delegations: Vec::new(),
+            applied_delegations: Vec::new(),
+            access_log: None,
+            access_logs: Vec::new(),
+            #[cfg(feature = "slot_cache")]
+            slot_cache: SlotCache::default(),
+        }
+    }
+
+    /// Starts recording the access log of every transaction committed from now on.
+    #[inline]
+    pub fn start_access_log(&mut self) {
+        self.access_log.get_or_insert_with(Default::default);
+    }
+
+    /// Stops recording access logs and returns the logs of committed transactions that were
+    /// not taken yet.
+    #[inline]
+    pub fn stop_access_log(&mut self) -> Vec<AccessLog> {
+        self.access_log = None;
+        mem::take(&mut self.access_logs)
+    }
+
+    /// Takes the access logs of transactions committed since the logs were taken.
+    #[inline]
+    pub fn take_access_logs(&mut self) -> Vec<AccessLog> {
+        mem::take(&mut self.access_logs)
+    }
+
+    /// Records a change of the balance, nonce or code of the account in the access log.
+    #[inline]
+    fn record_account_write(&mut self, address: Address) {
+        if let Some(access_log) = &mut self.access_log {
+            access_log.account_written(address);
         }
     }
 
@@ -98,6 +170,26 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         mem::take(&mut self.logs)
     }
 
+    /// Takes the accounts destructed by transactions committed since the journal was finalized
+    /// or the accounts were taken.
+    #[inline]
+    pub fn take_destructed_accounts(&mut self) -> Vec<DestructedAccount> {
+        mem::take(&mut self.destructed_accounts)
+    }
+
+    /// Records the delegation applied by an EIP-7702 authorization of the current transaction.
+    #[inline]
+    pub fn record_delegation(&mut self, delegation: AppliedDelegation) {
+        self.tx_delegations.push(delegation);
+    }
+
+    /// Takes the delegations applied by transactions committed since the journal was finalized
+    /// or the delegations were taken.
+    #[inline]
+    pub fn take_applied_delegations(&mut self) -> Vec<AppliedDelegation> {
+        mem::take(&mut self.applied_delegations)
+    }
+
     /// Prepare for next transaction, by committing the current journal to history, incrementing the transaction id
     /// and returning the logs.
     ///
@@ -116,17 +208,35 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             journal,
             transaction_id,
             spec,
+            finalized_state_mode,
+            original_infos,
             warm_preloaded_addresses,
             precompiles,
             warm_coinbase_address,
+            destructed_accounts,
+            tx_delegations,
+            applied_delegations,
+            access_log,
+            access_logs,
+            #[cfg(feature = "slot_cache")]
+            slot_cache,
         } = self;
         // Spec precompiles and state are not changed. It is always set again execution.
         let _ = spec;
+        let _ = finalized_state_mode;
+        let _ = original_infos;
         let _ = precompiles;
         let _ = state;
         transient_storage.clear();
         *depth = 0;
 
+        // Entries of reverted calls are already removed, the rest are committed.
+        destructed_accounts.extend(journal.iter().filter_map(ENTRY::destructed_account));
+        applied_delegations.append(tx_delegations);
+        if let Some(access_log) = access_log {
+            access_logs.push(access_log.take());
+        }
+
         // Do nothing with journal history so we can skip cloning present journal.
         journal.clear();
 
@@ -139,6 +249,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         // increment transaction id.
         *transaction_id += 1;
         logs.clear();
+        #[cfg(feature = "slot_cache")]
+        slot_cache.invalidate();
     }
 
     /// Discard the current transaction, by reverting the journal entries and incrementing the transaction id.
@@ -152,10 +264,29 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             journal,
             transaction_id,
             spec,
+            finalized_state_mode,
+            original_infos,
             warm_preloaded_addresses,
             warm_coinbase_address,
             precompiles,
+            destructed_accounts,
+            tx_delegations,
+            applied_delegations,
+            access_log,
+            access_logs,
+            #[cfg(feature = "slot_cache")]
+            slot_cache,
         } = self;
+        let _ = destructed_accounts;
+        let _ = applied_delegations;
+        let _ = access_logs;
+        tx_delegations.clear();
+        if let Some(access_log) = access_log {
+            access_log.take();
+        }
+        let _ = finalized_state_mode;
+        // Loaded accounts stay in the state, so their original information is kept.
+        let _ = original_infos;
 
         let is_spurious_dragon_enabled = spec.is_enabled_in(SPURIOUS_DRAGON);
         // iterate over all journals entries and revert our global state
@@ -169,6 +300,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         // Clear coinbase address warming for next tx
         *warm_coinbase_address = None;
         reset_preloaded_addresses(warm_preloaded_addresses, precompiles);
+        #[cfg(feature = "slot_cache")]
+        slot_cache.invalidate();
     }
 
     /// Take the [`EvmState`] and clears the journal by resetting it to initial state.
@@ -187,18 +320,48 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             journal,
             transaction_id,
             spec,
+            finalized_state_mode,
+            original_infos,
             warm_preloaded_addresses,
             warm_coinbase_address,
             precompiles,
+            destructed_accounts,
+            tx_delegations,
+            applied_delegations,
+            access_log,
+            access_logs,
+            #[cfg(feature = "slot_cache")]
+            slot_cache,
         } = self;
         // Spec is not changed. And it is always set again in execution.
         let _ = spec;
+        // Access logs are kept until they are taken.
+        let _ = access_log;
+        let _ = access_logs;
+        destructed_accounts.clear();
+        tx_delegations.clear();
+        applied_delegations.clear();
         // Clear coinbase address warming for next tx
         *warm_coinbase_address = None;
         // Load precompiles into warm_preloaded_addresses.
         reset_preloaded_addresses(warm_preloaded_addresses, precompiles);
 
-        let state = mem::take(state);
+        let mut state = mem::take(state);
+        match finalized_state_mode {
+            FinalizedStateMode::Loaded => {}
+            FinalizedStateMode::Touched => state.retain(|_, account| account.is_touched()),
+            FinalizedStateMode::Changed => {
+                let is_spurious_dragon_enabled = spec.is_enabled_in(SPURIOUS_DRAGON);
+                state.retain(|address, account| {
+                    is_account_changed(
+                        account,
+                        original_infos.get(address),
+                        is_spurious_dragon_enabled,
+                    )
+                })
+            }
+        }
+        original_infos.clear();
         logs.clear();
         transient_storage.clear();
 
@@ -207,6 +370,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         *depth = 0;
         // reset transaction id.
         *transaction_id = 0;
+        #[cfg(feature = "slot_cache")]
+        slot_cache.invalidate();
 
         state
     }
@@ -223,6 +388,15 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         self.spec = spec;
     }
 
+    /// Sets which accounts are included in the state returned by [`JournalInner::finalize`].
+    ///
+    /// Changed accounts are found by comparing with the account information at load, so the mode
+    /// should be set before accounts are loaded.
+    #[inline]
+    pub fn set_finalized_state_mode(&mut self, mode: FinalizedStateMode) {
+        self.finalized_state_mode = mode;
+    }
+
     /// Mark account as touched as only touched accounts will be added to state.
     /// This is especially important for state clear where touched empty accounts needs to
     /// be removed from state.
@@ -256,6 +430,34 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             .expect("Account expected to be loaded") // Always assume that acc is already loaded
     }
 
+    /// Returns `true` if the account existed in the database, `None` if it is not loaded.
+    #[inline]
+    pub fn account_existed(&self, address: Address) -> Option<bool> {
+        self.state
+            .get(&address)
+            .map(|account| !account.is_loaded_as_not_existing())
+    }
+
+    /// Returns `true` if the account was created in the current transaction, `None` if it is
+    /// not loaded.
+    ///
+    /// Creation flag of the account is left over from previous transactions until the account
+    /// is loaded again, so the transaction id of the account is checked as well.
+    #[inline]
+    pub fn is_account_created(&self, address: Address) -> Option<bool> {
+        self.state.get(&address).map(|account| {
+            account.transaction_id == self.transaction_id && account.is_created_locally()
+        })
+    }
+
+    /// Returns `true` if the account is touched and empty, `None` if it is not loaded.
+    #[inline]
+    pub fn is_account_touched_and_empty(&self, address: Address) -> Option<bool> {
+        self.state
+            .get(&address)
+            .map(|account| account.is_touched() && account.is_empty())
+    }
+
     /// Set code and its hash to the account.
     ///
     /// Note: Assume account is warm and that hash is calculated from code.
@@ -268,6 +470,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         account.info.code_hash = hash;
         account.info.code = Some(code);
+        self.record_account_write(address);
     }
 
     /// Use it only if you know that acc is warm.
@@ -306,6 +509,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             // nonce changed.
             self.journal.push(ENTRY::nonce_changed(address));
         }
+        self.record_account_write(address);
     }
 
     /// Increments the balance of the account.
@@ -331,6 +535,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         // add journal entry for balance increment.
         self.journal
             .push(ENTRY::balance_changed(address, old_balance));
+        self.record_account_write(address);
         Ok(())
     }
 
@@ -338,6 +543,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
     #[inline]
     pub fn nonce_bump_journal_entry(&mut self, address: Address) {
         self.journal.push(ENTRY::nonce_changed(address));
+        self.record_account_write(address);
     }
 
     /// Transfers balance from two accounts. Returns error if sender balance is not enough.
@@ -381,6 +587,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         self.journal
             .push(ENTRY::balance_transfer(from, to, balance));
+        self.record_account_write(from);
+        self.record_account_write(to);
 
         Ok(None)
     }
@@ -432,6 +640,10 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             return Err(TransferError::CreateCollision);
         }
 
+        // Storage of the created account is cleared.
+        #[cfg(feature = "slot_cache")]
+        self.slot_cache.invalidate();
+
         // set account status to create.
         let is_created_globally = target_acc.mark_created_locally();
 
@@ -460,6 +672,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         // add journal entry of transferred balance
         last_journal.push(ENTRY::balance_transfer(caller, target_address, balance));
+        self.record_account_write(caller);
+        self.record_account_write(target_address);
 
         Ok(checkpoint)
     }
@@ -489,6 +703,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         let transient_storage = &mut self.transient_storage;
         self.depth -= 1;
         self.logs.truncate(checkpoint.log_i);
+        #[cfg(feature = "slot_cache")]
+        self.slot_cache.invalidate();
 
         // iterate over last N journals sets and revert our global state
         self.journal
@@ -516,6 +732,22 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         db: &mut DB,
         address: Address,
         target: Address,
+    ) -> Result<StateLoad<SelfDestructResult>, DB::Error> {
+        let mode = SelfdestructMode::from_spec(self.spec);
+        self.selfdestruct_with_mode(db, address, target, mode)
+    }
+
+    /// Performs selfdestruct action with the given behaviour, see [`JournalInner::selfdestruct`].
+    ///
+    /// [`SelfdestructMode::Disabled`] is handled by the instruction before the journal is
+    /// called, the journal treats it as [`SelfdestructMode::SameTxOnly`].
+    #[inline]
+    pub fn selfdestruct_with_mode<DB: Database>(
+        &mut self,
+        db: &mut DB,
+        address: Address,
+        target: Address,
+        mode: SelfdestructMode,
     ) -> Result<StateLoad<SelfDestructResult>, DB::Error> {
         let spec = self.spec;
         let account_load = self.load_account(db, target)?;
@@ -543,10 +775,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             SelfdestructionRevertStatus::RepeatedSelfdestruction
         };
 
-        let is_cancun_enabled = spec.is_enabled_in(CANCUN);
-
         // EIP-6780 (Cancun hard-fork): selfdestruct only if contract is created in the same tx
-        let journal_entry = if acc.is_created_locally() || !is_cancun_enabled {
+        let journal_entry = if acc.is_created_locally() || mode.is_destroy() {
             acc.mark_selfdestructed_locally();
             acc.info.balance = U256::ZERO;
             Some(ENTRY::account_destroyed(
@@ -566,8 +796,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             None
         };
 
+        if address != target {
+            self.record_account_write(target);
+        }
         if let Some(entry) = journal_entry {
             self.journal.push(entry);
+            self.record_account_write(address);
         };
 
         Ok(StateLoad {
@@ -651,6 +885,9 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         load_code: bool,
         storage_keys: impl IntoIterator<Item = StorageKey>,
     ) -> Result<StateLoad<&mut Account>, DB::Error> {
+        let mut storage_keys = storage_keys.into_iter().peekable();
+        // Slots fetched together with a vacant account, they still need to be loaded below.
+        let mut prefetched_keys = Vec::new();
         let load = match self.state.entry(address) {
             Entry::Occupied(entry) => {
                 let account = entry.into_mut();
@@ -672,11 +909,30 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
                 }
             }
             Entry::Vacant(vac) => {
-                let account = if let Some(account) = db.basic(address)? {
-                    account.into()
+                // Fetch account together with requested slots so database can batch them.
+                let (info, values) = if storage_keys.peek().is_none() {
+                    (db.basic(address)?, Vec::new())
+                } else {
+                    prefetched_keys.extend(storage_keys.by_ref());
+                    db.basic_with_storage(address, &prefetched_keys)?
+                };
+                let mut account = if let Some(info) = info {
+                    info.into()
                 } else {
                     Account::new_not_existing(self.transaction_id)
                 };
+                if self.finalized_state_mode == FinalizedStateMode::Changed {
+                    self.original_infos
+                        .insert(address, account.info.copy_without_code());
+                }
+                // Prefetched slots are inserted as cold so loading them below journals the warming.
+                account
+                    .storage
+                    .extend(prefetched_keys.iter().zip(values).map(|(key, value)| {
+                        let mut slot = EvmStorageSlot::new(value, self.transaction_id);
+                        slot.mark_cold();
+                        (*key, slot)
+                    }));
 
                 // Precompiles among some other account(coinbase included) are warm loaded so we need to take that into account
                 let is_cold = !self.warm_preloaded_addresses.contains(&address)
@@ -689,6 +945,9 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             }
         };
 
+        if let Some(access_log) = &mut self.access_log {
+            access_log.account(address, load.is_cold);
+        }
         // journal loading of cold account.
         if load.is_cold {
             self.journal.push(ENTRY::account_warmed(address));
@@ -705,7 +964,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             }
         }
 
-        for storage_key in storage_keys.into_iter() {
+        for storage_key in prefetched_keys.into_iter().chain(storage_keys) {
             sload_with_account(
                 load.data,
                 db,
@@ -718,6 +977,49 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         Ok(load)
     }
 
+    /// Loads accounts that are not in the state yet with one [`Database::many_basics`] call.
+    ///
+    /// Accounts are inserted as cold, so warming them afterwards is journaled without reading the
+    /// database again.
+    pub fn prefetch_accounts<DB: Database>(
+        &mut self,
+        db: &mut DB,
+        addresses: &[Address],
+    ) -> Result<(), DB::Error> {
+        let mut missing: Vec<Address> = addresses
+            .iter()
+            .filter(|address| !self.state.contains_key(*address))
+            .copied()
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let infos = db.many_basics(&missing)?;
+        for (address, info) in missing.into_iter().zip(infos) {
+            let mut account = if let Some(info) = info {
+                info.into()
+            } else {
+                Account::new_not_existing(self.transaction_id)
+            };
+            if self.finalized_state_mode == FinalizedStateMode::Changed {
+                self.original_infos
+                    .insert(address, account.info.copy_without_code());
+            }
+            account.transaction_id = self.transaction_id;
+            // Precompiles and coinbase are warm without being journaled.
+            if !self.warm_preloaded_addresses.contains(&address)
+                && self.warm_coinbase_address.as_ref() != Some(&address)
+            {
+                account.mark_cold();
+            }
+            self.state.insert(address, account);
+        }
+        Ok(())
+    }
+
     /// Loads storage slot.
     ///
     /// # Panics
@@ -730,17 +1032,31 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         address: Address,
         key: StorageKey,
     ) -> Result<StateLoad<StorageValue>, DB::Error> {
+        // cached slots are warm.
+        #[cfg(feature = "slot_cache")]
+        if let Some(value) = self.slot_cache.get(&address, &key) {
+            if let Some(access_log) = &mut self.access_log {
+                access_log.slot(address, key, false);
+            }
+            return Ok(StateLoad::new(value, false));
+        }
         // assume acc is warm
         let account = self.state.get_mut(&address).unwrap();
         // only if account is created in this tx we can assume that storage is empty.
-        sload_with_account(
+        let load = sload_with_account(
             account,
             db,
             &mut self.journal,
             self.transaction_id,
             address,
             key,
-        )
+        )?;
+        if let Some(access_log) = &mut self.access_log {
+            access_log.slot(address, key, load.is_cold);
+        }
+        #[cfg(feature = "slot_cache")]
+        self.slot_cache.insert(address, key, load.data);
+        Ok(load)
     }
 
     /// Stores storage slot.
@@ -779,6 +1095,11 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             .push(ENTRY::storage_changed(address, key, present.data));
         // insert value into present state.
         slot.present_value = new;
+        if let Some(access_log) = &mut self.access_log {
+            access_log.slot(address, key, present.is_cold).written = true;
+        }
+        #[cfg(feature = "slot_cache")]
+        self.slot_cache.insert(address, key, new);
         Ok(StateLoad::new(
             SStoreResult {
                 original_value: slot.original_value(),
@@ -882,6 +1203,35 @@ pub fn sload_with_account<DB: Database, ENTRY: JournalEntryTr>(
     Ok(StateLoad::new(value, is_cold))
 }
 
+/// Returns `true` if the account is changed compared to its information at load.
+///
+/// Accounts without recorded information are assumed to be changed if they are touched.
+fn is_account_changed(
+    account: &Account,
+    original: Option<&AccountInfo>,
+    is_spurious_dragon_enabled: bool,
+) -> bool {
+    if !account.is_touched() {
+        return false;
+    }
+    if account.is_created()
+        || account.is_selfdestructed()
+        || account.storage.values().any(EvmStorageSlot::is_changed)
+    {
+        return true;
+    }
+    // EIP-161 removes touched empty accounts that exist, before it touched accounts that don't
+    // exist are created.
+    if account.is_empty() && account.is_loaded_as_not_existing() != is_spurious_dragon_enabled {
+        return true;
+    }
+    original.is_none_or(|original| {
+        original.balance != account.info.balance
+            || original.nonce != account.info.nonce
+            || original.code_hash != account.info.code_hash
+    })
+}
+
 fn reset_preloaded_addresses(
     warm_preloaded_addresses: &mut HashSet<Address>,
     precompiles: &HashSet<Address>,
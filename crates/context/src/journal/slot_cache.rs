@@ -0,0 +1,173 @@
+//! Direct-mapped cache of hot storage slots in front of the journal state.
+use primitives::{Address, StorageKey, StorageValue};
+use std::{boxed::Box, vec};
+
+/// Number of entries of [`SlotCache`], a power of two.
+pub const SLOT_CACHE_SIZE: usize = 64;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct CachedSlot {
+    address: Address,
+    key: StorageKey,
+    value: StorageValue,
+    /// Entry is valid only if it is equal to [`SlotCache::epoch`].
+    epoch: u32,
+}
+
+/// Direct-mapped cache of present values of storage slots that are warm in the current
+/// transaction.
+///
+/// A hit skips the account and storage map lookups of `SLOAD`. `SSTORE` reads the present value
+/// from the cache too, but still looks up the slot in the state to update it.
+///
+/// Each slot maps to a single entry picked from the low bits of the key and the address, so
+/// tight loops over a few slots, like reserves and balances of a pool, stay in the cache.
+///
+/// The cache is invalidated in constant time when the transaction ends, the journal is reverted
+/// or an account is created. Changes of [`JournalInner::state`](super::JournalInner::state) made
+/// outside of the journal must be followed by [`SlotCache::invalidate`].
+///
+/// The cache does not affect equality and is not serialized.
+#[derive(Clone, Debug)]
+pub struct SlotCache {
+    entries: Box<[CachedSlot]>,
+    epoch: u32,
+}
+
+impl Default for SlotCache {
+    fn default() -> Self {
+        Self {
+            entries: vec![CachedSlot::default(); SLOT_CACHE_SIZE].into_boxed_slice(),
+            epoch: 1,
+        }
+    }
+}
+
+impl PartialEq for SlotCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SlotCache {}
+
+impl SlotCache {
+    #[inline]
+    fn index(address: &Address, key: &StorageKey) -> usize {
+        let address_low = u64::from_be_bytes(address.0[12..].try_into().unwrap());
+        (key.as_limbs()[0] ^ address_low) as usize & (SLOT_CACHE_SIZE - 1)
+    }
+
+    /// Returns the present value of the slot if it is cached.
+    #[inline]
+    pub fn get(&self, address: &Address, key: &StorageKey) -> Option<StorageValue> {
+        let entry = &self.entries[Self::index(address, key)];
+        (entry.epoch == self.epoch && entry.key == *key && entry.address == *address)
+            .then_some(entry.value)
+    }
+
+    /// Caches the present value of the warm slot, replacing the slot with the same index.
+    #[inline]
+    pub fn insert(&mut self, address: Address, key: StorageKey, value: StorageValue) {
+        self.entries[Self::index(&address, &key)] = CachedSlot {
+            address,
+            key,
+            value,
+            epoch: self.epoch,
+        };
+    }
+
+    /// Invalidates all entries.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+        if self.epoch == 0 {
+            // Entries of the first epoch would become valid again.
+            self.entries.fill(CachedSlot::default());
+            self.epoch = 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::{JournalEntry, JournalInner};
+    use database_interface::EmptyDB;
+    use primitives::address;
+
+    #[test]
+    fn slot_cache() {
+        let first = address!("0x0000000000000000000000000000000000000001");
+        let second = address!("0x0000000000000000000000000000000000000002");
+        let mut cache = SlotCache::default();
+        assert_eq!(cache.get(&first, &StorageKey::ZERO), None);
+
+        cache.insert(first, StorageKey::ZERO, StorageValue::from(5));
+        assert_eq!(
+            cache.get(&first, &StorageKey::ZERO),
+            Some(StorageValue::from(5))
+        );
+        // Both slots map to the same entry.
+        cache.insert(second, StorageKey::from(3), StorageValue::from(6));
+        assert_eq!(cache.get(&first, &StorageKey::ZERO), None);
+        assert_eq!(
+            cache.get(&second, &StorageKey::from(3)),
+            Some(StorageValue::from(6))
+        );
+
+        cache.invalidate();
+        assert_eq!(cache.get(&second, &StorageKey::from(3)), None);
+        cache.epoch = u32::MAX;
+        cache.insert(first, StorageKey::ZERO, StorageValue::from(5));
+        cache.invalidate();
+        assert_eq!(cache.epoch, 1);
+        assert_eq!(cache.get(&first, &StorageKey::ZERO), None);
+    }
+
+    #[test]
+    fn journal_revert_invalidates_cache() {
+        let address = address!("0x0000000000000000000000000000000000000001");
+        let key = StorageKey::from(1);
+        let mut db = EmptyDB::default();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, address).unwrap();
+
+        let checkpoint = journal.checkpoint();
+        journal
+            .sstore(&mut db, address, key, StorageValue::from(7))
+            .unwrap();
+        let load = journal.sload(&mut db, address, key).unwrap();
+        assert_eq!((load.data, load.is_cold), (StorageValue::from(7), false));
+
+        journal.checkpoint_revert(checkpoint);
+        let load = journal.sload(&mut db, address, key).unwrap();
+        assert_eq!((load.data, load.is_cold), (StorageValue::ZERO, true));
+
+        journal.commit_tx();
+        assert_eq!(journal.slot_cache.get(&address, &key), None);
+    }
+
+    #[test]
+    fn insert_storage_slot_invalidates_cache() {
+        use crate::Journal;
+        use context_interface::JournalTr;
+
+        let address = address!("0x0000000000000000000000000000000000000001");
+        let key = StorageKey::from(1);
+        let mut journal = Journal::<_, JournalEntry>::new(EmptyDB::default());
+        journal.load_account(address).unwrap();
+        journal.sload(address, key).unwrap();
+        assert_eq!(
+            journal.inner.slot_cache.get(&address, &key),
+            Some(StorageValue::ZERO)
+        );
+
+        journal
+            .insert_storage_slot(address, key, StorageValue::from(7))
+            .unwrap();
+        assert_eq!(journal.inner.slot_cache.get(&address, &key), None);
+        let load = journal.sload(address, key).unwrap();
+        assert_eq!((load.data, load.is_cold), (StorageValue::from(7), true));
+    }
+}
@@ -9,16 +9,33 @@ pub use context_interface::*;
 
 pub mod block;
 pub mod cfg;
+pub mod chain;
 pub mod context;
+#[cfg(feature = "rlp")]
+pub mod envelope;
 pub mod evm;
 pub mod journal;
 pub mod local;
+#[cfg(feature = "parallel_recovery")]
+pub mod recovery;
+pub mod sandbox;
 pub mod tx;
 
-pub use block::BlockEnv;
-pub use cfg::{Cfg, CfgEnv};
+pub use block::{
+    pending::{
+        BaseFeeParams, ParentHeader, PendingBlockBuilder, PrevrandaoSource, TimestampPolicy,
+    },
+    BlockEnv,
+};
+pub use cfg::{Cfg, CfgEnv, ForkActivation, ForkSchedule};
+pub use chain::{ChainData, ChainExtensions};
 pub use context::*;
+#[cfg(feature = "rlp")]
+pub use envelope::TxDecodeError;
 pub use evm::Evm;
 pub use journal::*;
 pub use local::LocalContext;
+#[cfg(feature = "parallel_recovery")]
+pub use recovery::{SenderCache, SenderRecoveryError};
+pub use sandbox::{CancellationToken, SandboxProfile};
 pub use tx::TxEnv;
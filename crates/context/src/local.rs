@@ -1,6 +1,10 @@
 //! Local context that is filled by execution.
-use context_interface::LocalContextTr;
-use core::cell::RefCell;
+use context_interface::{
+    local::{CallFrameInfo, KeccakCache},
+    result::CreateFailure,
+    LocalContextTr,
+};
+use core::{cell::RefCell, mem};
 use std::{rc::Rc, vec::Vec};
 
 /// Local context that is filled by execution.
@@ -8,12 +12,23 @@ use std::{rc::Rc, vec::Vec};
 pub struct LocalContext {
     /// Interpreter shared memory buffer. A reused memory buffer for calls.
     pub shared_memory_buffer: Rc<RefCell<Vec<u8>>>,
+    /// Frames of the running call stack.
+    pub call_stack: Vec<CallFrameInfo>,
+    /// Hashes cached by the KECCAK256 instruction, see [`Cfg::keccak_cache_size`][context_interface::Cfg::keccak_cache_size].
+    pub keccak_cache: KeccakCache,
+    /// Contract creations that failed, `None` if they are not recorded.
+    ///
+    /// Failures are kept by [`LocalContextTr::clear`] so they can be taken after the transaction.
+    pub create_failures: Option<Vec<CreateFailure>>,
 }
 
 impl Default for LocalContext {
     fn default() -> Self {
         Self {
             shared_memory_buffer: Rc::new(RefCell::new(Vec::with_capacity(1024 * 4))),
+            call_stack: Vec::new(),
+            keccak_cache: KeccakCache::new(),
+            create_failures: None,
         }
     }
 }
@@ -22,11 +37,56 @@ impl LocalContextTr for LocalContext {
     fn clear(&mut self) {
         // Sets len to 0 but it will not shrink to drop the capacity.
         unsafe { self.shared_memory_buffer.borrow_mut().set_len(0) };
+        self.call_stack.clear();
+        self.keccak_cache.clear();
     }
 
     fn shared_memory_buffer(&self) -> &Rc<RefCell<Vec<u8>>> {
         &self.shared_memory_buffer
     }
+
+    fn call_stack(&self) -> &[CallFrameInfo] {
+        &self.call_stack
+    }
+
+    fn push_call_frame(&mut self, frame: CallFrameInfo) {
+        self.call_stack.push(frame);
+    }
+
+    fn pop_call_frame(&mut self) {
+        self.call_stack.pop();
+    }
+
+    fn set_remaining_gas(&mut self, remaining_gas: u64) {
+        if let Some(frame) = self.call_stack.last_mut() {
+            frame.remaining_gas = remaining_gas;
+        }
+    }
+
+    fn keccak_cache(&mut self) -> Option<&mut KeccakCache> {
+        Some(&mut self.keccak_cache)
+    }
+
+    fn set_record_create_failures(&mut self, record: bool) {
+        self.create_failures = record.then(Vec::new);
+    }
+
+    fn records_create_failures(&self) -> bool {
+        self.create_failures.is_some()
+    }
+
+    fn record_create_failure(&mut self, failure: CreateFailure) {
+        if let Some(failures) = &mut self.create_failures {
+            failures.push(failure);
+        }
+    }
+
+    fn take_create_failures(&mut self) -> Vec<CreateFailure> {
+        self.create_failures
+            .as_mut()
+            .map(mem::take)
+            .unwrap_or_default()
+    }
 }
 
 impl LocalContext {
@@ -0,0 +1,178 @@
+//! Parallel recovery of transaction senders for block replay.
+use crate::TxEnv;
+use alloy_consensus::{crypto::RecoveryError, transaction::SignerRecoverable, TxEnvelope};
+use core::fmt;
+use primitives::{Address, HashMap, B256};
+use rayon::prelude::*;
+use std::vec::Vec;
+
+/// Error returned when the sender of a transaction in the block could not be recovered.
+#[derive(Debug)]
+pub struct SenderRecoveryError {
+    /// Index of the transaction in the block.
+    pub index: usize,
+    /// Recovery error.
+    pub error: RecoveryError,
+}
+
+impl fmt::Display for SenderRecoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transaction {}: invalid signature: {}",
+            self.index, self.error
+        )
+    }
+}
+
+impl core::error::Error for SenderRecoveryError {}
+
+/// Cache of recovered transaction senders, keyed by transaction hash.
+///
+/// Senders of a block are recovered on the rayon thread pool, transactions that were already
+/// recovered, for example when the same block is replayed again, are taken from the cache.
+/// The recovered transactions can be executed in order with the block executor of the handler.
+///
+/// Cache is never pruned, call [`SenderCache::clear`] to release the memory.
+#[derive(Clone, Debug, Default)]
+pub struct SenderCache {
+    senders: HashMap<B256, Address>,
+}
+
+impl SenderCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached senders.
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Returns the cached sender of the transaction with the given hash.
+    pub fn get(&self, tx_hash: &B256) -> Option<Address> {
+        self.senders.get(tx_hash).copied()
+    }
+
+    /// Inserts the sender of the transaction with the given hash.
+    pub fn insert(&mut self, tx_hash: B256, sender: Address) {
+        self.senders.insert(tx_hash, sender);
+    }
+
+    /// Removes all cached senders.
+    pub fn clear(&mut self) {
+        self.senders.clear();
+    }
+
+    /// Recovers senders of all transactions in parallel.
+    ///
+    /// Returns the error of the first transaction whose sender could not be recovered.
+    pub fn recover_senders(
+        &mut self,
+        txs: &[TxEnvelope],
+    ) -> Result<Vec<Address>, SenderRecoveryError> {
+        self.recover(txs, |_, sender| sender)
+    }
+
+    /// Recovers senders of all transactions in parallel and converts the transactions.
+    ///
+    /// See [`TxEnv::from_recovered_envelope`] for the conversion.
+    pub fn recover_block(&mut self, txs: &[TxEnvelope]) -> Result<Vec<TxEnv>, SenderRecoveryError> {
+        self.recover(txs, TxEnv::from_recovered_envelope)
+    }
+
+    fn recover<T: Send>(
+        &mut self,
+        txs: &[TxEnvelope],
+        map: impl Fn(&TxEnvelope, Address) -> T + Sync,
+    ) -> Result<Vec<T>, SenderRecoveryError> {
+        let senders = &self.senders;
+        let recovered: Vec<_> = txs
+            .par_iter()
+            .map(|tx| {
+                let hash = *tx.tx_hash();
+                let sender = match senders.get(&hash) {
+                    Some(sender) => *sender,
+                    None => tx.recover_signer()?,
+                };
+                Ok((hash, map(tx, sender), sender))
+            })
+            .collect();
+
+        let mut outputs = Vec::with_capacity(recovered.len());
+        for (index, result) in recovered.into_iter().enumerate() {
+            let (hash, output, sender) =
+                result.map_err(|error| SenderRecoveryError { index, error })?;
+            self.senders.insert(hash, sender);
+            outputs.push(output);
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, Signed, TxEip1559};
+    use alloy_signer::{Signature, SignerSync};
+    use alloy_signer_local::PrivateKeySigner;
+    use primitives::{TxKind, U256};
+
+    fn signed(signer: &PrivateKeySigner, nonce: u64) -> TxEnvelope {
+        let tx = TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::ZERO),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        tx.into_signed(signature).into()
+    }
+
+    #[test]
+    fn recover_block() {
+        let signers: Vec<_> = (0..8).map(|_| PrivateKeySigner::random()).collect();
+        let txs: Vec<_> = signers
+            .iter()
+            .enumerate()
+            .map(|(nonce, signer)| signed(signer, nonce as u64))
+            .collect();
+
+        let mut cache = SenderCache::new();
+        let block = cache.recover_block(&txs).unwrap();
+        assert_eq!(cache.len(), txs.len());
+        for (i, (tx, signer)) in block.iter().zip(&signers).enumerate() {
+            assert_eq!(tx.caller, signer.address());
+            assert_eq!(tx.nonce, i as u64);
+        }
+
+        // Cached senders are not recovered again.
+        let fake = Address::with_last_byte(0x42);
+        cache.insert(*txs[3].tx_hash(), fake);
+        let senders = cache.recover_senders(&txs).unwrap();
+        assert_eq!(senders[3], fake);
+        assert_eq!(senders[4], signers[4].address());
+    }
+
+    #[test]
+    fn recover_invalid_signature() {
+        let signer = PrivateKeySigner::random();
+        let tx = TxEip1559::default();
+        let hash = tx.signature_hash();
+        let invalid: TxEnvelope =
+            Signed::new_unchecked(tx, Signature::new(U256::ZERO, U256::ZERO, false), hash).into();
+        let txs = [signed(&signer, 0), invalid, signed(&signer, 1)];
+
+        let mut cache = SenderCache::new();
+        let error = cache.recover_senders(&txs).unwrap_err();
+        assert_eq!(error.index, 1);
+        assert_eq!(cache.len(), 1);
+    }
+}
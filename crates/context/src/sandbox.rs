@@ -0,0 +1,166 @@
+//! Configuration preset for executing untrusted bytecode.
+use crate::CfgEnv;
+use bytecode::opcode;
+use context_interface::cfg::{DisabledOpcodes, NonceCheckMode};
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Token that cancels sandboxed executions it is shared with.
+///
+/// Clones share the same state, so the token can be cancelled from another thread while the
+/// execution is running.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the executions that use this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Preset of limits for services that execute untrusted bytecode.
+///
+/// [`SandboxProfile::new`] returns a curated preset:
+///
+/// * Transaction gas limit is capped at [`SandboxProfile::DEFAULT_GAS_LIMIT_CAP`].
+/// * Memory of all running frames is capped at [`SandboxProfile::DEFAULT_MEMORY_LIMIT`] bytes.
+/// * Execution stops after [`SandboxProfile::DEFAULT_MAX_STEPS`] instructions.
+/// * Execution stops when the [`CancellationToken`] is cancelled.
+/// * `SELFDESTRUCT`, `CREATE` and `CREATE2` are disabled.
+/// * Callers can be fake accounts that don't exist in the database: nonces are neither checked
+///   nor incremented, and zero-value transactions with zero gas price need no balance. The base
+///   fee check is disabled if the `optional_no_base_fee` feature is enabled.
+///
+/// Gas cap, disabled opcodes and nonce handling are applied to the configuration with
+/// [`CfgEnv::with_sandbox`]. Step and memory limits and the cancellation are enforced by the
+/// sandboxed execution of the handler, which checks them every
+/// [`SandboxProfile::steps_per_check`] instructions.
+#[derive(Clone, Debug)]
+pub struct SandboxProfile {
+    /// Cap of the transaction gas limit.
+    pub gas_limit_cap: u64,
+    /// Limit of the memory of all running frames, in bytes.
+    pub memory_limit: usize,
+    /// Maximum number of executed instructions.
+    pub max_steps: u64,
+    /// Number of instructions executed between checks of the limits and the cancellation.
+    pub steps_per_check: u64,
+    /// Opcodes that halt instead of executing.
+    pub disabled_opcodes: DisabledOpcodes,
+    /// Token that cancels the execution.
+    pub cancellation: CancellationToken,
+}
+
+impl SandboxProfile {
+    /// Default cap of the transaction gas limit.
+    pub const DEFAULT_GAS_LIMIT_CAP: u64 = 30_000_000;
+    /// Default memory limit, 16 MiB.
+    pub const DEFAULT_MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+    /// Default maximum number of executed instructions.
+    pub const DEFAULT_MAX_STEPS: u64 = 10_000_000;
+    /// Default number of instructions between checks.
+    pub const DEFAULT_STEPS_PER_CHECK: u64 = 10_000;
+    /// Opcodes disabled by default.
+    pub const DEFAULT_DISABLED_OPCODES: DisabledOpcodes =
+        DisabledOpcodes::from_opcodes(&[opcode::SELFDESTRUCT, opcode::CREATE, opcode::CREATE2]);
+
+    /// Creates the default sandbox preset.
+    pub fn new() -> Self {
+        Self {
+            gas_limit_cap: Self::DEFAULT_GAS_LIMIT_CAP,
+            memory_limit: Self::DEFAULT_MEMORY_LIMIT,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            steps_per_check: Self::DEFAULT_STEPS_PER_CHECK,
+            disabled_opcodes: Self::DEFAULT_DISABLED_OPCODES,
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Sets the cap of the transaction gas limit.
+    pub fn with_gas_limit_cap(mut self, gas_limit_cap: u64) -> Self {
+        self.gas_limit_cap = gas_limit_cap;
+        self
+    }
+
+    /// Sets the memory limit in bytes.
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets the maximum number of executed instructions.
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets the number of instructions executed between checks, at least one.
+    pub fn with_steps_per_check(mut self, steps_per_check: u64) -> Self {
+        self.steps_per_check = steps_per_check.max(1);
+        self
+    }
+
+    /// Sets the disabled opcodes.
+    pub fn with_disabled_opcodes(mut self, disabled_opcodes: DisabledOpcodes) -> Self {
+        self.disabled_opcodes = disabled_opcodes;
+        self
+    }
+
+    /// Sets the cancellation token.
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+}
+
+impl Default for SandboxProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<SPEC> CfgEnv<SPEC> {
+    /// Applies the gas cap, disabled opcodes and nonce handling of the sandbox profile.
+    pub fn with_sandbox(mut self, profile: &SandboxProfile) -> Self {
+        self.tx_gas_limit_cap = Some(profile.gas_limit_cap);
+        self.disabled_opcodes = profile.disabled_opcodes;
+        self.nonce_check_mode = NonceCheckMode::Ignore;
+        #[cfg(feature = "optional_no_base_fee")]
+        {
+            self.disable_base_fee = true;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context_interface::Cfg;
+
+    #[test]
+    fn sandbox_cfg() {
+        let profile = SandboxProfile::new().with_gas_limit_cap(1_000_000);
+        let cfg = CfgEnv::new().with_sandbox(&profile);
+        assert_eq!(cfg.tx_gas_limit_cap(), 1_000_000);
+        assert!(cfg.disabled_opcodes().contains(opcode::SELFDESTRUCT));
+        assert!(!cfg.disabled_opcodes().contains(opcode::CALL));
+        assert_eq!(cfg.nonce_check_mode(), NonceCheckMode::Ignore);
+
+        let token = profile.cancellation.clone();
+        assert!(!profile.cancellation.is_cancelled());
+        token.cancel();
+        assert!(profile.cancellation.is_cancelled());
+    }
+}
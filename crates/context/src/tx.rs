@@ -3,8 +3,8 @@ use crate::TransactionType;
 use context_interface::{
     either::Either,
     transaction::{
-        AccessList, AccessListItem, Authorization, RecoveredAuthority, RecoveredAuthorization,
-        SignedAuthorization, Transaction,
+        AccessList, AccessListItem, Authorization, BlobSidecar, RecoveredAuthority,
+        RecoveredAuthorization, SignedAuthorization, Transaction,
     },
 };
 use core::fmt::Debug;
@@ -77,6 +77,13 @@ pub struct TxEnv {
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
     pub max_fee_per_blob_gas: u128,
 
+    /// Blobs with commitments and proofs of the transaction
+    ///
+    /// Not part of the signed transaction, verified only if enabled by
+    /// [`BlobSidecarMode::Verify`](context_interface::cfg::BlobSidecarMode::Verify).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blob_sidecar: Option<BlobSidecar>,
+
     /// List of authorizations
     ///
     /// `authorization_list` contains the signature that authorizes this
@@ -207,6 +214,10 @@ impl Transaction for TxEnv {
         self.max_fee_per_blob_gas
     }
 
+    fn blob_sidecar(&self) -> Option<&BlobSidecar> {
+        self.blob_sidecar.as_ref()
+    }
+
     fn authorization_list_len(&self) -> usize {
         self.authorization_list.len()
     }
@@ -244,6 +255,7 @@ pub struct TxEnvBuilder {
     gas_priority_fee: Option<u128>,
     blob_hashes: Vec<B256>,
     max_fee_per_blob_gas: u128,
+    blob_sidecar: Option<BlobSidecar>,
     authorization_list: Vec<Either<SignedAuthorization, RecoveredAuthorization>>,
 }
 
@@ -264,6 +276,7 @@ impl TxEnvBuilder {
             gas_priority_fee: None,
             blob_hashes: Vec::new(),
             max_fee_per_blob_gas: 0,
+            blob_sidecar: None,
             authorization_list: Vec::new(),
         }
     }
@@ -368,6 +381,12 @@ impl TxEnvBuilder {
         self
     }
 
+    /// Set the blobs with commitments and proofs
+    pub fn blob_sidecar(mut self, blob_sidecar: Option<BlobSidecar>) -> Self {
+        self.blob_sidecar = blob_sidecar;
+        self
+    }
+
     /// Set the max fee per blob gas
     pub fn max_fee_per_blob_gas(mut self, max_fee_per_blob_gas: u128) -> Self {
         self.max_fee_per_blob_gas = max_fee_per_blob_gas;
@@ -472,6 +491,7 @@ impl TxEnvBuilder {
             gas_priority_fee: self.gas_priority_fee,
             blob_hashes: self.blob_hashes,
             max_fee_per_blob_gas: self.max_fee_per_blob_gas,
+            blob_sidecar: self.blob_sidecar,
             authorization_list: self.authorization_list,
         };
 
@@ -564,6 +584,7 @@ impl TxEnvBuilder {
             gas_priority_fee: self.gas_priority_fee,
             blob_hashes: self.blob_hashes,
             max_fee_per_blob_gas: self.max_fee_per_blob_gas,
+            blob_sidecar: self.blob_sidecar,
             authorization_list: self.authorization_list,
         };
 
@@ -625,6 +646,7 @@ impl TxEnv {
             gas_priority_fee,
             blob_hashes,
             max_fee_per_blob_gas,
+            blob_sidecar,
             authorization_list,
         } = self;
 
@@ -642,6 +664,7 @@ impl TxEnv {
             .gas_priority_fee(gas_priority_fee)
             .blob_hashes(blob_hashes)
             .max_fee_per_blob_gas(max_fee_per_blob_gas)
+            .blob_sidecar(blob_sidecar)
             .authorization_list(authorization_list)
     }
 }
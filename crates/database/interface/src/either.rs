@@ -4,6 +4,7 @@ use crate::{Database, DatabaseCommit, DatabaseRef};
 use either::Either;
 use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
 use state::{Account, AccountInfo, Bytecode};
+use std::vec::Vec;
 
 impl<L, R> Database for Either<L, R>
 where
@@ -43,6 +44,27 @@ where
             Self::Right(db) => db.block_hash(number),
         }
     }
+
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        match self {
+            Self::Left(db) => db.basic_with_storage(address, slots),
+            Self::Right(db) => db.basic_with_storage(address, slots),
+        }
+    }
+
+    fn many_basics(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        match self {
+            Self::Left(db) => db.many_basics(addresses),
+            Self::Right(db) => db.many_basics(addresses),
+        }
+    }
 }
 
 impl<L, R> DatabaseCommit for Either<L, R>
@@ -96,4 +118,25 @@ where
             Self::Right(db) => db.block_hash_ref(number),
         }
     }
+
+    fn basic_with_storage_ref(
+        &self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        match self {
+            Self::Left(db) => db.basic_with_storage_ref(address, slots),
+            Self::Right(db) => db.basic_with_storage_ref(address, slots),
+        }
+    }
+
+    fn many_basics_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        match self {
+            Self::Left(db) => db.many_basics_ref(addresses),
+            Self::Right(db) => db.many_basics_ref(addresses),
+        }
+    }
 }
@@ -11,7 +11,7 @@ use auto_impl::auto_impl;
 use core::error::Error;
 use primitives::{address, Address, HashMap, StorageKey, StorageValue, B256, U256};
 use state::{Account, AccountInfo, Bytecode};
-use std::string::String;
+use std::{string::String, vec::Vec};
 
 /// Address with all `0xff..ff` in it. Used for testing.
 pub const FFADDRESS: Address = address!("0xffffffffffffffffffffffffffffffffffffffff");
@@ -30,11 +30,13 @@ pub const BENCH_CALLER_BALANCE: U256 = U256::from_limbs([10_000_000_000_000_000,
 pub mod async_db;
 pub mod either;
 pub mod empty_db;
+pub mod slow_reads;
 pub mod try_commit;
 
 #[cfg(feature = "asyncdb")]
 pub use async_db::{DatabaseAsync, WrapDatabaseAsync};
 pub use empty_db::{EmptyDB, EmptyDBTyped};
+pub use slow_reads::{DatabaseRead, SlowRead, SlowReads};
 pub use try_commit::{ArcUpgradeError, TryDatabaseCommit};
 
 /// Database error marker is needed to implement From conversion for Error type.
@@ -63,6 +65,38 @@ pub trait Database {
 
     /// Gets block hash by block number.
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error>;
+
+    /// Gets basic account information together with values of the given storage `slots`.
+    ///
+    /// Returned values are in the same order as `slots`. Default implementation calls
+    /// [`Database::basic`] and [`Database::storage`], remote databases can override it to fetch
+    /// everything in one round trip.
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        let info = self.basic(address)?;
+        let values = slots
+            .iter()
+            .map(|slot| self.storage(address, *slot))
+            .collect::<Result<_, _>>()?;
+        Ok((info, values))
+    }
+
+    /// Gets basic account information of multiple accounts.
+    ///
+    /// Returned accounts are in the same order as `addresses`. Default implementation calls
+    /// [`Database::basic`] for every address.
+    fn many_basics(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        addresses
+            .iter()
+            .map(|address| self.basic(*address))
+            .collect()
+    }
 }
 
 /// EVM database commit interface.
@@ -95,6 +129,35 @@ pub trait DatabaseRef {
 
     /// Gets block hash by block number.
     fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error>;
+
+    /// Gets basic account information together with values of the given storage `slots`.
+    ///
+    /// See [`Database::basic_with_storage`].
+    fn basic_with_storage_ref(
+        &self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        let info = self.basic_ref(address)?;
+        let values = slots
+            .iter()
+            .map(|slot| self.storage_ref(address, *slot))
+            .collect::<Result<_, _>>()?;
+        Ok((info, values))
+    }
+
+    /// Gets basic account information of multiple accounts.
+    ///
+    /// See [`Database::many_basics`].
+    fn many_basics_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        addresses
+            .iter()
+            .map(|address| self.basic_ref(*address))
+            .collect()
+    }
 }
 
 /// Wraps a [`DatabaseRef`] to provide a [`Database`] implementation.
@@ -134,6 +197,23 @@ impl<T: DatabaseRef> Database for WrapDatabaseRef<T> {
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
         self.0.block_hash_ref(number)
     }
+
+    #[inline]
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        self.0.basic_with_storage_ref(address, slots)
+    }
+
+    #[inline]
+    fn many_basics(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.0.many_basics_ref(addresses)
+    }
 }
 
 impl<T: DatabaseRef + DatabaseCommit> DatabaseCommit for WrapDatabaseRef<T> {
@@ -169,4 +249,21 @@ impl<T: DatabaseRef> DatabaseRef for WrapDatabaseRef<T> {
     fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
         self.0.block_hash_ref(number)
     }
+
+    #[inline]
+    fn basic_with_storage_ref(
+        &self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        self.0.basic_with_storage_ref(address, slots)
+    }
+
+    #[inline]
+    fn many_basics_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.0.many_basics_ref(addresses)
+    }
 }
@@ -0,0 +1,36 @@
+//! Slow database reads reported by latency measuring databases.
+use auto_impl::auto_impl;
+use core::time::Duration;
+use primitives::{Address, StorageKey, B256};
+use std::vec::Vec;
+
+/// Database read identified by the requested key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DatabaseRead {
+    /// Account info of the address, see [`Database::basic`](crate::Database::basic).
+    Account(Address),
+    /// Storage slot of the address, see [`Database::storage`](crate::Database::storage).
+    Storage(Address, StorageKey),
+    /// Code of the hash, see [`Database::code_by_hash`](crate::Database::code_by_hash).
+    Code(B256),
+    /// Hash of the block number, see [`Database::block_hash`](crate::Database::block_hash).
+    BlockHash(u64),
+}
+
+/// Database read that took at least the latency budget of the database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlowRead {
+    /// Key of the read.
+    pub read: DatabaseRead,
+    /// Time the read took.
+    pub duration: Duration,
+}
+
+/// Database that records reads exceeding a latency budget.
+#[auto_impl(&mut, Box)]
+pub trait SlowReads {
+    /// Returns recorded slow reads sorted from the slowest and clears them.
+    fn take_slow_reads(&mut self) -> Vec<SlowRead>;
+}
@@ -5,12 +5,13 @@ use alloy_provider::{
     network::{primitives::HeaderResponse, BlockResponse},
     Network, Provider,
 };
-use alloy_transport::TransportError;
+use alloy_transport::{RpcError, TransportError, TransportErrorKind};
+use bytecode::AnalysisStore;
 use core::error::Error;
 use database_interface::{async_db::DatabaseAsyncRef, DBErrorMarker};
-use primitives::{Address, StorageKey, StorageValue, B256};
+use primitives::{keccak256, Address, StorageKey, StorageValue, B256, KECCAK_EMPTY};
 use state::{AccountInfo, Bytecode};
-use std::fmt::Display;
+use std::{fmt::Display, sync::Arc};
 
 /// Error type for transport-related database operations.
 #[derive(Debug)]
@@ -18,6 +19,22 @@ pub struct DBTransportError(pub TransportError);
 
 impl DBErrorMarker for DBTransportError {}
 
+impl DBTransportError {
+    /// Returns `true` if the request failed for a reason that can go away on retry, like a rate
+    /// limit or a temporarily unavailable server.
+    ///
+    /// Can be used as the check of [`ResilientDb::with_transient_check`](crate::ResilientDb::with_transient_check).
+    pub fn is_transient(&self) -> bool {
+        match &self.0 {
+            RpcError::Transport(kind) => {
+                kind.is_retry_err() || matches!(kind, TransportErrorKind::BackendGone)
+            }
+            RpcError::ErrorResp(payload) => payload.is_retry_err(),
+            _ => false,
+        }
+    }
+}
+
 impl Display for DBTransportError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Transport error: {}", self.0)
@@ -41,6 +58,8 @@ pub struct AlloyDB<N: Network, P: Provider<N>> {
     provider: P,
     /// The block number on which the queries will be based on.
     block_number: BlockId,
+    /// Store of analysis artifacts used when fetched code is analyzed.
+    analysis_store: Option<Arc<dyn AnalysisStore>>,
     _marker: core::marker::PhantomData<fn() -> N>,
 }
 
@@ -50,10 +69,17 @@ impl<N: Network, P: Provider<N>> AlloyDB<N, P> {
         Self {
             provider,
             block_number,
+            analysis_store: None,
             _marker: core::marker::PhantomData,
         }
     }
 
+    /// Sets the store of analysis artifacts used when fetched code is analyzed.
+    pub fn with_analysis_store(mut self, store: Arc<dyn AnalysisStore>) -> Self {
+        self.analysis_store = Some(store);
+        self
+    }
+
     /// Sets the block number on which the queries will be based on.
     pub fn set_block_number(&mut self, block_number: BlockId) {
         self.block_number = block_number;
@@ -80,8 +106,17 @@ impl<N: Network, P: Provider<N>> DatabaseAsyncRef for AlloyDB<N, P> {
         let (nonce, balance, code) = tokio::join!(nonce, balance, code,);
 
         let balance = balance?;
-        let code = Bytecode::new_raw(code?.0.into());
-        let code_hash = code.hash_slow();
+        let code = code?.0;
+        let code_hash = if code.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            keccak256(&code)
+        };
+        let code = match &self.analysis_store {
+            Some(store) => Bytecode::new_raw_checked_with_store(code.into(), code_hash, &**store)
+                .expect("Expect correct bytecode"),
+            None => Bytecode::new_raw(code.into()),
+        };
         let nonce = nonce?;
 
         Ok(Some(AccountInfo::new(balance, nonce, code_hash, code)))
@@ -0,0 +1,235 @@
+//! Compressed storage of contract code.
+use bytecode::AnalysisStore;
+use primitives::{Bytes, HashMap, B256};
+use state::Bytecode;
+use std::{collections::BTreeMap, sync::Arc, vec::Vec};
+
+/// Default number of decoded contracts kept by [`CompressedCodeStore`].
+pub const DEFAULT_DECODED_CODE_CAPACITY: usize = 1024;
+
+/// Compression used for stored contract code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeCompression {
+    /// Code is stored as is, only the decoded cache is bounded.
+    None,
+    /// Code is compressed with zstd at the given level.
+    #[cfg(feature = "zstd")]
+    Zstd(i32),
+}
+
+impl CodeCompression {
+    /// Compresses the code.
+    pub fn compress(&self, code: &[u8]) -> Vec<u8> {
+        match self {
+            Self::None => code.to_vec(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(level) => zstd::encode_all(code, *level).expect("compress into memory"),
+        }
+    }
+
+    /// Decompresses the code, returns `None` if data is malformed.
+    pub fn decompress(&self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Self::None => Some(data.to_vec()),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => zstd::decode_all(data).ok(),
+        }
+    }
+}
+
+/// Contract code stored compressed by its code hash.
+///
+/// Code is decompressed and analyzed lazily when it is requested. Decoded code is kept in a
+/// least recently used cache bounded by the number of contracts. With an [`AnalysisStore`] set,
+/// the analysis of decoded code is reused from the store.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedCodeStore {
+    compression: CodeCompression,
+    codes: HashMap<B256, Bytes>,
+    decoded_capacity: usize,
+    /// Decoded code with the tick of its last use.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    decoded: HashMap<B256, (Bytecode, u64)>,
+    /// Code hashes of decoded code ordered by the tick of their last use.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lru: BTreeMap<u64, B256>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tick: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    analysis_store: Option<Arc<dyn AnalysisStore>>,
+}
+
+impl CompressedCodeStore {
+    /// Creates an empty store that keeps at most `decoded_capacity` decoded contracts.
+    pub fn new(compression: CodeCompression, decoded_capacity: usize) -> Self {
+        Self {
+            compression,
+            codes: HashMap::default(),
+            decoded_capacity,
+            decoded: HashMap::default(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            analysis_store: None,
+        }
+    }
+
+    /// Sets the store of analysis artifacts used when code is decoded.
+    pub fn with_analysis_store(mut self, store: Arc<dyn AnalysisStore>) -> Self {
+        self.analysis_store = Some(store);
+        self
+    }
+
+    /// Returns the compression of the store.
+    pub fn compression(&self) -> CodeCompression {
+        self.compression
+    }
+
+    /// Returns the number of stored contracts.
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Returns `true` if no contract is stored.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Returns the number of decoded contracts in the cache.
+    pub fn decoded_len(&self) -> usize {
+        self.decoded.len()
+    }
+
+    /// Returns the total size of the compressed code in bytes.
+    pub fn compressed_size(&self) -> usize {
+        self.codes.values().map(|code| code.len()).sum()
+    }
+
+    /// Returns `true` if the code is stored.
+    pub fn contains(&self, code_hash: &B256) -> bool {
+        self.codes.contains_key(code_hash)
+    }
+
+    /// Compresses and stores the code if it is not stored already.
+    pub fn insert(&mut self, code_hash: B256, code: &Bytecode) {
+        if !self.codes.contains_key(&code_hash) {
+            let compressed = self.compression.compress(&code.original_bytes());
+            self.codes.insert(code_hash, compressed.into());
+        }
+    }
+
+    /// Returns the code, decoding it if it is not in the cache.
+    pub fn get(&mut self, code_hash: B256) -> Option<Bytecode> {
+        self.tick += 1;
+        if let Some((code, tick)) = self.decoded.get_mut(&code_hash) {
+            self.lru.remove(tick);
+            *tick = self.tick;
+            self.lru.insert(self.tick, code_hash);
+            return Some(code.clone());
+        }
+
+        let code = self.get_uncached(code_hash)?;
+        if self.decoded_capacity == 0 {
+            return Some(code);
+        }
+        if self.decoded.len() >= self.decoded_capacity {
+            if let Some((_, evicted)) = self.lru.pop_first() {
+                self.decoded.remove(&evicted);
+            }
+        }
+        self.decoded.insert(code_hash, (code.clone(), self.tick));
+        self.lru.insert(self.tick, code_hash);
+        Some(code)
+    }
+
+    /// Returns the code without updating the decoded cache.
+    pub fn get_uncached(&self, code_hash: B256) -> Option<Bytecode> {
+        if let Some((code, _)) = self.decoded.get(&code_hash) {
+            return Some(code.clone());
+        }
+        let bytes = self.compression.decompress(self.codes.get(&code_hash)?)?;
+        decode(bytes.into(), code_hash, self.analysis_store.as_deref())
+    }
+
+    /// Decodes all stored code.
+    pub fn into_decoded(self) -> impl Iterator<Item = (B256, Bytecode)> {
+        let compression = self.compression;
+        let analysis_store = self.analysis_store;
+        self.codes.into_iter().filter_map(move |(code_hash, data)| {
+            let bytes = compression.decompress(&data)?;
+            Some((
+                code_hash,
+                decode(bytes.into(), code_hash, analysis_store.as_deref())?,
+            ))
+        })
+    }
+}
+
+/// Decodes the code, reusing the analysis from the store if it is set.
+fn decode(bytes: Bytes, code_hash: B256, store: Option<&dyn AnalysisStore>) -> Option<Bytecode> {
+    match store {
+        Some(store) => Bytecode::new_raw_checked_with_store(bytes, code_hash, store),
+        None => Bytecode::new_raw_checked(bytes),
+    }
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use state::bytecode::opcode;
+
+    fn code(n: u8) -> Bytecode {
+        Bytecode::new_raw([opcode::PUSH1, n, opcode::PUSH0, opcode::MSTORE].into())
+    }
+
+    #[test]
+    fn decoded_cache_evicts_least_recently_used() {
+        let mut store = CompressedCodeStore::new(CodeCompression::None, 2);
+        for n in 0..3 {
+            store.insert(B256::with_last_byte(n), &code(n));
+        }
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.decoded_len(), 0);
+
+        assert_eq!(store.get(B256::with_last_byte(0)), Some(code(0)));
+        assert_eq!(store.get(B256::with_last_byte(1)), Some(code(1)));
+        // Touch 0 so 1 is evicted.
+        store.get(B256::with_last_byte(0));
+        assert_eq!(store.get(B256::with_last_byte(2)), Some(code(2)));
+        assert_eq!(store.decoded_len(), 2);
+        assert!(!store.decoded.contains_key(&B256::with_last_byte(1)));
+        assert_eq!(store.get(B256::with_last_byte(1)), Some(code(1)));
+        assert_eq!(store.get(B256::with_last_byte(3)), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decoded_code_uses_analysis_store() {
+        use bytecode::{ArtifactKind, InMemoryAnalysisStore};
+
+        let analysis_store = Arc::new(InMemoryAnalysisStore::new());
+        let mut store = CompressedCodeStore::new(CodeCompression::None, 0)
+            .with_analysis_store(analysis_store.clone());
+        let code_hash = code(1).hash_slow();
+        store.insert(code_hash, &code(1));
+        assert!(analysis_store.is_empty());
+
+        let decoded = store.get(code_hash).unwrap();
+        assert_eq!(decoded.original_bytes(), code(1).original_bytes());
+        assert!(analysis_store
+            .load(code_hash, ArtifactKind::JumpTable)
+            .is_some());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_roundtrip() {
+        let large = Bytecode::new_raw(vec![opcode::JUMPDEST; 4096].into());
+        let mut store = CompressedCodeStore::new(CodeCompression::Zstd(3), 1);
+        store.insert(B256::ZERO, &large);
+        assert!(store.compressed_size() < 4096);
+        assert_eq!(store.get_uncached(B256::ZERO), Some(large));
+    }
+}
@@ -1,10 +1,14 @@
+use crate::code_store::{CodeCompression, CompressedCodeStore};
+#[cfg(feature = "std")]
+use crate::interner::CodeInterner;
 use core::convert::Infallible;
 use database_interface::{
-    Database, DatabaseCommit, DatabaseRef, EmptyDB, BENCH_CALLER, BENCH_CALLER_BALANCE,
-    BENCH_TARGET, BENCH_TARGET_BALANCE,
+    Database, DatabaseCommit, DatabaseRef, EmptyDB, SlowRead, SlowReads, BENCH_CALLER,
+    BENCH_CALLER_BALANCE, BENCH_TARGET, BENCH_TARGET_BALANCE,
 };
 use primitives::{
-    hash_map::Entry, Address, HashMap, Log, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
+    create_address::create2_address, hash_map::Entry, Address, HashMap, Log, StorageKey,
+    StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use state::{Account, AccountInfo, Bytecode};
 use std::vec::Vec;
@@ -29,6 +33,16 @@ pub struct Cache {
     pub logs: Vec<Log>,
     /// All cached block hashes from the [DatabaseRef].
     pub block_hashes: HashMap<U256, B256>,
+    /// Compressed contracts, new contracts are stored here instead of `contracts` if set.
+    ///
+    /// See [`CacheDB::with_code_compression`].
+    pub compressed_contracts: Option<CompressedCodeStore>,
+    /// Interner of the code inserted into the cache and loaded from the underlying database.
+    ///
+    /// See [`CacheDB::with_code_interner`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub code_interner: Option<CodeInterner>,
 }
 
 impl Default for Cache {
@@ -42,7 +56,69 @@ impl Default for Cache {
             contracts,
             logs: Vec::default(),
             block_hashes: HashMap::default(),
+            compressed_contracts: None,
+            #[cfg(feature = "std")]
+            code_interner: None,
+        }
+    }
+}
+
+impl Cache {
+    /// Inserts the code into `compressed_contracts` if set, otherwise into `contracts`.
+    pub fn insert_code(&mut self, code_hash: B256, code: &Bytecode) {
+        match &mut self.compressed_contracts {
+            Some(store) if !code.is_empty() => store.insert(code_hash, code),
+            _ => {
+                if !self.contracts.contains_key(&code_hash) {
+                    let code = self.intern(code_hash, code.clone());
+                    self.contracts.insert(code_hash, code);
+                }
+            }
+        }
+    }
+
+    /// Returns the interned instance of the code if `code_interner` is set.
+    #[cfg(feature = "std")]
+    pub fn intern(&self, code_hash: B256, code: Bytecode) -> Bytecode {
+        match &self.code_interner {
+            Some(interner) => interner.intern(code_hash, code),
+            None => code,
+        }
+    }
+
+    /// Returns the code, code is not interned without the `std` feature.
+    #[cfg(not(feature = "std"))]
+    pub fn intern(&self, _code_hash: B256, code: Bytecode) -> Bytecode {
+        code
+    }
+
+    /// Replaces the code of the account with its interned instance if `code_interner` is set.
+    #[cfg(feature = "std")]
+    pub fn intern_info(&self, info: &mut AccountInfo) {
+        if let Some(interner) = &self.code_interner {
+            interner.intern_info(info);
+        }
+    }
+
+    /// Does nothing, code is not interned without the `std` feature.
+    #[cfg(not(feature = "std"))]
+    pub fn intern_info(&self, _info: &mut AccountInfo) {}
+
+    /// Returns the code from `contracts` or `compressed_contracts`.
+    pub fn code(&mut self, code_hash: B256) -> Option<Bytecode> {
+        if let Some(code) = self.contracts.get(&code_hash) {
+            return Some(code.clone());
         }
+        self.compressed_contracts.as_mut()?.get(code_hash)
+    }
+
+    /// Returns the code from `contracts` or `compressed_contracts` without updating the cache of
+    /// decoded code.
+    pub fn code_ref(&self, code_hash: B256) -> Option<Bytecode> {
+        if let Some(code) = self.contracts.get(&code_hash) {
+            return Some(code.clone());
+        }
+        self.compressed_contracts.as_ref()?.get_uncached(code_hash)
     }
 }
 
@@ -82,12 +158,21 @@ impl<ExtDb> CacheDB<CacheDB<ExtDb>> {
                     contracts,
                     logs,
                     block_hashes,
+                    compressed_contracts,
+                    ..
                 },
             db: mut inner,
         } = self;
 
         inner.cache.accounts.extend(accounts);
         inner.cache.contracts.extend(contracts);
+        for (code_hash, code) in compressed_contracts
+            .into_iter()
+            .flat_map(CompressedCodeStore::into_decoded)
+        {
+            inner.cache.contracts.remove(&code_hash);
+            inner.cache.insert_code(code_hash, &code);
+        }
         inner.cache.logs.extend(logs);
         inner.cache.block_hashes.extend(block_hashes);
         inner
@@ -108,6 +193,44 @@ impl<ExtDB> CacheDB<ExtDB> {
         }
     }
 
+    /// Stores contracts compressed, decoding them lazily when they are loaded.
+    ///
+    /// At most `decoded_capacity` decoded contracts are kept in memory. Contracts already in the
+    /// cache are moved to the compressed store.
+    pub fn with_code_compression(
+        mut self,
+        compression: CodeCompression,
+        decoded_capacity: usize,
+    ) -> Self {
+        let mut store = CompressedCodeStore::new(compression, decoded_capacity);
+        self.cache.contracts.retain(|code_hash, code| {
+            if code.is_empty() {
+                return true;
+            }
+            store.insert(*code_hash, code);
+            false
+        });
+        self.cache.compressed_contracts = Some(store);
+        self
+    }
+
+    /// Shares the code of the cache with other databases using the same interner.
+    ///
+    /// Code inserted into the cache and code loaded from the underlying database is replaced with
+    /// its interned instance. Code stored compressed, see [`CacheDB::with_code_compression`], is
+    /// not interned.
+    #[cfg(feature = "std")]
+    pub fn with_code_interner(mut self, interner: CodeInterner) -> Self {
+        for (code_hash, code) in self.cache.contracts.iter_mut() {
+            *code = interner.intern(*code_hash, code.clone());
+        }
+        for account in self.cache.accounts.values_mut() {
+            interner.intern_info(&mut account.info);
+        }
+        self.cache.code_interner = Some(interner);
+        self
+    }
+
     /// Inserts the account's code into the cache.
     ///
     /// Accounts objects and code are stored separately in the cache, this will take the code from the account and instead map it to the code hash.
@@ -119,12 +242,10 @@ impl<ExtDB> CacheDB<ExtDB> {
                 if account.code_hash == KECCAK_EMPTY {
                     account.code_hash = code.hash_slow();
                 }
-                self.cache
-                    .contracts
-                    .entry(account.code_hash)
-                    .or_insert_with(|| code.clone());
+                self.cache.insert_code(account.code_hash, code);
             }
         }
+        self.cache.intern_info(account);
         if account.code_hash.is_zero() {
             account.code_hash = KECCAK_EMPTY;
         }
@@ -144,6 +265,46 @@ impl<ExtDB> CacheDB<ExtDB> {
     pub fn nest(self) -> CacheDB<Self> {
         CacheDB::new(self)
     }
+
+    /// Returns an iterator over all cached accounts, in arbitrary order.
+    pub fn accounts(&self) -> impl Iterator<Item = (&Address, &DbAccount)> {
+        self.cache.accounts.iter()
+    }
+
+    /// Returns an iterator over the cached accounts that were changed by committed transactions.
+    pub fn touched_accounts(&self) -> impl Iterator<Item = (&Address, &DbAccount)> {
+        self.accounts()
+            .filter(|(_, account)| account.account_state.is_touched())
+    }
+
+    /// Returns an iterator over the cached accounts that were created by committed transactions.
+    pub fn created_accounts(&self) -> impl Iterator<Item = (&Address, &DbAccount)> {
+        self.accounts()
+            .filter(|(_, account)| account.account_state.is_storage_cleared())
+    }
+
+    /// Returns an iterator over the cached accounts whose address starts with `prefix`.
+    pub fn accounts_with_prefix<'a>(
+        &'a self,
+        prefix: &'a [u8],
+    ) -> impl Iterator<Item = (&'a Address, &'a DbAccount)> {
+        self.accounts()
+            .filter(move |(address, _)| address.starts_with(prefix))
+    }
+
+    /// Returns an iterator over the cached storage slots of the account, in arbitrary order.
+    ///
+    /// Slots that were never loaded or written are not included.
+    pub fn account_storage(
+        &self,
+        address: Address,
+    ) -> impl Iterator<Item = (&StorageKey, &StorageValue)> {
+        self.cache
+            .accounts
+            .get(&address)
+            .into_iter()
+            .flat_map(|account| account.storage.iter())
+    }
 }
 
 impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
@@ -154,14 +315,14 @@ impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
         let db = &self.db;
         match self.cache.accounts.entry(address) {
             Entry::Occupied(entry) => Ok(entry.into_mut()),
-            Entry::Vacant(entry) => Ok(entry.insert(
-                db.basic_ref(address)?
-                    .map(|info| DbAccount {
-                        info,
-                        ..Default::default()
-                    })
-                    .unwrap_or_else(DbAccount::new_not_existing),
-            )),
+            Entry::Vacant(entry) => {
+                let account = entry.insert(db.basic_ref(address)?.into());
+                #[cfg(feature = "std")]
+                if let Some(interner) = &self.cache.code_interner {
+                    interner.intern_info(&mut account.info);
+                }
+                Ok(account)
+            }
         }
     }
 
@@ -188,6 +349,37 @@ impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
         account.storage = storage.into_iter().collect();
         Ok(())
     }
+
+    /// Sets code of the account without overriding its balance or storage.
+    ///
+    /// Nonce is bumped to one if it was zero, same as for contracts deployed with EIP-161.
+    pub fn insert_account_code(
+        &mut self,
+        address: Address,
+        code: Bytecode,
+    ) -> Result<(), ExtDB::Error> {
+        let mut info = self.load_account(address)?.info.clone();
+        info.nonce = info.nonce.max(1);
+        info.code_hash = code.hash_slow();
+        info.code = Some(code);
+        self.insert_account_info(address, info);
+        Ok(())
+    }
+
+    /// Places `code` at the address where `deployer` would deploy `init_code` with `CREATE2` and `salt`.
+    ///
+    /// Init code is not executed, this is useful to simulate contracts deployed by factories.
+    pub fn insert_create2_code(
+        &mut self,
+        deployer: Address,
+        salt: B256,
+        init_code: &[u8],
+        code: Bytecode,
+    ) -> Result<Address, ExtDB::Error> {
+        let address = create2_address(deployer, salt, init_code);
+        self.insert_account_code(address, code)?;
+        Ok(address)
+    }
 }
 
 impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
@@ -228,33 +420,40 @@ impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
     }
 }
 
+impl<ExtDB: SlowReads> SlowReads for CacheDB<ExtDB> {
+    fn take_slow_reads(&mut self) -> Vec<SlowRead> {
+        self.db.take_slow_reads()
+    }
+}
+
 impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
     type Error = ExtDB::Error;
 
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
         let basic = match self.cache.accounts.entry(address) {
             Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(
-                self.db
-                    .basic_ref(address)?
-                    .map(|info| DbAccount {
-                        info,
-                        ..Default::default()
-                    })
-                    .unwrap_or_else(DbAccount::new_not_existing),
-            ),
+            Entry::Vacant(entry) => {
+                let account = entry.insert(self.db.basic_ref(address)?.into());
+                #[cfg(feature = "std")]
+                if let Some(interner) = &self.cache.code_interner {
+                    interner.intern_info(&mut account.info);
+                }
+                account
+            }
         };
         Ok(basic.info())
     }
 
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
-        match self.cache.contracts.entry(code_hash) {
-            Entry::Occupied(entry) => Ok(entry.get().clone()),
-            Entry::Vacant(entry) => {
-                // If you return code bytes when basic fn is called this function is not needed.
-                Ok(entry.insert(self.db.code_by_hash_ref(code_hash)?).clone())
-            }
+        if let Some(code) = self.cache.code(code_hash) {
+            return Ok(code);
         }
+        // If you return code bytes when basic fn is called this function is not needed.
+        let code = self
+            .cache
+            .intern(code_hash, self.db.code_by_hash_ref(code_hash)?);
+        self.cache.insert_code(code_hash, &code);
+        Ok(code)
     }
 
     /// Get the value in an account's storage slot.
@@ -295,6 +494,11 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
                 } else {
                     (info.into(), StorageValue::ZERO)
                 };
+                #[cfg(feature = "std")]
+                if let Some(interner) = &self.cache.code_interner {
+                    interner.intern_info(&mut acc_entry.insert(account).info);
+                    return Ok(value);
+                }
                 acc_entry.insert(account);
                 Ok(value)
             }
@@ -324,8 +528,8 @@ impl<ExtDB: DatabaseRef> DatabaseRef for CacheDB<ExtDB> {
     }
 
     fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
-        match self.cache.contracts.get(&code_hash) {
-            Some(entry) => Ok(entry.clone()),
+        match self.cache.code_ref(code_hash) {
+            Some(code) => Ok(code),
             None => self.db.code_by_hash_ref(code_hash),
         }
     }
@@ -442,6 +646,11 @@ impl AccountState {
     pub fn is_storage_cleared(&self) -> bool {
         matches!(self, AccountState::StorageCleared)
     }
+
+    /// Returns `true` if EVM changed this account
+    pub fn is_touched(&self) -> bool {
+        matches!(self, AccountState::Touched | AccountState::StorageCleared)
+    }
 }
 
 /// Custom benchmarking DB that only has account info for the zero address.
@@ -503,10 +712,11 @@ impl Database for BenchmarkDB {
 
 #[cfg(test)]
 mod tests {
-    use super::{CacheDB, EmptyDB};
-    use database_interface::Database;
-    use primitives::{Address, HashMap, StorageKey, StorageValue};
-    use state::AccountInfo;
+    use super::{CacheDB, CodeCompression, EmptyDB};
+    use database_interface::{Database, DatabaseCommit, DatabaseRef};
+    use primitives::{Address, Bytes, HashMap, StorageKey, StorageValue, B256};
+    use state::{Account, AccountInfo, Bytecode};
+    use std::vec::Vec;
 
     #[test]
     fn test_insert_account_storage() {
@@ -531,6 +741,71 @@ mod tests {
         assert_eq!(new_state.storage(account, key), Ok(value));
     }
 
+    #[test]
+    fn test_batched_fetch() {
+        let account = Address::with_last_byte(42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            account,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(account, StorageKey::from(1), StorageValue::from(2))
+            .unwrap();
+
+        let (info, values) = db
+            .basic_with_storage(account, &[StorageKey::from(1), StorageKey::from(3)])
+            .unwrap();
+        assert_eq!(info.unwrap().nonce, 1);
+        assert_eq!(values, [StorageValue::from(2), StorageValue::ZERO]);
+
+        let infos = db
+            .many_basics(&[account, Address::with_last_byte(43)])
+            .unwrap();
+        assert_eq!(infos.len(), 2);
+        assert!(infos[0].is_some());
+        assert!(infos[1].is_none());
+    }
+
+    #[test]
+    fn test_insert_create2_code() {
+        let deployer = Address::with_last_byte(42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        let address = db
+            .insert_create2_code(deployer, B256::ZERO, &[0x60, 0x00], code.clone())
+            .unwrap();
+
+        assert_eq!(
+            address,
+            deployer.create2_from_code(B256::ZERO, [0x60, 0x00])
+        );
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.nonce, 1);
+        assert_eq!(db.code_by_hash(info.code_hash).unwrap(), code);
+    }
+
+    #[test]
+    fn test_code_compression() {
+        let address = Address::with_last_byte(42);
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x01, 0x00]));
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::default().with_code(code.clone()));
+        let mut db = db.with_code_compression(CodeCompression::None, 1);
+        let store = db.cache.compressed_contracts.as_ref().unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.decoded_len(), 0);
+
+        let code_hash = db.basic(address).unwrap().unwrap().code_hash;
+        assert_eq!(db.code_by_hash_ref(code_hash).unwrap(), code);
+        assert_eq!(db.code_by_hash(code_hash).unwrap(), code);
+        let store = db.cache.compressed_contracts.as_ref().unwrap();
+        assert_eq!(store.decoded_len(), 1);
+        assert!(!db.cache.contracts.contains_key(&code_hash));
+    }
+
     #[test]
     fn test_replace_account_storage() {
         let account = Address::with_last_byte(42);
@@ -589,4 +864,41 @@ mod tests {
             nonce
         );
     }
+
+    #[test]
+    fn test_account_iteration() {
+        let loaded = Address::with_last_byte(1);
+        let touched = Address::with_last_byte(2);
+        let created = Address::repeat_byte(0xaa);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(loaded, AccountInfo::default());
+        db.insert_account_storage(loaded, StorageKey::from(1), StorageValue::from(2))
+            .unwrap();
+
+        let mut touched_account = Account::from(AccountInfo::from_balance(StorageValue::from(1)));
+        touched_account.mark_touch();
+        let mut created_account = touched_account.clone();
+        created_account.mark_created();
+        db.commit(HashMap::from_iter([
+            (touched, touched_account),
+            (created, created_account),
+        ]));
+
+        assert_eq!(db.accounts().count(), 3);
+        let mut touched_accounts: Vec<_> = db.touched_accounts().map(|(a, _)| *a).collect();
+        touched_accounts.sort();
+        assert_eq!(touched_accounts, [touched, created]);
+        let created_accounts: Vec<_> = db.created_accounts().map(|(a, _)| *a).collect();
+        assert_eq!(created_accounts, [created]);
+        let prefixed: Vec<_> = db
+            .accounts_with_prefix(&[0xaa, 0xaa])
+            .map(|(a, _)| *a)
+            .collect();
+        assert_eq!(prefixed, [created]);
+
+        let storage: Vec<_> = db.account_storage(loaded).collect();
+        assert_eq!(storage, [(&StorageKey::from(1), &StorageValue::from(2))]);
+        assert_eq!(db.account_storage(touched).count(), 0);
+        assert_eq!(db.account_storage(Address::ZERO).count(), 0);
+    }
 }
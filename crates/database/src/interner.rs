@@ -0,0 +1,264 @@
+//! Contract code shared between databases.
+use primitives::{HashMap, B256, KECCAK_EMPTY};
+use state::{AccountInfo, Bytecode};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+/// Interner of contract code by code hash.
+///
+/// Replaying many transactions loads the same contracts over and over, often into databases that
+/// are created for every block. Every load allocates a new copy of the code and of its analysis.
+/// The interner keeps one instance of every code and hands out clones of it, which share the
+/// allocation of the bytes and of the jump table.
+///
+/// The interner is a handle, clones of it share the interned code and can be given to
+/// [`CacheDB`](crate::CacheDB), [`State`](crate::State) and [`BundleState`](crate::BundleState)
+/// at the same time. Code loaded by the journal comes from the database, so the journal holds
+/// interned code as well. Addresses and code hashes are fixed-size values stored inline, they
+/// are the keys of the interned code and are not interned themselves.
+///
+/// The interned code is bounded by [`CodeInterner::capacity`] bytes, the code interned first
+/// is evicted when new code doesn't fit. Evicted code handed out before stays valid.
+#[derive(Clone, Debug, Default)]
+pub struct CodeInterner {
+    codes: Arc<RwLock<InternedCodes>>,
+}
+
+/// Interned code and the order it was interned in.
+#[derive(Debug)]
+struct InternedCodes {
+    codes: HashMap<B256, Bytecode>,
+    order: VecDeque<B256>,
+    size: usize,
+    capacity: usize,
+}
+
+impl Default for InternedCodes {
+    fn default() -> Self {
+        Self::with_capacity(CodeInterner::DEFAULT_CAPACITY)
+    }
+}
+
+impl InternedCodes {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            codes: HashMap::default(),
+            order: VecDeque::new(),
+            size: 0,
+            capacity,
+        }
+    }
+
+    fn insert(&mut self, code_hash: B256, code: Bytecode) -> Bytecode {
+        if let Some(interned) = self.codes.get(&code_hash) {
+            return interned.clone();
+        }
+        let len = code.original_byte_slice().len();
+        if len > self.capacity {
+            return code;
+        }
+        while self.size + len > self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = self.codes.remove(&oldest) {
+                self.size -= evicted.original_byte_slice().len();
+            }
+        }
+        self.size += len;
+        self.order.push_back(code_hash);
+        self.codes.insert(code_hash, code.clone());
+        code
+    }
+
+    fn clear(&mut self) {
+        self.codes.clear();
+        self.order.clear();
+        self.size = 0;
+    }
+}
+
+impl CodeInterner {
+    /// Default maximum size of the interned code in bytes, 256 MiB.
+    pub const DEFAULT_CAPACITY: usize = 256 * 1024 * 1024;
+
+    /// Creates an empty interner that holds at most [`CodeInterner::DEFAULT_CAPACITY`] bytes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty interner that holds at most `capacity` bytes of code.
+    ///
+    /// Code larger than the capacity is not interned.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            codes: Arc::new(RwLock::new(InternedCodes::with_capacity(capacity))),
+        }
+    }
+
+    /// Returns the maximum size of the interned code in bytes.
+    pub fn capacity(&self) -> usize {
+        self.read().capacity
+    }
+
+    /// Returns the interned instance of the code, interning the code if it is new.
+    ///
+    /// The code hash is trusted to be the hash of the code. Empty code is returned as is.
+    pub fn intern(&self, code_hash: B256, code: Bytecode) -> Bytecode {
+        if code_hash == KECCAK_EMPTY || code.is_empty() {
+            return code;
+        }
+        if let Some(interned) = self.get(&code_hash) {
+            return interned;
+        }
+        self.codes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(code_hash, code)
+    }
+
+    /// Replaces the code of the account with its interned instance.
+    pub fn intern_info(&self, info: &mut AccountInfo) {
+        if let Some(code) = info.code.take() {
+            info.code = Some(self.intern(info.code_hash, code));
+        }
+    }
+
+    /// Returns the interned code.
+    pub fn get(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.read().codes.get(code_hash).cloned()
+    }
+
+    /// Returns the number of interned contracts.
+    pub fn len(&self) -> usize {
+        self.read().codes.len()
+    }
+
+    /// Returns `true` if no contract is interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total size of the interned code in bytes.
+    pub fn code_size(&self) -> usize {
+        self.read().size
+    }
+
+    /// Removes all interned code.
+    ///
+    /// Code handed out before stays valid, it is no longer shared with code interned later.
+    pub fn clear(&self) {
+        self.codes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, InternedCodes> {
+        self.codes.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl PartialEq for CodeInterner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.codes, &other.codes)
+    }
+}
+
+impl Eq for CodeInterner {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CacheDB, EmptyDB, State};
+    use database_interface::Database;
+    use primitives::{address, U256};
+    use state::bytecode::opcode;
+
+    fn code() -> Bytecode {
+        Bytecode::new_raw([opcode::PUSH1, 1, opcode::PUSH0, opcode::MSTORE].into())
+    }
+
+    #[test]
+    fn interned_code_is_shared() {
+        let interner = CodeInterner::new();
+        let first = code();
+        let hash = first.hash_slow();
+        let first = interner.intern(hash, first);
+
+        let second = interner.clone().intern(hash, code());
+        assert_eq!(first, second);
+        assert_eq!(
+            first.original_byte_slice().as_ptr(),
+            second.original_byte_slice().as_ptr()
+        );
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.code_size(), 4);
+
+        // Empty code is not interned.
+        interner.intern(KECCAK_EMPTY, Bytecode::default());
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn oldest_code_is_evicted() {
+        let interner = CodeInterner::with_capacity(8);
+        let codes: Vec<_> = (1..=3u8)
+            .map(|i| Bytecode::new_raw([opcode::PUSH1, i, opcode::POP, opcode::STOP].into()))
+            .collect();
+        for code in &codes {
+            interner.intern(code.hash_slow(), code.clone());
+        }
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.code_size(), 8);
+        assert!(interner.get(&codes[0].hash_slow()).is_none());
+        assert!(interner.get(&codes[2].hash_slow()).is_some());
+
+        // Code larger than the capacity is returned without being interned.
+        let large = Bytecode::new_raw([opcode::STOP; 9].into());
+        interner.intern(large.hash_slow(), large.clone());
+        assert_eq!(interner.len(), 2);
+
+        interner.clear();
+        assert!(interner.is_empty());
+        assert_eq!(interner.code_size(), 0);
+    }
+
+    #[test]
+    fn databases_share_interned_code() {
+        let interner = CodeInterner::new();
+        let address = address!("0x1000000000000000000000000000000000000001");
+        let info = || AccountInfo::from_bytecode(code()).with_balance(U256::from(1));
+
+        let mut first = CacheDB::new(EmptyDB::new()).with_code_interner(interner.clone());
+        first.insert_account_info(address, info());
+        let mut second = CacheDB::<EmptyDB>::default().with_code_interner(interner.clone());
+        second.insert_account_info(address, info());
+        let code_hash = info().code_hash;
+        let first_code = first.code_by_hash(code_hash).unwrap();
+        assert_eq!(
+            first_code.original_byte_slice().as_ptr(),
+            second
+                .code_by_hash(code_hash)
+                .unwrap()
+                .original_byte_slice()
+                .as_ptr()
+        );
+
+        // Code loaded by the state from a database without the interner.
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(address, info());
+        let mut state = State::builder()
+            .with_database(db)
+            .with_code_interner(interner.clone())
+            .build();
+        let loaded = state.basic(address).unwrap().unwrap().code.unwrap();
+        assert_eq!(
+            first_code.original_byte_slice().as_ptr(),
+            loaded.original_byte_slice().as_ptr()
+        );
+        assert_eq!(interner.len(), 1);
+    }
+}
@@ -0,0 +1,238 @@
+//! Database wrapper that measures the latency of every read and records the slowest ones.
+use core::{cell::RefCell, time::Duration};
+use database_interface::{
+    Database, DatabaseCommit, DatabaseRead, DatabaseRef, SlowRead, SlowReads,
+};
+use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
+use state::{Account, AccountInfo, Bytecode};
+use std::{mem, time::Instant, vec::Vec};
+
+/// Database wrapper that records reads of the inner database that take at least the latency
+/// budget.
+///
+/// Only the `max_reports` slowest reads are kept, they are taken with
+/// [`SlowReads::take_slow_reads`]. Useful for fork-backed simulators, where a few reads of a
+/// remote database like [`AlloyDB`](crate::AlloyDB) can dominate the execution time.
+///
+/// Reads of an account together with its storage are recorded as a single read of the account.
+/// Batched reads of multiple accounts are split into reads of single accounts.
+#[derive(Debug)]
+pub struct LatencyWatchdog<DB> {
+    db: DB,
+    budget: Duration,
+    max_reports: usize,
+    slow_reads: RefCell<Vec<SlowRead>>,
+}
+
+impl<DB> LatencyWatchdog<DB> {
+    /// Default number of reported reads.
+    pub const DEFAULT_MAX_REPORTS: usize = 16;
+
+    /// Wraps the database, reads taking at least `budget` are recorded.
+    pub fn new(db: DB, budget: Duration) -> Self {
+        Self {
+            db,
+            budget,
+            max_reports: Self::DEFAULT_MAX_REPORTS,
+            slow_reads: RefCell::default(),
+        }
+    }
+
+    /// Sets the number of the slowest reads that are kept.
+    pub fn with_max_reports(mut self, max_reports: usize) -> Self {
+        self.max_reports = max_reports;
+        self
+    }
+
+    /// Returns the latency budget.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns the inner database.
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Returns a mutable reference to the inner database.
+    pub fn db_mut(&mut self) -> &mut DB {
+        &mut self.db
+    }
+
+    /// Consumes the wrapper and returns the inner database.
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// Records the read started at `start` if it took at least the budget, returns `out`.
+    fn record<T>(&self, read: DatabaseRead, start: Instant, out: T) -> T {
+        let duration = start.elapsed();
+        if duration < self.budget || self.max_reports == 0 {
+            return out;
+        }
+        let mut slow_reads = self.slow_reads.borrow_mut();
+        // keep reads sorted from the slowest, equal reads keep their order.
+        let index = slow_reads.partition_point(|slow| slow.duration >= duration);
+        if index < self.max_reports {
+            slow_reads.insert(index, SlowRead { read, duration });
+            slow_reads.truncate(self.max_reports);
+        }
+        out
+    }
+}
+
+impl<DB> SlowReads for LatencyWatchdog<DB> {
+    fn take_slow_reads(&mut self) -> Vec<SlowRead> {
+        mem::take(self.slow_reads.get_mut())
+    }
+}
+
+impl<DB: Database> Database for LatencyWatchdog<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.basic(address);
+        self.record(DatabaseRead::Account(address), start, out)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.code_by_hash(code_hash);
+        self.record(DatabaseRead::Code(code_hash), start, out)
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.storage(address, index);
+        self.record(DatabaseRead::Storage(address, index), start, out)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.block_hash(number);
+        self.record(DatabaseRead::BlockHash(number), start, out)
+    }
+
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        let start = Instant::now();
+        let out = self.db.basic_with_storage(address, slots);
+        self.record(DatabaseRead::Account(address), start, out)
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for LatencyWatchdog<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.basic_ref(address);
+        self.record(DatabaseRead::Account(address), start, out)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.code_by_hash_ref(code_hash);
+        self.record(DatabaseRead::Code(code_hash), start, out)
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.storage_ref(address, index);
+        self.record(DatabaseRead::Storage(address, index), start, out)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let start = Instant::now();
+        let out = self.db.block_hash_ref(number);
+        self.record(DatabaseRead::BlockHash(number), start, out)
+    }
+
+    fn basic_with_storage_ref(
+        &self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        let start = Instant::now();
+        let out = self.db.basic_with_storage_ref(address, slots);
+        self.record(DatabaseRead::Account(address), start, out)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for LatencyWatchdog<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database_interface::EmptyDB;
+    use primitives::U256;
+    use std::thread;
+
+    /// Database that sleeps on storage reads for the slot number of milliseconds.
+    struct SlowStorageDb;
+
+    impl DatabaseRef for SlowStorageDb {
+        type Error = core::convert::Infallible;
+
+        fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            EmptyDB::new().basic_ref(address)
+        }
+
+        fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            EmptyDB::new().code_by_hash_ref(code_hash)
+        }
+
+        fn storage_ref(
+            &self,
+            _address: Address,
+            index: StorageKey,
+        ) -> Result<StorageValue, Self::Error> {
+            thread::sleep(Duration::from_millis(index.to()));
+            Ok(StorageValue::ZERO)
+        }
+
+        fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+            EmptyDB::new().block_hash_ref(number)
+        }
+    }
+
+    #[test]
+    fn test_slowest_reads() {
+        let mut db =
+            LatencyWatchdog::new(SlowStorageDb, Duration::from_millis(2)).with_max_reports(2);
+        for slot in [0u64, 5, 2, 10, 3] {
+            db.storage_ref(Address::ZERO, U256::from(slot)).unwrap();
+        }
+        db.basic_ref(Address::ZERO).unwrap();
+
+        let reads = db
+            .take_slow_reads()
+            .into_iter()
+            .map(|slow| slow.read)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reads,
+            [
+                DatabaseRead::Storage(Address::ZERO, U256::from(10)),
+                DatabaseRead::Storage(Address::ZERO, U256::from(5)),
+            ]
+        );
+        assert!(db.take_slow_reads().is_empty());
+    }
+}
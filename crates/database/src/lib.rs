@@ -9,15 +9,33 @@ mod alloydb;
 
 pub use database_interface::*;
 
+/// Compressed storage of contract code.
+pub mod code_store;
 /// In-memory database implementations.
 pub mod in_memory_db;
+/// Contract code shared between databases.
+#[cfg(feature = "std")]
+pub mod interner;
+/// Latency measuring wrapper that records slow reads.
+#[cfg(feature = "std")]
+pub mod latency;
+/// Retry and fallback wrapper for unreliable databases.
+#[cfg(feature = "std")]
+pub mod resilient;
 /// State management and tracking.
 pub mod states;
 
 #[cfg(feature = "alloydb")]
 pub use alloydb::{AlloyDB, BlockId, DBTransportError};
 
+pub use code_store::{CodeCompression, CompressedCodeStore};
 pub use in_memory_db::*;
+#[cfg(feature = "std")]
+pub use interner::CodeInterner;
+#[cfg(feature = "std")]
+pub use latency::LatencyWatchdog;
+#[cfg(feature = "std")]
+pub use resilient::{ResilientDb, RetryPolicy};
 pub use states::{
     AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
     OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
@@ -0,0 +1,356 @@
+//! Database wrapper that retries transient errors and falls back to a secondary database.
+use core::time::Duration;
+use database_interface::{Database, DatabaseCommit};
+use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
+use state::{Account, AccountInfo, Bytecode};
+use std::{thread, time::Instant, vec::Vec};
+
+/// Retry policy of [`ResilientDb`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first failed attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay, it doubles after every retry.
+    pub max_backoff: Duration,
+    /// Time after the first attempt in which retries can start, `None` for no limit.
+    ///
+    /// Requests to the database are not interrupted, the timeout only stops further retries.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            timeout: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    pub const fn no_retry() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            timeout: None,
+        }
+    }
+}
+
+/// Database wrapper that retries transient errors with exponential backoff and, if the retries
+/// are exhausted, reads from a fallback database.
+///
+/// Useful for remote databases like [`AlloyDB`](crate::AlloyDB) forks, where a network blip
+/// would otherwise fail the whole transaction. Errors are considered transient unless a check is
+/// set with [`ResilientDb::with_transient_check`], errors that are not transient are returned
+/// without a retry and without the fallback.
+#[derive(Debug)]
+pub struct ResilientDb<D: Database, F = D> {
+    db: D,
+    fallback: Option<F>,
+    policy: RetryPolicy,
+    is_transient: fn(&D::Error) -> bool,
+    retries: u64,
+    fallbacks: u64,
+}
+
+impl<D: Database> ResilientDb<D> {
+    /// Wraps the database with the default [`RetryPolicy`] and without a fallback.
+    pub fn new(db: D) -> Self {
+        Self {
+            db,
+            fallback: None,
+            policy: RetryPolicy::default(),
+            is_transient: |_| true,
+            retries: 0,
+            fallbacks: 0,
+        }
+    }
+}
+
+impl<D: Database, F> ResilientDb<D, F> {
+    /// Sets the retry policy.
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the check of errors that are worth retrying.
+    pub fn with_transient_check(mut self, is_transient: fn(&D::Error) -> bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    /// Sets the database read when retries of the primary database are exhausted.
+    pub fn with_fallback<F2: Database<Error = D::Error>>(self, fallback: F2) -> ResilientDb<D, F2> {
+        ResilientDb {
+            db: self.db,
+            fallback: Some(fallback),
+            policy: self.policy,
+            is_transient: self.is_transient,
+            retries: self.retries,
+            fallbacks: self.fallbacks,
+        }
+    }
+
+    /// Returns the primary database.
+    pub fn db(&self) -> &D {
+        &self.db
+    }
+
+    /// Returns a mutable reference to the primary database.
+    pub fn db_mut(&mut self) -> &mut D {
+        &mut self.db
+    }
+
+    /// Returns the number of retries done so far.
+    pub fn retries(&self) -> u64 {
+        self.retries
+    }
+
+    /// Returns the number of requests served by the fallback database.
+    pub fn fallbacks(&self) -> u64 {
+        self.fallbacks
+    }
+
+    /// Consumes the wrapper and returns the primary and fallback databases.
+    pub fn into_inner(self) -> (D, Option<F>) {
+        (self.db, self.fallback)
+    }
+}
+
+impl<D: Database, F: Database<Error = D::Error>> ResilientDb<D, F> {
+    fn with_retry<T>(
+        &mut self,
+        mut primary: impl FnMut(&mut D) -> Result<T, D::Error>,
+        fallback: impl FnOnce(&mut F) -> Result<T, D::Error>,
+    ) -> Result<T, D::Error> {
+        let start = Instant::now();
+        let mut backoff = self.policy.initial_backoff;
+        let mut attempt = 0;
+        let error = loop {
+            let error = match primary(&mut self.db) {
+                Ok(value) => return Ok(value),
+                Err(error) if !(self.is_transient)(&error) => return Err(error),
+                Err(error) => error,
+            };
+            let timed_out = self
+                .policy
+                .timeout
+                .is_some_and(|timeout| start.elapsed() + backoff > timeout);
+            if attempt >= self.policy.max_retries || timed_out {
+                break error;
+            }
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(self.policy.max_backoff);
+            attempt += 1;
+            self.retries += 1;
+        };
+
+        match &mut self.fallback {
+            Some(db) => {
+                self.fallbacks += 1;
+                fallback(db)
+            }
+            None => Err(error),
+        }
+    }
+}
+
+impl<D: Database, F: Database<Error = D::Error>> Database for ResilientDb<D, F> {
+    type Error = D::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.with_retry(|db| db.basic(address), |db| db.basic(address))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.with_retry(
+            |db| db.code_by_hash(code_hash),
+            |db| db.code_by_hash(code_hash),
+        )
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.with_retry(
+            |db| db.storage(address, index),
+            |db| db.storage(address, index),
+        )
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.with_retry(|db| db.block_hash(number), |db| db.block_hash(number))
+    }
+
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        self.with_retry(
+            |db| db.basic_with_storage(address, slots),
+            |db| db.basic_with_storage(address, slots),
+        )
+    }
+
+    fn many_basics(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.with_retry(
+            |db| db.many_basics(addresses),
+            |db| db.many_basics(addresses),
+        )
+    }
+}
+
+impl<D: Database + DatabaseCommit, F> DatabaseCommit for ResilientDb<D, F> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt;
+    use database_interface::DBErrorMarker;
+    use primitives::U256;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum FlakyError {
+        Network,
+        Fatal,
+    }
+
+    impl DBErrorMarker for FlakyError {}
+
+    impl fmt::Display for FlakyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+
+    impl core::error::Error for FlakyError {}
+
+    /// Fails the first `failures` requests with `error`.
+    #[derive(Debug)]
+    struct FlakyDb {
+        failures: u32,
+        error: fn() -> FlakyError,
+        requests: u32,
+    }
+
+    impl FlakyDb {
+        fn new(failures: u32, error: fn() -> FlakyError) -> Self {
+            Self {
+                failures,
+                error,
+                requests: 0,
+            }
+        }
+
+        fn request<T>(&mut self, value: T) -> Result<T, FlakyError> {
+            self.requests += 1;
+            if self.requests <= self.failures {
+                return Err((self.error)());
+            }
+            Ok(value)
+        }
+    }
+
+    impl Database for FlakyDb {
+        type Error = FlakyError;
+
+        fn basic(&mut self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.request(Some(AccountInfo::from_balance(U256::from(1))))
+        }
+
+        fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.request(Bytecode::default())
+        }
+
+        fn storage(
+            &mut self,
+            _address: Address,
+            _index: StorageKey,
+        ) -> Result<StorageValue, Self::Error> {
+            self.request(StorageValue::from(1))
+        }
+
+        fn block_hash(&mut self, _number: u64) -> Result<B256, Self::Error> {
+            self.request(B256::with_last_byte(1))
+        }
+    }
+
+    const NO_BACKOFF: RetryPolicy = RetryPolicy {
+        max_retries: 2,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        timeout: None,
+    };
+
+    #[test]
+    fn retries_transient_errors() {
+        let mut db =
+            ResilientDb::new(FlakyDb::new(2, || FlakyError::Network)).with_policy(NO_BACKOFF);
+        assert_eq!(db.block_hash(1), Ok(B256::with_last_byte(1)));
+        assert_eq!(db.retries(), 2);
+        assert_eq!(db.db().requests, 3);
+
+        let mut db =
+            ResilientDb::new(FlakyDb::new(3, || FlakyError::Network)).with_policy(NO_BACKOFF);
+        assert_eq!(db.block_hash(1), Err(FlakyError::Network));
+        assert_eq!(db.db().requests, 3);
+
+        let mut db = ResilientDb::new(FlakyDb::new(1, || FlakyError::Fatal))
+            .with_policy(NO_BACKOFF)
+            .with_transient_check(|error| *error == FlakyError::Network);
+        assert_eq!(db.block_hash(1), Err(FlakyError::Fatal));
+        assert_eq!(db.retries(), 0);
+    }
+
+    #[test]
+    fn falls_back_to_secondary() {
+        let mut db = ResilientDb::new(FlakyDb::new(u32::MAX, || FlakyError::Network))
+            .with_policy(NO_BACKOFF)
+            .with_fallback(FlakyDb::new(0, || FlakyError::Fatal));
+        assert_eq!(
+            db.storage(Address::ZERO, StorageKey::ZERO),
+            Ok(U256::from(1))
+        );
+        assert_eq!(db.retries(), 2);
+        assert_eq!(db.fallbacks(), 1);
+
+        // Errors that are not transient don't reach the fallback.
+        let mut db = ResilientDb::new(FlakyDb::new(1, || FlakyError::Fatal))
+            .with_transient_check(|error| *error == FlakyError::Network)
+            .with_fallback(FlakyDb::new(0, || FlakyError::Fatal));
+        assert_eq!(db.basic(Address::ZERO), Err(FlakyError::Fatal));
+        assert_eq!(db.fallbacks(), 0);
+    }
+
+    #[test]
+    fn timeout_stops_retries() {
+        let mut db = ResilientDb::new(FlakyDb::new(u32::MAX, || FlakyError::Network)).with_policy(
+            RetryPolicy {
+                max_retries: 10,
+                initial_backoff: Duration::from_millis(20),
+                max_backoff: Duration::from_millis(20),
+                timeout: Some(Duration::from_millis(30)),
+            },
+        );
+        assert_eq!(db.block_hash(1), Err(FlakyError::Network));
+        assert_eq!(db.retries(), 1);
+    }
+}
@@ -12,6 +12,8 @@ pub mod cache;
 pub mod cache_account;
 /// State changeset tracking.
 pub mod changes;
+/// EIP-7702 delegation queries.
+pub mod delegation;
 /// Plain account representation.
 pub mod plain_account;
 /// State revert tracking.
@@ -31,7 +33,10 @@ pub use bundle_account::BundleAccount;
 pub use bundle_state::{BundleBuilder, BundleState, OriginalValuesKnown};
 pub use cache::CacheState;
 pub use cache_account::CacheAccount;
-pub use changes::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset};
+pub use changes::{
+    BlockChangeset, PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
+};
+pub use delegation::EffectiveCode;
 pub use plain_account::{PlainAccount, StorageSlot, StorageWithOriginalValues};
 pub use reverts::{AccountRevert, RevertToSlot};
 pub use state::{DBBox, State, StateDBBox};
@@ -1,9 +1,11 @@
 use super::{
-    changes::{PlainStorageChangeset, StateChangeset},
+    changes::{BlockChangeset, PlainStorageChangeset, StateChangeset},
     reverts::{AccountInfoRevert, Reverts},
     AccountRevert, AccountStatus, BundleAccount, PlainStateReverts, RevertToSlot, StorageSlot,
     TransitionState,
 };
+#[cfg(feature = "std")]
+use crate::CodeInterner;
 use bytecode::Bytecode;
 use core::{mem, ops::RangeInclusive};
 use primitives::{
@@ -539,6 +541,20 @@ impl BundleState {
         self.contracts.get(hash).cloned()
     }
 
+    /// Replaces the contracts of the bundle and the code of its accounts with their interned
+    /// instances.
+    #[cfg(feature = "std")]
+    pub fn intern_code(&mut self, interner: &CodeInterner) {
+        for (code_hash, code) in self.contracts.iter_mut() {
+            *code = interner.intern(*code_hash, code.clone());
+        }
+        for account in self.state.values_mut() {
+            if let Some(info) = &mut account.info {
+                interner.intern_info(info);
+            }
+        }
+    }
+
     /// Consumes [`TransitionState`] by applying the changes and creating the
     /// reverts.
     ///
@@ -659,6 +675,23 @@ impl BundleState {
         }
     }
 
+    /// Generates a [`StateChangeset`] with values after the last block and
+    /// [`BlockChangeset`]s with values before each block.
+    ///
+    /// Reverts are assumed to be one per block, the first one belonging to the `first_block`.
+    pub fn to_block_changesets(
+        &self,
+        first_block: u64,
+        is_value_known: OriginalValuesKnown,
+    ) -> (StateChangeset, Vec<BlockChangeset>) {
+        (
+            self.to_plain_state(is_value_known),
+            self.reverts
+                .to_plain_state_reverts()
+                .to_block_changesets(first_block),
+        )
+    }
+
     /// Converts the bundle state into a [`StateChangeset`].
     #[deprecated = "Use `to_plain_state` instead"]
     pub fn into_plain_state(self, is_value_known: OriginalValuesKnown) -> StateChangeset {
@@ -1154,6 +1187,40 @@ mod tests {
         sanity_path(test_bundle3(), test_bundle4());
     }
 
+    #[test]
+    fn test_block_changesets() {
+        let mut bundle = test_bundle1();
+        bundle.extend(test_bundle2());
+
+        let (plain_state, changesets) = bundle.to_block_changesets(100, OriginalValuesKnown::Yes);
+        assert!(plain_state
+            .accounts
+            .iter()
+            .any(|(address, info)| *address == account1() && info.as_ref().unwrap().nonce == 3));
+        assert_eq!(changesets.len(), 2);
+
+        assert_eq!(changesets[0].block_number, 100);
+        assert_eq!(
+            changesets[0].accounts,
+            vec![(account1(), None), (account2(), None)]
+        );
+        assert_eq!(
+            changesets[0].storage,
+            vec![
+                (account1(), slot1(), StorageValue::ZERO),
+                (account1(), slot2(), StorageValue::ZERO),
+            ]
+        );
+
+        assert_eq!(changesets[1].block_number, 101);
+        assert_eq!(changesets[1].accounts[0].1.as_ref().unwrap().nonce, 1);
+        assert_eq!(
+            changesets[1].storage,
+            vec![(account1(), slot1(), StorageValue::from(10))]
+        );
+        assert!(changesets[1].wiped_storage.is_empty());
+    }
+
     #[test]
     fn test_multi_reverts_with_delete() {
         let mut state = BundleBuilder::new(0..=3)
@@ -10,7 +10,8 @@ use std::vec::Vec;
 /// that mostly have separate tables to store `accounts`/`storages`/`contracts` data.
 ///
 /// **Note**: That data is **not** sorted. Some database benefit of faster inclusion
-/// and smaller footprint if data is inserted in sorted order.
+/// and smaller footprint if data is inserted in sorted order, use [`StateChangeset::sort`]
+/// for that or for deterministic output.
 #[derive(Clone, Debug, Default)]
 pub struct StateChangeset {
     /// Vector of **not** sorted accounts information.
@@ -21,6 +22,18 @@ pub struct StateChangeset {
     pub contracts: Vec<(B256, Bytecode)>,
 }
 
+impl StateChangeset {
+    /// Sorts accounts and storage by address and storage key and contracts by code hash.
+    pub fn sort(&mut self) {
+        self.accounts.sort_unstable_by_key(|(address, _)| *address);
+        self.storage.sort_unstable_by_key(|storage| storage.address);
+        for storage in &mut self.storage {
+            storage.storage.sort_unstable_by_key(|(key, _)| *key);
+        }
+        self.contracts.sort_unstable_by_key(|(hash, _)| *hash);
+    }
+}
+
 /// Plain storage changeset.
 ///
 /// Used to apply storage changes of plain state to the database.
@@ -54,7 +67,7 @@ pub struct PlainStorageRevert {
 
 /// Plain state reverts are used to easily store reverts into database.
 ///
-/// Note that accounts are assumed **not** sorted.
+/// Note that accounts are assumed **not** sorted, use [`PlainStateReverts::sort`] to sort them.
 #[derive(Clone, Debug, Default)]
 pub struct PlainStateReverts {
     /// Vector of account with removed contracts bytecode.
@@ -73,7 +86,163 @@ impl PlainStateReverts {
             storage: Vec::with_capacity(capacity),
         }
     }
+
+    /// Sorts accounts and storage of every revert by address and storage key.
+    ///
+    /// Order of the reverts is kept.
+    pub fn sort(&mut self) {
+        for accounts in &mut self.accounts {
+            accounts.sort_unstable_by_key(|(address, _)| *address);
+        }
+        for storage in self.storage.iter_mut().flatten() {
+            storage.storage_revert.sort_unstable_by_key(|(key, _)| *key);
+        }
+        for storage in &mut self.storage {
+            storage.sort_unstable_by_key(|revert| revert.address);
+        }
+    }
+}
+
+/// Changes of a single block in a storage engine agnostic form.
+///
+/// Contains values from **before** the block for every account and storage slot changed in it,
+/// this is what databases usually store in account and storage changeset tables. Values after
+/// the block are the values before the next block or the plain state for the last block.
+///
+/// Accounts and storage are sorted by address and key.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct BlockChangeset {
+    /// Number of the block.
+    pub block_number: u64,
+    /// Account information before the block, without code.
+    ///
+    /// **Note**: `None` means that account did not exist.
+    pub accounts: Vec<(Address, Option<AccountInfo>)>,
+    /// Storage values before the block.
+    ///
+    /// Slots of wiped storage that did not exist before the block have zero value.
+    pub storage: Vec<(Address, StorageKey, StorageValue)>,
+    /// Accounts whose storage was wiped in the block.
+    ///
+    /// **Note**: Database needs to move all storage of these accounts to the changeset, as
+    /// slots that were not touched in the block are not known here.
+    pub wiped_storage: Vec<Address>,
+}
+
+impl PlainStateReverts {
+    /// Converts reverts into [`BlockChangeset`]s, first revert belongs to the `first_block`.
+    pub fn to_block_changesets(&self, first_block: u64) -> Vec<BlockChangeset> {
+        self.accounts
+            .iter()
+            .zip(&self.storage)
+            .zip(first_block..)
+            .map(|((accounts, storage), block_number)| {
+                let mut accounts: Vec<_> = accounts
+                    .iter()
+                    .map(|(address, info)| {
+                        (*address, info.as_ref().map(AccountInfo::copy_without_code))
+                    })
+                    .collect();
+                accounts.sort_unstable_by_key(|(address, _)| *address);
+
+                let mut wiped_storage = Vec::new();
+                let mut slots = Vec::new();
+                for revert in storage {
+                    if revert.wiped {
+                        wiped_storage.push(revert.address);
+                    }
+                    slots.extend(
+                        revert
+                            .storage_revert
+                            .iter()
+                            .map(|(key, slot)| (revert.address, *key, slot.to_previous_value())),
+                    );
+                }
+                slots.sort_unstable_by_key(|(address, key, _)| (*address, *key));
+                wiped_storage.sort_unstable();
+
+                BlockChangeset {
+                    block_number,
+                    accounts,
+                    storage: slots,
+                    wiped_storage,
+                }
+            })
+            .collect()
+    }
 }
 
 /// Storage reverts
 pub type StorageRevert = Vec<Vec<(Address, bool, Vec<(StorageKey, RevertToSlot)>)>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::U256;
+    use std::vec;
+
+    #[test]
+    fn sort_changeset() {
+        let address = Address::with_last_byte;
+        let slots = |keys: &[u64]| {
+            keys.iter()
+                .map(|key| (U256::from(*key), U256::from(1)))
+                .collect::<Vec<_>>()
+        };
+        let mut changeset = StateChangeset {
+            accounts: vec![(address(2), None), (address(1), None)],
+            storage: vec![
+                PlainStorageChangeset {
+                    address: address(2),
+                    wipe_storage: false,
+                    storage: slots(&[3, 1, 2]),
+                },
+                PlainStorageChangeset {
+                    address: address(1),
+                    wipe_storage: true,
+                    storage: Vec::new(),
+                },
+            ],
+            contracts: vec![(B256::with_last_byte(2), Bytecode::default())],
+        };
+        changeset.sort();
+        assert_eq!(changeset.accounts, [(address(1), None), (address(2), None)]);
+        assert_eq!(changeset.storage[0].address, address(1));
+        assert_eq!(changeset.storage[1].storage, slots(&[1, 2, 3]));
+
+        let mut reverts = PlainStateReverts {
+            accounts: vec![vec![(address(2), None), (address(1), None)], Vec::new()],
+            storage: vec![
+                Vec::new(),
+                vec![
+                    PlainStorageRevert {
+                        address: address(2),
+                        wiped: false,
+                        storage_revert: vec![
+                            (U256::from(2), RevertToSlot::Destroyed),
+                            (U256::from(1), RevertToSlot::Destroyed),
+                        ],
+                    },
+                    PlainStorageRevert {
+                        address: address(1),
+                        ..Default::default()
+                    },
+                ],
+            ],
+        };
+        reverts.sort();
+        assert_eq!(
+            reverts.accounts[0],
+            [(address(1), None), (address(2), None)]
+        );
+        assert!(reverts.accounts[1].is_empty());
+        assert_eq!(reverts.storage[1][0].address, address(1));
+        assert_eq!(
+            reverts.storage[1][1].storage_revert,
+            [
+                (U256::from(1), RevertToSlot::Destroyed),
+                (U256::from(2), RevertToSlot::Destroyed)
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use super::State;
+use bytecode::Bytecode;
+use database_interface::Database;
+use primitives::{Address, B256, KECCAK_EMPTY};
+use std::vec::Vec;
+
+/// Code that runs when an account is called, with the EIP-7702 delegation resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EffectiveCode {
+    /// Account the code is delegated to, `None` if the account is not delegated.
+    pub delegate: Option<Address>,
+    /// Hash of the code, [`KECCAK_EMPTY`] if there is no code.
+    pub code_hash: B256,
+    /// Size of the code in bytes.
+    pub code_size: usize,
+}
+
+impl<DB: Database> State<DB> {
+    /// Returns the accounts in the cache that delegate to the `target`, sorted by address.
+    ///
+    /// Only accounts loaded or changed through this state are known, accounts that are only in
+    /// the database are not returned.
+    pub fn delegations_to(&self, target: Address) -> Vec<Address> {
+        let mut delegations: Vec<Address> = self
+            .cache
+            .accounts
+            .iter()
+            .filter_map(|(address, account)| {
+                let info = &account.account.as_ref()?.info;
+                let code = info
+                    .code
+                    .as_ref()
+                    .or_else(|| self.cache.contracts.get(&info.code_hash))?;
+                (delegate(code)? == target).then_some(*address)
+            })
+            .collect();
+        delegations.sort_unstable();
+        delegations
+    }
+
+    /// Returns the account the code of the account is delegated to.
+    pub fn delegation(&mut self, address: Address) -> Result<Option<Address>, DB::Error> {
+        Ok(self.code(address)?.1.as_ref().and_then(delegate))
+    }
+
+    /// Returns hash and size of the code that runs when the account is called.
+    ///
+    /// Code of a delegated account is the code of its delegate. Delegations are not followed
+    /// further, a delegate that is delegated itself runs its delegation designation like the EVM.
+    pub fn effective_code(&mut self, address: Address) -> Result<EffectiveCode, DB::Error> {
+        let (mut code_hash, mut code) = self.code(address)?;
+        let delegate = code.as_ref().and_then(delegate);
+        if let Some(delegate) = delegate {
+            (code_hash, code) = self.code(delegate)?;
+        }
+        Ok(EffectiveCode {
+            delegate,
+            code_hash,
+            code_size: code.as_ref().map_or(0, Bytecode::len),
+        })
+    }
+
+    /// Returns the code hash and the code of the account, code is `None` if the account has no
+    /// code.
+    fn code(&mut self, address: Address) -> Result<(B256, Option<Bytecode>), DB::Error> {
+        let Some(info) = self.basic(address)? else {
+            return Ok((KECCAK_EMPTY, None));
+        };
+        if info.code_hash == KECCAK_EMPTY {
+            return Ok((KECCAK_EMPTY, None));
+        }
+        let code = match info.code {
+            Some(code) => code,
+            None => self.code_by_hash(info.code_hash)?,
+        };
+        Ok((info.code_hash, Some(code)))
+    }
+}
+
+fn delegate(code: &Bytecode) -> Option<Address> {
+    match code {
+        Bytecode::Eip7702(code) => Some(code.delegated_address),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CacheDB, EmptyDB};
+    use database_interface::DatabaseCommit;
+    use primitives::HashMap;
+    use state::{Account, AccountInfo, AccountStatus};
+
+    #[test]
+    fn delegation_queries() {
+        let target = Address::with_last_byte(0x10);
+        let code = Bytecode::new_raw([0x60, 0x01, 0x00].into());
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(target, AccountInfo::from_bytecode(code.clone()));
+        // Delegation in the database.
+        let first = Address::with_last_byte(1);
+        db.insert_account_info(
+            first,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(target)),
+        );
+        let mut state = State::builder().with_database(db).build();
+
+        // Delegation applied by a transaction.
+        let second = Address::with_last_byte(2);
+        let mut account = Account::from(AccountInfo::from_bytecode(Bytecode::new_eip7702(target)));
+        account.status = AccountStatus::Touched;
+        state.load_cache_account(second).unwrap();
+        state.commit(HashMap::from_iter([(second, account)]));
+
+        // Accounts are only known once they are loaded.
+        assert_eq!(state.delegations_to(target), [second]);
+        assert_eq!(state.delegation(first).unwrap(), Some(target));
+        assert_eq!(state.delegations_to(target), [first, second]);
+        assert_eq!(state.delegation(target).unwrap(), None);
+
+        assert_eq!(
+            state.effective_code(first).unwrap(),
+            EffectiveCode {
+                delegate: Some(target),
+                code_hash: code.hash_slow(),
+                code_size: 3,
+            }
+        );
+        assert_eq!(
+            state.effective_code(target).unwrap(),
+            EffectiveCode {
+                delegate: None,
+                code_hash: code.hash_slow(),
+                code_size: 3,
+            }
+        );
+        assert_eq!(
+            state.effective_code(Address::with_last_byte(3)).unwrap(),
+            EffectiveCode {
+                delegate: None,
+                code_hash: KECCAK_EMPTY,
+                code_size: 0,
+            }
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use super::{
-    bundle_state::BundleRetention, cache::CacheState, plain_account::PlainStorage, BundleState,
-    CacheAccount, StateBuilder, TransitionAccount, TransitionState,
+    bundle_state::BundleRetention, cache::CacheState, plain_account::PlainStorage, AccountStatus,
+    BundleState, CacheAccount, StateBuilder, TransitionAccount, TransitionState,
 };
+#[cfg(feature = "std")]
+use crate::CodeInterner;
 use bytecode::Bytecode;
-use database_interface::{Database, DatabaseCommit, DatabaseRef, EmptyDB};
+use database_interface::{Database, DatabaseCommit, DatabaseRef, EmptyDB, SlowRead, SlowReads};
 use primitives::{hash_map, Address, HashMap, StorageKey, StorageValue, B256, BLOCK_HASH_HISTORY};
 use state::{Account, AccountInfo};
 use std::{
@@ -63,6 +65,11 @@ pub struct State<DB> {
     ///
     /// The fork block is different or some blocks are not saved inside database.
     pub block_hashes: BTreeMap<u64, B256>,
+    /// Interner of the code loaded from the database and committed to the state.
+    ///
+    /// See [`StateBuilder::with_code_interner`].
+    #[cfg(feature = "std")]
+    pub code_interner: Option<CodeInterner>,
 }
 
 // Have ability to call State::builder without having to specify the type.
@@ -73,6 +80,57 @@ impl State<EmptyDB> {
     }
 }
 
+impl<DB> State<DB> {
+    /// Returns an iterator over all cached accounts, in arbitrary order.
+    pub fn accounts(&self) -> impl Iterator<Item = (&Address, &CacheAccount)> {
+        self.cache.accounts.iter()
+    }
+
+    /// Returns an iterator over the cached accounts that were changed since they were loaded.
+    pub fn touched_accounts(&self) -> impl Iterator<Item = (&Address, &CacheAccount)> {
+        self.accounts()
+            .filter(|(_, account)| !account.status.is_not_modified())
+    }
+
+    /// Returns an iterator over the cached accounts that were created and exist in memory only.
+    ///
+    /// Accounts without nonce and code are treated as created once they are changed, as they have
+    /// no storage in the database.
+    pub fn created_accounts(&self) -> impl Iterator<Item = (&Address, &CacheAccount)> {
+        self.accounts().filter(|(_, account)| {
+            account.account.is_some()
+                && matches!(
+                    account.status,
+                    AccountStatus::InMemoryChange | AccountStatus::DestroyedChanged
+                )
+        })
+    }
+
+    /// Returns an iterator over the cached accounts whose address starts with `prefix`.
+    pub fn accounts_with_prefix<'a>(
+        &'a self,
+        prefix: &'a [u8],
+    ) -> impl Iterator<Item = (&'a Address, &'a CacheAccount)> {
+        self.accounts()
+            .filter(move |(address, _)| address.starts_with(prefix))
+    }
+
+    /// Returns an iterator over the cached storage slots of the account, in arbitrary order.
+    ///
+    /// Slots that were never loaded or written are not included.
+    pub fn account_storage(
+        &self,
+        address: Address,
+    ) -> impl Iterator<Item = (&StorageKey, &StorageValue)> {
+        self.cache
+            .accounts
+            .get(&address)
+            .and_then(|account| account.account.as_ref())
+            .into_iter()
+            .flat_map(|account| account.storage.iter())
+    }
+}
+
 impl<DB: Database> State<DB> {
     /// Returns the size hint for the inner bundle state.
     ///
@@ -197,6 +255,13 @@ impl<DB: Database> State<DB> {
                 }
                 // If not found in bundle, load it from database
                 let info = self.database.basic(address)?;
+                #[cfg(feature = "std")]
+                let info = info.map(|mut info| {
+                    if let Some(interner) = &self.code_interner {
+                        interner.intern_info(&mut info);
+                    }
+                    info
+                });
                 let account = match info {
                     None => CacheAccount::new_loaded_not_existing(),
                     Some(acc) if acc.is_empty() => {
@@ -226,6 +291,12 @@ impl<DB: Database> State<DB> {
     }
 }
 
+impl<DB: SlowReads> SlowReads for State<DB> {
+    fn take_slow_reads(&mut self) -> Vec<SlowRead> {
+        self.database.take_slow_reads()
+    }
+}
+
 impl<DB: Database> Database for State<DB> {
     type Error = DB::Error;
 
@@ -245,6 +316,11 @@ impl<DB: Database> Database for State<DB> {
                 }
                 // If not found in bundle ask database
                 let code = self.database.code_by_hash(code_hash)?;
+                #[cfg(feature = "std")]
+                let code = match &self.code_interner {
+                    Some(interner) => interner.intern(code_hash, code),
+                    None => code,
+                };
                 entry.insert(code.clone());
                 Ok(code)
             }
@@ -310,6 +386,16 @@ impl<DB: Database> Database for State<DB> {
 
 impl<DB: Database> DatabaseCommit for State<DB> {
     fn commit(&mut self, evm_state: HashMap<Address, Account>) {
+        #[cfg(feature = "std")]
+        let evm_state = {
+            let mut evm_state = evm_state;
+            if let Some(interner) = &self.code_interner {
+                for account in evm_state.values_mut() {
+                    interner.intern_info(&mut account.info);
+                }
+            }
+            evm_state
+        };
         let transitions = self.cache.apply_evm_state(evm_state);
         self.apply_transition(transitions);
     }
@@ -888,4 +974,45 @@ mod tests {
             )])])
         )
     }
+
+    #[test]
+    fn account_iteration() {
+        let loaded = Address::with_last_byte(1);
+        let changed = Address::with_last_byte(2);
+        let created = Address::repeat_byte(0xaa);
+        let mut state = State::builder().build();
+        state.insert_account_with_storage(
+            loaded,
+            AccountInfo::default(),
+            HashMap::from_iter([(StorageKey::from(1), StorageValue::from(2))]),
+        );
+        state.insert_account(
+            changed,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        state.cache.accounts.insert(
+            created,
+            CacheAccount::new_newly_created(AccountInfo::default(), Default::default()),
+        );
+        state.increment_balances([(changed, 1)]).unwrap();
+
+        assert_eq!(state.accounts().count(), 3);
+        let mut touched: Vec<_> = state.touched_accounts().map(|(a, _)| *a).collect();
+        touched.sort();
+        assert_eq!(touched, [changed, created]);
+        let created_accounts: Vec<_> = state.created_accounts().map(|(a, _)| *a).collect();
+        assert_eq!(created_accounts, [created]);
+        let prefixed: Vec<_> = state
+            .accounts_with_prefix(&[0xaa])
+            .map(|(a, _)| *a)
+            .collect();
+        assert_eq!(prefixed, [created]);
+
+        let storage: Vec<_> = state.account_storage(loaded).collect();
+        assert_eq!(storage, [(&StorageKey::from(1), &StorageValue::from(2))]);
+        assert_eq!(state.account_storage(Address::ZERO).count(), 0);
+    }
 }
@@ -1,4 +1,6 @@
 use super::{cache::CacheState, state::DBBox, BundleState, State, TransitionState};
+#[cfg(feature = "std")]
+use crate::CodeInterner;
 use database_interface::{DBErrorMarker, Database, DatabaseRef, EmptyDB, WrapDatabaseRef};
 use primitives::B256;
 use std::collections::BTreeMap;
@@ -29,6 +31,9 @@ pub struct StateBuilder<DB> {
     with_background_transition_merge: bool,
     /// If we want to set different block hashes,
     with_block_hashes: BTreeMap<u64, B256>,
+    /// Interner of the code of the state.
+    #[cfg(feature = "std")]
+    with_code_interner: Option<CodeInterner>,
 }
 
 impl StateBuilder<EmptyDB> {
@@ -58,6 +63,8 @@ impl<DB: Database> StateBuilder<DB> {
             with_bundle_update: false,
             with_background_transition_merge: false,
             with_block_hashes: BTreeMap::new(),
+            #[cfg(feature = "std")]
+            with_code_interner: None,
         }
     }
 
@@ -73,6 +80,8 @@ impl<DB: Database> StateBuilder<DB> {
             with_bundle_update: self.with_bundle_update,
             with_background_transition_merge: self.with_background_transition_merge,
             with_block_hashes: self.with_block_hashes,
+            #[cfg(feature = "std")]
+            with_code_interner: self.with_code_interner,
         }
     }
 
@@ -158,6 +167,18 @@ impl<DB: Database> StateBuilder<DB> {
         }
     }
 
+    /// Shares the code of the state with other databases using the same interner.
+    ///
+    /// Code of the prestates, code loaded from the database and code of committed accounts is
+    /// replaced with its interned instance.
+    #[cfg(feature = "std")]
+    pub fn with_code_interner(self, interner: CodeInterner) -> Self {
+        Self {
+            with_code_interner: Some(interner),
+            ..self
+        }
+    }
+
     /// Builds the State with the configured settings.
     pub fn build(mut self) -> State<DB> {
         let use_preloaded_bundle = if self.with_cache_prestate.is_some() {
@@ -166,6 +187,17 @@ impl<DB: Database> StateBuilder<DB> {
         } else {
             self.with_bundle_prestate.is_some()
         };
+        #[cfg(feature = "std")]
+        if let Some(interner) = &self.with_code_interner {
+            if let Some(bundle) = &mut self.with_bundle_prestate {
+                bundle.intern_code(interner);
+            }
+            if let Some(cache) = &mut self.with_cache_prestate {
+                for (code_hash, code) in cache.contracts.iter_mut() {
+                    *code = interner.intern(*code_hash, code.clone());
+                }
+            }
+        }
         State {
             cache: self
                 .with_cache_prestate
@@ -175,6 +207,8 @@ impl<DB: Database> StateBuilder<DB> {
             bundle_state: self.with_bundle_prestate.unwrap_or_default(),
             use_preloaded_bundle,
             block_hashes: self.with_block_hashes,
+            #[cfg(feature = "std")]
+            code_interner: self.with_code_interner,
         }
     }
 }
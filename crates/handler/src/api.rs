@@ -1,5 +1,6 @@
 use crate::{
-    frame::EthFrame, instructions::InstructionProvider, Handler, MainnetHandler, PrecompileProvider,
+    instructions::InstructionProvider, EvmFrameTr, FrameResult, Handler, MainnetHandler,
+    PrecompileProvider,
 };
 use context::{
     result::{
@@ -9,7 +10,7 @@ use context::{
     Block, ContextSetters, ContextTr, Database, Evm, JournalTr, Transaction,
 };
 use database_interface::DatabaseCommit;
-use interpreter::{interpreter::EthInterpreter, InterpreterResult};
+use interpreter::interpreter_action::FrameInit;
 use state::EvmState;
 use std::vec::Vec;
 
@@ -162,12 +163,12 @@ pub trait ExecuteCommitEvm: ExecuteEvm {
     }
 }
 
-impl<CTX, INSP, INST, PRECOMPILES> ExecuteEvm
-    for Evm<CTX, INSP, INST, PRECOMPILES, EthFrame<EthInterpreter>>
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> ExecuteEvm for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
 where
     CTX: ContextTr<Journal: JournalTr<State = EvmState>> + ContextSetters,
-    INST: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
-    PRECOMPILES: PrecompileProvider<CTX, Output = InterpreterResult>,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
 {
     type ExecutionResult = ExecutionResult<HaltReason>;
     type State = EvmState;
@@ -200,12 +201,13 @@ where
     }
 }
 
-impl<CTX, INSP, INST, PRECOMPILES> ExecuteCommitEvm
-    for Evm<CTX, INSP, INST, PRECOMPILES, EthFrame<EthInterpreter>>
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> ExecuteCommitEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
 where
     CTX: ContextTr<Journal: JournalTr<State = EvmState>, Db: DatabaseCommit> + ContextSetters,
-    INST: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
-    PRECOMPILES: PrecompileProvider<CTX, Output = InterpreterResult>,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
 {
     #[inline]
     fn commit(&mut self, state: Self::State) {
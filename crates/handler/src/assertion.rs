@@ -0,0 +1,325 @@
+//! Postcondition assertions evaluated against the outcome of a simulated transaction.
+use crate::{EvmTr, ExecuteEvm};
+use context::{
+    result::{ExecResultAndState, ExecutionResult, HaltReasonTr},
+    ContextTr, Database,
+};
+use core::fmt;
+use primitives::{Address, HashMap, Log, StorageKey, StorageValue, B256, I256, U256};
+use state::EvmState;
+use std::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Finalized outcome of a transaction that postconditions are evaluated against.
+///
+/// The state is not committed, balances before the transaction are read from the database.
+#[derive(Debug)]
+pub struct PostState<'a, H: HaltReasonTr> {
+    /// Execution result of the transaction.
+    pub result: &'a ExecutionResult<H>,
+    /// State changed by the transaction.
+    pub state: &'a EvmState,
+    balances_before: HashMap<Address, U256>,
+}
+
+impl<'a, H: HaltReasonTr> PostState<'a, H> {
+    /// Returns the balance after the transaction, `None` if the account was not touched.
+    pub fn balance(&self, address: Address) -> Option<U256> {
+        self.state.get(&address).map(|account| account.info.balance)
+    }
+
+    /// Returns the balance change caused by the transaction.
+    pub fn balance_delta(&self, address: Address) -> I256 {
+        let (Some(after), Some(before)) =
+            (self.balance(address), self.balances_before.get(&address))
+        else {
+            return I256::ZERO;
+        };
+        I256::from_raw(after.wrapping_sub(*before))
+    }
+
+    /// Returns the storage value after the transaction, `None` if the slot was not accessed.
+    pub fn storage(&self, address: Address, slot: StorageKey) -> Option<StorageValue> {
+        self.state
+            .get(&address)?
+            .storage
+            .get(&slot)
+            .map(|slot| slot.present_value)
+    }
+
+    /// Returns the logs emitted by the transaction.
+    pub fn logs(&self) -> &[Log] {
+        self.result.logs()
+    }
+}
+
+/// Postcondition check, returns the failure message if the postcondition does not hold.
+pub type PostconditionFn<H> = Box<dyn Fn(&PostState<'_, H>) -> Result<(), String>>;
+
+/// Failed postcondition.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssertionFailure {
+    /// Name of the postcondition.
+    pub name: String,
+    /// Description of the failure.
+    pub message: String,
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+/// Named postconditions checked by [`ExecuteAssertEvm::execute_with_assertions`].
+pub struct Postconditions<H: HaltReasonTr> {
+    checks: Vec<(String, PostconditionFn<H>)>,
+}
+
+impl<H: HaltReasonTr> fmt::Debug for Postconditions<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.checks.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+impl<H: HaltReasonTr> Default for Postconditions<H> {
+    fn default() -> Self {
+        Self { checks: Vec::new() }
+    }
+}
+
+impl<H: HaltReasonTr> Postconditions<H> {
+    /// Creates an empty set of postconditions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a custom postcondition.
+    pub fn with(
+        mut self,
+        name: impl Into<String>,
+        check: impl Fn(&PostState<'_, H>) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.checks.push((name.into(), Box::new(check)));
+        self
+    }
+
+    /// Transaction has to succeed.
+    pub fn success(self) -> Self {
+        self.with("success", |post| {
+            if post.result.is_success() {
+                Ok(())
+            } else {
+                Err(format!("transaction did not succeed: {:?}", post.result))
+            }
+        })
+    }
+
+    /// Balance of `address` has to change by exactly `delta`.
+    pub fn balance_delta(self, address: Address, delta: I256) -> Self {
+        self.with(format!("balance_delta({address})"), move |post| {
+            let actual = post.balance_delta(address);
+            if actual == delta {
+                Ok(())
+            } else {
+                Err(format!("expected {delta}, got {actual}"))
+            }
+        })
+    }
+
+    /// Storage `slot` of `address` has to hold `value` after the transaction.
+    ///
+    /// Slots that were not accessed by the transaction fail the postcondition.
+    pub fn storage(self, address: Address, slot: StorageKey, value: StorageValue) -> Self {
+        self.with(
+            format!("storage({address}, {slot})"),
+            move |post| match post.storage(address, slot) {
+                Some(actual) if actual == value => Ok(()),
+                Some(actual) => Err(format!("expected {value}, got {actual}")),
+                None => Err("slot was not accessed".to_string()),
+            },
+        )
+    }
+
+    /// Transaction has to emit a log from `address` with `topic` as the first topic.
+    pub fn log(self, address: Address, topic: B256) -> Self {
+        self.with(format!("log({address}, {topic})"), move |post| {
+            if post
+                .logs()
+                .iter()
+                .any(|log| log.address == address && log.topics().first() == Some(&topic))
+            {
+                Ok(())
+            } else {
+                Err("log was not emitted".to_string())
+            }
+        })
+    }
+
+    /// Evaluates all postconditions and returns the failed ones.
+    pub fn evaluate(&self, post: &PostState<'_, H>) -> Vec<AssertionFailure> {
+        self.checks
+            .iter()
+            .filter_map(|(name, check)| {
+                check(post).err().map(|message| AssertionFailure {
+                    name: name.clone(),
+                    message,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Outcome of [`ExecuteAssertEvm::execute_with_assertions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssertedExecution<H: HaltReasonTr> {
+    /// Execution result of the transaction.
+    pub result: ExecutionResult<H>,
+    /// State changed by the transaction, not committed to the database.
+    pub state: EvmState,
+    /// Postconditions that did not hold.
+    pub failures: Vec<AssertionFailure>,
+}
+
+impl<H: HaltReasonTr> AssertedExecution<H> {
+    /// Returns `true` if all postconditions hold.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Execution of a transaction with postconditions checked on its finalized state.
+///
+/// Useful as a pre-flight check of a transaction before it is signed or sent.
+pub trait ExecuteAssertEvm: ExecuteEvm {
+    /// Halt reason of the execution result.
+    type HaltReason: HaltReasonTr;
+
+    /// Executes and finalizes the transaction without committing it, then evaluates
+    /// `postconditions` against the result and the changed state.
+    ///
+    /// Errors of the transaction are returned as errors, postconditions that do not hold are
+    /// returned in [`AssertedExecution::failures`].
+    fn execute_with_assertions(
+        &mut self,
+        tx: Self::Tx,
+        postconditions: &Postconditions<Self::HaltReason>,
+    ) -> Result<AssertedExecution<Self::HaltReason>, Self::Error>;
+}
+
+impl<EVM, H> ExecuteAssertEvm for EVM
+where
+    EVM: ExecuteEvm<ExecutionResult = ExecutionResult<H>, State = EvmState> + EvmTr,
+    EVM::Error: From<<<EVM::Context as ContextTr>::Db as Database>::Error>,
+    H: HaltReasonTr,
+{
+    type HaltReason = H;
+
+    fn execute_with_assertions(
+        &mut self,
+        tx: Self::Tx,
+        postconditions: &Postconditions<H>,
+    ) -> Result<AssertedExecution<H>, Self::Error> {
+        let ExecResultAndState { result, state, .. } = self.transact(tx)?;
+
+        // State is not committed so the database still holds the balances before the transaction.
+        let db = self.ctx().db_mut();
+        let mut balances_before = HashMap::default();
+        for address in state.keys() {
+            let balance = db.basic(*address)?.map(|info| info.balance);
+            balances_before.insert(*address, balance.unwrap_or_default());
+        }
+
+        let failures = postconditions.evaluate(&PostState {
+            result: &result,
+            state: &state,
+            balances_before,
+        });
+        Ok(AssertedExecution {
+            result,
+            state,
+            failures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_TARGET};
+
+    #[test]
+    fn test_execute_with_assertions() {
+        // sstore(0, 1), log1(0, 0, 0xaa)
+        let bytecode = Bytecode::new_legacy(
+            [
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::PUSH1,
+                0xaa,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::LOG1,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        let tx = TxEnv::builder_for_bench()
+            .value(U256::from(10))
+            .build_fill();
+
+        let postconditions = Postconditions::new()
+            .success()
+            .balance_delta(BENCH_TARGET, I256::try_from(10).unwrap())
+            .storage(BENCH_TARGET, U256::ZERO, U256::from(1))
+            .log(BENCH_TARGET, B256::with_last_byte(0xaa));
+        let outcome = evm
+            .execute_with_assertions(tx.clone(), &postconditions)
+            .unwrap();
+        assert!(outcome.is_ok(), "{:?}", outcome.failures);
+
+        let postconditions = Postconditions::new()
+            .balance_delta(BENCH_TARGET, I256::ZERO)
+            .storage(BENCH_TARGET, U256::from(1), U256::ZERO)
+            .log(BENCH_TARGET, B256::ZERO)
+            .with("custom", |_| Err("failed".into()));
+        let outcome = evm.execute_with_assertions(tx, &postconditions).unwrap();
+        assert!(outcome.result.is_success());
+        assert_eq!(
+            outcome.failures,
+            vec![
+                AssertionFailure {
+                    name: format!("balance_delta({BENCH_TARGET})"),
+                    message: "expected 0, got 10".into(),
+                },
+                AssertionFailure {
+                    name: format!("storage({BENCH_TARGET}, 1)"),
+                    message: "slot was not accessed".into(),
+                },
+                AssertionFailure {
+                    name: format!("log({BENCH_TARGET}, {})", B256::ZERO),
+                    message: "log was not emitted".into(),
+                },
+                AssertionFailure {
+                    name: "custom".into(),
+                    message: "failed".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Balance change summary of executed transactions.
+use crate::{EvmTr, ExecuteEvm};
+use context::{
+    result::{BalanceChanges, ExecResultAndState, ExecutionResult, HaltReasonTr},
+    Block, ContextTr, Database, Transaction,
+};
+use primitives::TxKind;
+use state::EvmState;
+
+/// Execution of a transaction that summarizes balance changes of the touched accounts.
+pub trait ExecuteBalanceChangesEvm: ExecuteEvm {
+    /// Executes and finalizes the transaction like [`ExecuteEvm::transact`] and sets
+    /// [`ExecResultAndState::balance_changes`].
+    ///
+    /// The state is not committed, balances before the transaction are read from the database.
+    fn transact_with_balance_changes(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error>;
+}
+
+impl<EVM, H> ExecuteBalanceChangesEvm for EVM
+where
+    EVM: ExecuteEvm<ExecutionResult = ExecutionResult<H>, State = EvmState> + EvmTr,
+    EVM::Error: From<<<EVM::Context as ContextTr>::Db as Database>::Error>,
+    H: HaltReasonTr,
+{
+    fn transact_with_balance_changes(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<ExecutionResult<H>, EvmState>, Self::Error> {
+        let sender = tx.caller();
+        let kind = tx.kind();
+        let output = self.transact(tx)?;
+
+        let coinbase = self.ctx().block().beneficiary();
+        let recipient = match kind {
+            TxKind::Call(address) => Some(address),
+            TxKind::Create => output.result.created_address(),
+        };
+        let db = self.ctx().db_mut();
+        let balance_changes =
+            BalanceChanges::new(&output.state, sender, recipient, coinbase, |address| {
+                Ok::<_, Self::Error>(
+                    db.basic(address)?
+                        .map(|info| info.balance)
+                        .unwrap_or_default(),
+                )
+            })?;
+        Ok(output.with_balance_changes(balance_changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{result::BalanceChange, Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET};
+    use primitives::{Address, I256, U256};
+
+    #[test]
+    fn test_transact_with_balance_changes() {
+        // call(0, 0x1000, 3, 0, 0, 0, 0)
+        let bytecode = Bytecode::new_legacy(
+            [
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0x03,
+                opcode::PUSH2,
+                0x10,
+                0x00,
+                opcode::PUSH0,
+                opcode::CALL,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let coinbase = Address::with_last_byte(0xcb);
+        let mut evm = Context::mainnet()
+            .modify_block_chained(|block| {
+                block.beneficiary = coinbase;
+                block.basefee = 0;
+            })
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        let tx = TxEnv::builder_for_bench()
+            .value(U256::from(10))
+            .gas_price(1)
+            .build_fill();
+
+        let output = evm.transact_with_balance_changes(tx).unwrap();
+        assert!(output.result.is_success());
+        let gas_used = U256::from(output.result.gas_used());
+        let changes = output.balance_changes.unwrap();
+        assert_eq!(
+            changes.sender,
+            Some(BalanceChange {
+                address: BENCH_CALLER,
+                before: BENCH_CALLER_BALANCE,
+                after: BENCH_CALLER_BALANCE - U256::from(10) - gas_used,
+            })
+        );
+        assert_eq!(
+            changes.recipient.unwrap().delta(),
+            I256::try_from(7).unwrap()
+        );
+        assert_eq!(
+            changes.coinbase.unwrap().delta(),
+            I256::try_from(gas_used).unwrap()
+        );
+        assert_eq!(
+            changes.others,
+            [BalanceChange {
+                address: Address::left_padding_from(&[0x10, 0x00]),
+                before: U256::ZERO,
+                after: U256::from(3),
+            }]
+        );
+        assert_eq!(changes.get(BENCH_TARGET), changes.recipient.as_ref());
+    }
+}
@@ -0,0 +1,316 @@
+//! Execution of a sequence of transactions within block gas limits.
+use crate::ExecuteEvm;
+use context::{
+    result::{ExecutionResult, HaltReasonTr},
+    Transaction,
+};
+use core::fmt;
+use std::vec::Vec;
+
+/// Transaction does not fit into the remaining block gas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockGasError {
+    /// Transaction gas limit is higher than the remaining block gas.
+    GasLimit {
+        /// Gas limit of the transaction.
+        tx_gas_limit: u64,
+        /// Gas remaining in the block.
+        remaining: u64,
+    },
+    /// Transaction blob gas is higher than the remaining block blob gas.
+    BlobGasLimit {
+        /// Blob gas of the transaction.
+        tx_blob_gas: u64,
+        /// Blob gas remaining in the block.
+        remaining: u64,
+    },
+}
+
+impl fmt::Display for BlockGasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GasLimit {
+                tx_gas_limit,
+                remaining,
+            } => write!(
+                f,
+                "transaction gas limit {tx_gas_limit} is higher than remaining block gas {remaining}"
+            ),
+            Self::BlobGasLimit {
+                tx_blob_gas,
+                remaining,
+            } => write!(
+                f,
+                "transaction blob gas {tx_blob_gas} is higher than remaining block blob gas {remaining}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BlockGasError {}
+
+/// Error of the [`BlockExecutor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockExecutionError<E> {
+    /// Transaction does not fit into the block.
+    Gas {
+        /// Index of the transaction in the executed sequence.
+        index: usize,
+        /// Reason why the transaction does not fit.
+        error: BlockGasError,
+    },
+    /// Transaction execution failed.
+    Evm {
+        /// Index of the transaction in the executed sequence.
+        index: usize,
+        /// Execution error.
+        error: E,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for BlockExecutionError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gas { index, error } => write!(f, "transaction {index}: {error}"),
+            Self::Evm { index, error } => write!(f, "transaction {index}: {error}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for BlockExecutionError<E> {}
+
+/// Handling of transactions that do not fit into the remaining block gas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockGasPolicy {
+    /// Transaction is skipped and execution continues with the next one.
+    #[default]
+    Skip,
+    /// Execution stops with [`BlockExecutionError::Gas`].
+    Reject,
+}
+
+/// Result of a transaction executed by the [`BlockExecutor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockTxResult<R> {
+    /// Index of the transaction in the executed sequence.
+    pub index: usize,
+    /// Type of the transaction.
+    pub tx_type: u8,
+    /// Execution result.
+    pub result: R,
+    /// Gas used by all executed transactions including this one, as reported in receipts.
+    pub cumulative_gas_used: u64,
+}
+
+/// Outcome of [`BlockExecutor::execute_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockExecutionOutcome<R> {
+    /// Results of executed transactions.
+    pub results: Vec<BlockTxResult<R>>,
+    /// Indices of skipped transactions and the reason they were skipped.
+    pub skipped: Vec<(usize, BlockGasError)>,
+    /// Gas used by all executed transactions.
+    pub gas_used: u64,
+    /// Blob gas used by all executed transactions.
+    pub blob_gas_used: u64,
+}
+
+/// Executes transactions while keeping the cumulative gas and blob gas within block limits.
+///
+/// Transactions are checked against the remaining gas with their gas limit before execution,
+/// used gas is accounted after execution. State changes accumulate in the journal, call
+/// [`ExecuteEvm::finalize`] on the EVM after the block is done.
+///
+/// When replaying historical blocks, senders of the signed transactions can be recovered in
+/// parallel with `SenderCache::recover_block` of the context crate, enabled by its
+/// `parallel_recovery` feature.
+#[derive(Debug)]
+pub struct BlockExecutor<'a, EVM> {
+    evm: &'a mut EVM,
+    gas_limit: u64,
+    blob_gas_limit: Option<u64>,
+    gas_used: u64,
+    blob_gas_used: u64,
+    executed: usize,
+}
+
+impl<'a, EVM, H> BlockExecutor<'a, EVM>
+where
+    EVM: ExecuteEvm<ExecutionResult = ExecutionResult<H>>,
+    H: HaltReasonTr,
+{
+    /// Creates a new executor with the given block gas limit and no blob gas limit.
+    pub fn new(evm: &'a mut EVM, gas_limit: u64) -> Self {
+        Self {
+            evm,
+            gas_limit,
+            blob_gas_limit: None,
+            gas_used: 0,
+            blob_gas_used: 0,
+            executed: 0,
+        }
+    }
+
+    /// Sets the block blob gas limit.
+    pub fn with_blob_gas_limit(mut self, blob_gas_limit: u64) -> Self {
+        self.blob_gas_limit = Some(blob_gas_limit);
+        self
+    }
+
+    /// Returns the wrapped EVM.
+    pub fn evm(&mut self) -> &mut EVM {
+        self.evm
+    }
+
+    /// Returns the gas used by executed transactions.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    /// Returns the blob gas used by executed transactions.
+    pub fn blob_gas_used(&self) -> u64 {
+        self.blob_gas_used
+    }
+
+    /// Returns the gas remaining in the block.
+    pub fn remaining_gas(&self) -> u64 {
+        self.gas_limit.saturating_sub(self.gas_used)
+    }
+
+    /// Returns the blob gas remaining in the block, `None` if blob gas is not limited.
+    pub fn remaining_blob_gas(&self) -> Option<u64> {
+        self.blob_gas_limit
+            .map(|limit| limit.saturating_sub(self.blob_gas_used))
+    }
+
+    /// Checks whether the transaction fits into the remaining block gas.
+    pub fn check_fits(&self, tx: &EVM::Tx) -> Result<(), BlockGasError> {
+        let remaining = self.remaining_gas();
+        if tx.gas_limit() > remaining {
+            return Err(BlockGasError::GasLimit {
+                tx_gas_limit: tx.gas_limit(),
+                remaining,
+            });
+        }
+        if let Some(remaining) = self.remaining_blob_gas() {
+            let tx_blob_gas = tx.total_blob_gas();
+            if tx_blob_gas > remaining {
+                return Err(BlockGasError::BlobGasLimit {
+                    tx_blob_gas,
+                    remaining,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes the transaction if it fits into the remaining block gas.
+    ///
+    /// Gas counters are not changed if the transaction does not fit or is invalid.
+    pub fn execute(
+        &mut self,
+        tx: EVM::Tx,
+    ) -> Result<BlockTxResult<ExecutionResult<H>>, BlockExecutionError<EVM::Error>> {
+        let index = self.executed;
+        self.executed += 1;
+        self.check_fits(&tx)
+            .map_err(|error| BlockExecutionError::Gas { index, error })?;
+        let blob_gas = tx.total_blob_gas();
+        let tx_type = tx.tx_type();
+        let result = self
+            .evm
+            .transact_one(tx)
+            .map_err(|error| BlockExecutionError::Evm { index, error })?;
+        self.gas_used += result.gas_used();
+        self.blob_gas_used += blob_gas;
+        Ok(BlockTxResult {
+            index,
+            tx_type,
+            result,
+            cumulative_gas_used: self.gas_used,
+        })
+    }
+
+    /// Executes all transactions, handling transactions that do not fit according to `policy`.
+    ///
+    /// Execution stops on the first transaction execution error.
+    pub fn execute_all(
+        &mut self,
+        txs: impl IntoIterator<Item = EVM::Tx>,
+        policy: BlockGasPolicy,
+    ) -> Result<BlockExecutionOutcome<ExecutionResult<H>>, BlockExecutionError<EVM::Error>> {
+        let mut results = Vec::new();
+        let mut skipped = Vec::new();
+        for tx in txs {
+            match self.execute(tx) {
+                Ok(result) => results.push(result),
+                Err(BlockExecutionError::Gas { index, error })
+                    if policy == BlockGasPolicy::Skip =>
+                {
+                    skipped.push((index, error))
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(BlockExecutionOutcome {
+            results,
+            skipped,
+            gas_used: self.gas_used,
+            blob_gas_used: self.blob_gas_used,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use database::BenchmarkDB;
+
+    #[test]
+    fn test_block_gas_accounting() {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::STOP].into(),
+            )))
+            .build_mainnet();
+        let tx = |nonce, gas_limit| {
+            TxEnv::builder_for_bench()
+                .nonce(nonce)
+                .gas_limit(gas_limit)
+                .build_fill()
+        };
+
+        let mut executor = BlockExecutor::new(&mut evm, 50_000);
+        let outcome = executor
+            .execute_all(
+                [tx(0, 30_000), tx(1, 30_000), tx(1, 21_000)],
+                BlockGasPolicy::Skip,
+            )
+            .unwrap();
+        assert_eq!(outcome.results.len(), 2);
+        assert_eq!(outcome.results[1].index, 2);
+        assert_eq!(outcome.results[1].cumulative_gas_used, 42_000);
+        assert_eq!(outcome.gas_used, 42_000);
+        assert_eq!(
+            outcome.skipped,
+            vec![(
+                1,
+                BlockGasError::GasLimit {
+                    tx_gas_limit: 30_000,
+                    remaining: 29_000
+                }
+            )]
+        );
+
+        let error = executor
+            .execute_all([tx(2, 21_000)], BlockGasPolicy::Reject)
+            .unwrap_err();
+        assert!(matches!(error, BlockExecutionError::Gas { index: 3, .. }));
+        assert_eq!(executor.remaining_gas(), 8_000);
+    }
+}
@@ -0,0 +1,287 @@
+//! Verification of block header fields against execution results.
+use crate::block_executor::{BlockExecutionOutcome, BlockTxResult};
+use context::result::{ExecutionResult, HaltReasonTr};
+use core::fmt;
+use primitives::{
+    alloy_primitives::{logs_bloom, Bloom},
+    Bytes, Log, B256,
+};
+use std::{boxed::Box, vec::Vec};
+
+/// Receipt of an executed transaction with the fields that are committed to in the header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockReceipt {
+    /// Type of the transaction.
+    pub tx_type: u8,
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// Gas used by the block up to and including this transaction.
+    pub cumulative_gas_used: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+impl BlockReceipt {
+    /// Returns the logs bloom of the receipt.
+    pub fn bloom(&self) -> Bloom {
+        logs_bloom(&self.logs)
+    }
+}
+
+impl<H: HaltReasonTr> From<&BlockTxResult<ExecutionResult<H>>> for BlockReceipt {
+    fn from(tx: &BlockTxResult<ExecutionResult<H>>) -> Self {
+        Self {
+            tx_type: tx.tx_type,
+            success: tx.result.is_success(),
+            cumulative_gas_used: tx.cumulative_gas_used,
+            logs: tx.result.logs().to_vec(),
+        }
+    }
+}
+
+impl<H: HaltReasonTr> BlockExecutionOutcome<ExecutionResult<H>> {
+    /// Returns receipts of the executed transactions.
+    pub fn receipts(&self) -> Vec<BlockReceipt> {
+        self.results.iter().map(BlockReceipt::from).collect()
+    }
+}
+
+/// Header fields that are checked by [`verify_block`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedHeader {
+    /// Gas used by the block.
+    pub gas_used: u64,
+    /// Root of the receipts trie.
+    pub receipts_root: B256,
+    /// Bloom filter of all logs of the block.
+    pub logs_bloom: Bloom,
+    /// EIP-7685 requests hash, checked only if present.
+    pub requests_hash: Option<B256>,
+    /// State root, checked only if present and the state root is provided in [`ExecutedBlock`].
+    pub state_root: Option<B256>,
+}
+
+/// Results of the block execution.
+#[derive(Clone, Copy, Debug)]
+pub struct ExecutedBlock<'a> {
+    /// Receipts of all transactions of the block.
+    pub receipts: &'a [BlockReceipt],
+    /// EIP-7685 requests, each prefixed with its request type.
+    pub requests: &'a [Bytes],
+    /// State root computed by a trie backed state, `None` if not available.
+    pub state_root: Option<B256>,
+}
+
+/// Header field that does not match the execution result.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HeaderMismatch {
+    /// Gas used mismatch.
+    GasUsed {
+        /// Value from the header.
+        expected: u64,
+        /// Value computed from execution.
+        got: u64,
+    },
+    /// Receipts root mismatch.
+    ReceiptsRoot {
+        /// Value from the header.
+        expected: B256,
+        /// Value computed from execution.
+        got: B256,
+    },
+    /// Logs bloom mismatch.
+    LogsBloom {
+        /// Value from the header.
+        expected: Box<Bloom>,
+        /// Value computed from execution.
+        got: Box<Bloom>,
+    },
+    /// Requests hash mismatch.
+    RequestsHash {
+        /// Value from the header.
+        expected: B256,
+        /// Value computed from execution.
+        got: B256,
+    },
+    /// State root mismatch.
+    StateRoot {
+        /// Value from the header.
+        expected: B256,
+        /// Value computed from execution.
+        got: B256,
+    },
+}
+
+impl fmt::Display for HeaderMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GasUsed { expected, got } => {
+                write!(f, "gas used mismatch: expected {expected}, got {got}")
+            }
+            Self::ReceiptsRoot { expected, got } => {
+                write!(f, "receipts root mismatch: expected {expected}, got {got}")
+            }
+            Self::LogsBloom { expected, got } => {
+                write!(f, "logs bloom mismatch: expected {expected}, got {got}")
+            }
+            Self::RequestsHash { expected, got } => {
+                write!(f, "requests hash mismatch: expected {expected}, got {got}")
+            }
+            Self::StateRoot { expected, got } => {
+                write!(f, "state root mismatch: expected {expected}, got {got}")
+            }
+        }
+    }
+}
+
+/// All mismatched header fields found by [`verify_block`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlockMismatchReport {
+    /// Mismatched fields in the order they were checked.
+    pub mismatches: Vec<HeaderMismatch>,
+}
+
+impl fmt::Display for BlockMismatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, mismatch) in self.mismatches.iter().enumerate() {
+            if i != 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for BlockMismatchReport {}
+
+/// Computes the EIP-7685 requests hash, `sha256(sha256(request_0) ++ sha256(request_1) ++ ...)`.
+///
+/// Requests without data, consisting only of the request type, are skipped.
+pub fn requests_hash(requests: &[Bytes]) -> B256 {
+    let crypto = precompile::crypto();
+    let mut hashes = Vec::with_capacity(requests.len() * 32);
+    for request in requests.iter().filter(|request| request.len() > 1) {
+        hashes.extend_from_slice(&crypto.sha256(request));
+    }
+    crypto.sha256(&hashes).into()
+}
+
+/// Verifies header fields against the results of the block execution.
+///
+/// Receipts root requires RLP encoding and trie hashing which are not part of revm, so it is
+/// computed by `receipts_root`. All fields are checked and every mismatch is reported.
+pub fn verify_block(
+    header: &ExpectedHeader,
+    block: &ExecutedBlock<'_>,
+    receipts_root: impl FnOnce(&[BlockReceipt]) -> B256,
+) -> Result<(), BlockMismatchReport> {
+    let mut mismatches = Vec::new();
+
+    let gas_used = block
+        .receipts
+        .last()
+        .map(|receipt| receipt.cumulative_gas_used)
+        .unwrap_or_default();
+    if gas_used != header.gas_used {
+        mismatches.push(HeaderMismatch::GasUsed {
+            expected: header.gas_used,
+            got: gas_used,
+        });
+    }
+
+    let root = receipts_root(block.receipts);
+    if root != header.receipts_root {
+        mismatches.push(HeaderMismatch::ReceiptsRoot {
+            expected: header.receipts_root,
+            got: root,
+        });
+    }
+
+    let bloom = logs_bloom(block.receipts.iter().flat_map(|receipt| &receipt.logs));
+    if bloom != header.logs_bloom {
+        mismatches.push(HeaderMismatch::LogsBloom {
+            expected: Box::new(header.logs_bloom),
+            got: Box::new(bloom),
+        });
+    }
+
+    if let Some(expected) = header.requests_hash {
+        let got = requests_hash(block.requests);
+        if got != expected {
+            mismatches.push(HeaderMismatch::RequestsHash { expected, got });
+        }
+    }
+
+    if let (Some(expected), Some(got)) = (header.state_root, block.state_root) {
+        if got != expected {
+            mismatches.push(HeaderMismatch::StateRoot { expected, got });
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(BlockMismatchReport { mismatches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{b256, Address, LogData};
+
+    #[test]
+    fn test_empty_requests_hash() {
+        // Requests hash of a block without requests, from EIP-7685.
+        assert_eq!(
+            requests_hash(&[]),
+            b256!("0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+    }
+
+    #[test]
+    fn test_verify_block() {
+        let receipts = [BlockReceipt {
+            tx_type: 2,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log {
+                address: Address::with_last_byte(1),
+                data: LogData::new_unchecked(vec![B256::with_last_byte(2)], Bytes::new()),
+            }],
+        }];
+        let block = ExecutedBlock {
+            receipts: &receipts,
+            requests: &[],
+            state_root: Some(B256::with_last_byte(3)),
+        };
+        let mut header = ExpectedHeader {
+            gas_used: 21_000,
+            receipts_root: B256::with_last_byte(4),
+            logs_bloom: receipts[0].bloom(),
+            requests_hash: Some(requests_hash(&[])),
+            state_root: Some(B256::with_last_byte(3)),
+        };
+        assert_eq!(
+            verify_block(&header, &block, |_| B256::with_last_byte(4)),
+            Ok(())
+        );
+
+        header.gas_used = 1;
+        header.logs_bloom = Bloom::ZERO;
+        let report = verify_block(&header, &block, |_| B256::with_last_byte(4)).unwrap_err();
+        assert_eq!(report.mismatches.len(), 2);
+        assert_eq!(
+            report.mismatches[0],
+            HeaderMismatch::GasUsed {
+                expected: 1,
+                got: 21_000
+            }
+        );
+        assert!(matches!(
+            report.mismatches[1],
+            HeaderMismatch::LogsBloom { .. }
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+//! Contract calls with ABI encoded inputs and outputs.
+use crate::SystemCallEvm;
+use alloy_sol_types::{decode_revert_reason, SolCall};
+use context::result::{ExecutionResult, HaltReasonTr, Output};
+use core::fmt;
+use primitives::{Address, Bytes};
+use std::string::String;
+
+/// Error of a contract call made with [`ContractCallEvm`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContractCallError<E, H> {
+    /// Execution failed with an EVM error, like a database error.
+    Evm(E),
+    /// Contract reverted.
+    Revert {
+        /// Revert data returned by the contract.
+        output: Bytes,
+        /// Reason decoded from `Error(string)`, `Panic(uint256)` or a string returned by the
+        /// contract.
+        reason: Option<String>,
+    },
+    /// Execution halted.
+    Halt(H),
+    /// Returned data can't be decoded as the return type of the function.
+    Decode(alloy_sol_types::Error),
+}
+
+impl<E, H> ContractCallError<E, H> {
+    /// Returns the revert data, if the contract reverted.
+    ///
+    /// Custom errors of the contract can be decoded from it with
+    /// [`SolInterface::abi_decode`](alloy_sol_types::SolInterface::abi_decode).
+    pub fn revert_data(&self) -> Option<&Bytes> {
+        match self {
+            Self::Revert { output, .. } => Some(output),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Display, H: fmt::Debug> fmt::Display for ContractCallError<E, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Evm(e) => e.fmt(f),
+            Self::Revert {
+                reason: Some(reason),
+                ..
+            } => write!(f, "execution reverted: {reason}"),
+            Self::Revert { output, .. } => write!(f, "execution reverted: {output}"),
+            Self::Halt(reason) => write!(f, "execution halted: {reason:?}"),
+            Self::Decode(e) => write!(f, "failed to decode return data: {e}"),
+        }
+    }
+}
+
+impl<E, H> core::error::Error for ContractCallError<E, H>
+where
+    E: fmt::Display + fmt::Debug,
+    H: fmt::Debug,
+{
+}
+
+/// Calls of contract functions that encode the calldata and decode the returned value or revert.
+///
+/// Calls are executed as system calls, see [`SystemCallEvm`]: the caller is not charged, its
+/// nonce is not checked and the state changes of the call are dropped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sol! {
+///     function balanceOf(address owner) external view returns (uint256);
+/// }
+///
+/// let balance = evm.call_contract(token, &balanceOfCall { owner })?;
+/// ```
+pub trait ContractCallEvm: SystemCallEvm {
+    /// Halt reason of the execution result.
+    type HaltReason;
+
+    /// Calls the contract with raw calldata and returns the returned data.
+    ///
+    /// Useful with dynamically encoded calls, like the ones made with `alloy-dyn-abi`.
+    fn call_contract_raw(
+        &mut self,
+        caller: Address,
+        address: Address,
+        data: Bytes,
+    ) -> Result<Bytes, ContractCallError<Self::Error, Self::HaltReason>>;
+
+    /// Calls the function of the contract from [`Address::ZERO`] and decodes the returned value.
+    fn call_contract<C: SolCall>(
+        &mut self,
+        address: Address,
+        call: &C,
+    ) -> Result<C::Return, ContractCallError<Self::Error, Self::HaltReason>> {
+        self.call_contract_with_caller(Address::ZERO, address, call)
+    }
+
+    /// Calls the function of the contract from the caller and decodes the returned value.
+    fn call_contract_with_caller<C: SolCall>(
+        &mut self,
+        caller: Address,
+        address: Address,
+        call: &C,
+    ) -> Result<C::Return, ContractCallError<Self::Error, Self::HaltReason>> {
+        let output = self.call_contract_raw(caller, address, call.abi_encode().into())?;
+        C::abi_decode_returns(&output).map_err(ContractCallError::Decode)
+    }
+}
+
+impl<EVM, H> ContractCallEvm for EVM
+where
+    EVM: SystemCallEvm<ExecutionResult = ExecutionResult<H>>,
+    H: HaltReasonTr,
+{
+    type HaltReason = H;
+
+    fn call_contract_raw(
+        &mut self,
+        caller: Address,
+        address: Address,
+        data: Bytes,
+    ) -> Result<Bytes, ContractCallError<Self::Error, H>> {
+        let output = self
+            .system_call_with_caller(caller, address, data)
+            .map_err(ContractCallError::Evm)?;
+        match output.result {
+            ExecutionResult::Success {
+                output: Output::Call(output),
+                ..
+            } => Ok(output),
+            ExecutionResult::Success {
+                output: Output::Create(output, _),
+                ..
+            } => Ok(output),
+            ExecutionResult::Revert { output, .. } => Err(ContractCallError::Revert {
+                reason: decode_revert_reason(&output),
+                output,
+            }),
+            ExecutionResult::Halt { reason, .. } => Err(ContractCallError::Halt(reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use alloy_sol_types::{sol, SolError};
+    use bytecode::{opcode, Bytecode};
+    use context::{result::HaltReason, Context};
+    use database::{BenchmarkDB, BENCH_TARGET};
+    use primitives::U256;
+
+    sol! {
+        function answer() external view returns (uint256);
+    }
+
+    /// Returns the bytecode that returns the first `len` bytes of memory after storing `word`.
+    fn returning(word: U256, len: u8) -> Bytecode {
+        let mut code = vec![opcode::PUSH32];
+        code.extend_from_slice(&word.to_be_bytes::<32>());
+        code.extend_from_slice(&[
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            len,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]);
+        Bytecode::new_legacy(code.into())
+    }
+
+    #[test]
+    fn test_call_contract() {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(returning(U256::from(42), 32)))
+            .build_mainnet();
+        assert_eq!(
+            evm.call_contract(BENCH_TARGET, &answerCall {}).unwrap(),
+            U256::from(42)
+        );
+
+        // Returned data is shorter than a word.
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(returning(U256::from(42), 16)))
+            .build_mainnet();
+        assert!(matches!(
+            evm.call_contract(BENCH_TARGET, &answerCall {}),
+            Err(ContractCallError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_contract_revert() {
+        let revert = alloy_sol_types::Revert::from("not allowed").abi_encode();
+        let mut code = Vec::new();
+        for (i, chunk) in revert.chunks(32).enumerate() {
+            code.push(opcode::PUSH32);
+            let mut word = [0; 32];
+            word[..chunk.len()].copy_from_slice(chunk);
+            code.extend_from_slice(&word);
+            code.extend_from_slice(&[opcode::PUSH1, (i * 32) as u8, opcode::MSTORE]);
+        }
+        code.extend_from_slice(&[
+            opcode::PUSH1,
+            revert.len() as u8,
+            opcode::PUSH0,
+            opcode::REVERT,
+        ]);
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .build_mainnet();
+        let error = evm.call_contract(BENCH_TARGET, &answerCall {}).unwrap_err();
+        assert_eq!(error.revert_data().map(|data| data.to_vec()), Some(revert));
+        assert_eq!(error.to_string(), "execution reverted: revert: not allowed");
+
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::INVALID].into(),
+            )))
+            .build_mainnet();
+        assert_eq!(
+            evm.call_contract(BENCH_TARGET, &answerCall {}),
+            Err(ContractCallError::Halt(HaltReason::InvalidFEOpcode))
+        );
+    }
+}
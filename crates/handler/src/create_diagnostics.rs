@@ -0,0 +1,154 @@
+//! Diagnostics of contract creations that failed in executed transactions.
+use crate::{EvmTr, ExecuteEvm};
+use context::{result::ExecResultAndState, ContextTr, LocalContextTr};
+
+/// Execution of a transaction that reports why its contract creations failed.
+///
+/// Requires a local context that records failures, like `LocalContext` of the context crate.
+pub trait ExecuteCreateDiagnosticsEvm: ExecuteEvm {
+    /// Executes and finalizes the transaction like [`ExecuteEvm::transact`] and sets
+    /// [`ExecResultAndState::create_failures`].
+    ///
+    /// Failures of `CREATE` and `CREATE2` instructions are reported even if the transaction
+    /// succeeds. Failures are recorded only while this method runs.
+    fn transact_with_create_diagnostics(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error>;
+}
+
+impl<EVM> ExecuteCreateDiagnosticsEvm for EVM
+where
+    EVM: ExecuteEvm + EvmTr<Context: ContextTr>,
+{
+    fn transact_with_create_diagnostics(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
+        self.ctx().local_mut().set_record_create_failures(true);
+        let output = self.transact(tx);
+        let create_failures = self.ctx().local_mut().take_create_failures();
+        self.ctx().local_mut().set_record_create_failures(false);
+        Ok(output?.with_create_failures(create_failures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{
+        result::{CreateFailure, CreateFailureReason},
+        Context, TxEnv,
+    };
+    use database::{BenchmarkDB, BENCH_CALLER};
+    use primitives::{Bytes, TxKind, U256};
+
+    fn create(initcode: Vec<u8>) -> Vec<CreateFailure> {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        evm.transact_with_create_diagnostics(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Create)
+                .data(initcode.into())
+                .gas_limit(1_000_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+        .create_failures
+        .unwrap()
+    }
+
+    #[test]
+    fn test_create_diagnostics() {
+        // return(0, 0x6001)
+        let failures = create(vec![
+            opcode::PUSH2,
+            0x60,
+            0x01,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].caller, BENCH_CALLER);
+        assert_eq!(failures[0].depth, 0);
+        assert!(failures[0].address.is_some());
+        assert_eq!(
+            failures[0].reason,
+            CreateFailureReason::CodeSizeLimit {
+                size: 0x6001,
+                limit: 0x6000,
+            }
+        );
+
+        // mstore8(0, 0xef), return(0, 1)
+        let failures = create(vec![
+            opcode::PUSH1,
+            0xef,
+            opcode::PUSH0,
+            opcode::MSTORE8,
+            opcode::PUSH1,
+            1,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]);
+        assert_eq!(failures[0].reason, CreateFailureReason::StartsWithEF);
+
+        // revert(0, 0)
+        let failures = create(vec![opcode::PUSH0, opcode::PUSH0, opcode::REVERT]);
+        assert_eq!(
+            failures[0].reason,
+            CreateFailureReason::Reverted {
+                output: Bytes::new()
+            }
+        );
+
+        // create(1, 0, 0) with a caller without balance, then stop.
+        let failures = create(vec![
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            1,
+            opcode::CREATE,
+            opcode::STOP,
+        ]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].depth, 1);
+        assert_eq!(failures[0].address, None);
+        assert_eq!(
+            failures[0].reason,
+            CreateFailureReason::InsufficientBalance {
+                balance: U256::ZERO,
+                value: U256::from(1),
+            }
+        );
+
+        // Successful creation reports nothing.
+        assert!(create(vec![opcode::STOP]).is_empty());
+    }
+
+    #[test]
+    fn test_failures_not_recorded_by_default() {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        let output = evm
+            .transact(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Create)
+                    .data(vec![opcode::PUSH0, opcode::PUSH0, opcode::REVERT].into())
+                    .gas_limit(1_000_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(!output.result.is_success());
+        assert!(output.create_failures.is_none());
+        assert!(evm.ctx.local.create_failures.is_none());
+    }
+}
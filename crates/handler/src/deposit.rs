@@ -0,0 +1,559 @@
+//! Deposit transactions that are forced into the chain and mint balance to the caller.
+//!
+//! Deposits are paid for outside of the EVM, for example on L1 or by the sequencer, so their fee
+//! is not validated, charged or paid to the beneficiary. Nonce of the caller is not checked and is
+//! always bumped. The minted balance persists even when the deposit fails, in which case the
+//! result is a halt with [`DepositHaltReason::FailedDeposit`].
+//!
+//! [`DepositHandler`] runs deposits next to regular transactions, which are executed by the
+//! [`MainnetHandler`].
+use crate::{evm::FrameTr, handler::EvmTrError, EvmTr, FrameResult, Handler, MainnetHandler};
+use context::{
+    result::{EVMError, ExecutionResult, HaltReason},
+    ContextTr, JournalTr, LocalContextTr, Transaction, TxEnv,
+};
+use interpreter::{interpreter_action::FrameInit, Gas, InitialAndFloorGas};
+use primitives::{Address, Bytes, TxKind, B256, U256};
+use state::EvmState;
+
+/// Transaction that can be a deposit.
+pub trait DepositTxTr: Transaction {
+    /// Returns `true` if the transaction is a deposit.
+    fn is_deposit(&self) -> bool;
+
+    /// Returns the balance minted to the caller before execution, `None` if nothing is minted.
+    fn mint(&self) -> Option<u128>;
+
+    /// Returns `true` if the deposit is a system transaction that does not use gas of the block.
+    fn is_system_transaction(&self) -> bool {
+        false
+    }
+}
+
+/// Deposit fields of [`DepositTx`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositInfo {
+    /// Balance minted to the caller before execution.
+    pub mint: u128,
+    /// Successful system transactions report zero gas used.
+    pub is_system_transaction: bool,
+}
+
+/// Transaction that is either a regular transaction or a deposit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositTx<T = TxEnv> {
+    /// Fields of the transaction, fee fields are ignored for deposits.
+    pub base: T,
+    /// Deposit fields, `None` for regular transactions.
+    pub deposit: Option<DepositInfo>,
+}
+
+impl<T> DepositTx<T> {
+    /// Creates a regular transaction.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            deposit: None,
+        }
+    }
+
+    /// Creates a deposit.
+    pub fn deposit(base: T, deposit: DepositInfo) -> Self {
+        Self {
+            base,
+            deposit: Some(deposit),
+        }
+    }
+}
+
+impl<T: Transaction> Transaction for DepositTx<T> {
+    type AccessListItem<'a>
+        = T::AccessListItem<'a>
+    where
+        T: 'a;
+    type Authorization<'a>
+        = T::Authorization<'a>
+    where
+        T: 'a;
+
+    fn tx_type(&self) -> u8 {
+        self.base.tx_type()
+    }
+
+    fn caller(&self) -> Address {
+        self.base.caller()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.base.gas_limit()
+    }
+
+    fn value(&self) -> U256 {
+        self.base.value()
+    }
+
+    fn input(&self) -> &Bytes {
+        self.base.input()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.base.nonce()
+    }
+
+    fn kind(&self) -> TxKind {
+        self.base.kind()
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.base.chain_id()
+    }
+
+    fn access_list(&self) -> Option<impl Iterator<Item = Self::AccessListItem<'_>>> {
+        self.base.access_list()
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.base.max_priority_fee_per_gas()
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.base.max_fee_per_gas()
+    }
+
+    fn gas_price(&self) -> u128 {
+        self.base.gas_price()
+    }
+
+    fn blob_versioned_hashes(&self) -> &[B256] {
+        self.base.blob_versioned_hashes()
+    }
+
+    fn max_fee_per_blob_gas(&self) -> u128 {
+        self.base.max_fee_per_blob_gas()
+    }
+
+    fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        // Deposits do not pay the base fee.
+        if self.deposit.is_some() {
+            return self.gas_price();
+        }
+        self.base.effective_gas_price(base_fee)
+    }
+
+    fn authorization_list_len(&self) -> usize {
+        self.base.authorization_list_len()
+    }
+
+    fn authorization_list(&self) -> impl Iterator<Item = Self::Authorization<'_>> {
+        self.base.authorization_list()
+    }
+}
+
+impl<T: Transaction> DepositTxTr for DepositTx<T> {
+    fn is_deposit(&self) -> bool {
+        self.deposit.is_some()
+    }
+
+    fn mint(&self) -> Option<u128> {
+        self.deposit.map(|deposit| deposit.mint)
+    }
+
+    fn is_system_transaction(&self) -> bool {
+        self.deposit
+            .is_some_and(|deposit| deposit.is_system_transaction)
+    }
+}
+
+/// Halt reason of chains with deposits.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepositHaltReason {
+    /// Base halt reason.
+    Base(HaltReason),
+    /// Deposit failed, only the mint and the nonce bump are applied.
+    FailedDeposit,
+}
+
+impl DepositHaltReason {
+    /// Returns stable numeric code of the halt reason.
+    ///
+    /// Base halt reasons keep their [`HaltReason::code`].
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Base(reason) => reason.code(),
+            Self::FailedDeposit => 3501,
+        }
+    }
+}
+
+impl From<HaltReason> for DepositHaltReason {
+    fn from(value: HaltReason) -> Self {
+        Self::Base(value)
+    }
+}
+
+/// Trait to check if the error is a transaction error.
+///
+/// Used in [`Handler::catch_error`] to catch deposits that failed validation.
+pub trait IsTxError {
+    /// Check if the error is a transaction error.
+    fn is_tx_error(&self) -> bool;
+}
+
+impl<DB, TX> IsTxError for EVMError<DB, TX> {
+    fn is_tx_error(&self) -> bool {
+        matches!(self, EVMError::Transaction(_))
+    }
+}
+
+/// Chain specific rules of deposits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositPolicy {
+    /// Deposits report the gas they used and receive gas refunds.
+    ///
+    /// If disabled, the gas limit of the deposit is reported as used.
+    pub refund_gas: bool,
+}
+
+impl Default for DepositPolicy {
+    fn default() -> Self {
+        Self { refund_gas: true }
+    }
+}
+
+/// Handler that executes deposits, regular transactions are executed by the [`MainnetHandler`].
+#[derive(Debug, Clone)]
+pub struct DepositHandler<EVM, ERROR, FRAME> {
+    /// Handler used for regular transactions.
+    pub mainnet: MainnetHandler<EVM, ERROR, FRAME>,
+    /// Rules of deposits.
+    pub policy: DepositPolicy,
+}
+
+impl<EVM, ERROR, FRAME> DepositHandler<EVM, ERROR, FRAME> {
+    /// Creates a new handler with the deposit rules of the chain.
+    pub fn new(policy: DepositPolicy) -> Self {
+        Self {
+            mainnet: MainnetHandler::default(),
+            policy,
+        }
+    }
+}
+
+impl<EVM, ERROR, FRAME> Default for DepositHandler<EVM, ERROR, FRAME> {
+    fn default() -> Self {
+        Self::new(DepositPolicy::default())
+    }
+}
+
+impl<EVM, ERROR, FRAME> DepositHandler<EVM, ERROR, FRAME>
+where
+    EVM: EvmTr<Context: ContextTr<Tx: DepositTxTr>>,
+    ERROR: EvmTrError<EVM>,
+{
+    /// Applies the mint and the nonce bump of the failed deposit after its changes are discarded.
+    fn failed_deposit(&self, evm: &mut EVM) -> Result<ExecutionResult<DepositHaltReason>, ERROR> {
+        let ctx = evm.ctx();
+        let tx = ctx.tx();
+        let caller = tx.caller();
+        let mint = U256::from(tx.mint().unwrap_or_default());
+        let gas_used = if tx.is_system_transaction() {
+            0
+        } else {
+            tx.gas_limit()
+        };
+
+        let journal = ctx.journal_mut();
+        journal.discard_tx();
+        let caller_account = journal.load_account(caller)?.data;
+        let old_balance = caller_account.info.balance;
+        caller_account.info.balance = old_balance.saturating_add(mint);
+        caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
+        caller_account.mark_touch();
+        journal.caller_accounting_journal_entry(caller, old_balance, true);
+        journal.commit_tx();
+
+        Ok(ExecutionResult::Halt {
+            reason: DepositHaltReason::FailedDeposit,
+            gas_used,
+        })
+    }
+}
+
+impl<EVM, ERROR, FRAME> Handler for DepositHandler<EVM, ERROR, FRAME>
+where
+    EVM: EvmTr<
+        Context: ContextTr<Tx: DepositTxTr, Journal: JournalTr<State = EvmState>>,
+        Frame = FRAME,
+    >,
+    ERROR: EvmTrError<EVM> + IsTxError,
+    FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+{
+    type Evm = EVM;
+    type Error = ERROR;
+    type HaltReason = DepositHaltReason;
+
+    fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        // Deposits are validated by the chain before they are included.
+        if evm.ctx().tx().is_deposit() {
+            return Ok(());
+        }
+        self.mainnet.validate_env(evm)
+    }
+
+    fn validate_against_state_and_deduct_caller(
+        &self,
+        evm: &mut Self::Evm,
+    ) -> Result<(), Self::Error> {
+        if !evm.ctx().tx().is_deposit() {
+            return self.mainnet.validate_against_state_and_deduct_caller(evm);
+        }
+
+        let (tx, journal) = evm.ctx().tx_journal_mut();
+        let caller_account = journal.load_account_code(tx.caller())?.data;
+
+        // Mint is applied before execution and fee is not deducted.
+        let old_balance = caller_account.info.balance;
+        caller_account.info.balance =
+            old_balance.saturating_add(U256::from(tx.mint().unwrap_or_default()));
+        caller_account.mark_touch();
+
+        // Nonce for CREATE is bumped when the frame is created.
+        let bump_nonce = tx.kind().is_call();
+        if bump_nonce {
+            caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
+        }
+        journal.caller_accounting_journal_entry(tx.caller(), old_balance, bump_nonce);
+        Ok(())
+    }
+
+    fn last_frame_result(
+        &mut self,
+        evm: &mut Self::Evm,
+        frame_result: &mut FrameResult,
+    ) -> Result<(), Self::Error> {
+        let ctx = evm.ctx();
+        if !ctx.tx().is_deposit() {
+            return self.mainnet.last_frame_result(evm, frame_result);
+        }
+        let gas_limit = ctx.tx().gas_limit();
+        let is_system_transaction = ctx.tx().is_system_transaction();
+
+        let is_ok = frame_result.interpreter_result().result.is_ok();
+        if self.policy.refund_gas {
+            self.mainnet.last_frame_result(evm, frame_result)?;
+        } else {
+            *frame_result.gas_mut() = Gas::new_spent(gas_limit);
+        }
+        if is_ok && is_system_transaction {
+            *frame_result.gas_mut() = Gas::new(gas_limit);
+        }
+        Ok(())
+    }
+
+    fn refund(&self, evm: &mut Self::Evm, frame_result: &mut FrameResult, eip7702_refund: i64) {
+        if evm.ctx().tx().is_deposit() && !self.policy.refund_gas {
+            return;
+        }
+        self.mainnet.refund(evm, frame_result, eip7702_refund)
+    }
+
+    fn eip7623_check_gas_floor(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &mut FrameResult,
+        init_and_floor_gas: InitialAndFloorGas,
+    ) {
+        // System transactions do not use gas of the block.
+        if evm.ctx().tx().is_system_transaction() {
+            return;
+        }
+        self.mainnet
+            .eip7623_check_gas_floor(evm, exec_result, init_and_floor_gas)
+    }
+
+    fn reimburse_caller(
+        &self,
+        evm: &mut Self::Evm,
+        frame_result: &mut FrameResult,
+    ) -> Result<(), Self::Error> {
+        // Deposits did not pay for gas.
+        if evm.ctx().tx().is_deposit() {
+            return Ok(());
+        }
+        self.mainnet.reimburse_caller(evm, frame_result)
+    }
+
+    fn reward_beneficiary(
+        &self,
+        evm: &mut Self::Evm,
+        frame_result: &mut FrameResult,
+    ) -> Result<(), Self::Error> {
+        if evm.ctx().tx().is_deposit() {
+            return Ok(());
+        }
+        self.mainnet.reward_beneficiary(evm, frame_result)
+    }
+
+    fn execution_result(
+        &mut self,
+        evm: &mut Self::Evm,
+        frame_result: FrameResult,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        let is_deposit = evm.ctx().tx().is_deposit();
+        let gas_limit = evm.ctx().tx().gas_limit();
+        let result = self
+            .mainnet
+            .execution_result(evm, frame_result)?
+            .map_haltreason(DepositHaltReason::Base);
+
+        // Changes of the halted frames are reverted, the committed state only contains the mint
+        // and the nonce bump.
+        if is_deposit && result.is_halt() {
+            return Ok(ExecutionResult::Halt {
+                reason: DepositHaltReason::FailedDeposit,
+                gas_used: gas_limit,
+            });
+        }
+        Ok(result)
+    }
+
+    fn catch_error(
+        &self,
+        evm: &mut Self::Evm,
+        error: Self::Error,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        if !error.is_tx_error() || !evm.ctx().tx().is_deposit() {
+            return self
+                .mainnet
+                .catch_error(evm, error)
+                .map(|result| result.map_haltreason(DepositHaltReason::Base));
+        }
+        let output = self.failed_deposit(evm);
+        evm.ctx().local_mut().clear();
+        evm.frame_stack().clear();
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{result::InvalidTransaction, Context};
+    use database::{
+        BenchmarkDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET, BENCH_TARGET_BALANCE,
+    };
+    use database_interface::EmptyDB;
+    use interpreter::interpreter::EthInterpreter;
+
+    type TestHandler<EVM> =
+        DepositHandler<EVM, EVMError<core::convert::Infallible>, crate::EthFrame<EthInterpreter>>;
+
+    fn deposit(mint: u128, gas_limit: u64) -> DepositTx {
+        DepositTx::deposit(
+            TxEnv::builder_for_bench()
+                .value(U256::from(1))
+                .gas_price(10)
+                .gas_limit(gas_limit)
+                .nonce(5)
+                .build_fill(),
+            DepositInfo {
+                mint,
+                is_system_transaction: false,
+            },
+        )
+    }
+
+    #[test]
+    fn test_deposit_tx() {
+        let coinbase = Address::with_last_byte(0xcb);
+        let mut evm = Context::mainnet()
+            .with_tx(DepositTx::default())
+            .modify_block_chained(|block| block.beneficiary = coinbase)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::STOP].into(),
+            )))
+            .build_mainnet();
+
+        // Nonce and fee are not checked.
+        evm.ctx.tx = deposit(100, 100_000);
+        let result = TestHandler::default().run(&mut evm).unwrap();
+        assert_eq!(result.gas_used(), 21_000);
+        let caller = &evm.ctx.journal_ref().state[&BENCH_CALLER];
+        assert_eq!(caller.info.balance, BENCH_CALLER_BALANCE + U256::from(99));
+        assert_eq!(caller.info.nonce, 1);
+        assert!(!evm.ctx.journal_ref().state.contains_key(&coinbase));
+        assert_eq!(
+            evm.ctx.journal_ref().state[&BENCH_TARGET].info.balance,
+            BENCH_TARGET_BALANCE + U256::from(1)
+        );
+
+        // Regular transactions are validated.
+        evm.ctx.tx = DepositTx::new(deposit(0, 100_000).base);
+        assert!(matches!(
+            TestHandler::default().run(&mut evm),
+            Err(EVMError::Transaction(
+                InvalidTransaction::NonceTooHigh { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_failed_deposit() {
+        let mut evm = Context::mainnet()
+            .with_tx(DepositTx::default())
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::INVALID].into(),
+            )))
+            .build_mainnet();
+
+        // Halted deposit keeps the mint.
+        evm.ctx.tx = deposit(100, 100_000);
+        let result = TestHandler::default().run(&mut evm).unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: DepositHaltReason::FailedDeposit,
+                gas_used: 100_000,
+            }
+        );
+        let caller = &evm.ctx.journal_ref().state[&BENCH_CALLER];
+        assert_eq!(caller.info.balance, BENCH_CALLER_BALANCE + U256::from(100));
+        assert_eq!(caller.info.nonce, 1);
+
+        // Deposit that failed validation keeps the mint.
+        evm.ctx.tx = deposit(100, 1_000);
+        let result = TestHandler::default().run(&mut evm).unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: DepositHaltReason::FailedDeposit,
+                gas_used: 1_000,
+            }
+        );
+        let caller = &evm.ctx.journal_ref().state[&BENCH_CALLER];
+        assert_eq!(caller.info.balance, BENCH_CALLER_BALANCE + U256::from(200));
+        assert_eq!(caller.info.nonce, 2);
+    }
+
+    #[test]
+    fn test_deposit_gas_policy() {
+        let mut evm = Context::mainnet()
+            .with_tx(DepositTx::default())
+            .with_db(EmptyDB::new())
+            .build_mainnet();
+
+        // Mint covers the transferred value.
+        evm.ctx.tx = deposit(1, 100_000);
+        let mut handler = TestHandler::new(DepositPolicy { refund_gas: false });
+        assert_eq!(handler.run(&mut evm).unwrap().gas_used(), 100_000);
+
+        evm.ctx.tx.deposit.as_mut().unwrap().is_system_transaction = true;
+        assert_eq!(handler.run(&mut evm).unwrap().gas_used(), 0);
+    }
+}
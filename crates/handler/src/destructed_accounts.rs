@@ -0,0 +1,96 @@
+//! Accounts destructed by executed transactions.
+use crate::{EvmTr, ExecuteEvm};
+use context::{result::ExecResultAndState, ContextTr, JournalTr};
+
+/// Execution of a transaction that reports the accounts destructed by `SELFDESTRUCT`.
+pub trait ExecuteDestructedAccountsEvm: ExecuteEvm {
+    /// Executes and finalizes the transaction like [`ExecuteEvm::transact`] and sets
+    /// [`ExecResultAndState::destructed_accounts`].
+    ///
+    /// Destructions of calls that reverted are not reported.
+    fn transact_with_destructed_accounts(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error>;
+}
+
+impl<EVM> ExecuteDestructedAccountsEvm for EVM
+where
+    EVM: ExecuteEvm + EvmTr,
+{
+    fn transact_with_destructed_accounts(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
+        let output_or_error = self.transact_one(tx);
+        let destructed_accounts = self.ctx().journal_mut().take_destructed_accounts();
+        // finalize will clear the journal
+        let state = self.finalize();
+        let output = output_or_error?;
+        Ok(ExecResultAndState::new(output, state).with_destructed_accounts(destructed_accounts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{result::DestructedAccount, Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET, BENCH_TARGET_BALANCE};
+    use primitives::{hardfork::SpecId, Address, TxKind, U256};
+
+    const BENEFICIARY: Address = Address::with_last_byte(0xbe);
+
+    /// Code that self-destructs with [`BENEFICIARY`] as the target.
+    fn selfdestruct() -> [u8; 3] {
+        [opcode::PUSH1, 0xbe, opcode::SELFDESTRUCT]
+    }
+
+    fn transact(spec: SpecId, kind: TxKind, data: &[u8]) -> Vec<DestructedAccount> {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                selfdestruct().to_vec().into(),
+            )))
+            .build_mainnet();
+        let output = evm
+            .transact_with_destructed_accounts(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(kind)
+                    .data(data.to_vec().into())
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(output.result.is_success());
+        output.destructed_accounts.unwrap()
+    }
+
+    #[test]
+    fn test_destructed_accounts() {
+        assert_eq!(
+            transact(SpecId::SHANGHAI, TxKind::Call(BENCH_TARGET), &[]),
+            [DestructedAccount {
+                address: BENCH_TARGET,
+                target: BENEFICIARY,
+                balance: BENCH_TARGET_BALANCE,
+            }]
+        );
+
+        // EIP-6780: existing contract only moves its balance.
+        assert_eq!(
+            transact(SpecId::CANCUN, TxKind::Call(BENCH_TARGET), &[]),
+            []
+        );
+
+        // Contract created in the same transaction is destructed.
+        let destructed = transact(SpecId::CANCUN, TxKind::Create, &selfdestruct());
+        assert_eq!(destructed.len(), 1);
+        assert_eq!(destructed[0].target, BENEFICIARY);
+        assert_eq!(destructed[0].balance, U256::ZERO);
+        assert!(!destructed[0].is_swept());
+    }
+}
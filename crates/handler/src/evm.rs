@@ -3,9 +3,12 @@ use crate::{
     ItemOrResult, PrecompileProvider,
 };
 use auto_impl::auto_impl;
-use context::{ContextTr, Database, Evm, FrameStack};
+use context::{Cfg, ContextTr, Database, Evm, FrameStack};
 use context_interface::context::ContextError;
-use interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, InterpreterResult};
+use context_interface::local::{FrameToken, OutFrame};
+use interpreter::{
+    interpreter::EthInterpreter, interpreter_action::FrameInit, FrameInput, InterpreterResult,
+};
 
 /// Type alias for database error within a context
 pub type ContextDbError<CTX> = ContextError<ContextTrDbError<CTX>>;
@@ -74,6 +77,18 @@ pub trait EvmTr {
         &mut self,
     ) -> Result<FrameInitOrResult<Self::Frame>, ContextDbError<Self::Context>>;
 
+    /// Run the frame from the top of the stack executing at most `fuel` instructions.
+    ///
+    /// Returns `None` if fuel ran out, calling it again continues the execution of the frame.
+    /// Default implementation ignores fuel and runs the frame with [`EvmTr::frame_run`].
+    fn frame_run_with_fuel(
+        &mut self,
+        fuel: &mut u64,
+    ) -> Result<Option<FrameInitOrResult<Self::Frame>>, ContextDbError<Self::Context>> {
+        let _ = fuel;
+        self.frame_run().map(Some)
+    }
+
     /// Returns the result of the frame to the caller. Frame is popped from the frame stack.
     /// Consumes the frame result or returns it if there is more frames to run.
     fn frame_return_result(
@@ -82,16 +97,160 @@ pub trait EvmTr {
     ) -> Result<Option<<Self::Frame as FrameTr>::FrameResult>, ContextDbError<Self::Context>>;
 }
 
-impl<CTX, INSP, I, P> EvmTr for Evm<CTX, INSP, I, P, EthFrame<EthInterpreter>>
+/// Frame that can be driven by the [`Evm`] struct.
+///
+/// Implementing this trait is enough to run a custom frame strategy (compiled, instrumented,
+/// async frames) with [`Evm`] and the [`Handler`][crate::Handler] without reimplementing
+/// [`EvmTr`]. Requirements on the implementation:
+///
+/// * [`init`][EvmFrameTr::init] either initializes the frame in the given [`OutFrame`] and returns
+///   its [`FrameToken`], or returns the result directly if no execution is needed.
+/// * [`run`][EvmFrameTr::run] executes the frame until it needs a new child frame or it is done.
+///   When a result is returned the frame must report [`is_finished`][EvmFrameTr::is_finished].
+/// * [`return_result`][EvmFrameTr::return_result] applies the result of the child frame so the
+///   frame can continue execution. Errors stored in the context must be propagated.
+///
+/// Frames are pooled by the [`FrameStack`], so initialization may reuse a previously used frame.
+pub trait EvmFrameTr<CTX: ContextTr, I, P>: FrameTr + Sized {
+    /// Initializes the frame for the given frame input.
+    fn init(
+        this: OutFrame<'_, Self>,
+        ctx: &mut CTX,
+        precompiles: &mut P,
+        frame_init: Self::FrameInit,
+    ) -> Result<ItemOrResult<FrameToken, Self::FrameResult>, ContextDbError<CTX>>;
+
+    /// Runs the frame. Returns the init of the next frame or the result of this frame.
+    fn run(
+        &mut self,
+        ctx: &mut CTX,
+        instructions: &mut I,
+    ) -> Result<FrameInitOrResult<Self>, ContextDbError<CTX>>;
+
+    /// Runs the frame executing at most `fuel` instructions, fuel is decreased by the number of
+    /// executed instructions.
+    ///
+    /// Returns `None` if fuel ran out before the frame needed a new child frame or was done,
+    /// calling it again continues the execution. Default implementation ignores fuel and runs
+    /// the frame with [`run`][EvmFrameTr::run].
+    fn run_with_fuel(
+        &mut self,
+        ctx: &mut CTX,
+        instructions: &mut I,
+        fuel: &mut u64,
+    ) -> Result<Option<FrameInitOrResult<Self>>, ContextDbError<CTX>> {
+        let _ = fuel;
+        self.run(ctx, instructions).map(Some)
+    }
+
+    /// Returns `true` if the frame has finished execution.
+    fn is_finished(&self) -> bool;
+
+    /// Inserts the result of the child frame.
+    fn return_result(
+        &mut self,
+        ctx: &mut CTX,
+        result: Self::FrameResult,
+    ) -> Result<(), ContextDbError<CTX>>;
+
+    /// Takes allocations of the finished child frame that this frame can reuse for its next
+    /// child frames. Called before the result of the child is returned.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn reclaim(&mut self, child: &mut Self) {
+        let _ = child;
+    }
+}
+
+impl<CTX, I, P> EvmFrameTr<CTX, I, P> for EthFrame<EthInterpreter>
 where
     CTX: ContextTr,
     I: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
     P: PrecompileProvider<CTX, Output = InterpreterResult>,
+{
+    #[inline]
+    fn init(
+        this: OutFrame<'_, Self>,
+        ctx: &mut CTX,
+        precompiles: &mut P,
+        frame_init: Self::FrameInit,
+    ) -> Result<ItemOrResult<FrameToken, Self::FrameResult>, ContextDbError<CTX>> {
+        Self::init_with_context(this, ctx, precompiles, frame_init)
+    }
+
+    #[inline]
+    fn run(
+        &mut self,
+        ctx: &mut CTX,
+        instructions: &mut I,
+    ) -> Result<FrameInitOrResult<Self>, ContextDbError<CTX>> {
+        let disabled = ctx.cfg().disabled_opcodes();
+        let action = instructions
+            .with_instruction_table(disabled, |table| self.interpreter.run_plain(table, ctx));
+
+        self.process_next_action(ctx, action).inspect(|i| {
+            if i.is_result() {
+                self.set_finished(true);
+            }
+        })
+    }
+
+    #[inline]
+    fn run_with_fuel(
+        &mut self,
+        ctx: &mut CTX,
+        instructions: &mut I,
+        fuel: &mut u64,
+    ) -> Result<Option<FrameInitOrResult<Self>>, ContextDbError<CTX>> {
+        let disabled = ctx.cfg().disabled_opcodes();
+        let Some(action) = instructions.with_instruction_table(disabled, |table| {
+            self.interpreter.run_with_fuel(table, ctx, fuel)
+        }) else {
+            return Ok(None);
+        };
+
+        self.process_next_action(ctx, action)
+            .inspect(|i| {
+                if i.is_result() {
+                    self.set_finished(true);
+                }
+            })
+            .map(Some)
+    }
+
+    #[inline]
+    fn is_finished(&self) -> bool {
+        EthFrame::is_finished(self)
+    }
+
+    #[inline]
+    fn return_result(
+        &mut self,
+        ctx: &mut CTX,
+        result: Self::FrameResult,
+    ) -> Result<(), ContextDbError<CTX>> {
+        EthFrame::return_result::<_, ContextDbError<CTX>>(self, ctx, result)
+    }
+
+    #[inline]
+    fn reclaim(&mut self, child: &mut Self) {
+        let input = core::mem::replace(&mut child.input, FrameInput::Empty);
+        self.interpreter.frame_input_pool.recycle(input);
+    }
+}
+
+impl<CTX, INSP, I, P, F> EvmTr for Evm<CTX, INSP, I, P, F>
+where
+    CTX: ContextTr,
+    I: InstructionProvider<Context = CTX>,
+    P: PrecompileProvider<CTX>,
+    F: EvmFrameTr<CTX, I, P>,
 {
     type Context = CTX;
     type Instructions = I;
     type Precompiles = P;
-    type Frame = EthFrame<EthInterpreter>;
+    type Frame = F;
 
     #[inline]
     fn ctx(&mut self) -> &mut Self::Context {
@@ -123,7 +282,7 @@ where
 
         let ctx = &mut self.ctx;
         let precompiles = &mut self.precompiles;
-        let res = Self::Frame::init_with_context(new_frame, ctx, precompiles, frame_input)?;
+        let res = F::init(new_frame, ctx, precompiles, frame_input)?;
 
         Ok(res.map_frame(|token| {
             if is_first_init {
@@ -138,19 +297,19 @@ where
     /// Run the frame from the top of the stack. Returns the frame init or result.
     #[inline]
     fn frame_run(&mut self) -> Result<FrameInitOrResult<Self::Frame>, ContextDbError<CTX>> {
-        let frame = self.frame_stack.get();
-        let context = &mut self.ctx;
-        let instructions = &mut self.instruction;
-
-        let action = frame
-            .interpreter
-            .run_plain(instructions.instruction_table(), context);
+        self.frame_stack
+            .get()
+            .run(&mut self.ctx, &mut self.instruction)
+    }
 
-        frame.process_next_action(context, action).inspect(|i| {
-            if i.is_result() {
-                frame.set_finished(true);
-            }
-        })
+    #[inline]
+    fn frame_run_with_fuel(
+        &mut self,
+        fuel: &mut u64,
+    ) -> Result<Option<FrameInitOrResult<Self::Frame>>, ContextDbError<CTX>> {
+        self.frame_stack
+            .get()
+            .run_with_fuel(&mut self.ctx, &mut self.instruction, fuel)
     }
 
     /// Returns the result of the frame to the caller. Frame is popped from the frame stack.
@@ -159,15 +318,18 @@ where
         &mut self,
         result: <Self::Frame as FrameTr>::FrameResult,
     ) -> Result<Option<<Self::Frame as FrameTr>::FrameResult>, ContextDbError<Self::Context>> {
-        if self.frame_stack.get().is_finished() {
+        let is_popped = self.frame_stack.get().is_finished();
+        if is_popped {
             self.frame_stack.pop();
         }
         if self.frame_stack.index().is_none() {
             return Ok(Some(result));
         }
-        self.frame_stack
-            .get()
-            .return_result::<_, ContextDbError<Self::Context>>(&mut self.ctx, result)?;
+        let (frame, child) = self.frame_stack.get_with_next();
+        if let Some(child) = child.filter(|_| is_popped) {
+            frame.reclaim(child);
+        }
+        frame.return_result(&mut self.ctx, result)?;
         Ok(None)
     }
 
@@ -181,3 +343,134 @@ where
         (&mut self.ctx, &mut self.precompiles)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExecuteEvm, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use database::BenchmarkDB;
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Frame that counts how many times frames were run.
+    #[derive(Default)]
+    struct CountingFrame {
+        inner: EthFrame<EthInterpreter>,
+    }
+
+    impl FrameTr for CountingFrame {
+        type FrameResult = FrameResult;
+        type FrameInit = FrameInit;
+    }
+
+    impl<CTX, I, P> EvmFrameTr<CTX, I, P> for CountingFrame
+    where
+        CTX: ContextTr,
+        I: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
+        P: PrecompileProvider<CTX, Output = InterpreterResult>,
+    {
+        fn init(
+            mut this: OutFrame<'_, Self>,
+            ctx: &mut CTX,
+            precompiles: &mut P,
+            frame_init: Self::FrameInit,
+        ) -> Result<ItemOrResult<FrameToken, Self::FrameResult>, ContextDbError<CTX>> {
+            let frame = this.get(Self::default);
+            let res = EthFrame::init_with_context(
+                OutFrame::new_init(&mut frame.inner),
+                ctx,
+                precompiles,
+                frame_init,
+            )?;
+            Ok(res.map_frame(|_| this.consume()))
+        }
+
+        fn run(
+            &mut self,
+            ctx: &mut CTX,
+            instructions: &mut I,
+        ) -> Result<FrameInitOrResult<Self>, ContextDbError<CTX>> {
+            RUNS.fetch_add(1, Ordering::Relaxed);
+            EvmFrameTr::<CTX, I, P>::run(&mut self.inner, ctx, instructions)
+        }
+
+        fn is_finished(&self) -> bool {
+            self.inner.is_finished()
+        }
+
+        fn return_result(
+            &mut self,
+            ctx: &mut CTX,
+            result: Self::FrameResult,
+        ) -> Result<(), ContextDbError<CTX>> {
+            EvmFrameTr::<CTX, I, P>::return_result(&mut self.inner, ctx, result)
+        }
+    }
+
+    #[test]
+    fn test_custom_frame() {
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            [opcode::STOP].into(),
+        )));
+        let mut evm = Evm::<_, _, _, _, CountingFrame>::new(
+            ctx,
+            crate::instructions::EthInstructions::new_mainnet(),
+            crate::EthPrecompiles::default(),
+        );
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        assert!(result.is_success());
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+    }
+    #[test]
+    fn test_frame_input_reclaimed() {
+        use crate::{ExecuteFuelEvm, FuelProgress, MainBuilder};
+        use database::{BENCH_CALLER, BENCH_TARGET};
+        use primitives::TxKind;
+
+        // Without calldata the contract calls itself twice with one byte of calldata.
+        let call = [
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            1,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::ADDRESS,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+        ];
+        let mut code = vec![opcode::CALLDATASIZE, opcode::PUSH1, 24, opcode::JUMPI];
+        code.extend(call);
+        code.extend(call);
+        code.extend([opcode::JUMPDEST, opcode::STOP]);
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .build_mainnet();
+
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+        let mut progress = evm.transact_with_fuel(tx, 1).unwrap();
+        let mut pooled = Vec::new();
+        while let FuelProgress::Yielded(continuation) = progress {
+            if evm.frame_stack.index() == Some(0) {
+                pooled.push(evm.frame_stack.get().interpreter.frame_input_pool.len());
+            }
+            progress = evm.resume(continuation, 1).unwrap();
+        }
+        assert!(progress.done().unwrap().is_success());
+        // Input of the first call is reused by the second one.
+        assert_eq!(pooled.iter().max(), Some(&1));
+        assert_eq!(pooled.first(), Some(&0));
+        assert_eq!(pooled.last(), Some(&1));
+    }
+}
@@ -2,12 +2,13 @@ use crate::evm::FrameTr;
 use crate::item_or_result::FrameInitOrResult;
 use crate::{precompile_provider::PrecompileProvider, ItemOrResult};
 use crate::{CallFrame, CreateFrame, FrameData, FrameResult};
-use context::result::FromStringError;
+use context::result::{CreateFailure, CreateFailureReason, FromStringError, HaltReason};
 use context_interface::context::ContextError;
-use context_interface::local::{FrameToken, OutFrame};
+use context_interface::local::{CallFrameInfo, FrameToken, LocalContextTr, OutFrame};
 use context_interface::ContextTr;
 use context_interface::{
-    journaled_state::{JournalCheckpoint, JournalTr},
+    cfg::StandardCreateAddress,
+    journaled_state::{JournalCheckpoint, JournalTr, TransferError},
     Cfg, Database,
 };
 use core::cmp::min;
@@ -19,14 +20,14 @@ use interpreter::{
     interpreter_types::ReturnData,
     CallInput, CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome, CreateScheme,
     FrameInput, Gas, InputsImpl, InstructionResult, Interpreter, InterpreterAction,
-    InterpreterResult, InterpreterTypes, SharedMemory,
+    InterpreterResult, InterpreterTypes, SharedMemory, SuccessOrHalt,
 };
 use primitives::{
     constants::CALL_STACK_LIMIT,
     hardfork::SpecId::{self, HOMESTEAD, LONDON, SPURIOUS_DRAGON},
 };
 use primitives::{keccak256, Address, Bytes, B256, U256};
-use state::Bytecode;
+use state::{bytecode::AnalysisStore, Bytecode};
 use std::borrow::ToOwned;
 use std::boxed::Box;
 
@@ -169,6 +170,11 @@ impl EthFrame<EthInterpreter> {
 
         // Create subroutine checkpoint
         let checkpoint = ctx.journal_mut().checkpoint();
+        ctx.local_mut().push_call_frame(CallFrameInfo::new(
+            inputs.target_address,
+            inputs.caller,
+            inputs.gas_limit,
+        ));
 
         // Touch address. For "EIP-158 State Clear", this will erase empty accounts.
         if let CallValue::Transfer(value) = inputs.value {
@@ -179,6 +185,7 @@ impl EthFrame<EthInterpreter> {
                     .transfer(inputs.caller, inputs.target_address, value)?
             {
                 ctx.journal_mut().checkpoint_revert(checkpoint);
+                ctx.local_mut().pop_call_frame();
                 return return_result(i.into());
             }
         }
@@ -208,6 +215,7 @@ impl EthFrame<EthInterpreter> {
             } else {
                 ctx.journal_mut().checkpoint_revert(checkpoint);
             }
+            ctx.local_mut().pop_call_frame();
             return Ok(ItemOrResult::Result(FrameResult::Call(CallOutcome {
                 result,
                 memory_offset: inputs.return_memory_offset.clone(),
@@ -233,6 +241,7 @@ impl EthFrame<EthInterpreter> {
         // Returns success if bytecode is empty.
         if bytecode.is_empty() {
             ctx.journal_mut().checkpoint_commit();
+            ctx.local_mut().pop_call_frame();
             return return_result(InstructionResult::Stop);
         }
 
@@ -277,9 +286,21 @@ impl EthFrame<EthInterpreter> {
                 address: None,
             })))
         };
+        // failures are only built when the local context records them.
+        let record_failure = |context: &mut CTX, address, reason| {
+            if context.local().records_create_failures() {
+                context.local_mut().record_create_failure(CreateFailure {
+                    caller: inputs.caller,
+                    address,
+                    depth,
+                    reason,
+                });
+            }
+        };
 
         // Check depth
         if depth > CALL_STACK_LIMIT as usize {
+            record_failure(context, None, CreateFailureReason::CallTooDeep);
             return return_error(InstructionResult::CallTooDeep);
         }
 
@@ -294,12 +315,18 @@ impl EthFrame<EthInterpreter> {
 
         // Check if caller has enough balance to send to the created contract.
         if caller_info.balance < inputs.value {
+            let reason = CreateFailureReason::InsufficientBalance {
+                balance: caller_info.balance,
+                value: inputs.value,
+            };
+            record_failure(context, None, reason);
             return return_error(InstructionResult::OutOfFunds);
         }
 
         // Increase nonce of caller and check if it overflows
         let old_nonce = caller_info.nonce;
         let Some(new_nonce) = old_nonce.checked_add(1) else {
+            record_failure(context, None, CreateFailureReason::NonceOverflow);
             return return_error(InstructionResult::Return);
         };
         caller_info.nonce = new_nonce;
@@ -309,17 +336,26 @@ impl EthFrame<EthInterpreter> {
 
         // Create address
         let mut init_code_hash = B256::ZERO;
+        let policy = context
+            .cfg()
+            .create_address_policy()
+            .unwrap_or(&StandardCreateAddress);
         let created_address = match inputs.scheme {
-            CreateScheme::Create => inputs.caller.create(old_nonce),
+            CreateScheme::Create => policy.create_address(inputs.caller, old_nonce),
             CreateScheme::Create2 { salt } => {
                 init_code_hash = keccak256(&inputs.init_code);
-                inputs.caller.create2(salt.to_be_bytes(), init_code_hash)
+                policy.create2_address(inputs.caller, salt, init_code_hash)
             }
             CreateScheme::Custom { address } => address,
         };
 
         // warm load account.
-        context.journal_mut().load_account(created_address)?;
+        let created_info = &context
+            .journal_mut()
+            .load_account(created_address)?
+            .data
+            .info;
+        let (created_nonce, created_code_hash) = (created_info.nonce, created_info.code_hash);
 
         // Create account, transfer funds and make the journal checkpoint.
         let checkpoint = match context.journal_mut().create_account_checkpoint(
@@ -329,8 +365,22 @@ impl EthFrame<EthInterpreter> {
             spec,
         ) {
             Ok(checkpoint) => checkpoint,
-            Err(e) => return return_error(e.into()),
+            Err(e) => {
+                if e == TransferError::CreateCollision {
+                    let reason = CreateFailureReason::Collision {
+                        nonce: created_nonce,
+                        code_hash: created_code_hash,
+                    };
+                    record_failure(context, Some(created_address), reason);
+                }
+                return return_error(e.into());
+            }
         };
+        context.local_mut().push_call_frame(CallFrameInfo::new(
+            created_address,
+            inputs.caller,
+            inputs.gas_limit,
+        ));
 
         let bytecode = ExtBytecode::new_with_hash(
             Bytecode::new_legacy(inputs.init_code.clone()),
@@ -527,6 +577,9 @@ impl EthFrame<EthInterpreter> {
 
         let mut interpreter_result = match next_action {
             InterpreterAction::NewFrame(frame_input) => {
+                context
+                    .local_mut()
+                    .set_remaining_gas(self.interpreter.gas.remaining());
                 let depth = self.depth + 1;
                 return Ok(ItemOrResult::Item(FrameInit {
                     frame_input,
@@ -536,6 +589,7 @@ impl EthFrame<EthInterpreter> {
             }
             InterpreterAction::Return(result) => result,
         };
+        context.local_mut().pop_call_frame();
 
         // Handle return from frame
         let result = match &self.data {
@@ -555,7 +609,8 @@ impl EthFrame<EthInterpreter> {
             FrameData::Create(frame) => {
                 let max_code_size = context.cfg().max_code_size();
                 let is_eip3541_disabled = context.cfg().is_eip3541_disabled();
-                return_create(
+                let analysis_store = context.cfg().analysis_store();
+                let failure = return_create_with_store(
                     context.journal_mut(),
                     self.checkpoint,
                     &mut interpreter_result,
@@ -563,7 +618,17 @@ impl EthFrame<EthInterpreter> {
                     max_code_size,
                     is_eip3541_disabled,
                     spec,
+                    analysis_store.as_deref(),
                 );
+                if let Some(reason) = failure.filter(|_| context.local().records_create_failures())
+                {
+                    context.local_mut().record_create_failure(CreateFailure {
+                        caller: self.interpreter.input.caller_address,
+                        address: Some(frame.created_address),
+                        depth: self.depth,
+                        reason,
+                    });
+                }
 
                 ItemOrResult::Result(FrameResult::Create(CreateOutcome::new(
                     interpreter_result,
@@ -668,6 +733,8 @@ impl EthFrame<EthInterpreter> {
 }
 
 /// Handles the result of a CREATE operation, including validation and state updates.
+///
+/// Returns the rule that failed the creation, if any.
 pub fn return_create<JOURNAL: JournalTr>(
     journal: &mut JOURNAL,
     checkpoint: JournalCheckpoint,
@@ -676,11 +743,42 @@ pub fn return_create<JOURNAL: JournalTr>(
     max_code_size: usize,
     is_eip3541_disabled: bool,
     spec_id: SpecId,
-) {
+) -> Option<CreateFailureReason> {
+    return_create_with_store(
+        journal,
+        checkpoint,
+        interpreter_result,
+        address,
+        max_code_size,
+        is_eip3541_disabled,
+        spec_id,
+        None,
+    )
+}
+
+/// Same as [`return_create`], the analysis of the created code is reused from and stored in
+/// `analysis_store` if it is set.
+#[allow(clippy::too_many_arguments)]
+pub fn return_create_with_store<JOURNAL: JournalTr>(
+    journal: &mut JOURNAL,
+    checkpoint: JournalCheckpoint,
+    interpreter_result: &mut InterpreterResult,
+    address: Address,
+    max_code_size: usize,
+    is_eip3541_disabled: bool,
+    spec_id: SpecId,
+    analysis_store: Option<&dyn AnalysisStore>,
+) -> Option<CreateFailureReason> {
     // If return is not ok revert and return.
     if !interpreter_result.result.is_ok() {
         journal.checkpoint_revert(checkpoint);
-        return;
+        return match SuccessOrHalt::<HaltReason>::from(interpreter_result.result) {
+            SuccessOrHalt::Revert => Some(CreateFailureReason::Reverted {
+                output: interpreter_result.output.clone(),
+            }),
+            SuccessOrHalt::Halt(reason) => Some(CreateFailureReason::Halted { reason }),
+            _ => None,
+        };
     }
     // Host error if present on execution
     // If ok, check contract creation limit and calculate gas deduction on output len.
@@ -692,7 +790,7 @@ pub fn return_create<JOURNAL: JournalTr>(
     {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractStartingWithEF;
-        return;
+        return Some(CreateFailureReason::StartsWithEF);
     }
 
     // EIP-170: Contract code size limit to 0x6000 (~25kb)
@@ -700,9 +798,13 @@ pub fn return_create<JOURNAL: JournalTr>(
     if spec_id.is_enabled_in(SPURIOUS_DRAGON) && interpreter_result.output.len() > max_code_size {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractSizeLimit;
-        return;
+        return Some(CreateFailureReason::CodeSizeLimit {
+            size: interpreter_result.output.len(),
+            limit: max_code_size,
+        });
     }
     let gas_for_code = interpreter_result.output.len() as u64 * gas::CODEDEPOSIT;
+    let remaining = interpreter_result.gas.remaining();
     if !interpreter_result.gas.record_cost(gas_for_code) {
         // Record code deposit gas cost and check if we are out of gas.
         // EIP-2 point 3: If contract creation does not have enough gas to pay for the
@@ -711,7 +813,10 @@ pub fn return_create<JOURNAL: JournalTr>(
         if spec_id.is_enabled_in(HOMESTEAD) {
             journal.checkpoint_revert(checkpoint);
             interpreter_result.result = InstructionResult::OutOfGas;
-            return;
+            return Some(CreateFailureReason::CodeDepositOutOfGas {
+                cost: gas_for_code,
+                remaining,
+            });
         } else {
             interpreter_result.output = Bytes::new();
         }
@@ -720,12 +825,18 @@ pub fn return_create<JOURNAL: JournalTr>(
     journal.checkpoint_commit();
 
     // Do analysis of bytecode straight away.
-    let bytecode = Bytecode::new_legacy(interpreter_result.output.clone());
+    let code = interpreter_result.output.clone();
+    let hash = keccak256(&code);
+    let bytecode = match analysis_store {
+        Some(store) => Bytecode::new_legacy_with_store(code, hash, store),
+        None => Bytecode::new_legacy(code),
+    };
 
     // Set code
-    journal.set_code(address, bytecode);
+    journal.set_code_with_hash(address, bytecode, hash);
 
     interpreter_result.result = InstructionResult::Return;
+    None
 }
 
 /*
@@ -0,0 +1,175 @@
+//! Execution that yields back to the caller after a number of instructions.
+//!
+//! Long transactions can be run in steps by environments that can not block, for example a
+//! single threaded event loop. Every step executes at most the given amount of fuel, one unit of
+//! fuel is one instruction.
+use crate::{
+    instructions::InstructionProvider, EvmFrameTr, EvmTr, ExecuteEvm, FrameResult, Handler,
+    MainnetHandler, PrecompileProvider,
+};
+use context::{
+    result::{ExecutionResult, HaltReason},
+    ContextSetters, ContextTr, Evm, JournalTr, LocalContextTr,
+};
+use interpreter::{interpreter_action::FrameInit, InitialAndFloorGas};
+use state::EvmState;
+
+/// Transaction paused because it ran out of fuel.
+///
+/// The paused frames are kept in the frame stack of the EVM, so the EVM must not be used for
+/// anything else until the transaction is resumed to the end or aborted.
+#[derive(Debug)]
+#[must_use = "paused transaction has to be resumed or aborted"]
+pub struct Continuation {
+    /// Initial and floor gas of the transaction.
+    pub init_and_floor_gas: InitialAndFloorGas,
+    /// Refund of EIP-7702 authorizations applied before execution.
+    pub eip7702_refund: i64,
+    /// Input of the first frame if it is not initialized yet.
+    pub first_frame_input: Option<FrameInit>,
+}
+
+/// Progress of a transaction executed with fuel.
+#[derive(Debug)]
+pub enum FuelProgress<T> {
+    /// Transaction is done.
+    Done(T),
+    /// Fuel ran out, the transaction continues with the continuation.
+    Yielded(Continuation),
+}
+
+impl<T> FuelProgress<T> {
+    /// Returns `true` if the transaction is done.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Done(_))
+    }
+
+    /// Returns the output if the transaction is done.
+    pub fn done(self) -> Option<T> {
+        match self {
+            Self::Done(output) => Some(output),
+            Self::Yielded(_) => None,
+        }
+    }
+}
+
+/// Execution of transactions in steps bounded by fuel.
+///
+/// Inspectors are not called by this execution.
+pub trait ExecuteFuelEvm: ExecuteEvm {
+    /// Executes the transaction until it is done or `fuel` instructions are executed.
+    ///
+    /// State is stored inside the journal like with [`ExecuteEvm::transact_one`].
+    fn transact_with_fuel(
+        &mut self,
+        tx: Self::Tx,
+        fuel: u64,
+    ) -> Result<FuelProgress<Self::ExecutionResult>, Self::Error>;
+
+    /// Continues the paused transaction for at most `fuel` instructions.
+    fn resume(
+        &mut self,
+        continuation: Continuation,
+        fuel: u64,
+    ) -> Result<FuelProgress<Self::ExecutionResult>, Self::Error>;
+
+    /// Aborts the paused transaction, discarding its changes.
+    fn abort(&mut self, continuation: Continuation);
+}
+
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> ExecuteFuelEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
+where
+    CTX: ContextTr<Journal: JournalTr<State = EvmState>> + ContextSetters,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
+{
+    #[inline]
+    fn transact_with_fuel(
+        &mut self,
+        tx: Self::Tx,
+        fuel: u64,
+    ) -> Result<FuelProgress<ExecutionResult<HaltReason>>, Self::Error> {
+        self.ctx.set_tx(tx);
+        MainnetHandler::default().run_with_fuel(self, fuel)
+    }
+
+    #[inline]
+    fn resume(
+        &mut self,
+        continuation: Continuation,
+        fuel: u64,
+    ) -> Result<FuelProgress<ExecutionResult<HaltReason>>, Self::Error> {
+        MainnetHandler::default().resume_with_fuel(self, continuation, fuel)
+    }
+
+    #[inline]
+    fn abort(&mut self, continuation: Continuation) {
+        let _ = continuation;
+        self.ctx.local_mut().clear();
+        self.ctx.journal_mut().discard_tx();
+        self.frame_stack().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use database::BenchmarkDB;
+
+    #[test]
+    fn test_transact_with_fuel() {
+        // Loop that decrements the counter from 100 to 0.
+        let bytecode = Bytecode::new_legacy(
+            [
+                opcode::PUSH1,
+                100,
+                opcode::JUMPDEST,
+                opcode::PUSH1,
+                1,
+                opcode::SWAP1,
+                opcode::SUB,
+                opcode::DUP1,
+                opcode::PUSH1,
+                2,
+                opcode::JUMPI,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let tx = TxEnv::builder_for_bench().build_fill();
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        let expected = evm.transact_one(tx.clone()).unwrap();
+        let _ = evm.finalize();
+
+        let mut steps = 1;
+        let mut progress = evm.transact_with_fuel(tx.clone(), 50).unwrap();
+        let result = loop {
+            match progress {
+                FuelProgress::Done(result) => break result,
+                FuelProgress::Yielded(continuation) => {
+                    steps += 1;
+                    progress = evm.resume(continuation, 50).unwrap();
+                }
+            }
+        };
+        let _ = evm.finalize();
+        assert_eq!(result, expected);
+        // 1 + 100 * 7 + 1 instructions.
+        assert_eq!(steps, 15);
+
+        // Aborted transaction leaves the EVM ready for the next one.
+        let FuelProgress::Yielded(continuation) = evm.transact_with_fuel(tx.clone(), 10).unwrap()
+        else {
+            panic!("transaction should yield");
+        };
+        evm.abort(continuation);
+        assert_eq!(evm.transact_one(tx).unwrap(), expected);
+    }
+}
@@ -0,0 +1,116 @@
+//! Breakdown of the gas used by executed transactions.
+use crate::{
+    instructions::InstructionProvider, EvmFrameTr, ExecuteEvm, FrameResult, Handler,
+    MainnetHandler, PrecompileProvider,
+};
+use context::{
+    result::{ExecResultAndState, ExecutionResult, HaltReason},
+    ContextSetters, ContextTr, Evm, JournalTr,
+};
+use interpreter::interpreter_action::FrameInit;
+use state::EvmState;
+
+/// Execution of a transaction that reports the parts of the gas it used.
+pub trait ExecuteGasBreakdownEvm: ExecuteEvm {
+    /// Executes and finalizes the transaction like [`ExecuteEvm::transact`] and sets
+    /// [`ExecResultAndState::gas_breakdown`].
+    fn transact_with_gas_breakdown(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error>;
+}
+
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> ExecuteGasBreakdownEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
+where
+    CTX: ContextTr<Journal: JournalTr<State = EvmState>> + ContextSetters,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
+{
+    fn transact_with_gas_breakdown(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<ExecutionResult<HaltReason>, EvmState>, Self::Error> {
+        self.ctx.set_tx(tx);
+        let output_or_error: Result<_, Self::Error> =
+            MainnetHandler::default().run_with_gas_breakdown(self);
+        // finalize will clear the journal
+        let state = self.finalize();
+        let (result, gas_breakdown) = output_or_error?;
+        Ok(ExecResultAndState::new(result, state).with_gas_breakdown(gas_breakdown))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use primitives::{hardfork::SpecId, TxKind};
+
+    fn transact(code: &[u8], data: Vec<u8>) -> ExecResultAndState<ExecutionResult, EvmState> {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                code.to_vec().into(),
+            )))
+            .build_mainnet();
+        evm.transact_with_gas_breakdown(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .data(data.into())
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gas_breakdown() {
+        // sstore(0, 1), sstore(0, 0)
+        let output = transact(
+            &[
+                opcode::PUSH1,
+                1,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::SSTORE,
+            ],
+            Vec::new(),
+        );
+        let breakdown = output.gas_breakdown.unwrap();
+        assert_eq!(breakdown.intrinsic_gas, 21_000);
+        assert_eq!(breakdown.execution_gas, 3 + 2 + 22_100 + 2 + 2 + 100);
+        // Refund is capped to a fifth of the gas spent.
+        assert_eq!(
+            breakdown.refunded_gas,
+            (breakdown.intrinsic_gas + breakdown.execution_gas) / 5
+        );
+        assert!(matches!(
+            output.result,
+            ExecutionResult::Success { gas_refunded, .. } if gas_refunded == breakdown.refunded_gas
+        ));
+        assert_eq!(breakdown.floor_gas_adjustment, 0);
+        assert_eq!(breakdown.blob_gas, 0);
+        assert_eq!(breakdown.gas_used(), output.result.gas_used());
+    }
+
+    #[test]
+    fn test_gas_breakdown_floor() {
+        // Calldata of a cheap call is charged with the floor cost of EIP-7623.
+        let output = transact(&[opcode::STOP], vec![1; 100]);
+        let breakdown = output.gas_breakdown.unwrap();
+        assert_eq!(breakdown.intrinsic_gas, 21_000 + 100 * 16);
+        assert_eq!(breakdown.execution_gas, 0);
+        assert_eq!(breakdown.floor_gas_adjustment, 100 * 40 - 100 * 16);
+        assert_eq!(breakdown.gas_used(), output.result.gas_used());
+        assert_eq!(output.result.gas_used(), 21_000 + 100 * 40);
+    }
+}
@@ -0,0 +1,148 @@
+//! Gas limit ceiling of simulated calls, as applied by RPC nodes to `eth_call`.
+use crate::{EvmTr, ExecuteEvm};
+use context::{result::ExecResultAndState, Block, Cfg, ContextTr, Transaction, TxEnv};
+use interpreter::gas::MAX_GAS_LIMIT;
+
+/// Transaction whose gas limit can be lowered to the gas cap.
+pub trait GasCapTx: Transaction {
+    /// Sets the gas limit of the transaction.
+    fn set_gas_limit(&mut self, gas_limit: u64);
+}
+
+impl GasCapTx for TxEnv {
+    fn set_gas_limit(&mut self, gas_limit: u64) {
+        self.gas_limit = gas_limit;
+    }
+}
+
+/// Gas limit of a transaction lowered to the gas cap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasCap {
+    /// Gas limit of the transaction as requested.
+    pub requested: u64,
+    /// Gas limit the transaction was executed with.
+    pub applied: u64,
+}
+
+/// Output of [`ExecuteGasCapEvm::transact_with_gas_cap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasCapped<T> {
+    /// Output of the execution.
+    pub output: T,
+    /// Set if the gas limit of the transaction was lowered.
+    pub capped: Option<GasCap>,
+}
+
+/// Output of [`ExecuteGasCapEvm::transact_with_gas_cap`] for the EVM.
+pub type GasCappedOutput<EVM> =
+    GasCapped<ExecResultAndState<<EVM as ExecuteEvm>::ExecutionResult, <EVM as ExecuteEvm>::State>>;
+
+impl<T> GasCapped<T> {
+    /// Returns `true` if the gas limit of the transaction was lowered.
+    pub fn is_capped(&self) -> bool {
+        self.capped.is_some()
+    }
+}
+
+/// Returns the gas cap of calls in the context.
+///
+/// It is the lowest of [`Cfg::call_gas_cap`], the block gas limit unless its check is disabled,
+/// [`Cfg::tx_gas_limit_cap`] and [`MAX_GAS_LIMIT`].
+pub fn gas_cap<CTX: ContextTr>(context: &CTX) -> u64 {
+    let cfg = context.cfg();
+    let mut cap = cfg
+        .call_gas_cap()
+        .unwrap_or(u64::MAX)
+        .min(cfg.tx_gas_limit_cap())
+        .min(MAX_GAS_LIMIT);
+    if !cfg.is_block_gas_limit_disabled() {
+        cap = cap.min(context.block().gas_limit());
+    }
+    cap
+}
+
+/// Execution of simulated calls with the gas limit lowered to the gas cap of the context.
+///
+/// Callers keep the transaction as requested, the lowered gas limit is reported in
+/// [`GasCapped::capped`].
+pub trait ExecuteGasCapEvm: ExecuteEvm<Tx: GasCapTx> {
+    /// Lowers the gas limit of the transaction to [`gas_cap`] and executes and finalizes it
+    /// like [`ExecuteEvm::transact`].
+    fn transact_with_gas_cap(&mut self, tx: Self::Tx)
+        -> Result<GasCappedOutput<Self>, Self::Error>;
+}
+
+impl<EVM> ExecuteGasCapEvm for EVM
+where
+    EVM: ExecuteEvm<Tx: GasCapTx> + EvmTr,
+{
+    fn transact_with_gas_cap(
+        &mut self,
+        mut tx: Self::Tx,
+    ) -> Result<GasCappedOutput<Self>, Self::Error> {
+        let cap = gas_cap(self.ctx_ref());
+        let requested = tx.gas_limit();
+        let capped = (requested > cap).then(|| {
+            tx.set_gas_limit(cap);
+            GasCap {
+                requested,
+                applied: cap,
+            }
+        });
+        let output = self.transact(tx)?;
+        Ok(GasCapped { output, capped })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use context::{result::ExecutionResult, Context};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use primitives::TxKind;
+
+    #[test]
+    fn test_transact_with_gas_cap() {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.call_gas_cap = Some(50_000))
+            .modify_block_chained(|block| block.gas_limit = 40_000)
+            .with_db(BenchmarkDB::default())
+            .build_mainnet();
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(1_000_000)
+            .build()
+            .unwrap();
+        assert_eq!(gas_cap(evm.ctx_ref()), 40_000);
+
+        let outcome = evm.transact_with_gas_cap(tx.clone()).unwrap();
+        assert_eq!(
+            outcome.capped,
+            Some(GasCap {
+                requested: 1_000_000,
+                applied: 40_000,
+            })
+        );
+        assert!(matches!(
+            outcome.output.result,
+            ExecutionResult::Success { .. }
+        ));
+
+        // Ceiling of the configuration applies below the block gas limit.
+        evm.ctx().block.gas_limit = 60_000;
+        let outcome = evm.transact_with_gas_cap(tx).unwrap();
+        assert_eq!(outcome.capped.map(|cap| cap.applied), Some(50_000));
+
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(30_000)
+            .build()
+            .unwrap();
+        let outcome = evm.transact_with_gas_cap(tx).unwrap();
+        assert!(!outcome.is_capped());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
-    evm::FrameTr, execution, post_execution, pre_execution, validation, EvmTr, FrameResult,
-    ItemOrResult,
+    evm::FrameTr,
+    execution,
+    fuel::{Continuation, FuelProgress},
+    post_execution, pre_execution, validation, EvmTr, FrameInitOrResult, FrameResult, ItemOrResult,
 };
-use context::result::{ExecutionResult, FromStringError};
+use context::result::{ExecutionResult, FromStringError, GasBreakdown};
 use context::LocalContextTr;
 use context_interface::context::ContextError;
 use context_interface::ContextTr;
@@ -144,10 +146,140 @@ pub trait Handler {
         &mut self,
         evm: &mut Self::Evm,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        let (init_and_floor_gas, eip7702_refund) = self.validate_and_pre_execution(evm)?;
+        let exec_result = self.execution(evm, &init_and_floor_gas)?;
+        self.finish_execution(evm, exec_result, init_and_floor_gas, eip7702_refund, |_| {})
+    }
+
+    /// Runs the transaction like [`Handler::run`] and returns the breakdown of the gas used.
+    ///
+    /// If an error is turned into a result by [`Handler::catch_error`], all gas used by it is
+    /// reported as execution gas.
+    #[inline]
+    fn run_with_gas_breakdown(
+        &mut self,
+        evm: &mut Self::Evm,
+    ) -> Result<(ExecutionResult<Self::HaltReason>, GasBreakdown), Self::Error> {
+        let mut gas_breakdown = GasBreakdown::default();
+        let output = (|| {
+            let (init_and_floor_gas, eip7702_refund) = self.validate_and_pre_execution(evm)?;
+            let exec_result = self.execution(evm, &init_and_floor_gas)?;
+            let execution_spent = exec_result.gas().spent();
+            let blob_gas = evm.ctx().tx().total_blob_gas();
+            self.finish_execution(
+                evm,
+                exec_result,
+                init_and_floor_gas,
+                eip7702_refund,
+                |gas| {
+                    gas_breakdown = post_execution::gas_breakdown(
+                        init_and_floor_gas,
+                        execution_spent,
+                        gas,
+                        blob_gas,
+                    );
+                },
+            )
+        })();
+        match output {
+            Ok(result) => Ok((result, gas_breakdown)),
+            Err(e) => self.catch_error(evm, e).map(|result| {
+                let gas_breakdown = GasBreakdown {
+                    execution_gas: result.gas_used(),
+                    ..Default::default()
+                };
+                (result, gas_breakdown)
+            }),
+        }
+    }
+
+    /// Runs the transaction like [`Handler::run`], yielding back after `fuel` instructions.
+    ///
+    /// [`Handler::execution`] is replaced with [`Handler::execution_with_fuel`]. If fuel runs
+    /// out the returned [`Continuation`] is passed to [`Handler::resume_with_fuel`] to continue
+    /// the transaction. Errors are caught with [`Handler::catch_error`].
+    #[inline]
+    fn run_with_fuel(
+        &mut self,
+        evm: &mut Self::Evm,
+        fuel: u64,
+    ) -> Result<FuelProgress<ExecutionResult<Self::HaltReason>>, Self::Error> {
+        let prepared = self.validate_and_pre_execution(evm).and_then(|prepared| {
+            let (init_and_floor_gas, eip7702_refund) = prepared;
+            let gas_limit = evm.ctx().tx().gas_limit() - init_and_floor_gas.initial_gas;
+            let first_frame_input = self.first_frame_input(evm, gas_limit)?;
+            Ok(Continuation {
+                init_and_floor_gas,
+                eip7702_refund,
+                first_frame_input: Some(first_frame_input),
+            })
+        });
+        match prepared {
+            Ok(continuation) => self.resume_with_fuel(evm, continuation, fuel),
+            Err(e) => self.catch_error(evm, e).map(FuelProgress::Done),
+        }
+    }
+
+    /// Continues the transaction paused by [`Handler::run_with_fuel`] for at most `fuel`
+    /// instructions, post execution is run when the execution is done.
+    #[inline]
+    fn resume_with_fuel(
+        &mut self,
+        evm: &mut Self::Evm,
+        mut continuation: Continuation,
+        fuel: u64,
+    ) -> Result<FuelProgress<ExecutionResult<Self::HaltReason>>, Self::Error> {
+        let output = self
+            .execution_with_fuel(evm, &mut continuation, fuel)
+            .and_then(|exec_result| {
+                let Some(exec_result) = exec_result else {
+                    return Ok(None);
+                };
+                self.finish_execution(
+                    evm,
+                    exec_result,
+                    continuation.init_and_floor_gas,
+                    continuation.eip7702_refund,
+                    |_| {},
+                )
+                .map(Some)
+            });
+        match output {
+            Ok(Some(output)) => Ok(FuelProgress::Done(output)),
+            Ok(None) => Ok(FuelProgress::Yielded(continuation)),
+            Err(e) => self.catch_error(evm, e).map(FuelProgress::Done),
+        }
+    }
+
+    /// Runs [`Handler::validate`] and [`Handler::pre_execution`], the steps before the execution
+    /// shared by all entry points.
+    ///
+    /// Returns the initial and floor gas and the EIP-7702 gas refund.
+    #[inline]
+    fn validate_and_pre_execution(
+        &self,
+        evm: &mut Self::Evm,
+    ) -> Result<(InitialAndFloorGas, i64), Self::Error> {
         let init_and_floor_gas = self.validate(evm)?;
         let eip7702_refund = self.pre_execution(evm)? as i64;
-        let mut exec_result = self.execution(evm, &init_and_floor_gas)?;
+        Ok((init_and_floor_gas, eip7702_refund))
+    }
+
+    /// Runs [`Handler::post_execution`] and prepares the output with
+    /// [`Handler::execution_result`], the steps after the execution shared by all entry points.
+    ///
+    /// `on_gas` is called with the final gas of the transaction before the output is prepared.
+    #[inline]
+    fn finish_execution(
+        &mut self,
+        evm: &mut Self::Evm,
+        mut exec_result: FrameResult,
+        init_and_floor_gas: InitialAndFloorGas,
+        eip7702_refund: i64,
+        on_gas: impl FnOnce(&Gas),
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
         self.post_execution(evm, &mut exec_result, init_and_floor_gas, eip7702_refund)?;
+        on_gas(exec_result.gas());
 
         // Prepare the output
         self.execution_result(evm, exec_result)
@@ -202,6 +334,31 @@ pub trait Handler {
         Ok(frame_result)
     }
 
+    /// Executes the frames of the transaction for at most `fuel` instructions.
+    ///
+    /// Returns `None` if fuel ran out before the first frame returned.
+    #[inline]
+    fn execution_with_fuel(
+        &mut self,
+        evm: &mut Self::Evm,
+        continuation: &mut Continuation,
+        mut fuel: u64,
+    ) -> Result<Option<FrameResult>, Self::Error> {
+        let frame_result = match continuation.first_frame_input.take() {
+            Some(first_frame_input) => match evm.frame_init(first_frame_input)? {
+                ItemOrResult::Result(frame_result) => Some(frame_result),
+                ItemOrResult::Item(_) => self.run_exec_loop_with_fuel(evm, &mut fuel)?,
+            },
+            None => self.run_exec_loop_with_fuel(evm, &mut fuel)?,
+        };
+        let Some(mut frame_result) = frame_result else {
+            return Ok(None);
+        };
+
+        self.last_frame_result(evm, &mut frame_result)?;
+        Ok(Some(frame_result))
+    }
+
     /// Handles the final steps of transaction execution.
     ///
     /// Calculates final refunds and validates the gas floor (EIP-7623) to ensure minimum gas is spent.
@@ -348,26 +505,58 @@ pub trait Handler {
 
         loop {
             let call_or_result = evm.frame_run()?;
+            if let Some(result) = self.process_frame_output(evm, call_or_result)? {
+                return Ok(result);
+            }
+        }
+    }
 
-            let result = match call_or_result {
-                ItemOrResult::Item(init) => {
-                    match evm.frame_init(init)? {
-                        ItemOrResult::Item(_) => {
-                            continue;
-                        }
-                        // Do not pop the frame since no new frame was created
-                        ItemOrResult::Result(result) => result,
-                    }
-                }
-                ItemOrResult::Result(result) => result,
+    /// Executes the frame processing loop of initialized frames until the first frame returns or
+    /// fuel runs out.
+    ///
+    /// Returns `None` if fuel ran out, calling it again continues the execution.
+    #[inline]
+    fn run_exec_loop_with_fuel(
+        &mut self,
+        evm: &mut Self::Evm,
+        fuel: &mut u64,
+    ) -> Result<Option<FrameResult>, Self::Error> {
+        loop {
+            let Some(call_or_result) = evm.frame_run_with_fuel(fuel)? else {
+                return Ok(None);
             };
-
-            if let Some(result) = evm.frame_return_result(result)? {
-                return Ok(result);
+            if let Some(result) = self.process_frame_output(evm, call_or_result)? {
+                return Ok(Some(result));
             }
         }
     }
 
+    /// Handles the output of a frame run, used by the execution loops.
+    ///
+    /// Initializes the new frame if the frame called another one, otherwise returns the result
+    /// to the parent frame. Returns the result of the first frame once it returns.
+    #[inline]
+    fn process_frame_output(
+        &mut self,
+        evm: &mut Self::Evm,
+        call_or_result: FrameInitOrResult<<Self::Evm as EvmTr>::Frame>,
+    ) -> Result<Option<FrameResult>, Self::Error> {
+        let result = match call_or_result {
+            ItemOrResult::Item(init) => {
+                match evm.frame_init(init)? {
+                    ItemOrResult::Item(_) => {
+                        return Ok(None);
+                    }
+                    // Do not pop the frame since no new frame was created
+                    ItemOrResult::Result(result) => result,
+                }
+            }
+            ItemOrResult::Result(result) => result,
+        };
+
+        Ok(evm.frame_return_result(result)?)
+    }
+
     /* POST EXECUTION */
 
     /// Validates that the minimum gas floor requirements are satisfied.
@@ -391,8 +580,8 @@ pub trait Handler {
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
         eip7702_refund: i64,
     ) {
-        let spec = evm.ctx().cfg().spec().into();
-        post_execution::refund(spec, exec_result.gas_mut(), eip7702_refund)
+        let policy = evm.ctx().cfg().refund_policy();
+        post_execution::refund_with_policy(policy, exec_result.gas_mut(), eip7702_refund)
     }
 
     /// Returns unused gas costs to the transaction sender's account.
@@ -1,6 +1,7 @@
 use auto_impl::auto_impl;
+use context_interface::cfg::DisabledOpcodes;
 use interpreter::{
-    instructions::{instruction_table, InstructionTable},
+    instructions::{control, instruction_table, with_disabled_opcodes, InstructionTable},
     Host, Instruction, InterpreterTypes,
 };
 use std::boxed::Box;
@@ -15,13 +16,32 @@ pub trait InstructionProvider {
 
     /// Returns the instruction table that is used by EvmTr to execute instructions.
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context>;
+
+    /// Calls `f` with the instruction table where the `disabled` opcodes halt with
+    /// [`InstructionResult::OpcodeDisabled`](interpreter::InstructionResult::OpcodeDisabled).
+    ///
+    /// Default implementation copies the table on every call if some opcodes are disabled,
+    /// [`EthInstructions`] builds the patched table once and reuses it.
+    #[auto_impl(keep_default_for(&, Arc, Rc))]
+    fn with_instruction_table<R>(
+        &mut self,
+        disabled: DisabledOpcodes,
+        f: impl FnOnce(&InstructionTable<Self::InterpreterTypes, Self::Context>) -> R,
+    ) -> R {
+        with_disabled_opcodes(self.instruction_table(), disabled, f)
+    }
 }
 
 /// Ethereum instruction contains list of mainnet instructions that is used for Interpreter execution.
 #[derive(Debug)]
 pub struct EthInstructions<WIRE: InterpreterTypes, HOST> {
     /// Table containing instruction implementations indexed by opcode.
+    ///
+    /// Use [`EthInstructions::insert_instruction`] to change it after the table with disabled
+    /// opcodes was built, changing the table directly does not rebuild it.
     pub instruction_table: Box<InstructionTable<WIRE, HOST>>,
+    /// Instruction table with disabled opcodes and the opcodes it was built for.
+    disabled_table: Option<(DisabledOpcodes, Box<InstructionTable<WIRE, HOST>>)>,
 }
 
 impl<WIRE, HOST> Clone for EthInstructions<WIRE, HOST>
@@ -31,6 +51,7 @@ where
     fn clone(&self) -> Self {
         Self {
             instruction_table: self.instruction_table.clone(),
+            disabled_table: self.disabled_table.clone(),
         }
     }
 }
@@ -49,12 +70,14 @@ where
     pub fn new(base_table: InstructionTable<WIRE, HOST>) -> Self {
         Self {
             instruction_table: Box::new(base_table),
+            disabled_table: None,
         }
     }
 
     /// Inserts a new instruction into the instruction table.s
     pub fn insert_instruction(&mut self, opcode: u8, instruction: Instruction<WIRE, HOST>) {
         self.instruction_table[opcode as usize] = instruction;
+        self.disabled_table = None;
     }
 }
 
@@ -69,6 +92,27 @@ where
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context> {
         &self.instruction_table
     }
+
+    fn with_instruction_table<R>(
+        &mut self,
+        disabled: DisabledOpcodes,
+        f: impl FnOnce(&InstructionTable<Self::InterpreterTypes, Self::Context>) -> R,
+    ) -> R {
+        if disabled.is_empty() {
+            return f(&self.instruction_table);
+        }
+        let (_, table) = match &mut self.disabled_table {
+            Some(cached) if cached.0 == disabled => cached,
+            cached => {
+                let mut table = self.instruction_table.clone();
+                for opcode in disabled.iter() {
+                    table[opcode as usize] = control::disabled;
+                }
+                cached.insert((disabled, table))
+            }
+        };
+        f(table)
+    }
 }
 
 impl<WIRE, HOST> Default for EthInstructions<WIRE, HOST>
@@ -80,3 +124,33 @@ where
         Self::new_mainnet()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode;
+    use context_interface::DummyHost;
+    use interpreter::interpreter::EthInterpreter;
+
+    #[test]
+    fn disabled_table_is_reused() {
+        let mut instructions = EthInstructions::<EthInterpreter, DummyHost>::new_mainnet();
+        let base = &*instructions.instruction_table as *const _;
+        assert_eq!(
+            instructions.with_instruction_table(DisabledOpcodes::new(), |table| table as *const _),
+            base
+        );
+
+        let disabled = DisabledOpcodes::new().with(opcode::SELFDESTRUCT);
+        let patched = instructions.with_instruction_table(disabled, |table| table as *const _);
+        assert_ne!(patched, base);
+        assert_eq!(
+            instructions.with_instruction_table(disabled, |table| table as *const _),
+            patched
+        );
+
+        // Changing the base table drops the patched table.
+        instructions.insert_instruction(opcode::ADD, control::unknown);
+        assert!(instructions.disabled_table.is_none());
+    }
+}
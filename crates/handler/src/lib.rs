@@ -9,12 +9,35 @@ extern crate alloc as std;
 
 /// EVM execution API traits and implementations.
 pub mod api;
+/// Postcondition assertions for transaction simulation.
+pub mod assertion;
+/// Balance change summary of executed transactions.
+pub mod balance_changes;
+/// Execution of transaction sequences within block gas limits.
+pub mod block_executor;
+/// Verification of block header fields against execution results.
+pub mod block_verification;
+/// Contract calls with ABI encoded inputs and outputs.
+#[cfg(feature = "sol")]
+pub mod contract_call;
+/// Diagnostics of failed contract creations.
+pub mod create_diagnostics;
+/// Deposit transactions that mint balance and skip fee validation.
+pub mod deposit;
+/// Accounts destructed by executed transactions.
+pub mod destructed_accounts;
 /// Core EVM traits for execution and frame management.
 pub mod evm;
 /// EVM execution logic and utilities.
 pub mod execution;
 mod frame;
 mod frame_data;
+/// Execution that yields back to the caller after a number of instructions.
+pub mod fuel;
+/// Breakdown of the gas used by executed transactions.
+pub mod gas_breakdown;
+/// Gas limit ceiling of simulated calls.
+pub mod gas_cap;
 /// Handler implementation for orchestrating EVM execution.
 pub mod handler;
 /// EVM instruction set implementations and tables.
@@ -26,19 +49,46 @@ mod mainnet_handler;
 pub mod post_execution;
 pub mod pre_execution;
 mod precompile_provider;
+/// Execution of untrusted bytecode within the limits of a sandbox profile.
+pub mod sandbox;
+/// Slowest database reads of executed transactions.
+pub mod slow_reads;
 /// System call implementations for special EVM operations.
 pub mod system_call;
+/// Simulation of ERC-4337 user operation bundles.
+pub mod user_operation;
 /// Transaction and environment validation utilities.
 pub mod validation;
+/// Consensus layer withdrawals applied as balance increments.
+pub mod withdrawals;
 
 // Public exports
 pub use api::{ExecuteCommitEvm, ExecuteEvm};
-pub use evm::{EvmTr, FrameTr};
-pub use frame::{return_create, ContextTrDbError, EthFrame};
+pub use assertion::{ExecuteAssertEvm, Postconditions};
+pub use balance_changes::ExecuteBalanceChangesEvm;
+pub use block_executor::{BlockExecutor, BlockGasPolicy};
+pub use block_verification::{verify_block, BlockMismatchReport, ExpectedHeader};
+#[cfg(feature = "sol")]
+pub use contract_call::{ContractCallError, ContractCallEvm};
+pub use create_diagnostics::ExecuteCreateDiagnosticsEvm;
+pub use deposit::{DepositHandler, DepositPolicy, DepositTx, DepositTxTr};
+pub use destructed_accounts::ExecuteDestructedAccountsEvm;
+pub use evm::{EvmFrameTr, EvmTr, FrameTr};
+pub use frame::{return_create, return_create_with_store, ContextTrDbError, EthFrame};
 pub use frame_data::{CallFrame, CreateFrame, FrameData, FrameResult};
+pub use fuel::{Continuation, ExecuteFuelEvm, FuelProgress};
+pub use gas_breakdown::ExecuteGasBreakdownEvm;
+pub use gas_cap::{ExecuteGasCapEvm, GasCap, GasCapTx, GasCapped, GasCappedOutput};
 pub use handler::{EvmTrError, Handler};
 pub use item_or_result::{FrameInitOrResult, ItemOrResult};
 pub use mainnet_builder::{MainBuilder, MainContext, MainnetContext, MainnetEvm};
 pub use mainnet_handler::MainnetHandler;
-pub use precompile_provider::{EthPrecompiles, PrecompileProvider};
+pub use precompile_provider::{
+    ChainedPrecompiles, DynPrecompile, DynPrecompileProvider, DynStatefulPrecompile,
+    EthPrecompiles, PrecompileProvider, StatefulPrecompiles,
+};
+pub use sandbox::{ExecuteSandboxEvm, SandboxOutcome};
+pub use slow_reads::ExecuteSlowReadsEvm;
 pub use system_call::{SystemCallCommitEvm, SystemCallEvm, SystemCallTx, SYSTEM_ADDRESS};
+pub use user_operation::{UserOperation, UserOperationEvm, UserOperationResult};
+pub use withdrawals::{apply_withdrawals, Withdrawal, WithdrawalsEvm};
@@ -131,4 +131,107 @@ mod test {
             StorageValue::from(1)
         );
     }
+
+    #[test]
+    fn applied_delegations() {
+        use context_interface::{journaled_state::AppliedDelegation, ContextTr, JournalTr};
+
+        let signer = PrivateKeySigner::random();
+        let auth = |nonce, address| {
+            let auth = Authorization {
+                chain_id: U256::ZERO,
+                nonce,
+                address,
+            };
+            let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+            Either::Left(auth.into_signed(signature))
+        };
+
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        let result = evm
+            .transact_one(
+                TxEnv::builder()
+                    .gas_limit(100_000)
+                    // Authorization with the wrong nonce is skipped.
+                    .authorization_list(vec![
+                        auth(0, FFADDRESS),
+                        auth(0, EEADDRESS),
+                        auth(1, primitives::Address::ZERO),
+                    ])
+                    .caller(EEADDRESS)
+                    .kind(TxKind::Call(signer.address()))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+
+        let delegations = evm.ctx.journal_mut().take_applied_delegations();
+        assert_eq!(
+            delegations,
+            [
+                AppliedDelegation {
+                    authority: signer.address(),
+                    delegate: FFADDRESS,
+                },
+                AppliedDelegation {
+                    authority: signer.address(),
+                    delegate: primitives::Address::ZERO,
+                },
+            ]
+        );
+        assert!(delegations[1].is_cleared());
+        assert!(evm.ctx.journal_mut().take_applied_delegations().is_empty());
+    }
+
+    #[test]
+    fn eip7702_chain_id_policy() {
+        use context::cfg::ChainIdPolicy;
+        use context_interface::{journaled_state::AppliedDelegation, ContextTr, JournalTr};
+
+        let signer = PrivateKeySigner::random();
+        let auth = |chain_id, nonce| {
+            let auth = Authorization {
+                chain_id: U256::from(chain_id),
+                nonce,
+                address: FFADDRESS,
+            };
+            let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+            Either::Left(auth.into_signed(signature))
+        };
+
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| {
+                cfg.spec = SpecId::PRAGUE;
+                *cfg = core::mem::take(cfg).with_chain_id_policy(ChainIdPolicy::AnyOf(vec![10]));
+            })
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        let result = evm
+            .transact_one(
+                TxEnv::builder()
+                    .gas_limit(100_000)
+                    // Chain id policy does not apply to authorizations, only the chain id
+                    // of the chain is accepted.
+                    .authorization_list(vec![auth(56, 0), auth(10, 0), auth(1, 0)])
+                    .caller(EEADDRESS)
+                    .kind(TxKind::Call(signer.address()))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+
+        let delegation = AppliedDelegation {
+            authority: signer.address(),
+            delegate: FFADDRESS,
+        };
+        assert_eq!(
+            evm.ctx.journal_mut().take_applied_delegations(),
+            [delegation]
+        );
+    }
 }
@@ -1,7 +1,8 @@
 use crate::FrameResult;
 use context_interface::{
+    cfg::{FeeRecipient, RefundPolicy},
     journaled_state::JournalTr,
-    result::{ExecutionResult, HaltReasonTr},
+    result::{ExecutionResult, GasBreakdown, HaltReasonTr},
     Block, Cfg, ContextTr, Database, Transaction,
 };
 use interpreter::{Gas, InitialAndFloorGas, SuccessOrHalt};
@@ -18,13 +19,37 @@ pub fn eip7623_check_gas_floor(gas: &mut Gas, init_and_floor_gas: InitialAndFloo
     }
 }
 
+/// Returns the breakdown of the gas used by the transaction.
+///
+/// `execution_spent` is the gas spent before refunds and the gas floor are applied, `gas` is the
+/// final gas of the transaction.
+pub fn gas_breakdown(
+    init_and_floor_gas: InitialAndFloorGas,
+    execution_spent: u64,
+    gas: &Gas,
+    blob_gas: u64,
+) -> GasBreakdown {
+    GasBreakdown {
+        intrinsic_gas: init_and_floor_gas.initial_gas,
+        execution_gas: execution_spent.saturating_sub(init_and_floor_gas.initial_gas),
+        refunded_gas: gas.refunded() as u64,
+        floor_gas_adjustment: gas.spent().saturating_sub(execution_spent),
+        blob_gas,
+    }
+}
+
 /// Calculates and applies gas refunds based on the specification.
 pub fn refund(spec: SpecId, gas: &mut Gas, eip7702_refund: i64) {
+    refund_with_policy(RefundPolicy::from_spec(spec), gas, eip7702_refund)
+}
+
+/// Calculates and applies gas refunds based on the given refund rules.
+pub fn refund_with_policy(policy: RefundPolicy, gas: &mut Gas, eip7702_refund: i64) {
     gas.record_refund(eip7702_refund);
     // Calculate gas refund for transaction.
     // If spec is set to london, it will decrease the maximum refund amount to 5th part of
     // gas spend. (Before london it was 2th part of gas spend)
-    gas.set_final_refund(spec.is_enabled_in(SpecId::LONDON));
+    gas.set_final_refund_with_quotient(policy.cap.quotient());
 }
 
 /// Reimburses the caller for unused gas.
@@ -34,28 +59,81 @@ pub fn reimburse_caller<CTX: ContextTr>(
     gas: &Gas,
     additional_refund: U256,
 ) -> Result<(), <CTX::Db as Database>::Error> {
-    let basefee = context.block().basefee() as u128;
     let caller = context.tx().caller();
-    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    let reimbursement = caller_reimbursement(context, gas, additional_refund);
 
     // Return balance of not spend gas.
-    context.journal_mut().balance_incr(
-        caller,
-        U256::from(
-            effective_gas_price.saturating_mul((gas.remaining() + gas.refunded() as u64) as u128),
-        ) + additional_refund,
-    )?;
+    context.journal_mut().balance_incr(caller, reimbursement)?;
 
     Ok(())
 }
 
-/// Rewards the beneficiary with transaction fees.
+/// Returns the amount returned to the caller for unused and refunded gas.
+#[inline]
+pub fn caller_reimbursement<CTX: ContextTr>(
+    context: &CTX,
+    gas: &Gas,
+    additional_refund: U256,
+) -> U256 {
+    let basefee = context.block().basefee() as u128;
+    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    U256::from(
+        effective_gas_price.saturating_mul((gas.remaining() + gas.refunded() as u64) as u128),
+    ) + additional_refund
+}
+
+/// Pays the transaction fees to the recipients of the [`FeePolicy`] of the configuration.
+///
+/// Block beneficiary is always touched, other recipients are credited only with non-zero fees.
+///
+/// [`FeePolicy`]: context_interface::cfg::FeePolicy
 #[inline]
 pub fn reward_beneficiary<CTX: ContextTr>(
     context: &mut CTX,
     gas: &Gas,
 ) -> Result<(), <CTX::Db as Database>::Error> {
     let beneficiary = context.block().beneficiary();
+    let mut reward = U256::ZERO;
+    for (recipient, fee) in fee_distribution(context, gas) {
+        match recipient {
+            FeeRecipient::Beneficiary => reward += fee,
+            FeeRecipient::Burn => {}
+            FeeRecipient::Address(address) => {
+                if !fee.is_zero() {
+                    context.journal_mut().balance_incr(address, fee)?;
+                }
+            }
+        }
+    }
+
+    // reward beneficiary
+    context.journal_mut().balance_incr(beneficiary, reward)?;
+
+    Ok(())
+}
+
+/// Returns the recipients of the base fee and the priority fee of the transaction according to
+/// the [`FeePolicy`] of the configuration.
+///
+/// Recipients can repeat and fees can be zero.
+///
+/// [`FeePolicy`]: context_interface::cfg::FeePolicy
+#[inline]
+pub fn fee_distribution<CTX: ContextTr>(context: &CTX, gas: &Gas) -> [(FeeRecipient, U256); 4] {
+    let policy = context.cfg().fee_policy();
+    let [priority, priority_split] = policy
+        .priority_fee
+        .distribute(beneficiary_reward(context, gas));
+    let [base, base_split] = policy.base_fee.distribute(burned_base_fee(context, gas));
+    [priority, priority_split, base, base_split]
+}
+
+/// Returns the priority fee of the transaction, paid to the block beneficiary by
+/// [`FeePolicy::MAINNET`].
+///
+/// [`FeePolicy::MAINNET`]: context_interface::cfg::FeePolicy::MAINNET
+#[inline]
+pub fn beneficiary_reward<CTX: ContextTr>(context: &CTX, gas: &Gas) -> U256 {
     let basefee = context.block().basefee() as u128;
     let effective_gas_price = context.tx().effective_gas_price(basefee);
 
@@ -66,14 +144,25 @@ pub fn reward_beneficiary<CTX: ContextTr>(
     } else {
         effective_gas_price
     };
+    U256::from(coinbase_gas_price * gas.used() as u128)
+}
 
-    // reward beneficiary
-    context.journal_mut().balance_incr(
-        beneficiary,
-        U256::from(coinbase_gas_price * gas.used() as u128),
-    )?;
-
-    Ok(())
+/// Returns the base fee of the transaction, burned by [`FeePolicy::MAINNET`]. Zero before
+/// London.
+///
+/// [`FeePolicy::MAINNET`]: context_interface::cfg::FeePolicy::MAINNET
+#[inline]
+pub fn burned_base_fee<CTX: ContextTr>(context: &CTX, gas: &Gas) -> U256 {
+    if !context.cfg().spec().into().is_enabled_in(SpecId::LONDON) {
+        return U256::ZERO;
+    }
+    let basefee = context.block().basefee() as u128;
+    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    U256::from(
+        basefee
+            .min(effective_gas_price)
+            .saturating_mul(gas.used() as u128),
+    )
 }
 
 /// Calculate last gas spent and transform internal reason to external.
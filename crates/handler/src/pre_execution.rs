@@ -7,16 +7,17 @@ use bytecode::Bytecode;
 use context_interface::transaction::{AccessListItemTr, AuthorizationTr};
 use context_interface::ContextTr;
 use context_interface::{
-    journaled_state::JournalTr,
+    cfg::CallerCodePolicy,
+    journaled_state::{AppliedDelegation, JournalTr},
     result::InvalidTransaction,
     transaction::{Transaction, TransactionType},
     Block, Cfg, Database,
 };
 use core::cmp::Ordering;
 use primitives::StorageKey;
-use primitives::{eip7702, hardfork::SpecId, KECCAK_EMPTY, U256};
+use primitives::{eip7702, hardfork::SpecId, Address, KECCAK_EMPTY, U256};
 use state::AccountInfo;
-use std::boxed::Box;
+use std::{boxed::Box, vec::Vec};
 
 /// Loads and warms accounts for execution, including precompiles and access list.
 pub fn load_accounts<
@@ -31,6 +32,10 @@ pub fn load_accounts<
     let spec = gen_spec.clone().into();
     // sets eth spec id in journal
     context.journal_mut().set_spec_id(spec);
+    let finalized_state_mode = context.cfg().finalized_state_mode();
+    context
+        .journal_mut()
+        .set_finalized_state_mode(finalized_state_mode);
     let precompiles_changed = precompiles.set_spec(gen_spec);
     let empty_warmed_precompiles = context.journal_mut().precompile_addresses().is_empty();
 
@@ -50,14 +55,25 @@ pub fn load_accounts<
     }
 
     // Load access list
+    let prefetch = context.cfg().is_access_list_prefetch_enabled();
     let (tx, journal) = context.tx_journal_mut();
     // legacy is only tx type that does not have access list.
     if tx.tx_type() != TransactionType::Legacy {
+        if prefetch {
+            // Accounts with slots are fetched together with their slots when they are warmed.
+            if let Some(access_list) = tx.access_list() {
+                let addresses: Vec<Address> = access_list
+                    .filter(|item| item.storage_slots().next().is_none())
+                    .map(|item| *item.address())
+                    .collect();
+                journal.prefetch_accounts(&addresses)?;
+            }
+        }
         if let Some(access_list) = tx.access_list() {
             for item in access_list {
                 let address = item.address();
                 let mut storage = item.storage_slots().peekable();
-                if storage.peek().is_none() {
+                if storage.peek().is_none() && !prefetch {
                     journal.warm_account(*address);
                 } else {
                     journal.warm_account_and_storage(
@@ -79,22 +95,63 @@ pub fn validate_account_nonce_and_code(
     tx_nonce: u64,
     is_eip3607_disabled: bool,
     is_nonce_check_disabled: bool,
+) -> Result<(), InvalidTransaction> {
+    if !is_eip3607_disabled {
+        validate_caller_code(caller_info, CallerCodePolicy::AllowDelegated)?;
+    }
+    validate_account_nonce(caller_info, tx_nonce, is_nonce_check_disabled)
+}
+
+/// Validates caller code according to EIP-3607 and the given [`CallerCodePolicy`].
+#[inline]
+pub fn validate_caller_code(
+    caller_info: &AccountInfo,
+    policy: CallerCodePolicy,
 ) -> Result<(), InvalidTransaction> {
     // EIP-3607: Reject transactions from senders with deployed code
     // This EIP is introduced after london but there was no collision in past
     // so we can leave it enabled always
-    if !is_eip3607_disabled {
-        let bytecode = match caller_info.code.as_ref() {
-            Some(code) => code,
-            None => &Bytecode::default(),
-        };
-        // Allow EOAs whose code is a valid delegation designation,
-        // i.e. 0xef0100 || address, to continue to originate transactions.
-        if !bytecode.is_empty() && !bytecode.is_eip7702() {
-            return Err(InvalidTransaction::RejectCallerWithCode);
-        }
+    if policy.is_disabled() {
+        return Ok(());
+    }
+    let bytecode = match caller_info.code.as_ref() {
+        Some(code) => code,
+        None => &Bytecode::default(),
+    };
+    if bytecode.is_empty() {
+        return Ok(());
+    }
+    // Allow EOAs whose code is a valid delegation designation,
+    // i.e. 0xef0100 || address, to continue to originate transactions.
+    if !bytecode.is_eip7702() {
+        return Err(InvalidTransaction::RejectCallerWithCode);
+    }
+    if policy == CallerCodePolicy::Strict {
+        return Err(InvalidTransaction::RejectDelegatedCaller);
     }
+    Ok(())
+}
+
+/// Validates caller account nonce and code according to EIP-3607 and the given
+/// [`CallerCodePolicy`].
+#[inline]
+pub fn validate_account_nonce_and_code_with_policy(
+    caller_info: &mut AccountInfo,
+    tx_nonce: u64,
+    caller_code_policy: CallerCodePolicy,
+    is_nonce_check_disabled: bool,
+) -> Result<(), InvalidTransaction> {
+    validate_caller_code(caller_info, caller_code_policy)?;
+    validate_account_nonce(caller_info, tx_nonce, is_nonce_check_disabled)
+}
 
+/// Validates that the transaction nonce is equal to the caller account nonce.
+#[inline]
+fn validate_account_nonce(
+    caller_info: &AccountInfo,
+    tx_nonce: u64,
+    is_nonce_check_disabled: bool,
+) -> Result<(), InvalidTransaction> {
     // Check that the transaction's nonce is correct
     if !is_nonce_check_disabled {
         let tx = tx_nonce;
@@ -123,19 +180,19 @@ pub fn validate_against_state_and_deduct_caller<
     let basefee = context.block().basefee() as u128;
     let blob_price = context.block().blob_gasprice().unwrap_or_default();
     let is_balance_check_disabled = context.cfg().is_balance_check_disabled();
-    let is_eip3607_disabled = context.cfg().is_eip3607_disabled();
-    let is_nonce_check_disabled = context.cfg().is_nonce_check_disabled();
+    let caller_code_policy = context.cfg().caller_code_policy();
+    let nonce_check_mode = context.cfg().nonce_check_mode();
 
     let (tx, journal) = context.tx_journal_mut();
 
     // Load caller's account.
     let caller_account = journal.load_account_code(tx.caller())?.data;
 
-    validate_account_nonce_and_code(
+    validate_account_nonce_and_code_with_policy(
         &mut caller_account.info,
         tx.nonce(),
-        is_eip3607_disabled,
-        is_nonce_check_disabled,
+        caller_code_policy,
+        !nonce_check_mode.is_strict(),
     )?;
 
     let max_balance_spending = tx.max_balance_spending()?;
@@ -173,12 +230,13 @@ pub fn validate_against_state_and_deduct_caller<
     caller_account.info.balance = new_balance;
 
     // Bump the nonce for calls. Nonce for CREATE will be bumped in `make_create_frame`.
-    if tx.kind().is_call() {
+    let bump_nonce = tx.kind().is_call() && nonce_check_mode.bumps_nonce();
+    if bump_nonce {
         // Nonce is already checked
         caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
     }
 
-    journal.caller_accounting_journal_entry(tx.caller(), old_balance, tx.kind().is_call());
+    journal.caller_accounting_journal_entry(tx.caller(), old_balance, bump_nonce);
     Ok(())
 }
 
@@ -197,11 +255,13 @@ pub fn apply_eip7702_auth_list<
     }
 
     let chain_id = context.cfg().chain_id();
+    let nonce_check_mode = context.cfg().nonce_check_mode();
     let (tx, journal) = context.tx_journal_mut();
 
     let mut refunded_accounts = 0;
     for authorization in tx.authorization_list() {
         // 1. Verify the chain id is either 0 or the chain's current ID.
+        // Chain id policy only applies to the transaction, authorizations are always strict.
         let auth_chain_id = authorization.chain_id();
         if !auth_chain_id.is_zero() && auth_chain_id != U256::from(chain_id) {
             continue;
@@ -231,7 +291,7 @@ pub fn apply_eip7702_auth_list<
         }
 
         // 6. Verify the nonce of `authority` is equal to `nonce`. In case `authority` does not exist in the trie, verify that `nonce` is equal to `0`.
-        if authorization.nonce() != authority_acc.info.nonce {
+        if nonce_check_mode.is_strict() && authorization.nonce() != authority_acc.info.nonce {
             continue;
         }
 
@@ -255,8 +315,14 @@ pub fn apply_eip7702_auth_list<
         authority_acc.info.code = Some(bytecode);
 
         // 9. Increase the nonce of `authority` by one.
-        authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
+        if nonce_check_mode.bumps_nonce() {
+            authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
+        }
         authority_acc.mark_touch();
+        journal.record_delegation(AppliedDelegation {
+            authority,
+            delegate: address,
+        });
     }
 
     let refunded_gas =
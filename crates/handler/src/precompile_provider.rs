@@ -1,12 +1,14 @@
 use auto_impl::auto_impl;
 use context::{Cfg, LocalContextTr};
 use context_interface::ContextTr;
+use core::fmt;
 use interpreter::{CallInput, Gas, InputsImpl, InstructionResult, InterpreterResult};
-use precompile::PrecompileError;
+use precompile::{PrecompileError, PrecompileResult, StatefulCall, StatefulPrecompile};
 use precompile::{PrecompileSpecId, Precompiles};
-use primitives::{hardfork::SpecId, Address, Bytes};
+use primitives::{hardfork::SpecId, Address, Bytes, HashMap};
 use std::boxed::Box;
 use std::string::String;
+use std::sync::Arc;
 
 /// Provider for precompiled contracts in the EVM.
 #[auto_impl(&mut, Box)]
@@ -102,53 +104,700 @@ impl<CTX: ContextTr> PrecompileProvider<CTX> for EthPrecompiles {
             return Ok(None);
         };
 
-        let mut result = InterpreterResult {
-            result: InstructionResult::Return,
-            gas: Gas::new(gas_limit),
-            output: Bytes::new(),
-        };
+        run_precompile(context, inputs, gas_limit, precompile).map(Some)
+    }
 
-        let r;
-        let input_bytes = match &inputs.input {
-            CallInput::SharedBuffer(range) => {
-                if let Some(slice) = context.local().shared_memory_buffer_slice(range.clone()) {
-                    r = slice;
-                    r.as_ref()
-                } else {
-                    &[]
-                }
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        self.warm_addresses()
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.contains(address)
+    }
+}
+
+/// Runs the precompile with the call input and converts its result into [`InterpreterResult`].
+///
+/// Fatal precompile errors are returned as an error.
+fn run_precompile<CTX: ContextTr>(
+    context: &mut CTX,
+    inputs: &InputsImpl,
+    gas_limit: u64,
+    precompile: impl FnOnce(&[u8], u64) -> PrecompileResult,
+) -> Result<InterpreterResult, String> {
+    let r;
+    let input_bytes = match &inputs.input {
+        CallInput::SharedBuffer(range) => {
+            if let Some(slice) = context.local().shared_memory_buffer_slice(range.clone()) {
+                r = slice;
+                r.as_ref()
+            } else {
+                &[]
             }
-            CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
+        }
+        CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
+    };
+
+    into_interpreter_result(precompile(input_bytes, gas_limit), gas_limit)
+}
+
+/// Converts the precompile result into [`InterpreterResult`].
+///
+/// Fatal precompile errors are returned as an error.
+fn into_interpreter_result(
+    output: PrecompileResult,
+    gas_limit: u64,
+) -> Result<InterpreterResult, String> {
+    let mut result = InterpreterResult {
+        result: InstructionResult::Return,
+        gas: Gas::new(gas_limit),
+        output: Bytes::new(),
+    };
+
+    match output {
+        Ok(output) => {
+            let underflow = result.gas.record_cost(output.gas_used);
+            assert!(underflow, "Gas underflow is not possible");
+            result.result = if output.reverted {
+                InstructionResult::Revert
+            } else {
+                InstructionResult::Return
+            };
+            result.output = output.bytes;
+        }
+        Err(PrecompileError::Fatal(e)) => return Err(e),
+        Err(e) => {
+            result.result = if e.is_oog() {
+                InstructionResult::PrecompileOOG
+            } else {
+                InstructionResult::PrecompileError
+            };
+        }
+    }
+    Ok(result)
+}
+
+/// [`PrecompileProvider`] that combines two providers, the first provider takes precedence.
+///
+/// An address is run by the first provider that contains it, so custom precompiles can be
+/// layered over [`EthPrecompiles`] and can override its addresses.
+#[derive(Clone, Debug, Default)]
+pub struct ChainedPrecompiles<First, Second> {
+    /// Provider that is checked first.
+    pub first: First,
+    /// Provider used for addresses that are not in the first provider.
+    pub second: Second,
+}
+
+impl<First, Second> ChainedPrecompiles<First, Second> {
+    /// Creates a new provider where `first` takes precedence over `second`.
+    pub fn new(first: First, second: Second) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<CTX, First, Second> PrecompileProvider<CTX> for ChainedPrecompiles<First, Second>
+where
+    CTX: ContextTr,
+    First: PrecompileProvider<CTX>,
+    Second: PrecompileProvider<CTX, Output = First::Output>,
+{
+    type Output = First::Output;
+
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        let first_changed = self.first.set_spec(spec.clone());
+        let second_changed = self.second.set_spec(spec);
+        first_changed || second_changed
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        inputs: &InputsImpl,
+        is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<Self::Output>, String> {
+        if self.first.contains(address) {
+            return self
+                .first
+                .run(context, address, inputs, is_static, gas_limit);
+        }
+        self.second
+            .run(context, address, inputs, is_static, gas_limit)
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        Box::new(
+            self.first.warm_addresses().chain(
+                self.second
+                    .warm_addresses()
+                    .filter(|address| !self.first.contains(address)),
+            ),
+        )
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.first.contains(address) || self.second.contains(address)
+    }
+}
+
+/// Precompile that is called through dynamic dispatch.
+pub type DynPrecompile = Arc<dyn Fn(&[u8], u64) -> PrecompileResult + Send + Sync>;
+
+/// [`PrecompileProvider`] with a precompile set that is configured at runtime.
+///
+/// Precompiles are closures or objects called through dynamic dispatch, so the set can be built
+/// from a chain configuration instead of being known at compile time. Calls are slightly slower
+/// than with [`EthPrecompiles`].
+///
+/// Every precompile has an activation spec, it is enabled from that spec on. Chain the provider
+/// before [`EthPrecompiles`] in [`ChainedPrecompiles`] to add or override precompiles of a spec.
+#[derive(Clone, Default)]
+pub struct DynPrecompileProvider {
+    precompiles: HashMap<Address, (SpecId, DynPrecompile)>,
+    /// Current spec.
+    spec: SpecId,
+    /// Whether the set changed since the last [`PrecompileProvider::set_spec`] call.
+    changed: bool,
+}
+
+impl fmt::Debug for DynPrecompileProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynPrecompileProvider")
+            .field("addresses", &self.precompiles.keys())
+            .field("spec", &self.spec)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DynPrecompileProvider {
+    /// Creates an empty provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a provider with the static precompiles.
+    pub fn from_precompiles(precompiles: &Precompiles) -> Self {
+        let mut provider = Self::new();
+        for (address, precompile) in precompiles.inner() {
+            provider.insert(*address, *precompile);
+        }
+        provider
+    }
+
+    /// Adds the precompile to the set and returns it.
+    pub fn with_precompile(
+        mut self,
+        address: Address,
+        precompile: impl Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    ) -> Self {
+        self.insert(address, precompile);
+        self
+    }
+
+    /// Adds the precompile that is enabled from the activation spec to the set and returns it.
+    pub fn with_activated_precompile(
+        mut self,
+        address: Address,
+        activation: SpecId,
+        precompile: impl Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    ) -> Self {
+        self.insert_activated(address, activation, precompile);
+        self
+    }
+
+    /// Inserts the precompile, returns the precompile that was at the address before.
+    pub fn insert(
+        &mut self,
+        address: Address,
+        precompile: impl Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    ) -> Option<DynPrecompile> {
+        self.insert_activated(address, SpecId::FRONTIER, precompile)
+    }
+
+    /// Inserts the precompile that is enabled from the activation spec, returns the precompile
+    /// that was at the address before.
+    pub fn insert_activated(
+        &mut self,
+        address: Address,
+        activation: SpecId,
+        precompile: impl Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    ) -> Option<DynPrecompile> {
+        self.changed = true;
+        self.precompiles
+            .insert(address, (activation, Arc::new(precompile)))
+            .map(|(_, precompile)| precompile)
+    }
+
+    /// Removes the precompile at the address.
+    pub fn remove(&mut self, address: &Address) -> Option<DynPrecompile> {
+        let removed = self.precompiles.remove(address);
+        self.changed |= removed.is_some();
+        removed.map(|(_, precompile)| precompile)
+    }
+
+    /// Returns the precompile at the address if it is enabled in the current spec.
+    pub fn get(&self, address: &Address) -> Option<&DynPrecompile> {
+        self.precompiles
+            .get(address)
+            .filter(|(activation, _)| self.spec.is_enabled_in(*activation))
+            .map(|(_, precompile)| precompile)
+    }
+
+    /// Returns the activation spec of the precompile at the address.
+    pub fn activation(&self, address: &Address) -> Option<SpecId> {
+        self.precompiles
+            .get(address)
+            .map(|(activation, _)| *activation)
+    }
+
+    /// Returns the number of precompiles, including the ones not enabled in the current spec.
+    pub fn len(&self) -> usize {
+        self.precompiles.len()
+    }
+
+    /// Returns `true` if there are no precompiles.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+}
+
+impl<CTX: ContextTr> PrecompileProvider<CTX> for DynPrecompileProvider {
+    type Output = InterpreterResult;
+
+    /// Returns `true` if the set changed since the last call or if the new spec enables a
+    /// different set of precompiles.
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        let spec = spec.into();
+        let activation_changed = spec != self.spec
+            && self.precompiles.values().any(|(activation, _)| {
+                self.spec.is_enabled_in(*activation) != spec.is_enabled_in(*activation)
+            });
+        self.spec = spec;
+        core::mem::take(&mut self.changed) || activation_changed
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        inputs: &InputsImpl,
+        _is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, String> {
+        let Some(precompile) = self.get(address) else {
+            return Ok(None);
         };
+        run_precompile(context, inputs, gas_limit, precompile.as_ref()).map(Some)
+    }
 
-        match (*precompile)(input_bytes, gas_limit) {
-            Ok(output) => {
-                let underflow = result.gas.record_cost(output.gas_used);
-                assert!(underflow, "Gas underflow is not possible");
-                result.result = if output.reverted {
-                    InstructionResult::Revert
-                } else {
-                    InstructionResult::Return
-                };
-                result.output = output.bytes;
-            }
-            Err(PrecompileError::Fatal(e)) => return Err(e),
-            Err(e) => {
-                result.result = if e.is_oog() {
-                    InstructionResult::PrecompileOOG
-                } else {
-                    InstructionResult::PrecompileError
-                };
-            }
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        Box::new(
+            self.precompiles
+                .iter()
+                .filter(|(_, (activation, _))| self.spec.is_enabled_in(*activation))
+                .map(|(address, _)| *address),
+        )
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.get(address).is_some()
+    }
+}
+
+/// Stateful precompile that is called through dynamic dispatch.
+pub type DynStatefulPrecompile<CTX> = Arc<dyn StatefulPrecompile<CTX>>;
+
+/// [`PrecompileProvider`] of [`StatefulPrecompile`]s that access the context.
+///
+/// Precompiles can read and write the state through the journal of the context, changes are
+/// reverted together with the call frame. Combine it with [`EthPrecompiles`] in
+/// [`ChainedPrecompiles`] to add stateful precompiles to a chain.
+pub struct StatefulPrecompiles<CTX> {
+    precompiles: HashMap<Address, DynStatefulPrecompile<CTX>>,
+    /// Whether the set changed since the last [`PrecompileProvider::set_spec`] call.
+    changed: bool,
+}
+
+impl<CTX> Clone for StatefulPrecompiles<CTX> {
+    fn clone(&self) -> Self {
+        Self {
+            precompiles: self.precompiles.clone(),
+            changed: self.changed,
+        }
+    }
+}
+
+impl<CTX> Default for StatefulPrecompiles<CTX> {
+    fn default() -> Self {
+        Self {
+            precompiles: HashMap::default(),
+            changed: false,
         }
-        Ok(Some(result))
+    }
+}
+
+impl<CTX> fmt::Debug for StatefulPrecompiles<CTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatefulPrecompiles")
+            .field("addresses", &self.precompiles.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<CTX> StatefulPrecompiles<CTX> {
+    /// Creates an empty provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the precompile to the set and returns it.
+    pub fn with_precompile(
+        mut self,
+        address: Address,
+        precompile: impl StatefulPrecompile<CTX> + 'static,
+    ) -> Self {
+        self.insert(address, precompile);
+        self
+    }
+
+    /// Inserts the precompile, returns the precompile that was at the address before.
+    pub fn insert(
+        &mut self,
+        address: Address,
+        precompile: impl StatefulPrecompile<CTX> + 'static,
+    ) -> Option<DynStatefulPrecompile<CTX>> {
+        self.changed = true;
+        self.precompiles.insert(address, Arc::new(precompile))
+    }
+
+    /// Removes the precompile at the address.
+    pub fn remove(&mut self, address: &Address) -> Option<DynStatefulPrecompile<CTX>> {
+        let removed = self.precompiles.remove(address);
+        self.changed |= removed.is_some();
+        removed
+    }
+
+    /// Returns the precompile at the address.
+    pub fn get(&self, address: &Address) -> Option<&DynStatefulPrecompile<CTX>> {
+        self.precompiles.get(address)
+    }
+
+    /// Returns the number of precompiles.
+    pub fn len(&self) -> usize {
+        self.precompiles.len()
+    }
+
+    /// Returns `true` if there are no precompiles.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+}
+
+impl<CTX: ContextTr> PrecompileProvider<CTX> for StatefulPrecompiles<CTX> {
+    type Output = InterpreterResult;
+
+    /// Returns `true` if the set changed since the last call, precompiles don't depend on the spec.
+    fn set_spec(&mut self, _spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        core::mem::take(&mut self.changed)
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        inputs: &InputsImpl,
+        is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, String> {
+        let Some(precompile) = self.precompiles.get(address) else {
+            return Ok(None);
+        };
+        // input is copied as the shared buffer can't be borrowed together with the context.
+        let input = inputs.input.bytes(context);
+        let call = StatefulCall {
+            input: &input,
+            gas_limit,
+            caller: inputs.caller_address,
+            target_address: inputs.target_address,
+            value: inputs.call_value,
+            is_static,
+        };
+        into_interpreter_result(precompile.call(context, &call), gas_limit).map(Some)
     }
 
     fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
-        self.warm_addresses()
+        Box::new(self.precompiles.keys().copied())
     }
 
     fn contains(&self, address: &Address) -> bool {
-        self.contains(address)
+        self.precompiles.contains_key(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MainnetContext;
+    use context::Context;
+    use database_interface::EmptyDB;
+    use precompile::PrecompileOutput;
+    use primitives::{address, bytes};
+
+    /// Provider with a single precompile that returns its address.
+    #[derive(Clone, Debug)]
+    struct Single(Address);
+
+    impl<CTX: ContextTr> PrecompileProvider<CTX> for Single {
+        type Output = InterpreterResult;
+
+        fn set_spec(&mut self, _spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+            false
+        }
+
+        fn run(
+            &mut self,
+            _context: &mut CTX,
+            address: &Address,
+            _inputs: &InputsImpl,
+            _is_static: bool,
+            gas_limit: u64,
+        ) -> Result<Option<InterpreterResult>, String> {
+            Ok((*address == self.0).then(|| InterpreterResult {
+                result: InstructionResult::Return,
+                gas: Gas::new(gas_limit),
+                output: Bytes::copy_from_slice(self.0.as_slice()),
+            }))
+        }
+
+        fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+            Box::new(core::iter::once(self.0))
+        }
+
+        fn contains(&self, address: &Address) -> bool {
+            *address == self.0
+        }
+    }
+
+    #[test]
+    fn test_chained_precompiles() {
+        let ecrecover = address!("0x0000000000000000000000000000000000000001");
+        let sha256 = address!("0x0000000000000000000000000000000000000002");
+        let custom = address!("0x0000000000000000000000000000000000000100");
+        let mut ctx: MainnetContext<EmptyDB> = Context::new(EmptyDB::new(), SpecId::default());
+
+        let mut precompiles = ChainedPrecompiles::new(
+            ChainedPrecompiles::new(Single(ecrecover), Single(custom)),
+            EthPrecompiles::default(),
+        );
+        assert!(PrecompileProvider::<MainnetContext<EmptyDB>>::set_spec(
+            &mut precompiles,
+            SpecId::BERLIN
+        ));
+        assert!(PrecompileProvider::<MainnetContext<EmptyDB>>::contains(
+            &precompiles,
+            &custom
+        ));
+        let warm: Vec<_> =
+            PrecompileProvider::<MainnetContext<EmptyDB>>::warm_addresses(&precompiles).collect();
+        assert_eq!(warm.len(), 10);
+        assert_eq!(warm[..2], [ecrecover, custom]);
+
+        let inputs = InputsImpl {
+            input: CallInput::Bytes(bytes!("0x00")),
+            ..Default::default()
+        };
+        let mut run = |address| {
+            precompiles
+                .run(&mut ctx, &address, &inputs, false, 100_000)
+                .unwrap()
+        };
+        // Overridden by the first provider.
+        assert_eq!(
+            run(ecrecover).unwrap().output,
+            Bytes::copy_from_slice(ecrecover.as_slice())
+        );
+        assert_eq!(
+            run(custom).unwrap().output,
+            Bytes::copy_from_slice(custom.as_slice())
+        );
+        assert_eq!(run(sha256).unwrap().output.len(), 32);
+        assert!(run(address!("0x0000000000000000000000000000000000000200")).is_none());
+    }
+
+    #[test]
+    fn test_dyn_precompiles_activation() {
+        let ecrecover = address!("0x0000000000000000000000000000000000000001");
+        let custom = address!("0x0000000000000000000000000000000000000100");
+        let mut ctx: MainnetContext<EmptyDB> = Context::new(EmptyDB::new(), SpecId::default());
+
+        fn echo(input: &[u8], _gas_limit: u64) -> PrecompileResult {
+            Ok(PrecompileOutput::new(10, Bytes::copy_from_slice(input)))
+        }
+        let mut precompiles = ChainedPrecompiles::new(
+            DynPrecompileProvider::new()
+                .with_activated_precompile(custom, SpecId::CANCUN, echo)
+                .with_activated_precompile(ecrecover, SpecId::PRAGUE, echo),
+            EthPrecompiles::default(),
+        );
+        type Provider = ChainedPrecompiles<DynPrecompileProvider, EthPrecompiles>;
+        let set_spec = |precompiles: &mut Provider, spec| {
+            PrecompileProvider::<MainnetContext<EmptyDB>>::set_spec(precompiles, spec)
+        };
+        let contains = |precompiles: &Provider, address| {
+            PrecompileProvider::<MainnetContext<EmptyDB>>::contains(precompiles, &address)
+        };
+        let warm_count = |precompiles: &Provider| {
+            PrecompileProvider::<MainnetContext<EmptyDB>>::warm_addresses(precompiles).count()
+        };
+
+        assert!(set_spec(&mut precompiles, SpecId::BERLIN));
+        assert!(!contains(&precompiles, custom));
+        assert_eq!(warm_count(&precompiles), 9);
+
+        assert!(set_spec(&mut precompiles, SpecId::CANCUN));
+        assert!(contains(&precompiles, custom));
+        assert_eq!(warm_count(&precompiles), 11);
+
+        let inputs = InputsImpl {
+            input: CallInput::Bytes(bytes!("0x0102")),
+            ..Default::default()
+        };
+        let mut run = |precompiles: &mut Provider, address| {
+            precompiles
+                .run(&mut ctx, &address, &inputs, false, 100_000)
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(run(&mut precompiles, custom).output, bytes!("0x0102"));
+        // Override is not active before Prague.
+        assert_eq!(run(&mut precompiles, ecrecover).output, Bytes::new());
+
+        assert!(set_spec(&mut precompiles, SpecId::PRAGUE));
+        assert_eq!(run(&mut precompiles, ecrecover).output, bytes!("0x0102"));
+
+        // Changed set has to be warmed again.
+        assert!(precompiles.first.remove(&custom).is_some());
+        assert!(set_spec(&mut precompiles, SpecId::PRAGUE));
+        assert!(!set_spec(&mut precompiles, SpecId::PRAGUE));
+        assert!(!contains(&precompiles, custom));
+    }
+
+    #[test]
+    fn test_dyn_precompiles() {
+        let custom = address!("0x0000000000000000000000000000000000000100");
+        let mut ctx: MainnetContext<EmptyDB> = Context::new(EmptyDB::new(), SpecId::default());
+
+        let cost = 15;
+        let mut precompiles = DynPrecompileProvider::from_precompiles(Precompiles::berlin())
+            .with_precompile(custom, move |input: &[u8], gas_limit| {
+                if cost > gas_limit {
+                    return Err(PrecompileError::OutOfGas);
+                }
+                Ok(PrecompileOutput::new(cost, Bytes::copy_from_slice(input)))
+            });
+        assert_eq!(precompiles.len(), 10);
+        assert!(PrecompileProvider::<MainnetContext<EmptyDB>>::set_spec(
+            &mut precompiles,
+            SpecId::BERLIN
+        ));
+        assert!(!PrecompileProvider::<MainnetContext<EmptyDB>>::set_spec(
+            &mut precompiles,
+            SpecId::PRAGUE
+        ));
+
+        let inputs = InputsImpl {
+            input: CallInput::Bytes(bytes!("0x0102")),
+            ..Default::default()
+        };
+        let result = precompiles
+            .run(&mut ctx, &custom, &inputs, false, 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.output, bytes!("0x0102"));
+        assert_eq!(result.gas.spent(), cost);
+        let result = precompiles
+            .run(&mut ctx, &custom, &inputs, false, 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileOOG);
+
+        // Changed set has to be warmed again.
+        assert!(precompiles.remove(&custom).is_some());
+        assert!(PrecompileProvider::<MainnetContext<EmptyDB>>::set_spec(
+            &mut precompiles,
+            SpecId::PRAGUE
+        ));
+        assert!(!PrecompileProvider::<MainnetContext<EmptyDB>>::contains(
+            &precompiles,
+            &custom
+        ));
+        assert!(precompiles
+            .run(&mut ctx, &custom, &inputs, false, 100)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_stateful_precompiles() {
+        use crate::{ExecuteEvm, MainBuilder, MainContext};
+        use context::{JournalTr, TxEnv};
+        use database::BenchmarkDB;
+        use primitives::{Log, TxKind, U256};
+
+        type Ctx = MainnetContext<BenchmarkDB>;
+        let stateful = address!("0x0000000000000000000000000000000000000100");
+
+        // stores the first input byte in slot zero and logs it, reverts if the byte is 0xff.
+        let store = |context: &mut Ctx, call: &StatefulCall<'_>| {
+            if call.is_static {
+                return Err(PrecompileError::StateChangeDuringStaticCall);
+            }
+            let value = U256::from(call.input[0]);
+            context
+                .journal_mut()
+                .sstore(call.target_address, U256::ZERO, value)
+                .map_err(|e| PrecompileError::Fatal(e.to_string()))?;
+            context.journal_mut().log(Log::new_unchecked(
+                call.target_address,
+                Vec::new(),
+                Bytes::copy_from_slice(&call.input[..1]),
+            ));
+            if call.input[0] == 0xff {
+                return Ok(PrecompileOutput::new_reverted(100, Bytes::new()));
+            }
+            Ok(PrecompileOutput::new(100, Bytes::new()))
+        };
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::default())
+            .build_mainnet()
+            .with_precompiles(ChainedPrecompiles::new(
+                StatefulPrecompiles::new().with_precompile(stateful, store),
+                EthPrecompiles::default(),
+            ));
+        let tx = |nonce, data: Bytes| {
+            TxEnv::builder_for_bench()
+                .nonce(nonce)
+                .kind(TxKind::Call(stateful))
+                .data(data)
+                .build_fill()
+        };
+
+        let output = evm.transact(tx(0, bytes!("0x2a"))).unwrap();
+        assert!(output.result.is_success());
+        assert_eq!(output.result.logs().len(), 1);
+        assert_eq!(
+            output.state[&stateful].storage[&U256::ZERO].present_value(),
+            U256::from(0x2a)
+        );
+
+        let output = evm.transact(tx(0, bytes!("0xff"))).unwrap();
+        assert!(!output.result.is_success());
+        assert!(output.result.logs().is_empty());
+        assert!(!output.state[&stateful]
+            .storage
+            .values()
+            .any(|slot| slot.is_changed()));
     }
 }
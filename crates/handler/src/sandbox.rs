@@ -0,0 +1,218 @@
+//! Execution of untrusted bytecode within the limits of a [`SandboxProfile`].
+//!
+//! The transaction is executed with fuel, and between the steps the instruction count, memory
+//! and the cancellation token are checked. Execution that crosses a limit is aborted and its
+//! changes are discarded.
+use crate::{ExecuteFuelEvm, FuelProgress};
+use context::{ContextTr, Evm, LocalContextTr, SandboxProfile};
+
+/// Outcome of a sandboxed execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SandboxOutcome<T> {
+    /// Transaction is done.
+    Done(T),
+    /// Transaction executed the maximum number of instructions and was aborted.
+    StepLimitReached,
+    /// Memory of the running frames exceeded the limit and the transaction was aborted.
+    MemoryLimitReached {
+        /// Memory of the running frames in bytes.
+        memory: usize,
+    },
+    /// Cancellation token was cancelled and the transaction was aborted.
+    Cancelled,
+}
+
+impl<T> SandboxOutcome<T> {
+    /// Returns `true` if the transaction is done.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Done(_))
+    }
+
+    /// Returns the output if the transaction is done.
+    pub fn done(self) -> Option<T> {
+        match self {
+            Self::Done(output) => Some(output),
+            _ => None,
+        }
+    }
+}
+
+/// Execution of transactions within the limits of a [`SandboxProfile`].
+///
+/// Configuration part of the profile is applied with [`CfgEnv::with_sandbox`].
+///
+/// [`CfgEnv::with_sandbox`]: context::CfgEnv::with_sandbox
+pub trait ExecuteSandboxEvm: ExecuteFuelEvm {
+    /// Executes the transaction until it is done or crosses a limit of the profile.
+    ///
+    /// State is stored inside the journal like with [`ExecuteEvm::transact_one`], aborted
+    /// transactions leave no changes.
+    ///
+    /// [`ExecuteEvm::transact_one`]: crate::ExecuteEvm::transact_one
+    fn transact_sandboxed(
+        &mut self,
+        tx: Self::Tx,
+        profile: &SandboxProfile,
+    ) -> Result<SandboxOutcome<Self::ExecutionResult>, Self::Error>;
+}
+
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> ExecuteSandboxEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
+where
+    Self: ExecuteFuelEvm,
+    CTX: ContextTr,
+{
+    fn transact_sandboxed(
+        &mut self,
+        tx: Self::Tx,
+        profile: &SandboxProfile,
+    ) -> Result<SandboxOutcome<Self::ExecutionResult>, Self::Error> {
+        if profile.cancellation.is_cancelled() {
+            return Ok(SandboxOutcome::Cancelled);
+        }
+        let mut steps = 0;
+        let fuel = |steps: u64| {
+            profile
+                .steps_per_check
+                .max(1)
+                .min(profile.max_steps - steps)
+        };
+        let mut progress = self.transact_with_fuel(tx, fuel(steps))?;
+        loop {
+            let continuation = match progress {
+                FuelProgress::Done(result) => return Ok(SandboxOutcome::Done(result)),
+                FuelProgress::Yielded(continuation) => continuation,
+            };
+            steps += fuel(steps);
+            let memory = self.ctx.local().shared_memory_buffer().borrow().len();
+            let outcome = if memory > profile.memory_limit {
+                SandboxOutcome::MemoryLimitReached { memory }
+            } else if profile.cancellation.is_cancelled() {
+                SandboxOutcome::Cancelled
+            } else if steps >= profile.max_steps {
+                SandboxOutcome::StepLimitReached
+            } else {
+                progress = self.resume(continuation, fuel(steps))?;
+                continue;
+            };
+            self.abort(continuation);
+            return Ok(outcome);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{
+        result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction},
+        CancellationToken, CfgEnv, Context, TxEnv,
+    };
+    use database::{BenchmarkDB, BENCH_TARGET};
+    use primitives::{Address, TxKind, U256};
+
+    fn run(code: &[u8], profile: &SandboxProfile) -> SandboxOutcome<ExecutionResult> {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                code.to_vec().into(),
+            )))
+            .with_cfg(CfgEnv::new().with_sandbox(profile))
+            .build_mainnet();
+        // Fake caller that doesn't exist in the database.
+        let tx = TxEnv::builder()
+            .caller(Address::with_last_byte(0x42))
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_price(0)
+            .gas_limit(1_000_000)
+            .build()
+            .unwrap();
+        evm.transact_sandboxed(tx, profile).unwrap()
+    }
+
+    #[test]
+    fn test_sandbox_done() {
+        let outcome = run(
+            &[opcode::PUSH0, opcode::PUSH0, opcode::SSTORE],
+            &SandboxProfile::new(),
+        );
+        assert!(outcome.done().unwrap().is_success());
+    }
+
+    #[test]
+    fn test_sandbox_step_limit() {
+        // Infinite loop.
+        let code = [opcode::JUMPDEST, opcode::PUSH0, opcode::JUMP];
+        let profile = SandboxProfile::new()
+            .with_max_steps(1_000)
+            .with_steps_per_check(100);
+        assert_eq!(run(&code, &profile), SandboxOutcome::StepLimitReached);
+    }
+
+    #[test]
+    fn test_sandbox_memory_limit() {
+        // mstore(0x10000, 0) grows the memory past the limit.
+        let code = [
+            opcode::PUSH0,
+            opcode::PUSH3,
+            0x01,
+            0x00,
+            0x00,
+            opcode::MSTORE,
+        ];
+        let profile = SandboxProfile::new()
+            .with_memory_limit(0x1000)
+            .with_steps_per_check(1);
+        assert!(matches!(
+            run(&code, &profile),
+            SandboxOutcome::MemoryLimitReached { memory } if memory > 0x1000
+        ));
+    }
+
+    #[test]
+    fn test_sandbox_cancelled() {
+        let token = CancellationToken::new();
+        let profile = SandboxProfile::new().with_cancellation(token.clone());
+        token.cancel();
+        assert_eq!(run(&[opcode::STOP], &profile), SandboxOutcome::Cancelled);
+    }
+
+    #[test]
+    fn test_sandbox_disabled_opcode() {
+        let code = [opcode::PUSH0, opcode::SELFDESTRUCT];
+        let outcome = run(&code, &SandboxProfile::new()).done().unwrap();
+        assert!(matches!(
+            outcome,
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeDisabled,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_sandbox_fake_caller_value() {
+        let profile = SandboxProfile::new();
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::STOP].into(),
+            )))
+            .with_cfg(CfgEnv::new().with_sandbox(&profile))
+            .build_mainnet();
+        // Fake accounts have no value to transfer.
+        let tx = TxEnv::builder()
+            .caller(Address::with_last_byte(0x42))
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_price(0)
+            .value(U256::from(1))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            evm.transact_sandboxed(tx, &profile),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+    }
+}
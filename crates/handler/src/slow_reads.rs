@@ -0,0 +1,62 @@
+//! Slowest database reads of executed transactions.
+use crate::{EvmTr, ExecuteEvm};
+use context::{result::ExecResultAndState, ContextTr};
+use database_interface::SlowReads;
+
+/// Execution of a transaction that reports the slowest reads of the database.
+///
+/// Requires a database that records slow reads, like `LatencyWatchdog` of the database crate.
+pub trait ExecuteSlowReadsEvm: ExecuteEvm {
+    /// Executes and finalizes the transaction like [`ExecuteEvm::transact`] and sets
+    /// [`ExecResultAndState::slow_reads`].
+    ///
+    /// Reads recorded before the transaction are discarded. Reads served from caches of the
+    /// context are not reported, only reads that reach the measured database are.
+    fn transact_with_slow_reads(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error>;
+}
+
+impl<EVM> ExecuteSlowReadsEvm for EVM
+where
+    EVM: ExecuteEvm + EvmTr<Context: ContextTr<Db: SlowReads>>,
+{
+    fn transact_with_slow_reads(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
+        self.ctx().db_mut().take_slow_reads();
+        let output = self.transact(tx)?;
+        let slow_reads = self.ctx().db_mut().take_slow_reads();
+        Ok(output.with_slow_reads(slow_reads))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
+    use context::{Context, TxEnv};
+    use core::time::Duration;
+    use database::{BenchmarkDB, LatencyWatchdog};
+
+    #[test]
+    fn test_transact_with_slow_reads() {
+        let db = BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            [opcode::PUSH0, opcode::SLOAD, opcode::STOP].into(),
+        ));
+        let mut evm = Context::mainnet()
+            .with_db(LatencyWatchdog::new(db, Duration::ZERO).with_max_reports(2))
+            .build_mainnet();
+
+        let output = evm
+            .transact_with_slow_reads(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        assert!(output.result.is_success());
+        let slow_reads = output.slow_reads.unwrap();
+        assert_eq!(slow_reads.len(), 2);
+        assert!(slow_reads[0].duration >= slow_reads[1].duration);
+    }
+}
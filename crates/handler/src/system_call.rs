@@ -20,12 +20,12 @@
 //!
 //! See the book section on [External State Transitions](../../book/src/external_state_transitions.md) for more details.
 use crate::{
-    frame::EthFrame, instructions::InstructionProvider, ExecuteCommitEvm, ExecuteEvm, Handler,
-    MainnetHandler, PrecompileProvider,
+    instructions::InstructionProvider, EvmFrameTr, ExecuteCommitEvm, ExecuteEvm, FrameResult,
+    Handler, MainnetHandler, PrecompileProvider,
 };
 use context::{result::ExecResultAndState, ContextSetters, ContextTr, Evm, JournalTr, TxEnv};
 use database_interface::DatabaseCommit;
-use interpreter::{interpreter::EthInterpreter, InterpreterResult};
+use interpreter::interpreter_action::FrameInit;
 use primitives::{address, Address, Bytes, TxKind};
 use state::EvmState;
 
@@ -203,12 +203,12 @@ pub trait SystemCallCommitEvm: SystemCallEvm + ExecuteCommitEvm {
     }
 }
 
-impl<CTX, INSP, INST, PRECOMPILES> SystemCallEvm
-    for Evm<CTX, INSP, INST, PRECOMPILES, EthFrame<EthInterpreter>>
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> SystemCallEvm for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
 where
     CTX: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SystemCallTx> + ContextSetters,
-    INST: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
-    PRECOMPILES: PrecompileProvider<CTX, Output = InterpreterResult>,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
 {
     fn system_call_one(
         &mut self,
@@ -227,13 +227,14 @@ where
     }
 }
 
-impl<CTX, INSP, INST, PRECOMPILES> SystemCallCommitEvm
-    for Evm<CTX, INSP, INST, PRECOMPILES, EthFrame<EthInterpreter>>
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> SystemCallCommitEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
 where
     CTX: ContextTr<Journal: JournalTr<State = EvmState>, Db: DatabaseCommit, Tx: SystemCallTx>
         + ContextSetters,
-    INST: InstructionProvider<Context = CTX, InterpreterTypes = EthInterpreter>,
-    PRECOMPILES: PrecompileProvider<CTX, Output = InterpreterResult>,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
 {
     fn system_call_with_caller_commit(
         &mut self,
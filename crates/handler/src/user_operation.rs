@@ -0,0 +1,1065 @@
+//! Simulation of ERC-4337 `handleOps` bundles against the v0.7 entry point.
+//!
+//! [`UserOperationEvm::simulate_handle_ops`] runs the phases the entry point would run, as calls
+//! from the entry point address: validation of every user operation with `validateUserOp` and
+//! `validatePaymasterUserOp`, followed by the execution and `postOp` of every valid one.
+//!
+//! The entry point code is not executed, so the simulation does not need it to be deployed:
+//!
+//! * Deposits are read from the storage of the entry point, using the `StakeManager` layout. The
+//!   prefund an account pays during validation is the balance the entry point receives.
+//! * Gas refunds to deposits and the payment of the beneficiary are not applied to the state.
+//! * Accounts and paymasters must be deployed, `initCode` and aggregators are not supported.
+//! * Nonces are not validated.
+//!
+//! Operations that fail validation are reported and skipped, where the entry point would revert
+//! the whole bundle.
+use crate::{
+    instructions::InstructionProvider, EvmFrameTr, EvmTr, ExecuteEvm, FrameResult, Handler,
+    MainnetHandler, PrecompileProvider,
+};
+use context::{
+    result::{EVMError, ExecutionResult},
+    Block, Cfg, ContextError, ContextSetters, ContextTr, Evm, JournalTr, LocalContextTr, TxEnv,
+};
+use core::fmt;
+use interpreter::{interpreter_action::FrameInit, InitialAndFloorGas};
+use primitives::{address, keccak256, Address, Bytes, Log, TxKind, B256, U256};
+use state::EvmState;
+use std::vec::Vec;
+
+/// Address of the ERC-4337 entry point v0.7.
+pub const ENTRY_POINT_V07: Address = address!("0x0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// Selector of `validateUserOp(PackedUserOperation,bytes32,uint256)`.
+const VALIDATE_USER_OP_SELECTOR: [u8; 4] = [0x19, 0x82, 0x2f, 0x7c];
+/// Selector of `validatePaymasterUserOp(PackedUserOperation,bytes32,uint256)`.
+const VALIDATE_PAYMASTER_USER_OP_SELECTOR: [u8; 4] = [0x52, 0xb7, 0x51, 0x2c];
+/// Selector of `postOp(uint8,bytes,uint256,uint256)`.
+const POST_OP_SELECTOR: [u8; 4] = [0x7c, 0x62, 0x7b, 0x21];
+/// Selector of `executeUserOp(PackedUserOperation,bytes32)`.
+const EXECUTE_USER_OP_SELECTOR: [u8; 4] = [0x8d, 0xd7, 0x71, 0x2f];
+
+/// Percentage of the unused execution gas that is charged.
+const UNUSED_GAS_PENALTY_PERCENT: u64 = 10;
+
+/// Paymaster that sponsors a [`UserOperation`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paymaster {
+    /// Address of the paymaster.
+    pub address: Address,
+    /// Gas limit of `validatePaymasterUserOp`.
+    pub verification_gas_limit: u64,
+    /// Gas limit of `postOp`.
+    pub post_op_gas_limit: u64,
+    /// Data passed to the paymaster.
+    pub data: Bytes,
+}
+
+/// ERC-4337 user operation of an already deployed account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserOperation {
+    /// Account that executes the operation.
+    pub sender: Address,
+    /// Nonce of the operation, with the key in the upper 192 bits.
+    pub nonce: U256,
+    /// Data the account is called with.
+    pub call_data: Bytes,
+    /// Gas limit of the execution.
+    pub call_gas_limit: u64,
+    /// Gas limit of `validateUserOp`.
+    pub verification_gas_limit: u64,
+    /// Gas paid for the bundle overhead and calldata.
+    pub pre_verification_gas: u64,
+    /// Maximum fee per gas.
+    pub max_fee_per_gas: u128,
+    /// Maximum priority fee per gas.
+    pub max_priority_fee_per_gas: u128,
+    /// Paymaster that pays for the operation, `None` if the account pays.
+    pub paymaster: Option<Paymaster>,
+    /// Signature validated by the account.
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Returns the `paymasterAndData` field of the packed operation.
+    pub fn paymaster_and_data(&self) -> Bytes {
+        let Some(paymaster) = &self.paymaster else {
+            return Bytes::new();
+        };
+        let mut data = Vec::with_capacity(52 + paymaster.data.len());
+        data.extend_from_slice(paymaster.address.as_slice());
+        data.extend_from_slice(&u128::from(paymaster.verification_gas_limit).to_be_bytes());
+        data.extend_from_slice(&u128::from(paymaster.post_op_gas_limit).to_be_bytes());
+        data.extend_from_slice(&paymaster.data);
+        data.into()
+    }
+
+    /// Returns the sum of all gas limits of the operation.
+    pub fn total_gas_limit(&self) -> u64 {
+        let paymaster = self.paymaster.as_ref().map_or(0, |paymaster| {
+            paymaster
+                .verification_gas_limit
+                .saturating_add(paymaster.post_op_gas_limit)
+        });
+        self.verification_gas_limit
+            .saturating_add(self.call_gas_limit)
+            .saturating_add(self.pre_verification_gas)
+            .saturating_add(paymaster)
+    }
+
+    /// Returns the prefund the account or the paymaster must have deposited.
+    pub fn required_prefund(&self) -> U256 {
+        U256::from(self.total_gas_limit()) * U256::from(self.max_fee_per_gas)
+    }
+
+    /// Returns the fee per gas paid with the block base fee.
+    pub fn gas_price(&self, basefee: u64) -> u128 {
+        self.max_fee_per_gas.min(
+            self.max_priority_fee_per_gas
+                .saturating_add(basefee as u128),
+        )
+    }
+
+    /// Returns the hash signed by the account.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let mut packed = Vec::with_capacity(8 * 32);
+        push_address(&mut packed, self.sender);
+        packed.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+        packed.extend_from_slice(keccak256([]).as_slice());
+        packed.extend_from_slice(keccak256(&self.call_data).as_slice());
+        packed.extend_from_slice(&self.account_gas_limits());
+        push_u64(&mut packed, self.pre_verification_gas);
+        packed.extend_from_slice(&self.gas_fees());
+        packed.extend_from_slice(keccak256(self.paymaster_and_data()).as_slice());
+
+        let mut data = Vec::with_capacity(3 * 32);
+        data.extend_from_slice(keccak256(packed).as_slice());
+        push_address(&mut data, entry_point);
+        push_u64(&mut data, chain_id);
+        keccak256(data)
+    }
+
+    fn account_gas_limits(&self) -> [u8; 32] {
+        pack_u128(
+            self.verification_gas_limit as u128,
+            self.call_gas_limit as u128,
+        )
+    }
+
+    fn gas_fees(&self) -> [u8; 32] {
+        pack_u128(self.max_priority_fee_per_gas, self.max_fee_per_gas)
+    }
+
+    /// ABI encodes the operation as the `PackedUserOperation` tuple.
+    fn abi_encode(&self) -> Vec<u8> {
+        let tails = [
+            Bytes::new(),
+            self.call_data.clone(),
+            self.paymaster_and_data(),
+            self.signature.clone(),
+        ];
+        let mut offsets = [0usize; 4];
+        let mut offset = 9 * 32;
+        for (i, tail) in tails.iter().enumerate() {
+            offsets[i] = offset;
+            offset += 32 + tail.len().next_multiple_of(32);
+        }
+
+        let mut data = Vec::with_capacity(offset);
+        push_address(&mut data, self.sender);
+        data.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+        push_u64(&mut data, offsets[0] as u64);
+        push_u64(&mut data, offsets[1] as u64);
+        data.extend_from_slice(&self.account_gas_limits());
+        push_u64(&mut data, self.pre_verification_gas);
+        data.extend_from_slice(&self.gas_fees());
+        push_u64(&mut data, offsets[2] as u64);
+        push_u64(&mut data, offsets[3] as u64);
+        for tail in &tails {
+            push_bytes(&mut data, tail);
+        }
+        data
+    }
+
+    /// Returns calldata of a validation call, `validateUserOp` or `validatePaymasterUserOp`.
+    fn validation_call(&self, selector: [u8; 4], hash: B256, amount: U256) -> Bytes {
+        let mut data = selector.to_vec();
+        push_u64(&mut data, 3 * 32);
+        data.extend_from_slice(hash.as_slice());
+        data.extend_from_slice(&amount.to_be_bytes::<32>());
+        data.extend(self.abi_encode());
+        data.into()
+    }
+
+    /// Returns calldata the account is executed with.
+    ///
+    /// Call data starting with the `executeUserOp` selector is replaced with the call of
+    /// `executeUserOp(userOp, userOpHash)`.
+    fn execution_call(&self, hash: B256) -> Bytes {
+        if !self.call_data.starts_with(&EXECUTE_USER_OP_SELECTOR) {
+            return self.call_data.clone();
+        }
+        let mut data = EXECUTE_USER_OP_SELECTOR.to_vec();
+        push_u64(&mut data, 2 * 32);
+        data.extend_from_slice(hash.as_slice());
+        data.extend(self.abi_encode());
+        data.into()
+    }
+}
+
+/// Validation data returned by `validateUserOp` and `validatePaymasterUserOp`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationData {
+    /// Zero if the signature is valid, one if it is not, otherwise the aggregator.
+    pub authorizer: Address,
+    /// Timestamp the operation is valid until, zero if it does not expire.
+    pub valid_until: u64,
+    /// Timestamp the operation is valid from.
+    pub valid_after: u64,
+}
+
+impl ValidationData {
+    /// Unpacks the validation data.
+    pub fn from_word(word: U256) -> Self {
+        let bytes = word.to_be_bytes::<32>();
+        let timestamp = |range: core::ops::Range<usize>| {
+            bytes[range]
+                .iter()
+                .fold(0u64, |value, byte| (value << 8) | *byte as u64)
+        };
+        Self {
+            authorizer: Address::from_slice(&bytes[12..]),
+            valid_until: timestamp(6..12),
+            valid_after: timestamp(0..6),
+        }
+    }
+
+    /// Returns `true` if the signature is invalid.
+    pub fn is_signature_failure(&self) -> bool {
+        self.authorizer == Address::with_last_byte(1)
+    }
+
+    /// Returns `true` if the operation is valid at the timestamp.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        let valid_until = if self.valid_until == 0 {
+            u64::MAX
+        } else {
+            self.valid_until
+        };
+        self.valid_after <= timestamp && timestamp <= valid_until
+    }
+}
+
+/// Reason a user operation failed validation, with the entry point error code.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UserOperationError {
+    /// `AA20`, sender has no code.
+    AccountNotDeployed,
+    /// `AA21`, deposit of the account does not cover the prefund after validation.
+    PrefundNotPaid {
+        /// Required prefund.
+        required: U256,
+        /// Deposit after validation.
+        deposit: U256,
+    },
+    /// `AA22`, operation is expired or not due.
+    Expired(ValidationData),
+    /// `AA23`, `validateUserOp` reverted, halted or returned invalid data.
+    AccountReverted(Bytes),
+    /// `AA24`, signature of the account is invalid.
+    SignatureFailure,
+    /// `AA30`, paymaster has no code.
+    PaymasterNotDeployed,
+    /// `AA31`, deposit of the paymaster does not cover the prefund.
+    PaymasterDepositTooLow {
+        /// Required prefund.
+        required: U256,
+        /// Deposit of the paymaster.
+        deposit: U256,
+    },
+    /// `AA32`, paymaster validation is expired or not due.
+    PaymasterExpired(ValidationData),
+    /// `AA33`, `validatePaymasterUserOp` reverted, halted or returned invalid data.
+    PaymasterReverted(Bytes),
+    /// `AA34`, signature of the paymaster is invalid.
+    PaymasterSignatureFailure,
+    /// Account returned an aggregator, which is not supported.
+    UnsupportedAggregator(Address),
+}
+
+impl UserOperationError {
+    /// Returns the error code of the entry point.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AccountNotDeployed => "AA20",
+            Self::PrefundNotPaid { .. } => "AA21",
+            Self::Expired(_) => "AA22",
+            Self::AccountReverted(_) => "AA23",
+            Self::SignatureFailure | Self::UnsupportedAggregator(_) => "AA24",
+            Self::PaymasterNotDeployed => "AA30",
+            Self::PaymasterDepositTooLow { .. } => "AA31",
+            Self::PaymasterExpired(_) => "AA32",
+            Self::PaymasterReverted(_) => "AA33",
+            Self::PaymasterSignatureFailure => "AA34",
+        }
+    }
+}
+
+impl core::error::Error for UserOperationError {}
+
+impl fmt::Display for UserOperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = self.code();
+        match self {
+            Self::AccountNotDeployed => write!(f, "{code} account not deployed"),
+            Self::PrefundNotPaid { required, deposit } => {
+                write!(
+                    f,
+                    "{code} didn't pay prefund, required {required}, deposit {deposit}"
+                )
+            }
+            Self::Expired(_) => write!(f, "{code} expired or not due"),
+            Self::AccountReverted(output) => write!(f, "{code} reverted: {output}"),
+            Self::SignatureFailure => write!(f, "{code} signature error"),
+            Self::PaymasterNotDeployed => write!(f, "{code} paymaster not deployed"),
+            Self::PaymasterDepositTooLow { required, deposit } => {
+                write!(
+                    f,
+                    "{code} paymaster deposit too low, required {required}, deposit {deposit}"
+                )
+            }
+            Self::PaymasterExpired(_) => write!(f, "{code} paymaster expired or not due"),
+            Self::PaymasterReverted(output) => write!(f, "{code} paymaster reverted: {output}"),
+            Self::PaymasterSignatureFailure => write!(f, "{code} paymaster signature error"),
+            Self::UnsupportedAggregator(aggregator) => {
+                write!(f, "{code} unsupported aggregator {aggregator}")
+            }
+        }
+    }
+}
+
+/// Outcome of a user operation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UserOperationStatus {
+    /// Operation failed validation and was not executed.
+    Invalid(UserOperationError),
+    /// Execution and `postOp` succeeded.
+    Succeeded,
+    /// Execution reverted or halted with the output, `postOp` succeeded.
+    Reverted(Bytes),
+    /// `postOp` reverted or halted with the output, state changes of the execution are reverted.
+    PostOpReverted(Bytes),
+}
+
+/// Gas used by a user operation, split by phase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserOperationGas {
+    /// Pre-verification gas of the operation.
+    pub pre_verification: u64,
+    /// Gas used by `validateUserOp`.
+    pub verification: u64,
+    /// Gas used by `validatePaymasterUserOp`.
+    pub paymaster_verification: u64,
+    /// Gas used by the execution.
+    pub execution: u64,
+    /// Gas used by `postOp`.
+    pub post_op: u64,
+    /// Penalty for the unused gas of the execution and `postOp` gas limits.
+    pub unused_gas_penalty: u64,
+}
+
+impl UserOperationGas {
+    /// Returns the gas the operation is charged for.
+    pub fn total(&self) -> u64 {
+        self.pre_verification
+            + self.verification
+            + self.paymaster_verification
+            + self.execution
+            + self.post_op
+            + self.unused_gas_penalty
+    }
+}
+
+/// Simulated outcome of a user operation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserOperationResult {
+    /// Hash of the operation.
+    pub hash: B256,
+    /// Outcome of the operation.
+    pub status: UserOperationStatus,
+    /// Gas used, execution gas is zero for invalid operations.
+    pub gas: UserOperationGas,
+    /// Prefund required from the account or the paymaster.
+    pub required_prefund: U256,
+    /// Cost charged to the account or the paymaster, zero for invalid operations.
+    pub actual_gas_cost: U256,
+    /// Logs of the execution and `postOp`.
+    pub logs: Vec<Log>,
+}
+
+impl UserOperationResult {
+    /// Returns `true` if the operation passed validation.
+    pub fn is_valid(&self) -> bool {
+        !matches!(self.status, UserOperationStatus::Invalid(_))
+    }
+}
+
+/// API for simulating ERC-4337 bundles.
+pub trait UserOperationEvm: ExecuteEvm {
+    /// Simulates `handleOps` of the entry point with the operations and returns the result of
+    /// each of them.
+    ///
+    /// State changes stay in the journal as with [`ExecuteEvm::transact_one`], use
+    /// [`ExecuteEvm::finalize`] to obtain them.
+    fn simulate_handle_ops(
+        &mut self,
+        entry_point: Address,
+        ops: &[UserOperation],
+    ) -> Result<Vec<UserOperationResult>, Self::Error>;
+}
+
+/// Operation that passed validation.
+struct ValidatedOperation {
+    hash: B256,
+    gas: UserOperationGas,
+    /// Context returned by the paymaster, `postOp` is called if it is not empty.
+    paymaster_context: Bytes,
+}
+
+type EvmError<CTX> = EVMError<<<CTX as ContextTr>::Db as context::Database>::Error>;
+
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> UserOperationEvm
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
+where
+    CTX: ContextTr<Journal: JournalTr<State = EvmState>, Tx = TxEnv> + ContextSetters,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
+{
+    fn simulate_handle_ops(
+        &mut self,
+        entry_point: Address,
+        ops: &[UserOperation],
+    ) -> Result<Vec<UserOperationResult>, Self::Error> {
+        let validated = ops
+            .iter()
+            .map(|op| self.validate_user_op(entry_point, op))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let basefee = self.ctx.block().basefee();
+        let mut results = Vec::with_capacity(ops.len());
+        for (op, validated) in ops.iter().zip(validated) {
+            let result = match validated {
+                Ok(validated) => self.execute_user_op(entry_point, op, validated, basefee)?,
+                Err((hash, gas, error)) => UserOperationResult {
+                    hash,
+                    status: UserOperationStatus::Invalid(error),
+                    gas,
+                    required_prefund: op.required_prefund(),
+                    actual_gas_cost: U256::ZERO,
+                    logs: Vec::new(),
+                },
+            };
+            results.push(result);
+        }
+        self.ctx.journal_mut().commit_tx();
+        Ok(results)
+    }
+}
+
+type ValidationOutcome = Result<ValidatedOperation, (B256, UserOperationGas, UserOperationError)>;
+
+/// Phases of [`UserOperationEvm::simulate_handle_ops`].
+trait UserOperationPhases: ExecuteEvm {
+    fn validate_user_op(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+    ) -> Result<ValidationOutcome, Self::Error>;
+
+    fn validate_user_op_phases(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+        hash: B256,
+        gas: &mut UserOperationGas,
+    ) -> Result<Result<Bytes, UserOperationError>, Self::Error>;
+
+    fn execute_user_op(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+        validated: ValidatedOperation,
+        basefee: u64,
+    ) -> Result<UserOperationResult, Self::Error>;
+
+    fn run_user_op_phase(
+        &mut self,
+        entry_point: Address,
+        target: Address,
+        data: Bytes,
+        gas_limit: u64,
+    ) -> Result<ExecutionResult, Self::Error>;
+
+    fn has_code(&mut self, address: Address) -> Result<bool, Self::Error>;
+
+    fn balance_of(&mut self, address: Address) -> Result<U256, Self::Error>;
+
+    fn deposit_of(&mut self, entry_point: Address, address: Address) -> Result<U256, Self::Error>;
+}
+
+impl<CTX, INSP, INST, PRECOMPILES, FRAME> UserOperationPhases
+    for Evm<CTX, INSP, INST, PRECOMPILES, FRAME>
+where
+    CTX: ContextTr<Journal: JournalTr<State = EvmState>, Tx = TxEnv> + ContextSetters,
+    INST: InstructionProvider<Context = CTX>,
+    PRECOMPILES: PrecompileProvider<CTX>,
+    FRAME: EvmFrameTr<CTX, INST, PRECOMPILES, FrameInit = FrameInit, FrameResult = FrameResult>,
+{
+    /// Runs the validation phase of the operation, state changes are reverted if it fails.
+    fn validate_user_op(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+    ) -> Result<ValidationOutcome, EvmError<CTX>> {
+        let hash = op.hash(entry_point, self.ctx.cfg().chain_id());
+        let mut gas = UserOperationGas {
+            pre_verification: op.pre_verification_gas,
+            ..Default::default()
+        };
+        let checkpoint = self.ctx.journal_mut().checkpoint();
+        match self.validate_user_op_phases(entry_point, op, hash, &mut gas) {
+            Ok(Ok(paymaster_context)) => {
+                self.ctx.journal_mut().checkpoint_commit();
+                Ok(Ok(ValidatedOperation {
+                    hash,
+                    gas,
+                    paymaster_context,
+                }))
+            }
+            Ok(Err(error)) => {
+                self.ctx.journal_mut().checkpoint_revert(checkpoint);
+                Ok(Err((hash, gas, error)))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn validate_user_op_phases(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+        hash: B256,
+        gas: &mut UserOperationGas,
+    ) -> Result<Result<Bytes, UserOperationError>, EvmError<CTX>> {
+        let timestamp = self.ctx.block().timestamp().saturating_to::<u64>();
+        let required_prefund = op.required_prefund();
+
+        if !self.has_code(op.sender)? {
+            return Ok(Err(UserOperationError::AccountNotDeployed));
+        }
+        let account_deposit = self.deposit_of(entry_point, op.sender)?;
+        let missing_funds = if op.paymaster.is_some() {
+            U256::ZERO
+        } else {
+            required_prefund.saturating_sub(account_deposit)
+        };
+
+        let balance_before = self.balance_of(entry_point)?;
+        let result = self.run_user_op_phase(
+            entry_point,
+            op.sender,
+            op.validation_call(VALIDATE_USER_OP_SELECTOR, hash, missing_funds),
+            op.verification_gas_limit,
+        )?;
+        gas.verification = result.gas_used();
+        let Some(validation_data) = result
+            .is_success()
+            .then(|| result.output().and_then(|output| read_word(output, 0)))
+            .flatten()
+        else {
+            return Ok(Err(UserOperationError::AccountReverted(
+                result.into_output().unwrap_or_default(),
+            )));
+        };
+
+        if op.paymaster.is_none() {
+            let paid = self.balance_of(entry_point)?.saturating_sub(balance_before);
+            let deposit = account_deposit.saturating_add(paid);
+            if deposit < required_prefund {
+                return Ok(Err(UserOperationError::PrefundNotPaid {
+                    required: required_prefund,
+                    deposit,
+                }));
+            }
+        }
+        let validation_data = ValidationData::from_word(validation_data);
+        if validation_data.is_signature_failure() {
+            return Ok(Err(UserOperationError::SignatureFailure));
+        }
+        if validation_data.authorizer != Address::ZERO {
+            return Ok(Err(UserOperationError::UnsupportedAggregator(
+                validation_data.authorizer,
+            )));
+        }
+        if !validation_data.is_valid_at(timestamp) {
+            return Ok(Err(UserOperationError::Expired(validation_data)));
+        }
+
+        let Some(paymaster) = &op.paymaster else {
+            return Ok(Ok(Bytes::new()));
+        };
+        if !self.has_code(paymaster.address)? {
+            return Ok(Err(UserOperationError::PaymasterNotDeployed));
+        }
+        let deposit = self.deposit_of(entry_point, paymaster.address)?;
+        if deposit < required_prefund {
+            return Ok(Err(UserOperationError::PaymasterDepositTooLow {
+                required: required_prefund,
+                deposit,
+            }));
+        }
+        let result = self.run_user_op_phase(
+            entry_point,
+            paymaster.address,
+            op.validation_call(VALIDATE_PAYMASTER_USER_OP_SELECTOR, hash, required_prefund),
+            paymaster.verification_gas_limit,
+        )?;
+        gas.paymaster_verification = result.gas_used();
+        let Some((context, validation_data)) = result
+            .is_success()
+            .then(|| result.output().and_then(decode_paymaster_validation))
+            .flatten()
+        else {
+            return Ok(Err(UserOperationError::PaymasterReverted(
+                result.into_output().unwrap_or_default(),
+            )));
+        };
+        let validation_data = ValidationData::from_word(validation_data);
+        if validation_data.authorizer != Address::ZERO {
+            return Ok(Err(UserOperationError::PaymasterSignatureFailure));
+        }
+        if !validation_data.is_valid_at(timestamp) {
+            return Ok(Err(UserOperationError::PaymasterExpired(validation_data)));
+        }
+        Ok(Ok(context))
+    }
+
+    /// Runs the execution and `postOp` of the validated operation.
+    fn execute_user_op(
+        &mut self,
+        entry_point: Address,
+        op: &UserOperation,
+        validated: ValidatedOperation,
+        basefee: u64,
+    ) -> Result<UserOperationResult, EvmError<CTX>> {
+        let ValidatedOperation {
+            hash,
+            mut gas,
+            paymaster_context,
+        } = validated;
+        let gas_price = U256::from(op.gas_price(basefee));
+
+        let checkpoint = self.ctx.journal_mut().checkpoint();
+        let result = self.run_user_op_phase(
+            entry_point,
+            op.sender,
+            op.execution_call(hash),
+            op.call_gas_limit,
+        )?;
+        gas.execution = result.gas_used();
+        let (mut status, mut logs) = match result {
+            ExecutionResult::Success { logs, .. } => (UserOperationStatus::Succeeded, logs),
+            result => (
+                UserOperationStatus::Reverted(result.into_output().unwrap_or_default()),
+                Vec::new(),
+            ),
+        };
+
+        let mut post_op_gas_limit = 0;
+        if let (Some(paymaster), false) = (&op.paymaster, paymaster_context.is_empty()) {
+            post_op_gas_limit = paymaster.post_op_gas_limit;
+            let mode = match status {
+                UserOperationStatus::Succeeded => 0,
+                _ => 1,
+            };
+            let actual_gas_cost = U256::from(gas.total()) * gas_price;
+            let mut data = POST_OP_SELECTOR.to_vec();
+            push_u64(&mut data, mode);
+            push_u64(&mut data, 4 * 32);
+            data.extend_from_slice(&actual_gas_cost.to_be_bytes::<32>());
+            data.extend_from_slice(&gas_price.to_be_bytes::<32>());
+            push_bytes(&mut data, &paymaster_context);
+
+            let result = self.run_user_op_phase(
+                entry_point,
+                paymaster.address,
+                data.into(),
+                post_op_gas_limit,
+            )?;
+            gas.post_op = result.gas_used();
+            match result {
+                ExecutionResult::Success {
+                    logs: post_op_logs, ..
+                } => logs.extend(post_op_logs),
+                result => {
+                    status = UserOperationStatus::PostOpReverted(
+                        result.into_output().unwrap_or_default(),
+                    );
+                    logs.clear();
+                }
+            }
+        }
+        if matches!(status, UserOperationStatus::PostOpReverted(_)) {
+            self.ctx.journal_mut().checkpoint_revert(checkpoint);
+        } else {
+            self.ctx.journal_mut().checkpoint_commit();
+        }
+
+        let unused_gas =
+            (op.call_gas_limit + post_op_gas_limit).saturating_sub(gas.execution + gas.post_op);
+        gas.unused_gas_penalty = unused_gas * UNUSED_GAS_PENALTY_PERCENT / 100;
+
+        Ok(UserOperationResult {
+            hash,
+            status,
+            gas,
+            required_prefund: op.required_prefund(),
+            actual_gas_cost: U256::from(gas.total()) * gas_price,
+            logs,
+        })
+    }
+
+    /// Calls the target from the entry point without committing the transaction.
+    fn run_user_op_phase(
+        &mut self,
+        entry_point: Address,
+        target: Address,
+        data: Bytes,
+        gas_limit: u64,
+    ) -> Result<ExecutionResult, EvmError<CTX>> {
+        self.set_tx(
+            TxEnv::builder()
+                .caller(entry_point)
+                .kind(TxKind::Call(target))
+                .data(data)
+                .gas_limit(gas_limit)
+                .build_fill(),
+        );
+        let mut handler = MainnetHandler::<Self, EvmError<CTX>, FRAME>::default();
+        let result = match handler.execution(self, &InitialAndFloorGas::new(0, 0)) {
+            Ok(result) => result,
+            Err(error) => return handler.catch_error(self, error),
+        };
+        self.ctx.local_mut().clear();
+        self.frame_stack().clear();
+        match core::mem::replace(self.ctx.error(), Ok(())) {
+            Err(ContextError::Db(e)) => return Err(e.into()),
+            Err(ContextError::Custom(e)) => return Err(EVMError::Custom(e)),
+            Ok(_) => (),
+        }
+        Ok(crate::post_execution::output(&mut self.ctx, result))
+    }
+
+    fn has_code(&mut self, address: Address) -> Result<bool, EvmError<CTX>> {
+        let account = self.ctx.journal_mut().load_account_code(address)?;
+        Ok(!account.data.info.is_empty_code_hash())
+    }
+
+    fn balance_of(&mut self, address: Address) -> Result<U256, EvmError<CTX>> {
+        Ok(self
+            .ctx
+            .journal_mut()
+            .load_account(address)?
+            .data
+            .info
+            .balance)
+    }
+
+    /// Reads the deposit from the `deposits` mapping of the entry point.
+    fn deposit_of(
+        &mut self,
+        entry_point: Address,
+        address: Address,
+    ) -> Result<U256, EvmError<CTX>> {
+        let mut key = Vec::with_capacity(64);
+        push_address(&mut key, address);
+        push_u64(&mut key, 0);
+        let slot = keccak256(key).into();
+        let journal = self.ctx.journal_mut();
+        journal.load_account(entry_point)?;
+        Ok(journal.sload(entry_point, slot)?.data)
+    }
+}
+
+/// Decodes the `(bytes context, uint256 validationData)` output of `validatePaymasterUserOp`.
+fn decode_paymaster_validation(output: &Bytes) -> Option<(Bytes, U256)> {
+    let offset = usize::try_from(read_word(output, 0)?).ok()?;
+    let validation_data = read_word(output, 32)?;
+    let len = usize::try_from(read_word(output, offset)?).ok()?;
+    let start = offset.checked_add(32)?;
+    let context = output.get(start..start.checked_add(len)?)?;
+    Some((Bytes::copy_from_slice(context), validation_data))
+}
+
+fn read_word(data: &[u8], offset: usize) -> Option<U256> {
+    let word = data.get(offset..offset.checked_add(32)?)?;
+    Some(U256::from_be_slice(word))
+}
+
+fn pack_u128(high: u128, low: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[..16].copy_from_slice(&high.to_be_bytes());
+    word[16..].copy_from_slice(&low.to_be_bytes());
+    word
+}
+
+fn push_address(data: &mut Vec<u8>, address: Address) {
+    data.extend_from_slice(&[0u8; 12]);
+    data.extend_from_slice(address.as_slice());
+}
+
+fn push_u64(data: &mut Vec<u8>, value: u64) {
+    data.extend_from_slice(&U256::from(value).to_be_bytes::<32>());
+}
+
+fn push_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    push_u64(data, bytes.len() as u64);
+    data.extend_from_slice(bytes);
+    data.resize(data.len().next_multiple_of(32), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use context::Context;
+    use database::InMemoryDB;
+    use primitives::StorageKey;
+    use state::{bytecode::opcode, AccountInfo, Bytecode};
+
+    const ACCOUNT: Address = address!("0x00000000000000000000000000000000000000a1");
+    const POOR_ACCOUNT: Address = address!("0x00000000000000000000000000000000000000a2");
+    const PAYMASTER: Address = address!("0x00000000000000000000000000000000000000b1");
+
+    /// Pays the missing funds and accepts `validateUserOp`, otherwise reverts on empty calldata
+    /// and stores one in slot zero.
+    fn account_code() -> Bytecode {
+        let [s0, s1, s2, s3] = VALIDATE_USER_OP_SELECTOR;
+        Bytecode::new_legacy(Bytes::from(vec![
+            opcode::PUSH0,
+            opcode::CALLDATALOAD,
+            opcode::PUSH1,
+            0xe0,
+            opcode::SHR,
+            opcode::PUSH4,
+            s0,
+            s1,
+            s2,
+            s3,
+            opcode::EQ,
+            opcode::PUSH1,
+            27,
+            opcode::JUMPI,
+            // execution
+            opcode::CALLDATASIZE,
+            opcode::PUSH1,
+            21,
+            opcode::JUMPI,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::REVERT,
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH0,
+            opcode::SSTORE,
+            opcode::STOP,
+            // validation: call(gas, caller, missingAccountFunds, 0, 0, 0, 0)
+            opcode::JUMPDEST,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            0x44,
+            opcode::CALLDATALOAD,
+            opcode::CALLER,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]))
+    }
+
+    /// Accepts `validatePaymasterUserOp` with a one byte context, `postOp` stores the actual gas
+    /// cost in slot zero.
+    fn paymaster_code() -> Bytecode {
+        let [s0, s1, s2, s3] = VALIDATE_PAYMASTER_USER_OP_SELECTOR;
+        Bytecode::new_legacy(Bytes::from(vec![
+            opcode::PUSH0,
+            opcode::CALLDATALOAD,
+            opcode::PUSH1,
+            0xe0,
+            opcode::SHR,
+            opcode::PUSH4,
+            s0,
+            s1,
+            s2,
+            s3,
+            opcode::EQ,
+            opcode::PUSH1,
+            20,
+            opcode::JUMPI,
+            // postOp
+            opcode::PUSH1,
+            0x44,
+            opcode::CALLDATALOAD,
+            opcode::PUSH0,
+            opcode::SSTORE,
+            opcode::STOP,
+            // validation: return abi.encode(bytes(0x00), 0)
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x40,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x80,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]))
+    }
+
+    fn user_op(sender: Address, call_data: &'static [u8]) -> UserOperation {
+        UserOperation {
+            sender,
+            call_data: Bytes::from_static(call_data),
+            call_gas_limit: 50_000,
+            verification_gas_limit: 100_000,
+            pre_verification_gas: 21_000,
+            max_fee_per_gas: 2,
+            max_priority_fee_per_gas: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_simulate_handle_ops() {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            ACCOUNT,
+            AccountInfo::from_balance(U256::from(1_000_000_000)).with_code(account_code()),
+        );
+        db.insert_account_info(
+            POOR_ACCOUNT,
+            AccountInfo::default().with_code(account_code()),
+        );
+        db.insert_account_info(
+            PAYMASTER,
+            AccountInfo::default().with_code(paymaster_code()),
+        );
+        let mut key = Vec::new();
+        push_address(&mut key, PAYMASTER);
+        push_u64(&mut key, 0);
+        db.insert_account_storage(
+            ENTRY_POINT_V07,
+            keccak256(key).into(),
+            U256::from(1_000_000_000),
+        )
+        .unwrap();
+
+        let sponsored = UserOperation {
+            paymaster: Some(Paymaster {
+                address: PAYMASTER,
+                verification_gas_limit: 50_000,
+                post_op_gas_limit: 50_000,
+                data: Bytes::new(),
+            }),
+            ..user_op(ACCOUNT, &[])
+        };
+        let ops = [
+            user_op(ACCOUNT, &[1]),
+            user_op(POOR_ACCOUNT, &[1]),
+            sponsored.clone(),
+        ];
+
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+        let results = evm.simulate_handle_ops(ENTRY_POINT_V07, &ops).unwrap();
+        let state = evm.finalize();
+
+        // Account paid the prefund and the execution stored one.
+        let result = &results[0];
+        assert_eq!(result.status, UserOperationStatus::Succeeded);
+        assert_eq!(result.hash, ops[0].hash(ENTRY_POINT_V07, 1));
+        assert_eq!(result.required_prefund, U256::from(171_000 * 2));
+        assert_eq!(
+            state[&ENTRY_POINT_V07].info.balance,
+            result.required_prefund
+        );
+        assert_eq!(
+            state[&ACCOUNT].storage[&StorageKey::ZERO].present_value,
+            U256::ONE
+        );
+        assert!(result.gas.verification > 0 && result.gas.execution > 0);
+        // Unused gas of the call gas limit is charged a penalty.
+        assert_eq!(
+            result.gas.unused_gas_penalty,
+            (50_000 - result.gas.execution) / 10
+        );
+        assert_eq!(result.actual_gas_cost, U256::from(result.gas.total()));
+
+        assert_eq!(
+            results[1].status,
+            UserOperationStatus::Invalid(UserOperationError::PrefundNotPaid {
+                required: ops[1].required_prefund(),
+                deposit: U256::ZERO,
+            })
+        );
+        assert_eq!(results[1].actual_gas_cost, U256::ZERO);
+
+        // Execution reverted and postOp received the cost of the gas used before it.
+        let result = &results[2];
+        assert_eq!(result.status, UserOperationStatus::Reverted(Bytes::new()));
+        assert!(result.gas.paymaster_verification > 0 && result.gas.post_op > 0);
+        let gas_before_post_op = result.gas.pre_verification
+            + result.gas.verification
+            + result.gas.paymaster_verification
+            + result.gas.execution;
+        assert_eq!(
+            state[&PAYMASTER].storage[&StorageKey::ZERO].present_value,
+            U256::from(gas_before_post_op)
+        );
+        assert_eq!(result.required_prefund, U256::from(271_000 * 2));
+    }
+
+    #[test]
+    fn test_validation_data() {
+        let data =
+            ValidationData::from_word(U256::from(5) << 208 | U256::from(10) << 160 | U256::from(1));
+        assert_eq!(data.valid_after, 5);
+        assert_eq!(data.valid_until, 10);
+        assert!(data.is_signature_failure());
+        assert!(!data.is_valid_at(4) && data.is_valid_at(5) && !data.is_valid_at(11));
+        assert!(ValidationData::default().is_valid_at(u64::MAX));
+    }
+}
@@ -1,11 +1,12 @@
 use context_interface::{
-    result::{InvalidHeader, InvalidTransaction},
-    transaction::{Transaction, TransactionType},
+    result::{AuthorizationListGas, InvalidHeader, InvalidTransaction},
+    transaction::{BlobSidecar, Transaction, TransactionType},
     Block, Cfg, ContextTr,
 };
 use core::cmp;
 use interpreter::gas::{self, InitialAndFloorGas};
-use primitives::{eip4844, hardfork::SpecId, B256};
+use primitives::{eip4844, eip7702, hardfork::SpecId, B256};
+use std::boxed::Box;
 
 /// Validates the execution environment including block and transaction parameters.
 pub fn validate_env<CTX: ContextTr, ERROR: From<InvalidHeader> + From<InvalidTransaction>>(
@@ -83,6 +84,45 @@ pub fn validate_eip4844_tx(
     Ok(())
 }
 
+/// Validate blob sidecar of EIP-4844 transaction against its versioned hashes.
+///
+/// Commitments are checked against the versioned hashes and blobs are verified against the
+/// commitments with the KZG backend of [`precompile::crypto`].
+pub fn validate_blob_sidecar(
+    versioned_hashes: &[B256],
+    sidecar: Option<&BlobSidecar>,
+) -> Result<(), InvalidTransaction> {
+    let Some(sidecar) = sidecar else {
+        return Err(InvalidTransaction::MissingBlobSidecar);
+    };
+    let blobs = match sidecar.iter() {
+        Some(blobs) if sidecar.len() == versioned_hashes.len() => blobs,
+        _ => return Err(InvalidTransaction::BlobSidecarMismatch { index: None }),
+    };
+
+    let crypto = precompile::crypto();
+    for (index, ((blob, commitment, proof), versioned_hash)) in
+        blobs.zip(versioned_hashes).enumerate()
+    {
+        // `VERSIONED_HASH_VERSION_KZG ++ sha256(commitment)[1..]`
+        let mut hash = crypto.sha256(commitment.as_slice());
+        hash[0] = eip4844::VERSIONED_HASH_VERSION_KZG;
+        if hash != versioned_hash.0 {
+            return Err(InvalidTransaction::BlobSidecarMismatch { index: Some(index) });
+        }
+        let Ok(blob) = blob.as_ref().try_into() else {
+            return Err(InvalidTransaction::InvalidBlobKzgProof { index });
+        };
+        if crypto
+            .verify_blob_kzg_proof(blob, &commitment.0, &proof.0)
+            .is_err()
+        {
+            return Err(InvalidTransaction::InvalidBlobKzgProof { index });
+        }
+    }
+    Ok(())
+}
+
 /// Validate transaction against block and configuration for mainnet.
 pub fn validate_tx_env<CTX: ContextTr, Error>(
     context: CTX,
@@ -104,7 +144,7 @@ pub fn validate_tx_env<CTX: ContextTr, Error>(
     // EIP-155: Simple replay attack protection
     if context.cfg().tx_chain_id_check() {
         if let Some(chain_id) = tx.chain_id() {
-            if chain_id != context.cfg().chain_id() {
+            if !context.cfg().is_chain_id_accepted(chain_id) {
                 return Err(InvalidTransaction::InvalidChainId);
             }
         } else if !tx_type.is_legacy() && !tx_type.is_custom() {
@@ -175,6 +215,10 @@ pub fn validate_tx_env<CTX: ContextTr, Error>(
                 context.block().blob_gasprice().unwrap_or_default(),
                 context.cfg().max_blobs_per_tx(),
             )?;
+
+            if context.cfg().blob_sidecar_mode().is_verify() {
+                validate_blob_sidecar(tx.blob_versioned_hashes(), tx.blob_sidecar())?;
+            }
         }
         TransactionType::Eip7702 => {
             // Check if EIP-7702 transaction is enabled.
@@ -196,6 +240,7 @@ pub fn validate_tx_env<CTX: ContextTr, Error>(
             }
         }
         /* // TODO(EOF) EOF removed from spec.
+        // InitcodeTransaction and TXCREATE only deploy EOF containers, they come back together with EOF.
         TransactionType::Eip7873 => {
             // Check if EIP-7873 transaction is enabled.
             if !spec_id.is_enabled_in(SpecId::OSAKA) {
@@ -290,6 +335,22 @@ pub fn validate_initial_tx_gas(
 
     // Additional check to see if limit is big enough to cover initial gas.
     if gas.initial_gas > tx.gas_limit() {
+        // Report the authorization that exceeded the limit if the rest of the transaction fits.
+        let authorizations = tx.authorization_list_len() as u64;
+        let base_gas = gas
+            .initial_gas
+            .saturating_sub(authorizations * eip7702::PER_EMPTY_ACCOUNT_COST);
+        if spec.is_enabled_in(SpecId::PRAGUE) && authorizations > 0 && base_gas <= tx.gas_limit() {
+            return Err(InvalidTransaction::AuthorizationListGasMoreThanGasLimit {
+                initial_gas: gas.initial_gas,
+                gas_limit: tx.gas_limit(),
+                accounting: Box::new(AuthorizationListGas::new(
+                    base_gas,
+                    authorizations,
+                    tx.gas_limit(),
+                )),
+            });
+        }
         return Err(InvalidTransaction::CallGasCostMoreThanGasLimit {
             gas_limit: tx.gas_limit(),
             initial_gas: gas.initial_gas,
@@ -313,11 +374,16 @@ mod tests {
     use crate::{ExecuteCommitEvm, MainBuilder, MainContext};
     use bytecode::opcode;
     use context::{
-        result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction, Output},
+        result::{
+            AuthorizationListGas, EVMError, ExecutionResult, HaltReason, InvalidTransaction, Output,
+        },
+        transaction::{Authorization, RecoveredAuthority, RecoveredAuthorization},
         Context, TxEnv,
     };
     use database::{CacheDB, EmptyDB};
-    use primitives::{address, eip3860, eip7907, hardfork::SpecId, Bytes, TxKind};
+    use primitives::{
+        address, eip3860, eip7702, eip7907, hardfork::SpecId, Address, Bytes, TxKind, U256,
+    };
 
     fn deploy_contract(
         bytecode: Bytes,
@@ -341,6 +407,38 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_intrinsic_gas_breakdown() {
+        use interpreter::gas::{intrinsic_gas, InitialAndFloorGas};
+
+        let tx = TxEnv::builder()
+            .kind(TxKind::Create)
+            .data(Bytes::from_static(&[opcode::STOP, 1, 1, 1]))
+            .build()
+            .unwrap();
+        let gas = intrinsic_gas(&tx, SpecId::PRAGUE);
+        assert_eq!(gas.base, 53000);
+        assert_eq!(gas.calldata_tokens, 1 + 3 * 4);
+        assert_eq!(gas.calldata, gas.calldata_tokens * 4);
+        assert_eq!(gas.initcode, 2);
+        assert_eq!(gas.floor, 21000 + gas.calldata_tokens * 10);
+        assert_eq!(gas.min_gas_limit(), gas.initial_gas());
+
+        let InitialAndFloorGas {
+            initial_gas,
+            floor_gas,
+        } = gas.into();
+        assert_eq!((initial_gas, floor_gas), (gas.initial_gas(), gas.floor));
+
+        // No initcode cost before Shanghai and no floor before Prague.
+        let london = intrinsic_gas(&tx, SpecId::LONDON);
+        assert_eq!((london.initcode, london.floor), (0, 0));
+
+        // Init code stops right away, so only the intrinsic gas is used.
+        let result = deploy_contract(tx.data, Some(SpecId::PRAGUE)).unwrap();
+        assert_eq!(result.gas_used(), gas.min_gas_limit());
+    }
+
     #[test]
     fn test_eip3860_initcode_size_limit_failure() {
         let large_bytecode = vec![opcode::STOP; eip3860::MAX_INITCODE_SIZE + 1];
@@ -610,4 +708,59 @@ mod tests {
             _ => panic!("execution result is not Success"),
         }
     }
+
+    #[test]
+    fn test_eip7702_authorization_list_gas_more_than_gas_limit() {
+        let auth = RecoveredAuthorization::new_unchecked(
+            Authorization {
+                chain_id: U256::from(1),
+                nonce: 0,
+                address: Address::default(),
+            },
+            RecoveredAuthority::Valid(Address::default()),
+        );
+        let tx = |gas_limit| {
+            TxEnv::builder()
+                .gas_limit(gas_limit)
+                .authorization_list_recovered(vec![auth.clone(); 4])
+                .build()
+                .unwrap()
+        };
+
+        // Base gas and two authorizations fit, third authorization exceeds the limit.
+        let gas_limit = 21_000 + 2 * eip7702::PER_EMPTY_ACCOUNT_COST + 1;
+        let Err(InvalidTransaction::AuthorizationListGasMoreThanGasLimit {
+            initial_gas,
+            accounting,
+            ..
+        }) = super::validate_initial_tx_gas(tx(gas_limit), SpecId::PRAGUE)
+        else {
+            panic!("expected authorization list gas error");
+        };
+        assert_eq!(initial_gas, 21_000 + 4 * eip7702::PER_EMPTY_ACCOUNT_COST);
+        assert_eq!(
+            *accounting,
+            AuthorizationListGas {
+                base_gas: 21_000,
+                authorizations: 4,
+                cost_per_authorization: eip7702::PER_EMPTY_ACCOUNT_COST,
+                existing_authority_refund: eip7702::PER_EMPTY_ACCOUNT_COST
+                    - eip7702::PER_AUTH_BASE_COST,
+                exceeding_index: 2,
+            }
+        );
+        assert_eq!(
+            accounting.authorization_gas(),
+            4 * eip7702::PER_EMPTY_ACCOUNT_COST
+        );
+
+        // Authorizations are not blamed if the base gas exceeds the limit.
+        assert_eq!(
+            super::validate_initial_tx_gas(tx(20_000), SpecId::PRAGUE).unwrap_err(),
+            InvalidTransaction::CallGasCostMoreThanGasLimit {
+                initial_gas: 21_000 + 4 * eip7702::PER_EMPTY_ACCOUNT_COST,
+                gas_limit: 20_000,
+            }
+        );
+    }
 }
@@ -0,0 +1,117 @@
+//! Consensus layer withdrawals applied as balance increments, see [EIP-4895](https://eips.ethereum.org/EIPS/eip-4895).
+//!
+//! Withdrawals are applied after the transactions of the block. They are not transactions, no gas
+//! is used and no code is executed, the withdrawn amount is added to the balance of the recipient.
+use crate::{EvmTr, ExecuteEvm};
+use context::{ContextTr, Database, JournalTr};
+use primitives::{Address, U256};
+
+/// Amount of wei in one gwei, withdrawal amounts are denominated in gwei.
+pub const GWEI_TO_WEI: u64 = 1_000_000_000;
+
+/// Withdrawal from the consensus layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Withdrawal {
+    /// Monotonically increasing index of the withdrawal.
+    pub index: u64,
+    /// Index of the validator the withdrawal is from.
+    pub validator_index: u64,
+    /// Recipient of the withdrawn amount.
+    pub address: Address,
+    /// Withdrawn amount in gwei.
+    pub amount: u64,
+}
+
+impl Withdrawal {
+    /// Returns the withdrawn amount in wei.
+    pub fn amount_wei(&self) -> U256 {
+        U256::from(self.amount) * U256::from(GWEI_TO_WEI)
+    }
+}
+
+/// Applies the withdrawals to the journal of the context.
+///
+/// Balances are incremented with journal entries, so the changes are part of the state returned
+/// by [`ExecuteEvm::finalize`]. Withdrawals with zero amount are skipped and do not touch the
+/// recipient. `on_withdrawal` is called after the balance of the recipient is incremented.
+pub fn apply_withdrawals<CTX: ContextTr>(
+    context: &mut CTX,
+    withdrawals: &[Withdrawal],
+    mut on_withdrawal: impl FnMut(&mut CTX, &Withdrawal),
+) -> Result<(), <CTX::Db as Database>::Error> {
+    for withdrawal in withdrawals {
+        if withdrawal.amount == 0 {
+            continue;
+        }
+        context
+            .journal_mut()
+            .balance_incr(withdrawal.address, withdrawal.amount_wei())?;
+        on_withdrawal(context, withdrawal);
+    }
+    Ok(())
+}
+
+/// API for applying consensus layer withdrawals to the state of the EVM.
+pub trait WithdrawalsEvm: ExecuteEvm {
+    /// Applies the withdrawals to the journal, see [`apply_withdrawals`].
+    ///
+    /// Changes are finalized together with executed transactions on [`ExecuteEvm::finalize`].
+    fn apply_withdrawals(&mut self, withdrawals: &[Withdrawal]) -> Result<(), Self::Error>;
+
+    /// Applies the withdrawals and finalizes the state.
+    fn apply_withdrawals_finalize(
+        &mut self,
+        withdrawals: &[Withdrawal],
+    ) -> Result<Self::State, Self::Error> {
+        self.apply_withdrawals(withdrawals)?;
+        Ok(self.finalize())
+    }
+}
+
+impl<EVM> WithdrawalsEvm for EVM
+where
+    EVM: ExecuteEvm + EvmTr,
+    EVM::Error: From<<<EVM::Context as ContextTr>::Db as Database>::Error>,
+{
+    fn apply_withdrawals(&mut self, withdrawals: &[Withdrawal]) -> Result<(), Self::Error> {
+        apply_withdrawals(self.ctx(), withdrawals, |_, _| {})?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MainBuilder, MainContext};
+    use context::Context;
+    use database::InMemoryDB;
+    use primitives::address;
+
+    #[test]
+    fn test_apply_withdrawals() {
+        let recipient = address!("0x0000000000000000000000000000000000001000");
+        let empty = address!("0x0000000000000000000000000000000000002000");
+        let mut evm = Context::mainnet()
+            .with_db(InMemoryDB::default())
+            .build_mainnet();
+
+        let withdrawal = |index, address, amount| Withdrawal {
+            index,
+            validator_index: 7,
+            address,
+            amount,
+        };
+        let state = evm
+            .apply_withdrawals_finalize(&[
+                withdrawal(0, recipient, 1),
+                withdrawal(1, recipient, 2),
+                withdrawal(2, empty, 0),
+            ])
+            .unwrap();
+
+        assert_eq!(state[&recipient].info.balance, U256::from(3 * GWEI_TO_WEI));
+        assert!(state[&recipient].is_touched());
+        assert!(!state.contains_key(&empty));
+    }
+}
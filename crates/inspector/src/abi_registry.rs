@@ -0,0 +1,479 @@
+//! Registry of function signatures used to decode calls into human-readable JSON.
+use core::fmt;
+use primitives::{hex, keccak256, Address, HashMap, I256, U256};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Selector of the standard `Error(string)` revert reason.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Error returned when a function signature or a type can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiParseError(String);
+
+impl fmt::Display for AbiParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid ABI signature: {}", self.0)
+    }
+}
+
+impl core::error::Error for AbiParseError {}
+
+/// Solidity ABI type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    /// `uintN`, with the number of bits.
+    Uint(usize),
+    /// `intN`, with the number of bits.
+    Int(usize),
+    /// `address`.
+    Address,
+    /// `bool`.
+    Bool,
+    /// `bytesN`, with the number of bytes.
+    FixedBytes(usize),
+    /// `bytes`.
+    Bytes,
+    /// `string`.
+    String,
+    /// `T[]`.
+    Array(Box<AbiType>),
+    /// `T[N]`.
+    FixedArray(Box<AbiType>, usize),
+    /// `(T1,T2,...)`.
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Parses the type, `uint` and `int` are aliases of `uint256` and `int256`.
+    pub fn parse(ty: &str) -> Result<Self, AbiParseError> {
+        let ty = ty.trim();
+        let error = || AbiParseError(ty.to_string());
+        if let Some(inner) = ty.strip_suffix(']') {
+            let open = inner.rfind('[').ok_or_else(error)?;
+            let element = Box::new(Self::parse(&inner[..open])?);
+            let size = &inner[open + 1..];
+            return if size.is_empty() {
+                Ok(Self::Array(element))
+            } else {
+                let size = size.parse().map_err(|_| error())?;
+                Ok(Self::FixedArray(element, size))
+            };
+        }
+        if let Some(components) = ty.strip_prefix('(') {
+            let components = components.strip_suffix(')').ok_or_else(error)?;
+            return Ok(Self::Tuple(parse_list(components)?));
+        }
+        let bits = |digits: &str, max: usize, step: usize| match digits {
+            "" if step == 8 => Ok(256),
+            _ => match digits.parse() {
+                Ok(n) if n > 0 && n <= max && n % step == 0 => Ok(n),
+                _ => Err(error()),
+            },
+        };
+        match ty {
+            "address" => Ok(Self::Address),
+            "bool" => Ok(Self::Bool),
+            "bytes" => Ok(Self::Bytes),
+            "string" => Ok(Self::String),
+            _ => {
+                if let Some(digits) = ty.strip_prefix("uint") {
+                    bits(digits, 256, 8).map(Self::Uint)
+                } else if let Some(digits) = ty.strip_prefix("int") {
+                    bits(digits, 256, 8).map(Self::Int)
+                } else if let Some(digits) = ty.strip_prefix("bytes") {
+                    bits(digits, 32, 1).map(Self::FixedBytes)
+                } else {
+                    Err(error())
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the encoding of the type is stored at an offset.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes | Self::String | Self::Array(_) => true,
+            Self::FixedArray(element, _) => element.is_dynamic(),
+            Self::Tuple(components) => components.iter().any(Self::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Returns the size of the type in the head of the encoding.
+    fn head_size(&self) -> usize {
+        match self {
+            Self::FixedArray(element, size) if !self.is_dynamic() => element.head_size() * size,
+            Self::Tuple(components) if !self.is_dynamic() => {
+                components.iter().map(Self::head_size).sum()
+            }
+            _ => 32,
+        }
+    }
+
+    /// Decodes the value whose encoding starts at the beginning of `data`.
+    ///
+    /// Integers are decoded as decimal strings, addresses as checksummed strings and byte
+    /// arrays as hex strings.
+    fn decode(&self, data: &[u8]) -> Option<Value> {
+        let value = match self {
+            Self::Uint(_) => Value::String(U256::from_be_bytes(word(data, 0)?).to_string()),
+            Self::Int(_) => Value::String(I256::from_be_bytes(word(data, 0)?).to_string()),
+            Self::Address => {
+                Value::String(Address::from_word(word(data, 0)?.into()).to_checksum(None))
+            }
+            Self::Bool => Value::Bool(word(data, 0)?[31] != 0),
+            Self::FixedBytes(size) => Value::String(hex::encode_prefixed(&word(data, 0)?[..*size])),
+            Self::Bytes => Value::String(hex::encode_prefixed(dynamic_bytes(data)?)),
+            Self::String => Value::String(String::from_utf8_lossy(dynamic_bytes(data)?).into()),
+            Self::Array(element) => {
+                let len = offset(data, 0)?;
+                // Every element takes at least one word.
+                if len > data.len() / 32 {
+                    return None;
+                }
+                Value::Array(decode_tuple(&vec![(**element).clone(); len], &data[32..])?)
+            }
+            Self::FixedArray(element, size) => {
+                Value::Array(decode_tuple(&vec![(**element).clone(); *size], data)?)
+            }
+            Self::Tuple(components) => Value::Array(decode_tuple(components, data)?),
+        };
+        Some(value)
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uint(bits) => write!(f, "uint{bits}"),
+            Self::Int(bits) => write!(f, "int{bits}"),
+            Self::Address => f.write_str("address"),
+            Self::Bool => f.write_str("bool"),
+            Self::FixedBytes(size) => write!(f, "bytes{size}"),
+            Self::Bytes => f.write_str("bytes"),
+            Self::String => f.write_str("string"),
+            Self::Array(element) => write!(f, "{element}[]"),
+            Self::FixedArray(element, size) => write!(f, "{element}[{size}]"),
+            Self::Tuple(components) => write_list(f, components),
+        }
+    }
+}
+
+/// Function signature with the types of its inputs and outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiFunction {
+    /// Name of the function.
+    pub name: String,
+    /// Types of the inputs.
+    pub inputs: Vec<AbiType>,
+    /// Types of the outputs.
+    pub outputs: Vec<AbiType>,
+}
+
+impl AbiFunction {
+    /// Parses the signature in the `name(inputs)(outputs)` form, like
+    /// `balanceOf(address)(uint256)`. Outputs are optional.
+    pub fn parse(signature: &str) -> Result<Self, AbiParseError> {
+        let error = || AbiParseError(signature.to_string());
+        let open = signature.find('(').ok_or_else(error)?;
+        let name = signature[..open].trim();
+        if name.is_empty() {
+            return Err(error());
+        }
+        let (inputs, rest) = split_group(&signature[open..]).ok_or_else(error)?;
+        let outputs = match rest.trim() {
+            "" => Vec::new(),
+            rest => match split_group(rest) {
+                Some((outputs, "")) => parse_list(outputs)?,
+                _ => return Err(error()),
+            },
+        };
+        Ok(Self {
+            name: name.to_string(),
+            inputs: parse_list(inputs)?,
+            outputs,
+        })
+    }
+
+    /// Returns the canonical signature used to compute the selector, like
+    /// `balanceOf(address)`.
+    pub fn signature(&self) -> String {
+        let mut signature = self.name.clone();
+        signature.push_str(&AbiType::Tuple(self.inputs.clone()).to_string());
+        signature
+    }
+
+    /// Returns the function selector, first four bytes of the hash of the signature.
+    pub fn selector(&self) -> [u8; 4] {
+        keccak256(self.signature())[..4].try_into().unwrap()
+    }
+
+    /// Decodes the inputs from the call data without the selector.
+    pub fn decode_inputs(&self, data: &[u8]) -> Option<Vec<Value>> {
+        decode_tuple(&self.inputs, data)
+    }
+
+    /// Decodes the outputs from the returned data.
+    pub fn decode_outputs(&self, data: &[u8]) -> Option<Vec<Value>> {
+        decode_tuple(&self.outputs, data)
+    }
+}
+
+/// Call decoded with an [`AbiRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+    /// Canonical signature of the called function.
+    pub signature: String,
+    /// Decoded inputs.
+    pub inputs: Vec<Value>,
+    /// Decoded outputs, `None` if the call failed or outputs could not be decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Vec<Value>>,
+}
+
+/// Functions known to the tracer, looked up by selector.
+#[derive(Clone, Debug, Default)]
+pub struct AbiRegistry {
+    functions: HashMap<[u8; 4], AbiFunction>,
+}
+
+impl AbiRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry of the signatures, see [`AbiFunction::parse`].
+    pub fn from_signatures<'a>(
+        signatures: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, AbiParseError> {
+        let mut registry = Self::new();
+        for signature in signatures {
+            registry.insert(AbiFunction::parse(signature)?);
+        }
+        Ok(registry)
+    }
+
+    /// Inserts the function, replacing the function with the same selector.
+    pub fn insert(&mut self, function: AbiFunction) {
+        self.functions.insert(function.selector(), function);
+    }
+
+    /// Returns the function with the selector.
+    pub fn get(&self, selector: [u8; 4]) -> Option<&AbiFunction> {
+        self.functions.get(&selector)
+    }
+
+    /// Returns the number of registered functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns `true` if no function is registered.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Decodes the call data and, if given, the data returned by a successful call.
+    ///
+    /// Returns `None` if the selector is unknown or the inputs can't be decoded.
+    pub fn decode_call(&self, input: &[u8], output: Option<&[u8]>) -> Option<DecodedCall> {
+        let (selector, data) = input.split_first_chunk::<4>()?;
+        let function = self.get(*selector)?;
+        Some(DecodedCall {
+            signature: function.signature(),
+            inputs: function.decode_inputs(data)?,
+            outputs: output.and_then(|output| function.decode_outputs(output)),
+        })
+    }
+}
+
+/// Decodes the `Error(string)` revert reason.
+pub fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    let data = output.strip_prefix(&ERROR_SELECTOR)?;
+    match decode_tuple(&[AbiType::String], data)?.pop()? {
+        Value::String(reason) => Some(reason),
+        _ => None,
+    }
+}
+
+/// Decodes the tuple whose encoding starts at the beginning of `data`.
+fn decode_tuple(types: &[AbiType], data: &[u8]) -> Option<Vec<Value>> {
+    let mut head = 0;
+    let mut values = Vec::with_capacity(types.len());
+    for ty in types {
+        let start = if ty.is_dynamic() {
+            offset(data, head)?
+        } else {
+            head
+        };
+        values.push(ty.decode(data.get(start..)?)?);
+        head += ty.head_size();
+    }
+    Some(values)
+}
+
+/// Returns the 32 bytes word at `at`.
+fn word(data: &[u8], at: usize) -> Option<[u8; 32]> {
+    data.get(at..at.checked_add(32)?)?.try_into().ok()
+}
+
+/// Returns the word at `at` as an offset or length.
+fn offset(data: &[u8], at: usize) -> Option<usize> {
+    usize::try_from(U256::from_be_bytes(word(data, at)?)).ok()
+}
+
+/// Returns the content of `bytes` or `string` encoded at the beginning of `data`.
+fn dynamic_bytes(data: &[u8]) -> Option<&[u8]> {
+    let len = offset(data, 0)?;
+    data.get(32..32usize.checked_add(len)?)
+}
+
+/// Parses a comma separated list of types.
+fn parse_list(list: &str) -> Result<Vec<AbiType>, AbiParseError> {
+    if list.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut types = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or(AbiParseError(list.to_string()))?
+            }
+            ',' if depth == 0 => {
+                types.push(AbiType::parse(&list[start..i])?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(AbiType::parse(&list[start..])?);
+    Ok(types)
+}
+
+/// Splits `(group)rest` into the content of the group and the rest.
+fn split_group(s: &str) -> Option<(&str, &str)> {
+    let s = s.strip_prefix('(')?;
+    let mut depth = 1usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&s[..i], &s[i + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, types: &[AbiType]) -> fmt::Result {
+    f.write_str("(")?;
+    for (i, ty) in types.iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{ty}")?;
+    }
+    f.write_str(")")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, bytes};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_signature() {
+        let function = AbiFunction::parse("transfer(address, uint)(bool)").unwrap();
+        assert_eq!(function.signature(), "transfer(address,uint256)");
+        assert_eq!(function.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(function.outputs, [AbiType::Bool]);
+
+        let function = AbiFunction::parse("f((uint8,bytes)[2],string[])").unwrap();
+        assert_eq!(function.signature(), "f((uint8,bytes)[2],string[])");
+        assert!(function.inputs.iter().all(AbiType::is_dynamic));
+
+        assert!(AbiFunction::parse("f(uint7)").is_err());
+        assert!(AbiFunction::parse("f(bytes33)").is_err());
+        assert!(AbiFunction::parse("(uint256)").is_err());
+        assert!(AbiFunction::parse("f(uint256").is_err());
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let registry = AbiRegistry::from_signatures([
+            "transfer(address,uint256)(bool)",
+            "f(int8,bytes,uint256[])",
+        ])
+        .unwrap();
+        let input = bytes!(
+            "a9059cbb"
+            "000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045"
+            "00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+        let mut output = [0; 32];
+        output[31] = 1;
+        let decoded = registry.decode_call(&input, Some(&output)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            json!({
+                "signature": "transfer(address,uint256)",
+                "inputs": [address!("0xd8da6bf26964af9d7eed9e03e53415d37aa96045").to_checksum(None), "1000"],
+                "outputs": [true],
+            })
+        );
+        // Truncated input.
+        assert!(registry.decode_call(&input[..40], None).is_none());
+        // Unknown selector.
+        assert!(registry.decode_call(&[0; 36], None).is_none());
+
+        let selector = AbiFunction::parse("f(int8,bytes,uint256[])")
+            .unwrap()
+            .selector();
+        let function = registry.get(selector).unwrap();
+        let data = bytes!(
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+            "0000000000000000000000000000000000000000000000000000000000000060"
+            "00000000000000000000000000000000000000000000000000000000000000a0"
+            "0000000000000000000000000000000000000000000000000000000000000002"
+            "beef000000000000000000000000000000000000000000000000000000000000"
+            "0000000000000000000000000000000000000000000000000000000000000002"
+            "0000000000000000000000000000000000000000000000000000000000000001"
+            "0000000000000000000000000000000000000000000000000000000000000002"
+        );
+        assert_eq!(
+            function.decode_inputs(&data).unwrap(),
+            [json!("-1"), json!("0xbeef"), json!(["1", "2"])]
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_reason() {
+        let output = bytes!(
+            "08c379a0"
+            "0000000000000000000000000000000000000000000000000000000000000020"
+            "0000000000000000000000000000000000000000000000000000000000000004"
+            "6e6f706500000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(decode_revert_reason(&output).as_deref(), Some("nope"));
+        assert_eq!(decode_revert_reason(&output[..40]), None);
+    }
+}
@@ -0,0 +1,251 @@
+//! AccessListUsageInspector - Inspector that reports which access list entries were used.
+use crate::inspector::Inspector;
+use context::{transaction::AccessListItemTr, ContextTr, Transaction};
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{InputsTr, Jumps},
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+};
+use primitives::{Address, HashMap, StorageKey};
+use state::bytecode::opcode;
+use std::vec::Vec;
+
+/// Usage of the transaction access list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessListUsage {
+    /// Number of accounts in the access list.
+    pub accounts: usize,
+    /// Number of access list accounts accessed during execution.
+    pub used_accounts: usize,
+    /// Number of storage slots in the access list.
+    pub slots: usize,
+    /// Number of access list storage slots accessed during execution.
+    pub used_slots: usize,
+}
+
+impl AccessListUsage {
+    /// Returns the share of used access list entries, accounts and slots counted together.
+    ///
+    /// Returns `1.0` for an empty access list.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.accounts + self.slots;
+        if total == 0 {
+            return 1.0;
+        }
+        (self.used_accounts + self.used_slots) as f64 / total as f64
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct AccountEntry {
+    used: bool,
+    slots: HashMap<StorageKey, bool>,
+}
+
+/// Inspector that records which accounts and storage slots of the transaction access list are
+/// accessed during execution.
+///
+/// Useful for evaluating generated access lists, unused entries only cost intrinsic gas.
+#[derive(Clone, Debug, Default)]
+pub struct AccessListUsageInspector {
+    entries: HashMap<Address, AccountEntry>,
+    /// Number of active frames, access list is loaded when the first frame starts.
+    depth: usize,
+}
+
+impl AccessListUsageInspector {
+    /// Create a new AccessListUsageInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns usage of the access list of the last inspected transaction.
+    pub fn usage(&self) -> AccessListUsage {
+        let mut usage = AccessListUsage {
+            accounts: self.entries.len(),
+            ..Default::default()
+        };
+        for entry in self.entries.values() {
+            usage.used_accounts += entry.used as usize;
+            usage.slots += entry.slots.len();
+            usage.used_slots += entry.slots.values().filter(|used| **used).count();
+        }
+        usage
+    }
+
+    /// Returns access list accounts that were not accessed.
+    pub fn unused_accounts(&self) -> Vec<Address> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| !entry.used)
+            .map(|(address, _)| *address)
+            .collect()
+    }
+
+    /// Returns access list storage slots that were not accessed.
+    pub fn unused_slots(&self) -> Vec<(Address, StorageKey)> {
+        self.entries
+            .iter()
+            .flat_map(|(address, entry)| {
+                entry
+                    .slots
+                    .iter()
+                    .filter(|(_, used)| !**used)
+                    .map(|(key, _)| (*address, *key))
+            })
+            .collect()
+    }
+
+    /// Clears recorded usage.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.depth = 0;
+    }
+
+    fn frame_start<CTX: ContextTr>(&mut self, context: &CTX) {
+        self.depth += 1;
+        if self.depth > 1 {
+            return;
+        }
+        self.entries.clear();
+        let Some(access_list) = context.tx().access_list() else {
+            return;
+        };
+        for item in access_list {
+            let entry = self.entries.entry(*item.address()).or_default();
+            for slot in item.storage_slots() {
+                entry.slots.insert(StorageKey::from_be_bytes(slot.0), false);
+            }
+        }
+    }
+
+    fn touch_account(&mut self, address: Address) {
+        if let Some(entry) = self.entries.get_mut(&address) {
+            entry.used = true;
+        }
+    }
+
+    fn touch_slot(&mut self, address: Address, key: StorageKey) {
+        if let Some(entry) = self.entries.get_mut(&address) {
+            entry.used = true;
+            if let Some(used) = entry.slots.get_mut(&key) {
+                *used = true;
+            }
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for AccessListUsageInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        match interp.bytecode.opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Ok(key) = interp.stack.peek(0) {
+                    self.touch_slot(interp.input.target_address(), key);
+                }
+            }
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => {
+                if let Ok(address) = interp.stack.peek(0) {
+                    self.touch_account(Address::from_word(address.into()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start(context);
+        self.touch_account(inputs.target_address);
+        self.touch_account(inputs.bytecode_address);
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    fn create(&mut self, context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start(context);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if let Some(address) = outcome.address {
+            self.touch_account(address);
+        }
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{
+        transaction::{AccessList, AccessListItem},
+        Context, TxEnv,
+    };
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{address, Bytes, TxKind, B256};
+    use state::bytecode::Bytecode;
+
+    #[test]
+    fn test_access_list_usage() {
+        // Reads slot 1 and stops.
+        let contract_data: Bytes =
+            Bytes::from(vec![opcode::PUSH1, 0x01, opcode::SLOAD, opcode::STOP]);
+        let unused = address!("0x1000000000000000000000000000000000000001");
+        let access_list = AccessList(vec![
+            AccessListItem {
+                address: BENCH_TARGET,
+                storage_keys: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+            },
+            AccessListItem {
+                address: unused,
+                storage_keys: vec![],
+            },
+        ]);
+
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(AccessListUsageInspector::new());
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .tx_type(Some(1))
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .access_list(access_list)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let usage = evm.inspector.usage();
+        assert_eq!(
+            usage,
+            AccessListUsage {
+                accounts: 2,
+                used_accounts: 1,
+                slots: 2,
+                used_slots: 1,
+            }
+        );
+        assert_eq!(usage.hit_rate(), 0.5);
+        assert_eq!(evm.inspector.unused_accounts(), vec![unused]);
+        assert_eq!(
+            evm.inspector.unused_slots(),
+            vec![(BENCH_TARGET, StorageKey::from(2))]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! BufferedInspector - Inspector that hands trace events to a non-blocking sink in batches.
+use crate::{inspector::Inspector, GasSettlement};
+use interpreter::{
+    interpreter_types::Jumps, CallInputs, CallOutcome, CreateInputs, CreateOutcome,
+    InstructionResult, Interpreter, InterpreterTypes,
+};
+use primitives::{Address, Bytes, Log, U256};
+use std::vec::Vec;
+
+/// Owned trace event produced by [`BufferedInspector`].
+///
+/// Events don't borrow the interpreter, so batches can be moved to another thread or task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// Instruction is about to be executed.
+    Step {
+        /// Depth of the frame, zero for the transaction frame.
+        depth: usize,
+        /// Program counter of the instruction.
+        pc: usize,
+        /// Opcode of the instruction.
+        opcode: u8,
+        /// Gas remaining in the frame before the instruction.
+        gas_remaining: u64,
+    },
+    /// Call frame is about to start.
+    Call {
+        /// Depth of the new frame.
+        depth: usize,
+        /// Caller of the call.
+        caller: Address,
+        /// Address whose storage is used by the call.
+        target: Address,
+        /// Address of the executed bytecode.
+        bytecode_address: Address,
+        /// Value transferred by the call.
+        value: U256,
+        /// Gas limit of the call.
+        gas_limit: u64,
+    },
+    /// Call frame has finished.
+    CallEnd {
+        /// Depth of the finished frame.
+        depth: usize,
+        /// Result of the call.
+        result: InstructionResult,
+        /// Gas spent by the call.
+        gas_spent: u64,
+        /// Returned data.
+        output: Bytes,
+    },
+    /// Create frame is about to start.
+    Create {
+        /// Depth of the new frame.
+        depth: usize,
+        /// Caller of the create.
+        caller: Address,
+        /// Value transferred to the created account.
+        value: U256,
+        /// Gas limit of the create.
+        gas_limit: u64,
+    },
+    /// Create frame has finished.
+    CreateEnd {
+        /// Depth of the finished frame.
+        depth: usize,
+        /// Result of the create.
+        result: InstructionResult,
+        /// Created address, if any.
+        address: Option<Address>,
+        /// Gas spent by the create.
+        gas_spent: u64,
+    },
+    /// Log was emitted.
+    Log(Log),
+    /// Contract was self-destructed.
+    Selfdestruct {
+        /// Self-destructed contract.
+        contract: Address,
+        /// Target of the remaining balance.
+        target: Address,
+        /// Transferred balance.
+        value: U256,
+    },
+    /// Gas and fees of the transaction were settled.
+    Settlement(GasSettlement),
+}
+
+/// Inspector that records [`TraceEvent`]s into a buffer and hands them to a sink in batches.
+///
+/// The sink is called with a full batch, at the end of every transaction and on
+/// [`BufferedInspector::flush`]. It must not block: tracers that write to async sinks, like gRPC
+/// or websocket streams, should forward the batch to a bounded channel with `try_send` and
+/// return the batch back if the channel is full. Rejected events stay buffered and are retried
+/// on the next flush, events that don't fit in `capacity` are dropped and counted in
+/// [`BufferedInspector::dropped`], so a slow consumer never stalls the interpreter thread.
+#[derive(Clone, Debug)]
+pub struct BufferedInspector<F> {
+    sink: F,
+    buffer: Vec<TraceEvent>,
+    batch_size: usize,
+    capacity: usize,
+    record_steps: bool,
+    depth: usize,
+    dropped: u64,
+}
+
+impl<F: FnMut(Vec<TraceEvent>) -> Result<(), Vec<TraceEvent>>> BufferedInspector<F> {
+    /// Creates a new inspector that delivers batches of `batch_size` events and buffers at most
+    /// `capacity` events while the sink rejects them.
+    pub fn new(sink: F, batch_size: usize, capacity: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            sink,
+            buffer: Vec::with_capacity(batch_size),
+            batch_size,
+            capacity: capacity.max(batch_size),
+            record_steps: true,
+            depth: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Sets whether [`TraceEvent::Step`] events are recorded, enabled by default.
+    pub fn with_steps(mut self, record_steps: bool) -> Self {
+        self.record_steps = record_steps;
+        self
+    }
+
+    /// Returns the number of events dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Returns the number of events waiting to be delivered.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Hands buffered events to the sink.
+    ///
+    /// Returns `false` if the sink rejected them, the events stay buffered in that case.
+    pub fn flush(&mut self) -> bool {
+        if self.buffer.is_empty() {
+            return true;
+        }
+        let batch = core::mem::replace(&mut self.buffer, Vec::with_capacity(self.batch_size));
+        match (self.sink)(batch) {
+            Ok(()) => true,
+            Err(batch) => {
+                self.buffer = batch;
+                false
+            }
+        }
+    }
+
+    /// Consumes the inspector and returns the sink and undelivered events.
+    pub fn into_parts(self) -> (F, Vec<TraceEvent>) {
+        (self.sink, self.buffer)
+    }
+
+    fn push(&mut self, event: TraceEvent) {
+        if self.buffer.len() >= self.capacity {
+            self.dropped += 1;
+            return;
+        }
+        self.buffer.push(event);
+        if self.buffer.len() >= self.batch_size {
+            self.flush();
+        }
+    }
+
+    fn frame_end(&mut self, event: TraceEvent) {
+        self.depth = self.depth.saturating_sub(1);
+        self.push(event);
+        if self.depth == 0 {
+            self.flush();
+        }
+    }
+}
+
+impl<CTX, INTR, F> Inspector<CTX, INTR> for BufferedInspector<F>
+where
+    INTR: InterpreterTypes,
+    F: FnMut(Vec<TraceEvent>) -> Result<(), Vec<TraceEvent>>,
+{
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        if !self.record_steps {
+            return;
+        }
+        self.push(TraceEvent::Step {
+            depth: self.depth.saturating_sub(1),
+            pc: interp.bytecode.pc(),
+            opcode: interp.bytecode.opcode(),
+            gas_remaining: interp.gas.remaining(),
+        });
+    }
+
+    fn log(&mut self, _interp: &mut Interpreter<INTR>, _context: &mut CTX, log: Log) {
+        self.push(TraceEvent::Log(log));
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.push(TraceEvent::Call {
+            depth: self.depth,
+            caller: inputs.caller,
+            target: inputs.target_address,
+            bytecode_address: inputs.bytecode_address,
+            value: inputs.transfer_value().unwrap_or_default(),
+            gas_limit: inputs.gas_limit,
+        });
+        self.depth += 1;
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frame_end(TraceEvent::CallEnd {
+            depth: self.depth.saturating_sub(1),
+            result: *outcome.instruction_result(),
+            gas_spent: outcome.gas().spent(),
+            output: outcome.output().clone(),
+        });
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.push(TraceEvent::Create {
+            depth: self.depth,
+            caller: inputs.caller,
+            value: inputs.value,
+            gas_limit: inputs.gas_limit,
+        });
+        self.depth += 1;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(TraceEvent::CreateEnd {
+            depth: self.depth.saturating_sub(1),
+            result: *outcome.instruction_result(),
+            address: outcome.address,
+            gas_spent: outcome.gas().spent(),
+        });
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.push(TraceEvent::Selfdestruct {
+            contract,
+            target,
+            value,
+        });
+    }
+
+    fn gas_settlement(&mut self, _context: &mut CTX, settlement: &GasSettlement) {
+        self.push(TraceEvent::Settlement(settlement.clone()));
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::TxKind;
+    use state::bytecode::{opcode, Bytecode};
+    use std::sync::mpsc;
+
+    fn bytecode() -> Bytecode {
+        Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::LOG0,
+                opcode::STOP,
+            ]
+            .into(),
+        )
+    }
+
+    fn tx(nonce: u64) -> TxEnv {
+        TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .nonce(nonce)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(100_000)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_buffered_channel() {
+        let (sender, receiver) = mpsc::sync_channel(16);
+        let inspector = BufferedInspector::new(
+            move |batch| {
+                sender.try_send(batch).map_err(|e| match e {
+                    mpsc::TrySendError::Full(batch) | mpsc::TrySendError::Disconnected(batch) => {
+                        batch
+                    }
+                })
+            },
+            2,
+            64,
+        );
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(bytecode()));
+        let mut evm = ctx.build_mainnet_with_inspector(inspector);
+        evm.inspect_one_tx(tx(0)).unwrap();
+        assert_eq!(evm.inspector.buffered(), 0);
+        drop(evm);
+
+        let batches: Vec<_> = receiver.iter().collect();
+        assert!(batches.iter().all(|batch| batch.len() <= 2));
+        let events: Vec<_> = batches.into_iter().flatten().collect();
+        // Call, four steps with the log, call end and settlement.
+        assert_eq!(events.len(), 8);
+        assert!(matches!(events[0], TraceEvent::Call { depth: 0, .. }));
+        assert!(matches!(
+            events[1],
+            TraceEvent::Step {
+                depth: 0,
+                pc: 0,
+                opcode: opcode::PUSH1,
+                ..
+            }
+        ));
+        assert!(matches!(events[4], TraceEvent::Log(_)));
+        assert!(matches!(
+            events[6],
+            TraceEvent::CallEnd {
+                result: InstructionResult::Stop,
+                ..
+            }
+        ));
+        assert!(matches!(events[7], TraceEvent::Settlement(_)));
+    }
+
+    #[test]
+    fn test_buffered_full_sink() {
+        let inspector = BufferedInspector::new(Err, 2, 4).with_steps(false);
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(bytecode()));
+        let mut evm = ctx.build_mainnet_with_inspector(inspector);
+        evm.inspect_one_tx(tx(0)).unwrap();
+        evm.inspect_one_tx(tx(1)).unwrap();
+
+        // Sink rejects everything, first four events are kept and the rest is dropped.
+        assert_eq!(evm.inspector.buffered(), 4);
+        assert_eq!(evm.inspector.dropped(), 4);
+        assert!(!evm.inspector.flush());
+    }
+}
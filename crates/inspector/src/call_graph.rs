@@ -0,0 +1,457 @@
+//! CallGraphInspector - Inspector that records the call tree and exports it as JSON or DOT.
+use crate::{inspector::Inspector, inspectors::TraceLimits};
+use core::fmt::Write;
+use interpreter::{
+    CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+    InterpreterTypes,
+};
+use primitives::{Address, U256};
+use serde::Serialize;
+
+/// Kind of the frame represented by a [`CallGraphNode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallGraphNodeKind {
+    /// `CALL` or a transaction call.
+    Call,
+    /// `CALLCODE`.
+    CallCode,
+    /// `DELEGATECALL`.
+    DelegateCall,
+    /// `STATICCALL`.
+    StaticCall,
+    /// `CREATE` or a create transaction.
+    Create,
+    /// `CREATE2`.
+    Create2,
+}
+
+impl CallGraphNodeKind {
+    /// Returns the opcode-like name of the kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Call => "CALL",
+            Self::CallCode => "CALLCODE",
+            Self::DelegateCall => "DELEGATECALL",
+            Self::StaticCall => "STATICCALL",
+            Self::Create => "CREATE",
+            Self::Create2 => "CREATE2",
+        }
+    }
+}
+
+impl From<CallScheme> for CallGraphNodeKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call => Self::Call,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall => Self::DelegateCall,
+            CallScheme::StaticCall => Self::StaticCall,
+        }
+    }
+}
+
+impl From<CreateScheme> for CallGraphNodeKind {
+    fn from(scheme: CreateScheme) -> Self {
+        match scheme {
+            CreateScheme::Create2 { .. } => Self::Create2,
+            _ => Self::Create,
+        }
+    }
+}
+
+/// Single frame in the [`CallGraph`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallGraphNode {
+    /// Index of the node inside [`CallGraph::nodes`].
+    pub id: usize,
+    /// Index of the parent node, `None` for the root frame.
+    pub parent: Option<usize>,
+    /// Depth of the frame, root frame has depth zero.
+    pub depth: usize,
+    /// Kind of the frame.
+    pub kind: CallGraphNodeKind,
+    /// Caller of the frame.
+    pub from: Address,
+    /// Address whose code the frame executes. For creates this is the created address, if creation succeeded.
+    pub to: Option<Address>,
+    /// Address whose storage and balance the frame runs against.
+    ///
+    /// Same as [`to`][Self::to] except for `DELEGATECALL` and `CALLCODE`, where it is the caller's own address.
+    pub target: Option<Address>,
+    /// Value of the call or endowment of the create.
+    pub value: U256,
+    /// Gas limit given to the frame.
+    pub gas_limit: u64,
+    /// Gas spent by the frame.
+    pub gas_used: u64,
+    /// Whether the frame finished successfully.
+    pub success: bool,
+}
+
+/// Call graph of a transaction, nodes are ordered in the order frames were entered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallGraph {
+    /// All recorded frames of the transaction.
+    pub nodes: Vec<CallGraphNode>,
+    /// Number of frames left out because of [`TraceLimits::max_frames`].
+    pub truncated_frames: usize,
+}
+
+impl CallGraph {
+    /// Returns `true` if frames were left out of the graph.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_frames > 0
+    }
+
+    /// Returns the root node of the graph.
+    pub fn root(&self) -> Option<&CallGraphNode> {
+        self.nodes.first()
+    }
+
+    /// Returns iterator over children of the node with the given id.
+    pub fn children(&self, id: usize) -> impl Iterator<Item = &CallGraphNode> {
+        self.nodes.iter().filter(move |n| n.parent == Some(id))
+    }
+
+    /// Serializes the graph to JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Serializes the graph to pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    ///
+    /// Nodes are labeled with the frame kind, executed code, value and gas used; failed frames are drawn in red.
+    /// Frames that run against a different account than their code (`DELEGATECALL`, `CALLCODE`) also show that account.
+    /// Truncated graphs get a note with the number of left out frames.
+    pub fn to_dot(&self) -> String {
+        let mut out =
+            String::from("digraph calls {\n    node [shape=box, fontname=\"monospace\"];\n");
+        if self.is_truncated() {
+            let _ = writeln!(
+                out,
+                "    truncated [shape=note, label=\"{} frames truncated\"];",
+                self.truncated_frames
+            );
+        }
+        for node in &self.nodes {
+            let to = node
+                .to
+                .map(|to| to.to_string())
+                .unwrap_or_else(|| "<failed>".to_string());
+            let _ = write!(
+                out,
+                "    n{} [label=\"{} {}\\nfrom: {}\\nvalue: {}\\ngas: {}/{}",
+                node.id,
+                node.kind.as_str(),
+                to,
+                node.from,
+                node.value,
+                node.gas_used,
+                node.gas_limit,
+            );
+            if node.target != node.to {
+                if let Some(target) = node.target {
+                    let _ = write!(out, "\\ntarget: {target}");
+                }
+            }
+            out.push('"');
+            if !node.success {
+                out.push_str(", color=red");
+            }
+            out.push_str("];\n");
+        }
+        for node in &self.nodes {
+            if let Some(parent) = node.parent {
+                let _ = writeln!(out, "    n{} -> n{};", parent, node.id);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Inspector that builds a [`CallGraph`] out of call and create frames.
+#[derive(Clone, Debug, Default)]
+pub struct CallGraphInspector {
+    graph: CallGraph,
+    limits: TraceLimits,
+    /// Indices of the currently open frames, `None` for frames that are not recorded.
+    stack: Vec<Option<usize>>,
+}
+
+impl CallGraphInspector {
+    /// Create a new CallGraphInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limits of the graph, only [`TraceLimits::max_frames`] applies.
+    pub fn with_limits(mut self, limits: TraceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the recorded call graph.
+    pub fn graph(&self) -> &CallGraph {
+        &self.graph
+    }
+
+    /// Consumes the inspector and returns the recorded call graph.
+    pub fn into_graph(self) -> CallGraph {
+        self.graph
+    }
+
+    /// Clears recorded frames so the inspector can be reused.
+    pub fn clear(&mut self) {
+        self.graph.nodes.clear();
+        self.graph.truncated_frames = 0;
+        self.stack.clear();
+    }
+
+    fn push_node(
+        &mut self,
+        kind: CallGraphNodeKind,
+        from: Address,
+        to: Option<Address>,
+        target: Option<Address>,
+        value: U256,
+        gas_limit: u64,
+    ) {
+        let id = self.graph.nodes.len();
+        if self.limits.exceeds_frames(id) {
+            self.graph.truncated_frames += 1;
+            self.stack.push(None);
+            return;
+        }
+        self.graph.nodes.push(CallGraphNode {
+            id,
+            parent: self.stack.last().copied().flatten(),
+            depth: self.stack.len(),
+            kind,
+            from,
+            to,
+            target,
+            value,
+            gas_limit,
+            gas_used: 0,
+            success: false,
+        });
+        self.stack.push(Some(id));
+    }
+
+    fn pop_node(&mut self) -> Option<&mut CallGraphNode> {
+        let id = self.stack.pop()??;
+        self.graph.nodes.get_mut(id)
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for CallGraphInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.push_node(
+            inputs.scheme.into(),
+            inputs.caller,
+            Some(inputs.bytecode_address),
+            Some(inputs.target_address),
+            inputs.call_value(),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if let Some(node) = self.pop_node() {
+            node.gas_used = outcome.result.gas.spent();
+            node.success = outcome.result.is_ok();
+        }
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.push_node(
+            inputs.scheme.into(),
+            inputs.caller,
+            None,
+            None,
+            inputs.value,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if let Some(node) = self.pop_node() {
+            node.gas_used = outcome.result.gas.spent();
+            node.success = outcome.result.is_ok();
+            node.to = outcome.address;
+            node.target = outcome.address;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::{opcode, Bytecode};
+
+    #[test]
+    fn test_call_graph_nested_call() {
+        // Calls identity precompile and stops.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x00, // retSize
+            opcode::PUSH1,
+            0x00, // retOffset
+            opcode::PUSH1,
+            0x00, // argsSize
+            opcode::PUSH1,
+            0x00, // argsOffset
+            opcode::PUSH1,
+            0x00, // value
+            opcode::PUSH1,
+            0x04, // address
+            opcode::GAS,
+            opcode::CALL,
+            opcode::STOP,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(CallGraphInspector::new());
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let graph = evm.inspector.graph();
+        assert_eq!(graph.nodes.len(), 2);
+        let root = graph.root().unwrap();
+        assert_eq!(root.parent, None);
+        assert_eq!(root.to, Some(BENCH_TARGET));
+        assert!(root.success);
+        let children: Vec<_> = graph.children(root.id).collect();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].depth, 1);
+        assert_eq!(children[0].kind, CallGraphNodeKind::Call);
+        assert!(root.gas_used > children[0].gas_used);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph calls {"));
+        assert!(dot.contains("n0 -> n1;"));
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(json["nodes"][1]["parent"], 0);
+        assert_eq!(json["nodes"][1]["kind"], "CALL");
+    }
+
+    #[test]
+    fn test_call_graph_delegate_call() {
+        // Delegate calls identity precompile and stops.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x00, // retSize
+            opcode::PUSH1,
+            0x00, // retOffset
+            opcode::PUSH1,
+            0x00, // argsSize
+            opcode::PUSH1,
+            0x00, // argsOffset
+            opcode::PUSH1,
+            0x04, // address
+            opcode::GAS,
+            opcode::DELEGATECALL,
+            opcode::STOP,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(CallGraphInspector::new());
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let graph = evm.inspector.graph();
+        let root = graph.root().unwrap();
+        assert_eq!(root.target, root.to);
+        let child = graph.children(root.id).next().unwrap();
+        assert_eq!(child.kind, CallGraphNodeKind::DelegateCall);
+        assert_eq!(child.from, BENCH_CALLER);
+        assert_eq!(
+            child.to,
+            Some(Address::with_last_byte(0x04)),
+            "node points at the executed code"
+        );
+        assert_eq!(child.target, Some(BENCH_TARGET));
+        assert!(graph
+            .to_dot()
+            .contains(&format!("\\ntarget: {BENCH_TARGET}\"")));
+    }
+
+    #[test]
+    fn test_call_graph_max_frames() {
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x04,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::STOP,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let inspector =
+            CallGraphInspector::new().with_limits(TraceLimits::default().with_max_frames(1));
+        let mut evm = ctx.build_mainnet_with_inspector(inspector);
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let graph = evm.inspector.graph();
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.root().unwrap().success);
+        assert_eq!(graph.truncated_frames, 1);
+        assert!(graph.to_dot().contains("1 frames truncated"));
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(json["truncatedFrames"], 1);
+    }
+}
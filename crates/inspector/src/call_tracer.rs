@@ -0,0 +1,363 @@
+//! CallTracer - Inspector that records the call tree in the format of the geth `callTracer`.
+use crate::{
+    inspector::Inspector,
+    inspectors::{decode_revert_reason, AbiRegistry, CallGraphNodeKind, DecodedCall},
+};
+use context::{result::HaltReason, ContextTr};
+use interpreter::{
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, InstructionResult, InterpreterResult,
+    InterpreterTypes, SuccessOrHalt,
+};
+use primitives::{Address, Bytes, U256};
+use serde::{Serialize, Serializer};
+use std::{format, string::String, sync::Arc, vec::Vec};
+
+/// Frame of the call tree recorded by the [`CallTracer`].
+///
+/// Serializes to the JSON format of the geth `callTracer`, with the call decoded by the
+/// [`AbiRegistry`] in the additional `decoded` field.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// Kind of the frame.
+    #[serde(rename = "type")]
+    pub kind: CallGraphNodeKind,
+    /// Caller of the frame.
+    pub from: Address,
+    /// Target of the frame. For creates this is the created address, if creation succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// Transferred value, `None` for `DELEGATECALL` and `STATICCALL`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// Gas limit given to the frame.
+    #[serde(serialize_with = "serialize_hex")]
+    pub gas: u64,
+    /// Gas spent by the frame.
+    #[serde(serialize_with = "serialize_hex")]
+    pub gas_used: u64,
+    /// Call data or init code.
+    pub input: Bytes,
+    /// Returned data or the code of the created contract.
+    #[serde(skip_serializing_if = "is_empty")]
+    pub output: Bytes,
+    /// Error if the frame did not succeed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Reason of the revert, decoded from an `Error(string)` revert.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Call decoded with the [`AbiRegistry`] of the tracer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedCall>,
+    /// Frames called by this frame.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    /// Serializes the frame to JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Serializes the frame to pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    fn finish(&mut self, result: &InterpreterResult, registry: Option<&AbiRegistry>) {
+        self.gas_used = result.gas.spent();
+        self.output = result.output.clone();
+        if !result.is_ok() {
+            self.error = Some(error_message(result.result));
+            if result.result.is_revert() {
+                self.revert_reason = decode_revert_reason(&self.output);
+            }
+        }
+        if let Some(registry) = registry.filter(|_| !self.is_create()) {
+            let output = result.is_ok().then_some(&self.output[..]);
+            self.decoded = registry.decode_call(&self.input, output);
+        }
+    }
+
+    fn is_create(&self) -> bool {
+        matches!(
+            self.kind,
+            CallGraphNodeKind::Create | CallGraphNodeKind::Create2
+        )
+    }
+}
+
+/// Inspector that records the call tree of a transaction as [`CallFrame`]s.
+///
+/// If an [`AbiRegistry`] is set, inputs and outputs of calls to known functions are decoded.
+#[derive(Clone, Debug, Default)]
+pub struct CallTracer {
+    registry: Option<Arc<AbiRegistry>>,
+    root: Option<CallFrame>,
+    /// Currently open frames.
+    stack: Vec<CallFrame>,
+}
+
+impl CallTracer {
+    /// Creates a new tracer without an ABI registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the registry used to decode calls.
+    pub fn with_abi_registry(mut self, registry: impl Into<Arc<AbiRegistry>>) -> Self {
+        self.registry = Some(registry.into());
+        self
+    }
+
+    /// Returns the root frame of the last traced transaction.
+    pub fn root(&self) -> Option<&CallFrame> {
+        self.root.as_ref()
+    }
+
+    /// Takes the root frame of the last traced transaction.
+    pub fn take_root(&mut self) -> Option<CallFrame> {
+        self.root.take()
+    }
+
+    /// Clears recorded frames so the tracer can be reused.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.stack.clear();
+    }
+
+    fn push_frame(
+        &mut self,
+        kind: CallGraphNodeKind,
+        from: Address,
+        to: Option<Address>,
+        value: Option<U256>,
+        gas: u64,
+        input: Bytes,
+    ) {
+        if self.stack.is_empty() {
+            self.root = None;
+        }
+        self.stack.push(CallFrame {
+            kind,
+            from,
+            to,
+            value,
+            gas,
+            gas_used: 0,
+            input,
+            output: Bytes::new(),
+            error: None,
+            revert_reason: None,
+            decoded: None,
+            calls: Vec::new(),
+        });
+    }
+
+    fn pop_frame(&mut self, result: &InterpreterResult, created: Option<Address>) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.finish(result, self.registry.as_deref());
+        if frame.is_create() {
+            frame.to = created;
+        }
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+}
+
+impl<CTX: ContextTr, INTR: InterpreterTypes> Inspector<CTX, INTR> for CallTracer {
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let input = inputs.input.bytes(context);
+        let kind = CallGraphNodeKind::from(inputs.scheme);
+        let value = match kind {
+            CallGraphNodeKind::DelegateCall | CallGraphNodeKind::StaticCall => None,
+            _ => Some(inputs.call_value()),
+        };
+        self.push_frame(
+            kind,
+            inputs.caller,
+            Some(inputs.target_address),
+            value,
+            inputs.gas_limit,
+            input,
+        );
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.pop_frame(&outcome.result, None);
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.push_frame(
+            inputs.scheme.into(),
+            inputs.caller,
+            None,
+            Some(inputs.value),
+            inputs.gas_limit,
+            inputs.init_code.clone(),
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.pop_frame(&outcome.result, outcome.address);
+    }
+}
+
+/// Returns the error message of the failed frame, matching geth where possible.
+fn error_message(result: InstructionResult) -> String {
+    match SuccessOrHalt::<HaltReason>::from(result) {
+        SuccessOrHalt::Revert => "execution reverted".into(),
+        SuccessOrHalt::Halt(HaltReason::OutOfGas(_)) => "out of gas".into(),
+        SuccessOrHalt::Halt(reason) => format!("{reason:?}"),
+        _ => format!("{result:?}"),
+    }
+}
+
+fn is_empty(bytes: &Bytes) -> bool {
+    bytes.is_empty()
+}
+
+fn serialize_hex<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{value:#x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{hex, TxKind};
+    use serde_json::json;
+    use state::bytecode::{opcode, Bytecode};
+
+    #[test]
+    fn test_call_tracer_decodes_calls() {
+        // Calls the identity precompile with the first argument and returns its output.
+        let contract_data = Bytes::from(vec![
+            opcode::CALLDATASIZE,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CALLDATACOPY,
+            opcode::PUSH1,
+            0x20, // retSize
+            opcode::PUSH1,
+            0x40, // retOffset
+            opcode::PUSH1,
+            0x20, // argsSize
+            opcode::PUSH1,
+            0x04, // argsOffset
+            opcode::PUSH1,
+            0x04, // address
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x40,
+            opcode::RETURN,
+        ]);
+        let registry = AbiRegistry::from_signatures(["balanceOf(address)(uint256)"]).unwrap();
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm =
+            ctx.build_mainnet_with_inspector(CallTracer::new().with_abi_registry(registry));
+        let input = hex!(
+            "70a08231"
+            "000000000000000000000000000000000000000000000000000000000000002a"
+        );
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .data(input.into())
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let root = evm.inspector.root().unwrap();
+        assert_eq!(root.kind, CallGraphNodeKind::Call);
+        assert_eq!(root.value, Some(U256::ZERO));
+        assert!(root.error.is_none());
+        // Output is the address argument, decoded as the balance.
+        assert_eq!(
+            root.decoded,
+            Some(DecodedCall {
+                signature: "balanceOf(address)".into(),
+                inputs: vec![json!(Address::with_last_byte(42).to_checksum(None))],
+                outputs: Some(vec![json!("42")]),
+            })
+        );
+        let [identity] = root.calls.as_slice() else {
+            panic!("expected one nested call");
+        };
+        assert_eq!(identity.kind, CallGraphNodeKind::StaticCall);
+        assert_eq!(identity.value, None);
+        assert!(identity.decoded.is_none());
+
+        let json: serde_json::Value = serde_json::from_str(&root.to_json().unwrap()).unwrap();
+        assert_eq!(json["type"], "CALL");
+        assert_eq!(json["gas"], format!("{:#x}", root.gas));
+        assert_eq!(json["decoded"]["outputs"][0], "42");
+        assert_eq!(json["calls"][0]["type"], "STATICCALL");
+        assert!(json["calls"][0].get("value").is_none());
+    }
+
+    #[test]
+    fn test_call_tracer_revert_reason() {
+        // Reverts with `Error("nope")`.
+        let revert = hex!(
+            "08c379a0"
+            "0000000000000000000000000000000000000000000000000000000000000020"
+            "0000000000000000000000000000000000000000000000000000000000000004"
+            "6e6f706500000000000000000000000000000000000000000000000000000000"
+        );
+        let mut code = Vec::new();
+        for (i, chunk) in revert.chunks(32).enumerate() {
+            code.push(opcode::PUSH32);
+            code.extend_from_slice(&[chunk, &[0; 32][chunk.len()..]].concat());
+            code.extend_from_slice(&[opcode::PUSH1, (i * 32) as u8, opcode::MSTORE]);
+        }
+        code.extend_from_slice(&[
+            opcode::PUSH1,
+            revert.len() as u8,
+            opcode::PUSH0,
+            opcode::REVERT,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())));
+        let mut evm = ctx.build_mainnet_with_inspector(CallTracer::new());
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let root = evm.inspector.take_root().unwrap();
+        assert_eq!(root.error.as_deref(), Some("execution reverted"));
+        assert_eq!(root.revert_reason.as_deref(), Some("nope"));
+        assert_eq!(root.output, Bytes::from(revert.to_vec()));
+        assert!(evm.inspector.root().is_none());
+    }
+}
@@ -0,0 +1,271 @@
+//! CreationReportInspector - Inspector that reports initcode and deployed code of every creation.
+use crate::inspector::Inspector;
+use context::{Cfg, ContextTr, JournalTr};
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{InputsTr, Jumps, LoopControl},
+    CreateInputs, CreateOutcome, CreateScheme, InstructionResult, Interpreter,
+};
+use primitives::Address;
+use state::bytecode::opcode;
+use std::vec::Vec;
+
+/// Contract creation with its code sizes and gas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreationReport {
+    /// Call depth of the creation, the transaction creation has depth zero.
+    pub depth: usize,
+    /// Creator of the contract.
+    pub caller: Address,
+    /// `CREATE`, `CREATE2` or custom scheme.
+    pub scheme: CreateScheme,
+    /// Created address, `None` if the creation did not start.
+    pub address: Option<Address>,
+    /// Size of the initcode.
+    pub initcode_size: usize,
+    /// Maximum initcode size, EIP-3860.
+    pub max_initcode_size: usize,
+    /// Gas limit of the creation frame.
+    pub gas_limit: u64,
+    /// Gas used by the creation frame, including the code deposit.
+    pub gas_used: u64,
+    /// Size of the code returned by the initcode, `None` if the initcode reverted or halted.
+    pub code_size: Option<usize>,
+    /// Maximum deployed code size, EIP-170.
+    pub max_code_size: usize,
+    /// Result of the creation.
+    pub result: InstructionResult,
+}
+
+impl CreationReport {
+    /// Returns `true` if the contract was deployed.
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Returns `true` if the initcode is larger than allowed.
+    pub fn exceeds_initcode_size(&self) -> bool {
+        self.initcode_size > self.max_initcode_size
+    }
+
+    /// Returns `true` if the returned code is larger than allowed.
+    pub fn exceeds_code_size(&self) -> bool {
+        self.code_size.is_some_and(|size| size > self.max_code_size)
+    }
+}
+
+/// Inspector that reports every contract creation with its initcode size, gas and deployed code
+/// size compared to the limits.
+///
+/// `CREATE` and `CREATE2` instructions rejected for initcode size are reported with zero gas.
+/// Creation transactions with oversized initcode fail validation and are not reported.
+#[derive(Clone, Debug, Default)]
+pub struct CreationReportInspector {
+    reports: Vec<CreationReport>,
+    /// Indices of reports of creations that did not end.
+    open: Vec<usize>,
+    /// Report of the current create instruction, in case it is rejected for initcode size.
+    pending: Option<CreationReport>,
+}
+
+impl CreationReportInspector {
+    /// Create a new CreationReportInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns reports in the order creations started.
+    pub fn reports(&self) -> &[CreationReport] {
+        &self.reports
+    }
+
+    /// Consumes the inspector and returns reports.
+    pub fn into_reports(self) -> Vec<CreationReport> {
+        self.reports
+    }
+
+    /// Clears reports.
+    pub fn clear(&mut self) {
+        self.reports.clear();
+        self.open.clear();
+        self.pending = None;
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for CreationReportInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, context: &mut CTX) {
+        let scheme = match interp.bytecode.opcode() {
+            opcode::CREATE => CreateScheme::Create,
+            opcode::CREATE2 => CreateScheme::Create2 {
+                salt: interp.stack.peek(3).unwrap_or_default(),
+            },
+            _ => return,
+        };
+        let Some(initcode_size) = interp
+            .stack
+            .peek(2)
+            .ok()
+            .and_then(|size| usize::try_from(size).ok())
+        else {
+            return;
+        };
+        self.pending = Some(CreationReport {
+            depth: context.journal_ref().depth(),
+            caller: interp.input.target_address(),
+            scheme,
+            initcode_size,
+            max_initcode_size: context.cfg().max_initcode_size(),
+            max_code_size: context.cfg().max_code_size(),
+            result: InstructionResult::CreateInitCodeSizeLimit,
+            ..Default::default()
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let Some(report) = self.pending.take() else {
+            return;
+        };
+        if interp.bytecode.instruction_result() == Some(InstructionResult::CreateInitCodeSizeLimit)
+        {
+            self.reports.push(report);
+        }
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.open.push(self.reports.len());
+        self.reports.push(CreationReport {
+            depth: context.journal_ref().depth(),
+            caller: inputs.caller,
+            scheme: inputs.scheme,
+            initcode_size: inputs.init_code.len(),
+            max_initcode_size: context.cfg().max_initcode_size(),
+            gas_limit: inputs.gas_limit,
+            max_code_size: context.cfg().max_code_size(),
+            ..Default::default()
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        let Some(report) = self
+            .open
+            .pop()
+            .and_then(|index| self.reports.get_mut(index))
+        else {
+            return;
+        };
+        let result = *outcome.instruction_result();
+        report.address = outcome.address;
+        report.gas_used = report.gas_limit.saturating_sub(outcome.gas().remaining());
+        report.result = result;
+        // Returned code is kept in the output when it is rejected.
+        report.code_size = (result.is_ok()
+            || matches!(
+                result,
+                InstructionResult::CreateContractSizeLimit
+                    | InstructionResult::CreateContractStartingWithEF
+            ))
+        .then(|| outcome.output().len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::BenchmarkDB;
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind, U256};
+    use state::bytecode::Bytecode;
+
+    #[test]
+    fn test_creation_report() {
+        // Initcode of the nested creation returns 0x6001 bytes: return(0, 0x6001)
+        let nested_initcode = [opcode::PUSH2, 0x60, 0x01, opcode::PUSH0, opcode::RETURN];
+        let mut initcode = vec![
+            // mstore(0, nested_initcode)
+            opcode::PUSH5,
+        ];
+        initcode.extend(nested_initcode);
+        initcode.extend([
+            opcode::PUSH0,
+            opcode::MSTORE,
+            // create2(0, 27, 5, 1)
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x05,
+            opcode::PUSH1,
+            27,
+            opcode::PUSH0,
+            opcode::CREATE2,
+            opcode::POP,
+            // create(0, 0, 0xc001)
+            opcode::PUSH2,
+            0xc0,
+            0x01,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CREATE,
+            // return(0, 1)
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]);
+        let initcode_size = initcode.len();
+
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::default()));
+        let mut evm = ctx.build_mainnet_with_inspector(CreationReportInspector::new());
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder_for_bench()
+                    .kind(TxKind::Create)
+                    .data(Bytes::from(initcode))
+                    .gas_limit(1_000_000)
+                    .build_fill(),
+            )
+            .unwrap();
+        // The create instruction with oversized initcode halts the transaction.
+        assert!(!result.is_success());
+
+        let reports = evm.inspector.reports();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].depth, 0);
+        assert_eq!(reports[0].initcode_size, initcode_size);
+        assert_eq!(reports[0].code_size, None);
+        assert_eq!(
+            reports[0].result,
+            InstructionResult::CreateInitCodeSizeLimit
+        );
+
+        assert_eq!(reports[1].depth, 1);
+        assert_eq!(
+            reports[1].scheme,
+            CreateScheme::Create2 {
+                salt: U256::from(1)
+            }
+        );
+        assert_eq!(reports[1].initcode_size, 5);
+        assert_eq!(reports[1].code_size, Some(0x6001));
+        assert!(reports[1].exceeds_code_size());
+        assert_eq!(
+            reports[1].result,
+            InstructionResult::CreateContractSizeLimit
+        );
+
+        assert_eq!(reports[2].depth, 1);
+        assert_eq!(reports[2].scheme, CreateScheme::Create);
+        assert_eq!(reports[2].address, None);
+        assert_eq!(reports[2].initcode_size, 0xc001);
+        assert!(reports[2].exceeds_initcode_size());
+        assert_eq!(reports[2].gas_used, 0);
+    }
+}
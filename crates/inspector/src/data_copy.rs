@@ -0,0 +1,192 @@
+//! DataCopyInspector - Inspector that accounts bytes copied by the `*COPY` instructions.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{Jumps, LoopControl},
+    Interpreter,
+};
+use state::bytecode::opcode;
+
+/// Number of instructions and bytes copied by one family of copy instructions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataCopy {
+    /// Number of executed instructions, including zero length copies.
+    pub count: u64,
+    /// Sum of copied bytes.
+    pub bytes: u64,
+}
+
+impl DataCopy {
+    fn record(&mut self, len: u64) {
+        self.count += 1;
+        self.bytes += len;
+    }
+}
+
+/// Data copied into memory by a transaction, by instruction family.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataCopyUsage {
+    /// Copied by `CALLDATACOPY`.
+    pub calldata: DataCopy,
+    /// Copied by `RETURNDATACOPY`.
+    pub returndata: DataCopy,
+    /// Copied by `CODECOPY` and `EXTCODECOPY`.
+    pub code: DataCopy,
+}
+
+impl DataCopyUsage {
+    /// Returns the sum of bytes copied by all instruction families.
+    pub fn total_bytes(&self) -> u64 {
+        self.calldata.bytes + self.returndata.bytes + self.code.bytes
+    }
+}
+
+/// Inspector that accounts number and size of calldata, returndata and code copies.
+///
+/// Copies of all frames are summed up. Instructions that fail are not accounted, copied
+/// bytes past the end of the source are zeros and are accounted like other bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DataCopyInspector {
+    usage: DataCopyUsage,
+    /// Family and length of the current instruction, accounted when it succeeds.
+    pending: Option<(Family, u64)>,
+}
+
+/// Family of the copy instruction.
+#[derive(Clone, Copy, Debug)]
+enum Family {
+    Calldata,
+    Returndata,
+    Code,
+}
+
+impl DataCopyInspector {
+    /// Create a new DataCopyInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the accounted usage.
+    pub fn usage(&self) -> &DataCopyUsage {
+        &self.usage
+    }
+
+    /// Clears accounted usage.
+    pub fn clear(&mut self) {
+        self.usage = DataCopyUsage::default();
+        self.pending = None;
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for DataCopyInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let (family, len) = match interp.bytecode.opcode() {
+            opcode::CALLDATACOPY => (Family::Calldata, 2),
+            opcode::RETURNDATACOPY => (Family::Returndata, 2),
+            opcode::CODECOPY => (Family::Code, 2),
+            opcode::EXTCODECOPY => (Family::Code, 3),
+            _ => return,
+        };
+        // Lengths that don't fit are out of gas and not accounted.
+        self.pending = interp
+            .stack
+            .peek(len)
+            .ok()
+            .and_then(|len| u64::try_from(len).ok())
+            .map(|len| (family, len));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let Some((family, len)) = self.pending.take() else {
+            return;
+        };
+        if interp
+            .bytecode
+            .instruction_result()
+            .is_some_and(|result| result.is_error())
+        {
+            return;
+        }
+        match family {
+            Family::Calldata => self.usage.calldata.record(len),
+            Family::Returndata => self.usage.returndata.record(len),
+            Family::Code => self.usage.code.record(len),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::Bytecode;
+
+    #[test]
+    fn test_data_copy() {
+        let contract_data: Bytes = Bytes::from(vec![
+            // calldatacopy(0, 0, 4)
+            opcode::PUSH1,
+            0x04,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CALLDATACOPY,
+            // codecopy(0, 0, 0x40)
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CODECOPY,
+            // extcodecopy(address(), 0, 0, 2)
+            opcode::PUSH1,
+            0x02,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::ADDRESS,
+            opcode::EXTCODECOPY,
+            // returndatacopy(0, 0, 0)
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::RETURNDATACOPY,
+            // returndatacopy(0, 0, 1) fails, there is no return data.
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::RETURNDATACOPY,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(DataCopyInspector::new());
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .data(Bytes::from_static(&[1, 2]))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_halt());
+
+        let usage = *evm.inspector.usage();
+        assert_eq!(usage.calldata, DataCopy { count: 1, bytes: 4 });
+        assert_eq!(
+            usage.code,
+            DataCopy {
+                count: 2,
+                bytes: 0x42
+            }
+        );
+        assert_eq!(usage.returndata, DataCopy { count: 1, bytes: 0 });
+        assert_eq!(usage.total_bytes(), 0x46);
+    }
+}
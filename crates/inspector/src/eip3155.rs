@@ -1,10 +1,10 @@
-use crate::inspectors::GasInspector;
+use crate::inspectors::{GasInspector, TraceLimits};
 use crate::Inspector;
 use context::{Cfg, ContextTr, JournalTr, Transaction};
 use interpreter::{
     interpreter_types::{Jumps, LoopControl, MemoryTr, StackTr},
     CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterResult,
-    InterpreterTypes, Stack,
+    InterpreterTypes,
 };
 use primitives::{hex, HashMap, B256, U256};
 use serde::Serialize;
@@ -26,6 +26,18 @@ pub struct TracerEip3155 {
     skip: bool,
     include_memory: bool,
     memory: Option<String>,
+    memory_truncated: bool,
+    limits: TraceLimits,
+    /// Whether each active frame is traced.
+    frames: Vec<bool>,
+    /// Number of frames entered in the transaction.
+    frame_count: usize,
+    /// Number of bytes written in the transaction.
+    written: usize,
+    frames_truncated: bool,
+    log_truncated: bool,
+    /// Buffer of the serialized line.
+    line: Vec<u8>,
 }
 
 impl std::fmt::Debug for TracerEip3155 {
@@ -42,6 +54,13 @@ impl std::fmt::Debug for TracerEip3155 {
             .field("skip", &self.skip)
             .field("include_memory", &self.include_memory)
             .field("memory", &self.memory)
+            .field("memory_truncated", &self.memory_truncated)
+            .field("limits", &self.limits)
+            .field("frames", &self.frames)
+            .field("frame_count", &self.frame_count)
+            .field("written", &self.written)
+            .field("frames_truncated", &self.frames_truncated)
+            .field("log_truncated", &self.log_truncated)
             .finish()
     }
 }
@@ -85,6 +104,9 @@ struct Output<'a> {
     /// Array of all allocated values
     #[serde(default, skip_serializing_if = "Option::is_none")]
     memory: Option<String>,
+    /// Whether `memory` was cut at [`TraceLimits::max_memory_bytes`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memory_truncated: Option<bool>,
     /// Array of all stored values
     #[serde(default, skip_serializing_if = "Option::is_none")]
     storage: Option<HashMap<String, String>>,
@@ -115,6 +137,20 @@ struct Summary {
     /// Name of the fork rules used for execution
     #[serde(default, skip_serializing_if = "Option::is_none")]
     fork: Option<String>,
+    /// Whether parts of the trace were left out because of [`TraceLimits`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated: Option<bool>,
+}
+
+// # Truncation marker
+// Written once for each limit of [`TraceLimits`] that is reached.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Truncated {
+    /// Name of the reached limit
+    truncated: &'static str,
+    /// Value of the reached limit
+    limit: usize,
 }
 
 impl TracerEip3155 {
@@ -138,6 +174,14 @@ impl TracerEip3155 {
             include_memory: false,
             stack: Default::default(),
             memory: Default::default(),
+            memory_truncated: false,
+            limits: TraceLimits::default(),
+            frames: Vec::new(),
+            frame_count: 0,
+            written: 0,
+            frames_truncated: false,
+            log_truncated: false,
+            line: Vec::new(),
             pc: 0,
             opcode: 0,
             gas: 0,
@@ -164,6 +208,18 @@ impl TracerEip3155 {
         self
     }
 
+    /// Sets the limits of the trace.
+    ///
+    /// When a limit is reached the tracer writes a `{"truncated":<limit name>,"limit":<value>}`
+    /// line and the summary gets a `"truncated":true` field. Steps of frames over
+    /// [`TraceLimits::max_frames`] and lines over [`TraceLimits::max_log_bytes`] are not written,
+    /// memory over [`TraceLimits::max_memory_bytes`] is cut and the step gets a
+    /// `"memoryTruncated":true` field. The summary and the markers are always written.
+    pub fn with_limits(mut self, limits: TraceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Resets the tracer to its initial state of [`Self::new`].
     ///
     /// This makes the inspector ready to be used again.
@@ -177,6 +233,12 @@ impl TracerEip3155 {
             refunded,
             mem_size,
             skip,
+            memory_truncated,
+            frames,
+            frame_count,
+            written,
+            frames_truncated,
+            log_truncated,
             ..
         } = self;
         *gas_inspector = GasInspector::new();
@@ -187,6 +249,12 @@ impl TracerEip3155 {
         *refunded = 0;
         *mem_size = 0;
         *skip = false;
+        *memory_truncated = false;
+        frames.clear();
+        *frame_count = 0;
+        *written = 0;
+        *frames_truncated = false;
+        *log_truncated = false;
     }
 
     fn print_summary(&mut self, result: &InterpreterResult, context: &mut impl ContextTr) {
@@ -202,6 +270,7 @@ impl TracerEip3155 {
             pass: result.is_ok(),
             time: None,
             fork: Some(spec.to_string()),
+            truncated: (self.frames_truncated || self.log_truncated).then_some(true),
         };
         let _ = self.write_value(&value);
     }
@@ -209,22 +278,55 @@ impl TracerEip3155 {
     fn write_value(&mut self, value: &impl serde::Serialize) -> std::io::Result<()> {
         write_value(&mut *self.output, value)
     }
-}
 
-pub trait CloneStack {
-    fn clone_into(&self, stack: &mut Vec<U256>);
-}
+    /// Writes the serialized line in [`Self::line`] if it fits in [`TraceLimits::max_log_bytes`].
+    fn write_line(&mut self) -> std::io::Result<()> {
+        let len = self.line.len() + 1;
+        if let Some(limit) = self.limits.max_log_bytes {
+            if self.log_truncated {
+                return Ok(());
+            }
+            if self.written + len > limit {
+                self.log_truncated = true;
+                return self.write_value(&Truncated {
+                    truncated: "maxLogBytes",
+                    limit,
+                });
+            }
+        }
+        self.written += len;
+        self.output.write_all(&self.line)?;
+        self.output.write_all(b"\n")
+    }
 
-impl CloneStack for Stack {
-    fn clone_into(&self, stack: &mut Vec<U256>) {
-        stack.extend_from_slice(self.data());
+    fn frame_start(&mut self) {
+        let traced = !self.limits.exceeds_frames(self.frame_count);
+        self.frame_count += 1;
+        self.frames.push(traced);
+        if !traced && !self.frames_truncated {
+            self.frames_truncated = true;
+            let _ = self.write_value(&Truncated {
+                truncated: "maxFrames",
+                limit: self.limits.max_frames.unwrap_or_default(),
+            });
+        }
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult, context: &mut impl ContextTr) {
+        self.frames.pop();
+        if context.journal_mut().depth() == 0 {
+            self.print_summary(result, context);
+            let _ = self.output.flush();
+            // Clear the state if we are at the top level.
+            self.clear();
+        }
     }
 }
 
 impl<CTX, INTR> Inspector<CTX, INTR> for TracerEip3155
 where
     CTX: ContextTr,
-    INTR: InterpreterTypes<Stack: StackTr + CloneStack>,
+    INTR: InterpreterTypes<Stack: StackTr>,
 {
     fn initialize_interp(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
         self.gas_inspector.initialize_interp(&interp.gas);
@@ -232,11 +334,21 @@ where
 
     fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
         self.gas_inspector.step(&interp.gas);
+        if self.frames.last() == Some(&false) {
+            self.skip = true;
+            return;
+        }
         self.stack.clear();
-        interp.stack.clone_into(&mut self.stack);
+        self.stack.extend_from_slice(interp.stack.as_slice());
         self.memory = if self.include_memory {
+            let size = interp.memory.size();
+            let captured = self
+                .limits
+                .max_memory_bytes
+                .map_or(size, |max| size.min(max));
+            self.memory_truncated = captured < size;
             Some(hex::encode_prefixed(
-                interp.memory.slice(0..interp.memory.size()).as_ref(),
+                interp.memory.slice(0..captured).as_ref(),
             ))
         } else {
             None
@@ -274,32 +386,35 @@ where
                 .and_then(|a| a.instruction_result())
                 .map(|ir| format!("{ir:?}")),
             memory: self.memory.take(),
+            memory_truncated: self.memory_truncated.then_some(true),
             storage: None,
             return_stack: None,
         };
-        let _ = write_value(&mut self.output, &value);
+        let mut line = core::mem::take(&mut self.line);
+        line.clear();
+        let _ = serde_json::to_writer(&mut line, &value);
+        self.line = line;
+        let _ = self.write_line();
+    }
+
+    fn call(&mut self, _: &mut CTX, _: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start();
+        None
     }
 
     fn call_end(&mut self, context: &mut CTX, _: &CallInputs, outcome: &mut CallOutcome) {
         self.gas_inspector.call_end(outcome);
+        self.frame_end(&outcome.result, context);
+    }
 
-        if context.journal_mut().depth() == 0 {
-            self.print_summary(&outcome.result, context);
-            let _ = self.output.flush();
-            // Clear the state if we are at the top level.
-            self.clear();
-        }
+    fn create(&mut self, _: &mut CTX, _: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
     }
 
     fn create_end(&mut self, context: &mut CTX, _: &CreateInputs, outcome: &mut CreateOutcome) {
         self.gas_inspector.create_end(outcome);
-
-        if context.journal_mut().depth() == 0 {
-            self.print_summary(&outcome.result, context);
-            let _ = self.output.flush();
-            // Clear the state if we are at the top level.
-            self.clear();
-        }
+        self.frame_end(&outcome.result, context);
     }
 }
 
@@ -314,3 +429,117 @@ fn write_value(
 fn serde_hex_u64<S: serde::Serializer>(n: &u64, serializer: S) -> Result<S::Ok, S::Error> {
     serializer.serialize_str(&format!("{:#x}", *n))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::{opcode, Bytecode};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Default)]
+    struct SharedWriter(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Stores a word in memory, calls the identity precompile and returns the traced lines.
+    fn trace(limits: TraceLimits) -> Vec<serde_json::Value> {
+        let code = vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x04,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::STOP,
+        ];
+        let writer = SharedWriter::default();
+        let tracer = TracerEip3155::new(Box::new(writer.clone()))
+            .with_memory()
+            .with_limits(limits);
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+            Bytes::from(code),
+        )));
+        let mut evm = ctx.build_mainnet_with_inspector(tracer);
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let output = writer.0.borrow();
+        output
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_unlimited_trace() {
+        let lines = trace(TraceLimits::unlimited());
+        // 12 steps and the summary.
+        assert_eq!(lines.len(), 13);
+        assert!(lines.iter().all(|line| line.get("truncated").is_none()));
+        assert_eq!(lines[11]["memory"].as_str().unwrap().len(), 2 + 64);
+    }
+
+    #[test]
+    fn test_truncated_memory() {
+        let lines = trace(TraceLimits::default().with_max_memory_bytes(4));
+        assert_eq!(lines[11]["memory"], "0x00000000");
+        assert_eq!(lines[11]["memoryTruncated"], true);
+        assert!(lines[0].get("memoryTruncated").is_none());
+    }
+
+    #[test]
+    fn test_truncated_frames() {
+        let lines = trace(TraceLimits::default().with_max_frames(0));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["truncated"], "maxFrames");
+        assert_eq!(lines[0]["limit"], 0);
+        assert_eq!(lines[1]["truncated"], true);
+        assert_eq!(lines[1]["pass"], true);
+    }
+
+    #[test]
+    fn test_truncated_log() {
+        let full = trace(TraceLimits::unlimited());
+        let first_line = serde_json::to_string(&full[0]).unwrap().len() + 1;
+        let lines = trace(TraceLimits::default().with_max_log_bytes(first_line));
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], full[0]);
+        assert_eq!(lines[1]["truncated"], "maxLogBytes");
+        assert_eq!(lines[2]["truncated"], true);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::inspector::Inspector;
+use crate::{inspector::Inspector, GasSettlement};
 use either::Either;
+use handler::Withdrawal;
 use interpreter::{
     CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterTypes,
 };
@@ -86,6 +87,22 @@ where
             Either::Right(inspector) => inspector.selfdestruct(contract, target, value),
         }
     }
+
+    #[inline]
+    fn gas_settlement(&mut self, context: &mut CTX, settlement: &GasSettlement) {
+        match self {
+            Either::Left(inspector) => inspector.gas_settlement(context, settlement),
+            Either::Right(inspector) => inspector.gas_settlement(context, settlement),
+        }
+    }
+
+    #[inline]
+    fn withdrawal(&mut self, context: &mut CTX, withdrawal: &Withdrawal) {
+        match self {
+            Either::Left(inspector) => inspector.withdrawal(context, withdrawal),
+            Either::Right(inspector) => inspector.withdrawal(context, withdrawal),
+        }
+    }
 }
 
 #[cfg(test)]
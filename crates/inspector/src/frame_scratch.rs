@@ -0,0 +1,292 @@
+//! Per-frame scratch data of inspectors.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter::EthInterpreter, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+    InterpreterResult, InterpreterTypes,
+};
+use primitives::{Address, Log, U256};
+use std::vec::Vec;
+
+/// Inputs of the frame that is starting, passed to [`FrameScratchInspector::frame_start`].
+#[derive(Clone, Copy, Debug)]
+pub enum FrameStart<'a> {
+    /// Call frame.
+    Call(&'a CallInputs),
+    /// Create frame.
+    Create(&'a CreateInputs),
+}
+
+/// Inspector with typed scratch data for every frame.
+///
+/// Scratch data is created by [`FrameScratchInspector::frame_start`] when a frame starts, passed
+/// to the hooks of instructions of that frame and handed back to
+/// [`FrameScratchInspector::frame_end`] together with the scratch data of the parent frame when
+/// the frame ends. Wrap the inspector in [`WithFrameScratch`] to use it as an [`Inspector`].
+pub trait FrameScratchInspector<CTX, INTR: InterpreterTypes = EthInterpreter> {
+    /// Scratch data of a frame.
+    type Scratch;
+
+    /// Creates the scratch data of the starting frame.
+    fn frame_start(&mut self, context: &mut CTX, frame: FrameStart<'_>) -> Self::Scratch;
+
+    /// Called before the instruction is executed, see [`Inspector::step`].
+    #[inline]
+    fn step(
+        &mut self,
+        interp: &mut Interpreter<INTR>,
+        context: &mut CTX,
+        scratch: &mut Self::Scratch,
+    ) {
+        let _ = interp;
+        let _ = context;
+        let _ = scratch;
+    }
+
+    /// Called after the instruction is executed, see [`Inspector::step_end`].
+    #[inline]
+    fn step_end(
+        &mut self,
+        interp: &mut Interpreter<INTR>,
+        context: &mut CTX,
+        scratch: &mut Self::Scratch,
+    ) {
+        let _ = interp;
+        let _ = context;
+        let _ = scratch;
+    }
+
+    /// Called when a log is emitted, see [`Inspector::log`].
+    #[inline]
+    fn log(
+        &mut self,
+        interp: &mut Interpreter<INTR>,
+        context: &mut CTX,
+        scratch: &mut Self::Scratch,
+        log: Log,
+    ) {
+        let _ = interp;
+        let _ = context;
+        let _ = scratch;
+        let _ = log;
+    }
+
+    /// Called when the frame ends with the scratch data of the frame and of its parent frame.
+    ///
+    /// Parent is `None` for the transaction frame. Scratch data is dropped after the call.
+    #[inline]
+    fn frame_end(
+        &mut self,
+        context: &mut CTX,
+        scratch: Self::Scratch,
+        parent: Option<&mut Self::Scratch>,
+        result: &InterpreterResult,
+    ) {
+        let _ = context;
+        let _ = scratch;
+        let _ = parent;
+        let _ = result;
+    }
+
+    /// Called when a contract is self-destructed, see [`Inspector::selfdestruct`].
+    #[inline]
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        let _ = contract;
+        let _ = target;
+        let _ = value;
+    }
+}
+
+/// [`Inspector`] that keeps the stack of scratch data of a [`FrameScratchInspector`].
+///
+/// The wrapped inspector can't override frames, the wrapper always lets them execute.
+#[derive(Clone, Debug)]
+pub struct WithFrameScratch<I, S> {
+    inner: I,
+    scratch: Vec<S>,
+}
+
+impl<I, S> WithFrameScratch<I, S> {
+    /// Wraps the inspector.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Returns the wrapped inspector.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped inspector.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped inspector.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    /// Returns the scratch data of the active frames, the transaction frame is the first one.
+    pub fn scratch(&self) -> &[S] {
+        &self.scratch
+    }
+
+    fn end<CTX, INTR>(&mut self, context: &mut CTX, result: &InterpreterResult)
+    where
+        INTR: InterpreterTypes,
+        I: FrameScratchInspector<CTX, INTR, Scratch = S>,
+    {
+        if let Some(scratch) = self.scratch.pop() {
+            self.inner
+                .frame_end(context, scratch, self.scratch.last_mut(), result);
+        }
+    }
+}
+
+impl<CTX, INTR, I> Inspector<CTX, INTR> for WithFrameScratch<I, I::Scratch>
+where
+    INTR: InterpreterTypes,
+    I: FrameScratchInspector<CTX, INTR>,
+{
+    fn step(&mut self, interp: &mut Interpreter<INTR>, context: &mut CTX) {
+        if let Some(scratch) = self.scratch.last_mut() {
+            self.inner.step(interp, context, scratch);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, context: &mut CTX) {
+        if let Some(scratch) = self.scratch.last_mut() {
+            self.inner.step_end(interp, context, scratch);
+        }
+    }
+
+    fn log(&mut self, interp: &mut Interpreter<INTR>, context: &mut CTX, log: Log) {
+        if let Some(scratch) = self.scratch.last_mut() {
+            self.inner.log(interp, context, scratch, log);
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let scratch = self.inner.frame_start(context, FrameStart::Call(inputs));
+        self.scratch.push(scratch);
+        None
+    }
+
+    fn call_end(&mut self, context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.end(context, &outcome.result);
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        let scratch = self.inner.frame_start(context, FrameStart::Create(inputs));
+        self.scratch.push(scratch);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.end(context, &outcome.result);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.inner.selfdestruct(contract, target, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::{opcode, Bytecode};
+
+    #[derive(Default)]
+    struct StepCount {
+        own: u64,
+        inclusive: u64,
+    }
+
+    /// Counts steps of every frame, with and without the steps of child frames.
+    #[derive(Default)]
+    struct StepAttribution {
+        /// Call depth, own and inclusive steps of ended frames.
+        frames: Vec<(usize, u64, u64)>,
+        depth: usize,
+    }
+
+    impl<CTX> FrameScratchInspector<CTX> for StepAttribution {
+        type Scratch = StepCount;
+
+        fn frame_start(&mut self, _context: &mut CTX, _frame: FrameStart<'_>) -> StepCount {
+            self.depth += 1;
+            StepCount::default()
+        }
+
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut CTX, scratch: &mut StepCount) {
+            scratch.own += 1;
+            scratch.inclusive += 1;
+        }
+
+        fn frame_end(
+            &mut self,
+            _context: &mut CTX,
+            scratch: StepCount,
+            parent: Option<&mut StepCount>,
+            _result: &InterpreterResult,
+        ) {
+            self.depth -= 1;
+            if let Some(parent) = parent {
+                parent.inclusive += scratch.inclusive;
+            }
+            self.frames
+                .push((self.depth, scratch.own, scratch.inclusive));
+        }
+    }
+
+    #[test]
+    fn test_frame_scratch() {
+        // Without calldata the contract calls itself with one byte of calldata.
+        let code = Bytes::from(vec![
+            opcode::CALLDATASIZE,
+            opcode::PUSH1,
+            14,
+            opcode::JUMPI,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            1,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::ADDRESS,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]);
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(code)));
+        let mut evm =
+            ctx.build_mainnet_with_inspector(WithFrameScratch::new(StepAttribution::default()));
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert!(evm.inspector.scratch().is_empty());
+        assert_eq!(evm.inspector.inner().frames, [(1, 5, 5), (0, 14, 19)]);
+    }
+}
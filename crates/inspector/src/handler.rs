@@ -1,12 +1,15 @@
-use crate::{Inspector, InspectorEvmTr, JournalExt};
-use context::{result::ExecutionResult, ContextTr, JournalEntry, Transaction};
-use handler::{evm::FrameTr, EvmTr, FrameResult, Handler, ItemOrResult};
+use crate::{BalanceCredits, GasSettlement, Inspector, InspectorEvmTr, JournalExt};
+use context::{
+    cfg::FeeRecipient, result::ExecutionResult, Block, ContextTr, JournalEntry, Transaction,
+};
+use handler::{evm::FrameTr, post_execution, EvmTr, FrameResult, Handler, ItemOrResult};
 use interpreter::{
     instructions::InstructionTable,
     interpreter_types::{Jumps, LoopControl},
     FrameInput, Host, InitialAndFloorGas, InstructionResult, Interpreter, InterpreterAction,
     InterpreterTypes,
 };
+use primitives::U256;
 use state::bytecode::opcode;
 
 /// Trait that extends [`Handler`] with inspection functionality.
@@ -22,6 +25,7 @@ use state::bytecode::opcode;
 /// * [`Handler::run`] replaced with [`InspectorHandler::inspect_run`]
 /// * [`Handler::run_without_catch_error`] replaced with [`InspectorHandler::inspect_run_without_catch_error`]
 /// * [`Handler::execution`] replaced with [`InspectorHandler::inspect_execution`]
+/// * [`Handler::post_execution`] replaced with [`InspectorHandler::inspect_post_execution`]
 /// * [`Handler::run_exec_loop`] replaced with [`InspectorHandler::inspect_run_exec_loop`]
 ///   * `run_exec_loop` calls `inspect_frame_init` and `inspect_frame_run` that call inspector inside.
 pub trait InspectorHandler: Handler
@@ -52,10 +56,9 @@ where
         &mut self,
         evm: &mut Self::Evm,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
-        let init_and_floor_gas = self.validate(evm)?;
-        let eip7702_refund = self.pre_execution(evm)? as i64;
+        let (init_and_floor_gas, eip7702_refund) = self.validate_and_pre_execution(evm)?;
         let mut frame_result = self.inspect_execution(evm, &init_and_floor_gas)?;
-        self.post_execution(evm, &mut frame_result, init_and_floor_gas, eip7702_refund)?;
+        self.inspect_post_execution(evm, &mut frame_result, init_and_floor_gas, eip7702_refund)?;
         self.execution_result(evm, frame_result)
     }
 
@@ -79,6 +82,78 @@ where
         Ok(frame_result)
     }
 
+    /// Runs post execution and reports the gas settlement to the inspector.
+    ///
+    /// This method acts as [`Handler::post_execution`] method for inspection. Balances credited by
+    /// [`Handler::reimburse_caller`] and [`Handler::reward_beneficiary`] are captured from the
+    /// journal and passed to [`InspectorHandler::gas_settlement`].
+    fn inspect_post_execution(
+        &mut self,
+        evm: &mut Self::Evm,
+        exec_result: &mut FrameResult,
+        init_and_floor_gas: InitialAndFloorGas,
+        eip7702_refund: i64,
+    ) -> Result<(), Self::Error> {
+        // Calculate final refund and add EIP-7702 refund to gas.
+        self.refund(evm, exec_result, eip7702_refund);
+        // Ensure gas floor is met and minimum floor gas is spent.
+        self.eip7623_check_gas_floor(evm, exec_result, init_and_floor_gas);
+        // Return unused gas to caller
+        let checkpoint = evm.ctx_ref().journal_ref().journal().len();
+        self.reimburse_caller(evm, exec_result)?;
+        let caller_credits = balance_credits(evm.ctx_ref(), checkpoint);
+        // Pay transaction fees to beneficiary
+        let checkpoint = evm.ctx_ref().journal_ref().journal().len();
+        self.reward_beneficiary(evm, exec_result)?;
+        let fee_credits = balance_credits(evm.ctx_ref(), checkpoint);
+
+        let settlement = self.gas_settlement(evm, exec_result, &caller_credits, &fee_credits)?;
+        let (context, inspector) = evm.ctx_inspector();
+        inspector.gas_settlement(context, &settlement);
+        Ok(())
+    }
+
+    /// Builds the gas settlement out of the balances credited by [`Handler::reimburse_caller`]
+    /// and [`Handler::reward_beneficiary`].
+    ///
+    /// Credits of the reimbursement are reported as the caller refund. Credits of the reward are
+    /// split between the beneficiary and other fee recipients. The burned base fee is not a
+    /// balance change and is derived from the [`FeePolicy`](context::cfg::FeePolicy), handlers
+    /// that handle the base fee differently should override it to match.
+    fn gas_settlement(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &FrameResult,
+        caller_credits: &BalanceCredits,
+        fee_credits: &BalanceCredits,
+    ) -> Result<GasSettlement, Self::Error> {
+        let context = evm.ctx_ref();
+        let gas = exec_result.gas();
+        let basefee = context.block().basefee() as u128;
+        let beneficiary = context.block().beneficiary();
+        let burned = post_execution::fee_distribution(context, gas)
+            .into_iter()
+            .filter(|(recipient, _)| *recipient == FeeRecipient::Burn)
+            .fold(U256::ZERO, |acc, (_, amount)| acc + amount);
+        Ok(GasSettlement {
+            gas_limit: gas.limit(),
+            gas_spent: gas.spent(),
+            gas_refunded: gas.refunded() as u64,
+            gas_used: gas.used(),
+            effective_gas_price: context.tx().effective_gas_price(basefee),
+            caller: context.tx().caller(),
+            caller_refund: caller_credits.total(),
+            beneficiary,
+            beneficiary_reward: fee_credits.amount(beneficiary),
+            burned,
+            fee_payments: fee_credits
+                .iter()
+                .filter(|payment| payment.recipient != beneficiary)
+                .copied()
+                .collect(),
+        })
+    }
+
     /* FRAMES */
 
     /// Run inspection on execution loop.
@@ -123,6 +198,15 @@ where
     }
 }
 
+/// Returns the balances credited by the journal entries added after `checkpoint`.
+fn balance_credits<CTX: ContextTr<Journal: JournalExt>>(
+    context: &CTX,
+    checkpoint: usize,
+) -> BalanceCredits {
+    let journal = context.journal_ref();
+    BalanceCredits::from_journal(&journal.journal()[checkpoint..], journal.evm_state())
+}
+
 /// Handles the start of a frame by calling the appropriate inspector method.
 pub fn frame_start<CTX, INTR: InterpreterTypes>(
     context: &mut CTX,
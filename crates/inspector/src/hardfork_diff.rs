@@ -0,0 +1,403 @@
+//! Differential execution of a transaction under two hardforks.
+use crate::{inspector::Inspector, InspectEvm};
+use context::{
+    result::{
+        diff::{diff_results, Change, ResultDiff},
+        EVMError,
+    },
+    BlockEnv, CfgEnv, Context, ContextTr, JournalTr, TxEnv,
+};
+use core::fmt;
+use database_interface::Database;
+use handler::{MainBuilder, MainContext};
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{InputsTr, Jumps, LoopControl},
+    InstructionResult, Interpreter,
+};
+use primitives::{hardfork::SpecId, Address, StorageKey, StorageValue};
+use state::bytecode::opcode;
+use std::vec::Vec;
+
+/// Instruction executed by the transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceStep {
+    /// Call depth of the frame, the transaction frame has depth zero.
+    pub depth: usize,
+    /// Address of the executing contract.
+    pub address: Address,
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Opcode of the instruction.
+    pub opcode: u8,
+    /// Gas remaining in the frame before the instruction.
+    pub gas_remaining: u64,
+    /// Gas charged by the instruction, including the gas forwarded to a new frame.
+    pub gas_cost: u64,
+    /// Result of the instruction if it ended the frame.
+    pub result: Option<InstructionResult>,
+}
+
+impl TraceStep {
+    /// Returns `true` if both steps execute the same instruction at the same location with the
+    /// same result, gas may differ.
+    pub fn same_instruction(&self, other: &Self) -> bool {
+        (self.depth, self.address, self.pc, self.opcode, self.result)
+            == (
+                other.depth,
+                other.address,
+                other.pc,
+                other.opcode,
+                other.result,
+            )
+    }
+}
+
+/// Storage slot written by `SSTORE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageWrite {
+    /// Address of the contract.
+    pub address: Address,
+    /// Key of the slot.
+    pub key: StorageKey,
+    /// Written value.
+    pub value: StorageValue,
+}
+
+/// Instructions and storage writes of a transaction, recorded by [`HardforkTraceInspector`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HardforkTrace {
+    /// Executed instructions, in order.
+    pub steps: Vec<TraceStep>,
+    /// Storage writes, in order, including writes of reverted frames.
+    pub writes: Vec<StorageWrite>,
+}
+
+impl HardforkTrace {
+    /// Returns the first instruction where the traces diverge.
+    pub fn first_step_divergence(&self, other: &Self) -> Option<StepDivergence> {
+        let len = self.steps.len().max(other.steps.len());
+        (0..len).find_map(|index| {
+            let step = Change::new(self.steps.get(index), other.steps.get(index))?;
+            let kind = match (step.left, step.right) {
+                (Some(left), Some(right)) if left.same_instruction(right) => {
+                    StepDivergenceKind::Gas
+                }
+                _ => StepDivergenceKind::Instruction,
+            };
+            Some(StepDivergence {
+                index,
+                kind,
+                step: Change {
+                    left: step.left.copied(),
+                    right: step.right.copied(),
+                },
+            })
+        })
+    }
+
+    /// Returns the first storage write where the traces diverge.
+    pub fn first_write_divergence(&self, other: &Self) -> Option<WriteDivergence> {
+        let len = self.writes.len().max(other.writes.len());
+        (0..len).find_map(|index| {
+            let write = Change::new(
+                self.writes.get(index).copied(),
+                other.writes.get(index).copied(),
+            )?;
+            Some(WriteDivergence { index, write })
+        })
+    }
+}
+
+/// How the first diverging instructions differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepDivergenceKind {
+    /// Different instructions are executed, the instruction has a different result or one
+    /// trace has ended.
+    Instruction,
+    /// Same instruction is executed with different remaining gas or gas cost.
+    Gas,
+}
+
+/// First instruction where the traces diverge.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepDivergence {
+    /// Position of the instruction in the traces.
+    pub index: usize,
+    /// How the instructions differ.
+    pub kind: StepDivergenceKind,
+    /// Instruction of the left and the right trace, `None` if the trace has ended.
+    pub step: Change<Option<TraceStep>>,
+}
+
+/// First storage write where the traces diverge.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WriteDivergence {
+    /// Position of the write in the traces.
+    pub index: usize,
+    /// Write of the left and the right trace, `None` if the trace has fewer writes.
+    pub write: Change<Option<StorageWrite>>,
+}
+
+/// Divergence report of a transaction executed under two hardforks, created by
+/// [`diff_hardforks`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HardforkDiff {
+    /// Spec of the left execution.
+    pub left: SpecId,
+    /// Spec of the right execution.
+    pub right: SpecId,
+    /// First instruction where the executions diverge.
+    pub first_step: Option<StepDivergence>,
+    /// First storage write where the executions diverge.
+    pub first_write: Option<WriteDivergence>,
+    /// Differences of the results and their state.
+    pub result: ResultDiff,
+    /// Trace of the left execution.
+    pub left_trace: HardforkTrace,
+    /// Trace of the right execution.
+    pub right_trace: HardforkTrace,
+}
+
+impl HardforkDiff {
+    /// Returns `true` if the executions are the same.
+    pub fn is_empty(&self) -> bool {
+        self.first_step.is_none() && self.first_write.is_none() && self.result.is_empty()
+    }
+
+    /// Returns `true` if the executions only differ in gas.
+    ///
+    /// Executed instructions and storage writes are the same and the results differ at most in
+    /// the gas used or refunded.
+    pub fn is_gas_only(&self) -> bool {
+        self.first_step
+            .as_ref()
+            .is_some_and(|step| step.kind == StepDivergenceKind::Gas)
+            && self.first_write.is_none()
+            && (self.result.is_empty() || self.result.is_gas_only())
+            && self.left_trace.steps.len() == self.right_trace.steps.len()
+            && self
+                .left_trace
+                .steps
+                .iter()
+                .zip(&self.right_trace.steps)
+                .all(|(left, right)| left.same_instruction(right))
+    }
+}
+
+impl fmt::Display for HardforkDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(
+                f,
+                "{:?} and {:?} executions are equal",
+                self.left, self.right
+            );
+        }
+        writeln!(f, "{:?} != {:?}", self.left, self.right)?;
+        if let Some(step) = &self.first_step {
+            writeln!(
+                f,
+                "first {:?} divergence at step {}: {}",
+                step.kind, step.index, step.step
+            )?;
+        }
+        if let Some(write) = &self.first_write {
+            writeln!(
+                f,
+                "first storage write divergence at write {}: {}",
+                write.index, write.write
+            )?;
+        }
+        if !self.result.is_empty() {
+            write!(f, "{}", self.result)?;
+        }
+        Ok(())
+    }
+}
+
+/// Inspector that records executed instructions and storage writes into a [`HardforkTrace`].
+#[derive(Clone, Debug, Default)]
+pub struct HardforkTraceInspector {
+    trace: HardforkTrace,
+    /// Instruction that is being executed.
+    pending_step: Option<TraceStep>,
+    /// Write of the `SSTORE` that is being executed.
+    pending_write: Option<StorageWrite>,
+}
+
+impl HardforkTraceInspector {
+    /// Creates a new inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded trace.
+    pub fn trace(&self) -> &HardforkTrace {
+        &self.trace
+    }
+
+    /// Takes the recorded trace, leaving an empty one.
+    pub fn take_trace(&mut self) -> HardforkTrace {
+        core::mem::take(&mut self.trace)
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for HardforkTraceInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, context: &mut CTX) {
+        let address = interp.input.target_address();
+        let opcode = interp.bytecode.opcode();
+        self.pending_step = Some(TraceStep {
+            depth: context.journal_ref().depth(),
+            address,
+            pc: interp.bytecode.pc(),
+            opcode,
+            gas_remaining: interp.gas.remaining(),
+            gas_cost: 0,
+            result: None,
+        });
+        if opcode == opcode::SSTORE {
+            if let (Ok(key), Ok(value)) = (interp.stack.peek(0), interp.stack.peek(1)) {
+                self.pending_write = Some(StorageWrite {
+                    address,
+                    key,
+                    value,
+                });
+            }
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        if let Some(mut step) = self.pending_step.take() {
+            step.gas_cost = step.gas_remaining.saturating_sub(interp.gas.remaining());
+            step.result = interp.bytecode.instruction_result();
+            self.trace.steps.push(step);
+        }
+        if let Some(write) = self.pending_write.take() {
+            if interp.bytecode.instruction_result().is_none() {
+                self.trace.writes.push(write);
+            }
+        }
+    }
+}
+
+/// Executes the transaction under the `left` and the `right` spec and reports the first
+/// instruction, the first storage write and the result fields that differ.
+///
+/// Both executions start from a clone of the database, the spec replaces the spec and the fork
+/// schedule of the configuration. Helps verifying that a new fork gate only changes what it
+/// should, for example that a repricing changes gas costs and nothing else.
+pub fn diff_hardforks<DB: Database + Clone>(
+    db: &DB,
+    block: &BlockEnv,
+    cfg: &CfgEnv,
+    tx: &TxEnv,
+    left: SpecId,
+    right: SpecId,
+) -> Result<HardforkDiff, EVMError<DB::Error>> {
+    let run = |spec: SpecId| {
+        let mut evm = Context::mainnet()
+            .with_db(db.clone())
+            .with_block(block.clone())
+            .with_cfg(cfg.clone().with_spec(spec))
+            .build_mainnet_with_inspector(HardforkTraceInspector::new());
+        let output = evm.inspect_tx(tx.clone())?;
+        Ok::<_, EVMError<DB::Error>>((output, evm.inspector.take_trace()))
+    };
+    let (left_output, left_trace) = run(left)?;
+    let (right_output, right_trace) = run(right)?;
+
+    Ok(HardforkDiff {
+        left,
+        right,
+        first_step: left_trace.first_step_divergence(&right_trace),
+        first_write: left_trace.first_write_divergence(&right_trace),
+        result: diff_results(&left_output, &right_output),
+        left_trace,
+        right_trace,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use primitives::{TxKind, U256};
+    use state::Bytecode;
+
+    fn diff(code: &[u8], left: SpecId, right: SpecId) -> HardforkDiff {
+        let db = BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.to_vec().into()));
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+        diff_hardforks(
+            &db,
+            &BlockEnv::default(),
+            &CfgEnv::default(),
+            &tx,
+            left,
+            right,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn same_execution() {
+        let code = [opcode::PUSH1, 0x01, opcode::PUSH0, opcode::SSTORE];
+        let diff = diff(&code, SpecId::CANCUN, SpecId::PRAGUE);
+        assert!(diff.is_empty(), "{diff}");
+        // Implicit STOP at the end of the code.
+        assert_eq!(diff.left_trace.steps.len(), 4);
+        assert_eq!(
+            diff.left_trace.writes,
+            [StorageWrite {
+                address: BENCH_TARGET,
+                key: U256::ZERO,
+                value: U256::from(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn gas_divergence() {
+        // BALANCE is repriced by EIP-2929 in Berlin.
+        let code = [opcode::ADDRESS, opcode::BALANCE, opcode::POP];
+        let diff = diff(&code, SpecId::ISTANBUL, SpecId::BERLIN);
+        assert!(diff.is_gas_only(), "{diff}");
+        let step = diff.first_step.unwrap();
+        assert_eq!(step.index, 1);
+        assert_eq!(step.kind, StepDivergenceKind::Gas);
+        assert_eq!(step.step.left.unwrap().gas_cost, 700);
+        assert_eq!(step.step.right.unwrap().gas_cost, 100);
+        assert!(diff.result.gas_used.is_some());
+    }
+
+    #[test]
+    fn instruction_divergence() {
+        // PUSH0 is introduced in Shanghai, before it the opcode halts.
+        let code = [opcode::PUSH1, 0x01, opcode::PUSH0, opcode::SSTORE];
+        let diff = diff(&code, SpecId::MERGE, SpecId::SHANGHAI);
+        assert!(!diff.is_gas_only());
+        let step = diff.first_step.unwrap();
+        assert_eq!(step.index, 1);
+        assert_eq!(step.kind, StepDivergenceKind::Instruction);
+        assert_eq!(
+            step.step.left.unwrap().result,
+            Some(InstructionResult::NotActivated)
+        );
+        assert_eq!(step.step.right.unwrap().result, None);
+        let write = diff.first_write.unwrap();
+        assert_eq!(write.index, 0);
+        assert!(write.write.left.is_none());
+        assert!(diff.result.status.is_some());
+    }
+}
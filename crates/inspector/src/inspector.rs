@@ -1,5 +1,7 @@
+use crate::GasSettlement;
 use auto_impl::auto_impl;
 use context::{Database, Journal, JournalEntry};
+use handler::Withdrawal;
 use interpreter::{
     interpreter::EthInterpreter, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
     InterpreterTypes,
@@ -111,6 +113,24 @@ pub trait Inspector<CTX, INTR: InterpreterTypes = EthInterpreter> {
         let _ = target;
         let _ = value;
     }
+
+    /// Called after post execution when refunds are applied and fees are paid.
+    ///
+    /// Not called if the transaction fails before or during post execution.
+    #[inline]
+    fn gas_settlement(&mut self, context: &mut CTX, settlement: &GasSettlement) {
+        let _ = context;
+        let _ = settlement;
+    }
+
+    /// Called after the balance of the recipient is incremented by a consensus layer withdrawal.
+    ///
+    /// Withdrawals with zero amount are skipped and not reported.
+    #[inline]
+    fn withdrawal(&mut self, context: &mut CTX, withdrawal: &Withdrawal) {
+        let _ = context;
+        let _ = withdrawal;
+    }
 }
 
 /// Extends the journal with additional methods that are used by the inspector.
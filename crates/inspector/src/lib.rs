@@ -5,27 +5,86 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc as std;
+
+#[cfg(feature = "tracer")]
+mod abi_registry;
+mod access_list_usage;
+mod buffered;
+#[cfg(feature = "tracer")]
+mod call_graph;
+#[cfg(feature = "tracer")]
+mod call_tracer;
 mod count_inspector;
+mod creation_report;
+mod data_copy;
 #[cfg(feature = "tracer")]
 mod eip3155;
 mod either;
+mod frame_scratch;
 mod gas;
 /// Handler implementations for inspector integration.
 pub mod handler;
+mod hardfork_diff;
 mod inspect;
 mod inspector;
+mod log_stream;
 mod mainnet_inspect;
+mod memory_access;
 mod noop;
+#[cfg(feature = "std")]
+mod profiler;
+mod replay;
+mod resource_usage;
+mod settlement;
+#[cfg(feature = "tracer")]
+mod storage_layout;
+mod taint;
+mod trace_limits;
 mod traits;
+mod withdrawals;
 
 #[cfg(test)]
 mod inspector_tests;
 
 /// Inspector implementations.
 pub mod inspectors {
+    #[cfg(feature = "tracer")]
+    pub use super::abi_registry::{
+        decode_revert_reason, AbiFunction, AbiParseError, AbiRegistry, AbiType, DecodedCall,
+    };
+    pub use super::access_list_usage::{AccessListUsage, AccessListUsageInspector};
+    pub use super::buffered::{BufferedInspector, TraceEvent};
+    #[cfg(feature = "tracer")]
+    pub use super::call_graph::{CallGraph, CallGraphInspector, CallGraphNode, CallGraphNodeKind};
+    #[cfg(feature = "tracer")]
+    pub use super::call_tracer::{CallFrame, CallTracer};
+    pub use super::creation_report::{CreationReport, CreationReportInspector};
+    pub use super::data_copy::{DataCopy, DataCopyInspector, DataCopyUsage};
     #[cfg(feature = "tracer")]
     pub use super::eip3155::TracerEip3155;
+    pub use super::frame_scratch::{FrameScratchInspector, FrameStart, WithFrameScratch};
     pub use super::gas::GasInspector;
+    pub use super::hardfork_diff::{
+        diff_hardforks, HardforkDiff, HardforkTrace, HardforkTraceInspector, StepDivergence,
+        StepDivergenceKind, StorageWrite, TraceStep, WriteDivergence,
+    };
+    pub use super::log_stream::{LogEvent, LogStreamInspector};
+    pub use super::memory_access::{MemoryAccess, MemoryAccessInspector, MemoryAccessKind};
+    #[cfg(feature = "std")]
+    pub use super::profiler::{DatabaseTimer, ProfilerInspector, ProfilingDatabase, TxProfile};
+    pub use super::replay::{
+        RecordedEvent, RecordedTrace, RecordingInspector, StepState, TraceDecodeError,
+    };
+    pub use super::resource_usage::{ResourceInspector, ResourceUsage};
+    #[cfg(feature = "tracer")]
+    pub use super::storage_layout::{
+        StorageAccess, StorageAccessKind, StorageLayout, StorageLayoutInspector, StorageType,
+        StorageVariable,
+    };
+    pub use super::taint::{TaintFinding, TaintInspector, TaintSink, TaintStep};
+    pub use super::trace_limits::TraceLimits;
 }
 
 pub use count_inspector::CountInspector;
@@ -33,7 +92,9 @@ pub use handler::{inspect_instructions, InspectorHandler};
 pub use inspect::{InspectCommitEvm, InspectEvm};
 pub use inspector::*;
 pub use noop::NoOpInspector;
+pub use settlement::{BalanceCredits, FeePayment, GasSettlement};
 pub use traits::*;
+pub use withdrawals::InspectWithdrawalsEvm;
 
 #[cfg(test)]
 mod tests {
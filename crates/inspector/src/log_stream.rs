@@ -0,0 +1,181 @@
+//! LogStreamInspector - Inspector that delivers logs as soon as they are emitted.
+use crate::inspector::Inspector;
+use interpreter::{
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterResult,
+    InterpreterTypes,
+};
+use primitives::Log;
+use std::vec::Vec;
+
+/// Event delivered by [`LogStreamInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEvent {
+    /// Log was emitted.
+    Log {
+        /// Index of the log inside the transaction, counting only logs that were not reverted.
+        index: usize,
+        /// Emitted log.
+        log: Log,
+    },
+    /// Frame that emitted logs has reverted or halted.
+    ///
+    /// All previously delivered logs with index greater or equal to `index` are discarded.
+    Revert {
+        /// Index of the first discarded log.
+        index: usize,
+    },
+}
+
+/// Inspector that calls the callback for every emitted log and for every revert that discards
+/// already delivered logs.
+///
+/// Callback can forward events to a channel so consumers can process logs while the transaction
+/// is still executing.
+#[derive(Clone, Debug)]
+pub struct LogStreamInspector<F> {
+    on_event: F,
+    /// Number of live logs.
+    log_count: usize,
+    /// Number of live logs at the start of each active frame.
+    checkpoints: Vec<usize>,
+}
+
+impl<F: FnMut(LogEvent)> LogStreamInspector<F> {
+    /// Create a new LogStreamInspector with the given callback.
+    pub fn new(on_event: F) -> Self {
+        Self {
+            on_event,
+            log_count: 0,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Returns the number of delivered logs that are not reverted.
+    pub fn log_count(&self) -> usize {
+        self.log_count
+    }
+
+    /// Consumes the inspector and returns the callback.
+    pub fn into_inner(self) -> F {
+        self.on_event
+    }
+
+    fn frame_start(&mut self) {
+        self.checkpoints.push(self.log_count);
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult) {
+        let Some(checkpoint) = self.checkpoints.pop() else {
+            return;
+        };
+        if !result.is_ok() && checkpoint < self.log_count {
+            self.log_count = checkpoint;
+            (self.on_event)(LogEvent::Revert { index: checkpoint });
+        }
+        // Transaction is finished, next one starts from zero.
+        if self.checkpoints.is_empty() {
+            self.log_count = 0;
+        }
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes, F: FnMut(LogEvent)> Inspector<CTX, INTR>
+    for LogStreamInspector<F>
+{
+    fn log(&mut self, _interp: &mut Interpreter<INTR>, _context: &mut CTX, log: Log) {
+        let index = self.log_count;
+        self.log_count += 1;
+        (self.on_event)(LogEvent::Log { index, log });
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frame_end(&outcome.result);
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(&outcome.result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::{opcode, Bytecode};
+
+    fn run(code: Vec<u8>) -> Vec<LogEvent> {
+        let mut events = Vec::new();
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+            Bytes::from(code),
+        )));
+        let mut evm = ctx.build_mainnet_with_inspector(LogStreamInspector::new(|e| events.push(e)));
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        drop(evm);
+        events
+    }
+
+    #[test]
+    fn test_log_stream() {
+        let events = run(vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::LOG0,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::LOG0,
+            opcode::STOP,
+        ]);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], LogEvent::Log { index: 1, .. }));
+    }
+
+    #[test]
+    fn test_log_stream_revert() {
+        let events = run(vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::LOG0,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::REVERT,
+        ]);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], LogEvent::Log { index: 0, .. }));
+        assert_eq!(events[1], LogEvent::Revert { index: 0 });
+    }
+}
@@ -0,0 +1,241 @@
+//! MemoryAccessInspector - Inspector that records memory ranges read and written by instructions.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{Jumps, LoopControl},
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+};
+use state::bytecode::opcode;
+use std::vec::Vec;
+
+/// Direction of a memory access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MemoryAccessKind {
+    /// Instruction reads the range.
+    Read,
+    /// Instruction writes the range.
+    Write,
+}
+
+/// Memory range accessed by a single instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryAccess {
+    /// Call depth of the frame, the transaction frame has depth one.
+    pub depth: usize,
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Opcode of the instruction.
+    pub opcode: u8,
+    /// Read or write.
+    pub kind: MemoryAccessKind,
+    /// Start of the range in frame memory.
+    pub offset: usize,
+    /// Length of the range in bytes.
+    pub len: usize,
+}
+
+/// Inspector that records offsets and lengths of memory accessed by instructions.
+///
+/// Only ranges are recorded, memory content is not copied. Covered instructions are `MLOAD`,
+/// `MSTORE`, `MSTORE8`, `MCOPY`, the `*COPY` family, `KECCAK256`, `LOG*`, `RETURN` and `REVERT`.
+/// `MCOPY` produces a read followed by a write. Zero length accesses and instructions that fail
+/// are not recorded.
+///
+/// Memory passed to and returned from calls is not recorded.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryAccessInspector {
+    accesses: Vec<MemoryAccess>,
+    /// Accesses of the current instruction, recorded when it succeeds.
+    pending: [Option<MemoryAccess>; 2],
+    depth: usize,
+}
+
+impl MemoryAccessInspector {
+    /// Create a new MemoryAccessInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns recorded accesses in execution order.
+    pub fn accesses(&self) -> &[MemoryAccess] {
+        &self.accesses
+    }
+
+    /// Consumes the inspector and returns recorded accesses.
+    pub fn into_accesses(self) -> Vec<MemoryAccess> {
+        self.accesses
+    }
+
+    /// Clears recorded accesses.
+    pub fn clear(&mut self) {
+        self.accesses.clear();
+        self.pending = [None; 2];
+        self.depth = 0;
+    }
+
+    /// Creates access with offset read from stack position `offset`.
+    fn access(
+        &self,
+        interp: &Interpreter<EthInterpreter>,
+        kind: MemoryAccessKind,
+        offset: usize,
+        len: Len,
+    ) -> Option<MemoryAccess> {
+        let len = match len {
+            Len::Fixed(len) => len,
+            Len::Stack(n) => usize::try_from(interp.stack.peek(n).ok()?).ok()?,
+        };
+        if len == 0 {
+            return None;
+        }
+        let offset = interp.stack.peek(offset).ok()?;
+        Some(MemoryAccess {
+            depth: self.depth,
+            pc: interp.bytecode.pc(),
+            opcode: interp.bytecode.opcode(),
+            kind,
+            offset: usize::try_from(offset).ok()?,
+            len,
+        })
+    }
+}
+
+/// Length of the accessed range.
+enum Len {
+    /// Length is defined by the instruction.
+    Fixed(usize),
+    /// Length is read from the stack position.
+    Stack(usize),
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for MemoryAccessInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        use MemoryAccessKind::{Read, Write};
+        self.pending = match interp.bytecode.opcode() {
+            opcode::MLOAD => [self.access(interp, Read, 0, Len::Fixed(32)), None],
+            opcode::MSTORE => [self.access(interp, Write, 0, Len::Fixed(32)), None],
+            opcode::MSTORE8 => [self.access(interp, Write, 0, Len::Fixed(1)), None],
+            opcode::MCOPY => [
+                self.access(interp, Read, 1, Len::Stack(2)),
+                self.access(interp, Write, 0, Len::Stack(2)),
+            ],
+            opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY => {
+                [self.access(interp, Write, 0, Len::Stack(2)), None]
+            }
+            opcode::EXTCODECOPY => [self.access(interp, Write, 1, Len::Stack(3)), None],
+            opcode::KECCAK256 | opcode::LOG0..=opcode::LOG4 | opcode::RETURN | opcode::REVERT => {
+                [self.access(interp, Read, 0, Len::Stack(1)), None]
+            }
+            _ => return,
+        };
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let pending = core::mem::take(&mut self.pending);
+        if interp
+            .bytecode
+            .instruction_result()
+            .is_some_and(|result| result.is_error())
+        {
+            return;
+        }
+        self.accesses.extend(pending.into_iter().flatten());
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::Bytecode;
+
+    #[test]
+    fn test_memory_access() {
+        let contract_data: Bytes = Bytes::from(vec![
+            // mstore(0x20, 1)
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x20,
+            opcode::MSTORE,
+            // mload(0x00)
+            opcode::PUSH0,
+            opcode::MLOAD,
+            opcode::POP,
+            // mcopy(0x40, 0x20, 0x10)
+            opcode::PUSH1,
+            0x10,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x40,
+            opcode::MCOPY,
+            // return(0x00, 0x00)
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(MemoryAccessInspector::new());
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+
+        let access = |pc, opcode, kind, offset, len| MemoryAccess {
+            depth: 1,
+            pc,
+            opcode,
+            kind,
+            offset,
+            len,
+        };
+        use MemoryAccessKind::{Read, Write};
+        assert_eq!(
+            evm.inspector.accesses(),
+            [
+                access(4, opcode::MSTORE, Write, 0x20, 32),
+                access(6, opcode::MLOAD, Read, 0x00, 32),
+                access(14, opcode::MCOPY, Read, 0x20, 0x10),
+                access(14, opcode::MCOPY, Write, 0x40, 0x10),
+            ]
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! ProfilerInspector - Inspector that measures where the execution time of a transaction is spent.
+use crate::inspector::Inspector;
+use context::{ContextTr, JournalTr};
+use database_interface::{Database, DatabaseCommit};
+use interpreter::{
+    interpreter_types::Jumps, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+    InterpreterTypes,
+};
+use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
+use state::{bytecode::opcode, Account, AccountInfo, Bytecode};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// Wall-clock time profile of a single transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TxProfile {
+    /// Time from the start to the end of the top frame.
+    pub total: Duration,
+    /// Time spent in instruction dispatch and execution, the part of `total` not spent in
+    /// host instructions, database calls or precompiles.
+    pub interpreter: Duration,
+    /// Time spent in instructions that access the host, such as `SLOAD`, `BALANCE` or `LOG`,
+    /// without the database time. Dominated by journal operations.
+    pub journal: Duration,
+    /// Time spent in database calls, including loads done before the top frame started.
+    pub database: Duration,
+    /// Time spent in precompiles.
+    pub precompiles: Duration,
+    /// Number of executed host instructions.
+    pub host_calls: u64,
+    /// Number of database calls.
+    pub database_calls: u64,
+    /// Number of precompile calls.
+    pub precompile_calls: u64,
+}
+
+/// Time spent in database calls, shared between [`ProfilingDatabase`] and [`ProfilerInspector`].
+#[derive(Clone, Debug, Default)]
+pub struct DatabaseTimer(Rc<Cell<(Duration, u64)>>);
+
+impl DatabaseTimer {
+    /// Create a new DatabaseTimer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the time spent in database calls.
+    pub fn elapsed(&self) -> Duration {
+        self.0.get().0
+    }
+
+    /// Returns the number of database calls.
+    pub fn calls(&self) -> u64 {
+        self.0.get().1
+    }
+
+    fn time<T>(&self, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = f();
+        let (elapsed, calls) = self.0.get();
+        self.0.set((elapsed + start.elapsed(), calls + 1));
+        out
+    }
+}
+
+/// Database wrapper that records the time spent in the inner database into a [`DatabaseTimer`].
+#[derive(Clone, Debug)]
+pub struct ProfilingDatabase<DB> {
+    db: DB,
+    timer: DatabaseTimer,
+}
+
+impl<DB> ProfilingDatabase<DB> {
+    /// Create a new ProfilingDatabase that records into `timer`.
+    pub fn new(db: DB, timer: DatabaseTimer) -> Self {
+        Self { db, timer }
+    }
+
+    /// Returns the timer.
+    pub fn timer(&self) -> &DatabaseTimer {
+        &self.timer
+    }
+
+    /// Consumes the wrapper and returns the inner database.
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+}
+
+impl<DB: Database> Database for ProfilingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.timer.time(|| self.db.basic(address))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.timer.time(|| self.db.code_by_hash(code_hash))
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.timer.time(|| self.db.storage(address, index))
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.timer.time(|| self.db.block_hash(number))
+    }
+
+    fn basic_with_storage(
+        &mut self,
+        address: Address,
+        slots: &[StorageKey],
+    ) -> Result<(Option<AccountInfo>, Vec<StorageValue>), Self::Error> {
+        self.timer
+            .time(|| self.db.basic_with_storage(address, slots))
+    }
+
+    fn many_basics(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.timer.time(|| self.db.many_basics(addresses))
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for ProfilingDatabase<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+/// Inspector that records a [`TxProfile`] for every inspected transaction.
+///
+/// Database time is only measured if the database is wrapped in a [`ProfilingDatabase`] that
+/// shares the [`DatabaseTimer`] with the inspector. Timing is wall-clock and includes the
+/// overhead of running with an inspector, so results are meant for comparing where time goes
+/// rather than for absolute numbers.
+#[derive(Clone, Debug, Default)]
+pub struct ProfilerInspector {
+    timer: DatabaseTimer,
+    profiles: Vec<TxProfile>,
+    current: TxProfile,
+    /// Start of the top frame and database time at that point.
+    tx_start: Option<(Instant, Duration)>,
+    /// Database time and calls at the end of the previous transaction.
+    db_checkpoint: (Duration, u64),
+    /// Start of the running host instruction and database time at that point.
+    host_start: Option<(Instant, Duration)>,
+    /// Database time spent inside host instructions of the current transaction.
+    host_database: Duration,
+    /// Start of each active call frame, `Some` for precompile calls.
+    call_starts: Vec<Option<Instant>>,
+    depth: usize,
+}
+
+impl ProfilerInspector {
+    /// Create a new ProfilerInspector that reads database time from `timer`.
+    pub fn new(timer: DatabaseTimer) -> Self {
+        let db_checkpoint = (timer.elapsed(), timer.calls());
+        Self {
+            timer,
+            db_checkpoint,
+            ..Default::default()
+        }
+    }
+
+    /// Returns profiles of inspected transactions in execution order.
+    pub fn profiles(&self) -> &[TxProfile] {
+        &self.profiles
+    }
+
+    /// Returns the profile of the last inspected transaction.
+    pub fn last_profile(&self) -> Option<&TxProfile> {
+        self.profiles.last()
+    }
+
+    /// Clears recorded profiles.
+    pub fn clear(&mut self) {
+        self.profiles.clear();
+    }
+
+    fn frame_start(&mut self) {
+        if self.depth == 0 {
+            self.current = TxProfile::default();
+            self.host_database = Duration::ZERO;
+            self.tx_start = Some((Instant::now(), self.timer.elapsed()));
+        }
+        self.depth += 1;
+    }
+
+    fn frame_end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth != 0 {
+            return;
+        }
+        let Some((start, start_db)) = self.tx_start.take() else {
+            return;
+        };
+        let (db_elapsed, db_calls) = (self.timer.elapsed(), self.timer.calls());
+        let profile = &mut self.current;
+        profile.total = start.elapsed();
+        profile.database = db_elapsed.saturating_sub(self.db_checkpoint.0);
+        profile.database_calls = db_calls.saturating_sub(self.db_checkpoint.1);
+        profile.journal = profile.journal.saturating_sub(self.host_database);
+        profile.interpreter = profile
+            .total
+            .saturating_sub(profile.journal)
+            .saturating_sub(db_elapsed.saturating_sub(start_db))
+            .saturating_sub(profile.precompiles);
+        self.db_checkpoint = (db_elapsed, db_calls);
+        self.profiles.push(*profile);
+    }
+}
+
+/// Returns `true` if the instruction accesses the host.
+fn is_host_opcode(op: u8) -> bool {
+    matches!(
+        op,
+        opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::BLOCKHASH
+            | opcode::SELFBALANCE
+            | opcode::SLOAD
+            | opcode::SSTORE
+            | opcode::TLOAD
+            | opcode::TSTORE
+            | opcode::LOG0
+            ..=opcode::LOG4
+                | opcode::CREATE
+                | opcode::CALL
+                | opcode::CALLCODE
+                | opcode::DELEGATECALL
+                | opcode::CREATE2
+                | opcode::STATICCALL
+                | opcode::SELFDESTRUCT
+    )
+}
+
+impl<CTX: ContextTr, INTR: InterpreterTypes> Inspector<CTX, INTR> for ProfilerInspector {
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        if is_host_opcode(interp.bytecode.opcode()) {
+            self.host_start = Some((Instant::now(), self.timer.elapsed()));
+        }
+    }
+
+    fn step_end(&mut self, _interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        if let Some((start, start_db)) = self.host_start.take() {
+            self.current.journal += start.elapsed();
+            self.current.host_calls += 1;
+            self.host_database += self.timer.elapsed().saturating_sub(start_db);
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start();
+        let is_precompile = context
+            .journal_ref()
+            .precompile_addresses()
+            .contains(&inputs.bytecode_address);
+        self.call_starts.push(is_precompile.then(Instant::now));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        if let Some(Some(start)) = self.call_starts.pop() {
+            self.current.precompiles += start.elapsed();
+            self.current.precompile_calls += 1;
+        }
+        self.frame_end();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+
+    #[test]
+    fn test_profiler() {
+        // Reads slot 0 and calls the identity precompile.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x00,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x04,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let timer = DatabaseTimer::new();
+        let db = ProfilingDatabase::new(
+            BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)),
+            timer.clone(),
+        );
+        let mut evm = Context::mainnet()
+            .with_db(db)
+            .build_mainnet_with_inspector(ProfilerInspector::new(timer));
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let profile = *evm.inspector.last_profile().unwrap();
+        assert_eq!(evm.inspector.profiles().len(), 1);
+        assert_eq!(profile.host_calls, 2);
+        assert_eq!(profile.precompile_calls, 1);
+        assert!(profile.database_calls > 0);
+        assert!(profile.interpreter + profile.journal + profile.precompiles <= profile.total);
+    }
+}
@@ -0,0 +1,1069 @@
+//! RecordingInspector - Inspector that records the event stream so it can be replayed later.
+use crate::{inspector::Inspector, FeePayment, GasSettlement};
+use context::ContextTr;
+use core::{fmt, ops::Range};
+use interpreter::{
+    interpreter::{EthInterpreter, ExtBytecode},
+    interpreter_types::{InputsTr, Jumps, LoopControl, ReturnData, RuntimeFlag},
+    CallInput, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
+    CreateScheme, Gas, InputsImpl, InstructionResult, Interpreter, InterpreterAction,
+    InterpreterResult, SharedMemory,
+};
+use primitives::{hardfork::SpecId, Address, Bytes, Log, B256, U256};
+use state::bytecode::Bytecode;
+use std::vec::Vec;
+
+/// State of the frame interpreter recorded around an instruction.
+///
+/// Stack and memory are recorded as changes to the previous state of the same frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StepState {
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Gas remaining in the frame.
+    pub gas_remaining: u64,
+    /// Gas refunded by the frame.
+    pub gas_refunded: i64,
+    /// Number of items, from the bottom, kept from the previous stack of the frame.
+    pub stack_kept: usize,
+    /// Items pushed on top of the kept items.
+    pub stack_pushed: Vec<U256>,
+    /// Memory of the frame, `None` if it didn't change or memory is not recorded.
+    pub memory: Option<Bytes>,
+}
+
+/// Inspector event recorded by [`RecordingInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordedEvent {
+    /// Interpreter of a frame was initialized.
+    Initialize {
+        /// Original bytecode of the frame.
+        bytecode: Bytes,
+        /// Inputs of the frame, with the call data copied into [`CallInput::Bytes`].
+        inputs: InputsImpl,
+        /// Whether the frame is static.
+        is_static: bool,
+        /// Spec of the frame.
+        spec_id: SpecId,
+        /// Gas limit of the frame.
+        gas_limit: u64,
+    },
+    /// Instruction is about to be executed.
+    Step(StepState),
+    /// Instruction was executed.
+    StepEnd {
+        /// State after the instruction.
+        state: StepState,
+        /// Result of the instruction if it ended the frame.
+        result: Option<InstructionResult>,
+    },
+    /// Log was emitted.
+    Log(Log),
+    /// Call frame is about to start, input is copied into [`CallInput::Bytes`].
+    Call(CallInputs),
+    /// Call frame has finished, memory expansion memo of the gas is not recorded.
+    CallEnd(CallOutcome),
+    /// Create frame is about to start.
+    Create(CreateInputs),
+    /// Create frame has finished, memory expansion memo of the gas is not recorded.
+    CreateEnd(CreateOutcome),
+    /// Contract was self-destructed.
+    Selfdestruct {
+        /// Self-destructed contract.
+        contract: Address,
+        /// Target of the remaining balance.
+        target: Address,
+        /// Transferred balance.
+        value: U256,
+    },
+    /// Gas and fees of the transaction were settled.
+    Settlement(GasSettlement),
+}
+
+/// Inspector event stream recorded by [`RecordingInspector`].
+///
+/// Trace can be encoded into a compact binary format, written to a file and replayed into
+/// any inspector later, so the execution runs once and the trace is analyzed many times.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordedTrace {
+    /// Recorded events, in order.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// Magic bytes at the start of the encoded trace.
+const MAGIC: [u8; 4] = *b"RVMT";
+/// Version of the encoding.
+const VERSION: u8 = 1;
+
+impl RecordedTrace {
+    /// Encodes the trace into the compact binary format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoder = Encoder(Vec::with_capacity(64 * self.events.len()));
+        encoder.0.extend_from_slice(&MAGIC);
+        encoder.u8(VERSION);
+        for event in &self.events {
+            encoder.event(event);
+        }
+        encoder.0
+    }
+
+    /// Decodes the trace encoded by [`RecordedTrace::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, TraceDecodeError> {
+        let mut decoder = Decoder(bytes);
+        if decoder.take(MAGIC.len())? != MAGIC {
+            return Err(TraceDecodeError::InvalidHeader);
+        }
+        let version = decoder.u8()?;
+        if version != VERSION {
+            return Err(TraceDecodeError::UnsupportedVersion(version));
+        }
+        let mut events = Vec::new();
+        while !decoder.0.is_empty() {
+            events.push(decoder.event()?);
+        }
+        Ok(Self { events })
+    }
+
+    /// Replays the events into the inspector.
+    ///
+    /// Interpreters of the frames are rebuilt from the recorded bytecode and inputs, and their
+    /// program counter, gas, stack and memory are restored before every step. Memory is only
+    /// available if it was recorded, return data is set from the outcomes of child frames.
+    /// Outcomes returned by the inspector from `call` and `create` are ignored.
+    pub fn replay<CTX, I: Inspector<CTX, EthInterpreter>>(
+        &self,
+        inspector: &mut I,
+        context: &mut CTX,
+    ) {
+        let mut frames: Vec<ReplayFrame> = Vec::new();
+        for event in &self.events {
+            match event {
+                RecordedEvent::Initialize {
+                    bytecode,
+                    inputs,
+                    is_static,
+                    spec_id,
+                    gas_limit,
+                } => {
+                    let mut interp = Interpreter::new(
+                        SharedMemory::new(),
+                        ExtBytecode::new(Bytecode::new_legacy(bytecode.clone())),
+                        inputs.clone(),
+                        *is_static,
+                        *spec_id,
+                        *gas_limit,
+                    );
+                    inspector.initialize_interp(&mut interp, context);
+                    match frames.last_mut() {
+                        Some(frame) => frame.interp = Some(interp),
+                        None => frames.push(ReplayFrame {
+                            inputs: None,
+                            interp: Some(interp),
+                        }),
+                    }
+                }
+                RecordedEvent::Step(state) => {
+                    if let Some(interp) = current(&mut frames) {
+                        restore(interp, state);
+                        *interp.bytecode.action() = None;
+                        interp.bytecode.reset_action();
+                        inspector.step(interp, context);
+                    }
+                }
+                RecordedEvent::StepEnd { state, result } => {
+                    if let Some(interp) = current(&mut frames) {
+                        restore(interp, state);
+                        match result {
+                            Some(result) => interp
+                                .bytecode
+                                .set_action(InterpreterAction::new_halt(*result, interp.gas)),
+                            None => {
+                                *interp.bytecode.action() = None;
+                                interp.bytecode.reset_action();
+                            }
+                        }
+                        inspector.step_end(interp, context);
+                    }
+                }
+                RecordedEvent::Log(log) => {
+                    if let Some(interp) = current(&mut frames) {
+                        inspector.log(interp, context, log.clone());
+                    }
+                }
+                RecordedEvent::Call(inputs) => {
+                    inspector.call(context, &mut inputs.clone());
+                    frames.push(ReplayFrame {
+                        inputs: Some(FrameInputs::Call(inputs.clone())),
+                        interp: None,
+                    });
+                }
+                RecordedEvent::CallEnd(outcome) => {
+                    let frame = frames.pop();
+                    if let Some(FrameInputs::Call(inputs)) = frame.and_then(|frame| frame.inputs) {
+                        inspector.call_end(context, &inputs, &mut outcome.clone());
+                    }
+                    if let Some(interp) = current(&mut frames) {
+                        interp.return_data.set_buffer(outcome.output().clone());
+                    }
+                }
+                RecordedEvent::Create(inputs) => {
+                    inspector.create(context, &mut inputs.clone());
+                    frames.push(ReplayFrame {
+                        inputs: Some(FrameInputs::Create(inputs.clone())),
+                        interp: None,
+                    });
+                }
+                RecordedEvent::CreateEnd(outcome) => {
+                    let frame = frames.pop();
+                    if let Some(FrameInputs::Create(inputs)) = frame.and_then(|frame| frame.inputs)
+                    {
+                        inspector.create_end(context, &inputs, &mut outcome.clone());
+                    }
+                    if let Some(interp) = current(&mut frames) {
+                        if outcome.instruction_result().is_revert() {
+                            interp.return_data.set_buffer(outcome.output().clone());
+                        } else {
+                            interp.return_data.clear();
+                        }
+                    }
+                }
+                RecordedEvent::Selfdestruct {
+                    contract,
+                    target,
+                    value,
+                } => inspector.selfdestruct(*contract, *target, *value),
+                RecordedEvent::Settlement(settlement) => {
+                    inspector.gas_settlement(context, settlement)
+                }
+            }
+        }
+    }
+}
+
+/// Frame rebuilt during the replay.
+struct ReplayFrame {
+    inputs: Option<FrameInputs>,
+    interp: Option<Interpreter<EthInterpreter>>,
+}
+
+enum FrameInputs {
+    Call(CallInputs),
+    Create(CreateInputs),
+}
+
+/// Returns the interpreter of the innermost frame.
+fn current(frames: &mut [ReplayFrame]) -> Option<&mut Interpreter<EthInterpreter>> {
+    frames.last_mut().and_then(|frame| frame.interp.as_mut())
+}
+
+/// Restores the recorded state into the interpreter.
+fn restore(interp: &mut Interpreter<EthInterpreter>, state: &StepState) {
+    interp.bytecode.absolute_jump(state.pc);
+    interp.gas = new_gas(interp.gas.limit(), state.gas_remaining, state.gas_refunded);
+    let stack = interp.stack.data_mut();
+    stack.truncate(state.stack_kept);
+    stack.extend_from_slice(&state.stack_pushed);
+    if let Some(memory) = &state.memory {
+        interp.memory.resize(memory.len());
+        interp.memory.set(0, memory);
+    }
+}
+
+/// Returns the gas with the given values and without memory expansion memo.
+fn new_gas(limit: u64, remaining: u64, refunded: i64) -> Gas {
+    let mut gas = Gas::new(limit);
+    gas.set_spent(limit.saturating_sub(remaining));
+    gas.set_refund(refunded);
+    gas
+}
+
+/// Returns the gas as it is recorded in the trace.
+fn recorded_gas(gas: &Gas) -> Gas {
+    new_gas(gas.limit(), gas.remaining(), gas.refunded())
+}
+
+/// Inspector that records the inspector event stream into a [`RecordedTrace`].
+///
+/// Stack and memory are recorded as changes, memory is only recorded if enabled with
+/// [`RecordingInspector::with_memory`].
+#[derive(Clone, Debug, Default)]
+pub struct RecordingInspector {
+    trace: RecordedTrace,
+    record_memory: bool,
+    /// Last recorded stack and memory of the frames, `None` for frames without interpreter.
+    frames: Vec<Option<FrameSnapshot>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct FrameSnapshot {
+    stack: Vec<U256>,
+    memory: Vec<u8>,
+}
+
+impl RecordingInspector {
+    /// Create a new RecordingInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the memory is recorded, disabled by default.
+    pub fn with_memory(mut self, record_memory: bool) -> Self {
+        self.record_memory = record_memory;
+        self
+    }
+
+    /// Returns the recorded trace.
+    pub fn trace(&self) -> &RecordedTrace {
+        &self.trace
+    }
+
+    /// Returns the recorded trace and clears the inspector.
+    pub fn take_trace(&mut self) -> RecordedTrace {
+        self.frames.clear();
+        core::mem::take(&mut self.trace)
+    }
+
+    fn push(&mut self, event: RecordedEvent) {
+        self.trace.events.push(event);
+    }
+
+    fn state(&mut self, interp: &Interpreter<EthInterpreter>) -> StepState {
+        let record_memory = self.record_memory;
+        let mut detached = FrameSnapshot::default();
+        let frame = match self.frames.last_mut() {
+            Some(Some(frame)) => frame,
+            _ => &mut detached,
+        };
+
+        let stack = interp.stack.data();
+        let stack_kept = frame
+            .stack
+            .iter()
+            .zip(stack)
+            .take_while(|(previous, current)| previous == current)
+            .count();
+        let stack_pushed = stack[stack_kept..].to_vec();
+        frame.stack.truncate(stack_kept);
+        frame.stack.extend_from_slice(&stack_pushed);
+
+        let mut memory = None;
+        if record_memory {
+            let current = interp.memory.context_memory();
+            if *current != *frame.memory {
+                frame.memory.clear();
+                frame.memory.extend_from_slice(&current);
+                memory = Some(Bytes::copy_from_slice(&current));
+            }
+        }
+
+        StepState {
+            pc: interp.bytecode.pc(),
+            gas_remaining: interp.gas.remaining(),
+            gas_refunded: interp.gas.refunded(),
+            stack_kept,
+            stack_pushed,
+            memory,
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for RecordingInspector {
+    fn initialize_interp(&mut self, interp: &mut Interpreter<EthInterpreter>, context: &mut CTX) {
+        let mut inputs = interp.input.clone();
+        inputs.input = CallInput::Bytes(interp.input.input().bytes(context));
+        match self.frames.last_mut() {
+            Some(frame) => *frame = Some(FrameSnapshot::default()),
+            None => self.frames.push(Some(FrameSnapshot::default())),
+        }
+        self.push(RecordedEvent::Initialize {
+            bytecode: interp.bytecode.original_bytes(),
+            inputs,
+            is_static: interp.runtime_flag.is_static(),
+            spec_id: interp.runtime_flag.spec_id(),
+            gas_limit: interp.gas.limit(),
+        });
+    }
+
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let state = self.state(interp);
+        self.push(RecordedEvent::Step(state));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let state = self.state(interp);
+        let result = interp.bytecode.instruction_result();
+        self.push(RecordedEvent::StepEnd { state, result });
+    }
+
+    fn log(&mut self, _interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX, log: Log) {
+        self.push(RecordedEvent::Log(log));
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let mut inputs = inputs.clone();
+        inputs.input = CallInput::Bytes(inputs.input.bytes(context));
+        self.frames.push(None);
+        self.push(RecordedEvent::Call(inputs));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frames.pop();
+        let mut outcome = outcome.clone();
+        outcome.result.gas = recorded_gas(&outcome.result.gas);
+        self.push(RecordedEvent::CallEnd(outcome));
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frames.push(None);
+        self.push(RecordedEvent::Create(inputs.clone()));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frames.pop();
+        let mut outcome = outcome.clone();
+        outcome.result.gas = recorded_gas(&outcome.result.gas);
+        self.push(RecordedEvent::CreateEnd(outcome));
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.push(RecordedEvent::Selfdestruct {
+            contract,
+            target,
+            value,
+        });
+    }
+
+    fn gas_settlement(&mut self, _context: &mut CTX, settlement: &GasSettlement) {
+        self.push(RecordedEvent::Settlement(settlement.clone()));
+    }
+}
+
+/// Error returned by [`RecordedTrace::decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceDecodeError {
+    /// Data doesn't start with the trace header.
+    InvalidHeader,
+    /// Trace was encoded with an unsupported version.
+    UnsupportedVersion(u8),
+    /// Data ended in the middle of an event.
+    UnexpectedEnd,
+    /// Unknown event tag.
+    InvalidTag(u8),
+    /// Value of the named type is invalid.
+    InvalidValue(&'static str),
+}
+
+impl fmt::Display for TraceDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid trace header"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported trace version {version}")
+            }
+            Self::UnexpectedEnd => write!(f, "unexpected end of trace"),
+            Self::InvalidTag(tag) => write!(f, "invalid event tag {tag}"),
+            Self::InvalidValue(name) => write!(f, "invalid {name} value"),
+        }
+    }
+}
+
+impl core::error::Error for TraceDecodeError {}
+
+const TAG_INITIALIZE: u8 = 0;
+const TAG_STEP: u8 = 1;
+const TAG_STEP_END: u8 = 2;
+const TAG_LOG: u8 = 3;
+const TAG_CALL: u8 = 4;
+const TAG_CALL_END: u8 = 5;
+const TAG_CREATE: u8 = 6;
+const TAG_CREATE_END: u8 = 7;
+const TAG_SELFDESTRUCT: u8 = 8;
+const TAG_SETTLEMENT: u8 = 9;
+
+/// Writes values of the trace, integers are LEB128 encoded and 256-bit values skip leading zeros.
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value.into());
+    }
+
+    fn varint(&mut self, mut value: u128) {
+        while value >= 0x80 {
+            self.u8(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.u8(value as u8);
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.varint(value.into());
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.varint(value as u128);
+    }
+
+    fn i64(&mut self, value: i64) {
+        // Zigzag encoding keeps small negative values small.
+        self.u64(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn address(&mut self, address: &Address) {
+        self.0.extend_from_slice(address.as_slice());
+    }
+
+    fn b256(&mut self, value: &B256) {
+        self.0.extend_from_slice(value.as_slice());
+    }
+
+    fn u256(&mut self, value: &U256) {
+        let bytes = value.to_be_bytes::<32>();
+        let len = 32 - value.leading_zeros() / 8;
+        self.u8(len as u8);
+        self.0.extend_from_slice(&bytes[32 - len..]);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.usize(bytes.len());
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn range(&mut self, range: &Range<usize>) {
+        self.usize(range.start);
+        self.usize(range.end);
+    }
+
+    fn option_address(&mut self, address: &Option<Address>) {
+        self.bool(address.is_some());
+        if let Some(address) = address {
+            self.address(address);
+        }
+    }
+
+    fn gas(&mut self, gas: &Gas) {
+        self.u64(gas.limit());
+        self.u64(gas.remaining());
+        self.i64(gas.refunded());
+    }
+
+    fn result(&mut self, result: &InterpreterResult) {
+        self.u8(result.result as u8);
+        self.bytes(&result.output);
+        self.gas(&result.gas);
+    }
+
+    fn call_input(&mut self, input: &CallInput) {
+        match input {
+            CallInput::Bytes(bytes) => self.bytes(bytes),
+            // Inputs are recorded as bytes, shared buffer is not available in the trace.
+            CallInput::SharedBuffer(_) => self.bytes(&[]),
+        }
+    }
+
+    fn state(&mut self, state: &StepState) {
+        self.usize(state.pc);
+        self.u64(state.gas_remaining);
+        self.i64(state.gas_refunded);
+        self.usize(state.stack_kept);
+        self.usize(state.stack_pushed.len());
+        for value in &state.stack_pushed {
+            self.u256(value);
+        }
+        self.bool(state.memory.is_some());
+        if let Some(memory) = &state.memory {
+            self.bytes(memory);
+        }
+    }
+
+    fn event(&mut self, event: &RecordedEvent) {
+        match event {
+            RecordedEvent::Initialize {
+                bytecode,
+                inputs,
+                is_static,
+                spec_id,
+                gas_limit,
+            } => {
+                self.u8(TAG_INITIALIZE);
+                self.bytes(bytecode);
+                self.address(&inputs.target_address);
+                self.option_address(&inputs.bytecode_address);
+                self.address(&inputs.caller_address);
+                self.call_input(&inputs.input);
+                self.u256(&inputs.call_value);
+                self.bool(*is_static);
+                self.u8(*spec_id as u8);
+                self.u64(*gas_limit);
+            }
+            RecordedEvent::Step(state) => {
+                self.u8(TAG_STEP);
+                self.state(state);
+            }
+            RecordedEvent::StepEnd { state, result } => {
+                self.u8(TAG_STEP_END);
+                self.state(state);
+                // Instruction results start at one.
+                self.u8(result.map_or(0, |result| result as u8));
+            }
+            RecordedEvent::Log(log) => {
+                self.u8(TAG_LOG);
+                self.address(&log.address);
+                self.usize(log.topics().len());
+                for topic in log.topics() {
+                    self.b256(topic);
+                }
+                self.bytes(&log.data.data);
+            }
+            RecordedEvent::Call(inputs) => {
+                self.u8(TAG_CALL);
+                self.call_input(&inputs.input);
+                self.range(&inputs.return_memory_offset);
+                self.u64(inputs.gas_limit);
+                self.address(&inputs.bytecode_address);
+                self.address(&inputs.target_address);
+                self.address(&inputs.caller);
+                match inputs.value {
+                    CallValue::Transfer(value) => {
+                        self.u8(0);
+                        self.u256(&value);
+                    }
+                    CallValue::Apparent(value) => {
+                        self.u8(1);
+                        self.u256(&value);
+                    }
+                }
+                self.u8(match inputs.scheme {
+                    CallScheme::Call => 0,
+                    CallScheme::CallCode => 1,
+                    CallScheme::DelegateCall => 2,
+                    CallScheme::StaticCall => 3,
+                });
+                self.bool(inputs.is_static);
+            }
+            RecordedEvent::CallEnd(outcome) => {
+                self.u8(TAG_CALL_END);
+                self.result(&outcome.result);
+                self.range(&outcome.memory_offset);
+            }
+            RecordedEvent::Create(inputs) => {
+                self.u8(TAG_CREATE);
+                self.address(&inputs.caller);
+                match inputs.scheme {
+                    CreateScheme::Create => self.u8(0),
+                    CreateScheme::Create2 { salt } => {
+                        self.u8(1);
+                        self.u256(&salt);
+                    }
+                    CreateScheme::Custom { address } => {
+                        self.u8(2);
+                        self.address(&address);
+                    }
+                }
+                self.u256(&inputs.value);
+                self.bytes(&inputs.init_code);
+                self.u64(inputs.gas_limit);
+            }
+            RecordedEvent::CreateEnd(outcome) => {
+                self.u8(TAG_CREATE_END);
+                self.result(&outcome.result);
+                self.option_address(&outcome.address);
+            }
+            RecordedEvent::Selfdestruct {
+                contract,
+                target,
+                value,
+            } => {
+                self.u8(TAG_SELFDESTRUCT);
+                self.address(contract);
+                self.address(target);
+                self.u256(value);
+            }
+            RecordedEvent::Settlement(settlement) => {
+                self.u8(TAG_SETTLEMENT);
+                self.u64(settlement.gas_limit);
+                self.u64(settlement.gas_spent);
+                self.u64(settlement.gas_refunded);
+                self.u64(settlement.gas_used);
+                self.varint(settlement.effective_gas_price);
+                self.address(&settlement.caller);
+                self.u256(&settlement.caller_refund);
+                self.address(&settlement.beneficiary);
+                self.u256(&settlement.beneficiary_reward);
+                self.u256(&settlement.burned);
+                self.usize(settlement.fee_payments.len());
+                for payment in &settlement.fee_payments {
+                    self.address(&payment.recipient);
+                    self.u256(&payment.amount);
+                }
+            }
+        }
+    }
+}
+
+/// Reads values written by [`Encoder`].
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TraceDecodeError> {
+        if self.0.len() < len {
+            return Err(TraceDecodeError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, TraceDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, TraceDecodeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(TraceDecodeError::InvalidValue("bool")),
+        }
+    }
+
+    fn varint(&mut self) -> Result<u128, TraceDecodeError> {
+        let mut value = 0u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.u8()?;
+            value |= u128::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(TraceDecodeError::InvalidValue("integer"))
+    }
+
+    fn u64(&mut self) -> Result<u64, TraceDecodeError> {
+        u64::try_from(self.varint()?).map_err(|_| TraceDecodeError::InvalidValue("integer"))
+    }
+
+    fn usize(&mut self) -> Result<usize, TraceDecodeError> {
+        usize::try_from(self.varint()?).map_err(|_| TraceDecodeError::InvalidValue("integer"))
+    }
+
+    fn i64(&mut self) -> Result<i64, TraceDecodeError> {
+        let value = self.u64()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn address(&mut self) -> Result<Address, TraceDecodeError> {
+        Ok(Address::from_slice(self.take(20)?))
+    }
+
+    fn b256(&mut self) -> Result<B256, TraceDecodeError> {
+        Ok(B256::from_slice(self.take(32)?))
+    }
+
+    fn u256(&mut self) -> Result<U256, TraceDecodeError> {
+        let len = self.u8()? as usize;
+        if len > 32 {
+            return Err(TraceDecodeError::InvalidValue("u256"));
+        }
+        Ok(U256::from_be_slice(self.take(len)?))
+    }
+
+    fn bytes(&mut self) -> Result<Bytes, TraceDecodeError> {
+        let len = self.usize()?;
+        Ok(Bytes::copy_from_slice(self.take(len)?))
+    }
+
+    fn range(&mut self) -> Result<Range<usize>, TraceDecodeError> {
+        Ok(self.usize()?..self.usize()?)
+    }
+
+    fn option_address(&mut self) -> Result<Option<Address>, TraceDecodeError> {
+        Ok(if self.bool()? {
+            Some(self.address()?)
+        } else {
+            None
+        })
+    }
+
+    fn instruction_result(&mut self) -> Result<InstructionResult, TraceDecodeError> {
+        InstructionResult::try_from_u8(self.u8()?)
+            .ok_or(TraceDecodeError::InvalidValue("instruction result"))
+    }
+
+    fn gas(&mut self) -> Result<Gas, TraceDecodeError> {
+        let limit = self.u64()?;
+        let remaining = self.u64()?;
+        let refunded = self.i64()?;
+        Ok(new_gas(limit, remaining, refunded))
+    }
+
+    fn result(&mut self) -> Result<InterpreterResult, TraceDecodeError> {
+        Ok(InterpreterResult::new(
+            self.instruction_result()?,
+            self.bytes()?,
+            self.gas()?,
+        ))
+    }
+
+    fn state(&mut self) -> Result<StepState, TraceDecodeError> {
+        let pc = self.usize()?;
+        let gas_remaining = self.u64()?;
+        let gas_refunded = self.i64()?;
+        let stack_kept = self.usize()?;
+        let pushed = self.usize()?;
+        let stack_pushed = (0..pushed).map(|_| self.u256()).collect::<Result<_, _>>()?;
+        let memory = if self.bool()? {
+            Some(self.bytes()?)
+        } else {
+            None
+        };
+        Ok(StepState {
+            pc,
+            gas_remaining,
+            gas_refunded,
+            stack_kept,
+            stack_pushed,
+            memory,
+        })
+    }
+
+    fn event(&mut self) -> Result<RecordedEvent, TraceDecodeError> {
+        Ok(match self.u8()? {
+            TAG_INITIALIZE => RecordedEvent::Initialize {
+                bytecode: self.bytes()?,
+                inputs: InputsImpl {
+                    target_address: self.address()?,
+                    bytecode_address: self.option_address()?,
+                    caller_address: self.address()?,
+                    input: CallInput::Bytes(self.bytes()?),
+                    call_value: self.u256()?,
+                },
+                is_static: self.bool()?,
+                spec_id: SpecId::try_from_u8(self.u8()?)
+                    .ok_or(TraceDecodeError::InvalidValue("spec id"))?,
+                gas_limit: self.u64()?,
+            },
+            TAG_STEP => RecordedEvent::Step(self.state()?),
+            TAG_STEP_END => RecordedEvent::StepEnd {
+                state: self.state()?,
+                result: match self.u8()? {
+                    0 => None,
+                    result => Some(
+                        InstructionResult::try_from_u8(result)
+                            .ok_or(TraceDecodeError::InvalidValue("instruction result"))?,
+                    ),
+                },
+            },
+            TAG_LOG => {
+                let address = self.address()?;
+                let topics = self.usize()?;
+                let topics = (0..topics).map(|_| self.b256()).collect::<Result<_, _>>()?;
+                let data = self.bytes()?;
+                RecordedEvent::Log(
+                    Log::new(address, topics, data).ok_or(TraceDecodeError::InvalidValue("log"))?,
+                )
+            }
+            TAG_CALL => RecordedEvent::Call(CallInputs {
+                input: CallInput::Bytes(self.bytes()?),
+                return_memory_offset: self.range()?,
+                gas_limit: self.u64()?,
+                bytecode_address: self.address()?,
+                target_address: self.address()?,
+                caller: self.address()?,
+                value: match self.u8()? {
+                    0 => CallValue::Transfer(self.u256()?),
+                    1 => CallValue::Apparent(self.u256()?),
+                    _ => return Err(TraceDecodeError::InvalidValue("call value")),
+                },
+                scheme: match self.u8()? {
+                    0 => CallScheme::Call,
+                    1 => CallScheme::CallCode,
+                    2 => CallScheme::DelegateCall,
+                    3 => CallScheme::StaticCall,
+                    _ => return Err(TraceDecodeError::InvalidValue("call scheme")),
+                },
+                is_static: self.bool()?,
+            }),
+            TAG_CALL_END => RecordedEvent::CallEnd(CallOutcome {
+                result: self.result()?,
+                memory_offset: self.range()?,
+            }),
+            TAG_CREATE => RecordedEvent::Create(CreateInputs {
+                caller: self.address()?,
+                scheme: match self.u8()? {
+                    0 => CreateScheme::Create,
+                    1 => CreateScheme::Create2 { salt: self.u256()? },
+                    2 => CreateScheme::Custom {
+                        address: self.address()?,
+                    },
+                    _ => return Err(TraceDecodeError::InvalidValue("create scheme")),
+                },
+                value: self.u256()?,
+                init_code: self.bytes()?,
+                gas_limit: self.u64()?,
+            }),
+            TAG_CREATE_END => RecordedEvent::CreateEnd(CreateOutcome {
+                result: self.result()?,
+                address: self.option_address()?,
+            }),
+            TAG_SELFDESTRUCT => RecordedEvent::Selfdestruct {
+                contract: self.address()?,
+                target: self.address()?,
+                value: self.u256()?,
+            },
+            TAG_SETTLEMENT => RecordedEvent::Settlement(GasSettlement {
+                gas_limit: self.u64()?,
+                gas_spent: self.u64()?,
+                gas_refunded: self.u64()?,
+                gas_used: self.u64()?,
+                effective_gas_price: self.varint()?,
+                caller: self.address()?,
+                caller_refund: self.u256()?,
+                beneficiary: self.address()?,
+                beneficiary_reward: self.u256()?,
+                burned: self.u256()?,
+                fee_payments: {
+                    let len = self.usize()?;
+                    (0..len)
+                        .map(|_| {
+                            Ok(FeePayment {
+                                recipient: self.address()?,
+                                amount: self.u256()?,
+                            })
+                        })
+                        .collect::<Result<_, _>>()?
+                },
+            }),
+            tag => return Err(TraceDecodeError::InvalidTag(tag)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CountInspector, InspectEvm};
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::TxKind;
+    use state::bytecode::opcode;
+
+    fn bytecode() -> Bytecode {
+        Bytecode::new_raw(
+            [
+                // mstore(0, 0x2a)
+                opcode::PUSH1,
+                0x2a,
+                opcode::PUSH0,
+                opcode::MSTORE,
+                // log0(0, 0x20)
+                opcode::PUSH1,
+                0x20,
+                opcode::PUSH0,
+                opcode::LOG0,
+                // call(gas, 1, 0, 0, 0x20, 0, 0x20), calls the ecrecover precompile.
+                opcode::PUSH1,
+                0x20,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0x20,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0x01,
+                opcode::GAS,
+                opcode::CALL,
+                // sstore(0, 1)
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::STOP,
+            ]
+            .into(),
+        )
+    }
+
+    fn record() -> RecordedTrace {
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(bytecode()));
+        let mut evm = ctx.build_mainnet_with_inspector(RecordingInspector::new().with_memory(true));
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .data(Bytes::from_static(&[1, 2, 3]))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+        evm.inspector.take_trace()
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let trace = record();
+        assert!(trace
+            .events
+            .iter()
+            .any(|event| matches!(event, RecordedEvent::Log(_))));
+        let encoded = trace.encode();
+        assert_eq!(RecordedTrace::decode(&encoded), Ok(trace));
+
+        assert_eq!(
+            RecordedTrace::decode(b"RVMX"),
+            Err(TraceDecodeError::InvalidHeader)
+        );
+        assert_eq!(
+            RecordedTrace::decode(&encoded[..encoded.len() - 1]),
+            Err(TraceDecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let trace = RecordedTrace::decode(&record().encode()).unwrap();
+
+        // Replaying into a recorder reproduces the trace, including the interpreter state.
+        let mut ctx = Context::mainnet();
+        let mut recorder = RecordingInspector::new().with_memory(true);
+        trace.replay(&mut recorder, &mut ctx);
+        assert_eq!(recorder.trace(), &trace);
+
+        // Replay gives the same events as the execution.
+        let mut replayed = CountInspector::new();
+        trace.replay(&mut replayed, &mut ctx);
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode()))
+            .build_mainnet_with_inspector(CountInspector::new());
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let executed = &evm.inspector;
+        assert_eq!(replayed.step_count(), executed.step_count());
+        assert_eq!(replayed.step_end_count(), executed.step_end_count());
+        assert_eq!(replayed.call_count(), executed.call_count());
+        assert_eq!(replayed.call_end_count(), executed.call_end_count());
+        assert_eq!(replayed.log_count(), executed.log_count());
+        assert_eq!(replayed.opcode_counts(), executed.opcode_counts());
+    }
+}
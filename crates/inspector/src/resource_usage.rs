@@ -0,0 +1,168 @@
+//! ResourceInspector - Inspector that tracks memory, call depth and return data usage.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter_types::MemoryTr, CallInputs, CallOutcome, CreateInputs, CreateOutcome,
+    InstructionResult, Interpreter, InterpreterResult, InterpreterTypes,
+};
+
+/// Resource usage of a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceUsage {
+    /// Peak size of the shared memory buffer in bytes, memory of all active frames combined.
+    pub peak_memory: usize,
+    /// Peak memory size of a single frame in bytes.
+    pub peak_frame_memory: usize,
+    /// Maximum call depth reached, the transaction frame has depth one.
+    pub max_depth: usize,
+    /// Number of frames created.
+    pub frame_count: u64,
+    /// Largest output returned by a single frame.
+    pub max_return_data: usize,
+    /// Sum of outputs returned by all frames.
+    pub total_return_data: u64,
+}
+
+/// Inspector that tracks peak memory, frame depth and returned data sizes.
+///
+/// Optionally halts execution with [`InstructionResult::MemoryLimitOOG`] when the combined
+/// memory of all frames exceeds the configured limit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResourceInspector {
+    usage: ResourceUsage,
+    depth: usize,
+    memory_limit: Option<usize>,
+}
+
+impl ResourceInspector {
+    /// Create a new ResourceInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Halts execution when combined memory of all frames grows above `limit` bytes.
+    pub fn with_memory_limit(mut self, limit: usize) -> Self {
+        self.memory_limit = Some(limit);
+        self
+    }
+
+    /// Returns the collected usage.
+    pub fn usage(&self) -> &ResourceUsage {
+        &self.usage
+    }
+
+    /// Clears collected usage. Memory limit is kept.
+    pub fn clear(&mut self) {
+        self.usage = ResourceUsage::default();
+        self.depth = 0;
+    }
+
+    fn frame_start(&mut self) {
+        self.depth += 1;
+        self.usage.frame_count += 1;
+        self.usage.max_depth = self.usage.max_depth.max(self.depth);
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult) {
+        self.depth = self.depth.saturating_sub(1);
+        let len = result.output.len();
+        self.usage.max_return_data = self.usage.max_return_data.max(len);
+        self.usage.total_return_data += len as u64;
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for ResourceInspector {
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        let frame_memory = interp.memory.size();
+        let memory = interp.memory.local_memory_offset() + frame_memory;
+        self.usage.peak_frame_memory = self.usage.peak_frame_memory.max(frame_memory);
+        self.usage.peak_memory = self.usage.peak_memory.max(memory);
+        if self.memory_limit.is_some_and(|limit| memory > limit) {
+            interp.halt(InstructionResult::MemoryLimitOOG);
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frame_end(&outcome.result);
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(&outcome.result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{result::ExecutionResult, Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{Bytes, TxKind};
+    use state::bytecode::{opcode, Bytecode};
+
+    fn run(inspector: ResourceInspector) -> (ExecutionResult, ResourceUsage) {
+        // Stores at memory offset 0x100 and returns 0x40 bytes.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH2,
+            0x01,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH1,
+            0x00,
+            opcode::RETURN,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(inspector);
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        (result, *evm.inspector.usage())
+    }
+
+    #[test]
+    fn test_resource_usage() {
+        let (result, usage) = run(ResourceInspector::new());
+        assert!(result.is_success());
+        assert_eq!(usage.peak_memory, 0x120);
+        assert_eq!(usage.peak_frame_memory, 0x120);
+        assert_eq!(usage.max_depth, 1);
+        assert_eq!(usage.frame_count, 1);
+        assert_eq!(usage.max_return_data, 0x40);
+        assert_eq!(usage.total_return_data, 0x40);
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let (result, usage) = run(ResourceInspector::new().with_memory_limit(0x100));
+        assert!(result.is_halt());
+        assert_eq!(usage.peak_memory, 0x120);
+    }
+}
@@ -0,0 +1,352 @@
+//! Final gas and fee settlement of a transaction reported to inspectors.
+use context::JournalEntry;
+use primitives::{Address, U256};
+use state::EvmState;
+use std::vec::Vec;
+
+/// Fee paid to a recipient other than the block beneficiary, like the L1 fee vault of OP stack
+/// chains.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeePayment {
+    /// Recipient of the fee.
+    pub recipient: Address,
+    /// Amount credited to the recipient.
+    pub amount: U256,
+}
+
+/// Gas and balance movements applied after the execution of the transaction.
+///
+/// Passed to [`Inspector::gas_settlement`](crate::Inspector::gas_settlement) once refunds are
+/// applied and fees are paid, so tracers can report a fee breakdown without diffing balances.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasSettlement {
+    /// Gas limit of the transaction.
+    pub gas_limit: u64,
+    /// Gas spent before the refund, including the EIP-7623 floor.
+    pub gas_spent: u64,
+    /// Gas refunded after the refund cap is applied.
+    pub gas_refunded: u64,
+    /// Gas paid by the caller, spent gas minus the refund.
+    pub gas_used: u64,
+    /// Effective gas price of the transaction.
+    pub effective_gas_price: u128,
+    /// Caller of the transaction.
+    pub caller: Address,
+    /// Amount returned to the caller for unused and refunded gas, including chain specific
+    /// refunds.
+    pub caller_refund: U256,
+    /// Beneficiary of the block.
+    pub beneficiary: Address,
+    /// Fee credited to the beneficiary.
+    pub beneficiary_reward: U256,
+    /// Base fee removed from circulation.
+    pub burned: U256,
+    /// Fees paid to other recipients.
+    pub fee_payments: Vec<FeePayment>,
+}
+
+impl GasSettlement {
+    /// Returns the total amount paid by the caller for the transaction.
+    pub fn total_fee(&self) -> U256 {
+        self.fee_payments
+            .iter()
+            .fold(self.beneficiary_reward + self.burned, |acc, payment| {
+                acc + payment.amount
+            })
+    }
+}
+
+/// Balances credited by a step of the post execution, like
+/// [`Handler::reimburse_caller`](handler::Handler::reimburse_caller) or
+/// [`Handler::reward_beneficiary`](handler::Handler::reward_beneficiary).
+///
+/// Recovered from the journal entries added by the step, so it holds what the handler actually
+/// applied. Accounts whose balance did not grow are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BalanceCredits {
+    credits: Vec<FeePayment>,
+}
+
+impl BalanceCredits {
+    /// Recovers the credits from the last `entries` of the journal by undoing them on top of `state`.
+    pub fn from_journal(entries: &[JournalEntry], state: &EvmState) -> Self {
+        let mut balances: Vec<(Address, U256)> = Vec::new();
+        for entry in entries {
+            let addresses = match entry {
+                JournalEntry::BalanceChange { address, .. } => [Some(*address), None],
+                JournalEntry::BalanceTransfer { from, to, .. } => [Some(*from), Some(*to)],
+                _ => continue,
+            };
+            for address in addresses.into_iter().flatten() {
+                if !balances.iter().any(|(a, _)| *a == address) {
+                    let balance = state
+                        .get(&address)
+                        .map(|account| account.info.balance)
+                        .unwrap_or_default();
+                    balances.push((address, balance));
+                }
+            }
+        }
+
+        for entry in entries.iter().rev() {
+            match *entry {
+                JournalEntry::BalanceChange {
+                    address,
+                    old_balance,
+                } => *balance_mut(&mut balances, address) = old_balance,
+                JournalEntry::BalanceTransfer { from, to, balance } => {
+                    let to_balance = balance_mut(&mut balances, to);
+                    *to_balance = to_balance.saturating_sub(balance);
+                    let from_balance = balance_mut(&mut balances, from);
+                    *from_balance = from_balance.saturating_add(balance);
+                }
+                _ => {}
+            }
+        }
+
+        let credits = balances
+            .into_iter()
+            .filter_map(|(recipient, initial)| {
+                let current = state.get(&recipient)?.info.balance;
+                let amount = current.checked_sub(initial)?;
+                (!amount.is_zero()).then_some(FeePayment { recipient, amount })
+            })
+            .collect();
+        Self { credits }
+    }
+
+    /// Returns the amount credited to `address`.
+    pub fn amount(&self, address: Address) -> U256 {
+        self.credits
+            .iter()
+            .find(|credit| credit.recipient == address)
+            .map(|credit| credit.amount)
+            .unwrap_or_default()
+    }
+
+    /// Returns the sum of all credits.
+    pub fn total(&self) -> U256 {
+        self.credits
+            .iter()
+            .fold(U256::ZERO, |acc, credit| acc + credit.amount)
+    }
+
+    /// Returns the credits in the order the accounts were first changed.
+    pub fn iter(&self) -> impl Iterator<Item = &FeePayment> {
+        self.credits.iter()
+    }
+}
+
+fn balance_mut(balances: &mut [(Address, U256)], address: Address) -> &mut U256 {
+    &mut balances
+        .iter_mut()
+        .find(|(a, _)| *a == address)
+        .expect("balances hold every changed account")
+        .1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InspectEvm, Inspector};
+    use context::{
+        cfg::{FeeDestination, FeePolicy, FeeRecipient},
+        BlockEnv, CfgEnv, Context, TxEnv,
+    };
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use interpreter::InterpreterTypes;
+    use primitives::{address, TxKind};
+    use state::{bytecode::opcode, Bytecode};
+
+    #[derive(Default)]
+    struct SettlementInspector {
+        settlements: Vec<GasSettlement>,
+    }
+
+    impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for SettlementInspector {
+        fn gas_settlement(&mut self, _context: &mut CTX, settlement: &GasSettlement) {
+            self.settlements.push(settlement.clone());
+        }
+    }
+
+    #[test]
+    fn gas_settlement_matches_balances() {
+        let beneficiary = address!("0x00000000000000000000000000000000000000bb");
+        // Sets and clears the slot to get a refund.
+        let bytecode = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                1,
+                opcode::PUSH1,
+                0,
+                opcode::SSTORE,
+                opcode::PUSH1,
+                0,
+                opcode::PUSH1,
+                0,
+                opcode::SSTORE,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let ctx = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_block(BlockEnv {
+                basefee: 7,
+                beneficiary,
+                ..Default::default()
+            });
+        let mut evm = ctx.build_mainnet_with_inspector(SettlementInspector::default());
+        let output = evm
+            .inspect_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .gas_price(10)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(output.result.is_success());
+
+        let [settlement] = evm.inspector.settlements.as_slice() else {
+            panic!("expected one settlement");
+        };
+        assert_eq!(settlement.gas_used, output.result.gas_used());
+        assert_eq!(
+            settlement.gas_spent - settlement.gas_refunded,
+            settlement.gas_used
+        );
+        assert!(settlement.gas_refunded > 0);
+        assert_eq!(settlement.effective_gas_price, 10);
+        assert_eq!(
+            settlement.burned,
+            U256::from(7 * settlement.gas_used as u128)
+        );
+        assert_eq!(
+            settlement.beneficiary_reward,
+            U256::from(3 * settlement.gas_used as u128)
+        );
+        assert_eq!(
+            output.state[&beneficiary].info.balance,
+            settlement.beneficiary_reward
+        );
+        assert_eq!(
+            output.state[&BENCH_CALLER].info.balance,
+            BENCH_CALLER_BALANCE - settlement.total_fee()
+        );
+        assert_eq!(
+            settlement.caller_refund + settlement.total_fee(),
+            U256::from(10 * 100_000u128)
+        );
+    }
+
+    #[test]
+    fn balance_credits_from_journal() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let sponsor = address!("0x00000000000000000000000000000000000000bb");
+        let vault = address!("0x00000000000000000000000000000000000000cc");
+        let mut state = EvmState::default();
+        for (address, balance) in [(caller, 70), (sponsor, 30), (vault, 5)] {
+            let mut account = state::Account::default();
+            account.info.balance = U256::from(balance);
+            state.insert(address, account);
+        }
+        // Caller is credited 100 and 30 of it is forwarded to the sponsor, vault is debited.
+        let entries = [
+            JournalEntry::BalanceChange {
+                old_balance: U256::ZERO,
+                address: caller,
+            },
+            JournalEntry::BalanceTransfer {
+                balance: U256::from(30),
+                from: caller,
+                to: sponsor,
+            },
+            JournalEntry::BalanceChange {
+                old_balance: U256::from(10),
+                address: vault,
+            },
+        ];
+
+        let credits = BalanceCredits::from_journal(&entries, &state);
+        assert_eq!(
+            credits.iter().copied().collect::<Vec<_>>(),
+            [
+                FeePayment {
+                    recipient: caller,
+                    amount: U256::from(70)
+                },
+                FeePayment {
+                    recipient: sponsor,
+                    amount: U256::from(30)
+                },
+            ]
+        );
+        assert_eq!(credits.amount(vault), U256::ZERO);
+        assert_eq!(credits.total(), U256::from(100));
+    }
+
+    #[test]
+    fn gas_settlement_fee_policy() {
+        let beneficiary = address!("0x00000000000000000000000000000000000000bb");
+        let vault = address!("0x00000000000000000000000000000000000000cc");
+        let policy = FeePolicy::MAINNET
+            .with_priority_fee(FeeDestination::split(
+                FeeRecipient::Beneficiary,
+                FeeRecipient::Address(vault),
+                50,
+            ))
+            .with_base_fee(FeeDestination::to(FeeRecipient::Address(vault)));
+        let ctx = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+                [opcode::STOP].into(),
+            )))
+            .with_cfg(CfgEnv::new().with_fee_policy(policy))
+            .with_block(BlockEnv {
+                basefee: 7,
+                beneficiary,
+                ..Default::default()
+            });
+        let mut evm = ctx.build_mainnet_with_inspector(SettlementInspector::default());
+        let output = evm
+            .inspect_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .gas_price(11)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        let gas_used = output.result.gas_used() as u128;
+
+        let [settlement] = evm.inspector.settlements.as_slice() else {
+            panic!("expected one settlement");
+        };
+        assert_eq!(settlement.burned, U256::ZERO);
+        assert_eq!(settlement.beneficiary_reward, U256::from(2 * gas_used));
+        assert_eq!(
+            settlement.fee_payments,
+            [FeePayment {
+                recipient: vault,
+                amount: U256::from(9 * gas_used)
+            }]
+        );
+        assert_eq!(
+            output.state[&beneficiary].info.balance,
+            settlement.beneficiary_reward
+        );
+        assert_eq!(
+            output.state[&vault].info.balance,
+            settlement.fee_payments[0].amount
+        );
+        assert_eq!(
+            output.state[&BENCH_CALLER].info.balance,
+            BENCH_CALLER_BALANCE - settlement.total_fee()
+        );
+    }
+}
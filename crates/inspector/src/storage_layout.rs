@@ -0,0 +1,486 @@
+//! Storage layout aware storage tracing.
+//!
+//! [`StorageLayout`] is the `storageLayout` output of solc. Together with the KECCAK256 preimages
+//! observed during execution it is used to map raw storage slots back to variable names, including
+//! mapping keys, array indices and struct members.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{InputsTr, Jumps, MemoryTr},
+    Interpreter,
+};
+use primitives::{Address, Bytes, HashMap, B256, U256};
+use serde::{Deserialize, Deserializer, Serialize};
+use state::bytecode::opcode;
+
+/// Maximum distance between a keccak derived base slot and an accessed slot that is still
+/// attributed to that base. Covers struct members and elements of arrays.
+const MAX_SLOT_OFFSET: u64 = 1 << 32;
+
+/// Maximum size of KECCAK256 input that is recorded as a preimage.
+const MAX_PREIMAGE_LEN: usize = 1024;
+
+/// Solidity storage layout as emitted by solc in `storageLayout` output selection.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct StorageLayout {
+    /// Top level state variables.
+    pub storage: Vec<StorageVariable>,
+    /// Type definitions referenced by variables.
+    #[serde(default)]
+    pub types: HashMap<String, StorageType>,
+}
+
+/// State variable or struct member in a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct StorageVariable {
+    /// Name of the variable.
+    pub label: String,
+    /// Slot of the variable, relative to the enclosing struct for members.
+    #[serde(deserialize_with = "deserialize_u256_dec")]
+    pub slot: U256,
+    /// Byte offset inside the slot.
+    #[serde(default)]
+    pub offset: u32,
+    /// Type identifier, key in [`StorageLayout::types`].
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Type definition in a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// One of `inplace`, `mapping`, `dynamic_array` or `bytes`.
+    pub encoding: String,
+    /// Human readable type name.
+    pub label: String,
+    /// Number of bytes the type occupies, for `inplace` encoding.
+    #[serde(deserialize_with = "deserialize_u64_dec")]
+    pub number_of_bytes: u64,
+    /// Key type of a mapping.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Value type of a mapping.
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Element type of an array.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Members of a struct.
+    #[serde(default)]
+    pub members: Option<Vec<StorageVariable>>,
+}
+
+impl StorageLayout {
+    /// Parses the solc storage layout JSON.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the human readable name of the given slot, e.g. `balances[0x..].amount`.
+    ///
+    /// `preimages` maps KECCAK256 outputs to their inputs and is used to reconstruct mapping keys
+    /// and dynamic array indices.
+    pub fn decode_slot(&self, slot: U256, preimages: &HashMap<B256, Bytes>) -> Option<String> {
+        self.resolve(slot, preimages, 0).map(|(label, _)| label)
+    }
+
+    /// Resolves slot into variable name and its type identifier.
+    fn resolve(
+        &self,
+        slot: U256,
+        preimages: &HashMap<B256, Bytes>,
+        depth: usize,
+    ) -> Option<(String, &String)> {
+        // Bound recursion of crafted preimage chains.
+        if depth > 16 {
+            return None;
+        }
+        // Slots derived from a hash, e.g. mapping values or dynamic array elements.
+        for (hash, preimage) in preimages {
+            let Some(offset) = slot.checked_sub(U256::from_be_bytes(hash.0)) else {
+                continue;
+            };
+            if offset >= U256::from(MAX_SLOT_OFFSET) {
+                continue;
+            }
+            if let Some(resolved) = self.resolve_hashed(preimage, offset, preimages, depth) {
+                return Some(resolved);
+            }
+        }
+        // Top level variables, closest variable below the slot contains it.
+        let var = self
+            .storage
+            .iter()
+            .filter(|var| var.slot <= slot)
+            .max_by_key(|var| var.slot)?;
+        self.descend(var.label.clone(), &var.ty, slot - var.slot)
+    }
+
+    /// Resolves slot `keccak256(preimage) + offset`.
+    fn resolve_hashed(
+        &self,
+        preimage: &Bytes,
+        offset: U256,
+        preimages: &HashMap<B256, Bytes>,
+        depth: usize,
+    ) -> Option<(String, &String)> {
+        if preimage.len() < 32 {
+            return None;
+        }
+        let (key, base) = preimage.split_at(preimage.len() - 32);
+        let (parent, parent_ty) = self.resolve(U256::from_be_slice(base), preimages, depth + 1)?;
+        let ty = self.types.get(parent_ty)?;
+        match ty.encoding.as_str() {
+            "mapping" if !key.is_empty() => {
+                let key_ty = ty.key.as_ref().and_then(|key| self.types.get(key));
+                let label = format!("{parent}[{}]", format_key(key, key_ty));
+                self.descend(label, ty.value.as_ref()?, offset)
+            }
+            "dynamic_array" if key.is_empty() => {
+                self.descend_array(parent, ty.base.as_ref()?, offset)
+            }
+            _ => None,
+        }
+    }
+
+    /// Descends into in-place structs and static arrays until the slot offset is consumed.
+    fn descend<'a>(
+        &'a self,
+        label: String,
+        ty: &'a String,
+        offset: U256,
+    ) -> Option<(String, &'a String)> {
+        let Some(def) = self.types.get(ty).filter(|def| def.encoding == "inplace") else {
+            return offset.is_zero().then_some((label, ty));
+        };
+        if let Some(members) = &def.members {
+            let member = members
+                .iter()
+                .filter(|m| m.slot <= offset)
+                .max_by_key(|m| m.slot)?;
+            return self.descend(
+                format!("{label}.{}", member.label),
+                &member.ty,
+                offset - member.slot,
+            );
+        }
+        if let Some(base) = &def.base {
+            return self.descend_array(label, base, offset);
+        }
+        (offset < U256::from(def.number_of_bytes.div_ceil(32).max(1))).then_some((label, ty))
+    }
+
+    /// Descends into array element at the given slot offset.
+    ///
+    /// For packed elements the index of the first element in the slot is used.
+    fn descend_array<'a>(
+        &'a self,
+        label: String,
+        base: &'a String,
+        offset: U256,
+    ) -> Option<(String, &'a String)> {
+        let size = self.types.get(base)?.number_of_bytes;
+        let (index, offset) = if size >= 32 {
+            let slots = U256::from(size.div_ceil(32));
+            (offset / slots, offset % slots)
+        } else {
+            (offset * U256::from(32 / size.max(1)), U256::ZERO)
+        };
+        self.descend(format!("{label}[{index}]"), base, offset)
+    }
+}
+
+/// Formats mapping key according to the key type.
+fn format_key(key: &[u8], ty: Option<&StorageType>) -> String {
+    let label = ty.map(|ty| ty.label.as_str()).unwrap_or_default();
+    if key.len() == 32 {
+        if label == "address" || label.starts_with("contract ") {
+            return Address::from_word(B256::from_slice(key)).to_string();
+        }
+        if label.starts_with("uint") || label == "bool" {
+            return U256::from_be_slice(key).to_string();
+        }
+    }
+    if label == "string" {
+        if let Ok(key) = core::str::from_utf8(key) {
+            return format!("{key:?}");
+        }
+    }
+    primitives::hex::encode_prefixed(key)
+}
+
+fn deserialize_u256_dec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    U256::from_str_radix(&s, 10).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_u64_dec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+/// Kind of storage access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageAccessKind {
+    /// `SLOAD`.
+    Read,
+    /// `SSTORE`.
+    Write,
+}
+
+/// Storage access annotated with variable name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StorageAccess {
+    /// Account whose storage is accessed.
+    pub address: Address,
+    /// Kind of the access.
+    pub kind: StorageAccessKind,
+    /// Raw storage slot.
+    pub slot: U256,
+    /// Loaded or stored value.
+    pub value: U256,
+    /// Variable name if the slot could be decoded with the layout of the account.
+    pub label: Option<String>,
+}
+
+/// Inspector that records `SLOAD` and `SSTORE` and annotates them using [`StorageLayout`]s.
+#[derive(Clone, Debug, Default)]
+pub struct StorageLayoutInspector {
+    layouts: HashMap<Address, StorageLayout>,
+    preimages: HashMap<B256, Bytes>,
+    accesses: Vec<StorageAccess>,
+    /// Input of KECCAK256 that is being executed.
+    pending_keccak: Option<Bytes>,
+    /// Address and slot of SLOAD that is being executed.
+    pending_sload: Option<(Address, U256)>,
+}
+
+impl StorageLayoutInspector {
+    /// Create a new StorageLayoutInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers storage layout of the contract at the given address.
+    pub fn with_layout(mut self, address: Address, layout: StorageLayout) -> Self {
+        self.layouts.insert(address, layout);
+        self
+    }
+
+    /// Registers storage layout of the contract at the given address.
+    pub fn insert_layout(&mut self, address: Address, layout: StorageLayout) {
+        self.layouts.insert(address, layout);
+    }
+
+    /// Returns recorded storage accesses in execution order.
+    pub fn accesses(&self) -> &[StorageAccess] {
+        &self.accesses
+    }
+
+    /// Returns observed KECCAK256 preimages.
+    pub fn preimages(&self) -> &HashMap<B256, Bytes> {
+        &self.preimages
+    }
+
+    /// Clears recorded accesses and preimages. Registered layouts are kept.
+    pub fn clear(&mut self) {
+        self.preimages.clear();
+        self.accesses.clear();
+        self.pending_keccak = None;
+        self.pending_sload = None;
+    }
+
+    fn record(&mut self, address: Address, kind: StorageAccessKind, slot: U256, value: U256) {
+        let label = self
+            .layouts
+            .get(&address)
+            .and_then(|layout| layout.decode_slot(slot, &self.preimages));
+        self.accesses.push(StorageAccess {
+            address,
+            kind,
+            slot,
+            value,
+            label,
+        });
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for StorageLayoutInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        match interp.bytecode.opcode() {
+            opcode::KECCAK256 => {
+                let (Ok(offset), Ok(len)) = (interp.stack.peek(0), interp.stack.peek(1)) else {
+                    return;
+                };
+                let (Ok(offset), Ok(len)) = (usize::try_from(offset), usize::try_from(len)) else {
+                    return;
+                };
+                if len <= MAX_PREIMAGE_LEN && offset.saturating_add(len) <= interp.memory.size() {
+                    self.pending_keccak = Some(Bytes::copy_from_slice(
+                        &interp.memory.slice_len(offset, len),
+                    ));
+                }
+            }
+            opcode::SLOAD => {
+                if let Ok(slot) = interp.stack.peek(0) {
+                    self.pending_sload = Some((interp.input.target_address(), slot));
+                }
+            }
+            opcode::SSTORE => {
+                if let (Ok(slot), Ok(value)) = (interp.stack.peek(0), interp.stack.peek(1)) {
+                    self.record(
+                        interp.input.target_address(),
+                        StorageAccessKind::Write,
+                        slot,
+                        value,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        if let Some(preimage) = self.pending_keccak.take() {
+            if let Ok(hash) = interp.stack.peek(0) {
+                self.preimages.insert(hash.into(), preimage);
+            }
+        }
+        if let Some((address, slot)) = self.pending_sload.take() {
+            if let Ok(value) = interp.stack.peek(0) {
+                self.record(address, StorageAccessKind::Read, slot, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::{keccak256, TxKind};
+    use state::bytecode::Bytecode;
+
+    const LAYOUT: &str = r#"{
+        "storage": [
+            {"astId": 1, "contract": "C", "label": "total", "offset": 0, "slot": "0", "type": "t_uint256"},
+            {"astId": 2, "contract": "C", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_struct(Info)1_storage)"},
+            {"astId": 3, "contract": "C", "label": "list", "offset": 0, "slot": "2", "type": "t_array(t_uint256)dyn_storage"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+            "t_array(t_uint256)dyn_storage": {"encoding": "dynamic_array", "label": "uint256[]", "numberOfBytes": "32", "base": "t_uint256"},
+            "t_mapping(t_address,t_struct(Info)1_storage)": {"encoding": "mapping", "label": "mapping(address => struct C.Info)", "numberOfBytes": "32", "key": "t_address", "value": "t_struct(Info)1_storage"},
+            "t_struct(Info)1_storage": {"encoding": "inplace", "label": "struct C.Info", "numberOfBytes": "64", "members": [
+                {"astId": 4, "contract": "C", "label": "amount", "offset": 0, "slot": "0", "type": "t_uint256"},
+                {"astId": 5, "contract": "C", "label": "nonce", "offset": 0, "slot": "1", "type": "t_uint256"}
+            ]}
+        }
+    }"#;
+
+    #[test]
+    fn test_decode_slot() {
+        let layout = StorageLayout::from_json(LAYOUT).unwrap();
+        let mut preimages = HashMap::default();
+        assert_eq!(
+            layout.decode_slot(U256::ZERO, &preimages).as_deref(),
+            Some("total")
+        );
+
+        let mut preimage = [0u8; 64];
+        preimage[12..32].copy_from_slice(BENCH_CALLER.as_slice());
+        preimage[63] = 1;
+        let hash = keccak256(preimage);
+        preimages.insert(hash, Bytes::copy_from_slice(&preimage));
+        let base = U256::from_be_bytes(hash.0);
+        assert_eq!(
+            layout.decode_slot(base + U256::from(1), &preimages),
+            Some(format!("balances[{BENCH_CALLER}].nonce"))
+        );
+
+        let hash = keccak256(U256::from(2).to_be_bytes::<32>());
+        preimages.insert(
+            hash,
+            Bytes::copy_from_slice(&U256::from(2).to_be_bytes::<32>()),
+        );
+        let base = U256::from_be_bytes(hash.0);
+        assert_eq!(
+            layout
+                .decode_slot(base + U256::from(3), &preimages)
+                .as_deref(),
+            Some("list[3]")
+        );
+    }
+
+    #[test]
+    fn test_storage_layout_inspector() {
+        let mut code = vec![
+            // total = 42
+            opcode::PUSH1,
+            0x2a,
+            opcode::PUSH1,
+            0x00,
+            opcode::SSTORE,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(BENCH_CALLER.as_slice());
+        code.extend_from_slice(&[
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x20,
+            opcode::MSTORE,
+            // keccak256(caller . 1)
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH1,
+            0x00,
+            opcode::KECCAK256,
+            // balances[caller].amount = 7
+            opcode::PUSH1,
+            0x07,
+            opcode::SWAP1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x00,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())));
+        let inspector = StorageLayoutInspector::new()
+            .with_layout(BENCH_TARGET, StorageLayout::from_json(LAYOUT).unwrap());
+        let mut evm = ctx.build_mainnet_with_inspector(inspector);
+        evm.inspect_one_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let accesses = evm.inspector.accesses();
+        assert_eq!(accesses.len(), 3);
+        assert_eq!(accesses[0].kind, StorageAccessKind::Write);
+        assert_eq!(accesses[0].label.as_deref(), Some("total"));
+        assert_eq!(
+            accesses[1].label,
+            Some(format!("balances[{BENCH_CALLER}].amount"))
+        );
+        assert_eq!(accesses[1].value, U256::from(7));
+        assert_eq!(accesses[2].kind, StorageAccessKind::Read);
+        assert_eq!(accesses[2].value, U256::from(42));
+        assert_eq!(accesses[2].label.as_deref(), Some("total"));
+    }
+}
@@ -0,0 +1,514 @@
+//! TaintInspector - Inspector that tracks values derived from calldata into sensitive sinks.
+use crate::inspector::Inspector;
+use interpreter::{
+    interpreter::EthInterpreter,
+    interpreter_types::{InputsTr, Jumps, LoopControl},
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+};
+use primitives::{Address, HashMap, U256};
+use state::bytecode::opcode::{self, OpCode};
+use std::{vec, vec::Vec};
+
+/// Label of a tainted value, index of its origin.
+type Label = usize;
+
+/// Instruction that consumes a tainted value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaintSink {
+    /// Value transferred by `CALL` or `CALLCODE`.
+    CallValue,
+    /// Target of `DELEGATECALL`.
+    DelegatecallTarget,
+    /// Beneficiary of `SELFDESTRUCT`.
+    SelfdestructTarget,
+}
+
+/// Instruction where a tainted value entered the stack or memory of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaintStep {
+    /// Address of the executing contract.
+    pub address: Address,
+    /// Call depth of the frame, the transaction frame has depth one.
+    pub depth: usize,
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// `CALLDATALOAD`, `CALLDATACOPY` or `SLOAD`.
+    pub opcode: u8,
+}
+
+/// Tainted value consumed by a sink.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaintFinding {
+    /// Kind of the sink.
+    pub sink: TaintSink,
+    /// Instruction of the sink.
+    pub step: TaintStep,
+    /// Steps the tainted value went through, starting with the read of transaction calldata.
+    ///
+    /// Calldata of nested calls and storage loads continue the path of the tainted value they
+    /// were derived from.
+    pub path: Vec<TaintStep>,
+}
+
+/// Taint of the calldata of a frame.
+#[derive(Clone, Copy, Debug)]
+enum CalldataTaint {
+    /// Calldata of the transaction, controlled by the caller.
+    Transaction,
+    /// Calldata passed from tainted memory of the parent frame.
+    Derived(Label),
+    /// Calldata is not tainted.
+    Clean,
+}
+
+/// Shadow state of a frame.
+#[derive(Clone, Debug)]
+struct Frame {
+    stack: Vec<Option<Label>>,
+    /// Taint of memory bytes, only grown when tainted bytes are written.
+    memory: Vec<Option<Label>>,
+    calldata: CalldataTaint,
+}
+
+impl Frame {
+    fn new(calldata: CalldataTaint) -> Self {
+        Self {
+            stack: Vec::new(),
+            memory: Vec::new(),
+            calldata,
+        }
+    }
+
+    /// Returns the label of the first tainted byte in the range.
+    fn memory_label(&self, offset: usize, len: usize) -> Option<Label> {
+        let end = offset.saturating_add(len).min(self.memory.len());
+        self.memory
+            .get(offset..end)?
+            .iter()
+            .find_map(|label| *label)
+    }
+
+    fn set_memory(&mut self, offset: usize, len: usize, label: Option<Label>) {
+        let end = offset.saturating_add(len);
+        if label.is_some() && end > self.memory.len() {
+            self.memory.resize(end, None);
+        }
+        let end = end.min(self.memory.len());
+        if let Some(range) = self.memory.get_mut(offset..end) {
+            range.fill(label);
+        }
+    }
+}
+
+/// Effect of the current instruction applied when it succeeds.
+#[derive(Clone, Debug, Default)]
+enum Effect {
+    #[default]
+    None,
+    SetMemory {
+        offset: usize,
+        len: usize,
+        label: Option<Label>,
+    },
+    CopyMemory {
+        dst: usize,
+        src: usize,
+        len: usize,
+    },
+    Store {
+        key: U256,
+        label: Option<Label>,
+    },
+}
+
+/// Pending results of the current instruction.
+#[derive(Clone, Debug, Default)]
+struct Pending {
+    outputs: Vec<Option<Label>>,
+    effect: Effect,
+    finding: Option<(TaintSink, TaintStep, Label)>,
+}
+
+/// Inspector that tracks values derived from transaction calldata through the stack, memory and
+/// storage and reports tainted values reaching `CALL` value, `DELEGATECALL` target and
+/// `SELFDESTRUCT` beneficiary.
+///
+/// Results of instructions are tainted if any of their inputs is, `MLOAD`, `KECCAK256` and call
+/// arguments are tainted if any byte of the memory range is. Return data, code, call results and
+/// created addresses are not tainted. Storage writes stay tainted if their frame reverts.
+#[derive(Clone, Debug, Default)]
+pub struct TaintInspector {
+    findings: Vec<TaintFinding>,
+    /// Origins of labels with the label they were derived from.
+    origins: Vec<(TaintStep, Option<Label>)>,
+    storage: HashMap<(Address, U256), Label>,
+    frames: Vec<Frame>,
+    pending: Pending,
+    /// Taint of arguments of the call being made.
+    call_args: Option<Label>,
+}
+
+impl TaintInspector {
+    /// Create a new TaintInspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns findings in execution order.
+    pub fn findings(&self) -> &[TaintFinding] {
+        &self.findings
+    }
+
+    /// Consumes the inspector and returns findings.
+    pub fn into_findings(self) -> Vec<TaintFinding> {
+        self.findings
+    }
+
+    /// Clears findings and tracked taint.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the path of the label, starting with its root origin.
+    fn path(&self, label: Label) -> Vec<TaintStep> {
+        let mut path = Vec::new();
+        let mut next = Some(label);
+        while let Some(label) = next {
+            let (step, parent) = self.origins[label];
+            path.push(step);
+            next = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn origin(&mut self, step: TaintStep, parent: Option<Label>) -> Label {
+        self.origins.push((step, parent));
+        self.origins.len() - 1
+    }
+
+    /// Returns the label of a calldata read.
+    fn calldata_label(&mut self, step: TaintStep) -> Option<Label> {
+        match self.frames.last()?.calldata {
+            CalldataTaint::Transaction => Some(self.origin(step, None)),
+            CalldataTaint::Derived(parent) => Some(self.origin(step, Some(parent))),
+            CalldataTaint::Clean => None,
+        }
+    }
+}
+
+/// Reads stack value `n` from the top as usize.
+fn peek_usize(interp: &Interpreter<EthInterpreter>, n: usize) -> Option<usize> {
+    usize::try_from(interp.stack.peek(n).ok()?).ok()
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for TaintInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let op = interp.bytecode.opcode();
+        let Some(info) = OpCode::new(op).map(|op| op.info()) else {
+            return;
+        };
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+        frame.stack.resize(interp.stack.len(), None);
+        let n_inputs = info.inputs() as usize;
+        if frame.stack.len() < n_inputs {
+            return;
+        }
+        let inputs = frame.stack.split_off(frame.stack.len() - n_inputs);
+        // Label of the stack input `n` from the top.
+        let input = |n: usize| inputs[n_inputs - 1 - n];
+        let step = TaintStep {
+            address: interp.input.target_address(),
+            depth: self.frames.len(),
+            pc: interp.bytecode.pc(),
+            opcode: op,
+        };
+
+        let mut effect = Effect::None;
+        let mut finding = None;
+        let mut output = inputs.iter().rev().find_map(|label| *label);
+        match op {
+            opcode::CALLDATALOAD => output = self.calldata_label(step),
+            opcode::CALLDATACOPY => {
+                if let (Some(offset), Some(len)) = (peek_usize(interp, 0), peek_usize(interp, 2)) {
+                    let label = self.calldata_label(step);
+                    effect = Effect::SetMemory { offset, len, label };
+                }
+            }
+            opcode::CODECOPY | opcode::RETURNDATACOPY | opcode::EXTCODECOPY => {
+                let (offset, len) = if op == opcode::EXTCODECOPY {
+                    (1, 3)
+                } else {
+                    (0, 2)
+                };
+                if let (Some(offset), Some(len)) =
+                    (peek_usize(interp, offset), peek_usize(interp, len))
+                {
+                    effect = Effect::SetMemory {
+                        offset,
+                        len,
+                        label: None,
+                    };
+                }
+            }
+            opcode::MLOAD | opcode::KECCAK256 => {
+                let frame = self.frames.last().expect("frame exists");
+                let len = if op == opcode::MLOAD {
+                    Some(32)
+                } else {
+                    peek_usize(interp, 1)
+                };
+                output = peek_usize(interp, 0)
+                    .zip(len)
+                    .and_then(|(offset, len)| frame.memory_label(offset, len));
+            }
+            opcode::MSTORE | opcode::MSTORE8 => {
+                if let Some(offset) = peek_usize(interp, 0) {
+                    let len = if op == opcode::MSTORE { 32 } else { 1 };
+                    effect = Effect::SetMemory {
+                        offset,
+                        len,
+                        label: input(1),
+                    };
+                }
+            }
+            opcode::MCOPY => {
+                if let (Some(dst), Some(src), Some(len)) = (
+                    peek_usize(interp, 0),
+                    peek_usize(interp, 1),
+                    peek_usize(interp, 2),
+                ) {
+                    effect = Effect::CopyMemory { dst, src, len };
+                }
+            }
+            opcode::SLOAD => {
+                let key = (step.address, interp.stack.peek(0).unwrap_or_default());
+                output = self
+                    .storage
+                    .get(&key)
+                    .copied()
+                    .map(|parent| self.origin(step, Some(parent)));
+            }
+            opcode::SSTORE => {
+                effect = Effect::Store {
+                    key: interp.stack.peek(0).unwrap_or_default(),
+                    label: input(1),
+                };
+            }
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                let has_value = matches!(op, opcode::CALL | opcode::CALLCODE);
+                let args = if has_value { 3 } else { 2 };
+                finding = match op {
+                    opcode::CALL | opcode::CALLCODE => input(2).map(|l| (TaintSink::CallValue, l)),
+                    opcode::DELEGATECALL => input(1).map(|l| (TaintSink::DelegatecallTarget, l)),
+                    _ => None,
+                };
+                let frame = self.frames.last().expect("frame exists");
+                self.call_args = peek_usize(interp, args)
+                    .zip(peek_usize(interp, args + 1))
+                    .and_then(|(offset, len)| frame.memory_label(offset, len));
+                if let (Some(offset), Some(len)) =
+                    (peek_usize(interp, args + 2), peek_usize(interp, args + 3))
+                {
+                    effect = Effect::SetMemory {
+                        offset,
+                        len,
+                        label: None,
+                    };
+                }
+                output = None;
+            }
+            opcode::SELFDESTRUCT => {
+                finding = input(0).map(|l| (TaintSink::SelfdestructTarget, l));
+            }
+            // Created addresses are not tainted.
+            opcode::CREATE | opcode::CREATE2 => output = None,
+            _ => {}
+        }
+        let outputs = match op {
+            opcode::DUP1..=opcode::DUP16 => {
+                let mut outputs = inputs.clone();
+                outputs.push(inputs[0]);
+                outputs
+            }
+            opcode::SWAP1..=opcode::SWAP16 => {
+                let mut outputs = inputs.clone();
+                outputs.swap(0, n_inputs - 1);
+                outputs
+            }
+            _ => vec![output; info.outputs() as usize],
+        };
+        self.pending = Pending {
+            outputs,
+            effect,
+            finding: finding.map(|(sink, label)| (sink, step, label)),
+        };
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let pending = core::mem::take(&mut self.pending);
+        if interp
+            .bytecode
+            .instruction_result()
+            .is_some_and(|result| result.is_error())
+        {
+            return;
+        }
+        let address = interp.input.target_address();
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+        frame.stack.extend(pending.outputs);
+        match pending.effect {
+            Effect::None => {}
+            Effect::SetMemory { offset, len, label } => frame.set_memory(offset, len, label),
+            Effect::CopyMemory { dst, src, len } => {
+                let end = src.saturating_add(len).min(frame.memory.len());
+                let labels = frame.memory.get(src..end).unwrap_or_default().to_vec();
+                frame.set_memory(dst, len, None);
+                for (i, label) in labels.into_iter().enumerate() {
+                    if label.is_some() {
+                        frame.set_memory(dst + i, 1, label);
+                    }
+                }
+            }
+            Effect::Store { key, label } => match label {
+                Some(label) => {
+                    self.storage.insert((address, key), label);
+                }
+                None => {
+                    self.storage.remove(&(address, key));
+                }
+            },
+        }
+        if let Some((sink, step, label)) = pending.finding {
+            self.findings.push(TaintFinding {
+                sink,
+                step,
+                path: self.path(label),
+            });
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let calldata = if self.frames.is_empty() {
+            CalldataTaint::Transaction
+        } else {
+            match self.call_args.take() {
+                Some(label) => CalldataTaint::Derived(label),
+                None => CalldataTaint::Clean,
+            }
+        };
+        self.frames.push(Frame::new(calldata));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frames.pop();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frames.push(Frame::new(CalldataTaint::Clean));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frames.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InspectEvm;
+    use context::{Context, TxEnv};
+    use database::{BenchmarkDB, BENCH_TARGET};
+    use handler::{MainBuilder, MainContext};
+    use primitives::Bytes;
+    use state::bytecode::Bytecode;
+
+    #[test]
+    fn test_taint() {
+        let contract_data: Bytes = Bytes::from(vec![
+            // sstore(0, calldataload(0))
+            opcode::PUSH0,
+            opcode::CALLDATALOAD,
+            opcode::PUSH0,
+            opcode::SSTORE,
+            // call(0, 0x1000, sload(0), 0, 0, 0, 0)
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::SLOAD,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::PUSH0,
+            opcode::CALL,
+            opcode::POP,
+            // calldatacopy(0, 0, 0x20)
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CALLDATACOPY,
+            // delegatecall(0, mload(0), 0, 0, 0, 0)
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::MLOAD,
+            opcode::PUSH0,
+            opcode::DELEGATECALL,
+            opcode::POP,
+            opcode::STOP,
+        ]);
+        let ctx =
+            Context::mainnet().with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        let mut evm = ctx.build_mainnet_with_inspector(TaintInspector::new());
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder_for_bench()
+                    .gas_limit(200_000)
+                    .data(U256::from(1).to_be_bytes_vec().into())
+                    .build_fill(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+
+        let step = |pc, opcode| TaintStep {
+            address: BENCH_TARGET,
+            depth: 1,
+            pc,
+            opcode,
+        };
+        assert_eq!(
+            evm.inspector.findings(),
+            [
+                TaintFinding {
+                    sink: TaintSink::CallValue,
+                    step: step(14, opcode::CALL),
+                    path: vec![step(1, opcode::CALLDATALOAD), step(9, opcode::SLOAD)],
+                },
+                TaintFinding {
+                    sink: TaintSink::DelegatecallTarget,
+                    step: step(28, opcode::DELEGATECALL),
+                    path: vec![step(20, opcode::CALLDATACOPY)],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Output size limits of the built-in tracers.
+
+/// Limits of the trace produced by a tracer.
+///
+/// Tracers of adversarial transactions can grow to gigabytes, nodes serving traces over RPC
+/// should bound them. Every limit is disabled by default, tracers mark the truncated parts of
+/// the output explicitly so consumers can tell a limited trace from a complete one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TraceLimits {
+    /// Maximum number of traced call and create frames, including the transaction frame.
+    ///
+    /// Frames entered after the limit is reached are not traced.
+    pub max_frames: Option<usize>,
+    /// Maximum number of memory bytes captured per step.
+    pub max_memory_bytes: Option<usize>,
+    /// Maximum size in bytes of the trace log written by the tracer.
+    ///
+    /// Lines that would exceed the limit are not written.
+    pub max_log_bytes: Option<usize>,
+}
+
+impl TraceLimits {
+    /// Limits that don't bound the trace.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_frames: None,
+            max_memory_bytes: None,
+            max_log_bytes: None,
+        }
+    }
+
+    /// Sets the maximum number of traced frames.
+    pub const fn with_max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    /// Sets the maximum number of memory bytes captured per step.
+    pub const fn with_max_memory_bytes(mut self, max_memory_bytes: usize) -> Self {
+        self.max_memory_bytes = Some(max_memory_bytes);
+        self
+    }
+
+    /// Sets the maximum size of the trace log.
+    pub const fn with_max_log_bytes(mut self, max_log_bytes: usize) -> Self {
+        self.max_log_bytes = Some(max_log_bytes);
+        self
+    }
+
+    /// Returns `true` if the frame with the given index, counted from zero in the order frames
+    /// are entered, is over the frame limit.
+    pub fn exceeds_frames(&self, frame_index: usize) -> bool {
+        self.max_frames.is_some_and(|max| frame_index >= max)
+    }
+}
@@ -1,4 +1,4 @@
-use context::ContextTr;
+use context::{Cfg, ContextTr};
 use handler::{
     evm::{ContextDbError, FrameInitResult, FrameTr},
     instructions::InstructionProvider,
@@ -86,12 +86,10 @@ pub trait InspectorEvmTr:
     ) -> Result<FrameInitOrResult<Self::Frame>, ContextDbError<Self::Context>> {
         let (ctx, inspector, frame, instructions) = self.ctx_inspector_frame_instructions();
 
-        let next_action = inspect_instructions(
-            ctx,
-            frame.interpreter(),
-            inspector,
-            instructions.instruction_table(),
-        );
+        let disabled = ctx.cfg().disabled_opcodes();
+        let next_action = instructions.with_instruction_table(disabled, |table| {
+            inspect_instructions(ctx, frame.interpreter(), inspector, table)
+        });
         let mut result = frame.process_next_action(ctx, next_action);
 
         if let Ok(ItemOrResult::Result(frame_result)) = &mut result {
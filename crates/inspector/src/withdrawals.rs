@@ -0,0 +1,92 @@
+//! Consensus layer withdrawals reported to inspectors.
+use crate::{Inspector, InspectorEvmTr};
+use context::{ContextTr, Database};
+use handler::{apply_withdrawals, Withdrawal, WithdrawalsEvm};
+use interpreter::interpreter::EthInterpreter;
+
+/// Extension of [`WithdrawalsEvm`] that reports applied withdrawals to the inspector.
+pub trait InspectWithdrawalsEvm: WithdrawalsEvm {
+    /// Applies the withdrawals like [`WithdrawalsEvm::apply_withdrawals`] and calls
+    /// [`Inspector::withdrawal`] for each of them.
+    fn inspect_withdrawals(&mut self, withdrawals: &[Withdrawal]) -> Result<(), Self::Error>;
+
+    /// Inspects the withdrawals and finalizes the state.
+    fn inspect_withdrawals_finalize(
+        &mut self,
+        withdrawals: &[Withdrawal],
+    ) -> Result<Self::State, Self::Error> {
+        self.inspect_withdrawals(withdrawals)?;
+        Ok(self.finalize())
+    }
+}
+
+impl<EVM> InspectWithdrawalsEvm for EVM
+where
+    EVM: WithdrawalsEvm + InspectorEvmTr,
+    EVM::Error: From<<<EVM::Context as ContextTr>::Db as Database>::Error>,
+{
+    fn inspect_withdrawals(&mut self, withdrawals: &[Withdrawal]) -> Result<(), Self::Error> {
+        let (context, inspector) = self.ctx_inspector();
+        apply_withdrawals(context, withdrawals, |context, withdrawal| {
+            Inspector::<_, EthInterpreter>::withdrawal(inspector, context, withdrawal)
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context::{Context, JournalTr};
+    use database::InMemoryDB;
+    use handler::{MainBuilder, MainContext};
+    use interpreter::InterpreterTypes;
+    use primitives::{address, U256};
+    use std::vec::Vec;
+
+    #[derive(Default)]
+    struct WithdrawalInspector {
+        balances: Vec<(Withdrawal, U256)>,
+    }
+
+    impl<CTX: ContextTr, INTR: InterpreterTypes> Inspector<CTX, INTR> for WithdrawalInspector {
+        fn withdrawal(&mut self, context: &mut CTX, withdrawal: &Withdrawal) {
+            let balance = context
+                .journal_mut()
+                .load_account(withdrawal.address)
+                .unwrap()
+                .data
+                .info
+                .balance;
+            self.balances.push((*withdrawal, balance));
+        }
+    }
+
+    #[test]
+    fn test_inspect_withdrawals() {
+        let recipient = address!("0x0000000000000000000000000000000000001000");
+        let mut evm = Context::mainnet()
+            .with_db(InMemoryDB::default())
+            .build_mainnet_with_inspector(WithdrawalInspector::default());
+
+        let withdrawals = [
+            Withdrawal {
+                index: 0,
+                validator_index: 1,
+                address: recipient,
+                amount: 5,
+            },
+            Withdrawal {
+                index: 1,
+                validator_index: 1,
+                address: recipient,
+                amount: 0,
+            },
+        ];
+        let state = evm.inspect_withdrawals_finalize(&withdrawals).unwrap();
+
+        let expected = withdrawals[0].amount_wei();
+        assert_eq!(state[&recipient].info.balance, expected);
+        assert_eq!(evm.inspector.balances, [(withdrawals[0], expected)]);
+    }
+}
@@ -6,14 +6,22 @@ mod constants;
 pub use calc::*;
 pub use constants::*;
 
+/// Maximum gas limit of [`Gas`], larger limits are clamped.
+///
+/// Remaining gas is kept as a signed integer so recording a cost is one subtraction followed by
+/// a sign check, no execution can spend this much gas.
+pub const MAX_GAS_LIMIT: u64 = i64::MAX as u64;
+
 /// Represents the state of gas during execution.
+///
+/// Gas limit is clamped to [`MAX_GAS_LIMIT`].
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gas {
     /// The initial gas limit. This is constant throughout execution.
     limit: u64,
-    /// The remaining gas.
-    remaining: u64,
+    /// The remaining gas, never negative and at most the limit.
+    remaining: i64,
     /// Refunded gas. This is used only at the end of execution.
     refunded: i64,
     /// Memoisation of values for memory expansion cost.
@@ -24,9 +32,10 @@ impl Gas {
     /// Creates a new `Gas` struct with the given gas limit.
     #[inline]
     pub const fn new(limit: u64) -> Self {
+        let limit = clamp_limit(limit);
         Self {
             limit,
-            remaining: limit,
+            remaining: limit as i64,
             refunded: 0,
             memory: MemoryGas::new(),
         }
@@ -36,7 +45,7 @@ impl Gas {
     #[inline]
     pub const fn new_spent(limit: u64) -> Self {
         Self {
-            limit,
+            limit: clamp_limit(limit),
             remaining: 0,
             refunded: 0,
             memory: MemoryGas::new(),
@@ -70,7 +79,7 @@ impl Gas {
     /// Returns the total amount of gas spent.
     #[inline]
     pub const fn spent(&self) -> u64 {
-        self.limit - self.remaining
+        self.limit - self.remaining()
     }
 
     /// Returns the final amount of gas used by subtracting the refund from spent gas.
@@ -88,18 +97,20 @@ impl Gas {
     /// Returns the amount of gas remaining.
     #[inline]
     pub const fn remaining(&self) -> u64 {
-        self.remaining
+        self.remaining as u64
     }
 
     /// Return remaining gas after subtracting 63/64 parts.
     pub const fn remaining_63_of_64_parts(&self) -> u64 {
-        self.remaining - self.remaining / 64
+        self.remaining() - self.remaining() / 64
     }
 
     /// Erases a gas cost from the totals.
+    ///
+    /// Remaining gas is capped at the limit.
     #[inline]
     pub fn erase_cost(&mut self, returned: u64) {
-        self.remaining += returned;
+        self.remaining = self.remaining().saturating_add(returned).min(self.limit) as i64;
     }
 
     /// Spends all remaining gas.
@@ -125,7 +136,20 @@ impl Gas {
     #[inline]
     pub fn set_final_refund(&mut self, is_london: bool) {
         let max_refund_quotient = if is_london { 5 } else { 2 };
-        self.refunded = (self.refunded() as u64).min(self.spent() / max_refund_quotient) as i64;
+        self.set_final_refund_with_quotient(max_refund_quotient);
+    }
+
+    /// Set a refund value for final refund, limited to the gas spent divided by
+    /// `max_refund_quotient`.
+    ///
+    /// Quotient of zero clears the refund.
+    #[inline]
+    pub fn set_final_refund_with_quotient(&mut self, max_refund_quotient: u64) {
+        let max_refund = self
+            .spent()
+            .checked_div(max_refund_quotient)
+            .unwrap_or_default();
+        self.refunded = (self.refunded() as u64).min(max_refund) as i64;
     }
 
     /// Set a refund value. This overrides the current refund value.
@@ -137,20 +161,25 @@ impl Gas {
     /// Set a spent value. This overrides the current spent value.
     #[inline]
     pub fn set_spent(&mut self, spent: u64) {
-        self.remaining = self.limit.saturating_sub(spent);
+        self.remaining = self.limit.saturating_sub(spent) as i64;
     }
 
     /// Records an explicit cost.
     ///
-    /// Returns `false` if the gas limit is exceeded.
+    /// Returns `false` if the gas limit is exceeded, remaining gas is not changed then.
+    ///
+    /// Costs of `2^63` and above are clamped to `2^63`, which wraps to a negative remaining
+    /// gas like any other cost exceeding it, so there is a single underflow check. The clamp
+    /// is branchless and folds away for constant costs.
     #[inline]
     #[must_use = "prefer using `gas!` instead to return an out-of-gas error on failure"]
     pub fn record_cost(&mut self, cost: u64) -> bool {
-        if let Some(new_remaining) = self.remaining.checked_sub(cost) {
-            self.remaining = new_remaining;
-            return true;
+        let new_remaining = self.remaining.wrapping_sub(cost.min(1 << 63) as i64);
+        if new_remaining < 0 {
+            return false;
         }
-        false
+        self.remaining = new_remaining;
+        true
     }
 
     /// Record memory expansion
@@ -169,6 +198,16 @@ impl Gas {
     }
 }
 
+/// Clamps the gas limit to [`MAX_GAS_LIMIT`].
+#[inline]
+const fn clamp_limit(limit: u64) -> u64 {
+    if limit > MAX_GAS_LIMIT {
+        MAX_GAS_LIMIT
+    } else {
+        limit
+    }
+}
+
 /// Result of attempting to extend memory during execution.
 #[derive(Debug)]
 pub enum MemoryExtensionResult {
@@ -218,3 +257,103 @@ impl MemoryGas {
         Some(self.expansion_cost - cost)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gas accounting with checked unsigned arithmetic, the reference for [`Gas`].
+    #[derive(Debug)]
+    struct CheckedGas {
+        limit: u64,
+        remaining: u64,
+    }
+
+    impl CheckedGas {
+        fn record_cost(&mut self, cost: u64) -> bool {
+            match self.remaining.checked_sub(cost) {
+                Some(remaining) => {
+                    self.remaining = remaining;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    #[test]
+    fn record_cost_matches_checked_arithmetic() {
+        const EDGE_COSTS: [u64; 9] = [
+            0,
+            1,
+            2,
+            3,
+            MAX_GAS_LIMIT - 1,
+            MAX_GAS_LIMIT,
+            MAX_GAS_LIMIT + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        // xorshift, deterministic costs of every magnitude.
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for limit in [0, 1, 21_000, 30_000_000, MAX_GAS_LIMIT - 1, MAX_GAS_LIMIT] {
+            for round in 0..200 {
+                let mut gas = Gas::new(limit);
+                let mut reference = CheckedGas {
+                    limit,
+                    remaining: limit,
+                };
+                for _ in 0..64 {
+                    let random = next();
+                    let cost = match random % 4 {
+                        0 => EDGE_COSTS[(random >> 8) as usize % EDGE_COSTS.len()],
+                        1 => random >> (random % 64),
+                        2 => reference
+                            .remaining
+                            .saturating_add(random % 3)
+                            .saturating_sub(1),
+                        _ => random % 100,
+                    };
+                    assert_eq!(
+                        gas.record_cost(cost),
+                        reference.record_cost(cost),
+                        "limit {limit}, round {round}, cost {cost}"
+                    );
+                    assert_eq!(gas.remaining(), reference.remaining);
+                    assert_eq!(gas.spent(), reference.limit - reference.remaining);
+
+                    if random % 7 == 0 {
+                        let returned = next() % (reference.limit - reference.remaining + 1);
+                        gas.erase_cost(returned);
+                        reference.remaining += returned;
+                        assert_eq!(gas.remaining(), reference.remaining);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clamped_limit() {
+        let mut gas = Gas::new(u64::MAX);
+        assert_eq!(gas.limit(), MAX_GAS_LIMIT);
+        assert_eq!(gas.remaining(), MAX_GAS_LIMIT);
+        assert!(!gas.record_cost(MAX_GAS_LIMIT + 1));
+        assert!(!gas.record_cost(u64::MAX));
+        assert!(gas.record_cost(MAX_GAS_LIMIT));
+        assert_eq!(gas.spent(), MAX_GAS_LIMIT);
+        assert!(!gas.record_cost(1));
+        assert!(gas.record_cost(0));
+
+        let mut gas = Gas::new_spent(u64::MAX);
+        gas.erase_cost(u64::MAX);
+        assert_eq!(gas.remaining(), MAX_GAS_LIMIT);
+    }
+}
@@ -1,60 +1,15 @@
 use super::constants::*;
 use crate::{num_words, tri, SStoreResult, SelfDestructResult, StateLoad};
 use context_interface::{
-    journaled_state::AccountLoad, transaction::AccessListItemTr as _, Transaction, TransactionType,
+    cfg::GasSchedule, journaled_state::AccountLoad, transaction::AccessListItemTr as _,
+    Transaction, TransactionType,
 };
 use primitives::{eip7702, hardfork::SpecId, U256};
 
 /// `SSTORE` opcode refund calculation.
-#[allow(clippy::collapsible_else_if)]
 #[inline]
 pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
-    if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-3529: Reduction in refunds
-        let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
-        } else {
-            REFUND_SSTORE_CLEARS
-        };
-        if vals.is_new_eq_present() {
-            0
-        } else {
-            if vals.is_original_eq_present() && vals.is_new_zero() {
-                sstore_clears_schedule
-            } else {
-                let mut refund = 0;
-
-                if !vals.is_original_zero() {
-                    if vals.is_present_zero() {
-                        refund -= sstore_clears_schedule;
-                    } else if vals.is_new_zero() {
-                        refund += sstore_clears_schedule;
-                    }
-                }
-
-                if vals.is_original_eq_new() {
-                    let (gas_sstore_reset, gas_sload) = if spec_id.is_enabled_in(SpecId::BERLIN) {
-                        (SSTORE_RESET - COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
-                    } else {
-                        (SSTORE_RESET, sload_cost(spec_id, false))
-                    };
-                    if vals.is_original_zero() {
-                        refund += (SSTORE_SET - gas_sload) as i64;
-                    } else {
-                        refund += (gas_sstore_reset - gas_sload) as i64;
-                    }
-                }
-
-                refund
-            }
-        }
-    } else {
-        if !vals.is_present_zero() && vals.is_new_zero() {
-            REFUND_SSTORE_CLEARS
-        } else {
-            0
-        }
-    }
+    GasSchedule::for_spec(spec_id).sstore_refund(vals)
 }
 
 /// `CREATE2` opcode cost calculation.
@@ -86,25 +41,22 @@ const fn log2floor(value: U256) -> u64 {
     l
 }
 
-/// `EXP` opcode cost calculation.
+/// Returns the byte length of the `EXP` exponent.
 #[inline]
-pub fn exp_cost(spec_id: SpecId, power: U256) -> Option<u64> {
+pub fn exp_exponent_bytes(power: U256) -> u64 {
     if power.is_zero() {
-        Some(EXP)
+        0
     } else {
-        // EIP-160: EXP cost increase
-        let gas_byte = U256::from(if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
-            50
-        } else {
-            10
-        });
-        let gas = U256::from(EXP)
-            .checked_add(gas_byte.checked_mul(U256::from(log2floor(power) / 8 + 1))?)?;
-
-        u64::try_from(gas).ok()
+        log2floor(power) / 8 + 1
     }
 }
 
+/// `EXP` opcode cost calculation.
+#[inline]
+pub fn exp_cost(spec_id: SpecId, power: U256) -> Option<u64> {
+    GasSchedule::for_spec(spec_id).exp_cost(exp_exponent_bytes(power))
+}
+
 /// `*COPY` opcodes cost calculation.
 #[inline]
 pub const fn copy_cost_verylow(len: usize) -> Option<u64> {
@@ -114,14 +66,7 @@ pub const fn copy_cost_verylow(len: usize) -> Option<u64> {
 /// `EXTCODECOPY` opcode cost calculation.
 #[inline]
 pub const fn extcodecopy_cost(spec_id: SpecId, len: usize, is_cold: bool) -> Option<u64> {
-    let base_gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost(is_cold)
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        700
-    } else {
-        20
-    };
-    copy_cost(base_gas, len)
+    GasSchedule::for_spec(spec_id).extcodecopy_cost(len, is_cold)
 }
 
 #[inline]
@@ -164,99 +109,19 @@ pub const fn initcode_cost(len: usize) -> u64 {
 /// `SLOAD` opcode cost calculation.
 #[inline]
 pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        if is_cold {
-            COLD_SLOAD_COST
-        } else {
-            WARM_STORAGE_READ_COST
-        }
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-1884: Repricing for trie-size-dependent opcodes
-        ISTANBUL_SLOAD_GAS
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        200
-    } else {
-        50
-    }
+    GasSchedule::for_spec(spec_id).sload_cost(is_cold)
 }
 
 /// `SSTORE` opcode cost calculation.
 #[inline]
 pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        // Berlin specification logic
-        let mut gas_cost = istanbul_sstore_cost::<WARM_STORAGE_READ_COST, WARM_SSTORE_RESET>(vals);
-
-        if is_cold {
-            gas_cost += COLD_SLOAD_COST;
-        }
-        gas_cost
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // Istanbul logic
-        istanbul_sstore_cost::<ISTANBUL_SLOAD_GAS, SSTORE_RESET>(vals)
-    } else {
-        // Frontier logic
-        frontier_sstore_cost(vals)
-    }
-}
-
-/// EIP-2200: Structured Definitions for Net Gas Metering
-#[inline]
-fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
-    vals: &SStoreResult,
-) -> u64 {
-    if vals.is_new_eq_present() {
-        SLOAD_GAS
-    } else if vals.is_original_eq_present() && vals.is_original_zero() {
-        SSTORE_SET
-    } else if vals.is_original_eq_present() {
-        SSTORE_RESET_GAS
-    } else {
-        SLOAD_GAS
-    }
-}
-
-/// Frontier sstore cost just had two cases set and reset values.
-#[inline]
-fn frontier_sstore_cost(vals: &SStoreResult) -> u64 {
-    if vals.is_present_zero() && !vals.is_new_zero() {
-        SSTORE_SET
-    } else {
-        SSTORE_RESET
-    }
+    GasSchedule::for_spec(spec_id).sstore_cost(vals, is_cold)
 }
 
 /// `SELFDESTRUCT` opcode cost calculation.
 #[inline]
 pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResult>) -> u64 {
-    // EIP-161: State trie clearing (invariant-preserving alternative)
-    let should_charge_topup = if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
-        res.data.had_value && !res.data.target_exists
-    } else {
-        !res.data.target_exists
-    };
-
-    // EIP-150: Gas cost changes for IO-heavy operations
-    let selfdestruct_gas_topup = if spec_id.is_enabled_in(SpecId::TANGERINE) && should_charge_topup
-    {
-        25000
-    } else {
-        0
-    };
-
-    // EIP-150: Gas cost changes for IO-heavy operations
-    let selfdestruct_gas = if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        5000
-    } else {
-        0
-    };
-
-    let mut gas = selfdestruct_gas + selfdestruct_gas_topup;
-    if spec_id.is_enabled_in(SpecId::BERLIN) && res.is_cold {
-        gas += COLD_ACCOUNT_ACCESS_COST
-    }
-    gas
+    GasSchedule::for_spec(spec_id).selfdestruct_cost(&res)
 }
 
 /// Calculate call gas cost for the call instruction.
@@ -279,36 +144,7 @@ pub const fn call_cost(
     transfers_value: bool,
     account_load: StateLoad<AccountLoad>,
 ) -> u64 {
-    let is_empty = account_load.data.is_empty;
-    // Account access.
-    let mut gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost_with_delegation(account_load)
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        700
-    } else {
-        40
-    };
-
-    // Transfer value cost
-    if transfers_value {
-        gas += CALLVALUE;
-    }
-
-    // New account cost
-    if is_empty {
-        // EIP-161: State trie clearing (invariant-preserving alternative)
-        if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
-            // Account only if there is value transferred.
-            if transfers_value {
-                gas += NEWACCOUNT;
-            }
-        } else {
-            gas += NEWACCOUNT;
-        }
-    }
-
-    gas
+    GasSchedule::for_spec(spec_id).call_cost(transfers_value, &account_load)
 }
 
 /// Berlin warm and cold storage access cost for account access.
@@ -363,25 +199,76 @@ impl InitialAndFloorGas {
     }
 }
 
-/// Initial gas that is deducted for transaction to be included.
-/// Initial gas contains initial stipend gas, gas for access list and input data.
-///
-/// # Returns
+/// Parts of the intrinsic gas of a transaction, returned by [`intrinsic_gas`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntrinsicGas {
+    /// Base cost of the transaction, higher for creations since Homestead.
+    pub base: u64,
+    /// Number of tokens in calldata, see [`get_tokens_in_calldata`].
+    pub calldata_tokens: u64,
+    /// Gas of the calldata tokens.
+    pub calldata: u64,
+    /// Gas of the access list accounts and storage keys,
+    /// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930).
+    pub access_list: u64,
+    /// Gas of the authorizations, charged as if every authority is a new account,
+    /// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702).
+    pub authorization_list: u64,
+    /// Gas of the initcode words of a creation,
+    /// [EIP-3860](https://eips.ethereum.org/EIPS/eip-3860).
+    pub initcode: u64,
+    /// Minimum gas used by the transaction,
+    /// [EIP-7623](https://eips.ethereum.org/EIPS/eip-7623). Zero before Prague.
+    pub floor: u64,
+}
+
+impl IntrinsicGas {
+    /// Returns the initial gas charged before execution.
+    #[inline]
+    pub const fn initial_gas(&self) -> u64 {
+        self.base + self.calldata + self.access_list + self.authorization_list + self.initcode
+    }
+
+    /// Returns the minimum gas limit of the transaction, the larger of the initial gas and the
+    /// floor.
+    #[inline]
+    pub const fn min_gas_limit(&self) -> u64 {
+        let initial_gas = self.initial_gas();
+        if initial_gas > self.floor {
+            initial_gas
+        } else {
+            self.floor
+        }
+    }
+}
+
+impl From<IntrinsicGas> for InitialAndFloorGas {
+    #[inline]
+    fn from(gas: IntrinsicGas) -> Self {
+        Self::new(gas.initial_gas(), gas.floor)
+    }
+}
+
+/// Returns the parts of the intrinsic gas of a transaction with the given properties.
 ///
-/// - Intrinsic gas
-/// - Number of tokens in calldata
-pub fn calculate_initial_tx_gas(
+/// See [`intrinsic_gas`].
+pub fn intrinsic_gas_parts(
     spec_id: SpecId,
     input: &[u8],
     is_create: bool,
     access_list_accounts: u64,
     access_list_storages: u64,
     authorization_list_num: u64,
-) -> InitialAndFloorGas {
-    let mut gas = InitialAndFloorGas::default();
-
-    // Initdate stipend
-    let tokens_in_calldata = get_tokens_in_calldata(input, spec_id.is_enabled_in(SpecId::ISTANBUL));
+) -> IntrinsicGas {
+    let calldata_tokens = get_tokens_in_calldata(input, spec_id.is_enabled_in(SpecId::ISTANBUL));
+    let mut gas = IntrinsicGas {
+        calldata_tokens,
+        calldata: calldata_tokens * STANDARD_TOKEN_COST,
+        access_list: access_list_accounts * ACCESS_LIST_ADDRESS
+            + access_list_storages * ACCESS_LIST_STORAGE_KEY,
+        ..Default::default()
+    };
 
     // TODO(EOF) Tx type is removed
     // initcode stipend
@@ -389,20 +276,10 @@ pub fn calculate_initial_tx_gas(
     //     tokens_in_calldata += get_tokens_in_calldata(initcode.as_ref(), true);
     // }
 
-    gas.initial_gas += tokens_in_calldata * STANDARD_TOKEN_COST;
-
-    // Get number of access list account and storages.
-    gas.initial_gas += access_list_accounts * ACCESS_LIST_ADDRESS;
-    gas.initial_gas += access_list_storages * ACCESS_LIST_STORAGE_KEY;
-
     // Base stipend
-    gas.initial_gas += if is_create {
-        if spec_id.is_enabled_in(SpecId::HOMESTEAD) {
-            // EIP-2: Homestead Hard-fork Changes
-            53000
-        } else {
-            21000
-        }
+    gas.base = if is_create && spec_id.is_enabled_in(SpecId::HOMESTEAD) {
+        // EIP-2: Homestead Hard-fork Changes
+        53000
     } else {
         21000
     };
@@ -410,28 +287,26 @@ pub fn calculate_initial_tx_gas(
     // EIP-3860: Limit and meter initcode
     // Init code stipend for bytecode analysis
     if spec_id.is_enabled_in(SpecId::SHANGHAI) && is_create {
-        gas.initial_gas += initcode_cost(input.len())
+        gas.initcode = initcode_cost(input.len());
     }
 
     // EIP-7702
     if spec_id.is_enabled_in(SpecId::PRAGUE) {
-        gas.initial_gas += authorization_list_num * eip7702::PER_EMPTY_ACCOUNT_COST;
+        gas.authorization_list = authorization_list_num * eip7702::PER_EMPTY_ACCOUNT_COST;
 
         // Calculate gas floor for EIP-7623
-        gas.floor_gas = calc_tx_floor_cost(tokens_in_calldata);
+        gas.floor = calc_tx_floor_cost(calldata_tokens);
     }
 
     gas
 }
 
-/// Initial gas that is deducted for transaction to be included.
-/// Initial gas contains initial stipend gas, gas for access list and input data.
-///
-/// # Returns
+/// Returns the parts of the intrinsic gas of the transaction, with the arithmetic used to
+/// validate transactions.
 ///
-/// - Intrinsic gas
-/// - Number of tokens in calldata
-pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> InitialAndFloorGas {
+/// Mempools and fee estimators can use it to check the gas limit of a transaction before
+/// executing it, the gas limit has to cover [`IntrinsicGas::min_gas_limit`].
+pub fn intrinsic_gas(tx: impl Transaction, spec: SpecId) -> IntrinsicGas {
     let mut accounts = 0;
     let mut storages = 0;
     // legacy is only tx type that does not have access list.
@@ -457,7 +332,7 @@ pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> In
     //     &[]
     // };
 
-    calculate_initial_tx_gas(
+    intrinsic_gas_parts(
         spec,
         tx.input(),
         tx.kind().is_create(),
@@ -468,6 +343,43 @@ pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> In
     )
 }
 
+/// Initial gas that is deducted for transaction to be included.
+/// Initial gas contains initial stipend gas, gas for access list and input data.
+///
+/// # Returns
+///
+/// - Intrinsic gas
+/// - Number of tokens in calldata
+pub fn calculate_initial_tx_gas(
+    spec_id: SpecId,
+    input: &[u8],
+    is_create: bool,
+    access_list_accounts: u64,
+    access_list_storages: u64,
+    authorization_list_num: u64,
+) -> InitialAndFloorGas {
+    intrinsic_gas_parts(
+        spec_id,
+        input,
+        is_create,
+        access_list_accounts,
+        access_list_storages,
+        authorization_list_num,
+    )
+    .into()
+}
+
+/// Initial gas that is deducted for transaction to be included.
+/// Initial gas contains initial stipend gas, gas for access list and input data.
+///
+/// # Returns
+///
+/// - Intrinsic gas
+/// - Number of tokens in calldata
+pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> InitialAndFloorGas {
+    intrinsic_gas(tx, spec).into()
+}
+
 /// Retrieve the total number of tokens in calldata.
 #[inline]
 pub fn get_tokens_in_calldata(input: &[u8], is_istanbul: bool) -> u64 {
@@ -81,6 +81,8 @@ pub enum InstructionResult {
     CreateInitCodeSizeLimit,
     /// Fatal external error. Returned by database.
     FatalExternalError,
+    /// Opcode is disabled by the configuration.
+    OpcodeDisabled,
 }
 
 impl From<TransferError> for InstructionResult {
@@ -132,6 +134,7 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::CallNotAllowedInsideStatic => Self::CallNotAllowedInsideStatic,
             HaltReason::OutOfFunds => Self::OutOfFunds,
             HaltReason::CallTooDeep => Self::CallTooDeep,
+            HaltReason::OpcodeDisabled => Self::OpcodeDisabled,
         }
     }
 }
@@ -189,10 +192,54 @@ macro_rules! return_error {
             | $crate::InstructionResult::CreateContractStartingWithEF
             | $crate::InstructionResult::CreateInitCodeSizeLimit
             | $crate::InstructionResult::FatalExternalError
+            | $crate::InstructionResult::OpcodeDisabled
     };
 }
 
 impl InstructionResult {
+    /// All instruction results.
+    pub const ALL: [Self; 33] = [
+        Self::Stop,
+        Self::Return,
+        Self::SelfDestruct,
+        Self::Revert,
+        Self::CallTooDeep,
+        Self::OutOfFunds,
+        Self::CreateInitCodeStartingEF00,
+        Self::InvalidEOFInitCode,
+        Self::InvalidExtDelegateCallTarget,
+        Self::OutOfGas,
+        Self::MemoryOOG,
+        Self::MemoryLimitOOG,
+        Self::PrecompileOOG,
+        Self::InvalidOperandOOG,
+        Self::ReentrancySentryOOG,
+        Self::OpcodeNotFound,
+        Self::CallNotAllowedInsideStatic,
+        Self::StateChangeDuringStaticCall,
+        Self::InvalidFEOpcode,
+        Self::InvalidJump,
+        Self::NotActivated,
+        Self::StackUnderflow,
+        Self::StackOverflow,
+        Self::OutOfOffset,
+        Self::CreateCollision,
+        Self::OverflowPayment,
+        Self::PrecompileError,
+        Self::NonceOverflow,
+        Self::CreateContractSizeLimit,
+        Self::CreateContractStartingWithEF,
+        Self::CreateInitCodeSizeLimit,
+        Self::FatalExternalError,
+        Self::OpcodeDisabled,
+    ];
+
+    /// Returns the [`InstructionResult`] for the given [`u8`].
+    #[inline]
+    pub fn try_from_u8(value: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|result| *result as u8 == value)
+    }
+
     /// Returns whether the result is a success.
     #[inline]
     pub const fn is_ok(self) -> bool {
@@ -325,6 +372,7 @@ impl<HaltReasonTr: From<HaltReason>> From<InstructionResult> for SuccessOrHalt<H
             InstructionResult::InvalidFEOpcode => Self::Halt(HaltReason::InvalidFEOpcode.into()),
             InstructionResult::InvalidJump => Self::Halt(HaltReason::InvalidJump.into()),
             InstructionResult::NotActivated => Self::Halt(HaltReason::NotActivated.into()),
+            InstructionResult::OpcodeDisabled => Self::Halt(HaltReason::OpcodeDisabled.into()),
             InstructionResult::StackUnderflow => Self::Halt(HaltReason::StackUnderflow.into()),
             InstructionResult::StackOverflow => Self::Halt(HaltReason::StackOverflow.into()),
             InstructionResult::OutOfOffset => Self::Halt(HaltReason::OutOfOffset.into()),
@@ -409,6 +457,7 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::OpcodeDisabled,
         ];
         for result in error_results {
             assert!(!result.is_ok());
@@ -416,4 +465,13 @@ mod tests {
             assert!(result.is_error());
         }
     }
+
+    #[test]
+    fn test_try_from_u8() {
+        for result in InstructionResult::ALL {
+            assert_eq!(InstructionResult::try_from_u8(result as u8), Some(result));
+        }
+        assert_eq!(InstructionResult::try_from_u8(0), None);
+        assert_eq!(InstructionResult::try_from_u8(0xff), None);
+    }
 }
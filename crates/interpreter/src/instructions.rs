@@ -28,6 +28,7 @@ pub mod tx_info;
 pub mod utility;
 
 use crate::{interpreter_types::InterpreterTypes, Host, InstructionContext};
+use context_interface::cfg::DisabledOpcodes;
 
 /// EVM opcode function signature.
 pub type Instruction<W, H> = fn(InstructionContext<'_, H, W>);
@@ -42,6 +43,26 @@ pub const fn instruction_table<WIRE: InterpreterTypes, H: Host + ?Sized>(
     const { instruction_table_impl::<WIRE, H>() }
 }
 
+/// Calls `f` with the instruction table where the disabled opcodes halt with
+/// [`InstructionResult::OpcodeDisabled`](crate::InstructionResult::OpcodeDisabled).
+///
+/// The table is copied only if some opcodes are disabled.
+#[inline]
+pub fn with_disabled_opcodes<WIRE: InterpreterTypes, H: ?Sized, R>(
+    table: &InstructionTable<WIRE, H>,
+    disabled: DisabledOpcodes,
+    f: impl FnOnce(&InstructionTable<WIRE, H>) -> R,
+) -> R {
+    if disabled.is_empty() {
+        return f(table);
+    }
+    let mut table = *table;
+    for opcode in disabled.iter() {
+        table[opcode as usize] = control::disabled;
+    }
+    f(&table)
+}
+
 const fn instruction_table_impl<WIRE: InterpreterTypes, H: Host + ?Sized>(
 ) -> [Instruction<WIRE, H>; 256] {
     use bytecode::opcode::*;
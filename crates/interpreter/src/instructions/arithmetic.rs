@@ -1,8 +1,8 @@
 use super::i256::{i256_div, i256_mod};
 use crate::{
     gas,
-    interpreter_types::{InterpreterTypes, RuntimeFlag, StackTr},
-    InstructionContext,
+    interpreter_types::{InterpreterTypes, StackTr},
+    Host, InstructionContext,
 };
 use primitives::U256;
 
@@ -84,10 +84,15 @@ pub fn mulmod<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_,
 }
 
 /// Implements the EXP instruction - exponentiates two values from stack.
-pub fn exp<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
-    let spec_id = context.interpreter.runtime_flag.spec_id();
+pub fn exp<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     popn_top!([op1], op2, context.interpreter);
-    gas_or_fail!(context.interpreter, gas::exp_cost(spec_id, *op2));
+    gas_or_fail!(
+        context.interpreter,
+        context
+            .host
+            .gas_schedule()
+            .exp_cost(gas::exp_exponent_bytes(*op2))
+    );
     *op2 = op1.pow(*op2);
 }
 
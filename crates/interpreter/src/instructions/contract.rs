@@ -3,7 +3,6 @@ mod call_helpers;
 pub use call_helpers::{calc_call_gas, get_memory_input_and_out_ranges, resize_memory};
 
 use crate::{
-    gas,
     instructions::utility::IntoAddress,
     interpreter_action::FrameInput,
     interpreter_types::{InputsTr, InterpreterTypes, LoopControl, MemoryTr, RuntimeFlag, StackTr},
@@ -12,7 +11,6 @@ use crate::{
 };
 use context_interface::CreateScheme;
 use primitives::{hardfork::SpecId, Address, Bytes, B256, U256};
-use std::boxed::Box;
 
 use crate::InstructionContext;
 
@@ -48,7 +46,10 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
                     .halt(InstructionResult::CreateInitCodeSizeLimit);
                 return;
             }
-            gas!(context.interpreter, gas::initcode_cost(len));
+            gas_or_fail!(
+                context.interpreter,
+                context.host.gas_schedule().initcode_cost(len)
+            );
         }
 
         let code_offset = as_usize_or_fail!(context.interpreter, code_offset);
@@ -66,40 +67,37 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
     let scheme = if IS_CREATE2 {
         popn!([salt], context.interpreter);
         // SAFETY: `len` is reasonable in size as gas for it is already deducted.
-        gas_or_fail!(context.interpreter, gas::create2_cost(len));
+        gas_or_fail!(
+            context.interpreter,
+            context.host.gas_schedule().create2_cost(len)
+        );
         CreateScheme::Create2 { salt }
     } else {
-        gas!(context.interpreter, gas::CREATE);
+        gas!(context.interpreter, context.host.gas_schedule().create);
         CreateScheme::Create
     };
 
-    let mut gas_limit = context.interpreter.gas.remaining();
-
     // EIP-150: Gas cost changes for IO-heavy operations
-    if context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(SpecId::TANGERINE)
-    {
-        // Take remaining gas and deduce l64 part of it.
-        gas_limit -= gas_limit / 64
-    }
+    let remaining = context.interpreter.gas.remaining();
+    let gas_limit = context
+        .host
+        .gas_schedule()
+        .call_gas_limit(remaining, remaining);
     gas!(context.interpreter, gas_limit);
 
     // Call host to interact with target contract
     context
         .interpreter
         .bytecode
-        .set_action(InterpreterAction::NewFrame(FrameInput::Create(Box::new(
-            CreateInputs {
+        .set_action(InterpreterAction::NewFrame(FrameInput::Create(
+            context.interpreter.frame_input_pool.create(CreateInputs {
                 caller: context.interpreter.input.target_address(),
                 scheme,
                 value,
                 init_code: code,
                 gas_limit,
-            },
-        ))));
+            }),
+        )));
 }
 
 /// Implements the CALL instruction.
@@ -131,8 +129,10 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContex
         return;
     };
 
+    let schedule = context.host.gas_schedule();
     let Some(mut gas_limit) = calc_call_gas(
         context.interpreter,
+        schedule,
         account_load,
         has_transfer,
         local_gas_limit,
@@ -144,15 +144,15 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContex
 
     // Add call stipend if there is value to be transferred.
     if has_transfer {
-        gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        gas_limit = gas_limit.saturating_add(schedule.call_stipend);
     }
 
     // Call host to interact with target contract
     context
         .interpreter
         .bytecode
-        .set_action(InterpreterAction::NewFrame(FrameInput::Call(Box::new(
-            CallInputs {
+        .set_action(InterpreterAction::NewFrame(FrameInput::Call(
+            context.interpreter.frame_input_pool.call(CallInputs {
                 input: CallInput::SharedBuffer(input),
                 gas_limit,
                 target_address: to,
@@ -162,8 +162,8 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContex
                 scheme: CallScheme::Call,
                 is_static: context.interpreter.runtime_flag.is_static(),
                 return_memory_offset,
-            },
-        ))));
+            }),
+        )));
 }
 
 /// Implements the CALLCODE instruction.
@@ -192,9 +192,14 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // Set `is_empty` to false as we are not creating this account.
     load.is_empty = false;
-    let Some(mut gas_limit) =
-        calc_call_gas(context.interpreter, load, !value.is_zero(), local_gas_limit)
-    else {
+    let schedule = context.host.gas_schedule();
+    let Some(mut gas_limit) = calc_call_gas(
+        context.interpreter,
+        schedule,
+        load,
+        !value.is_zero(),
+        local_gas_limit,
+    ) else {
         return;
     };
 
@@ -202,15 +207,15 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // Add call stipend if there is value to be transferred.
     if !value.is_zero() {
-        gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        gas_limit = gas_limit.saturating_add(schedule.call_stipend);
     }
 
     // Call host to interact with target contract
     context
         .interpreter
         .bytecode
-        .set_action(InterpreterAction::NewFrame(FrameInput::Call(Box::new(
-            CallInputs {
+        .set_action(InterpreterAction::NewFrame(FrameInput::Call(
+            context.interpreter.frame_input_pool.call(CallInputs {
                 input: CallInput::SharedBuffer(input),
                 gas_limit,
                 target_address: context.interpreter.input.target_address(),
@@ -220,8 +225,8 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
                 scheme: CallScheme::CallCode,
                 is_static: context.interpreter.runtime_flag.is_static(),
                 return_memory_offset,
-            },
-        ))));
+            }),
+        )));
 }
 
 /// Implements the DELEGATECALL instruction.
@@ -250,7 +255,13 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // Set is_empty to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas(context.interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas(
+        context.interpreter,
+        context.host.gas_schedule(),
+        load,
+        false,
+        local_gas_limit,
+    ) else {
         return;
     };
 
@@ -260,8 +271,8 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     context
         .interpreter
         .bytecode
-        .set_action(InterpreterAction::NewFrame(FrameInput::Call(Box::new(
-            CallInputs {
+        .set_action(InterpreterAction::NewFrame(FrameInput::Call(
+            context.interpreter.frame_input_pool.call(CallInputs {
                 input: CallInput::SharedBuffer(input),
                 gas_limit,
                 target_address: context.interpreter.input.target_address(),
@@ -271,8 +282,8 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
                 scheme: CallScheme::DelegateCall,
                 is_static: context.interpreter.runtime_flag.is_static(),
                 return_memory_offset,
-            },
-        ))));
+            }),
+        )));
 }
 
 /// Implements the STATICCALL instruction.
@@ -300,7 +311,13 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     };
     // Set `is_empty` to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas(context.interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas(
+        context.interpreter,
+        context.host.gas_schedule(),
+        load,
+        false,
+        local_gas_limit,
+    ) else {
         return;
     };
     gas!(context.interpreter, gas_limit);
@@ -309,8 +326,8 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     context
         .interpreter
         .bytecode
-        .set_action(InterpreterAction::NewFrame(FrameInput::Call(Box::new(
-            CallInputs {
+        .set_action(InterpreterAction::NewFrame(FrameInput::Call(
+            context.interpreter.frame_input_pool.call(CallInputs {
                 input: CallInput::SharedBuffer(input),
                 gas_limit,
                 target_address: to,
@@ -320,6 +337,6 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
                 scheme: CallScheme::StaticCall,
                 is_static: true,
                 return_memory_offset,
-            },
-        ))));
+            }),
+        )));
 }
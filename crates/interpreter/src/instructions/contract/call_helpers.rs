@@ -1,11 +1,10 @@
 use crate::{
-    gas,
     interpreter::Interpreter,
-    interpreter_types::{InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
+    interpreter_types::{InterpreterTypes, MemoryTr, StackTr},
 };
-use context_interface::{context::StateLoad, journaled_state::AccountLoad};
-use core::{cmp::min, ops::Range};
-use primitives::{hardfork::SpecId::*, U256};
+use context_interface::{cfg::GasSchedule, context::StateLoad, journaled_state::AccountLoad};
+use core::ops::Range;
+use primitives::U256;
 
 /// Gets memory input and output ranges for call instructions.
 #[inline]
@@ -48,24 +47,14 @@ pub fn resize_memory(
 #[inline]
 pub fn calc_call_gas(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    gas_schedule: &GasSchedule,
     account_load: StateLoad<AccountLoad>,
     has_transfer: bool,
     local_gas_limit: u64,
 ) -> Option<u64> {
-    let call_cost = gas::call_cost(
-        interpreter.runtime_flag.spec_id(),
-        has_transfer,
-        account_load,
-    );
+    let call_cost = gas_schedule.call_cost(has_transfer, &account_load);
     gas!(interpreter, call_cost, None);
 
     // EIP-150: Gas cost changes for IO-heavy operations
-    let gas_limit = if interpreter.runtime_flag.spec_id().is_enabled_in(TANGERINE) {
-        // Take l64 part of gas_limit
-        min(interpreter.gas.remaining_63_of_64_parts(), local_gas_limit)
-    } else {
-        local_gas_limit
-    };
-
-    Some(gas_limit)
+    Some(gas_schedule.call_gas_limit(interpreter.gas.remaining(), local_gas_limit))
 }
@@ -114,6 +114,11 @@ pub fn invalid<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_
     context.interpreter.halt(InstructionResult::InvalidFEOpcode);
 }
 
+/// Opcode disabled by the configuration. This opcode halts the execution.
+pub fn disabled<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+    context.interpreter.halt(InstructionResult::OpcodeDisabled);
+}
+
 /// Unknown opcode. This opcode halts the execution.
 pub fn unknown<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     context.interpreter.halt(InstructionResult::OpcodeNotFound);
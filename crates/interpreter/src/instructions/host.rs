@@ -1,5 +1,5 @@
 use crate::{
-    gas::{self, warm_cold_cost, CALL_STIPEND},
+    gas,
     instructions::utility::{IntoAddress, IntoU256},
     interpreter_types::{InputsTr, InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
     Host, InstructionResult,
@@ -21,19 +21,9 @@ pub fn balance<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCon
             .halt(InstructionResult::FatalExternalError);
         return;
     };
-    let spec_id = context.interpreter.runtime_flag.spec_id();
     gas!(
         context.interpreter,
-        if spec_id.is_enabled_in(BERLIN) {
-            warm_cold_cost(balance.is_cold)
-        } else if spec_id.is_enabled_in(ISTANBUL) {
-            // EIP-1884: Repricing for trie-size-dependent opcodes
-            700
-        } else if spec_id.is_enabled_in(TANGERINE) {
-            400
-        } else {
-            20
-        }
+        context.host.gas_schedule().balance_cost(balance.is_cold)
     );
     *top = balance.data;
 }
@@ -71,14 +61,10 @@ pub fn extcodesize<WIRE: InterpreterTypes, H: Host + ?Sized>(
             .halt(InstructionResult::FatalExternalError);
         return;
     };
-    let spec_id = context.interpreter.runtime_flag.spec_id();
-    if spec_id.is_enabled_in(BERLIN) {
-        gas!(context.interpreter, warm_cold_cost(code.is_cold));
-    } else if spec_id.is_enabled_in(TANGERINE) {
-        gas!(context.interpreter, 700);
-    } else {
-        gas!(context.interpreter, 20);
-    }
+    gas!(
+        context.interpreter,
+        context.host.gas_schedule().extcodesize_cost(code.is_cold)
+    );
 
     *top = U256::from(code.len());
 }
@@ -96,14 +82,13 @@ pub fn extcodehash<WIRE: InterpreterTypes, H: Host + ?Sized>(
             .halt(InstructionResult::FatalExternalError);
         return;
     };
-    let spec_id = context.interpreter.runtime_flag.spec_id();
-    if spec_id.is_enabled_in(BERLIN) {
-        gas!(context.interpreter, warm_cold_cost(code_hash.is_cold));
-    } else if spec_id.is_enabled_in(ISTANBUL) {
-        gas!(context.interpreter, 700);
-    } else {
-        gas!(context.interpreter, 400);
-    }
+    gas!(
+        context.interpreter,
+        context
+            .host
+            .gas_schedule()
+            .extcodehash_cost(code_hash.is_cold)
+    );
     *top = code_hash.into_u256();
 }
 
@@ -128,11 +113,10 @@ pub fn extcodecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let len = as_usize_or_fail!(context.interpreter, len_u256);
     gas_or_fail!(
         context.interpreter,
-        gas::extcodecopy_cost(
-            context.interpreter.runtime_flag.spec_id(),
-            len,
-            code.is_cold
-        )
+        context
+            .host
+            .gas_schedule()
+            .extcodecopy_cost(len, code.is_cold)
     );
     if len == 0 {
         return;
@@ -154,7 +138,7 @@ pub fn extcodecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
 pub fn blockhash<WIRE: InterpreterTypes, H: Host + ?Sized>(
     context: InstructionContext<'_, H, WIRE>,
 ) {
-    gas!(context.interpreter, gas::BLOCKHASH);
+    gas!(context.interpreter, context.host.gas_schedule().blockhash);
     popn_top!([], number, context.interpreter);
 
     let requested_number = *number;
@@ -204,7 +188,7 @@ pub fn sload<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionConte
 
     gas!(
         context.interpreter,
-        gas::sload_cost(context.interpreter.runtime_flag.spec_id(), value.is_cold)
+        context.host.gas_schedule().sload_cost(value.is_cold)
     );
     *index = value.data;
 }
@@ -228,13 +212,14 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
         return;
     };
 
+    let schedule = context.host.gas_schedule();
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
     if context
         .interpreter
         .runtime_flag
         .spec_id()
         .is_enabled_in(ISTANBUL)
-        && context.interpreter.gas.remaining() <= CALL_STIPEND
+        && context.interpreter.gas.remaining() <= schedule.call_stipend
     {
         context
             .interpreter
@@ -243,17 +228,13 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
     }
     gas!(
         context.interpreter,
-        gas::sstore_cost(
-            context.interpreter.runtime_flag.spec_id(),
-            &state_load.data,
-            state_load.is_cold
-        )
+        schedule.sstore_cost(&state_load.data, state_load.is_cold)
     );
 
-    context.interpreter.gas.record_refund(gas::sstore_refund(
-        context.interpreter.runtime_flag.spec_id(),
-        &state_load.data,
-    ));
+    context
+        .interpreter
+        .gas
+        .record_refund(schedule.sstore_refund(&state_load.data));
 }
 
 /// EIP-1153: Transient storage opcodes
@@ -293,7 +274,10 @@ pub fn log<const N: usize, H: Host + ?Sized>(
 
     popn!([offset, len], context.interpreter);
     let len = as_usize_or_fail!(context.interpreter, len);
-    gas_or_fail!(context.interpreter, gas::log_cost(N as u8, len as u64));
+    gas_or_fail!(
+        context.interpreter,
+        context.host.gas_schedule().log_cost(N as u8, len as u64)
+    );
     let data = if len == 0 {
         Bytes::new()
     } else {
@@ -326,6 +310,10 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
     context: InstructionContext<'_, H, WIRE>,
 ) {
     require_non_staticcall!(context.interpreter);
+    if context.host.selfdestruct_mode().is_disabled() {
+        context.interpreter.halt(InstructionResult::NotActivated);
+        return;
+    }
     popn!([target], context.interpreter);
     let target = target.into_address();
 
@@ -339,21 +327,16 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
         return;
     };
 
+    let schedule = context.host.gas_schedule();
     // EIP-3529: Reduction in refunds
-    if !context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(LONDON)
-        && !res.previously_destroyed
-    {
-        context.interpreter.gas.record_refund(gas::SELFDESTRUCT)
+    if context.host.refund_policy().selfdestruct_refund && !res.previously_destroyed {
+        context
+            .interpreter
+            .gas
+            .record_refund(schedule.selfdestruct_refund)
     }
 
-    gas!(
-        context.interpreter,
-        gas::selfdestruct_cost(context.interpreter.runtime_flag.spec_id(), res)
-    );
+    gas!(context.interpreter, schedule.selfdestruct_cost(&res));
 
     context.interpreter.halt(InstructionResult::SelfDestruct);
 }
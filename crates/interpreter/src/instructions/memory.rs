@@ -1,6 +1,7 @@
 use crate::{
     gas,
     interpreter_types::{InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
+    Host,
 };
 use core::cmp::max;
 use primitives::U256;
@@ -58,14 +59,17 @@ pub fn msize<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_,
 /// Implements the MCOPY instruction.
 ///
 /// EIP-5656: Memory copying instruction that copies memory from one location to another.
-pub fn mcopy<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+pub fn mcopy<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     check!(context.interpreter, CANCUN);
     popn!([dst, src, len], context.interpreter);
 
     // Into usize or fail
     let len = as_usize_or_fail!(context.interpreter, len);
     // Deduce gas
-    gas_or_fail!(context.interpreter, gas::copy_cost_verylow(len));
+    gas_or_fail!(
+        context.interpreter,
+        context.host.gas_schedule().copy_cost(gas::VERYLOW, len)
+    );
     if len == 0 {
         return;
     }
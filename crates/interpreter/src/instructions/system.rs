@@ -4,8 +4,9 @@ use crate::{
     interpreter_types::{
         InputsTr, InterpreterTypes, LegacyBytecode, MemoryTr, ReturnData, RuntimeFlag, StackTr,
     },
-    CallInput, InstructionResult,
+    CallInput, Host, InstructionResult,
 };
+use context_interface::cfg::GasSchedule;
 use core::ptr;
 use primitives::{B256, KECCAK_EMPTY, U256};
 
@@ -14,16 +15,23 @@ use crate::InstructionContext;
 /// Implements the KECCAK256 instruction.
 ///
 /// Computes Keccak-256 hash of memory data.
-pub fn keccak256<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+pub fn keccak256<WIRE: InterpreterTypes, H: Host + ?Sized>(
+    context: InstructionContext<'_, H, WIRE>,
+) {
     popn_top!([offset], top, context.interpreter);
     let len = as_usize_or_fail!(context.interpreter, top);
-    gas_or_fail!(context.interpreter, gas::keccak256_cost(len));
+    gas_or_fail!(
+        context.interpreter,
+        context.host.gas_schedule().keccak256_cost(len)
+    );
     let hash = if len == 0 {
         KECCAK_EMPTY
     } else {
         let from = as_usize_or_fail!(context.interpreter, offset);
         resize_memory!(context.interpreter, from, len);
-        primitives::keccak256(context.interpreter.memory.slice_len(from, len).as_ref())
+        context
+            .host
+            .keccak256(context.interpreter.memory.slice_len(from, len).as_ref())
     };
     *top = hash.into();
 }
@@ -74,10 +82,17 @@ pub fn codesize<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'
 /// Implements the CODECOPY instruction.
 ///
 /// Copies running contract's bytecode to memory.
-pub fn codecopy<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+pub fn codecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
+    context: InstructionContext<'_, H, WIRE>,
+) {
     popn!([memory_offset, code_offset, len], context.interpreter);
     let len = as_usize_or_fail!(context.interpreter, len);
-    let Some(memory_offset) = memory_resize(context.interpreter, memory_offset, len) else {
+    let Some(memory_offset) = memory_resize(
+        context.interpreter,
+        context.host.gas_schedule(),
+        memory_offset,
+        len,
+    ) else {
         return;
     };
     let code_offset = as_usize_saturated!(code_offset);
@@ -151,10 +166,17 @@ pub fn callvalue<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<
 /// Implements the CALLDATACOPY instruction.
 ///
 /// Copies input data to memory.
-pub fn calldatacopy<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+pub fn calldatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
+    context: InstructionContext<'_, H, WIRE>,
+) {
     popn!([memory_offset, data_offset, len], context.interpreter);
     let len = as_usize_or_fail!(context.interpreter, len);
-    let Some(memory_offset) = memory_resize(context.interpreter, memory_offset, len) else {
+    let Some(memory_offset) = memory_resize(
+        context.interpreter,
+        context.host.gas_schedule(),
+        memory_offset,
+        len,
+    ) else {
         return;
     };
 
@@ -188,7 +210,9 @@ pub fn returndatasize<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionCon
 }
 
 /// EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
-pub fn returndatacopy<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+pub fn returndatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
+    context: InstructionContext<'_, H, WIRE>,
+) {
     check!(context.interpreter, BYZANTIUM);
     popn!([memory_offset, offset, len], context.interpreter);
 
@@ -202,7 +226,12 @@ pub fn returndatacopy<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionCon
         return;
     }
 
-    let Some(memory_offset) = memory_resize(context.interpreter, memory_offset, len) else {
+    let Some(memory_offset) = memory_resize(
+        context.interpreter,
+        context.host.gas_schedule(),
+        memory_offset,
+        len,
+    ) else {
         return;
     };
 
@@ -231,11 +260,12 @@ pub fn gas<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H,
 /// Handles memory expansion and gas calculation for data copy operations.
 pub fn memory_resize(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    gas_schedule: &GasSchedule,
     memory_offset: U256,
     len: usize,
 ) -> Option<usize> {
     // Safe to cast usize to u64
-    gas_or_fail!(interpreter, gas::copy_cost_verylow(len), None);
+    gas_or_fail!(interpreter, gas_schedule.copy_cost(gas::VERYLOW, len), None);
     if len == 0 {
         return None;
     }
@@ -19,8 +19,8 @@ pub use stack::{Stack, STACK_LIMIT};
 
 // imports
 use crate::{
-    host::DummyHost, instruction_context::InstructionContext, interpreter_types::*, Gas, Host,
-    InstructionResult, InstructionTable, InterpreterAction,
+    host::DummyHost, instruction_context::InstructionContext, interpreter_types::*, FrameInputPool,
+    Gas, Host, InstructionResult, InstructionTable, InterpreterAction,
 };
 use bytecode::Bytecode;
 use primitives::{hardfork::SpecId, Bytes};
@@ -45,6 +45,9 @@ pub struct Interpreter<WIRE: InterpreterTypes = EthInterpreter> {
     pub runtime_flag: WIRE::RuntimeFlag,
     /// Extended functionality and customizations.
     pub extend: WIRE::Extend,
+    /// Boxes reused for the inputs of frames created by this interpreter.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub frame_input_pool: FrameInputPool,
 }
 
 impl<EXT: Default> Interpreter<EthInterpreter<EXT>> {
@@ -109,6 +112,7 @@ impl<EXT: Default> Interpreter<EthInterpreter<EXT>> {
             input,
             runtime_flag: RuntimeFlags { is_static, spec_id },
             extend: Default::default(),
+            frame_input_pool: FrameInputPool::new(),
         }
     }
 
@@ -132,6 +136,8 @@ impl<EXT: Default> Interpreter<EthInterpreter<EXT>> {
             input: input_ref,
             runtime_flag,
             extend,
+            // Pooled boxes are kept for the next frame.
+            frame_input_pool: _,
         } = self;
         *bytecode_ref = bytecode;
         *gas = Gas::new(gas_limit);
@@ -253,6 +259,27 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
         }
         self.take_next_action()
     }
+
+    /// Executes the interpreter until it returns or stops, executing at most `fuel` instructions.
+    ///
+    /// Fuel is decreased by the number of executed instructions. Returns `None` if fuel ran out
+    /// before the interpreter finished, calling it again continues the execution.
+    #[inline]
+    pub fn run_with_fuel<H: ?Sized>(
+        &mut self,
+        instruction_table: &InstructionTable<IW, H>,
+        host: &mut H,
+        fuel: &mut u64,
+    ) -> Option<InterpreterAction> {
+        while self.bytecode.is_not_end() {
+            if *fuel == 0 {
+                return None;
+            }
+            *fuel -= 1;
+            self.step(instruction_table, host);
+        }
+        Some(self.take_next_action())
+    }
 }
 
 /// The result of an interpreter operation.
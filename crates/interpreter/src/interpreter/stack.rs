@@ -53,6 +53,11 @@ impl StackTr for Stack {
         self.len()
     }
 
+    #[inline]
+    fn as_slice(&self) -> &[U256] {
+        &self.data
+    }
+
     #[inline]
     fn clear(&mut self) {
         self.data.clear();
@@ -405,6 +410,8 @@ impl<'de> serde::Deserialize<'de> for Stack {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interpreter::SharedMemory;
+    use crate::interpreter_types::MemoryTr;
 
     fn run(f: impl FnOnce(&mut Stack)) {
         let mut stack = Stack::new();
@@ -502,4 +509,36 @@ mod tests {
         assert!(!full_stack.push(U256::from(100)));
         assert!(!cloned_full.push(U256::from(100)));
     }
+
+    #[test]
+    fn borrow_values() {
+        let mut stack = Stack::new();
+        for value in 1..=4u64 {
+            assert!(stack.push(U256::from(value)));
+        }
+        assert_eq!(stack.as_slice(), stack.data().as_slice());
+        assert_eq!(
+            stack.iter_top().copied().collect::<Vec<_>>(),
+            [4, 3, 2, 1].map(U256::from)
+        );
+        assert_eq!(stack.top_n(2), Some(&[U256::from(3), U256::from(4)][..]));
+        assert_eq!(stack.top_n(4), Some(stack.as_slice()));
+        assert_eq!(stack.top_n(5), None);
+
+        let mut out = vec![U256::ZERO];
+        stack.copy_top_into(1, &mut out);
+        stack.copy_top_into(10, &mut out);
+        assert_eq!(out, [0, 4, 1, 2, 3, 4].map(U256::from));
+
+        let mut memory = SharedMemory::new();
+        memory.resize(32);
+        memory.set(0, &[1, 2, 3]);
+        assert_eq!(&*memory.slice_clamped(1..3), &[2, 3]);
+        assert_eq!(memory.slice_clamped(30..40).len(), 2);
+        assert!(memory.slice_clamped(40..50).is_empty());
+        let mut out = Vec::new();
+        memory.copy_range_into(0..2, &mut out);
+        memory.copy_range_into(31..64, &mut out);
+        assert_eq!(out, [1, 2, 0]);
+    }
 }
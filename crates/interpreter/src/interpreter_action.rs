@@ -2,11 +2,13 @@ mod call_inputs;
 mod call_outcome;
 mod create_inputs;
 mod create_outcome;
+mod frame_input_pool;
 
 pub use call_inputs::{CallInput, CallInputs, CallScheme, CallValue};
 pub use call_outcome::CallOutcome;
 pub use create_inputs::CreateInputs;
 pub use create_outcome::CreateOutcome;
+pub use frame_input_pool::FrameInputPool;
 use primitives::Bytes;
 
 use crate::{Gas, InstructionResult, InterpreterResult, SharedMemory};
@@ -1,5 +1,5 @@
-use context_interface::CreateScheme;
-use primitives::{Address, Bytes, U256};
+use context_interface::{cfg::CreateAddressPolicy, CreateScheme};
+use primitives::{keccak256, Address, Bytes, U256};
 
 /// Inputs for a create call
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -28,4 +28,21 @@ impl CreateInputs {
             CreateScheme::Custom { address } => address,
         }
     }
+
+    /// Returns the address that this create call will create with the address policy of the
+    /// configuration.
+    pub fn created_address_with_policy(
+        &self,
+        nonce: u64,
+        policy: &dyn CreateAddressPolicy,
+    ) -> Address {
+        match self.scheme {
+            CreateScheme::Create => policy.create_address(self.caller, nonce),
+            CreateScheme::Create2 { salt } => {
+                let init_code_hash = keccak256(&self.init_code);
+                policy.create2_address(self.caller, salt, init_code_hash)
+            }
+            CreateScheme::Custom { address } => address,
+        }
+    }
 }
@@ -0,0 +1,104 @@
+use super::{CallInputs, CreateInputs, FrameInput};
+use std::boxed::Box;
+
+/// Pool of boxed inputs of new frames.
+///
+/// Every call and create instruction boxes the inputs of the new frame. The box lives until the
+/// frame slot is reused by another frame, so deep and wide call trees allocate and free a box
+/// for every frame. Inputs of finished child frames are given back to the pool of the parent
+/// interpreter and reused by its next call or create.
+///
+/// A frame has at most one running child frame, so the pool keeps one box of each kind.
+#[derive(Clone, Debug, Default)]
+pub struct FrameInputPool {
+    call: Option<Box<CallInputs>>,
+    create: Option<Box<CreateInputs>>,
+}
+
+impl FrameInputPool {
+    /// Creates an empty pool.
+    pub const fn new() -> Self {
+        Self {
+            call: None,
+            create: None,
+        }
+    }
+
+    /// Boxes the call inputs, reusing a pooled box if there is one.
+    #[inline]
+    pub fn call(&mut self, inputs: CallInputs) -> Box<CallInputs> {
+        match self.call.take() {
+            Some(mut boxed) => {
+                *boxed = inputs;
+                boxed
+            }
+            None => Box::new(inputs),
+        }
+    }
+
+    /// Boxes the create inputs, reusing a pooled box if there is one.
+    #[inline]
+    pub fn create(&mut self, inputs: CreateInputs) -> Box<CreateInputs> {
+        match self.create.take() {
+            Some(mut boxed) => {
+                *boxed = inputs;
+                boxed
+            }
+            None => Box::new(inputs),
+        }
+    }
+
+    /// Gives the box of the frame input back to the pool.
+    ///
+    /// Box is dropped if the pool already has a box of its kind.
+    #[inline]
+    pub fn recycle(&mut self, input: FrameInput) {
+        match input {
+            FrameInput::Call(boxed) => {
+                self.call.get_or_insert(boxed);
+            }
+            FrameInput::Create(boxed) => {
+                self.create.get_or_insert(boxed);
+            }
+            FrameInput::Empty => {}
+        }
+    }
+
+    /// Returns the number of pooled boxes.
+    pub fn len(&self) -> usize {
+        usize::from(self.call.is_some()) + usize::from(self.create.is_some())
+    }
+
+    /// Returns `true` if there are no pooled boxes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_boxes() {
+        let mut pool = FrameInputPool::new();
+        let boxed = pool.create(CreateInputs::default());
+        let ptr = &*boxed as *const CreateInputs;
+        pool.recycle(FrameInput::Create(boxed));
+        assert_eq!(pool.len(), 1);
+
+        let inputs = CreateInputs {
+            gas_limit: 100,
+            ..Default::default()
+        };
+        let boxed = pool.create(inputs.clone());
+        assert!(pool.is_empty());
+        assert_eq!(&*boxed as *const CreateInputs, ptr);
+        assert_eq!(*boxed, inputs);
+
+        pool.recycle(FrameInput::Create(Box::default()));
+        pool.recycle(FrameInput::Create(Box::default()));
+        pool.recycle(FrameInput::Empty);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{CallInput, InstructionResult, InterpreterAction};
 use core::cell::Ref;
 use core::ops::{Deref, Range};
+use core::{iter::Rev, slice};
 use primitives::{hardfork::SpecId, Address, Bytes, B256, U256};
+use std::vec::Vec;
 
 /// Helper function to read immediates data from the bytecode
 pub trait Immediates {
@@ -140,6 +142,20 @@ pub trait MemoryTr {
         self.slice(offset..offset + len)
     }
 
+    /// Memory slice with range cut at the memory size.
+    ///
+    /// Unlike [`slice`][MemoryTr::slice] it doesn't panic, parts of the range past the memory
+    /// size are omitted.
+    fn slice_clamped(&self, range: Range<usize>) -> Ref<'_, [u8]> {
+        let end = range.end.min(self.size());
+        self.slice(range.start.min(end)..end)
+    }
+
+    /// Appends the memory in the range, cut at the memory size, to the `out`.
+    fn copy_range_into(&self, range: Range<usize>, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.slice_clamped(range));
+    }
+
     /// Resizes memory to new size
     ///
     /// # Note
@@ -153,6 +169,29 @@ pub trait StackTr {
     /// Returns stack length.
     fn len(&self) -> usize;
 
+    /// Returns the stack values, the top of the stack is the last value.
+    fn as_slice(&self) -> &[U256];
+
+    /// Returns an iterator over the stack values, starting from the top of the stack.
+    fn iter_top(&self) -> Rev<slice::Iter<'_, U256>> {
+        self.as_slice().iter().rev()
+    }
+
+    /// Returns the top `n` values of the stack, the top of the stack is the last value.
+    ///
+    /// Returns `None` if the stack has less than `n` values.
+    fn top_n(&self, n: usize) -> Option<&[U256]> {
+        let data = self.as_slice();
+        data.get(data.len().checked_sub(n)?..)
+    }
+
+    /// Appends up to `n` top values of the stack to the `out`, the top of the stack is
+    /// appended last.
+    fn copy_top_into(&self, n: usize, out: &mut Vec<U256>) {
+        let data = self.as_slice();
+        out.extend_from_slice(&data[data.len().saturating_sub(n)..]);
+    }
+
     /// Returns `true` if stack is empty.
     fn is_empty(&self) -> bool {
         self.len() == 0
@@ -40,7 +40,7 @@ pub use interpreter::{
 };
 pub use interpreter_action::{
     CallInput, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
-    FrameInput, InterpreterAction,
+    FrameInput, FrameInputPool, InterpreterAction,
 };
 pub use interpreter_types::InterpreterTypes;
 pub use primitives::{eip7907::MAX_CODE_SIZE, eip7907::MAX_INITCODE_SIZE};
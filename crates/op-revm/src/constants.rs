@@ -4,6 +4,10 @@ use revm::primitives::{address, Address, U256};
 /// The cost of a non-zero byte in the EVM.
 pub const NON_ZERO_BYTE_COST: u64 = 16;
 
+/// Number of bytes added to the size of an unsigned transaction to account for its signature,
+/// same as in the `GasPriceOracle` predeploy.
+pub const UNSIGNED_TX_SIGNATURE_PADDING: u64 = 68;
+
 /// The two 4-byte Ecotone fee scalar values are packed into the same storage slot as the 8-byte sequence number.
 /// Byte offset within the storage slot of the 4-byte baseFeeScalar attribute.
 pub const BASE_FEE_SCALAR_OFFSET: usize = 16;
@@ -2,7 +2,10 @@
 use crate::{
     api::exec::OpContextTr,
     constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT},
-    transaction::{deposit::DEPOSIT_TRANSACTION_TYPE, OpTransactionError, OpTxTr},
+    transaction::{
+        deposit::{validate_deposit_tx, DEPOSIT_TRANSACTION_TYPE},
+        OpTransactionError, OpTxTr,
+    },
     L1BlockInfo, OpHaltReason, OpSpecId,
 };
 use revm::{
@@ -16,12 +19,12 @@ use revm::{
         evm::FrameTr,
         handler::EvmTrError,
         post_execution::{self, reimburse_caller},
-        pre_execution::validate_account_nonce_and_code,
+        pre_execution::validate_account_nonce_and_code_with_policy,
         EthFrame, EvmTr, FrameResult, Handler, MainnetHandler,
     },
-    inspector::{Inspector, InspectorEvmTr, InspectorHandler},
+    inspector::{BalanceCredits, GasSettlement, Inspector, InspectorEvmTr, InspectorHandler},
     interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, Gas},
-    primitives::{hardfork::SpecId, U256},
+    primitives::U256,
 };
 use std::boxed::Box;
 
@@ -84,12 +87,7 @@ where
         let tx_type = tx.tx_type();
         if tx_type == DEPOSIT_TRANSACTION_TYPE {
             // Do not allow for a system transaction to be processed if Regolith is enabled.
-            if tx.is_system_transaction()
-                && evm.ctx().cfg().spec().is_enabled_in(OpSpecId::REGOLITH)
-            {
-                return Err(OpTransactionError::DepositSystemTxPostRegolith.into());
-            }
-            return Ok(());
+            return validate_deposit_tx(tx, ctx.cfg().spec()).map_err(Into::into);
         }
         self.mainnet.validate_env(evm)
     }
@@ -106,8 +104,8 @@ where
         let spec = ctx.cfg().spec();
         let block_number = ctx.block().number();
         let is_balance_check_disabled = ctx.cfg().is_balance_check_disabled();
-        let is_eip3607_disabled = ctx.cfg().is_eip3607_disabled();
-        let is_nonce_check_disabled = ctx.cfg().is_nonce_check_disabled();
+        let caller_code_policy = ctx.cfg().caller_code_policy();
+        let nonce_check_mode = ctx.cfg().nonce_check_mode();
 
         let mint = if is_deposit {
             ctx.tx().mint().unwrap_or_default()
@@ -149,11 +147,11 @@ where
 
         if !is_deposit {
             // validates account nonce and code
-            validate_account_nonce_and_code(
+            validate_account_nonce_and_code_with_policy(
                 &mut caller_account.info,
                 tx.nonce(),
-                is_eip3607_disabled,
-                is_nonce_check_disabled,
+                caller_code_policy,
+                !nonce_check_mode.is_strict(),
             )?;
         }
 
@@ -206,13 +204,15 @@ where
         caller_account.info.balance = new_balance;
 
         // Bump the nonce for calls. Nonce for CREATE will be bumped in `handle_create`.
-        if tx.kind().is_call() {
+        // Deposits always bump the nonce.
+        let bump_nonce = tx.kind().is_call() && (is_deposit || nonce_check_mode.bumps_nonce());
+        if bump_nonce {
             caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
         }
 
         // NOTE: all changes to the caller account should journaled so in case of error
         // we can revert the changes.
-        journal.caller_accounting_journal_entry(tx.caller(), old_balance, tx.kind().is_call());
+        journal.caller_accounting_journal_entry(tx.caller(), old_balance, bump_nonce);
 
         Ok(())
     }
@@ -315,13 +315,10 @@ where
         // Prior to Regolith, deposit transactions did not receive gas refunds.
         let is_gas_refund_disabled = is_deposit && !is_regolith;
         if !is_gas_refund_disabled {
-            frame_result.gas_mut().set_final_refund(
-                evm.ctx()
-                    .cfg()
-                    .spec()
-                    .into_eth_spec()
-                    .is_enabled_in(SpecId::LONDON),
-            );
+            let policy = evm.ctx().cfg().refund_policy();
+            frame_result
+                .gas_mut()
+                .set_final_refund_with_quotient(policy.cap.quotient());
         }
     }
 
@@ -485,6 +482,21 @@ where
     ERROR: EvmTrError<EVM> + From<OpTransactionError> + FromStringError + IsTxError,
 {
     type IT = EthInterpreter;
+
+    fn gas_settlement(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &FrameResult,
+        caller_credits: &BalanceCredits,
+        fee_credits: &BalanceCredits,
+    ) -> Result<GasSettlement, Self::Error> {
+        let mut settlement =
+            self.mainnet
+                .gas_settlement(evm, exec_result, caller_credits, fee_credits)?;
+        // Base fee is paid to the Base Fee Vault instead of being burned.
+        settlement.burned = U256::ZERO;
+        Ok(settlement)
+    }
 }
 
 #[cfg(test)]
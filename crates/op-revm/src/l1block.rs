@@ -5,11 +5,14 @@ use crate::{
         ECOTONE_L1_FEE_SCALARS_SLOT, EMPTY_SCALARS, L1_BASE_FEE_SLOT, L1_BLOCK_CONTRACT,
         L1_OVERHEAD_SLOT, L1_SCALAR_SLOT, NON_ZERO_BYTE_COST, OPERATOR_FEE_CONSTANT_OFFSET,
         OPERATOR_FEE_SCALARS_SLOT, OPERATOR_FEE_SCALAR_DECIMAL, OPERATOR_FEE_SCALAR_OFFSET,
+        UNSIGNED_TX_SIGNATURE_PADDING,
     },
-    transaction::estimate_tx_compressed_size,
+    fast_lz::flz_compress_len,
+    transaction::{estimate_compressed_size, estimate_tx_compressed_size},
     OpSpecId,
 };
 use revm::{
+    context_interface::ChainData,
     database_interface::Database,
     interpreter::{
         gas::{get_tokens_in_calldata, NON_ZERO_BYTE_MULTIPLIER_ISTANBUL, STANDARD_TOKEN_COST},
@@ -251,9 +254,37 @@ impl L1BlockInfo {
         tx_l1_cost
     }
 
+    /// Previews the L1 cost of an unsigned transaction with the Ecotone and Fjord cost functions.
+    ///
+    /// `unsigned_tx` is the RLP encoded transaction envelope without the signature. Like in the
+    /// `GasPriceOracle` predeploy, [`UNSIGNED_TX_SIGNATURE_PADDING`] bytes are added to its size to
+    /// account for the signature, so the preview is an estimate of the cost of the signed
+    /// transaction. Cached L1 cost of the current transaction is not used or changed.
+    pub fn preview_unsigned_tx_l1_cost(&self, unsigned_tx: &[u8]) -> L1CostPreview {
+        let fastlz_size = flz_compress_len(unsigned_tx) as u64 + UNSIGNED_TX_SIGNATURE_PADDING;
+        let estimated_size = U256::from(estimate_compressed_size(fastlz_size));
+        let ecotone_data_gas = self
+            .data_gas(unsigned_tx, OpSpecId::ECOTONE)
+            .saturating_add(U256::from(
+                UNSIGNED_TX_SIGNATURE_PADDING * NON_ZERO_BYTE_COST,
+            ));
+
+        L1CostPreview {
+            fastlz_size,
+            estimated_size,
+            ecotone_data_gas,
+            ecotone_fee: self.l1_cost_ecotone(ecotone_data_gas),
+            fjord_fee: self.l1_cost_fjord(estimated_size),
+        }
+    }
+
     /// Calculate the gas cost of a transaction based on L1 block data posted on L2, pre-Ecotone.
     fn calculate_tx_l1_cost_bedrock(&self, input: &[u8], spec_id: OpSpecId) -> U256 {
-        let rollup_data_gas_cost = self.data_gas(input, spec_id);
+        self.l1_cost_bedrock(self.data_gas(input, spec_id))
+    }
+
+    /// Bedrock L1 cost function for the given data gas.
+    fn l1_cost_bedrock(&self, rollup_data_gas_cost: U256) -> U256 {
         rollup_data_gas_cost
             .saturating_add(self.l1_fee_overhead.unwrap_or_default())
             .saturating_mul(self.l1_base_fee)
@@ -275,11 +306,15 @@ impl L1BlockInfo {
         // There is an edgecase where, for the very first Ecotone block (unless it is activated at Genesis), we must
         // use the Bedrock cost function. To determine if this is the case, we can check if the Ecotone parameters are
         // unset.
+        self.l1_cost_ecotone(self.data_gas(input, spec_id))
+    }
+
+    /// Ecotone L1 cost function for the given data gas.
+    fn l1_cost_ecotone(&self, rollup_data_gas_cost: U256) -> U256 {
         if self.empty_ecotone_scalars {
-            return self.calculate_tx_l1_cost_bedrock(input, spec_id);
+            return self.l1_cost_bedrock(rollup_data_gas_cost);
         }
 
-        let rollup_data_gas_cost = self.data_gas(input, spec_id);
         let l1_fee_scaled = self.calculate_l1_fee_scaled_ecotone();
 
         l1_fee_scaled
@@ -292,8 +327,12 @@ impl L1BlockInfo {
     /// [OpSpecId::FJORD] L1 cost function:
     /// `estimatedSize*(baseFeeScalar*l1BaseFee*16 + blobFeeScalar*l1BlobBaseFee)/1e12`
     fn calculate_tx_l1_cost_fjord(&self, input: &[u8]) -> U256 {
+        self.l1_cost_fjord(self.tx_estimated_size_fjord(input))
+    }
+
+    /// Fjord L1 cost function for the given estimated size.
+    fn l1_cost_fjord(&self, estimated_size: U256) -> U256 {
         let l1_fee_scaled = self.calculate_l1_fee_scaled_ecotone();
-        let estimated_size = self.tx_estimated_size_fjord(input);
 
         estimated_size
             .saturating_mul(l1_fee_scaled)
@@ -315,6 +354,32 @@ impl L1BlockInfo {
     }
 }
 
+/// L1 cost preview of an unsigned transaction, see [`L1BlockInfo::preview_unsigned_tx_l1_cost`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct L1CostPreview {
+    /// FastLZ compressed size of the transaction in bytes, including the signature padding.
+    pub fastlz_size: u64,
+    /// Estimated compressed size of the transaction in bytes scaled by 1e6, used by Fjord.
+    pub estimated_size: U256,
+    /// L1 data gas of the transaction, used by Ecotone.
+    pub ecotone_data_gas: U256,
+    /// L1 fee with the Ecotone cost function.
+    pub ecotone_fee: U256,
+    /// L1 fee with the Fjord cost function.
+    pub fjord_fee: U256,
+}
+
+/// Chain field of the Optimism context is the L1 block info itself.
+impl ChainData<L1BlockInfo> for L1BlockInfo {
+    fn chain_data(&self) -> Option<&L1BlockInfo> {
+        Some(self)
+    }
+
+    fn chain_data_mut(&mut self) -> Option<&mut L1BlockInfo> {
+        Some(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,6 +553,50 @@ mod tests {
         assert_eq!(l1_fee, expected_l1_fee)
     }
 
+    #[test]
+    fn test_preview_unsigned_tx_l1_cost() {
+        // l1FeeScaled = 17e6
+        let mut l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_base_fee_scalar: U256::from(1_000),
+            l1_blob_base_fee: Some(U256::from(1_000)),
+            l1_blob_base_fee_scalar: Some(U256::from(1_000)),
+            tx_l1_cost: Some(U256::from(1)),
+            ..Default::default()
+        };
+
+        // fastLzSize = 202 + 68
+        let input = bytes!("02f901550a758302df1483be21b88304743f94f80e51afb613d764fa61751affd3313c190a86bb870151bd62fd12adb8e41ef24f3f000000000000000000000000000000000000000000000000000000000000006e000000000000000000000000af88d065e77c8cc2239327c5edb3a432268e5831000000000000000000000000000000000000000000000000000000000003c1e5000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000148c89ed219d02f1a5be012c689b4f5b731827bebe000000000000000000000000c001a033fd89cb37c31b2cba46b6466e040c61fc9b2a3675a7f5f493ebd5ad77c497f8a07cdf65680e238392693019b4092f610222e71b7cec06449cb922b93b6a12744e");
+        let preview = l1_block_info.preview_unsigned_tx_l1_cost(&input);
+        assert_eq!(
+            preview,
+            L1CostPreview {
+                fastlz_size: 270,
+                // 836500 * 270 - 42585600
+                estimated_size: U256::from(183_269_400),
+                // 178 zero bytes * 4 + (167 + 68) non-zero bytes * 16
+                ecotone_data_gas: U256::from(4472),
+                // 4472 * 17e6 / 16e6
+                ecotone_fee: U256::from(4751),
+                // 183269400 * 17e6 / 1e12
+                fjord_fee: U256::from(3115),
+            }
+        );
+        // Cached cost is not used.
+        assert_eq!(l1_block_info.tx_l1_cost, Some(U256::from(1)));
+
+        // Bedrock cost function is used before the Ecotone scalars are set.
+        l1_block_info.empty_ecotone_scalars = true;
+        l1_block_info.l1_fee_overhead = Some(U256::from(1_000));
+        // (4472 + 1000) * 1000 * 1000 / 1e6
+        assert_eq!(
+            l1_block_info
+                .preview_unsigned_tx_l1_cost(&input)
+                .ecotone_fee,
+            U256::from(5472)
+        );
+    }
+
     #[test]
     fn test_calculate_tx_l1_cost_fjord() {
         // l1FeeScaled = baseFeeScalar*l1BaseFee*16 + blobFeeScalar*l1BlobBaseFee
@@ -21,7 +21,7 @@ pub use api::{
     default_ctx::{DefaultOp, OpContext},
 };
 pub use evm::OpEvm;
-pub use l1block::L1BlockInfo;
+pub use l1block::{L1BlockInfo, L1CostPreview};
 pub use result::OpHaltReason;
 pub use spec::*;
 pub use transaction::{error::OpTransactionError, estimate_tx_compressed_size, OpTransaction};
@@ -1,15 +1,16 @@
 //! Contains Optimism specific precompiles.
-use crate::OpSpecId;
+use crate::{L1BlockInfo, OpSpecId};
+use l1_block::L1BlockPrecompile;
 use revm::{
     context::Cfg,
-    context_interface::ContextTr,
+    context_interface::{ChainData, ContextTr},
     handler::{EthPrecompiles, PrecompileProvider},
-    interpreter::{InputsImpl, InterpreterResult},
+    interpreter::{Gas, InputsImpl, InstructionResult, InterpreterResult},
     precompile::{
         self, bn254, secp256r1, PrecompileError, PrecompileResult, PrecompileWithAddress,
         Precompiles,
     },
-    primitives::{hardfork::SpecId, Address, OnceLock},
+    primitives::{hardfork::SpecId, Address, Bytes, OnceLock},
 };
 use std::boxed::Box;
 use std::string::String;
@@ -21,6 +22,8 @@ pub struct OpPrecompiles {
     inner: EthPrecompiles,
     /// Spec id of the precompile provider.
     spec: OpSpecId,
+    /// Precompile that exposes the L1 block attributes, not enabled by default.
+    l1_block: Option<L1BlockPrecompile>,
 }
 
 impl OpPrecompiles {
@@ -43,9 +46,24 @@ impl OpPrecompiles {
                 spec: SpecId::default(),
             },
             spec,
+            l1_block: None,
         }
     }
 
+    /// Enables the precompile that exposes the L1 block attributes to contracts.
+    ///
+    /// Precompile is kept when the spec changes.
+    pub fn with_l1_block_precompile(mut self, precompile: L1BlockPrecompile) -> Self {
+        self.l1_block = Some(precompile);
+        self
+    }
+
+    /// Returns the precompile that exposes the L1 block attributes, if enabled.
+    #[inline]
+    pub fn l1_block_precompile(&self) -> Option<&L1BlockPrecompile> {
+        self.l1_block.as_ref()
+    }
+
     /// Precompiles getter.
     #[inline]
     pub fn precompiles(&self) -> &'static Precompiles {
@@ -94,7 +112,7 @@ pub fn isthmus() -> &'static Precompiles {
 
 impl<CTX> PrecompileProvider<CTX> for OpPrecompiles
 where
-    CTX: ContextTr<Cfg: Cfg<Spec = OpSpecId>>,
+    CTX: ContextTr<Cfg: Cfg<Spec = OpSpecId>, Chain: ChainData<L1BlockInfo>>,
 {
     type Output = InterpreterResult;
 
@@ -103,7 +121,9 @@ where
         if spec == self.spec {
             return false;
         }
+        let l1_block = self.l1_block.take();
         *self = Self::new_with_spec(spec);
+        self.l1_block = l1_block;
         true
     }
 
@@ -116,18 +136,51 @@ where
         is_static: bool,
         gas_limit: u64,
     ) -> Result<Option<Self::Output>, String> {
+        if let Some(l1_block) = self
+            .l1_block
+            .as_ref()
+            .filter(|l1_block| l1_block.address == *address)
+        {
+            let mut result = InterpreterResult {
+                result: InstructionResult::Return,
+                gas: Gas::new(gas_limit),
+                output: Bytes::new(),
+            };
+            match l1_block.run(context.chain().chain_data(), gas_limit) {
+                Ok(output) => {
+                    let underflow = result.gas.record_cost(output.gas_used);
+                    assert!(underflow, "Gas underflow is not possible");
+                    result.output = output.bytes;
+                }
+                Err(e) => {
+                    result.result = if e.is_oog() {
+                        InstructionResult::PrecompileOOG
+                    } else {
+                        InstructionResult::PrecompileError
+                    };
+                }
+            }
+            return Ok(Some(result));
+        }
         self.inner
             .run(context, address, inputs, is_static, gas_limit)
     }
 
     #[inline]
     fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
-        self.inner.warm_addresses()
+        Box::new(
+            self.inner
+                .warm_addresses()
+                .chain(self.l1_block.as_ref().map(|l1_block| l1_block.address)),
+        )
     }
 
     #[inline]
     fn contains(&self, address: &Address) -> bool {
-        self.inner.contains(address)
+        self.l1_block
+            .as_ref()
+            .is_some_and(|l1_block| l1_block.address == *address)
+            || self.inner.contains(address)
     }
 }
 
@@ -137,6 +190,126 @@ impl Default for OpPrecompiles {
     }
 }
 
+/// Precompile that exposes the L1 block attributes.
+///
+/// Custom rollups can enable it with [`OpPrecompiles::with_l1_block_precompile`] to give
+/// contracts access to the L1 data without deploying a predeploy contract. Precompile reads the
+/// [`L1BlockInfo`] from the chain field of the context and returns the configured fields as
+/// 32 byte big endian words, in the configured order. Input is ignored.
+pub mod l1_block {
+    use super::*;
+    use revm::{precompile::PrecompileOutput, primitives::U256};
+    use std::vec::Vec;
+
+    #[cfg(not(feature = "std"))]
+    use crate::std::string::ToString;
+
+    /// Base gas cost of the precompile.
+    pub const BASE_GAS: u64 = 100;
+    /// Gas cost of every returned field.
+    pub const PER_FIELD_GAS: u64 = 3;
+
+    /// Field of the [`L1BlockInfo`] returned by the precompile.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum L1BlockField {
+        /// L2 block number the info was loaded for.
+        L2Block,
+        /// Base fee of the L1 origin block.
+        L1BaseFee,
+        /// L1 fee overhead, zero after Ecotone.
+        L1FeeOverhead,
+        /// L1 base fee scalar.
+        L1BaseFeeScalar,
+        /// L1 blob base fee, zero before Ecotone.
+        L1BlobBaseFee,
+        /// L1 blob base fee scalar, zero before Ecotone.
+        L1BlobBaseFeeScalar,
+        /// Operator fee scalar, zero before Isthmus.
+        OperatorFeeScalar,
+        /// Operator fee constant, zero before Isthmus.
+        OperatorFeeConstant,
+    }
+
+    impl L1BlockField {
+        /// All fields, in the order of [`L1BlockInfo`].
+        pub const ALL: [Self; 8] = [
+            Self::L2Block,
+            Self::L1BaseFee,
+            Self::L1FeeOverhead,
+            Self::L1BaseFeeScalar,
+            Self::L1BlobBaseFee,
+            Self::L1BlobBaseFeeScalar,
+            Self::OperatorFeeScalar,
+            Self::OperatorFeeConstant,
+        ];
+
+        /// Returns the value of the field, fields that are not set are zero.
+        pub fn value(self, info: &L1BlockInfo) -> U256 {
+            let value = match self {
+                Self::L2Block => Some(info.l2_block),
+                Self::L1BaseFee => Some(info.l1_base_fee),
+                Self::L1FeeOverhead => info.l1_fee_overhead,
+                Self::L1BaseFeeScalar => Some(info.l1_base_fee_scalar),
+                Self::L1BlobBaseFee => info.l1_blob_base_fee,
+                Self::L1BlobBaseFeeScalar => info.l1_blob_base_fee_scalar,
+                Self::OperatorFeeScalar => info.operator_fee_scalar,
+                Self::OperatorFeeConstant => info.operator_fee_constant,
+            };
+            value.unwrap_or_default()
+        }
+    }
+
+    /// Configuration of the L1 block attributes precompile.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct L1BlockPrecompile {
+        /// Address of the precompile.
+        pub address: Address,
+        /// Returned fields, in order.
+        pub fields: Vec<L1BlockField>,
+    }
+
+    impl L1BlockPrecompile {
+        /// Creates the precompile at `address` that returns the `fields`.
+        pub fn new(address: Address, fields: impl IntoIterator<Item = L1BlockField>) -> Self {
+            Self {
+                address,
+                fields: fields.into_iter().collect(),
+            }
+        }
+
+        /// Creates the precompile at `address` that returns all fields.
+        pub fn all_fields(address: Address) -> Self {
+            Self::new(address, L1BlockField::ALL)
+        }
+
+        /// Returns the gas cost of the call.
+        pub fn gas_cost(&self) -> u64 {
+            BASE_GAS + PER_FIELD_GAS * self.fields.len() as u64
+        }
+
+        /// Runs the precompile with the L1 block info of the chain.
+        ///
+        /// Fails if the chain has no L1 block info.
+        pub fn run(&self, info: Option<&L1BlockInfo>, gas_limit: u64) -> PrecompileResult {
+            let gas_used = self.gas_cost();
+            if gas_used > gas_limit {
+                return Err(PrecompileError::OutOfGas);
+            }
+            let Some(info) = info else {
+                return Err(PrecompileError::Other(
+                    "L1 block info is not available".to_string(),
+                ));
+            };
+            let mut output = Vec::with_capacity(self.fields.len() * 32);
+            for field in &self.fields {
+                output.extend_from_slice(&field.value(info).to_be_bytes::<32>());
+            }
+            Ok(PrecompileOutput::new(gas_used, output.into()))
+        }
+    }
+}
+
 /// Bn254 pair precompile.
 pub mod bn254_pair {
     use super::*;
@@ -314,6 +487,56 @@ mod tests {
         assert_eq!(intersection.len(), latest.len())
     }
 
+    #[test]
+    fn test_l1_block_precompile() {
+        use crate::DefaultOp;
+        use l1_block::{L1BlockField, BASE_GAS, PER_FIELD_GAS};
+        use revm::{database::EmptyDB, primitives::U256, Context};
+        type Ctx = crate::OpContext<EmptyDB>;
+
+        let address = Address::with_last_byte(0xff);
+        let mut precompiles =
+            OpPrecompiles::default().with_l1_block_precompile(L1BlockPrecompile::new(
+                address,
+                [
+                    L1BlockField::L1BaseFee,
+                    L1BlockField::OperatorFeeScalar,
+                    L1BlockField::L1FeeOverhead,
+                ],
+            ));
+        let mut ctx = Context::op().with_chain(L1BlockInfo {
+            l1_base_fee: U256::from(7),
+            operator_fee_scalar: Some(U256::from(3)),
+            ..Default::default()
+        });
+
+        // Precompile is kept on spec change.
+        assert!(PrecompileProvider::<Ctx>::set_spec(
+            &mut precompiles,
+            OpSpecId::GRANITE
+        ));
+        assert!(PrecompileProvider::<Ctx>::contains(&precompiles, &address));
+        assert!(PrecompileProvider::<Ctx>::warm_addresses(&precompiles).any(|a| a == address));
+
+        let gas = BASE_GAS + 3 * PER_FIELD_GAS;
+        let result = precompiles
+            .run(&mut ctx, &address, &InputsImpl::default(), false, gas)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(result.gas.spent(), gas);
+        let mut expected = vec![0u8; 96];
+        expected[31] = 7;
+        expected[63] = 3;
+        assert_eq!(result.output, expected);
+
+        let result = precompiles
+            .run(&mut ctx, &address, &InputsImpl::default(), false, gas - 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileOOG);
+    }
+
     #[test]
     fn test_g1_isthmus_max_size() {
         let oversized_input = vec![0u8; ISTHMUS_G1_MSM_MAX_INPUT_SIZE + 1];
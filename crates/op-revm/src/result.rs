@@ -11,6 +11,18 @@ pub enum OpHaltReason {
     FailedDeposit,
 }
 
+impl OpHaltReason {
+    /// Returns stable numeric code of the halt reason.
+    ///
+    /// Base halt reasons keep their [`HaltReason::code`].
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Base(reason) => reason.code(),
+            Self::FailedDeposit => 3501,
+        }
+    }
+}
+
 impl From<HaltReason> for OpHaltReason {
     fn from(value: HaltReason) -> Self {
         Self::Base(value)
@@ -20,8 +20,11 @@ const MIN_TX_SIZE_SCALED: u64 = 100 * 1_000_000;
 
 /// Estimates the compressed size of a transaction.
 pub fn estimate_tx_compressed_size(input: &[u8]) -> u64 {
-    let fastlz_size = flz_compress_len(input) as u64;
+    estimate_compressed_size(flz_compress_len(input) as u64)
+}
 
+/// Estimates the compressed size of a transaction from its FastLZ compressed size.
+pub(crate) fn estimate_compressed_size(fastlz_size: u64) -> u64 {
     fastlz_size
         .saturating_mul(L1_COST_FASTLZ_COEF)
         .saturating_sub(L1_COST_INTERCEPT)
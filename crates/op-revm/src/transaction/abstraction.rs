@@ -1,5 +1,5 @@
 //! Optimism transaction abstraction containing the `[OpTxTr]` trait and corresponding `[OpTransaction]` type.
-use super::deposit::{DepositTransactionParts, DEPOSIT_TRANSACTION_TYPE};
+use super::deposit::{DepositSource, DepositTransactionParts, DEPOSIT_TRANSACTION_TYPE};
 use auto_impl::auto_impl;
 use revm::{
     context::{
@@ -7,7 +7,7 @@ use revm::{
         TxEnv,
     },
     context_interface::transaction::Transaction,
-    handler::SystemCallTx,
+    handler::{GasCapTx, SystemCallTx},
     primitives::{Address, Bytes, TxKind, B256, U256},
 };
 use std::vec;
@@ -101,6 +101,12 @@ impl<TX: Transaction + SystemCallTx> SystemCallTx for OpTransaction<TX> {
     }
 }
 
+impl<TX: GasCapTx> GasCapTx for OpTransaction<TX> {
+    fn set_gas_limit(&mut self, gas_limit: u64) {
+        self.base.set_gas_limit(gas_limit);
+    }
+}
+
 impl<T: Transaction> Transaction for OpTransaction<T> {
     type AccessListItem<'a>
         = T::AccessListItem<'a>
@@ -246,6 +252,13 @@ impl OpTransactionBuilder {
         self
     }
 
+    /// Set the source hash computed from the deposit source and mark the transaction as a
+    /// deposit.
+    pub fn deposit_source(mut self, source: DepositSource) -> Self {
+        self.deposit.source_hash = source.source_hash();
+        self.is_deposit_tx()
+    }
+
     /// Set the mint of the deposit transaction.
     pub fn mint(mut self, mint: u128) -> Self {
         self.deposit.mint = Some(mint);
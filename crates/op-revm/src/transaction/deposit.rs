@@ -1,5 +1,7 @@
 //! Contains Deposit transaction parts.
-use revm::primitives::B256;
+use super::{OpTransactionError, OpTxTr};
+use crate::OpSpecId;
+use revm::primitives::{keccak256, B256};
 
 /// Deposit transaction type.
 pub const DEPOSIT_TRANSACTION_TYPE: u8 = 0x7E;
@@ -27,6 +29,163 @@ impl DepositTransactionParts {
     }
 }
 
+/// Origin of a deposit transaction, used to compute its source hash.
+///
+/// See the [deposit source](https://specs.optimism.io/protocol/deposits.html#source-hash-computation)
+/// specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepositSource {
+    /// Deposit emitted by the `TransactionDeposited` event on L1.
+    User {
+        /// Hash of the L1 block that contains the deposit log.
+        l1_block_hash: B256,
+        /// Index of the deposit log in the L1 block.
+        log_index: u64,
+    },
+    /// L1 attributes deposit at the start of every L2 block.
+    L1Info {
+        /// Hash of the L1 origin block.
+        l1_block_hash: B256,
+        /// Sequence number of the L2 block in the epoch.
+        seq_number: u64,
+    },
+    /// Network upgrade transaction, identified by the hash of its intent.
+    ///
+    /// Use [`DepositSource::upgrade`] to create it from the intent string.
+    Upgrade {
+        /// Keccak hash of the upgrade intent.
+        intent_hash: B256,
+    },
+}
+
+impl DepositSource {
+    /// Domain of user deposits.
+    pub const USER_DOMAIN: u64 = 0;
+    /// Domain of L1 attributes deposits.
+    pub const L1_INFO_DOMAIN: u64 = 1;
+    /// Domain of upgrade deposits.
+    pub const UPGRADE_DOMAIN: u64 = 2;
+
+    /// Creates the source of an upgrade transaction with the given intent,
+    /// e.g. `"Ecotone: L1 Block Deployment"`.
+    pub fn upgrade(intent: &str) -> Self {
+        Self::Upgrade {
+            intent_hash: keccak256(intent),
+        }
+    }
+
+    /// Returns the domain of the source.
+    pub const fn domain(&self) -> u64 {
+        match self {
+            Self::User { .. } => Self::USER_DOMAIN,
+            Self::L1Info { .. } => Self::L1_INFO_DOMAIN,
+            Self::Upgrade { .. } => Self::UPGRADE_DOMAIN,
+        }
+    }
+
+    /// Returns the source hash, `keccak256(bytes32(domain) ++ deposit_id)`.
+    pub fn source_hash(&self) -> B256 {
+        let deposit_id = match *self {
+            Self::User {
+                l1_block_hash,
+                log_index: index,
+            }
+            | Self::L1Info {
+                l1_block_hash,
+                seq_number: index,
+            } => {
+                let mut input = [0u8; 64];
+                input[..32].copy_from_slice(l1_block_hash.as_slice());
+                input[56..].copy_from_slice(&index.to_be_bytes());
+                keccak256(input)
+            }
+            Self::Upgrade { intent_hash } => intent_hash,
+        };
+
+        let mut input = [0u8; 64];
+        input[24..32].copy_from_slice(&self.domain().to_be_bytes());
+        input[32..].copy_from_slice(deposit_id.as_slice());
+        keccak256(input)
+    }
+}
+
+impl From<DepositSource> for B256 {
+    fn from(source: DepositSource) -> Self {
+        source.source_hash()
+    }
+}
+
+/// Validates the deposit fields of the transaction against the rules of the given spec.
+///
+/// Deposits are verified on L1, the only rule enforced by the EVM is that system transactions
+/// are not allowed since Regolith. Does nothing for non-deposit transactions.
+pub fn validate_deposit_tx(tx: impl OpTxTr, spec: OpSpecId) -> Result<(), OpTransactionError> {
+    if tx.is_deposit() && tx.is_system_transaction() && spec.is_enabled_in(OpSpecId::REGOLITH) {
+        return Err(OpTransactionError::DepositSystemTxPostRegolith);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod source_tests {
+    use super::*;
+    use crate::OpTransaction;
+    use revm::{context::TxEnv, primitives::b256};
+
+    #[test]
+    fn upgrade_source_hash() {
+        let source = DepositSource::upgrade("Ecotone: L1 Block Deployment");
+        assert_eq!(
+            source.source_hash(),
+            b256!("0x877a6077205782ea15a6dc8699fa5ebcec5e0f4389f09cb8eda09488231346f8")
+        );
+    }
+
+    #[test]
+    fn user_and_l1_info_source_hash() {
+        let l1_block_hash = B256::repeat_byte(0xaa);
+        let user = DepositSource::User {
+            l1_block_hash,
+            log_index: 3,
+        };
+        let info = DepositSource::L1Info {
+            l1_block_hash,
+            seq_number: 3,
+        };
+
+        let mut id = [0u8; 64];
+        id[..32].copy_from_slice(l1_block_hash.as_slice());
+        id[63] = 3;
+        let mut input = [0u8; 64];
+        input[32..].copy_from_slice(keccak256(id).as_slice());
+        assert_eq!(user.source_hash(), keccak256(input));
+
+        // Same deposit id, different domain.
+        input[31] = 1;
+        assert_eq!(info.source_hash(), keccak256(input));
+    }
+
+    #[test]
+    fn system_tx_rules() {
+        let tx = OpTransaction::builder()
+            .deposit_source(DepositSource::upgrade("test"))
+            .is_system_transaction()
+            .build_fill();
+        assert!(tx.is_deposit());
+        assert_eq!(validate_deposit_tx(&tx, OpSpecId::BEDROCK), Ok(()));
+        assert_eq!(
+            validate_deposit_tx(&tx, OpSpecId::REGOLITH),
+            Err(OpTransactionError::DepositSystemTxPostRegolith)
+        );
+
+        // The flag is ignored for transactions that are not deposits.
+        let mut tx = OpTransaction::new(TxEnv::default());
+        tx.deposit.is_system_transaction = true;
+        assert_eq!(validate_deposit_tx(&tx, OpSpecId::ISTHMUS), Ok(()));
+    }
+}
+
 #[cfg(all(test, feature = "serde"))]
 mod tests {
     use super::*;
@@ -43,7 +43,15 @@ pub enum OpTransactionError {
     HaltedDepositPostRegolith,
 }
 
-impl TransactionError for OpTransactionError {}
+impl TransactionError for OpTransactionError {
+    fn code(&self) -> u32 {
+        match self {
+            Self::Base(error) => error.code(),
+            Self::DepositSystemTxPostRegolith => 1501,
+            Self::HaltedDepositPostRegolith => 1502,
+        }
+    }
+}
 
 impl Display for OpTransactionError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -101,6 +109,19 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_op_error_codes() {
+        assert_eq!(
+            OpTransactionError::Base(InvalidTransaction::NonceTooHigh { tx: 2, state: 1 }).code(),
+            InvalidTransaction::NonceTooHigh { tx: 2, state: 1 }.code()
+        );
+        assert_eq!(OpTransactionError::DepositSystemTxPostRegolith.code(), 1501);
+        assert_eq!(
+            EVMError::<(), _>::Transaction(OpTransactionError::HaltedDepositPostRegolith).code(),
+            1502
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize_json_op_transaction_error() {
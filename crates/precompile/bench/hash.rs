@@ -0,0 +1,30 @@
+//! Benchmarks for the fixed-size output precompiles
+use criterion::{measurement::Measurement, BenchmarkGroup};
+use primitives::hex;
+use revm_precompile::{
+    hash::{ripemd160_run, sha256_run},
+    identity::identity_run,
+    secp256r1::p256_verify,
+};
+
+/// Add benches for the SHA-256, RIPEMD-160, identity and P256VERIFY precompiles
+pub fn add_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let input = [0x42u8; 32];
+
+    group.bench_function("sha256 precompile", |b| {
+        b.iter(|| sha256_run(&input, u64::MAX).unwrap())
+    });
+
+    group.bench_function("ripemd160 precompile", |b| {
+        b.iter(|| ripemd160_run(&input, u64::MAX).unwrap())
+    });
+
+    group.bench_function("identity precompile", |b| {
+        b.iter(|| identity_run(&input, u64::MAX).unwrap())
+    });
+
+    let p256_input = hex!("4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4da73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d604aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff37618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e");
+    group.bench_function("p256verify precompile", |b| {
+        b.iter(|| p256_verify(&p256_input, u64::MAX).unwrap())
+    });
+}
@@ -6,6 +6,9 @@ pub mod ecrecover;
 pub mod eip1962;
 pub mod eip2537;
 pub mod eip4844;
+pub mod hash;
+#[cfg(feature = "rip7696")]
+pub mod rip7696;
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
@@ -30,11 +33,18 @@ pub fn benchmark_crypto_precompiles(c: &mut Criterion) {
     // Run secp256k1 benchmarks
     ecrecover::add_benches(&mut group);
 
+    // Run RIP-7696 benchmarks, priced against ecrecover
+    #[cfg(feature = "rip7696")]
+    rip7696::add_benches(&mut group);
+
     // Run KZG point evaluation benchmarks
     eip4844::add_benches(&mut group);
 
     // Run Blake2 benchmarks
     blake2::add_benches(&mut group);
+
+    // Run fixed-size output benchmarks
+    hash::add_benches(&mut group);
 }
 
 criterion_group! {
@@ -0,0 +1,25 @@
+//! Benchmarks for the RIP-7696 double scalar multiplication precompile
+use criterion::{measurement::Measurement, BenchmarkGroup};
+use primitives::{hex, Bytes};
+use revm_precompile::rip7696::ec_mulmuladd;
+
+/// Add benches for the RIP-7696 precompile
+///
+/// Uses secp256r1 with all scalar bits set, the worst case for the double-and-add loop.
+pub fn add_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let p = hex!("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    let a = hex!("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc");
+    let b = hex!("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+    let gx = hex!("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+    let gy = hex!("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+    // 2G
+    let qx = hex!("7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978");
+    let qy = hex!("07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1");
+    let scalar = [0xff; 32];
+
+    let input: Bytes = [p, a, b, gx, gy, qx, qy, scalar, scalar].concat().into();
+
+    group.bench_function("rip7696 ecmulmuladd precompile", |bencher| {
+        bencher.iter(|| ec_mulmuladd(&input, u64::MAX).unwrap())
+    });
+}
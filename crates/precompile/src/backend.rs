@@ -0,0 +1,250 @@
+//! Report of the libraries that implement the precompiles.
+//!
+//! Backends are selected with crate features, see [`Backend`] for the feature of each library.
+use crate::{
+    blake2, bls12_381_const, bn254, hash, identity, merkle, modexp, secp256k1, secp256r1,
+    u64_to_address, Precompiles,
+};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use primitives::Address;
+use std::vec::Vec;
+
+/// Set when a custom [`Crypto`][crate::Crypto] provider is installed.
+static CUSTOM_CRYPTO: AtomicBool = AtomicBool::new(false);
+
+/// Marks that the precompiles run on a custom crypto provider.
+pub(crate) fn set_custom_crypto() {
+    CUSTOM_CRYPTO.store(true, Ordering::Relaxed);
+}
+
+/// Returns `true` if a custom [`Crypto`][crate::Crypto] provider is installed.
+pub fn is_custom_crypto() -> bool {
+    CUSTOM_CRYPTO.load(Ordering::Relaxed)
+}
+
+/// Library that implements the cryptography of a precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    /// Implemented in this crate, without a dedicated library.
+    Native,
+    /// Provided by a [`Crypto`][crate::Crypto] installed with [`install_crypto`][crate::install_crypto].
+    Custom,
+    /// The precompile is not available, the features of its backends are disabled.
+    Unavailable,
+    /// Pure Rust `k256`, `k256` feature, used when neither `secp256k1` nor `libsecp256k1` is
+    /// enabled.
+    K256,
+    /// C library `secp256k1`, `secp256k1` feature.
+    Secp256k1,
+    /// Pure Rust `libsecp256k1`, `libsecp256k1` feature.
+    Libsecp256k1,
+    /// Pure Rust `sha2`.
+    Sha2,
+    /// Pure Rust `ripemd`.
+    Ripemd,
+    /// Pure Rust `aurora-engine-modexp`, `aurora-engine-modexp` feature, used when `gmp` is not
+    /// enabled.
+    AuroraModexp,
+    /// GMP through `rug`, `gmp` feature.
+    Gmp,
+    /// Pure Rust arkworks, `arkworks-bn254` or `arkworks-bls12-381` feature, used when `bn` or
+    /// `blst` is not enabled.
+    Arkworks,
+    /// Pure Rust `substrate-bn`, `bn` feature.
+    SubstrateBn,
+    /// C library `blst`, `blst` feature.
+    Blst,
+    /// C library `c-kzg`, `c-kzg` feature.
+    CKzg,
+    /// Pure Rust `kzg-rs`, `kzg-rs` feature.
+    KzgRs,
+    /// Pure Rust `p256`, `p256` feature.
+    P256,
+}
+
+impl Backend {
+    /// Backend of the secp256k1 precompile.
+    pub const SECP256K1: Self = if cfg!(feature = "secp256k1") {
+        Self::Secp256k1
+    } else if cfg!(feature = "libsecp256k1") {
+        Self::Libsecp256k1
+    } else if cfg!(feature = "k256") {
+        Self::K256
+    } else {
+        Self::Unavailable
+    };
+
+    /// Backend of the modexp precompile.
+    pub const MODEXP: Self = if cfg!(feature = "gmp") {
+        Self::Gmp
+    } else if cfg!(feature = "aurora-engine-modexp") {
+        Self::AuroraModexp
+    } else {
+        Self::Unavailable
+    };
+
+    /// Backend of the BN254 precompiles.
+    pub const BN254: Self = if cfg!(feature = "bn") {
+        Self::SubstrateBn
+    } else if cfg!(feature = "arkworks-bn254") {
+        Self::Arkworks
+    } else {
+        Self::Unavailable
+    };
+
+    /// Backend of the BLS12-381 precompiles.
+    pub const BLS12_381: Self = if cfg!(feature = "blst") {
+        Self::Blst
+    } else if cfg!(feature = "arkworks-bls12-381") {
+        Self::Arkworks
+    } else {
+        Self::Unavailable
+    };
+
+    /// Backend of the P256VERIFY precompile.
+    pub const P256VERIFY: Self = if cfg!(feature = "p256") {
+        Self::P256
+    } else {
+        Self::Unavailable
+    };
+
+    /// Backend of the KZG point evaluation precompile.
+    pub const KZG: Self = if cfg!(feature = "c-kzg") {
+        Self::CKzg
+    } else if cfg!(feature = "kzg-rs") {
+        Self::KzgRs
+    } else {
+        Self::Unavailable
+    };
+
+    /// Returns the name of the backend.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Custom => "custom",
+            Self::Unavailable => "unavailable",
+            Self::K256 => "k256",
+            Self::Secp256k1 => "secp256k1",
+            Self::Libsecp256k1 => "libsecp256k1",
+            Self::Sha2 => "sha2",
+            Self::Ripemd => "ripemd",
+            Self::AuroraModexp => "aurora-engine-modexp",
+            Self::Gmp => "gmp",
+            Self::Arkworks => "arkworks",
+            Self::SubstrateBn => "substrate-bn",
+            Self::Blst => "blst",
+            Self::CKzg => "c-kzg",
+            Self::KzgRs => "kzg-rs",
+            Self::P256 => "p256",
+        }
+    }
+
+    /// Returns `true` if the backend links a C library.
+    pub const fn is_c_library(&self) -> bool {
+        matches!(self, Self::Secp256k1 | Self::Gmp | Self::Blst | Self::CKzg)
+    }
+
+    /// Returns the backend of the known precompile at the address.
+    ///
+    /// Returns [`Backend::Custom`] for precompiles that go through the crypto provider if a
+    /// custom one is installed, and `None` for unknown addresses.
+    pub fn of(address: &Address) -> Option<Self> {
+        let backend = match *address {
+            ECRECOVER_ADDRESS => Self::SECP256K1,
+            SHA256_ADDRESS => Self::Sha2,
+            RIPEMD160_ADDRESS => Self::Ripemd,
+            IDENTITY_ADDRESS => return Some(Self::Native),
+            MODEXP_ADDRESS => Self::MODEXP,
+            bn254::add::ADDRESS | bn254::mul::ADDRESS | bn254::pair::ADDRESS => Self::BN254,
+            BLAKE2_ADDRESS => Self::Native,
+            KZG_ADDRESS => match Self::KZG {
+                Self::Unavailable => return Some(Self::Unavailable),
+                kzg => kzg,
+            },
+            bls12_381_const::G1_ADD_ADDRESS
+            | bls12_381_const::G1_MSM_ADDRESS
+            | bls12_381_const::G2_ADD_ADDRESS
+            | bls12_381_const::G2_MSM_ADDRESS
+            | bls12_381_const::PAIRING_ADDRESS
+            | bls12_381_const::MAP_FP_TO_G1_ADDRESS
+            | bls12_381_const::MAP_FP2_TO_G2_ADDRESS
+            | bls12_381_const::BLS_VERIFY_ADDRESS
+            | bls12_381_const::BLS_FAST_AGGREGATE_VERIFY_ADDRESS
+            | bls12_381_const::BLS_AGGREGATE_VERIFY_ADDRESS => Self::BLS12_381,
+            P256VERIFY_ADDRESS => Self::P256VERIFY,
+            BINARY_PROOF_ADDRESS | MPT_PROOF_ADDRESS => Self::Native,
+            _ => return None,
+        };
+        Some(if is_custom_crypto() {
+            Self::Custom
+        } else {
+            backend
+        })
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+const ECRECOVER_ADDRESS: Address = secp256k1::ECRECOVER.0;
+const SHA256_ADDRESS: Address = hash::SHA256.0;
+const RIPEMD160_ADDRESS: Address = hash::RIPEMD160.0;
+const IDENTITY_ADDRESS: Address = identity::FUN.0;
+const MODEXP_ADDRESS: Address = modexp::BERLIN.0;
+const BLAKE2_ADDRESS: Address = blake2::FUN.0;
+const KZG_ADDRESS: Address = u64_to_address(0x0A);
+const P256VERIFY_ADDRESS: Address = u64_to_address(secp256r1::P256VERIFY_ADDRESS);
+const BINARY_PROOF_ADDRESS: Address = u64_to_address(merkle::BINARY_PROOF_ADDRESS);
+const MPT_PROOF_ADDRESS: Address = u64_to_address(merkle::MPT_PROOF_ADDRESS);
+
+impl Precompiles {
+    /// Returns the backend of every precompile in the set, sorted by address.
+    ///
+    /// Precompiles at unknown addresses are reported as `None`.
+    pub fn backend_report(&self) -> Vec<(Address, Option<Backend>)> {
+        let mut report: Vec<_> = self
+            .addresses()
+            .map(|address| (*address, Backend::of(address)))
+            .collect();
+        report.sort_unstable_by_key(|(address, _)| *address);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancun_report() {
+        let report = Precompiles::cancun().backend_report();
+        assert_eq!(report.len(), 10);
+        assert_eq!(report[0], (u64_to_address(1), Some(Backend::SECP256K1)));
+        assert_eq!(report[3], (u64_to_address(4), Some(Backend::Native)));
+        assert_eq!(report[7], (u64_to_address(8), Some(Backend::BN254)));
+        assert_eq!(report[9], (KZG_ADDRESS, Some(Backend::KZG)));
+        assert!(report.iter().all(|(_, backend)| backend.is_some()));
+
+        assert_eq!(Backend::of(&Address::ZERO), None);
+        assert_eq!(Backend::BLS12_381.is_c_library(), cfg!(feature = "blst"));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "bn", feature = "arkworks-bn254")))]
+    fn unavailable_backend() {
+        assert_eq!(Backend::BN254, Backend::Unavailable);
+        assert_eq!(
+            (bn254::add::ISTANBUL.1)(&[0; 128], u64::MAX),
+            Err(crate::PrecompileError::Fatal(
+                "bn254 backend is not enabled".into()
+            ))
+        );
+    }
+}
@@ -6,9 +6,67 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "blst")]{
         pub(crate) mod blst;
         pub(crate) use blst as crypto_backend;
-    } else {
+    } else if #[cfg(feature = "arkworks-bls12-381")] {
         pub(crate) mod arkworks;
         pub(crate) use arkworks as crypto_backend;
+    } else {
+        /// Used when neither `blst` nor `arkworks-bls12-381` is enabled.
+        pub(crate) mod crypto_backend {
+            use super::{G1Point, G1PointScalar, G2Point, G2PointScalar, PairingPair};
+            use crate::{
+                bls12_381_const::{FP_LENGTH, G1_LENGTH, G2_LENGTH},
+                PrecompileError,
+            };
+
+            fn unavailable() -> PrecompileError {
+                PrecompileError::Fatal("bls12-381 backend is not enabled".into())
+            }
+
+            pub(crate) fn p1_add_affine_bytes(
+                _a: G1Point,
+                _b: G1Point,
+            ) -> Result<[u8; G1_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn p2_add_affine_bytes(
+                _a: G2Point,
+                _b: G2Point,
+            ) -> Result<[u8; G2_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn map_fp_to_g1_bytes(
+                _fp: &[u8; FP_LENGTH],
+            ) -> Result<[u8; G1_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn map_fp2_to_g2_bytes(
+                _fp2_x: &[u8; FP_LENGTH],
+                _fp2_y: &[u8; FP_LENGTH],
+            ) -> Result<[u8; G2_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn p1_msm_bytes(
+                _pairs: impl Iterator<Item = Result<G1PointScalar, PrecompileError>>,
+            ) -> Result<[u8; G1_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn p2_msm_bytes(
+                _pairs: impl Iterator<Item = Result<G2PointScalar, PrecompileError>>,
+            ) -> Result<[u8; G2_LENGTH], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn pairing_check_bytes(
+                _pairs: &[PairingPair],
+            ) -> Result<bool, PrecompileError> {
+                Err(unavailable())
+            }
+        }
     }
 }
 
@@ -36,6 +94,7 @@ pub mod g2_msm;
 pub mod map_fp2_to_g2;
 pub mod map_fp_to_g1;
 pub mod pairing;
+pub mod signature;
 mod utils;
 
 /// Returns the BLS12-381 precompiles with their addresses.
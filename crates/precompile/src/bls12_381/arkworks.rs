@@ -360,7 +360,7 @@ fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
 
 /// pairing_check_bytes performs a pairing check on a list of G1 and G2 point pairs taking byte inputs.
 #[inline]
-pub fn pairing_check_bytes(pairs: &[PairingPair]) -> Result<bool, PrecompileError> {
+pub(crate) fn pairing_check_bytes(pairs: &[PairingPair]) -> Result<bool, PrecompileError> {
     if pairs.is_empty() {
         return Ok(true);
     }
@@ -405,7 +405,10 @@ pub fn pairing_check_bytes(pairs: &[PairingPair]) -> Result<bool, PrecompileErro
 
 /// Performs point addition on two G1 points taking byte coordinates.
 #[inline]
-pub fn p1_add_affine_bytes(a: G1Point, b: G1Point) -> Result<[u8; G1_LENGTH], PrecompileError> {
+pub(crate) fn p1_add_affine_bytes(
+    a: G1Point,
+    b: G1Point,
+) -> Result<[u8; G1_LENGTH], PrecompileError> {
     let (a_x, a_y) = a;
     let (b_x, b_y) = b;
     // Parse first point
@@ -423,7 +426,10 @@ pub fn p1_add_affine_bytes(a: G1Point, b: G1Point) -> Result<[u8; G1_LENGTH], Pr
 
 /// Performs point addition on two G2 points taking byte coordinates.
 #[inline]
-pub fn p2_add_affine_bytes(a: G2Point, b: G2Point) -> Result<[u8; G2_LENGTH], PrecompileError> {
+pub(crate) fn p2_add_affine_bytes(
+    a: G2Point,
+    b: G2Point,
+) -> Result<[u8; G2_LENGTH], PrecompileError> {
     let (a_x_0, a_x_1, a_y_0, a_y_1) = a;
     let (b_x_0, b_x_1, b_y_0, b_y_1) = b;
     // Parse first point
@@ -441,7 +447,9 @@ pub fn p2_add_affine_bytes(a: G2Point, b: G2Point) -> Result<[u8; G2_LENGTH], Pr
 
 /// Maps a field element to a G1 point from bytes
 #[inline]
-pub fn map_fp_to_g1_bytes(fp_bytes: &[u8; FP_LENGTH]) -> Result<[u8; G1_LENGTH], PrecompileError> {
+pub(crate) fn map_fp_to_g1_bytes(
+    fp_bytes: &[u8; FP_LENGTH],
+) -> Result<[u8; G1_LENGTH], PrecompileError> {
     let fp = read_fp(fp_bytes)?;
     let result = map_fp_to_g1(&fp);
     Ok(encode_g1_point(&result))
@@ -449,7 +457,7 @@ pub fn map_fp_to_g1_bytes(fp_bytes: &[u8; FP_LENGTH]) -> Result<[u8; G1_LENGTH],
 
 /// Maps field elements to a G2 point from bytes
 #[inline]
-pub fn map_fp2_to_g2_bytes(
+pub(crate) fn map_fp2_to_g2_bytes(
     fp2_x: &[u8; FP_LENGTH],
     fp2_y: &[u8; FP_LENGTH],
 ) -> Result<[u8; G2_LENGTH], PrecompileError> {
@@ -460,7 +468,7 @@ pub fn map_fp2_to_g2_bytes(
 
 /// Performs multi-scalar multiplication (MSM) for G1 points taking byte inputs.
 #[inline]
-pub fn p1_msm_bytes(
+pub(crate) fn p1_msm_bytes(
     point_scalar_pairs: impl Iterator<Item = Result<(G1Point, [u8; SCALAR_LENGTH]), PrecompileError>>,
 ) -> Result<[u8; G1_LENGTH], PrecompileError> {
     let mut g1_points = Vec::new();
@@ -497,7 +505,7 @@ pub fn p1_msm_bytes(
 
 /// Performs multi-scalar multiplication (MSM) for G2 points taking byte inputs.
 #[inline]
-pub fn p2_msm_bytes(
+pub(crate) fn p2_msm_bytes(
     point_scalar_pairs: impl Iterator<Item = Result<(G2Point, [u8; SCALAR_LENGTH]), PrecompileError>>,
 ) -> Result<[u8; G2_LENGTH], PrecompileError> {
     let mut g2_points = Vec::new();
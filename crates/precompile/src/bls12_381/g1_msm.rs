@@ -54,7 +54,7 @@ pub fn g1_msm(input: &[u8], gas_limit: u64) -> PrecompileResult {
     Ok(PrecompileOutput::new(required_gas, padded_result.into()))
 }
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "blst", feature = "arkworks-bls12-381")))]
 mod test {
     use super::*;
     use primitives::{hex, Bytes};
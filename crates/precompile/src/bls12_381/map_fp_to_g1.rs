@@ -35,7 +35,7 @@ pub fn map_fp_to_g1(input: &[u8], gas_limit: u64) -> PrecompileResult {
     ))
 }
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "blst", feature = "arkworks-bls12-381")))]
 mod test {
     use super::*;
     use primitives::{hex, Bytes};
@@ -5,8 +5,10 @@ use crate::bls12_381_const::{
     PADDED_G1_LENGTH, PADDED_G2_LENGTH, PAIRING_ADDRESS, PAIRING_INPUT_LENGTH,
     PAIRING_MULTIPLIER_BASE, PAIRING_OFFSET_BASE,
 };
-use crate::{crypto, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress};
-use primitives::B256;
+use crate::{
+    crypto, utilities::bool_to_bytes32, PrecompileError, PrecompileOutput, PrecompileResult,
+    PrecompileWithAddress,
+};
 use std::vec::Vec;
 
 /// [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537#specification) BLS12_PAIRING precompile.
@@ -53,10 +55,5 @@ pub fn pairing(input: &[u8], gas_limit: u64) -> PrecompileResult {
     }
 
     let result = crypto().bls12_381_pairing_check(&pairs)?;
-    let result = if result { 1 } else { 0 };
-
-    Ok(PrecompileOutput::new(
-        required_gas,
-        B256::with_last_byte(result).into(),
-    ))
+    Ok(PrecompileOutput::new(required_gas, bool_to_bytes32(result)))
 }
@@ -0,0 +1,385 @@
+//! BLS signature verification precompiles. More details in [`verify`], [`fast_aggregate_verify`]
+//! and [`aggregate_verify`].
+//!
+//! Signatures follow the Ethereum consensus scheme: public keys are in G1, signatures are in G2
+//! and messages are hashed to G2 with the [`BLS_SIG_DST`] domain separation tag. Points use the
+//! [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537#fine-points-and-encoding-of-base-elements)
+//! encoding and verification is built on the same arithmetic as the EIP-2537 precompiles.
+//!
+//! None of these precompiles are part of an Ethereum hardfork.
+use super::utils::{remove_g1_padding, remove_g2_padding};
+use super::{G1Point, G2Point};
+use crate::bls12_381_const::{
+    BLS_AGGREGATE_VERIFY_ADDRESS, BLS_FAST_AGGREGATE_VERIFY_ADDRESS, BLS_SIG_DST,
+    BLS_SIG_HASH_TO_G2_BASE_GAS, BLS_SIG_HASH_TO_G2_PER_WORD_GAS, BLS_SIG_MESSAGE_LENGTH,
+    BLS_VERIFY_ADDRESS, DISCOUNT_TABLE_G1_MSM, FP_LENGTH, G1_MSM_BASE_GAS_FEE, PADDED_G1_LENGTH,
+    PADDED_G2_LENGTH, PAIRING_MULTIPLIER_BASE, PAIRING_OFFSET_BASE, SCALAR_LENGTH,
+};
+use crate::bls12_381_utils::msm_required_gas;
+use crate::{crypto, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress};
+use primitives::{hex, ruint::aliases::U512, B256};
+use std::vec::Vec;
+
+/// BLS signature verify precompile.
+pub const VERIFY: PrecompileWithAddress = PrecompileWithAddress(BLS_VERIFY_ADDRESS, verify);
+/// BLS fast aggregate signature verify precompile.
+pub const FAST_AGGREGATE_VERIFY: PrecompileWithAddress =
+    PrecompileWithAddress(BLS_FAST_AGGREGATE_VERIFY_ADDRESS, fast_aggregate_verify);
+/// BLS aggregate signature verify precompile.
+pub const AGGREGATE_VERIFY: PrecompileWithAddress =
+    PrecompileWithAddress(BLS_AGGREGATE_VERIFY_ADDRESS, aggregate_verify);
+
+/// Returns the BLS signature precompiles with their addresses.
+pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+    [VERIFY, FAST_AGGREGATE_VERIFY, AGGREGATE_VERIFY].into_iter()
+}
+
+/// Input length of a fast aggregate verify call without the public keys.
+const FAST_AGGREGATE_VERIFY_PREFIX_LENGTH: usize = PADDED_G2_LENGTH + BLS_SIG_MESSAGE_LENGTH;
+
+/// Length of a public key and message pair of the aggregate verify input.
+const AGGREGATE_VERIFY_PAIR_LENGTH: usize = PADDED_G1_LENGTH + BLS_SIG_MESSAGE_LENGTH;
+
+/// Number of bytes expanded from the message, two Fp^2 elements with 64 bytes per Fp element.
+const HASH_TO_FIELD_LENGTH: usize = 4 * 64;
+
+/// Big-endian base field modulus.
+const MODULUS_REPR: [u8; FP_LENGTH] = hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+
+/// Negated generator of G1, pairing the signature with it moves the check to a single product.
+const NEG_G1_GENERATOR: G1Point = (
+    hex!("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"),
+    hex!("114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca"),
+);
+
+/// Verifies a signature of a message.
+///
+/// Input is the byte concatenation of:
+///    * 128 bytes of the G1 public key encoding
+///    * 256 bytes of the G2 signature encoding
+///    * message of any length
+///
+/// Output is 32 bytes where the last byte is 0x01 if the signature is valid and 0x00 otherwise.
+/// Malformed points, points outside of the subgroup and the public key at infinity fail the call.
+pub fn verify(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let min_len = PADDED_G1_LENGTH + PADDED_G2_LENGTH;
+    if input.len() < min_len {
+        return Err(PrecompileError::Other(format!(
+            "BLS verify input length should be at least {min_len}, was {}",
+            input.len()
+        )));
+    }
+
+    let message = &input[min_len..];
+    let required_gas = hash_to_g2_gas(message.len()) + pairing_gas(2);
+    if required_gas > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let pubkey = read_pubkey(&input[..PADDED_G1_LENGTH])?;
+    let signature = read_signature(&input[PADDED_G1_LENGTH..min_len])?;
+    let result = core_verify(pubkey, message, signature)?;
+    Ok(verify_output(required_gas, result))
+}
+
+/// Verifies an aggregate signature of a message signed by all public keys.
+///
+/// Input is the byte concatenation of:
+///    * 256 bytes of the G2 aggregate signature encoding
+///    * 32 bytes of the message
+///    * 128*k (k being a positive integer) bytes of G1 public key encodings
+///
+/// Public keys are aggregated with a G1 MSM and priced as one, which keeps the cost well below
+/// a pairing per key. Output and failures are the same as in [`verify`].
+pub fn fast_aggregate_verify(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let input_len = input.len();
+    let pubkeys_len = input_len.saturating_sub(FAST_AGGREGATE_VERIFY_PREFIX_LENGTH);
+    if pubkeys_len == 0 || !pubkeys_len.is_multiple_of(PADDED_G1_LENGTH) {
+        return Err(PrecompileError::Other(format!(
+            "BLS fast aggregate verify input length should be {FAST_AGGREGATE_VERIFY_PREFIX_LENGTH} plus a multiple of {PADDED_G1_LENGTH}, was {input_len}"
+        )));
+    }
+
+    let k = pubkeys_len / PADDED_G1_LENGTH;
+    let required_gas = msm_required_gas(k, &DISCOUNT_TABLE_G1_MSM, G1_MSM_BASE_GAS_FEE)
+        + hash_to_g2_gas(BLS_SIG_MESSAGE_LENGTH)
+        + pairing_gas(2);
+    if required_gas > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let signature = read_signature(&input[..PADDED_G2_LENGTH])?;
+    let message = &input[PADDED_G2_LENGTH..FAST_AGGREGATE_VERIFY_PREFIX_LENGTH];
+
+    // Multiplying every key by one sums them with the subgroup check of the MSM.
+    let mut one = [0u8; SCALAR_LENGTH];
+    one[SCALAR_LENGTH - 1] = 1;
+    let mut pubkeys = input[FAST_AGGREGATE_VERIFY_PREFIX_LENGTH..]
+        .chunks_exact(PADDED_G1_LENGTH)
+        .map(|encoded| read_pubkey(encoded).map(|pubkey| (pubkey, one)));
+    let aggregate = crypto().bls12_381_g1_msm(&mut pubkeys)?;
+
+    // Keys that cancel each other out are not a valid aggregate key.
+    if aggregate.iter().all(|&b| b == 0) {
+        return Ok(verify_output(required_gas, false));
+    }
+    let (x, y) = aggregate.split_at(FP_LENGTH);
+    let aggregate = (x.try_into().unwrap(), y.try_into().unwrap());
+
+    let result = core_verify(aggregate, message, signature)?;
+    Ok(verify_output(required_gas, result))
+}
+
+/// Verifies an aggregate signature of messages each signed by its own public key.
+///
+/// Input is the byte concatenation of:
+///    * 256 bytes of the G2 aggregate signature encoding
+///    * 160*k (k being a positive integer) bytes of slices, each being the byte concatenation of
+///      a 128 bytes G1 public key encoding and the 32 bytes message signed by it
+///
+/// Messages don't have to be distinct as public keys of the proof of possession scheme are
+/// expected to be registered with a proof. Output and failures are the same as in [`verify`].
+pub fn aggregate_verify(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let input_len = input.len();
+    let pairs_len = input_len.saturating_sub(PADDED_G2_LENGTH);
+    if pairs_len == 0 || !pairs_len.is_multiple_of(AGGREGATE_VERIFY_PAIR_LENGTH) {
+        return Err(PrecompileError::Other(format!(
+            "BLS aggregate verify input length should be {PADDED_G2_LENGTH} plus a multiple of {AGGREGATE_VERIFY_PAIR_LENGTH}, was {input_len}"
+        )));
+    }
+
+    let k = pairs_len / AGGREGATE_VERIFY_PAIR_LENGTH;
+    let required_gas = k as u64 * hash_to_g2_gas(BLS_SIG_MESSAGE_LENGTH) + pairing_gas(k + 1);
+    if required_gas > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let signature = read_signature(&input[..PADDED_G2_LENGTH])?;
+
+    let mut pairs: Vec<(G1Point, G2Point)> = Vec::with_capacity(k + 1);
+    for pair in input[PADDED_G2_LENGTH..].chunks_exact(AGGREGATE_VERIFY_PAIR_LENGTH) {
+        let (pubkey, message) = pair.split_at(PADDED_G1_LENGTH);
+        pairs.push((read_pubkey(pubkey)?, hash_to_g2(message)?));
+    }
+    pairs.push((NEG_G1_GENERATOR, signature));
+
+    let result = crypto().bls12_381_pairing_check(&pairs)?;
+    Ok(verify_output(required_gas, result))
+}
+
+/// Gas needed to hash a message of the given length to G2.
+fn hash_to_g2_gas(message_len: usize) -> u64 {
+    BLS_SIG_HASH_TO_G2_BASE_GAS
+        + (message_len as u64).div_ceil(32) * BLS_SIG_HASH_TO_G2_PER_WORD_GAS
+}
+
+/// Gas needed for a pairing check of `k` pairs, same as the EIP-2537 pairing precompile.
+fn pairing_gas(k: usize) -> u64 {
+    PAIRING_MULTIPLIER_BASE * k as u64 + PAIRING_OFFSET_BASE
+}
+
+fn verify_output(gas_used: u64, result: bool) -> PrecompileOutput {
+    PrecompileOutput::new(gas_used, B256::with_last_byte(result as u8).into())
+}
+
+/// Checks `e(pubkey, H(message)) == e(G1, signature)`.
+fn core_verify(
+    pubkey: G1Point,
+    message: &[u8],
+    signature: G2Point,
+) -> Result<bool, PrecompileError> {
+    let hash = hash_to_g2(message)?;
+    crypto().bls12_381_pairing_check(&[(pubkey, hash), (NEG_G1_GENERATOR, signature)])
+}
+
+/// Reads a public key, the point at infinity is rejected.
+///
+/// Subgroup check is done by the backend when the key is used.
+fn read_pubkey(input: &[u8]) -> Result<G1Point, PrecompileError> {
+    let [x, y] = remove_g1_padding(input)?;
+    if x.iter().chain(y).all(|&b| b == 0) {
+        return Err(PrecompileError::Other(
+            "BLS public key is the point at infinity".into(),
+        ));
+    }
+    Ok((*x, *y))
+}
+
+fn read_signature(input: &[u8]) -> Result<G2Point, PrecompileError> {
+    let [x_0, x_1, y_0, y_1] = remove_g2_padding(input)?;
+    Ok((*x_0, *x_1, *y_0, *y_1))
+}
+
+/// Hashes the message to G2 as specified by the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
+///
+/// Cofactor clearing is linear, so adding the two mapped points gives the same result as
+/// clearing the cofactor of their sum.
+fn hash_to_g2(message: &[u8]) -> Result<G2Point, PrecompileError> {
+    let uniform = expand_message_xmd(message);
+    let fp = |i: usize| reduce_to_fp(&uniform[i * 64..(i + 1) * 64]);
+
+    let q0 = crypto().bls12_381_fp2_to_g2((fp(0), fp(1)))?;
+    let q1 = crypto().bls12_381_fp2_to_g2((fp(2), fp(3)))?;
+    let sum = crypto().bls12_381_g2_add(split_g2(&q0), split_g2(&q1))?;
+    Ok(split_g2(&sum))
+}
+
+/// `expand_message_xmd` with SHA-256 and the [`BLS_SIG_DST`] tag.
+fn expand_message_xmd(message: &[u8]) -> [u8; HASH_TO_FIELD_LENGTH] {
+    let dst_len = [BLS_SIG_DST.len() as u8];
+
+    let mut msg_prime = Vec::with_capacity(64 + message.len() + 3 + BLS_SIG_DST.len() + 1);
+    msg_prime.extend_from_slice(&[0u8; 64]);
+    msg_prime.extend_from_slice(message);
+    msg_prime.extend_from_slice(&(HASH_TO_FIELD_LENGTH as u16).to_be_bytes());
+    msg_prime.push(0);
+    msg_prime.extend_from_slice(BLS_SIG_DST);
+    msg_prime.extend_from_slice(&dst_len);
+    let b_0 = crypto().sha256(&msg_prime);
+
+    let mut output = [0u8; HASH_TO_FIELD_LENGTH];
+    let mut b_i = [0u8; 32];
+    let mut block = Vec::with_capacity(32 + 1 + BLS_SIG_DST.len() + 1);
+    for (i, chunk) in output.chunks_exact_mut(32).enumerate() {
+        // `b_1` hashes `b_0` itself, which is `b_0` xor zeroes.
+        block.clear();
+        block.extend(b_0.iter().zip(b_i).map(|(a, b)| a ^ b));
+        block.push(i as u8 + 1);
+        block.extend_from_slice(BLS_SIG_DST);
+        block.extend_from_slice(&dst_len);
+        b_i = crypto().sha256(&block);
+        chunk.copy_from_slice(&b_i);
+    }
+    output
+}
+
+/// Reduces 64 big-endian bytes modulo the base field modulus.
+fn reduce_to_fp(input: &[u8]) -> [u8; FP_LENGTH] {
+    let value = U512::from_be_slice(input) % U512::from_be_slice(&MODULUS_REPR);
+    let bytes = value.to_be_bytes::<64>();
+    bytes[64 - FP_LENGTH..].try_into().unwrap()
+}
+
+fn split_g2(point: &[u8; 192]) -> G2Point {
+    let fp = |i: usize| -> [u8; FP_LENGTH] {
+        point[i * FP_LENGTH..(i + 1) * FP_LENGTH]
+            .try_into()
+            .unwrap()
+    };
+    (fp(0), fp(1), fp(2), fp(3))
+}
+
+#[cfg(all(test, feature = "blst"))]
+mod test {
+    use super::*;
+    use crate::bls12_381_const::PADDED_FP_LENGTH;
+    use blst::min_pk::{AggregateSignature, PublicKey, SecretKey, Signature};
+
+    fn secret_key(seed: u8) -> SecretKey {
+        SecretKey::key_gen(&[seed; 32], &[]).unwrap()
+    }
+
+    fn pad(fps: &[&[u8]]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for fp in fps {
+            out.extend_from_slice(&[0u8; PADDED_FP_LENGTH - FP_LENGTH]);
+            out.extend_from_slice(fp);
+        }
+        out
+    }
+
+    fn encode_pubkey(pubkey: &PublicKey) -> Vec<u8> {
+        let raw = pubkey.serialize();
+        pad(&[&raw[..48], &raw[48..]])
+    }
+
+    /// blst serializes Fp^2 elements with the imaginary part first.
+    fn encode_signature(signature: &Signature) -> Vec<u8> {
+        let raw = signature.serialize();
+        pad(&[&raw[48..96], &raw[..48], &raw[144..], &raw[96..144]])
+    }
+
+    fn sign(seed: u8, message: &[u8]) -> Signature {
+        secret_key(seed).sign(message, BLS_SIG_DST, &[])
+    }
+
+    fn is_valid(result: PrecompileResult) -> bool {
+        let output = result.unwrap();
+        assert_eq!(output.bytes.len(), 32);
+        output.bytes[31] == 1
+    }
+
+    #[test]
+    fn test_verify() {
+        let message = b"any length message";
+        let pubkey = secret_key(1).sk_to_pk();
+        let mut input = encode_pubkey(&pubkey);
+        input.extend(encode_signature(&sign(1, message)));
+        input.extend_from_slice(message);
+
+        let gas = hash_to_g2_gas(message.len()) + pairing_gas(2);
+        assert!(is_valid(verify(&input, gas)));
+        assert_eq!(verify(&input, gas - 1), Err(PrecompileError::OutOfGas));
+
+        // Different message.
+        *input.last_mut().unwrap() ^= 1;
+        assert!(!is_valid(verify(&input, gas)));
+
+        // Public key at infinity.
+        input[..PADDED_G1_LENGTH].fill(0);
+        assert!(verify(&input, gas).is_err());
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify() {
+        let message = [7u8; BLS_SIG_MESSAGE_LENGTH];
+        let signatures: Vec<_> = (1..=3).map(|seed| sign(seed, &message)).collect();
+        let signatures: Vec<_> = signatures.iter().collect();
+        let aggregate = AggregateSignature::aggregate(&signatures, true)
+            .unwrap()
+            .to_signature();
+
+        let mut input = encode_signature(&aggregate);
+        input.extend_from_slice(&message);
+        for seed in 1..=3 {
+            input.extend(encode_pubkey(&secret_key(seed).sk_to_pk()));
+        }
+        assert!(is_valid(fast_aggregate_verify(&input, u64::MAX)));
+
+        // Missing signer.
+        input.truncate(input.len() - PADDED_G1_LENGTH);
+        assert!(!is_valid(fast_aggregate_verify(&input, u64::MAX)));
+
+        // No public keys.
+        input.truncate(FAST_AGGREGATE_VERIFY_PREFIX_LENGTH);
+        assert!(fast_aggregate_verify(&input, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let messages: Vec<_> = (1..=3u8)
+            .map(|seed| [seed; BLS_SIG_MESSAGE_LENGTH])
+            .collect();
+        let signatures: Vec<_> = (1..=3u8)
+            .map(|seed| sign(seed, &messages[seed as usize - 1]))
+            .collect();
+        let signatures: Vec<_> = signatures.iter().collect();
+        let aggregate = AggregateSignature::aggregate(&signatures, true)
+            .unwrap()
+            .to_signature();
+
+        let mut input = encode_signature(&aggregate);
+        for seed in 1..=3u8 {
+            input.extend(encode_pubkey(&secret_key(seed).sk_to_pk()));
+            input.extend_from_slice(&messages[seed as usize - 1]);
+        }
+        let gas = 3 * hash_to_g2_gas(BLS_SIG_MESSAGE_LENGTH) + pairing_gas(4);
+        assert!(is_valid(aggregate_verify(&input, gas)));
+
+        // Messages swapped between signers.
+        let last = input.len() - BLS_SIG_MESSAGE_LENGTH;
+        input[last..].copy_from_slice(&messages[0]);
+        assert!(!is_valid(aggregate_verify(&input, gas)));
+    }
+}
@@ -18,6 +18,16 @@ pub const MAP_FP_TO_G1_ADDRESS: Address = u64_to_address(0x10);
 /// Map fp2 to g2 precompile address
 pub const MAP_FP2_TO_G2_ADDRESS: Address = u64_to_address(0x11);
 
+/// BLS signature verify precompile address.
+///
+/// Signature precompiles are not part of any Ethereum hardfork and are only enabled by chains
+/// that add them to their precompile set.
+pub const BLS_VERIFY_ADDRESS: Address = u64_to_address(0x0b80);
+/// BLS fast aggregate signature verify precompile address.
+pub const BLS_FAST_AGGREGATE_VERIFY_ADDRESS: Address = u64_to_address(0x0b81);
+/// BLS aggregate signature verify precompile address.
+pub const BLS_AGGREGATE_VERIFY_ADDRESS: Address = u64_to_address(0x0b82);
+
 /// G1_ADD_BASE_GAS_FEE specifies the amount of gas needed
 /// to perform the G1_ADD precompile.
 pub const G1_ADD_BASE_GAS_FEE: u64 = 375;
@@ -182,6 +192,25 @@ pub const PAIRING_INPUT_LENGTH: usize = PADDED_G1_LENGTH + PADDED_G2_LENGTH;
 /// Note: This should be equal to PADDED_FP_LENGTH - FP_LENGTH.
 pub const FP_PAD_BY: usize = 16;
 
+// Constants related to the BLS signature precompiles
+
+/// Domain separation tag of the Ethereum consensus BLS signature scheme.
+///
+/// Proof of possession scheme with public keys in G1 and signatures in G2.
+pub const BLS_SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// BLS_SIG_MESSAGE_LENGTH specifies the length of the messages of the aggregate precompiles.
+///
+/// Note: Consensus layer signing roots are 32 bytes.
+pub const BLS_SIG_MESSAGE_LENGTH: usize = 32;
+/// BLS_SIG_HASH_TO_G2_BASE_GAS specifies the amount of gas needed to hash a message to G2.
+///
+/// Note: Hashing to G2 maps two Fp^2 elements to G2 and adds the results.
+pub const BLS_SIG_HASH_TO_G2_BASE_GAS: u64 =
+    2 * MAP_FP2_TO_G2_BASE_GAS_FEE + G2_ADD_BASE_GAS_FEE + 1000;
+/// BLS_SIG_HASH_TO_G2_PER_WORD_GAS specifies the amount of gas needed to hash each
+/// 32 byte word of the message.
+pub const BLS_SIG_HASH_TO_G2_PER_WORD_GAS: u64 = 12;
+
 #[test]
 fn check_discount_table_invariant_holds() {
     // Currently EIP-2537 specifies the cost for a G1/G2 scalar multiplication in two places
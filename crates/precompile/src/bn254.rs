@@ -10,9 +10,30 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "bn")]{
         pub(crate) mod substrate;
         pub(crate) use substrate as crypto_backend;
-    } else {
+    } else if #[cfg(feature = "arkworks-bn254")] {
         pub(crate) mod arkworks;
         pub(crate) use arkworks as crypto_backend;
+    } else {
+        /// Used when neither `bn` nor `arkworks-bn254` is enabled.
+        pub(crate) mod crypto_backend {
+            use crate::PrecompileError;
+
+            fn unavailable() -> PrecompileError {
+                PrecompileError::Fatal("bn254 backend is not enabled".into())
+            }
+
+            pub(crate) fn g1_point_add(_p1: &[u8], _p2: &[u8]) -> Result<[u8; 64], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn g1_point_mul(_point: &[u8], _fr: &[u8]) -> Result<[u8; 64], PrecompileError> {
+                Err(unavailable())
+            }
+
+            pub(crate) fn pairing_check(_pairs: &[(&[u8], &[u8])]) -> Result<bool, PrecompileError> {
+                Err(unavailable())
+            }
+        }
     }
 }
 
@@ -220,7 +241,64 @@ pub fn run_pair(
     ))
 }
 
-#[cfg(test)]
+/// Checks multiple independent pairing inputs with a single pairing computation.
+///
+/// Every input has the format of the [`pair`] precompile input. Returns `true` if the pairing
+/// check of every input succeeds, an empty batch or input is successful like in the precompile.
+///
+/// G1 points of every input except the first one are multiplied by a 128-bit weight derived from
+/// the hash of the whole batch and all pairs are checked together, so the Miller loops share one
+/// final exponentiation. A batch with a failing input passes only with negligible probability.
+/// A failing batch does not tell which input failed, check the inputs one by one for that.
+pub fn pairing_batch_check(inputs: &[&[u8]]) -> Result<bool, PrecompileError> {
+    if inputs
+        .iter()
+        .any(|input| !input.len().is_multiple_of(PAIR_ELEMENT_LEN))
+    {
+        return Err(PrecompileError::Bn254PairLength);
+    }
+
+    let elements = inputs.iter().map(|input| input.len()).sum::<usize>() / PAIR_ELEMENT_LEN;
+
+    let mut seed = Vec::with_capacity(elements * PAIR_ELEMENT_LEN + inputs.len() * 8);
+    for input in inputs {
+        seed.extend_from_slice(&(input.len() as u64).to_be_bytes());
+        seed.extend_from_slice(input);
+    }
+    let seed = crypto().keccak256(&seed);
+
+    let mut g1_points = Vec::with_capacity(elements);
+    let mut g2_points = Vec::with_capacity(elements);
+    let mut weight = [0u8; SCALAR_LEN];
+    for (idx, input) in inputs.iter().enumerate() {
+        if idx != 0 {
+            // Weight is the first half of `keccak256(seed || idx)`.
+            let mut preimage = [0u8; 40];
+            preimage[..32].copy_from_slice(seed.as_slice());
+            preimage[32..].copy_from_slice(&(idx as u64).to_be_bytes());
+            let hash = crypto().keccak256(&preimage);
+            weight[SCALAR_LEN / 2..].copy_from_slice(&hash[..SCALAR_LEN / 2]);
+        }
+        for element in input.chunks_exact(PAIR_ELEMENT_LEN) {
+            let (g1, g2) = element.split_at(G1_LEN);
+            g1_points.push(if idx == 0 {
+                g1.try_into().expect("G1 has fixed length")
+            } else {
+                crypto().bn254_g1_mul(g1, &weight)?
+            });
+            g2_points.push(g2);
+        }
+    }
+
+    let pairs: Vec<_> = g1_points
+        .iter()
+        .map(|g1| g1.as_slice())
+        .zip(g2_points)
+        .collect();
+    crypto().bn254_pairing_check(&pairs)
+}
+
+#[cfg(all(test, any(feature = "bn", feature = "arkworks-bn254")))]
 mod tests {
     use crate::{
         bn254::{
@@ -393,6 +471,38 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_bn254_pairing_batch_check() {
+        let valid = hex::decode(
+            "\
+            1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+            3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+            209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+            04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+            2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+            120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+            111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+            2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+            198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+            1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+            090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+            12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        )
+        .unwrap();
+        // Pairing of the first element alone is not the identity.
+        let invalid = &valid[..PAIR_ELEMENT_LEN];
+
+        assert_eq!(pairing_batch_check(&[]), Ok(true));
+        assert_eq!(pairing_batch_check(&[&valid, &[], &valid]), Ok(true));
+        assert_eq!(pairing_batch_check(&[invalid]), Ok(false));
+        assert_eq!(pairing_batch_check(&[&valid, invalid]), Ok(false));
+        assert_eq!(pairing_batch_check(&[invalid, &valid]), Ok(false));
+        assert_eq!(
+            pairing_batch_check(&[&valid, &valid[1..]]),
+            Err(PrecompileError::Bn254PairLength)
+        );
+    }
+
     #[test]
     fn test_bn254_pair() {
         let input = hex::decode(
@@ -22,8 +22,7 @@ pub fn sha256_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     if cost > gas_limit {
         Err(PrecompileError::OutOfGas)
     } else {
-        let output = crypto().sha256(input);
-        Ok(PrecompileOutput::new(cost, output.to_vec().into()))
+        Ok(PrecompileOutput::from_array(cost, crypto().sha256(input)))
     }
 }
 
@@ -38,7 +37,9 @@ pub fn ripemd160_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     if gas_used > gas_limit {
         Err(PrecompileError::OutOfGas)
     } else {
-        let output = crypto().ripemd160(input);
-        Ok(PrecompileOutput::new(gas_used, output.to_vec().into()))
+        Ok(PrecompileOutput::from_array(
+            gas_used,
+            crypto().ripemd160(input),
+        ))
     }
 }
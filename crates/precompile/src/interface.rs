@@ -1,7 +1,7 @@
 //! Interface for the precompiles. It contains the precompile result type,
 //! the precompile output type, and the precompile error type.
 use core::fmt::{self, Debug};
-use primitives::{Bytes, OnceLock};
+use primitives::{eip4844::BYTES_PER_BLOB, Bytes, OnceLock, B256};
 use std::{boxed::Box, string::String, vec::Vec};
 
 use crate::bls12_381::{G1Point, G1PointScalar, G2Point, G2PointScalar};
@@ -16,6 +16,7 @@ pub fn install_crypto<C: Crypto + 'static>(crypto: C) -> bool {
     }
 
     CRYPTO.get_or_init(|| Box::new(crypto));
+    crate::backend::set_custom_crypto();
     true
 }
 
@@ -50,6 +51,13 @@ impl PrecompileOutput {
         }
     }
 
+    /// Returns new precompile output with the given gas used and fixed-size output.
+    ///
+    /// Size of the output is checked at compile time by the array length.
+    pub fn from_array<const N: usize>(gas_used: u64, bytes: [u8; N]) -> Self {
+        Self::new(gas_used, Bytes::copy_from_slice(&bytes))
+    }
+
     /// Returns new precompile revert with the given gas used and output bytes.
     pub fn new_reverted(gas_used: u64, bytes: Bytes) -> Self {
         Self {
@@ -68,6 +76,16 @@ impl PrecompileOutput {
 
 /// Crypto operations trait for precompiles.
 pub trait Crypto: Send + Sync + Debug {
+    /// Compute Keccak-256 hash used by precompiles.
+    ///
+    /// State hashing is protocol mandated and always uses [`primitives::keccak256`]: the
+    /// `KECCAK256` opcode, code hashes of deployed and loaded bytecode, `CREATE` and `CREATE2`
+    /// address derivation and EIP-7702 authority recovery.
+    #[inline]
+    fn keccak256(&self, input: &[u8]) -> B256 {
+        primitives::keccak256(input)
+    }
+
     /// Compute SHA-256 hash
     #[inline]
     fn sha256(&self, input: &[u8]) -> [u8; 32] {
@@ -114,14 +132,28 @@ pub trait Crypto: Send + Sync + Debug {
         recid: u8,
         msg: &[u8; 32],
     ) -> Result<[u8; 32], PrecompileError> {
-        crate::secp256k1::ecrecover_bytes(*sig, recid, *msg)
-            .ok_or_else(|| PrecompileError::other("ecrecover failed"))
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "secp256k1", feature = "libsecp256k1", feature = "k256"))] {
+                crate::secp256k1::ecrecover_bytes(*sig, recid, *msg)
+                    .ok_or_else(|| PrecompileError::other("ecrecover failed"))
+            } else {
+                let _ = (sig, recid, msg);
+                Err(PrecompileError::Fatal("secp256k1 backend is not enabled".into()))
+            }
+        }
     }
 
     /// Modular exponentiation.
     #[inline]
     fn modexp(&self, base: &[u8], exp: &[u8], modulus: &[u8]) -> Result<Vec<u8>, PrecompileError> {
-        Ok(crate::modexp::modexp(base, exp, modulus))
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "gmp", feature = "aurora-engine-modexp"))] {
+                Ok(crate::modexp::modexp(base, exp, modulus))
+            } else {
+                let _ = (base, exp, modulus);
+                Err(PrecompileError::Fatal("modexp backend is not enabled".into()))
+            }
+        }
     }
 
     /// Blake2 compression function.
@@ -131,9 +163,19 @@ pub trait Crypto: Send + Sync + Debug {
     }
 
     /// secp256r1 (P-256) signature verification.
+    ///
+    /// Without the `p256` feature the default implementation rejects every signature, custom
+    /// providers have to implement it.
     #[inline]
     fn secp256r1_verify_signature(&self, msg: &[u8; 32], sig: &[u8; 64], pk: &[u8; 64]) -> bool {
-        crate::secp256r1::verify_signature(*msg, *sig, *pk).is_some()
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "p256")] {
+                crate::secp256r1::verify_signature(*msg, *sig, *pk).is_some()
+            } else {
+                let _ = (msg, sig, pk);
+                false
+            }
+        }
     }
 
     /// KZG point evaluation.
@@ -153,6 +195,27 @@ pub trait Crypto: Send + Sync + Debug {
         Ok(())
     }
 
+    /// KZG proof verification of an EIP-4844 blob against its commitment.
+    #[inline]
+    fn verify_blob_kzg_proof(
+        &self,
+        blob: &[u8; BYTES_PER_BLOB],
+        commitment: &[u8; 48],
+        proof: &[u8; 48],
+    ) -> Result<(), PrecompileError> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))] {
+                if !crate::kzg_point_evaluation::verify_blob_kzg_proof(blob, commitment, proof) {
+                    return Err(PrecompileError::BlobVerifyKzgProofFailed);
+                }
+                Ok(())
+            } else {
+                let _ = (blob, commitment, proof);
+                Err(PrecompileError::Fatal("c-kzg feature is not enabled".into()))
+            }
+        }
+    }
+
     /// BLS12-381 G1 addition (returns 96-byte unpadded G1 point)
     fn bls12_381_g1_add(&self, a: G1Point, b: G1Point) -> Result<[u8; 96], PrecompileError> {
         crate::bls12_381::crypto_backend::p1_add_affine_bytes(a, b)
@@ -196,6 +259,36 @@ pub trait Crypto: Send + Sync + Debug {
     fn bls12_381_fp2_to_g2(&self, fp2: ([u8; 48], [u8; 48])) -> Result<[u8; 192], PrecompileError> {
         crate::bls12_381::crypto_backend::map_fp2_to_g2_bytes(&fp2.0, &fp2.1)
     }
+
+    /// Falcon-512 signature verification.
+    ///
+    /// There is no default verifier, the call fails unless overridden.
+    fn falcon512_verify(
+        &self,
+        message: &[u8],
+        public_key: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, PrecompileError> {
+        let _ = (message, public_key, signature);
+        Err(PrecompileError::other(
+            "Falcon-512 verifier is not installed",
+        ))
+    }
+
+    /// ML-DSA-44 signature verification.
+    ///
+    /// There is no default verifier, the call fails unless overridden.
+    fn ml_dsa44_verify(
+        &self,
+        message: &[u8],
+        public_key: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, PrecompileError> {
+        let _ = (message, public_key, signature);
+        Err(PrecompileError::other(
+            "ML-DSA-44 verifier is not installed",
+        ))
+    }
 }
 
 /// Precompile function type. Takes input, gas limit, and crypto implementation and returns precompile result.
@@ -203,6 +296,7 @@ pub type PrecompileFn = fn(&[u8], u64) -> PrecompileResult;
 
 /// Precompile error type.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrecompileError {
     /// out of gas is the main error. Others are here just for completeness
     OutOfGas,
@@ -231,6 +325,14 @@ pub enum PrecompileError {
     BlobMismatchedVersion,
     /// The proof verification failed
     BlobVerifyKzgProofFailed,
+    /// Stateful precompile tried to change the state in a static call
+    StateChangeDuringStaticCall,
+    /// The input length of the generic elliptic curve precompile is not exactly 288 bytes
+    EcInvalidInputLength,
+    /// Invalid parameters of the generic elliptic curve
+    EcInvalidCurve,
+    /// Point is not on the generic elliptic curve
+    EcPointNotOnCurve,
     /// Fatal error with a custom error message
     Fatal(String),
     /// Catch-all variant for other errors
@@ -247,6 +349,31 @@ impl PrecompileError {
     pub fn is_oog(&self) -> bool {
         matches!(self, Self::OutOfGas)
     }
+
+    /// Returns stable numeric code of the error, in `4000..5000` range.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::OutOfGas => 4001,
+            Self::Blake2WrongLength => 4002,
+            Self::Blake2WrongFinalIndicatorFlag => 4003,
+            Self::ModexpExpOverflow => 4004,
+            Self::ModexpBaseOverflow => 4005,
+            Self::ModexpModOverflow => 4006,
+            Self::ModexpEip7823LimitSize => 4007,
+            Self::Bn254FieldPointNotAMember => 4008,
+            Self::Bn254AffineGFailedToCreate => 4009,
+            Self::Bn254PairLength => 4010,
+            Self::BlobInvalidInputLength => 4011,
+            Self::BlobMismatchedVersion => 4012,
+            Self::BlobVerifyKzgProofFailed => 4013,
+            Self::Fatal(_) => 4014,
+            Self::Other(_) => 4015,
+            Self::StateChangeDuringStaticCall => 4016,
+            Self::EcInvalidInputLength => 4017,
+            Self::EcInvalidCurve => 4018,
+            Self::EcPointNotOnCurve => 4019,
+        }
+    }
 }
 
 impl core::error::Error for PrecompileError {}
@@ -267,6 +394,10 @@ impl fmt::Display for PrecompileError {
             Self::BlobInvalidInputLength => "invalid blob input length",
             Self::BlobMismatchedVersion => "mismatched blob version",
             Self::BlobVerifyKzgProofFailed => "verifying blob kzg proof failed",
+            Self::StateChangeDuringStaticCall => "state change during static call",
+            Self::EcInvalidInputLength => "invalid elliptic curve input length",
+            Self::EcInvalidCurve => "invalid elliptic curve parameters",
+            Self::EcPointNotOnCurve => "point is not on the elliptic curve",
             Self::Fatal(s) => s,
             Self::Other(s) => s,
         };
@@ -279,3 +410,51 @@ impl fmt::Display for PrecompileError {
 pub struct DefaultCrypto;
 
 impl Crypto for DefaultCrypto {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors() -> [(PrecompileError, u32); 19] {
+        use PrecompileError::*;
+        [
+            (OutOfGas, 4001),
+            (Blake2WrongLength, 4002),
+            (Blake2WrongFinalIndicatorFlag, 4003),
+            (ModexpExpOverflow, 4004),
+            (ModexpBaseOverflow, 4005),
+            (ModexpModOverflow, 4006),
+            (ModexpEip7823LimitSize, 4007),
+            (Bn254FieldPointNotAMember, 4008),
+            (Bn254AffineGFailedToCreate, 4009),
+            (Bn254PairLength, 4010),
+            (BlobInvalidInputLength, 4011),
+            (BlobMismatchedVersion, 4012),
+            (BlobVerifyKzgProofFailed, 4013),
+            (Fatal("fatal".into()), 4014),
+            (Other("other".into()), 4015),
+            (StateChangeDuringStaticCall, 4016),
+            (EcInvalidInputLength, 4017),
+            (EcInvalidCurve, 4018),
+            (EcPointNotOnCurve, 4019),
+        ]
+    }
+
+    #[test]
+    fn error_codes_are_stable() {
+        for (error, code) in errors() {
+            assert_eq!(error.code(), code, "{error:?}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn error_serde_roundtrip() {
+        for (error, code) in errors() {
+            let json = serde_json::to_string(&error).unwrap();
+            let decoded: PrecompileError = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.code(), code);
+            assert_eq!(decoded, error);
+        }
+    }
+}
@@ -5,12 +5,12 @@ use crate::{
 };
 cfg_if::cfg_if! {
     if #[cfg(feature = "c-kzg")] {
-        use c_kzg::{Bytes32, Bytes48};
+        use c_kzg::{Blob, Bytes32, Bytes48};
     } else if #[cfg(feature = "kzg-rs")] {
-        use kzg_rs::{Bytes32, Bytes48, KzgProof};
+        use kzg_rs::{Blob, Bytes32, Bytes48, KzgProof};
     }
 }
-use primitives::hex_literal::hex;
+use primitives::{hex_literal::hex, Bytes};
 
 /// KZG point evaluation precompile, containing address and function to run.
 pub const POINT_EVALUATION: PrecompileWithAddress = PrecompileWithAddress(ADDRESS, run);
@@ -24,6 +24,8 @@ pub const GAS_COST: u64 = 50_000;
 /// Versioned hash version for KZG.
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
 
+pub use primitives::eip4844::BYTES_PER_BLOB;
+
 /// `U256(FIELD_ELEMENTS_PER_BLOB).to_be_bytes() ++ BLS_MODULUS.to_bytes32()`
 pub const RETURN_VALUE: &[u8; 64] = &hex!(
     "0000000000000000000000000000000000000000000000000000000000001000"
@@ -63,7 +65,10 @@ pub fn run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     crypto().verify_kzg_proof(z, y, commitment, proof)?;
 
     // Return FIELD_ELEMENTS_PER_BLOB and BLS_MODULUS as padded 32 byte big endian values
-    Ok(PrecompileOutput::new(GAS_COST, RETURN_VALUE.into()))
+    Ok(PrecompileOutput::new(
+        GAS_COST,
+        Bytes::from_static(RETURN_VALUE),
+    ))
 }
 
 /// `VERSIONED_HASH_VERSION_KZG ++ sha256(commitment)[1..]`
@@ -94,6 +99,29 @@ pub fn verify_kzg_proof(
     }
 }
 
+/// Verify KZG proof of a blob against its commitment.
+#[inline]
+pub fn verify_blob_kzg_proof(
+    blob: &[u8; BYTES_PER_BLOB],
+    commitment: &[u8; 48],
+    proof: &[u8; 48],
+) -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "c-kzg")] {
+            let kzg_settings = c_kzg::ethereum_kzg_settings(8);
+            let blob = Blob::from(*blob);
+            kzg_settings.verify_blob_kzg_proof(&blob, as_bytes48(commitment), as_bytes48(proof)).unwrap_or(false)
+        } else if #[cfg(feature = "kzg-rs")] {
+            let env = kzg_rs::EnvKzgSettings::default();
+            let kzg_settings = env.get();
+            let Ok(blob) = Blob::from_slice(blob) else {
+                return false;
+            };
+            KzgProof::verify_blob_kzg_proof(blob, as_bytes48(commitment), as_bytes48(proof), kzg_settings).unwrap_or(false)
+        }
+    }
+}
+
 /// Convert a slice to an array of a specific size.
 #[inline]
 #[track_caller]
@@ -141,4 +169,17 @@ mod tests {
         assert_eq!(output.gas_used, gas);
         assert_eq!(output.bytes[..], expected_output);
     }
+
+    #[test]
+    fn blob_proof_test() {
+        // Commitment and proof of the zero blob are the point at infinity.
+        let blob = [0u8; BYTES_PER_BLOB];
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        assert!(verify_blob_kzg_proof(&blob, &infinity, &infinity));
+
+        let mut blob = blob;
+        blob[31] = 1;
+        assert!(!verify_blob_kzg_proof(&blob, &infinity, &infinity));
+    }
 }
@@ -8,6 +8,7 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+pub mod backend;
 pub mod blake2;
 pub mod bls12_381;
 pub mod bls12_381_const;
@@ -18,22 +19,24 @@ pub mod identity;
 pub mod interface;
 #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub mod kzg_point_evaluation;
+pub mod merkle;
 pub mod modexp;
+#[cfg(feature = "pq-experimental")]
+pub mod pq;
+#[cfg(feature = "rip7696")]
+pub mod rip7696;
 pub mod secp256k1;
 pub mod secp256r1;
+pub mod stateful;
 pub mod utilities;
 
+pub use backend::Backend;
 pub use interface::*;
+pub use stateful::{StatefulCall, StatefulPrecompile};
 
 // silence arkworks lint as bn impl will be used as default if both are enabled.
-cfg_if::cfg_if! {
-    if #[cfg(feature = "bn")]{
-        use ark_bn254 as _;
-        use ark_ff as _;
-        use ark_ec as _;
-        use ark_serialize as _;
-    }
-}
+#[cfg(all(feature = "arkworks-bn254", feature = "bn"))]
+use ark_bn254 as _;
 
 use arrayref as _;
 
@@ -42,19 +45,25 @@ use arrayref as _;
 use kzg_rs as _;
 
 // silence arkworks-bls12-381 lint as blst will be used as default if both are enabled.
-cfg_if::cfg_if! {
-    if #[cfg(feature = "blst")]{
-        use ark_bls12_381 as _;
-        use ark_ff as _;
-        use ark_ec as _;
-        use ark_serialize as _;
-    }
-}
+#[cfg(all(feature = "arkworks-bls12-381", feature = "blst"))]
+use ark_bls12_381 as _;
+
+// silence shared arkworks crates if neither arkworks backend is used.
+#[cfg(all(
+    any(feature = "arkworks-bn254", feature = "arkworks-bls12-381"),
+    not(all(feature = "arkworks-bn254", not(feature = "bn"))),
+    not(all(feature = "arkworks-bls12-381", not(feature = "blst")))
+))]
+use {ark_ec as _, ark_ff as _, ark_serialize as _};
 
 // silence aurora-engine-modexp if gmp is enabled
-#[cfg(feature = "gmp")]
+#[cfg(all(feature = "aurora-engine-modexp", feature = "gmp"))]
 use aurora_engine_modexp as _;
 
+// silence k256 if a faster secp256k1 backend is enabled
+#[cfg(all(feature = "k256", any(feature = "secp256k1", feature = "libsecp256k1")))]
+use k256 as _;
+
 use cfg_if::cfg_if;
 use core::hash::Hash;
 use primitives::{hardfork::SpecId, Address, HashMap, HashSet, OnceLock};
@@ -263,6 +272,58 @@ impl Precompiles {
         self.inner.extend(items.into_iter().map(|p| (p.0, p.1)));
     }
 
+    /// Inserts the precompile at the address, returns the precompile it overrides.
+    #[inline]
+    pub fn insert(&mut self, address: Address, precompile: PrecompileFn) -> Option<PrecompileFn> {
+        self.addresses.insert(address);
+        self.inner.insert(address, precompile)
+    }
+
+    /// Removes the precompile at the address.
+    #[inline]
+    pub fn remove(&mut self, address: &Address) -> Option<PrecompileFn> {
+        self.addresses.remove(address);
+        self.inner.remove(address)
+    }
+
+    /// Inserts the precompile at the address and returns the set.
+    ///
+    /// Used to build a custom set on top of a static one, e.g.
+    /// `Precompiles::cancun().clone().with_precompile(address, precompile)`.
+    pub fn with_precompile(mut self, address: Address, precompile: PrecompileFn) -> Self {
+        self.insert(address, precompile);
+        self
+    }
+
+    /// Removes the precompile at the address and returns the set.
+    pub fn without_precompile(mut self, address: &Address) -> Self {
+        self.remove(address);
+        self
+    }
+
+    /// Extends the precompiles with the given precompiles without overriding any address.
+    ///
+    /// Fails if an address is already used in the set, is repeated in `other` or is used by a
+    /// precompile of a later spec, see [`PrecompileSpecId::introduced_at`]. Nothing is added if
+    /// an error is returned.
+    pub fn try_extend(
+        &mut self,
+        other: impl IntoIterator<Item = PrecompileWithAddress>,
+    ) -> Result<(), PrecompileCollision> {
+        let items: Vec<PrecompileWithAddress> = other.into_iter().collect::<Vec<_>>();
+        let mut added: HashSet<Address> = HashSet::default();
+        for address in items.iter().map(|p| *p.address()) {
+            if self.contains(&address) || !added.insert(address) {
+                return Err(PrecompileCollision::Existing(address));
+            }
+            if let Some(spec) = PrecompileSpecId::introduced_at(&address) {
+                return Err(PrecompileCollision::Scheduled { address, spec });
+            }
+        }
+        self.extend(items);
+        Ok(())
+    }
+
     /// Returns complement of `other` in `self`.
     ///
     /// Two entries are considered equal if the precompile addresses are equal.
@@ -298,6 +359,33 @@ impl Precompiles {
     }
 }
 
+/// Address collision found by [`Precompiles::try_extend`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrecompileCollision {
+    /// Address is already used by a precompile in the set or added more than once.
+    Existing(Address),
+    /// Address is used by a precompile introduced in the given spec.
+    Scheduled {
+        /// Address of the precompile.
+        address: Address,
+        /// Spec that introduced the precompile.
+        spec: PrecompileSpecId,
+    },
+}
+
+impl core::fmt::Display for PrecompileCollision {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Existing(address) => write!(f, "precompile address {address} is already used"),
+            Self::Scheduled { address, spec } => {
+                write!(f, "precompile address {address} is used since {spec:?}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PrecompileCollision {}
+
 /// Precompile with address and function.
 #[derive(Clone, Debug)]
 pub struct PrecompileWithAddress(pub Address, pub PrecompileFn);
@@ -371,6 +459,24 @@ impl From<SpecId> for PrecompileSpecId {
 }
 
 impl PrecompileSpecId {
+    /// All specs in activation order.
+    pub const ALL: [Self; 7] = [
+        Self::HOMESTEAD,
+        Self::BYZANTIUM,
+        Self::ISTANBUL,
+        Self::BERLIN,
+        Self::CANCUN,
+        Self::PRAGUE,
+        Self::OSAKA,
+    ];
+
+    /// Returns the first spec with a precompile at the given address.
+    pub fn introduced_at(address: &Address) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|spec| Precompiles::new(*spec).contains(address))
+    }
+
     /// Returns the appropriate precompile Spec for the primitive [SpecId].
     pub const fn from_spec_id(spec_id: primitives::hardfork::SpecId) -> Self {
         use primitives::hardfork::SpecId::*;
@@ -383,7 +489,7 @@ impl PrecompileSpecId {
             BERLIN | LONDON | ARROW_GLACIER | GRAY_GLACIER | MERGE | SHANGHAI => Self::BERLIN,
             CANCUN => Self::CANCUN,
             PRAGUE => Self::PRAGUE,
-            OSAKA => Self::OSAKA,
+            OSAKA | AMSTERDAM => Self::OSAKA,
         }
     }
 }
@@ -403,7 +509,10 @@ pub const fn u64_to_address(x: u64) -> Address {
 
 #[cfg(test)]
 mod test {
-    use crate::Precompiles;
+    use crate::{
+        identity, secp256r1, u64_to_address, PrecompileCollision, PrecompileSpecId,
+        PrecompileWithAddress, Precompiles,
+    };
 
     #[test]
     fn test_difference_precompile_sets() {
@@ -417,4 +526,45 @@ mod test {
 
         assert_eq!(intersection.len(), 4)
     }
+
+    #[test]
+    fn test_try_extend_collisions() {
+        let custom =
+            |address| PrecompileWithAddress(u64_to_address(address), identity::identity_run);
+        let mut precompiles = Precompiles::berlin().clone();
+
+        assert_eq!(
+            precompiles.try_extend([custom(0x04)]),
+            Err(PrecompileCollision::Existing(u64_to_address(0x04)))
+        );
+        // RIP-7212 P256VERIFY address is used since Osaka.
+        assert_eq!(
+            precompiles.try_extend([custom(0x1000), custom(0x100)]),
+            Err(PrecompileCollision::Scheduled {
+                address: *secp256r1::P256VERIFY.address(),
+                spec: PrecompileSpecId::OSAKA
+            })
+        );
+        assert!(!precompiles.contains(&u64_to_address(0x1000)));
+
+        assert_eq!(precompiles.try_extend([custom(0x1000)]), Ok(()));
+        assert!(precompiles.contains(&u64_to_address(0x1000)));
+    }
+
+    #[test]
+    fn test_with_precompile() {
+        let custom = u64_to_address(0x1000);
+        let ecrecover = u64_to_address(0x01);
+        let precompiles = Precompiles::berlin()
+            .clone()
+            .with_precompile(custom, identity::identity_run)
+            .with_precompile(ecrecover, identity::identity_run)
+            .without_precompile(&u64_to_address(0x02));
+
+        assert_eq!(precompiles.len(), 9);
+        assert!(precompiles.contains(&custom));
+        assert!(!precompiles.addresses_set().contains(&u64_to_address(0x02)));
+        let output = precompiles.get(&ecrecover).unwrap()(&[1, 2], 100).unwrap();
+        assert_eq!(output.bytes.as_ref(), [1, 2]);
+    }
 }
@@ -0,0 +1,437 @@
+//! # Merkle proof precompiles
+//!
+//! Verification of keccak256 based Merkle inclusion proofs:
+//! * [`BINARY_PROOF`] verifies binary Merkle tree proofs with sorted pair hashing, the scheme
+//!   used by most Solidity Merkle proof libraries.
+//! * [`MPT_PROOF`] verifies Merkle-Patricia trie proofs, as returned by `eth_getProof`.
+//!
+//! Gas is proportional to the proof depth. These precompiles are not part of any hardfork.
+use crate::{
+    crypto, u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult,
+    PrecompileWithAddress,
+};
+use primitives::{Bytes, B256};
+use std::vec::Vec;
+
+/// Address of the binary Merkle proof precompile.
+pub const BINARY_PROOF_ADDRESS: u64 = 0x0c80;
+/// Address of the Merkle-Patricia trie proof precompile.
+pub const MPT_PROOF_ADDRESS: u64 = 0x0c81;
+
+/// Base gas fee of the binary Merkle proof precompile.
+pub const BINARY_PROOF_BASE_GAS_FEE: u64 = 100;
+/// Gas fee of each sibling of the binary Merkle proof, a keccak256 of 64 bytes.
+pub const BINARY_PROOF_PER_SIBLING_GAS_FEE: u64 = 50;
+
+/// Base gas fee of the Merkle-Patricia trie proof precompile.
+pub const MPT_PROOF_BASE_GAS_FEE: u64 = 200;
+/// Gas fee of each node of the Merkle-Patricia trie proof, for hashing and decoding.
+pub const MPT_PROOF_PER_NODE_GAS_FEE: u64 = 100;
+/// Gas fee of each word of the Merkle-Patricia trie proof nodes.
+pub const MPT_PROOF_PER_WORD_GAS_FEE: u64 = 6;
+
+/// Returns the Merkle proof precompiles with their addresses.
+pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+    [BINARY_PROOF, MPT_PROOF].into_iter()
+}
+
+/// Binary Merkle proof precompile.
+pub const BINARY_PROOF: PrecompileWithAddress =
+    PrecompileWithAddress(u64_to_address(BINARY_PROOF_ADDRESS), binary_proof_run);
+
+/// Merkle-Patricia trie proof precompile.
+pub const MPT_PROOF: PrecompileWithAddress =
+    PrecompileWithAddress(u64_to_address(MPT_PROOF_ADDRESS), mpt_proof_run);
+
+/// Verifies a binary Merkle tree proof.
+///
+/// Parent nodes are `keccak256(min(a, b) ++ max(a, b))` of their children.
+///
+/// The input is encoded as follows:
+///
+/// | root | leaf | siblings |
+/// | :--: | :--: | :------: |
+/// |  32  |  32  |   32*k   |
+///
+/// Output is 32 bytes where the last byte is 0x01 if the leaf is included and 0x00 otherwise.
+pub fn binary_proof_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    if input.len() < 64 || !input.len().is_multiple_of(32) {
+        return Err(PrecompileError::Other(format!(
+            "Binary Merkle proof input length should be 64 plus a multiple of 32, was {}",
+            input.len()
+        )));
+    }
+    let depth = (input.len() - 64) / 32;
+    let gas_used = BINARY_PROOF_BASE_GAS_FEE + depth as u64 * BINARY_PROOF_PER_SIBLING_GAS_FEE;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let root = B256::from_slice(&input[..32]);
+    let mut node = B256::from_slice(&input[32..64]);
+    let mut pair = [0u8; 64];
+    for sibling in input[64..].chunks_exact(32) {
+        let (left, right) = if node.as_slice() <= sibling {
+            (node.as_slice(), sibling)
+        } else {
+            (sibling, node.as_slice())
+        };
+        pair[..32].copy_from_slice(left);
+        pair[32..].copy_from_slice(right);
+        node = crypto().keccak256(&pair);
+    }
+
+    Ok(PrecompileOutput::new(
+        gas_used,
+        B256::with_last_byte((node == root) as u8).into(),
+    ))
+}
+
+/// Verifies a Merkle-Patricia trie proof.
+///
+/// The key is the path in the trie, secure tries like the state and storage tries expect the
+/// keccak256 hash of the address or slot. Lengths are 32 byte big-endian integers and nodes are
+/// RLP encoded, starting with the root node.
+///
+/// The input is encoded as follows:
+///
+/// | root | key length | key | node length | node | ... | node length | node |
+/// | :--: | :--------: | :-: | :---------: | :--: | :-: | :---------: | :--: |
+/// |  32  |     32     |  n  |     32      |  m   | ... |     32      |  m   |
+///
+/// Output is the value stored at the key, or empty bytes if the proof shows the key is not in
+/// the trie. Proofs that don't match the root or are malformed fail the call.
+pub fn mpt_proof_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let root = B256::from_slice(input.get(..32).ok_or_else(|| mpt_error("missing root"))?);
+    let key_len = read_length(input, 32)?;
+    let key = input
+        .get(64..)
+        .and_then(|rest| rest.get(..key_len))
+        .ok_or_else(|| mpt_error("missing key"))?;
+
+    let mut nodes = Vec::new();
+    let mut offset = 64 + key_len;
+    let mut nodes_len = 0u64;
+    while offset < input.len() {
+        let node_len = read_length(input, offset)?;
+        let node = input
+            .get(offset + 32..)
+            .and_then(|rest| rest.get(..node_len))
+            .ok_or_else(|| mpt_error("node is out of input bounds"))?;
+        nodes.push(node);
+        nodes_len += node_len as u64;
+        offset += 32 + node_len;
+    }
+
+    let gas_used = MPT_PROOF_BASE_GAS_FEE
+        + nodes.len() as u64 * MPT_PROOF_PER_NODE_GAS_FEE
+        + nodes_len.div_ceil(32) * MPT_PROOF_PER_WORD_GAS_FEE;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let value = verify_mpt_proof(&root, key, &nodes)?;
+    Ok(PrecompileOutput::new(
+        gas_used,
+        value.map(Bytes::copy_from_slice).unwrap_or_default(),
+    ))
+}
+
+fn mpt_error(reason: &str) -> PrecompileError {
+    PrecompileError::Other(format!("Invalid Merkle-Patricia proof: {reason}"))
+}
+
+/// Reads a 32 byte big-endian length at the offset.
+fn read_length(input: &[u8], offset: usize) -> Result<usize, PrecompileError> {
+    let word = input
+        .get(offset..offset + 32)
+        .ok_or_else(|| mpt_error("missing length"))?;
+    let (high, low) = word.split_at(24);
+    if high.iter().any(|&b| b != 0) {
+        return Err(mpt_error("length overflow"));
+    }
+    usize::try_from(u64::from_be_bytes(low.try_into().unwrap()))
+        .map_err(|_| mpt_error("length overflow"))
+}
+
+/// Walks the proof from the root following the key.
+///
+/// Returns the value at the key, or `None` if the proof ends in a node that excludes the key.
+fn verify_mpt_proof<'a>(
+    root: &'a B256,
+    key: &[u8],
+    proof: &[&'a [u8]],
+) -> Result<Option<&'a [u8]>, PrecompileError> {
+    let path: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut path = path.as_slice();
+    let mut proof = proof.iter();
+
+    // Reference to the next node, either its hash or the node itself when embedded in the parent.
+    let mut reference = RlpItem {
+        is_list: false,
+        payload: root.as_slice(),
+        raw: root.as_slice(),
+    };
+    loop {
+        let node = if reference.is_list {
+            reference.raw
+        } else if reference.payload.is_empty() {
+            return finish(proof, None);
+        } else if reference.payload.len() == 32 {
+            let node = proof.next().ok_or_else(|| mpt_error("missing node"))?;
+            if crypto().keccak256(node).as_slice() != reference.payload {
+                return Err(mpt_error("node hash mismatch"));
+            }
+            *node
+        } else {
+            return Err(mpt_error("invalid node reference"));
+        };
+
+        let items = decode_list(node)?;
+        match items.as_slice() {
+            [branch @ .., value] if branch.len() == 16 => {
+                let Some((&nibble, rest)) = path.split_first() else {
+                    let value = (!value.payload.is_empty()).then_some(value.payload);
+                    return finish(proof, value);
+                };
+                reference = branch[nibble as usize];
+                path = rest;
+            }
+            [encoded_path, next] => {
+                let (is_leaf, node_path) = decode_compact_path(encoded_path.payload)?;
+                let Some(rest) = path.strip_prefix(node_path.as_slice()) else {
+                    return finish(proof, None);
+                };
+                if is_leaf {
+                    let value = rest.is_empty().then_some(next.payload);
+                    return finish(proof, value);
+                }
+                reference = *next;
+                path = rest;
+            }
+            _ => return Err(mpt_error("invalid node")),
+        }
+    }
+}
+
+/// Checks that all proof nodes were used.
+fn finish(
+    mut proof: impl Iterator,
+    value: Option<&[u8]>,
+) -> Result<Option<&[u8]>, PrecompileError> {
+    if proof.next().is_some() {
+        return Err(mpt_error("unused nodes"));
+    }
+    Ok(value)
+}
+
+/// Decodes hex-prefix encoded path of leaf and extension nodes.
+fn decode_compact_path(encoded: &[u8]) -> Result<(bool, Vec<u8>), PrecompileError> {
+    let (&first, rest) = encoded
+        .split_first()
+        .ok_or_else(|| mpt_error("empty node path"))?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(mpt_error("invalid node path flag"));
+    }
+    let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        path.push(first & 0x0f);
+    }
+    path.extend(rest.iter().flat_map(|b| [b >> 4, b & 0x0f]));
+    Ok((flag & 2 == 2, path))
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RlpItem<'a> {
+    is_list: bool,
+    payload: &'a [u8],
+    raw: &'a [u8],
+}
+
+/// Decodes the RLP list that is the whole input.
+fn decode_list(input: &[u8]) -> Result<Vec<RlpItem<'_>>, PrecompileError> {
+    let (item, rest) = decode_item(input)?;
+    if !item.is_list || !rest.is_empty() {
+        return Err(mpt_error("node is not an RLP list"));
+    }
+    let mut items = Vec::with_capacity(17);
+    let mut payload = item.payload;
+    while !payload.is_empty() {
+        let (item, rest) = decode_item(payload)?;
+        items.push(item);
+        payload = rest;
+    }
+    Ok(items)
+}
+
+/// Decodes the first RLP item of the input, returning it and the remaining input.
+fn decode_item(input: &[u8]) -> Result<(RlpItem<'_>, &[u8]), PrecompileError> {
+    let invalid = || mpt_error("invalid RLP");
+    let &prefix = input.first().ok_or_else(invalid)?;
+    let (is_list, header_len, payload_len) = match prefix {
+        0x00..=0x7f => (false, 0, 1),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xb8..=0xbf | 0xf8..=0xff => {
+            let len_of_len = (prefix & 0x07) as usize + 1;
+            let len_bytes = input.get(1..1 + len_of_len).ok_or_else(invalid)?;
+            if len_bytes[0] == 0 {
+                return Err(invalid());
+            }
+            let len = len_bytes.iter().fold(0u64, |len, &b| (len << 8) | b as u64);
+            let len = usize::try_from(len).map_err(|_| invalid())?;
+            (prefix >= 0xf8, 1 + len_of_len, len)
+        }
+    };
+    let end = header_len
+        .checked_add(payload_len)
+        .filter(|&end| end <= input.len())
+        .ok_or_else(invalid)?;
+    let item = RlpItem {
+        is_list,
+        payload: &input[header_len..end],
+        raw: &input[..end],
+    };
+    Ok((item, &input[end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use primitives::keccak256;
+
+    fn rlp_string(bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            out.push(bytes[0]);
+        } else if bytes.len() < 56 {
+            out.push(0x80 + bytes.len() as u8);
+        } else {
+            out.extend([0xb8, bytes.len() as u8]);
+        }
+        if !(bytes.len() == 1 && bytes[0] < 0x80) {
+            out.extend_from_slice(bytes);
+        }
+        out
+    }
+
+    fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        let mut out = if payload.len() < 56 {
+            vec![0xc0 + payload.len() as u8]
+        } else {
+            vec![0xf8, payload.len() as u8]
+        };
+        out.extend(payload);
+        out
+    }
+
+    fn mpt_input(root: B256, key: &[u8], nodes: &[&[u8]]) -> Vec<u8> {
+        let mut input = root.to_vec();
+        input.extend(B256::left_padding_from(&(key.len() as u64).to_be_bytes()));
+        input.extend_from_slice(key);
+        for node in nodes {
+            input.extend(B256::left_padding_from(&(node.len() as u64).to_be_bytes()));
+            input.extend_from_slice(node);
+        }
+        input
+    }
+
+    #[test]
+    fn test_binary_proof() {
+        let hash_pair = |a: B256, b: B256| {
+            let (a, b) = if a <= b { (a, b) } else { (b, a) };
+            keccak256([a.as_slice(), b.as_slice()].concat())
+        };
+        let leaves = [
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        ];
+        let root = hash_pair(hash_pair(leaves[0], leaves[1]), leaves[2]);
+
+        let input = [root, leaves[1], leaves[0], leaves[2]].concat();
+        let gas = BINARY_PROOF_BASE_GAS_FEE + 2 * BINARY_PROOF_PER_SIBLING_GAS_FEE;
+        let output = binary_proof_run(&input, gas).unwrap();
+        assert_eq!(output.gas_used, gas);
+        assert_eq!(output.bytes, Bytes::from(B256::with_last_byte(1)));
+
+        let input = [root, leaves[2], leaves[0], leaves[1]].concat();
+        let output = binary_proof_run(&input, gas).unwrap();
+        assert_eq!(output.bytes, Bytes::from(B256::ZERO));
+
+        assert_eq!(
+            binary_proof_run(&input, gas - 1),
+            Err(PrecompileError::OutOfGas)
+        );
+        assert!(binary_proof_run(&input[..70], gas).is_err());
+    }
+
+    #[test]
+    fn test_mpt_proof() {
+        // Two keys sharing the first nibble: extension -> branch -> leaves.
+        let key_a = B256::repeat_byte(0x12);
+        let key_b = B256::repeat_byte(0x13);
+        let value_a = vec![0xaa; 40];
+        let value_b = vec![0xbb; 40];
+
+        // Leaves hold the path after the first two nibbles, even length leaf prefix is 0x20.
+        let leaf = |key: &B256, value: &[u8]| {
+            let path = [&[0x20][..], &key[1..]].concat();
+            rlp_list(&[rlp_string(&path), rlp_string(value)])
+        };
+        let leaf_a = leaf(&key_a, &value_a);
+        let leaf_b = leaf(&key_b, &value_b);
+
+        let mut branch_items = vec![rlp_string(&[]); 17];
+        branch_items[2] = rlp_string(keccak256(&leaf_a).as_slice());
+        branch_items[3] = rlp_string(keccak256(&leaf_b).as_slice());
+        let branch = rlp_list(&branch_items);
+
+        // Odd length extension with the single nibble 0x1.
+        let extension = rlp_list(&[
+            rlp_string(&[0x11]),
+            rlp_string(keccak256(&branch).as_slice()),
+        ]);
+        let root = keccak256(&extension);
+
+        let input = mpt_input(root, key_a.as_slice(), &[&extension, &branch, &leaf_a]);
+        let output = mpt_proof_run(&input, u64::MAX).unwrap();
+        assert_eq!(output.bytes, Bytes::from(value_a.clone()));
+
+        let input = mpt_input(root, key_b.as_slice(), &[&extension, &branch, &leaf_b]);
+        let output = mpt_proof_run(&input, u64::MAX).unwrap();
+        assert_eq!(output.bytes, Bytes::from(value_b));
+
+        // Key with a nibble that has no branch child is proven absent.
+        let key_c = B256::repeat_byte(0x14);
+        let input = mpt_input(root, key_c.as_slice(), &[&extension, &branch]);
+        let output = mpt_proof_run(&input, u64::MAX).unwrap();
+        assert!(output.bytes.is_empty());
+
+        // Wrong leaf for the key.
+        let input = mpt_input(root, key_b.as_slice(), &[&extension, &branch, &leaf_a]);
+        assert!(mpt_proof_run(&input, u64::MAX).is_err());
+
+        // Wrong root.
+        let input = mpt_input(
+            B256::ZERO,
+            key_a.as_slice(),
+            &[&extension, &branch, &leaf_a],
+        );
+        assert!(mpt_proof_run(&input, u64::MAX).is_err());
+
+        // Gas is proportional to the proof.
+        let input = mpt_input(root, key_a.as_slice(), &[&extension, &branch, &leaf_a]);
+        let nodes_len = (extension.len() + branch.len() + leaf_a.len()) as u64;
+        let gas = MPT_PROOF_BASE_GAS_FEE
+            + 3 * MPT_PROOF_PER_NODE_GAS_FEE
+            + nodes_len.div_ceil(32) * MPT_PROOF_PER_WORD_GAS_FEE;
+        assert_eq!(mpt_proof_run(&input, gas).unwrap().gas_used, gas);
+        assert_eq!(
+            mpt_proof_run(&input, gas - 1),
+            Err(PrecompileError::OutOfGas)
+        );
+    }
+}
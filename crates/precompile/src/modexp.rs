@@ -3,10 +3,11 @@
 use crate::{
     crypto,
     utilities::{left_pad, left_pad_vec, right_pad_vec, right_pad_with_offset},
-    PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
+    PrecompileError, PrecompileOutput, PrecompileResult, PrecompileSpecId, PrecompileWithAddress,
 };
 use core::cmp::{max, min};
 use primitives::{eip7823, Bytes, U256};
+#[cfg(any(feature = "gmp", feature = "aurora-engine-modexp"))]
 use std::vec::Vec;
 
 /// `modexp` precompile with BYZANTIUM gas rules.
@@ -39,7 +40,7 @@ pub(crate) fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
     output
 }
 
-#[cfg(not(feature = "gmp"))]
+#[cfg(all(not(feature = "gmp"), feature = "aurora-engine-modexp"))]
 pub(crate) fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
     aurora_engine_modexp::modexp(base, exponent, modulus)
 }
@@ -68,6 +69,165 @@ pub fn osaka_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     })
 }
 
+/// Gas pricing rules of the modexp precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ModexpPricing {
+    /// [EIP-198](https://eips.ethereum.org/EIPS/eip-198) pricing.
+    Byzantium,
+    /// [EIP-2565](https://eips.ethereum.org/EIPS/eip-2565) pricing.
+    Berlin,
+    /// [EIP-7883](https://eips.ethereum.org/EIPS/eip-7883) pricing and
+    /// [EIP-7823](https://eips.ethereum.org/EIPS/eip-7823) input limits.
+    Osaka,
+}
+
+impl ModexpPricing {
+    /// All pricing rules, from the oldest.
+    pub const ALL: [Self; 3] = [Self::Byzantium, Self::Berlin, Self::Osaka];
+
+    /// Returns the pricing of the spec, `None` if modexp is not enabled in it.
+    pub fn from_spec(spec: PrecompileSpecId) -> Option<Self> {
+        match spec {
+            PrecompileSpecId::HOMESTEAD => None,
+            PrecompileSpecId::BYZANTIUM | PrecompileSpecId::ISTANBUL => Some(Self::Byzantium),
+            PrecompileSpecId::BERLIN | PrecompileSpecId::CANCUN | PrecompileSpecId::PRAGUE => {
+                Some(Self::Berlin)
+            }
+            _ => Some(Self::Osaka),
+        }
+    }
+
+    /// Returns the gas charged for the input with both base and modulus length being zero.
+    pub const fn min_gas(self) -> u64 {
+        match self {
+            Self::Byzantium => 0,
+            Self::Berlin => 200,
+            Self::Osaka => 500,
+        }
+    }
+
+    /// Calculates the gas cost from the lengths and the first 32 bytes of the exponent.
+    pub fn gas_calc(self, base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &U256) -> u64 {
+        match self {
+            Self::Byzantium => byzantium_gas_calc(base_len, exp_len, mod_len, exp_highp),
+            Self::Berlin => berlin_gas_calc(base_len, exp_len, mod_len, exp_highp),
+            Self::Osaka => osaka_gas_calc(base_len, exp_len, mod_len, exp_highp),
+        }
+    }
+}
+
+/// Parsed modexp input.
+///
+/// The format of input is:
+/// `<length_of_BASE> <length_of_EXPONENT> <length_of_MODULUS> <BASE> <EXPONENT> <MODULUS>`
+/// where every length is a 32-byte left-padded integer representing the number of bytes
+/// to be taken up by the next value. Missing bytes of the input are zeroes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ModexpInput {
+    /// Length of the base in bytes.
+    pub base_len: U256,
+    /// Length of the exponent in bytes.
+    pub exp_len: U256,
+    /// Length of the modulus in bytes.
+    pub mod_len: U256,
+    /// Exponent if it is at most 32 bytes long, otherwise its first 32 bytes.
+    pub exp_highp: U256,
+}
+
+impl ModexpInput {
+    /// Length of the header with the lengths.
+    pub const HEADER_LENGTH: usize = 96;
+
+    /// Parses the header and the first 32 bytes of the exponent.
+    pub fn parse(input: &[u8]) -> Self {
+        let base_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 0).into_owned());
+        let exp_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 32).into_owned());
+        let mod_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 64).into_owned());
+
+        // Used to extract ADJUSTED_EXPONENT_LENGTH.
+        let exp_highp_len = min(exp_len.saturating_to::<usize>(), 32);
+        let exp_highp = {
+            let input = input.get(Self::HEADER_LENGTH..).unwrap_or_default();
+            // Get right padded bytes so if data.len is less then exp_len we will get right padded zeroes.
+            let right_padded_highp =
+                right_pad_with_offset::<32>(input, base_len.saturating_to::<usize>());
+            // If exp_len is less then 32 bytes get only exp_len bytes and do left padding.
+            let out = left_pad::<32>(&right_padded_highp[..exp_highp_len]);
+            U256::from_be_bytes(out.into_owned())
+        };
+
+        Self {
+            base_len,
+            exp_len,
+            mod_len,
+            exp_highp,
+        }
+    }
+
+    /// Returns base, exponent and modulus lengths, checked against the limits of EIP-7823 if
+    /// `osaka` is set.
+    ///
+    /// Base and modulus lengths must fit in `usize`, exponent length is saturated as it fails
+    /// the gas calculation if it is too large.
+    pub fn lengths(&self, osaka: bool) -> Result<(usize, usize, usize), PrecompileError> {
+        let base_len =
+            usize::try_from(self.base_len).map_err(|_| PrecompileError::ModexpEip7823LimitSize)?;
+        let mod_len =
+            usize::try_from(self.mod_len).map_err(|_| PrecompileError::ModexpEip7823LimitSize)?;
+        let exp_len = usize::try_from(self.exp_len).unwrap_or(usize::MAX);
+
+        // for EIP-7823 we need to check size of imputs
+        if osaka
+            && (base_len > eip7823::INPUT_SIZE_LIMIT
+                || mod_len > eip7823::INPUT_SIZE_LIMIT
+                || exp_len > eip7823::INPUT_SIZE_LIMIT)
+        {
+            return Err(PrecompileError::ModexpEip7823LimitSize);
+        }
+        Ok((base_len, exp_len, mod_len))
+    }
+
+    /// Returns the gas the precompile charges for the input, or the error it fails with
+    /// regardless of the gas limit.
+    pub fn gas(&self, pricing: ModexpPricing) -> Result<u64, PrecompileError> {
+        let (base_len, exp_len, mod_len) = self.lengths(pricing == ModexpPricing::Osaka)?;
+        if base_len == 0 && mod_len == 0 {
+            return Ok(pricing.min_gas());
+        }
+        Ok(pricing.gas_calc(
+            base_len as u64,
+            exp_len as u64,
+            mod_len as u64,
+            &self.exp_highp,
+        ))
+    }
+}
+
+/// Parsed modexp input and its gas under the pricing of every fork.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModexpGasPreview {
+    /// Parsed input.
+    pub input: ModexpInput,
+    /// Gas with [`ModexpPricing::Byzantium`] pricing.
+    pub byzantium: Result<u64, PrecompileError>,
+    /// Gas with [`ModexpPricing::Berlin`] pricing.
+    pub berlin: Result<u64, PrecompileError>,
+    /// Gas with [`ModexpPricing::Osaka`] pricing.
+    pub osaka: Result<u64, PrecompileError>,
+}
+
+/// Parses the modexp input and calculates its gas with the pricing of every fork, without
+/// executing it.
+pub fn gas_preview(input: &[u8]) -> ModexpGasPreview {
+    let input = ModexpInput::parse(input);
+    ModexpGasPreview {
+        input,
+        byzantium: input.gas(ModexpPricing::Byzantium),
+        berlin: input.gas(ModexpPricing::Berlin),
+        osaka: input.gas(ModexpPricing::Osaka),
+    }
+}
+
 /// Calculate the iteration count for the modexp precompile.
 pub fn calculate_iteration_count<const MULTIPLIER: u64>(exp_length: u64, exp_highp: &U256) -> u64 {
     let mut iteration_count: u64 = 0;
@@ -99,54 +259,24 @@ where
         return Err(PrecompileError::OutOfGas);
     }
 
-    // The format of input is:
-    // <length_of_BASE> <length_of_EXPONENT> <length_of_MODULUS> <BASE> <EXPONENT> <MODULUS>
-    // Where every length is a 32-byte left-padded integer representing the number of bytes
-    // to be taken up by the next value.
-    const HEADER_LENGTH: usize = 96;
-
-    // Extract the header
-    let base_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 0).into_owned());
-    let exp_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 32).into_owned());
-    let mod_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 64).into_owned());
-
-    // Cast base and modulus to usize, it does not make sense to handle larger values
-    let base_len =
-        usize::try_from(base_len).map_err(|_| PrecompileError::ModexpEip7823LimitSize)?;
-    let mod_len = usize::try_from(mod_len).map_err(|_| PrecompileError::ModexpEip7823LimitSize)?;
-    // cast exp len to the max size, it will fail later in gas calculation if it is too large.
-    let exp_len = usize::try_from(exp_len).unwrap_or(usize::MAX);
-
-    // for EIP-7823 we need to check size of imputs
-    if OSAKA
-        && (base_len > eip7823::INPUT_SIZE_LIMIT
-            || mod_len > eip7823::INPUT_SIZE_LIMIT
-            || exp_len > eip7823::INPUT_SIZE_LIMIT)
-    {
-        return Err(PrecompileError::ModexpEip7823LimitSize);
-    }
+    let parsed = ModexpInput::parse(input);
+    let (base_len, exp_len, mod_len) = parsed.lengths(OSAKA)?;
 
     // special case for both base and mod length being 0.
     if base_len == 0 && mod_len == 0 {
         return Ok(PrecompileOutput::new(min_gas, Bytes::new()));
     }
 
-    // Used to extract ADJUSTED_EXPONENT_LENGTH.
-    let exp_highp_len = min(exp_len, 32);
-
     // Throw away the header data as we already extracted lengths.
-    let input = input.get(HEADER_LENGTH..).unwrap_or_default();
-
-    let exp_highp = {
-        // Get right padded bytes so if data.len is less then exp_len we will get right padded zeroes.
-        let right_padded_highp = right_pad_with_offset::<32>(input, base_len);
-        // If exp_len is less then 32 bytes get only exp_len bytes and do left padding.
-        let out = left_pad::<32>(&right_padded_highp[..exp_highp_len]);
-        U256::from_be_bytes(out.into_owned())
-    };
+    let input = input.get(ModexpInput::HEADER_LENGTH..).unwrap_or_default();
 
     // Check if we have enough gas.
-    let gas_cost = calc_gas(base_len as u64, exp_len as u64, mod_len as u64, &exp_highp);
+    let gas_cost = calc_gas(
+        base_len as u64,
+        exp_len as u64,
+        mod_len as u64,
+        &parsed.exp_highp,
+    );
     if gas_cost > gas_limit {
         return Err(PrecompileError::OutOfGas);
     }
@@ -229,7 +359,7 @@ where
     max(MIN_PRICE, gas.saturating_to())
 }
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "gmp", feature = "aurora-engine-modexp")))]
 mod tests {
     use super::*;
     use primitives::hex;
@@ -789,6 +919,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_modexp_gas_preview() {
+        // base 3, exponent 5, modulus 7
+        let input = hex::decode(
+            "0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             030507",
+        )
+        .unwrap();
+        let preview = gas_preview(&input);
+        assert_eq!(
+            preview.input,
+            ModexpInput {
+                base_len: U256::from(1),
+                exp_len: U256::from(1),
+                mod_len: U256::from(1),
+                exp_highp: U256::from(5),
+            }
+        );
+        assert_eq!(preview.byzantium, Ok(0));
+        assert_eq!(preview.berlin, Ok(200));
+        assert_eq!(preview.osaka, Ok(500));
+        for (run, gas) in [
+            (
+                byzantium_run as fn(&[u8], u64) -> PrecompileResult,
+                &preview.byzantium,
+            ),
+            (berlin_run, &preview.berlin),
+            (osaka_run, &preview.osaka),
+        ] {
+            assert_eq!(
+                run(&input, u64::MAX).unwrap().gas_used,
+                *gas.as_ref().unwrap()
+            );
+        }
+
+        // Base is larger than the EIP-7823 limit.
+        let mut input = vec![0u8; 96];
+        input[30..32].copy_from_slice(&1025u16.to_be_bytes());
+        let preview = gas_preview(&input);
+        assert!(preview.berlin.is_ok());
+        assert_eq!(preview.osaka, Err(PrecompileError::ModexpEip7823LimitSize));
+        assert_eq!(
+            osaka_run(&input, u64::MAX),
+            Err(PrecompileError::ModexpEip7823LimitSize)
+        );
+
+        assert_eq!(ModexpPricing::from_spec(PrecompileSpecId::HOMESTEAD), None);
+        assert_eq!(
+            ModexpPricing::from_spec(PrecompileSpecId::PRAGUE),
+            Some(ModexpPricing::Berlin)
+        );
+        assert_eq!(
+            ModexpPricing::from_spec(PrecompileSpecId::OSAKA),
+            Some(ModexpPricing::Osaka)
+        );
+    }
+
     #[test]
     fn test_modexp_out_of_gas() {
         // Test that large inputs properly return out of gas error
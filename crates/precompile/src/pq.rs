@@ -0,0 +1,157 @@
+//! # Experimental post-quantum signature precompiles
+//!
+//! Verification of [Falcon-512](https://falcon-sign.info/) and
+//! [ML-DSA-44](https://csrc.nist.gov/pubs/fips/204/final) (Dilithium) signatures, meant for
+//! research chains that evaluate post-quantum account security.
+//!
+//! These precompiles are not part of any hardfork and have no canonical address, use
+//! [`falcon512`] and [`ml_dsa44`] to create them at the address chosen by the chain. Gas costs
+//! are provisional.
+//!
+//! Verification is done by [`Crypto::falcon512_verify`](crate::Crypto::falcon512_verify) and
+//! [`Crypto::ml_dsa44_verify`](crate::Crypto::ml_dsa44_verify), which have no default
+//! implementation. A verifier has to be installed with [`install_crypto`](crate::install_crypto),
+//! otherwise calls fail, consuming all gas of the call.
+use crate::{crypto, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress};
+use primitives::{Address, Bytes, B256};
+
+/// Length of a Falcon-512 public key.
+pub const FALCON512_PUBLIC_KEY_LENGTH: usize = 897;
+/// Length of a Falcon-512 signature in the padded format.
+pub const FALCON512_SIGNATURE_LENGTH: usize = 666;
+/// Base gas fee for Falcon-512 verification.
+pub const FALCON512_BASE_GAS_FEE: u64 = 4000;
+
+/// Length of a ML-DSA-44 public key.
+pub const ML_DSA44_PUBLIC_KEY_LENGTH: usize = 1312;
+/// Length of a ML-DSA-44 signature.
+pub const ML_DSA44_SIGNATURE_LENGTH: usize = 2420;
+/// Base gas fee for ML-DSA-44 verification.
+pub const ML_DSA44_BASE_GAS_FEE: u64 = 8000;
+
+/// Gas fee for each word of the signed message.
+pub const PQ_VERIFY_PER_WORD_GAS_FEE: u64 = 12;
+
+/// Returns the Falcon-512 verification precompile at the given address.
+pub const fn falcon512(address: Address) -> PrecompileWithAddress {
+    PrecompileWithAddress(address, falcon512_verify)
+}
+
+/// Returns the ML-DSA-44 verification precompile at the given address.
+pub const fn ml_dsa44(address: Address) -> PrecompileWithAddress {
+    PrecompileWithAddress(address, ml_dsa44_verify)
+}
+
+/// Falcon-512 verification precompile logic.
+///
+/// The input is encoded as follows:
+///
+/// | public key | signature | message |
+/// | :--------: | :-------: | :-----: |
+/// |    897     |    666    |   any   |
+///
+/// Output is 32 bytes with the last byte set to 1 if the signature is valid, and empty otherwise.
+pub fn falcon512_verify(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    pq_verify(
+        input,
+        gas_limit,
+        FALCON512_PUBLIC_KEY_LENGTH,
+        FALCON512_SIGNATURE_LENGTH,
+        FALCON512_BASE_GAS_FEE,
+        |message, public_key, signature| crypto().falcon512_verify(message, public_key, signature),
+    )
+}
+
+/// ML-DSA-44 verification precompile logic.
+///
+/// The input is encoded as follows:
+///
+/// | public key | signature | message |
+/// | :--------: | :-------: | :-----: |
+/// |    1312    |   2420    |   any   |
+///
+/// Output is 32 bytes with the last byte set to 1 if the signature is valid, and empty otherwise.
+pub fn ml_dsa44_verify(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    pq_verify(
+        input,
+        gas_limit,
+        ML_DSA44_PUBLIC_KEY_LENGTH,
+        ML_DSA44_SIGNATURE_LENGTH,
+        ML_DSA44_BASE_GAS_FEE,
+        |message, public_key, signature| crypto().ml_dsa44_verify(message, public_key, signature),
+    )
+}
+
+fn pq_verify(
+    input: &[u8],
+    gas_limit: u64,
+    public_key_len: usize,
+    signature_len: usize,
+    base_gas: u64,
+    verify: impl FnOnce(&[u8], &[u8], &[u8]) -> Result<bool, PrecompileError>,
+) -> PrecompileResult {
+    let message_len = input.len().saturating_sub(public_key_len + signature_len);
+    let gas_cost = base_gas + (message_len as u64).div_ceil(32) * PQ_VERIFY_PER_WORD_GAS_FEE;
+    if gas_cost > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    if input.len() < public_key_len + signature_len {
+        return Ok(PrecompileOutput::new(gas_cost, Bytes::new()));
+    }
+
+    let (public_key, rest) = input.split_at(public_key_len);
+    let (signature, message) = rest.split_at(signature_len);
+    let result = if verify(message, public_key, signature)? {
+        B256::with_last_byte(1).into()
+    } else {
+        Bytes::new()
+    };
+    Ok(PrecompileOutput::new(gas_cost, result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::u64_to_address;
+
+    #[test]
+    fn test_pq_verify() {
+        let precompile = falcon512(u64_to_address(0x0c00));
+        assert_eq!(*precompile.address(), u64_to_address(0x0c00));
+
+        // Short input is an invalid signature.
+        let output = falcon512_verify(&[0u8; 100], FALCON512_BASE_GAS_FEE).unwrap();
+        assert!(output.bytes.is_empty());
+
+        let input = [0u8; FALCON512_PUBLIC_KEY_LENGTH + FALCON512_SIGNATURE_LENGTH + 33];
+        let gas = FALCON512_BASE_GAS_FEE + 2 * PQ_VERIFY_PER_WORD_GAS_FEE;
+        assert_eq!(
+            falcon512_verify(&input, gas - 1),
+            Err(PrecompileError::OutOfGas)
+        );
+
+        // Without an installed verifier the call fails without aborting the transaction.
+        assert!(matches!(
+            falcon512_verify(&input, gas),
+            Err(PrecompileError::Other(_))
+        ));
+
+        // Verify custom verifier is used.
+        let verify = |message: &[u8], public_key: &[u8], signature: &[u8]| {
+            assert_eq!(public_key.len(), FALCON512_PUBLIC_KEY_LENGTH);
+            assert_eq!(signature.len(), FALCON512_SIGNATURE_LENGTH);
+            Ok(message.len() == 33)
+        };
+        let output = pq_verify(
+            &input,
+            gas,
+            FALCON512_PUBLIC_KEY_LENGTH,
+            FALCON512_SIGNATURE_LENGTH,
+            FALCON512_BASE_GAS_FEE,
+            verify,
+        )
+        .unwrap();
+        assert_eq!(output.gas_used, gas);
+        assert_eq!(output.bytes, Bytes::from(B256::with_last_byte(1)));
+    }
+}
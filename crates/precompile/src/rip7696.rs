@@ -0,0 +1,363 @@
+//! # RIP-7696 generic elliptic curve precompile
+//!
+//! This module implements the double scalar multiplication of
+//! [RIP-7696](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7696.md) for short Weierstrass
+//! curves `y^2 = x^3 + ax + b` over any prime field given in the input.
+//!
+//! Computing `u*P + v*Q` is the expensive part of verifying ECDSA-like signatures, so rollups can
+//! support curves other than secp256k1 and secp256r1 without a dedicated precompile for each of
+//! them. The [`ECMULMULADD`] const represents the implementation of this precompile at its default
+//! address, [`ecmulmuladd`] creates it at the address chosen by the chain.
+//!
+//! The variant of the RIP that reads precomputed tables from contract code needs state access and
+//! is not implemented.
+use crate::{
+    u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
+};
+use primitives::{Address, Bytes, U256};
+
+/// Default address of the double scalar multiplication precompile.
+pub const ECMULMULADD_ADDRESS: u64 = 0x101;
+
+/// Gas fee of the double scalar multiplication.
+///
+/// Priced from the precompile bench: the worst case input, secp256r1 with all scalar bits set,
+/// takes about 19 times as long as `ecrecover` and is charged at the same rate as its 3000 gas.
+pub const ECMULMULADD_GAS_FEE: u64 = 60_000;
+
+/// Length of the input, nine 32 bytes words.
+pub const ECMULMULADD_INPUT_LENGTH: usize = 288;
+
+/// Returns the RIP-7696 precompiles with their default addresses.
+pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+    [ECMULMULADD].into_iter()
+}
+
+/// [RIP-7696](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7696.md) double scalar
+/// multiplication precompile.
+pub const ECMULMULADD: PrecompileWithAddress = ecmulmuladd(u64_to_address(ECMULMULADD_ADDRESS));
+
+/// Returns the double scalar multiplication precompile at the given address.
+pub const fn ecmulmuladd(address: Address) -> PrecompileWithAddress {
+    PrecompileWithAddress(address, ec_mulmuladd)
+}
+
+/// Double scalar multiplication precompile logic. Computes `u*P + v*Q` on the curve
+/// `y^2 = x^3 + ax + b` over the prime field of order `p`.
+///
+/// The input is encoded as follows:
+///
+/// |  p  |  a  |  b  | P x | P y | Q x | Q y |  u  |  v  |
+/// | :-: | :-: | :-: | :-: | :-: | :-: | :-: | :-: | :-: |
+/// | 32  | 32  | 32  | 32  | 32  | 32  | 32  | 32  | 32  |
+///
+/// The point at infinity is encoded as `(0, 0)`. Output is the resulting point, 32 bytes for each
+/// coordinate.
+///
+/// Fails with [`PrecompileError::EcInvalidCurve`] if `p` is not an odd number greater than 3, if
+/// `a` or `b` are not reduced or if the curve is singular, and with
+/// [`PrecompileError::EcPointNotOnCurve`] if `P` or `Q` are not on the curve. Primality of `p` is
+/// not checked, the result is undefined for composite moduli.
+pub fn ec_mulmuladd(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    if ECMULMULADD_GAS_FEE > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    if input.len() != ECMULMULADD_INPUT_LENGTH {
+        return Err(PrecompileError::EcInvalidInputLength);
+    }
+
+    let word = |i: usize| U256::from_be_slice(&input[i * 32..(i + 1) * 32]);
+    let curve = Curve::new(word(0), word(1), word(2))?;
+    let p = curve.point(word(3), word(4))?;
+    let q = curve.point(word(5), word(6))?;
+    let (x, y) = curve
+        .affine(curve.mul_mul_add(&p, word(7), &q, word(8)))
+        .ok_or(PrecompileError::EcInvalidCurve)?;
+
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&x.to_be_bytes::<32>());
+    out[32..].copy_from_slice(&y.to_be_bytes::<32>());
+    Ok(PrecompileOutput::new(
+        ECMULMULADD_GAS_FEE,
+        Bytes::copy_from_slice(&out),
+    ))
+}
+
+/// Point in Jacobian coordinates `(X, Y, Z)` for the affine point `(X/Z^2, Y/Z^3)`.
+///
+/// `Z` is zero for the point at infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Point {
+    x: U256,
+    y: U256,
+    z: U256,
+}
+
+impl Point {
+    const INFINITY: Self = Self {
+        x: U256::ONE,
+        y: U256::ONE,
+        z: U256::ZERO,
+    };
+
+    fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+}
+
+/// Short Weierstrass curve `y^2 = x^3 + ax + b` over the field of order `p`.
+#[derive(Clone, Copy, Debug)]
+struct Curve {
+    p: U256,
+    a: U256,
+    b: U256,
+}
+
+impl Curve {
+    fn new(p: U256, a: U256, b: U256) -> Result<Self, PrecompileError> {
+        if p <= U256::from(3) || !p.bit(0) || a >= p || b >= p {
+            return Err(PrecompileError::EcInvalidCurve);
+        }
+        let curve = Self { p, a, b };
+        // 4a^3 + 27b^2 != 0
+        let a3 = curve.mul(curve.mul(a, a), a);
+        let discriminant = curve.add(
+            curve.mul(U256::from(4), a3),
+            curve.mul(U256::from(27), curve.mul(b, b)),
+        );
+        if discriminant.is_zero() {
+            return Err(PrecompileError::EcInvalidCurve);
+        }
+        Ok(curve)
+    }
+
+    /// Returns the point of the affine coordinates, `(0, 0)` is the point at infinity.
+    fn point(&self, x: U256, y: U256) -> Result<Point, PrecompileError> {
+        if x.is_zero() && y.is_zero() {
+            return Ok(Point::INFINITY);
+        }
+        if x >= self.p || y >= self.p {
+            return Err(PrecompileError::EcPointNotOnCurve);
+        }
+        let rhs = self.add(self.mul(self.add(self.mul(x, x), self.a), x), self.b);
+        if self.mul(y, y) != rhs {
+            return Err(PrecompileError::EcPointNotOnCurve);
+        }
+        Ok(Point { x, y, z: U256::ONE })
+    }
+
+    /// Returns the affine coordinates of the point, `(0, 0)` for the point at infinity.
+    ///
+    /// Returns `None` if `Z` is not invertible, which can happen only for composite `p`.
+    fn affine(&self, point: Point) -> Option<(U256, U256)> {
+        if point.is_infinity() {
+            return Some((U256::ZERO, U256::ZERO));
+        }
+        let z_inv = point.z.inv_mod(self.p)?;
+        let z_inv2 = self.mul(z_inv, z_inv);
+        Some((
+            self.mul(point.x, z_inv2),
+            self.mul(point.y, self.mul(z_inv2, z_inv)),
+        ))
+    }
+
+    /// Computes `u*P + v*Q` with the Shamir's trick, sharing the doublings of both
+    /// multiplications.
+    fn mul_mul_add(&self, p: &Point, u: U256, q: &Point, v: U256) -> Point {
+        let pq = self.add_points(p, q);
+        let mut acc = Point::INFINITY;
+        for i in (0..u.bit_len().max(v.bit_len())).rev() {
+            acc = self.double(&acc);
+            match (u.bit(i), v.bit(i)) {
+                (true, true) => acc = self.add_points(&acc, &pq),
+                (true, false) => acc = self.add_points(&acc, p),
+                (false, true) => acc = self.add_points(&acc, q),
+                (false, false) => {}
+            }
+        }
+        acc
+    }
+
+    fn double(&self, point: &Point) -> Point {
+        if point.is_infinity() || point.y.is_zero() {
+            return Point::INFINITY;
+        }
+        let Point { x, y, z } = *point;
+        let y2 = self.mul(y, y);
+        // S = 4XY^2, M = 3X^2 + aZ^4
+        let s = self.mul(U256::from(4), self.mul(x, y2));
+        let z2 = self.mul(z, z);
+        let m = self.add(
+            self.mul(U256::from(3), self.mul(x, x)),
+            self.mul(self.a, self.mul(z2, z2)),
+        );
+        // X' = M^2 - 2S, Y' = M(S - X') - 8Y^4, Z' = 2YZ
+        let x3 = self.sub(self.mul(m, m), self.add(s, s));
+        let y3 = self.sub(
+            self.mul(m, self.sub(s, x3)),
+            self.mul(U256::from(8), self.mul(y2, y2)),
+        );
+        let z3 = self.mul(U256::from(2), self.mul(y, z));
+        Point {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    fn add_points(&self, p1: &Point, p2: &Point) -> Point {
+        if p1.is_infinity() {
+            return *p2;
+        }
+        if p2.is_infinity() {
+            return *p1;
+        }
+        let z1z1 = self.mul(p1.z, p1.z);
+        let z2z2 = self.mul(p2.z, p2.z);
+        let u1 = self.mul(p1.x, z2z2);
+        let u2 = self.mul(p2.x, z1z1);
+        let s1 = self.mul(p1.y, self.mul(z2z2, p2.z));
+        let s2 = self.mul(p2.y, self.mul(z1z1, p1.z));
+        if u1 == u2 {
+            return if s1 == s2 {
+                self.double(p1)
+            } else {
+                Point::INFINITY
+            };
+        }
+        // H = U2 - U1, R = S2 - S1
+        let h = self.sub(u2, u1);
+        let r = self.sub(s2, s1);
+        let h2 = self.mul(h, h);
+        let h3 = self.mul(h2, h);
+        let u1h2 = self.mul(u1, h2);
+        // X3 = R^2 - H^3 - 2U1H^2, Y3 = R(U1H^2 - X3) - S1H^3, Z3 = HZ1Z2
+        let x3 = self.sub(self.sub(self.mul(r, r), h3), self.add(u1h2, u1h2));
+        let y3 = self.sub(self.mul(r, self.sub(u1h2, x3)), self.mul(s1, h3));
+        let z3 = self.mul(h, self.mul(p1.z, p2.z));
+        Point {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    fn add(&self, a: U256, b: U256) -> U256 {
+        a.add_mod(b, self.p)
+    }
+
+    fn sub(&self, a: U256, b: U256) -> U256 {
+        a.add_mod(self.p - b, self.p)
+    }
+
+    fn mul(&self, a: U256, b: U256) -> U256 {
+        a.mul_mod(b, self.p)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use primitives::hex;
+
+    // secp256r1 parameters.
+    const P: [u8; 32] = hex!("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    const A: [u8; 32] = hex!("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc");
+    const B: [u8; 32] = hex!("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+    const N: [u8; 32] = hex!("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+    const GX: [u8; 32] = hex!("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+    const GY: [u8; 32] = hex!("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+    // 2G
+    const G2X: [u8; 32] = hex!("7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978");
+    const G2Y: [u8; 32] = hex!("07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1");
+
+    fn input(words: [&[u8; 32]; 9]) -> Vec<u8> {
+        words.into_iter().flatten().copied().collect()
+    }
+
+    fn scalar(value: u64) -> [u8; 32] {
+        U256::from(value).to_be_bytes()
+    }
+
+    #[test]
+    fn test_ec_mulmuladd() {
+        let expected = [G2X, G2Y].concat();
+        // 1*G + 1*G
+        let out = ec_mulmuladd(
+            &input([&P, &A, &B, &GX, &GY, &GX, &GY, &scalar(1), &scalar(1)]),
+            ECMULMULADD_GAS_FEE,
+        )
+        .unwrap();
+        assert_eq!(out.gas_used, ECMULMULADD_GAS_FEE);
+        assert_eq!(out.bytes[..], expected);
+
+        // 2*G + 0*inf
+        let zero = [0u8; 32];
+        let out = ec_mulmuladd(
+            &input([&P, &A, &B, &GX, &GY, &zero, &zero, &scalar(2), &scalar(5)]),
+            ECMULMULADD_GAS_FEE,
+        )
+        .unwrap();
+        assert_eq!(out.bytes[..], expected);
+
+        // 3*G + (n - 1)*G = 2*G
+        let n_minus_one = (U256::from_be_bytes(N) - U256::ONE).to_be_bytes::<32>();
+        let out = ec_mulmuladd(
+            &input([&P, &A, &B, &GX, &GY, &GX, &GY, &scalar(3), &n_minus_one]),
+            ECMULMULADD_GAS_FEE,
+        )
+        .unwrap();
+        assert_eq!(out.bytes[..], expected);
+
+        // n*G is the point at infinity
+        let out = ec_mulmuladd(
+            &input([&P, &A, &B, &GX, &GY, &GX, &GY, &N, &zero]),
+            ECMULMULADD_GAS_FEE,
+        )
+        .unwrap();
+        assert_eq!(out.bytes[..], [0u8; 64]);
+    }
+
+    #[test]
+    fn test_ec_mulmuladd_errors() {
+        let valid = input([&P, &A, &B, &GX, &GY, &GX, &GY, &scalar(1), &scalar(1)]);
+        assert_eq!(
+            ec_mulmuladd(&valid, ECMULMULADD_GAS_FEE - 1),
+            Err(PrecompileError::OutOfGas)
+        );
+        assert_eq!(
+            ec_mulmuladd(&valid[1..], ECMULMULADD_GAS_FEE),
+            Err(PrecompileError::EcInvalidInputLength)
+        );
+
+        let even_p = input([
+            &scalar(10),
+            &A,
+            &B,
+            &GX,
+            &GY,
+            &GX,
+            &GY,
+            &scalar(1),
+            &scalar(1),
+        ]);
+        assert_eq!(
+            ec_mulmuladd(&even_p, ECMULMULADD_GAS_FEE),
+            Err(PrecompileError::EcInvalidCurve)
+        );
+
+        // y^2 = x^3 is singular.
+        let zero = [0u8; 32];
+        let singular = input([&P, &zero, &zero, &GX, &GY, &GX, &GY, &scalar(1), &scalar(1)]);
+        assert_eq!(
+            ec_mulmuladd(&singular, ECMULMULADD_GAS_FEE),
+            Err(PrecompileError::EcInvalidCurve)
+        );
+
+        let not_on_curve = input([&P, &A, &B, &GX, &GX, &GX, &GY, &scalar(1), &scalar(1)]);
+        assert_eq!(
+            ec_mulmuladd(&not_on_curve, ECMULMULADD_GAS_FEE),
+            Err(PrecompileError::EcPointNotOnCurve)
+        );
+    }
+}
@@ -6,7 +6,8 @@
 //!   It is faster than k256 and enabled by default and in std environment.
 //! * [`libsecp256k1`](https://crates.io/crates/libsecp256k1) - is made from parity in pure rust, it is alternative for k256.
 //!
-//! Order of preference is `secp256k1` -> `k256` -> `libsecp256k1`. Where if no features are enabled, it will use `k256`.
+//! Order of preference is `secp256k1` -> `libsecp256k1` -> `k256`. If none of them is enabled, the
+//! precompile fails with a fatal error unless a custom [`Crypto`][crate::Crypto] is installed.
 //!
 //! Input format:
 //! [32 bytes for message][64 bytes for signature][1 byte for recovery id]
@@ -15,6 +16,7 @@
 //! [32 bytes for recovered address]
 #[cfg(feature = "secp256k1")]
 pub mod bitcoin_secp256k1;
+#[cfg(feature = "k256")]
 pub mod k256;
 #[cfg(feature = "libsecp256k1")]
 pub mod parity_libsecp256k1;
@@ -48,11 +50,14 @@ pub fn ec_recover_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     let recid = input[63] - 27;
     let sig = <&B512>::try_from(&input[64..128]).unwrap();
 
-    let res = crypto().secp256k1_ecrecover(&sig.0, recid, &msg.0).ok();
-    let out = res.map(|o| o.to_vec().into()).unwrap_or_default();
-    Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
+    match crypto().secp256k1_ecrecover(&sig.0, recid, &msg.0) {
+        Ok(address) => Ok(PrecompileOutput::from_array(ECRECOVER_BASE, address)),
+        Err(err @ PrecompileError::Fatal(_)) => Err(err),
+        Err(_) => Ok(PrecompileOutput::new(ECRECOVER_BASE, Bytes::new())),
+    }
 }
 
+#[cfg(any(feature = "secp256k1", feature = "libsecp256k1", feature = "k256"))]
 pub(crate) fn ecrecover_bytes(sig: [u8; 64], recid: u8, msg: [u8; 32]) -> Option<[u8; 32]> {
     let sig = B512::from_slice(&sig);
     let msg = B256::from_slice(&msg);
@@ -69,7 +74,38 @@ cfg_if::cfg_if! {
         pub use bitcoin_secp256k1::ecrecover;
     } else if #[cfg(feature = "libsecp256k1")] {
         pub use parity_libsecp256k1::ecrecover;
-    } else {
+    } else if #[cfg(feature = "k256")] {
         pub use k256::ecrecover;
     }
 }
+
+#[cfg(all(
+    test,
+    any(feature = "secp256k1", feature = "libsecp256k1", feature = "k256")
+))]
+mod tests {
+    use super::*;
+    use crate::hash::{ripemd160_run, sha256_run};
+    use primitives::hex;
+
+    #[test]
+    fn fixed_output_sizes() {
+        let input = hex!("18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c000000000000000000000000000000000000000000000000000000000000001c73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75feeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549");
+        let output = ec_recover_run(&input, 3_000).unwrap();
+        assert_eq!(output.gas_used, 3_000);
+        assert_eq!(output.bytes.len(), 32);
+
+        // Invalid `v` returns empty output.
+        let mut invalid = input;
+        invalid[63] = 29;
+        assert!(ec_recover_run(&invalid, 3_000).unwrap().bytes.is_empty());
+
+        let output = sha256_run(&[], 60).unwrap();
+        assert_eq!(
+            output.bytes[..],
+            hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        assert_eq!(sha256_run(&[], 59), Err(PrecompileError::OutOfGas));
+        assert_eq!(ripemd160_run(&input, u64::MAX).unwrap().bytes.len(), 32);
+    }
+}
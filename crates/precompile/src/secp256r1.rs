@@ -7,9 +7,10 @@
 //! P256 elliptic curve. The [`P256VERIFY`] const represents the implementation of this precompile,
 //! with the address that it is currently deployed at.
 use crate::{
-    crypto, u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult,
-    PrecompileWithAddress,
+    crypto, u64_to_address, utilities::bool_to_bytes32, PrecompileError, PrecompileOutput,
+    PrecompileResult, PrecompileWithAddress,
 };
+#[cfg(feature = "p256")]
 use p256::{
     ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
     EncodedPoint,
@@ -68,8 +69,14 @@ fn p256_verify_inner(input: &[u8], gas_limit: u64, gas_cost: u64) -> PrecompileR
     if gas_cost > gas_limit {
         return Err(PrecompileError::OutOfGas);
     }
+    // Without `p256` only a custom crypto provider can verify the signature.
+    #[cfg(not(feature = "p256"))]
+    if !crate::backend::is_custom_crypto() {
+        return Err(PrecompileError::Fatal("p256 feature is not enabled".into()));
+    }
+    // Both outputs are static and don't allocate.
     let result = if verify_impl(input) {
-        B256::with_last_byte(1).into()
+        bool_to_bytes32(true)
     } else {
         Bytes::new()
     };
@@ -93,6 +100,7 @@ pub fn verify_impl(input: &[u8]) -> bool {
     crypto().secp256r1_verify_signature(&msg.0, &sig.0, &pk.0)
 }
 
+#[cfg(feature = "p256")]
 pub(crate) fn verify_signature(msg: [u8; 32], sig: [u8; 64], pk: [u8; 64]) -> Option<()> {
     // Can fail only if the input is not exact length.
     let signature = Signature::from_slice(&sig).ok()?;
@@ -104,7 +112,7 @@ pub(crate) fn verify_signature(msg: [u8; 32], sig: [u8; 64], pk: [u8; 64]) -> Op
     public_key.verify_prehash(&msg, &signature).ok()
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "p256"))]
 mod test {
     use super::*;
     use crate::PrecompileError;
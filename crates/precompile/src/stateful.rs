@@ -0,0 +1,46 @@
+//! Precompiles with access to the execution context.
+//!
+//! Unlike [`PrecompileFn`](crate::PrecompileFn), a [`StatefulPrecompile`] receives the context of
+//! the EVM, so it can read and write storage, move balances and emit logs through the journal.
+//! The precompile runs inside the checkpoint of its call frame, all changes are reverted if the
+//! precompile reverts, fails or runs out of gas.
+use crate::PrecompileResult;
+use primitives::{Address, U256};
+
+/// Call of a [`StatefulPrecompile`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatefulCall<'a> {
+    /// Call data.
+    pub input: &'a [u8],
+    /// Gas limit of the call.
+    pub gas_limit: u64,
+    /// Caller of the precompile.
+    pub caller: Address,
+    /// Address whose storage is used, differs from the precompile address for `DELEGATECALL`
+    /// and `CALLCODE`.
+    pub target_address: Address,
+    /// Apparent value of the call, already transferred to the target address.
+    pub value: U256,
+    /// Whether the call is static, state changes have to fail with
+    /// [`PrecompileError::StateChangeDuringStaticCall`](crate::PrecompileError::StateChangeDuringStaticCall).
+    pub is_static: bool,
+}
+
+/// Precompile that is called with the execution context `CTX`.
+///
+/// Implemented for closures taking the context and the [`StatefulCall`]. Gas used for state
+/// access has to be included in [`PrecompileOutput::gas_used`](crate::PrecompileOutput::gas_used),
+/// the journal does not charge gas by itself.
+pub trait StatefulPrecompile<CTX>: Send + Sync {
+    /// Runs the precompile.
+    fn call(&self, context: &mut CTX, call: &StatefulCall<'_>) -> PrecompileResult;
+}
+
+impl<CTX, F> StatefulPrecompile<CTX> for F
+where
+    F: Fn(&mut CTX, &StatefulCall<'_>) -> PrecompileResult + Send + Sync,
+{
+    fn call(&self, context: &mut CTX, call: &StatefulCall<'_>) -> PrecompileResult {
+        self(context, call)
+    }
+}
@@ -0,0 +1,86 @@
+//! Contract address derivation for `CREATE`, `CREATE2` and CREATE3 factories.
+use crate::{address, b256, keccak256, Address, B256};
+
+/// Address of the deterministic deployment proxy, deployed on most chains with a presigned
+/// transaction. Deploys `CREATE2` contracts with calldata `salt ++ init_code`.
+///
+/// <https://github.com/Arachnid/deterministic-deployment-proxy>
+pub const DETERMINISTIC_DEPLOYMENT_PROXY: Address =
+    address!("0x4e59b44847b379578588920ca78fbf26c0b4956c");
+
+/// Hash of the proxy init code used by the solmate/0xsequence `CREATE3` libraries.
+///
+/// Init code is `0x67363d3d37363d34f03d5260086018f3`.
+pub const CREATE3_PROXY_INITCODE_HASH: B256 =
+    b256!("0x21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f");
+
+/// Returns address created by `CREATE` from the `deployer` with the given `nonce`.
+#[inline]
+pub fn create_address(deployer: Address, nonce: u64) -> Address {
+    deployer.create(nonce)
+}
+
+/// Returns address created by `CREATE2` from the `deployer` with the given `salt` and `init_code`.
+#[inline]
+pub fn create2_address(deployer: Address, salt: B256, init_code: &[u8]) -> Address {
+    create2_address_from_hash(deployer, salt, keccak256(init_code))
+}
+
+/// Returns address created by `CREATE2` with already hashed init code.
+#[inline]
+pub fn create2_address_from_hash(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    deployer.create2(salt, init_code_hash)
+}
+
+/// Returns address created by a `CREATE3` factory that uses the solmate/0xsequence proxy scheme.
+///
+/// Factory deploys the proxy with `CREATE2` and the proxy deploys the contract with `CREATE`
+/// as its first transaction, so the address depends only on the factory and the salt.
+///
+/// Note that some factories (e.g. CreateX) hash the salt with the sender before deploying the
+/// proxy, for those the guarded salt should be passed.
+#[inline]
+pub fn create3_address(factory: Address, salt: B256) -> Address {
+    create2_address_from_hash(factory, salt, CREATE3_PROXY_INITCODE_HASH).create(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn test_create3_proxy_hash() {
+        assert_eq!(
+            keccak256(hex!("67363d3d37363d34f03d5260086018f3")),
+            CREATE3_PROXY_INITCODE_HASH
+        );
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // Example 0 from EIP-1014.
+        assert_eq!(
+            create2_address(Address::ZERO, B256::ZERO, &[0x00]),
+            address!("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+        );
+    }
+
+    #[test]
+    fn test_create_address() {
+        assert_eq!(
+            create_address(address!("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"), 0),
+            address!("0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+    }
+
+    #[test]
+    fn test_create3_address() {
+        let factory = address!("0x0000000000000000000000000000000000001234");
+        let proxy = create2_address_from_hash(factory, B256::ZERO, CREATE3_PROXY_INITCODE_HASH);
+        assert_eq!(
+            create3_address(factory, B256::ZERO),
+            create_address(proxy, 1)
+        );
+    }
+}
@@ -7,6 +7,9 @@ pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
 /// Gas consumption of a single data blob (== blob byte size)
 pub const GAS_PER_BLOB: u64 = 1 << 17;
 
+/// Size of a blob in bytes
+pub const BYTES_PER_BLOB: usize = 131_072;
+
 /// Min blob gas price
 pub const MIN_BLOB_GASPRICE: u64 = 1;
 
@@ -41,3 +44,7 @@ pub const TARGET_BLOB_GAS_PER_BLOCK_PRAGUE: u64 =
 
 /// Controls the maximum rate of change for blob gas price
 pub const BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE: u64 = 5_007_716;
+
+/// Max number of blobs per transaction, introduced in Osaka by
+/// [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594).
+pub const MAX_BLOBS_PER_TX_OSAKA: u64 = 6;
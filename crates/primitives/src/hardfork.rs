@@ -10,6 +10,21 @@ pub use SpecId::*;
 /// Specification IDs and their activation block
 ///
 /// Information was obtained from the [Ethereum Execution Specifications](https://github.com/ethereum/execution-specs).
+///
+/// # Prototyping a hardfork
+///
+/// Hardforks that are not scheduled yet don't need a variant here. Crates are generic over the
+/// spec of the `Cfg` and only require it to convert into the [`SpecId`] it is based on, so a fork
+/// is prototyped with its own spec type:
+///
+/// * The spec type wraps [`SpecId`] and adds the prototype fork, converting it into the fork it
+///   extends (for example [`SpecId::AMSTERDAM`]), so all existing rules stay enabled.
+/// * Precompile set is changed with a custom precompile provider that checks the spec in its
+///   `set_spec`.
+/// * Gas and instruction changes are made in a custom instruction table, validation toggles with
+///   the `Cfg` flags.
+///
+/// The `example_chain` example in the repository shows a complete spec type.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, TryFromPrimitive)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -75,6 +90,9 @@ pub enum SpecId {
     /// Osaka hard fork
     /// Activated at block TBD
     OSAKA,
+    /// Amsterdam hard fork
+    /// Activated at block TBD
+    AMSTERDAM,
 }
 
 impl SpecId {
@@ -133,6 +151,8 @@ pub mod name {
     pub const PRAGUE: &str = "Prague";
     /// String identifier for the Osaka hardfork
     pub const OSAKA: &str = "Osaka";
+    /// String identifier for the Amsterdam hardfork
+    pub const AMSTERDAM: &str = "Amsterdam";
     /// String identifier for the latest hardfork
     pub const LATEST: &str = "Latest";
 }
@@ -166,6 +186,7 @@ impl FromStr for SpecId {
             name::CANCUN => Ok(Self::CANCUN),
             name::PRAGUE => Ok(Self::PRAGUE),
             name::OSAKA => Ok(Self::OSAKA),
+            name::AMSTERDAM => Ok(Self::AMSTERDAM),
             _ => Err(UnknownHardfork),
         }
     }
@@ -194,6 +215,7 @@ impl From<SpecId> for &'static str {
             SpecId::CANCUN => name::CANCUN,
             SpecId::PRAGUE => name::PRAGUE,
             SpecId::OSAKA => name::OSAKA,
+            SpecId::AMSTERDAM => name::AMSTERDAM,
         }
     }
 }
@@ -8,6 +8,7 @@
 extern crate alloc as std;
 
 pub mod constants;
+pub mod create_address;
 pub mod eip170;
 pub mod eip3860;
 pub mod eip4844;
@@ -4,7 +4,7 @@ mod common;
 use common::compare_or_save_testdata;
 use context::ContextTr;
 use database::BENCH_CALLER;
-use primitives::{address, b256, hardfork::SpecId, Bytes, TxKind, KECCAK_EMPTY};
+use primitives::{address, b256, hardfork::SpecId, Address, Bytes, TxKind, KECCAK_EMPTY};
 use revm::{
     bytecode::opcode,
     context::TxEnv,
@@ -272,3 +272,1135 @@ fn test_disable_balance_check() {
     let expected_balance = U256::ZERO;
     assert_eq!(returned_balance, expected_balance);
 }
+
+#[test]
+fn test_nonce_check_mode() {
+    use context::cfg::NonceCheckMode;
+
+    for (mode, expected_nonce) in [(NonceCheckMode::AutoFill, 1), (NonceCheckMode::Ignore, 0)] {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.nonce_check_mode = mode)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::STOP].into(),
+            )))
+            .build_mainnet();
+
+        // Nonce of the caller is zero, transaction nonce is ignored.
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().nonce(5).build_fill())
+            .unwrap();
+        assert!(result.is_success());
+
+        let caller = evm.ctx.journal_mut().state.get(&BENCH_CALLER).unwrap();
+        assert_eq!(caller.info.nonce, expected_nonce);
+    }
+}
+
+#[test]
+fn test_chain_id_policy() {
+    use context::{cfg::ChainIdPolicy, result::InvalidTransaction};
+    use revm::context::result::EVMError;
+
+    let mut evm = Context::mainnet()
+        .modify_cfg_chained(|cfg| {
+            *cfg = core::mem::take(cfg).with_chain_id_policy(ChainIdPolicy::AnyOf(vec![10]))
+        })
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            [opcode::STOP].into(),
+        )))
+        .build_mainnet();
+
+    let result = evm.transact_one(TxEnv::builder_for_bench().chain_id(Some(10)).build_fill());
+    assert!(result.unwrap().is_success());
+
+    let result = evm.transact_one(
+        TxEnv::builder_for_bench()
+            .nonce(1)
+            .chain_id(Some(56))
+            .build_fill(),
+    );
+    assert!(matches!(
+        result,
+        Err(EVMError::Transaction(InvalidTransaction::InvalidChainId))
+    ));
+}
+
+#[test]
+fn test_prefetch_access_list() {
+    use context::transaction::{AccessList, AccessListItem};
+    use database::Database;
+    use primitives::B256;
+    use state::AccountInfo;
+
+    /// Database recording account reads.
+    #[derive(Default)]
+    struct CountingDB {
+        db: BenchmarkDB,
+        basics: Vec<Address>,
+        many_basics: Vec<Vec<Address>>,
+    }
+
+    impl Database for CountingDB {
+        type Error = core::convert::Infallible;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.basics.push(address);
+            self.db.basic(address)
+        }
+
+        fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.db.code_by_hash(code_hash)
+        }
+
+        fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.db.storage(address, index)
+        }
+
+        fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+            self.db.block_hash(number)
+        }
+
+        fn many_basics(
+            &mut self,
+            addresses: &[Address],
+        ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+            self.many_basics.push(addresses.to_vec());
+            addresses
+                .iter()
+                .map(|address| self.db.basic(*address))
+                .collect()
+        }
+    }
+
+    let first = address!("0x1000000000000000000000000000000000000001");
+    let second = address!("0x1000000000000000000000000000000000000002");
+    let run = |prefetch: bool| {
+        // balance(first)
+        let mut code = vec![opcode::PUSH20];
+        code.extend_from_slice(first.as_slice());
+        code.extend([opcode::BALANCE, opcode::STOP]);
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.prefetch_access_list = prefetch)
+            .with_db(CountingDB {
+                db: BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())),
+                ..Default::default()
+            })
+            .build_mainnet();
+        let item = |address| AccessListItem {
+            address,
+            storage_keys: vec![],
+        };
+        let result = evm
+            .transact_one(
+                TxEnv::builder_for_bench()
+                    .tx_type(Some(1))
+                    .access_list(AccessList(vec![item(second), item(first), item(second)]))
+                    .build_fill(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+        let db = &evm.ctx.journal_ref().database;
+        (result.gas_used(), db.basics.clone(), db.many_basics.clone())
+    };
+
+    let (prefetch_gas, basics, many_basics) = run(true);
+    // Access list accounts are fetched with one call.
+    assert_eq!(many_basics, vec![vec![first, second]]);
+    assert!(!basics.contains(&first) && !basics.contains(&second));
+
+    let (gas, basics, many_basics) = run(false);
+    assert!(many_basics.is_empty());
+    assert_eq!(basics.iter().filter(|a| **a == first).count(), 1);
+    assert!(!basics.contains(&second));
+
+    // Prefetching does not change the gas cost of warm access.
+    assert_eq!(prefetch_gas, gas);
+}
+
+#[test]
+fn test_eip7702_code_policy() {
+    use context::cfg::Eip7702CodePolicy;
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        primitives::{keccak256, B256},
+        state::AccountInfo,
+    };
+
+    let delegated = address!("0x1000000000000000000000000000000000000001");
+    let delegate = address!("0x1000000000000000000000000000000000000002");
+    let delegate_code = Bytes::from_static(&[opcode::PUSH1, 0x01, opcode::STOP]);
+
+    // Returns EXTCODESIZE and EXTCODEHASH of the delegated account.
+    let mut code = vec![opcode::PUSH20];
+    code.extend_from_slice(delegated.as_slice());
+    code.extend([opcode::EXTCODESIZE, opcode::PUSH1, 0x00, opcode::MSTORE]);
+    code.push(opcode::PUSH20);
+    code.extend_from_slice(delegated.as_slice());
+    code.extend([opcode::EXTCODEHASH, opcode::PUSH1, 0x20, opcode::MSTORE]);
+    code.extend([opcode::PUSH1, 0x40, opcode::PUSH1, 0x00, opcode::RETURN]);
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        BENCH_TARGET,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+    );
+    db.insert_account_info(
+        delegated,
+        AccountInfo::from_bytecode(Bytecode::new_eip7702(delegate)),
+    );
+    db.insert_account_info(
+        delegate,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(delegate_code.clone())),
+    );
+
+    let run = |policy: Eip7702CodePolicy| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.eip7702_code_policy = policy)
+            .with_db(db.clone())
+            .build_mainnet();
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        let output = result.output().unwrap().clone();
+        (
+            U256::from_be_slice(&output[..32]),
+            B256::from_slice(&output[32..]),
+        )
+    };
+
+    let designator = Bytecode::new_eip7702(delegate).original_bytes();
+    assert_eq!(
+        run(Eip7702CodePolicy::Designator),
+        (U256::from(designator.len()), keccak256(&designator))
+    );
+    assert_eq!(
+        run(Eip7702CodePolicy::DelegateCode),
+        (U256::from(delegate_code.len()), keccak256(&delegate_code))
+    );
+}
+
+#[test]
+fn test_selfdestruct_mode() {
+    use context::cfg::SelfdestructMode;
+    use revm::context::result::{ExecutionResult, HaltReason};
+
+    let run = |mode: SelfdestructMode| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| {
+                cfg.spec = SpecId::CANCUN;
+                cfg.selfdestruct_mode = Some(mode);
+            })
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                SELFDESTRUCT_BYTECODE.into(),
+            )))
+            .build_mainnet();
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        let destroyed = evm
+            .ctx
+            .journal_mut()
+            .state
+            .get(&BENCH_TARGET)
+            .unwrap()
+            .is_selfdestructed();
+        (result, destroyed)
+    };
+
+    // EIP-6780, account existing before the transaction is not destroyed.
+    let (result, destroyed) = run(SelfdestructMode::SameTxOnly);
+    assert!(result.is_success());
+    assert!(!destroyed);
+
+    let (result, destroyed) = run(SelfdestructMode::Destroy);
+    assert!(result.is_success());
+    assert!(destroyed);
+
+    let (result, destroyed) = run(SelfdestructMode::Disabled);
+    assert!(matches!(
+        result,
+        ExecutionResult::Halt {
+            reason: HaltReason::NotActivated,
+            ..
+        }
+    ));
+    assert!(!destroyed);
+}
+
+#[test]
+fn test_refund_policy() {
+    use context::cfg::{RefundCap, RefundPolicy};
+    use revm::context::result::ExecutionResult;
+
+    let run = |bytecode: &[u8], policy: Option<RefundPolicy>| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| {
+                cfg.spec = SpecId::CANCUN;
+                cfg.refund_policy = policy;
+            })
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                Bytes::copy_from_slice(bytecode),
+            )))
+            .build_mainnet();
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        let ExecutionResult::Success {
+            gas_used,
+            gas_refunded,
+            ..
+        } = result
+        else {
+            panic!("transaction should succeed: {result:?}")
+        };
+        (gas_used, gas_refunded)
+    };
+
+    // sstore(0, 1), sstore(0, 0) refunds 19900 gas.
+    let sstore_bytecode = [
+        opcode::PUSH1,
+        0x01,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        opcode::STOP,
+    ];
+    let london = RefundPolicy::from_spec(SpecId::CANCUN);
+
+    // EIP-3529 caps the refund to a fifth of the spent gas.
+    let (gas_used, gas_refunded) = run(&sstore_bytecode, None);
+    let spent = gas_used + gas_refunded;
+    assert_eq!(gas_refunded, spent / 5);
+
+    let (_, gas_refunded) = run(
+        &sstore_bytecode,
+        Some(london.with_cap(RefundCap::PreLondon)),
+    );
+    assert_eq!(gas_refunded, 19900);
+
+    let (gas_used, gas_refunded) = run(
+        &sstore_bytecode,
+        Some(london.with_cap(RefundCap::Quotient(0))),
+    );
+    assert_eq!(gas_refunded, 0);
+    assert_eq!(gas_used, spent);
+
+    // SELFDESTRUCT refund is removed in London, policy can enable it.
+    let (_, gas_refunded) = run(SELFDESTRUCT_BYTECODE, None);
+    assert_eq!(gas_refunded, 0);
+    let (gas_used, gas_refunded) = run(
+        SELFDESTRUCT_BYTECODE,
+        Some(london.with_selfdestruct_refund(true)),
+    );
+    assert_eq!(gas_refunded, (gas_used + gas_refunded) / 5);
+}
+
+#[test]
+fn test_gas_schedule() {
+    use context::cfg::GasSchedule;
+
+    let run = |schedule: Option<GasSchedule>| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.gas_schedule = schedule)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::PUSH0, opcode::SLOAD, opcode::STOP].into(),
+            )))
+            .build_mainnet();
+        evm.transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap()
+            .gas_used()
+    };
+
+    let default = run(None);
+    let schedule = GasSchedule {
+        cold_sload: 10_000,
+        ..GasSchedule::from_spec(SpecId::default())
+    };
+    assert_eq!(run(Some(schedule)), default + 10_000 - 2100);
+}
+
+#[test]
+fn test_disabled_opcodes() {
+    use context::{
+        cfg::DisabledOpcodes,
+        result::{ExecutionResult, HaltReason},
+    };
+    use revm::{inspector::NoOpInspector, InspectEvm};
+
+    let is_disabled = |result: ExecutionResult| {
+        matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeDisabled,
+                ..
+            }
+        )
+    };
+    let disabled = DisabledOpcodes::from_opcodes(&[opcode::SELFDESTRUCT, opcode::CREATE]);
+    let mut evm = Context::mainnet()
+        .modify_cfg_chained(|cfg| {
+            cfg.disabled_opcodes = disabled;
+            cfg.disable_nonce_check = true;
+        })
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            SELFDESTRUCT_BYTECODE.into(),
+        )))
+        .build_mainnet_with_inspector(NoOpInspector {});
+
+    let result = evm
+        .transact_one(TxEnv::builder_for_bench().build_fill())
+        .unwrap();
+    assert!(is_disabled(result));
+    let result = evm
+        .inspect_one_tx(TxEnv::builder_for_bench().build_fill())
+        .unwrap();
+    assert!(is_disabled(result));
+
+    // Opcode is enabled again when it is removed from the configuration.
+    evm.ctx.cfg.disabled_opcodes.remove(opcode::SELFDESTRUCT);
+    let result = evm
+        .transact_one(TxEnv::builder_for_bench().build_fill())
+        .unwrap();
+    assert!(result.is_success());
+}
+
+#[test]
+fn test_call_gas_policy() {
+    use context::cfg::{CallGasPolicy, GasSchedule};
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    // Callee consumes all forwarded gas.
+    let callee = address!("0x00000000000000000000000000000000000000aa");
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        BENCH_TARGET,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(
+            [
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0xaa,
+                opcode::GAS,
+                opcode::CALL,
+                opcode::STOP,
+            ]
+            .into(),
+        )),
+    );
+    db.insert_account_info(
+        callee,
+        AccountInfo::from_bytecode(Bytecode::new_legacy([opcode::INVALID].into())),
+    );
+
+    let run = |max_forwarded: u64| {
+        let schedule = GasSchedule {
+            call_gas: CallGasPolicy::from_spec(SpecId::default()).with_max_forwarded(max_forwarded),
+            ..GasSchedule::from_spec(SpecId::default())
+        };
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.gas_schedule = Some(schedule))
+            .with_db(db.clone())
+            .build_mainnet();
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().gas_limit(100_000).build_fill())
+            .unwrap();
+        assert!(result.is_success());
+        result.gas_used()
+    };
+
+    assert_eq!(run(2000), run(1000) + 1000);
+    assert!(run(u64::MAX) > run(2000) + 70_000);
+}
+
+#[test]
+fn test_fork_schedule() {
+    use revm::context::{BlockEnv, CfgEnv, ForkSchedule};
+
+    let mut evm = Context::mainnet()
+        .with_cfg(CfgEnv::new().with_fork_schedule(ForkSchedule::mainnet()))
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            [opcode::PUSH0, opcode::STOP].into(),
+        )))
+        .build_mainnet();
+
+    let mut run = |number: u64, timestamp: u64| {
+        evm.set_block(BlockEnv {
+            number: U256::from(number),
+            timestamp: U256::from(timestamp),
+            ..Default::default()
+        });
+        let output = evm
+            .transact(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        (evm.ctx.cfg.spec, output.result.is_success())
+    };
+
+    // PUSH0 is enabled in Shanghai.
+    assert_eq!(run(17_034_869, 1_681_338_443), (SpecId::MERGE, false));
+    assert_eq!(run(17_034_870, 1_681_338_455), (SpecId::SHANGHAI, true));
+    assert_eq!(run(4_370_000, 1_508_131_331), (SpecId::BYZANTIUM, false));
+}
+
+#[test]
+fn test_create_address_policy() {
+    use context::cfg::{CreateAddressPolicy, StandardCreateAddress};
+    use primitives::{keccak256, B256};
+    use revm::context::CfgEnv;
+
+    /// Mixes the chain ID into `CREATE2` salts and namespaces `CREATE` deployments.
+    #[derive(Debug)]
+    struct NamespacedCreate(u64);
+
+    impl CreateAddressPolicy for NamespacedCreate {
+        fn create_address(&self, caller: Address, nonce: u64) -> Address {
+            let mut address = caller.create(nonce);
+            address.0[0] = 0xee;
+            address
+        }
+
+        fn create2_address(&self, caller: Address, salt: U256, init_code_hash: B256) -> Address {
+            StandardCreateAddress.create2_address(caller, salt ^ U256::from(self.0), init_code_hash)
+        }
+    }
+
+    // Stores the address created by create2(0, 0, 0, 1) in slot zero.
+    let initcode = [
+        opcode::PUSH1,
+        0x01,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::PUSH0,
+        opcode::CREATE2,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        opcode::STOP,
+    ];
+    let policy = NamespacedCreate(10);
+    let mut evm = Context::mainnet()
+        .with_cfg(CfgEnv::new().with_create_address_policy(NamespacedCreate(10)))
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::default()))
+        .build_mainnet();
+    let output = evm
+        .transact(
+            TxEnv::builder_for_bench()
+                .kind(TxKind::Create)
+                .data(Bytes::copy_from_slice(&initcode))
+                .build_fill(),
+        )
+        .unwrap();
+
+    let created = policy.create_address(BENCH_CALLER, 0);
+    assert_eq!(output.result.created_address(), Some(created));
+    let child = policy.create2_address(created, U256::ONE, keccak256([]));
+    assert_ne!(
+        child,
+        created.create2(B256::with_last_byte(1), keccak256([]))
+    );
+    assert_eq!(
+        output.state[&created].storage[&U256::ZERO].present_value,
+        child.into_word().into()
+    );
+}
+
+#[test]
+fn test_analysis_store() {
+    use primitives::keccak256;
+    use revm::{
+        bytecode::{AnalysisStore, ArtifactKind, InMemoryAnalysisStore},
+        context::CfgEnv,
+    };
+    use std::sync::Arc;
+
+    // Returns the runtime code `JUMPDEST PUSH1 0x5b STOP`.
+    let runtime = [opcode::JUMPDEST, opcode::PUSH1, 0x5b, opcode::STOP];
+    let mut initcode = vec![opcode::PUSH4];
+    initcode.extend_from_slice(&runtime);
+    initcode.extend_from_slice(&[
+        opcode::PUSH0,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        0x04,
+        opcode::PUSH1,
+        0x1c,
+        opcode::RETURN,
+    ]);
+
+    let store = Arc::new(InMemoryAnalysisStore::new());
+    let mut evm = Context::mainnet()
+        .with_cfg(CfgEnv::new().with_analysis_store(store.clone()))
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::default()))
+        .build_mainnet();
+    let output = evm
+        .transact(
+            TxEnv::builder_for_bench()
+                .kind(TxKind::Create)
+                .data(initcode.into())
+                .build_fill(),
+        )
+        .unwrap();
+
+    let created = output.result.created_address().unwrap();
+    let code = output.state[&created].info.code.clone().unwrap();
+    assert_eq!(code.original_byte_slice(), runtime);
+    let artifact = store
+        .load(keccak256(runtime), ArtifactKind::JumpTable)
+        .unwrap();
+    let Bytecode::LegacyAnalyzed(analyzed) = code else {
+        panic!("created code is not legacy");
+    };
+    assert_eq!(artifact, analyzed.encode_artifact());
+}
+
+#[test]
+fn test_caller_code_policy() {
+    use context::{cfg::CallerCodePolicy, result::InvalidTransaction};
+    use revm::{
+        context::result::EVMError,
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    let run = |caller_code: Bytecode, policy: CallerCodePolicy| {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            BENCH_CALLER,
+            AccountInfo::from_bytecode(caller_code)
+                .with_balance(U256::from(10).pow(U256::from(18))),
+        );
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.caller_code_policy = policy)
+            .with_db(db)
+            .build_mainnet();
+        evm.transact_one(TxEnv::builder_for_bench().nonce(1).build_fill())
+            .map(|result| result.is_success())
+    };
+
+    let delegation = Bytecode::new_eip7702(BENCH_TARGET);
+    let contract = Bytecode::new_legacy([opcode::STOP].into());
+
+    assert_eq!(
+        run(delegation.clone(), CallerCodePolicy::AllowDelegated),
+        Ok(true)
+    );
+    assert!(matches!(
+        run(delegation.clone(), CallerCodePolicy::Strict),
+        Err(EVMError::Transaction(
+            InvalidTransaction::RejectDelegatedCaller
+        ))
+    ));
+    assert!(matches!(
+        run(contract.clone(), CallerCodePolicy::AllowDelegated),
+        Err(EVMError::Transaction(
+            InvalidTransaction::RejectCallerWithCode
+        ))
+    ));
+    #[cfg(feature = "optional_eip3607")]
+    {
+        assert_eq!(run(contract, CallerCodePolicy::Disabled), Ok(true));
+        assert_eq!(run(delegation, CallerCodePolicy::Disabled), Ok(true));
+    }
+}
+
+#[test]
+fn test_blob_sidecar_verification() {
+    use context::{
+        cfg::BlobSidecarMode,
+        result::InvalidTransaction,
+        transaction::{BlobSidecar, TransactionType},
+    };
+    use revm::{
+        context::result::EVMError,
+        precompile::kzg_point_evaluation::{kzg_to_versioned_hash, BYTES_PER_BLOB},
+        primitives::FixedBytes,
+    };
+
+    // Commitment and proof of the zero blob are the point at infinity.
+    let mut infinity = FixedBytes::<48>::ZERO;
+    infinity[0] = 0xc0;
+    let versioned_hash = kzg_to_versioned_hash(infinity.as_slice()).into();
+    let sidecar = |blob: Bytes| BlobSidecar::new(vec![blob], vec![infinity], vec![infinity]);
+
+    let run = |mode: BlobSidecarMode, sidecar: Option<BlobSidecar>| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.blob_sidecar_mode = mode)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [opcode::STOP].into(),
+            )))
+            .build_mainnet();
+        let tx = TxEnv::builder_for_bench()
+            .tx_type(Some(TransactionType::Eip4844 as u8))
+            .gas_priority_fee(Some(0))
+            .blob_hashes(vec![versioned_hash])
+            .max_fee_per_blob_gas(1)
+            .blob_sidecar(sidecar)
+            .build_fill();
+        evm.transact_one(tx).map(|result| result.is_success())
+    };
+
+    let zero_blob = Bytes::from(vec![0; BYTES_PER_BLOB]);
+    let mut invalid_blob = vec![0; BYTES_PER_BLOB];
+    invalid_blob[31] = 1;
+
+    assert_eq!(run(BlobSidecarMode::HashesOnly, None), Ok(true));
+    assert_eq!(
+        run(BlobSidecarMode::Verify, Some(sidecar(zero_blob.clone()))),
+        Ok(true)
+    );
+    assert!(matches!(
+        run(BlobSidecarMode::Verify, None),
+        Err(EVMError::Transaction(
+            InvalidTransaction::MissingBlobSidecar
+        ))
+    ));
+    assert!(matches!(
+        run(BlobSidecarMode::Verify, Some(sidecar(invalid_blob.into()))),
+        Err(EVMError::Transaction(
+            InvalidTransaction::InvalidBlobKzgProof { index: 0 }
+        ))
+    ));
+    let mut mismatched = sidecar(zero_blob);
+    mismatched.proofs.clear();
+    assert!(matches!(
+        run(BlobSidecarMode::Verify, Some(mismatched)),
+        Err(EVMError::Transaction(
+            InvalidTransaction::BlobSidecarMismatch { index: None }
+        ))
+    ));
+}
+
+#[test]
+fn test_call_stack_introspection() {
+    use context::{ContextTr, LocalContextTr};
+    use revm::{
+        interpreter::{CallInputs, CallOutcome},
+        InspectEvm, Inspector,
+    };
+
+    #[derive(Default)]
+    struct CallStackInspector {
+        calls: Vec<(usize, Vec<Address>, u64, u64)>,
+        depth_after_call: Vec<usize>,
+    }
+
+    impl<CTX: ContextTr> Inspector<CTX> for CallStackInspector {
+        fn call(&mut self, context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+            let local = context.local();
+            let active = local.active_frame().copied();
+            self.calls.push((
+                local.call_depth(),
+                local.call_stack_addresses().collect(),
+                active.map(|frame| frame.gas_limit).unwrap_or_default(),
+                active.map(|frame| frame.remaining_gas).unwrap_or_default(),
+            ));
+            None
+        }
+
+        fn call_end(
+            &mut self,
+            context: &mut CTX,
+            _inputs: &CallInputs,
+            _outcome: &mut CallOutcome,
+        ) {
+            self.depth_after_call.push(context.local().call_depth());
+        }
+    }
+
+    // call(0x1000, 0x04, 0, 0, 0, 0, 0)
+    let bytecode = Bytecode::new_legacy(
+        [
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            0x04,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::CALL,
+            opcode::STOP,
+        ]
+        .into(),
+    );
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(bytecode))
+        .build_mainnet_with_inspector(CallStackInspector::default());
+    let result = evm
+        .inspect_one_tx(TxEnv::builder_for_bench().gas_limit(100_000).build_fill())
+        .unwrap();
+    assert!(result.is_success());
+
+    let inspector = &evm.inspector;
+    // Transaction call is inspected before its frame is pushed.
+    assert_eq!(inspector.calls[0], (0, vec![], 0, 0));
+    let (depth, addresses, gas_limit, remaining_gas) = &inspector.calls[1];
+    assert_eq!(*depth, 1);
+    assert_eq!(addresses, &[BENCH_TARGET]);
+    assert_eq!(*gas_limit, 100_000 - 21_000);
+    assert!(*remaining_gas < *gas_limit - 0x1000);
+    assert_eq!(inspector.depth_after_call, [1, 0]);
+}
+
+#[test]
+fn test_journal_account_existence() {
+    use context::JournalTr;
+    use revm::{
+        interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+        InspectEvm, Inspector,
+    };
+
+    const EMPTY_TARGET: Address = address!("0x0000000000000000000000000000000000001000");
+    const UNKNOWN: Address = address!("0x0000000000000000000000000000000000002000");
+
+    type Existence = (Option<bool>, Option<bool>, Option<bool>);
+
+    #[derive(Default)]
+    struct ExistenceInspector {
+        created: Option<Address>,
+        accounts: Vec<Existence>,
+    }
+
+    fn existence<J: JournalTr>(journal: &J, address: Address) -> Existence {
+        (
+            journal.account_existed(address),
+            journal.is_account_created(address),
+            journal.is_account_touched_and_empty(address),
+        )
+    }
+
+    impl<CTX: ContextTr> Inspector<CTX> for ExistenceInspector {
+        fn create_end(
+            &mut self,
+            _context: &mut CTX,
+            _inputs: &CreateInputs,
+            outcome: &mut CreateOutcome,
+        ) {
+            self.created = outcome.address;
+        }
+
+        fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, _outcome: &mut CallOutcome) {
+            if inputs.target_address != BENCH_TARGET {
+                return;
+            }
+            let journal = context.journal_ref();
+            for address in [self.created.unwrap(), EMPTY_TARGET, BENCH_TARGET, UNKNOWN] {
+                self.accounts.push(existence(journal, address));
+            }
+        }
+    }
+
+    // create(0, 0, 0), call(0, 0x1000, 0, 0, 0, 0, 0)
+    let bytecode = Bytecode::new_legacy(
+        [
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CREATE,
+            opcode::POP,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::PUSH0,
+            opcode::CALL,
+            opcode::STOP,
+        ]
+        .into(),
+    );
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(bytecode))
+        .build_mainnet_with_inspector(ExistenceInspector::default());
+    let result = evm
+        .inspect_one_tx(TxEnv::builder_for_bench().gas_limit(200_000).build_fill())
+        .unwrap();
+    assert!(result.is_success());
+
+    assert_eq!(
+        evm.inspector.accounts,
+        [
+            (Some(false), Some(true), Some(false)),
+            (Some(false), Some(false), Some(true)),
+            (Some(true), Some(false), Some(false)),
+            (None, None, None),
+        ]
+    );
+    // Creation flag is scoped to the transaction.
+    let created = evm.inspector.created.unwrap();
+    assert_eq!(
+        evm.ctx.journal_ref().is_account_created(created),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_keccak_cache() {
+    // keccak256(0, 32) twice, return(0, 64) with both hashes.
+    let bytecode = Bytecode::new_legacy(
+        [
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH0,
+            opcode::KECCAK256,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]
+        .into(),
+    );
+    let expected = primitives::keccak256([0u8; 32]);
+    for cache_size in [0, 16] {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.keccak_cache_size = cache_size)
+            .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
+            .build_mainnet();
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().build_fill())
+            .unwrap();
+        let output = result.output().unwrap();
+        assert_eq!(output[..32], expected[..]);
+        assert_eq!(output[32..], expected[..]);
+        // Cache is scoped to the transaction.
+        assert!(evm.ctx.local.keccak_cache.is_empty());
+    }
+}
+
+#[test]
+fn test_set_account_shortcuts() {
+    use database::{CacheDB, EmptyDB};
+    use state::AccountInfo;
+
+    // mstore(0, sload(1)) sstore(1, 8) return(0, 32)
+    let bytecode = Bytecode::new_legacy(
+        [
+            opcode::PUSH1,
+            0x01,
+            opcode::SLOAD,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x08,
+            opcode::PUSH1,
+            0x01,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH0,
+            opcode::RETURN,
+        ]
+        .into(),
+    );
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .nonce(5)
+        .gas_limit(100_000)
+        .gas_price(1)
+        .build()
+        .unwrap();
+
+    let mut ctx = Context::mainnet();
+    ctx.set_account_balance(BENCH_CALLER, U256::from(1_000_000))
+        .unwrap();
+    ctx.set_account_nonce(BENCH_CALLER, 5).unwrap();
+    ctx.set_account_code(BENCH_TARGET, bytecode.clone())
+        .unwrap();
+    ctx.set_account_storage(BENCH_TARGET, U256::from(1), U256::from(7))
+        .unwrap();
+    let mut evm = ctx.build_mainnet();
+
+    let output = evm.transact(tx.clone()).unwrap();
+    assert_eq!(
+        output.result.output().unwrap().as_ref(),
+        U256::from(7).to_be_bytes::<32>()
+    );
+    let target = &output.state[&BENCH_TARGET];
+    assert_eq!(target.info.code_hash, bytecode.hash_slow());
+    let slot = &target.storage[&U256::from(1)];
+    assert_eq!(
+        (slot.original_value, slot.present_value),
+        (U256::from(7), U256::from(8))
+    );
+
+    // Accounts stay cold and slot is clean, gas is the same as with accounts inserted into the
+    // database.
+    let mut db = CacheDB::<EmptyDB>::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(U256::from(1_000_000)).with_nonce(5),
+    );
+    db.insert_account_info(BENCH_TARGET, AccountInfo::default().with_code(bytecode));
+    db.insert_account_storage(BENCH_TARGET, U256::from(1), U256::from(7))
+        .unwrap();
+    let mut evm = Context::mainnet().with_db(db).build_mainnet();
+    let expected = evm.transact(tx).unwrap();
+    assert_eq!(output.result, expected.result);
+}
+
+#[test]
+fn test_finalized_state_mode() {
+    use context::cfg::FinalizedStateMode;
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    let poor = address!("0x1000000000000000000000000000000000000001");
+    let empty = address!("0x1000000000000000000000000000000000000002");
+    let reverting = address!("0x1000000000000000000000000000000000000003");
+
+    let mut code = Vec::new();
+    // call(gas, poor, 1, 0, 0, 0, 0) fails, the contract has no balance.
+    code.extend([opcode::PUSH0; 4]);
+    code.extend([opcode::PUSH1, 0x01, opcode::PUSH20]);
+    code.extend_from_slice(poor.as_slice());
+    code.extend([opcode::GAS, opcode::CALL, opcode::POP]);
+    // call(gas, empty, 0, 0, 0, 0, 0) touches the account without changing it.
+    code.extend([opcode::PUSH0; 5]);
+    code.push(opcode::PUSH20);
+    code.extend_from_slice(empty.as_slice());
+    code.extend([opcode::GAS, opcode::CALL, opcode::POP]);
+    // sstore(0, 1)
+    code.extend([
+        opcode::PUSH1,
+        0x01,
+        opcode::PUSH0,
+        opcode::SSTORE,
+        opcode::STOP,
+    ]);
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(BENCH_CALLER, AccountInfo::from_balance(U256::from(10)));
+    db.insert_account_info(
+        BENCH_TARGET,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+    );
+    db.insert_account_info(
+        reverting,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(
+            [opcode::PUSH0, opcode::PUSH0, opcode::REVERT].into(),
+        )),
+    );
+
+    let run = |mode: FinalizedStateMode, target: Address, value: u64| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.finalized_state_mode = mode)
+            .with_db(db.clone())
+            .build_mainnet();
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(target))
+            .value(U256::from(value))
+            .gas_limit(1_000_000)
+            .build()
+            .unwrap();
+        let output = evm.transact(tx).unwrap();
+        let mut accounts: Vec<_> = output.state.keys().copied().collect();
+        accounts.sort_unstable();
+        accounts
+    };
+
+    let coinbase = Address::ZERO;
+    assert_eq!(
+        run(FinalizedStateMode::Loaded, BENCH_TARGET, 0),
+        [coinbase, poor, empty, BENCH_CALLER, BENCH_TARGET]
+    );
+    // Failed value transfer loads the account without touching it.
+    assert_eq!(
+        run(FinalizedStateMode::Touched, BENCH_TARGET, 0),
+        [coinbase, empty, BENCH_CALLER, BENCH_TARGET]
+    );
+    // Coinbase receives no fee and the empty account is not created.
+    assert_eq!(
+        run(FinalizedStateMode::Changed, BENCH_TARGET, 0),
+        [BENCH_CALLER, BENCH_TARGET]
+    );
+
+    // Value transfer of a reverted transaction is reverted together with the touch.
+    assert_eq!(
+        run(FinalizedStateMode::Loaded, reverting, 1),
+        [coinbase, reverting, BENCH_CALLER]
+    );
+    assert_eq!(
+        run(FinalizedStateMode::Touched, reverting, 1),
+        [coinbase, BENCH_CALLER]
+    );
+    assert_eq!(
+        run(FinalizedStateMode::Changed, reverting, 1),
+        [BENCH_CALLER]
+    );
+}
+
+#[test]
+fn test_access_log() {
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            [
+                // sload(0), sstore(1, 1), sload(0)
+                opcode::PUSH0,
+                opcode::SLOAD,
+                opcode::PUSH1,
+                0x01,
+                opcode::DUP1,
+                opcode::SSTORE,
+                opcode::PUSH0,
+                opcode::SLOAD,
+                opcode::STOP,
+            ]
+            .into(),
+        )))
+        .build_mainnet();
+    evm.ctx.journal_mut().start_access_log();
+
+    for nonce in 0..2 {
+        let result = evm
+            .transact_one(TxEnv::builder_for_bench().nonce(nonce).build_fill())
+            .unwrap();
+        assert!(result.is_success());
+    }
+    let logs = evm.ctx.journal_mut().take_access_logs();
+    assert_eq!(logs.len(), 2);
+
+    let caller = logs[0].account(BENCH_CALLER).unwrap();
+    assert!(caller.written);
+    let target = logs[0].account(BENCH_TARGET).unwrap();
+    assert!(target.cold);
+    assert!(!target.written);
+    let slots: Vec<_> = target
+        .slots
+        .iter()
+        .map(|slot| (slot.key, slot.cold, slot.written))
+        .collect();
+    assert_eq!(
+        slots,
+        [(U256::ZERO, true, false), (U256::from(1), true, true)]
+    );
+
+    // Slot already holds the value in the second transaction.
+    let target = logs[1].account(BENCH_TARGET).unwrap();
+    assert!(target.slots.iter().all(|slot| slot.cold && !slot.written));
+    assert!(logs[0].conflicts_with(&logs[1]));
+    assert!(evm.ctx.journal_mut().take_access_logs().is_empty());
+}
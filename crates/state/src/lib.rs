@@ -2,12 +2,17 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc as std;
+
 mod account_info;
+mod ordered;
 mod types;
 pub use bytecode;
 
 pub use account_info::AccountInfo;
 pub use bytecode::Bytecode;
+pub use ordered::{ordered_state, OrderedAccount, OrderedEvmState, OrderedEvmStorage};
 pub use primitives;
 pub use types::{EvmState, EvmStorage, TransientStorage};
 
@@ -0,0 +1,93 @@
+use super::{Account, AccountInfo, AccountStatus, EvmState, EvmStorageSlot};
+use primitives::{Address, StorageKey};
+use std::collections::BTreeMap;
+
+/// [`EvmState`] with accounts and storage ordered by address and storage key.
+///
+/// Iteration order, `Debug` and serialized output of the ordered state don't depend on the
+/// order of the execution or on the hasher, which makes it suitable for snapshots, hashing and
+/// diffs of the state of different runs.
+pub type OrderedEvmState = BTreeMap<Address, OrderedAccount>;
+
+/// [`EvmStorage`](crate::EvmStorage) ordered by storage key.
+pub type OrderedEvmStorage = BTreeMap<StorageKey, EvmStorageSlot>;
+
+/// [`Account`] with storage ordered by storage key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderedAccount {
+    /// Balance, nonce, and code
+    pub info: AccountInfo,
+    /// Transaction id, used to track when account was toched/loaded into journal.
+    pub transaction_id: usize,
+    /// Storage ordered by storage key.
+    pub storage: OrderedEvmStorage,
+    /// Account status flags
+    pub status: AccountStatus,
+}
+
+impl From<Account> for OrderedAccount {
+    fn from(account: Account) -> Self {
+        Self {
+            info: account.info,
+            transaction_id: account.transaction_id,
+            storage: account.storage.into_iter().collect(),
+            status: account.status,
+        }
+    }
+}
+
+impl From<OrderedAccount> for Account {
+    fn from(account: OrderedAccount) -> Self {
+        Self {
+            info: account.info,
+            transaction_id: account.transaction_id,
+            storage: account.storage.into_iter().collect(),
+            status: account.status,
+        }
+    }
+}
+
+/// Converts the state into the [`OrderedEvmState`].
+pub fn ordered_state(state: EvmState) -> OrderedEvmState {
+    state
+        .into_iter()
+        .map(|(address, account)| (address, account.into()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{HashMap, U256};
+
+    #[test]
+    fn ordered_state_is_sorted() {
+        let mut state = EvmState::default();
+        for n in (0..32u8).rev() {
+            let storage = (0..8u64)
+                .rev()
+                .map(|key| (U256::from(key), EvmStorageSlot::new(U256::from(n), 0)))
+                .collect::<HashMap<_, _>>();
+            state.insert(
+                Address::with_last_byte(n),
+                Account {
+                    storage,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let ordered = ordered_state(state.clone());
+        assert!(ordered.keys().is_sorted());
+        assert!(ordered
+            .values()
+            .all(|account| account.storage.keys().is_sorted()));
+
+        let roundtrip: EvmState = ordered
+            .into_iter()
+            .map(|(address, account)| (address, account.into()))
+            .collect();
+        assert_eq!(roundtrip, state);
+    }
+}
@@ -0,0 +1,394 @@
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+
+use crate::TestAuthorization;
+use revm::{
+    context::{block::BlockEnv, cfg::CfgEnv, tx::TxEnv, TransactionType},
+    context_interface::transaction::AccessList,
+    database::DatabaseRef,
+    primitives::{Address, Bytes, TxKind, B256, U256},
+    state::{Account, EvmState},
+};
+
+/// State test filler in the ethereum/tests format.
+///
+/// Created from an executed transaction with [`StateTestFiller::from_execution`], the pre-state
+/// contains only the accounts and storage slots loaded by the transaction and the expected
+/// result contains the accounts it touched.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StateTestFiller {
+    /// Block environment.
+    pub env: FillerEnv,
+    /// Accounts before the transaction.
+    pub pre: BTreeMap<Address, FillerAccount>,
+    /// Executed transaction.
+    pub transaction: FillerTransaction,
+    /// Expected results of the transaction.
+    pub expect: Vec<FillerExpect>,
+}
+
+/// Block environment of a [`StateTestFiller`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillerEnv {
+    /// Chain ID, omitted for mainnet.
+    #[serde(rename = "currentChainID", skip_serializing_if = "Option::is_none")]
+    pub current_chain_id: Option<U256>,
+    /// Block coinbase address.
+    pub current_coinbase: Address,
+    /// Block difficulty.
+    pub current_difficulty: U256,
+    /// Block gas limit.
+    pub current_gas_limit: U256,
+    /// Block number.
+    pub current_number: U256,
+    /// Block timestamp.
+    pub current_timestamp: U256,
+    /// EIP-1559 base fee per gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_base_fee: Option<U256>,
+    /// EIP-4399 prevrandao.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_random: Option<B256>,
+    /// EIP-4844 excess blob gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_excess_blob_gas: Option<U256>,
+}
+
+/// Account of a [`StateTestFiller`] pre-state or expected result.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FillerAccount {
+    /// Account balance.
+    pub balance: U256,
+    /// Account bytecode.
+    pub code: Bytes,
+    /// Account nonce.
+    pub nonce: U256,
+    /// Non-zero storage slots.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// Transaction of a [`StateTestFiller`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillerTransaction {
+    /// Transaction data.
+    pub data: Vec<Bytes>,
+    /// Gas limit.
+    pub gas_limit: Vec<U256>,
+    /// Gas price of legacy and EIP-2930 transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    /// Maximum fee per gas of EIP-1559 and later transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Maximum priority fee per gas of EIP-1559 and later transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Transaction nonce.
+    pub nonce: U256,
+    /// Private key of the sender.
+    ///
+    /// Key of the original sender is not known, it is zero unless set with
+    /// [`StateTestFiller::with_secret_key`].
+    pub secret_key: B256,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Recipient, empty for contract creation.
+    #[serde(serialize_with = "serialize_maybe_empty")]
+    pub to: Option<Address>,
+    /// Transferred value.
+    pub value: Vec<U256>,
+    /// EIP-2930 access list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_lists: Option<Vec<AccessList>>,
+    /// EIP-7702 authorization list, only signed authorizations are included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<TestAuthorization>>,
+    /// EIP-4844 blob versioned hashes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blob_versioned_hashes: Vec<B256>,
+    /// EIP-4844 maximum fee per blob gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+}
+
+/// Expected result of a [`StateTestFiller`] for a set of networks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FillerExpect {
+    /// Transaction part indices, `-1` matches all.
+    pub indexes: FillerIndexes,
+    /// Names of the networks the expectation applies to.
+    pub network: Vec<String>,
+    /// Expected accounts after the transaction.
+    pub result: BTreeMap<Address, FillerPostAccount>,
+}
+
+/// Transaction part indices of a [`FillerExpect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FillerIndexes {
+    /// Index into the data array.
+    pub data: i64,
+    /// Index into the gas limit array.
+    pub gas: i64,
+    /// Index into the value array.
+    pub value: i64,
+}
+
+impl Default for FillerIndexes {
+    fn default() -> Self {
+        Self {
+            data: -1,
+            gas: -1,
+            value: -1,
+        }
+    }
+}
+
+/// Expected account of a [`FillerExpect`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum FillerPostAccount {
+    /// Account exists with the given fields.
+    Exists(FillerAccount),
+    /// Account does not exist.
+    NotExists {
+        /// Always `1`.
+        #[serde(rename = "shouldnotexist")]
+        should_not_exist: u8,
+    },
+}
+
+impl StateTestFiller {
+    /// Creates a filler from an executed transaction.
+    ///
+    /// `db` has to hold the state before the transaction, `state` is the finalized state of the
+    /// transaction that is not yet committed to `db`.
+    pub fn from_execution<DB: DatabaseRef>(
+        db: &DB,
+        cfg: &CfgEnv,
+        block: &BlockEnv,
+        tx: &TxEnv,
+        state: &EvmState,
+    ) -> Result<Self, DB::Error> {
+        let mut pre = BTreeMap::new();
+        let mut result = BTreeMap::new();
+        for (address, account) in state {
+            if let Some(info) = db.basic_ref(*address)? {
+                let code = match info.code {
+                    Some(code) => code.original_bytes(),
+                    None => db.code_by_hash_ref(info.code_hash)?.original_bytes(),
+                };
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| !slot.original_value.is_zero())
+                    .map(|(key, slot)| (*key, slot.original_value))
+                    .collect();
+                pre.insert(
+                    *address,
+                    FillerAccount {
+                        balance: info.balance,
+                        code,
+                        nonce: U256::from(info.nonce),
+                        storage,
+                    },
+                );
+            }
+
+            if account.is_touched() {
+                result.insert(*address, post_account(account, cfg));
+            }
+        }
+
+        let network: &'static str = cfg.spec.into();
+        Ok(Self {
+            env: FillerEnv {
+                current_chain_id: (cfg.chain_id != 1).then(|| U256::from(cfg.chain_id)),
+                current_coinbase: block.beneficiary,
+                current_difficulty: block.difficulty,
+                current_gas_limit: U256::from(block.gas_limit),
+                current_number: block.number,
+                current_timestamp: block.timestamp,
+                current_base_fee: Some(U256::from(block.basefee)),
+                current_random: block.prevrandao,
+                current_excess_blob_gas: block
+                    .blob_excess_gas_and_price
+                    .map(|blob| U256::from(blob.excess_blob_gas)),
+            },
+            pre,
+            transaction: FillerTransaction::from(tx),
+            expect: vec![FillerExpect {
+                indexes: FillerIndexes::default(),
+                network: vec![network.to_string()],
+                result,
+            }],
+        })
+    }
+
+    /// Sets the private key used to sign the transaction.
+    pub fn with_secret_key(mut self, secret_key: B256) -> Self {
+        self.transaction.secret_key = secret_key;
+        self
+    }
+
+    /// Returns the filler JSON with the test named `name`.
+    pub fn to_json(&self, name: &str) -> serde_json::Value {
+        serde_json::json!({ name: self })
+    }
+}
+
+/// Returns the expected state of the account touched by the transaction.
+fn post_account(account: &Account, cfg: &CfgEnv) -> FillerPostAccount {
+    if account.is_selfdestructed() || account.state_clear_aware_is_empty(cfg.spec) {
+        return FillerPostAccount::NotExists {
+            should_not_exist: 1,
+        };
+    }
+    FillerPostAccount::Exists(FillerAccount {
+        balance: account.info.balance,
+        code: account
+            .info
+            .code
+            .as_ref()
+            .map(|code| code.original_bytes())
+            .unwrap_or_default(),
+        nonce: U256::from(account.info.nonce),
+        storage: account
+            .storage
+            .iter()
+            .filter(|(_, slot)| !slot.present_value.is_zero())
+            .map(|(key, slot)| (*key, slot.present_value))
+            .collect(),
+    })
+}
+
+impl From<&TxEnv> for FillerTransaction {
+    fn from(tx: &TxEnv) -> Self {
+        let tx_type = TransactionType::from(tx.tx_type);
+        let is_legacy_fee = matches!(tx_type, TransactionType::Legacy | TransactionType::Eip2930);
+        let authorization_list = (tx_type == TransactionType::Eip7702).then(|| {
+            tx.authorization_list
+                .iter()
+                .filter_map(|auth| auth.as_ref().left().cloned().map(TestAuthorization::from))
+                .collect()
+        });
+        Self {
+            data: vec![tx.data.clone()],
+            gas_limit: vec![U256::from(tx.gas_limit)],
+            gas_price: is_legacy_fee.then(|| U256::from(tx.gas_price)),
+            max_fee_per_gas: (!is_legacy_fee).then(|| U256::from(tx.gas_price)),
+            max_priority_fee_per_gas: (!is_legacy_fee)
+                .then(|| U256::from(tx.gas_priority_fee.unwrap_or_default())),
+            nonce: U256::from(tx.nonce),
+            secret_key: B256::ZERO,
+            sender: tx.caller,
+            to: match tx.kind {
+                TxKind::Call(address) => Some(address),
+                TxKind::Create => None,
+            },
+            value: vec![tx.value],
+            access_lists: (tx_type != TransactionType::Legacy)
+                .then(|| vec![tx.access_list.clone()]),
+            authorization_list,
+            blob_versioned_hashes: tx.blob_hashes.clone(),
+            max_fee_per_blob_gas: (tx_type == TransactionType::Eip4844)
+                .then(|| U256::from(tx.max_fee_per_blob_gas)),
+        }
+    }
+}
+
+/// Serializes an optional [Address] as an empty string when it is `None`.
+fn serialize_maybe_empty<S: Serializer>(
+    address: &Option<Address>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match address {
+        Some(address) => address.serialize(serializer),
+        None => serializer.serialize_str(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        bytecode::opcode,
+        database::{CacheDB, EmptyDB},
+        primitives::address,
+        state::{AccountInfo, Bytecode},
+        Context, ExecuteEvm, MainBuilder, MainContext,
+    };
+
+    #[test]
+    fn filler_from_execution() {
+        let caller = address!("0x1000000000000000000000000000000000000001");
+        let contract = address!("0x2000000000000000000000000000000000000002");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000_000_000),
+                ..Default::default()
+            },
+        );
+        // sstore(0, 1)
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [
+                    opcode::PUSH1,
+                    0x01,
+                    opcode::PUSH0,
+                    opcode::SSTORE,
+                    opcode::STOP,
+                ]
+                .into(),
+            )),
+        );
+        db.insert_account_storage(contract, U256::ZERO, U256::from(2))
+            .unwrap();
+
+        let tx = TxEnv::builder()
+            .caller(caller)
+            .kind(TxKind::Call(contract))
+            .value(U256::from(10))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+        let state = evm.transact(tx.clone()).unwrap().state;
+
+        let filler = StateTestFiller::from_execution(
+            &evm.ctx.journaled_state.database,
+            &evm.ctx.cfg,
+            &evm.ctx.block,
+            &tx,
+            &state,
+        )
+        .unwrap();
+
+        assert_eq!(filler.pre[&caller].balance, U256::from(1_000_000_000));
+        assert_eq!(
+            filler.pre[&contract].storage,
+            BTreeMap::from([(U256::ZERO, U256::from(2))])
+        );
+        let FillerPostAccount::Exists(post) = &filler.expect[0].result[&contract] else {
+            panic!("contract should exist");
+        };
+        assert_eq!(post.balance, U256::from(10));
+        assert_eq!(post.storage, BTreeMap::from([(U256::ZERO, U256::from(1))]));
+
+        let json = filler.to_json("regression");
+        let test = &json["regression"];
+        assert_eq!(test["transaction"]["to"], contract.to_string());
+        assert_eq!(test["transaction"]["sender"], caller.to_string());
+        assert_eq!(test["transaction"]["value"][0], "0xa");
+        assert_eq!(test["expect"][0]["indexes"]["data"], -1);
+        assert_eq!(
+            test["expect"][0]["network"][0],
+            <&str>::from(evm.ctx.cfg.spec)
+        );
+        assert!(test["transaction"].get("maxFeePerGas").is_none());
+    }
+}
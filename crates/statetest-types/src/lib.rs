@@ -10,6 +10,7 @@ mod account_info;
 mod deserializer;
 mod env;
 mod error;
+mod filler;
 mod spec;
 mod test;
 mod test_authorization;
@@ -22,6 +23,7 @@ pub use account_info::*;
 pub use deserializer::*;
 pub use env::*;
 pub use error::*;
+pub use filler::*;
 pub use spec::*;
 pub use test::*;
 pub use test_authorization::*;
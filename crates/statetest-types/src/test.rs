@@ -95,6 +95,7 @@ impl Test {
                 .max_priority_fee_per_gas
                 .map(|b| u128::try_from(b).expect("max priority fee less than u128::MAX")),
             blob_hashes: unit.transaction.blob_versioned_hashes.clone(),
+            blob_sidecar: None,
             max_fee_per_blob_gas: unit
                 .transaction
                 .max_fee_per_blob_gas
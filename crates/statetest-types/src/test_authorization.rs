@@ -10,6 +10,12 @@ pub struct TestAuthorization {
     inner: SignedAuthorization,
 }
 
+impl From<SignedAuthorization> for TestAuthorization {
+    fn from(inner: SignedAuthorization) -> Self {
+        Self { inner }
+    }
+}
+
 impl From<TestAuthorization> for SignedAuthorization {
     fn from(auth: TestAuthorization) -> Self {
         auth.inner
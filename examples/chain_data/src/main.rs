@@ -0,0 +1,145 @@
+//! Chain data example
+//!
+//! Attaches an oracle price to the context with [`ChainExtensions`]. The price is read from the
+//! oracle contract storage once per block and exposed to contracts by a custom precompile that
+//! is layered over the Ethereum precompiles.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use revm::{
+    context::{result::ExecutionResult, Block, Cfg, ChainData, ChainExtensions, ContextTr, TxEnv},
+    database::{CacheDB, EmptyDB},
+    handler::{ChainedPrecompiles, EthPrecompiles, PrecompileProvider},
+    interpreter::{Gas, InputsImpl, InstructionResult, InterpreterResult},
+    primitives::{address, Address, Bytes, TxKind, U256},
+    Context, Database, ExecuteCommitEvm, MainBuilder, MainContext,
+};
+
+/// Contract that stores the price in its first storage slot.
+const ORACLE_CONTRACT: Address = address!("0x0000000000000000000000000000000000001000");
+
+/// Precompile that returns the price of the current block.
+const ORACLE_PRECOMPILE: Address = address!("0x0000000000000000000000000000000000000a00");
+
+/// Gas charged by the oracle precompile.
+const ORACLE_GAS: u64 = 100;
+
+/// Price of the current block, stored in the chain extensions of the context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct OraclePrice(U256);
+
+/// Provider of the oracle precompile.
+#[derive(Clone, Debug, Default)]
+struct OraclePrecompile;
+
+impl<CTX> PrecompileProvider<CTX> for OraclePrecompile
+where
+    CTX: ContextTr<Chain = ChainExtensions>,
+{
+    type Output = InterpreterResult;
+
+    fn set_spec(&mut self, _spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        false
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        _inputs: &InputsImpl,
+        _is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, String> {
+        if *address != ORACLE_PRECOMPILE {
+            return Ok(None);
+        }
+        let mut gas = Gas::new(gas_limit);
+        if !gas.record_cost(ORACLE_GAS) {
+            return Ok(Some(InterpreterResult::new(
+                InstructionResult::PrecompileOOG,
+                Bytes::new(),
+                gas,
+            )));
+        }
+
+        // Load the price once per block.
+        let block_number = context.block().number();
+        if !context
+            .chain()
+            .is_loaded_for_block::<OraclePrice>(block_number)
+        {
+            let price = context
+                .db_mut()
+                .storage(ORACLE_CONTRACT, U256::ZERO)
+                .map_err(|e| e.to_string())?;
+            context
+                .chain_mut()
+                .insert_for_block(block_number, OraclePrice(price));
+        }
+        let OraclePrice(price) = context.chain().chain_data().expect("price is loaded");
+
+        Ok(Some(InterpreterResult::new(
+            InstructionResult::Return,
+            price.to_be_bytes_vec().into(),
+            gas,
+        )))
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        Box::new(core::iter::once(ORACLE_PRECOMPILE))
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        *address == ORACLE_PRECOMPILE
+    }
+}
+
+/// Calls the oracle precompile and returns the price.
+fn read_price<EVM>(evm: &mut EVM, nonce: u64) -> anyhow::Result<U256>
+where
+    EVM: ExecuteCommitEvm<Tx = TxEnv, ExecutionResult = ExecutionResult>,
+    EVM::Error: std::error::Error + Send + Sync + 'static,
+{
+    let tx = TxEnv::builder()
+        .kind(TxKind::Call(ORACLE_PRECOMPILE))
+        .nonce(nonce)
+        .build()
+        .unwrap();
+    let result = evm.transact_commit(tx)?;
+    let Some(output) = result.output() else {
+        anyhow::bail!("oracle call failed: {result:?}");
+    };
+    Ok(U256::from_be_slice(output))
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut db = CacheDB::<EmptyDB>::default();
+    db.insert_account_storage(ORACLE_CONTRACT, U256::ZERO, U256::from(2000))?;
+
+    let mut evm = Context::mainnet()
+        .with_db(db)
+        .with_chain(ChainExtensions::new())
+        .modify_block_chained(|block| block.number = U256::from(1))
+        .build_mainnet()
+        .with_precompiles(ChainedPrecompiles::new(
+            OraclePrecompile,
+            EthPrecompiles::default(),
+        ));
+
+    let price = read_price(&mut evm, 0)?;
+    println!("Price in block 1: {price}");
+    assert_eq!(price, U256::from(2000));
+
+    // The oracle is updated, but the price of the block is already loaded.
+    evm.ctx
+        .db_mut()
+        .insert_account_storage(ORACLE_CONTRACT, U256::ZERO, U256::from(2100))?;
+    assert_eq!(read_price(&mut evm, 1)?, U256::from(2000));
+
+    // The price is loaded again in the next block.
+    evm.ctx.modify_block(|block| block.number = U256::from(2));
+    let price = read_price(&mut evm, 2)?;
+    println!("Price in block 2: {price}");
+    assert_eq!(price, U256::from(2100));
+
+    Ok(())
+}
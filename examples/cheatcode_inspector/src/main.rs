@@ -8,7 +8,9 @@
 
 use revm::{
     context::{
-        result::InvalidTransaction, BlockEnv, Cfg, CfgEnv, ContextTr, Evm, LocalContext, TxEnv,
+        cfg::{FinalizedStateMode, SelfdestructMode},
+        result::InvalidTransaction,
+        BlockEnv, Cfg, CfgEnv, ContextTr, Evm, LocalContext, TxEnv,
     },
     context_interface::{
         journaled_state::{AccountLoad, JournalCheckpoint, TransferError},
@@ -108,6 +110,16 @@ impl JournalTr for Backend {
         self.journaled_state.selfdestruct(address, target)
     }
 
+    fn selfdestruct_with_mode(
+        &mut self,
+        address: Address,
+        target: Address,
+        mode: SelfdestructMode,
+    ) -> Result<StateLoad<SelfDestructResult>, Infallible> {
+        self.journaled_state
+            .selfdestruct_with_mode(address, target, mode)
+    }
+
     fn warm_account_and_storage(
         &mut self,
         address: Address,
@@ -139,6 +151,10 @@ impl JournalTr for Backend {
         self.journaled_state.set_spec_id(spec_id);
     }
 
+    fn set_finalized_state_mode(&mut self, mode: FinalizedStateMode) {
+        self.journaled_state.set_finalized_state_mode(mode);
+    }
+
     fn touch_account(&mut self, address: Address) {
         self.journaled_state.touch_account(address);
     }
@@ -221,6 +237,18 @@ impl JournalTr for Backend {
         self.journaled_state.depth()
     }
 
+    fn account_existed(&self, address: Address) -> Option<bool> {
+        self.journaled_state.account_existed(address)
+    }
+
+    fn is_account_created(&self, address: Address) -> Option<bool> {
+        self.journaled_state.is_account_created(address)
+    }
+
+    fn is_account_touched_and_empty(&self, address: Address) -> Option<bool> {
+        self.journaled_state.is_account_touched_and_empty(address)
+    }
+
     fn finalize(&mut self) -> Self::State {
         self.journaled_state.finalize()
     }
@@ -0,0 +1,63 @@
+use crate::{evm::L2Evm, handler::L2ContextTr, L2Handler};
+use revm::{
+    context::{
+        result::{ExecResultAndState, HaltReason, InvalidTransaction},
+        ContextSetters,
+    },
+    context_interface::{
+        result::{EVMError, ExecutionResult},
+        ContextTr, Database, JournalTr,
+    },
+    handler::{EvmTr, Handler},
+    state::EvmState,
+    DatabaseCommit, ExecuteCommitEvm, ExecuteEvm,
+};
+
+/// Type alias for the error type of the L2Evm.
+type L2Error<CTX> = EVMError<<<CTX as ContextTr>::Db as Database>::Error, InvalidTransaction>;
+
+// Trait that allows to replay and transact the transaction.
+impl<CTX, INSP> ExecuteEvm for L2Evm<CTX, INSP>
+where
+    CTX: L2ContextTr + ContextSetters,
+{
+    type State = EvmState;
+    type ExecutionResult = ExecutionResult<HaltReason>;
+    type Error = L2Error<CTX>;
+
+    type Tx = <CTX as ContextTr>::Tx;
+
+    type Block = <CTX as ContextTr>::Block;
+
+    fn set_block(&mut self, block: Self::Block) {
+        self.0.ctx.set_block(block);
+    }
+
+    fn transact_one(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
+        self.0.ctx.set_tx(tx);
+        L2Handler::default().run(self)
+    }
+
+    fn finalize(&mut self) -> Self::State {
+        self.ctx().journal_mut().finalize()
+    }
+
+    fn replay(
+        &mut self,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
+        L2Handler::default().run(self).map(|result| {
+            let state = self.finalize();
+            ExecResultAndState::new(result, state)
+        })
+    }
+}
+
+// Trait allows replay_commit and transact_commit functionality.
+impl<CTX, INSP> ExecuteCommitEvm for L2Evm<CTX, INSP>
+where
+    CTX: L2ContextTr<Db: DatabaseCommit> + ContextSetters,
+{
+    fn commit(&mut self, state: Self::State) {
+        self.ctx().db_mut().commit(state);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{L1FeeInfo, L2Precompiles, L2SpecId, L2Transaction};
+use revm::{
+    context::{BlockEnv, Cfg, CfgEnv, ContextError, ContextTr, Evm, FrameStack},
+    handler::{
+        evm::FrameTr, instructions::EthInstructions, EthFrame, EvmTr, FrameInitOrResult,
+        ItemOrResult,
+    },
+    interpreter::interpreter::EthInterpreter,
+    Context, Database, Journal,
+};
+
+/// Type alias for the default context of the rollup.
+///
+/// The chain field of the context holds the L1 fee parameters of the current block.
+pub type L2Context<DB> =
+    Context<BlockEnv, L2Transaction, CfgEnv<L2SpecId>, DB, Journal<DB>, L1FeeInfo>;
+
+/// Rollup variant of the EVM.
+///
+/// Uses Ethereum instructions and frames with the rollup precompile set. Wrapping [`Evm`]
+/// allows implementing execution traits with the rollup handler.
+#[derive(Debug)]
+pub struct L2Evm<CTX, INSP = ()>(
+    pub  Evm<
+        CTX,
+        INSP,
+        EthInstructions<EthInterpreter, CTX>,
+        L2Precompiles,
+        EthFrame<EthInterpreter>,
+    >,
+);
+
+impl<CTX: ContextTr<Cfg: Cfg<Spec = L2SpecId>>, INSP> L2Evm<CTX, INSP> {
+    /// Creates a new rollup EVM with precompiles of the context spec.
+    pub fn new(ctx: CTX, inspector: INSP) -> Self {
+        let spec = ctx.cfg().spec();
+        Self(Evm {
+            ctx,
+            inspector,
+            instruction: EthInstructions::new_mainnet(),
+            precompiles: L2Precompiles::new_with_spec(spec),
+            frame_stack: FrameStack::new(),
+        })
+    }
+}
+
+impl<CTX, INSP> EvmTr for L2Evm<CTX, INSP>
+where
+    CTX: ContextTr<Cfg: Cfg<Spec = L2SpecId>>,
+{
+    type Context = CTX;
+    type Instructions = EthInstructions<EthInterpreter, CTX>;
+    type Precompiles = L2Precompiles;
+    type Frame = EthFrame<EthInterpreter>;
+
+    fn ctx(&mut self) -> &mut Self::Context {
+        &mut self.0.ctx
+    }
+
+    fn ctx_ref(&self) -> &Self::Context {
+        self.0.ctx_ref()
+    }
+
+    fn ctx_instructions(&mut self) -> (&mut Self::Context, &mut Self::Instructions) {
+        self.0.ctx_instructions()
+    }
+
+    fn ctx_precompiles(&mut self) -> (&mut Self::Context, &mut Self::Precompiles) {
+        self.0.ctx_precompiles()
+    }
+
+    fn frame_stack(&mut self) -> &mut FrameStack<Self::Frame> {
+        self.0.frame_stack()
+    }
+
+    fn frame_init(
+        &mut self,
+        frame_input: <Self::Frame as FrameTr>::FrameInit,
+    ) -> Result<
+        ItemOrResult<&mut Self::Frame, <Self::Frame as FrameTr>::FrameResult>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_init(frame_input)
+    }
+
+    fn frame_run(
+        &mut self,
+    ) -> Result<
+        FrameInitOrResult<Self::Frame>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_run()
+    }
+
+    fn frame_return_result(
+        &mut self,
+        frame_result: <Self::Frame as FrameTr>::FrameResult,
+    ) -> Result<
+        Option<<Self::Frame as FrameTr>::FrameResult>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_return_result(frame_result)
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{L1FeeInfo, L2SpecId, L2TxTr, L1_FEE_VAULT};
+use revm::{
+    context::{
+        result::{HaltReason, InvalidTransaction},
+        Cfg,
+    },
+    context_interface::{transaction::TransactionType, Block, ContextTr, JournalTr, Transaction},
+    handler::{evm::FrameTr, handler::EvmTrError, EvmTr, FrameResult, Handler, MainnetHandler},
+    interpreter::interpreter_action::FrameInit,
+    primitives::U256,
+    state::EvmState,
+    Database,
+};
+
+/// Context of the rollup, the chain field holds the L1 fee parameters.
+pub trait L2ContextTr:
+    ContextTr<
+    Journal: JournalTr<State = EvmState>,
+    Tx: L2TxTr,
+    Cfg: Cfg<Spec = L2SpecId>,
+    Chain = L1FeeInfo,
+>
+{
+}
+
+impl<T> L2ContextTr for T where
+    T: ContextTr<
+        Journal: JournalTr<State = EvmState>,
+        Tx: L2TxTr,
+        Cfg: Cfg<Spec = L2SpecId>,
+        Chain = L1FeeInfo,
+    >
+{
+}
+
+/// Rollup handler.
+///
+/// Extends the mainnet handler by:
+/// * rejecting blob transactions, the rollup has no blob space,
+/// * charging the L1 fee from the caller together with the L2 gas,
+/// * paying the L1 fee to [`L1_FEE_VAULT`].
+#[derive(Debug)]
+pub struct L2Handler<EVM, ERROR, FRAME> {
+    /// Mainnet handler used for the parts that are not changed.
+    pub mainnet: MainnetHandler<EVM, ERROR, FRAME>,
+}
+
+impl<EVM, ERROR, FRAME> Default for L2Handler<EVM, ERROR, FRAME> {
+    fn default() -> Self {
+        Self {
+            mainnet: MainnetHandler::default(),
+        }
+    }
+}
+
+/// Returns the L1 fee of the current transaction, refreshing the fee parameters on a new block.
+fn tx_l1_fee<CTX: L2ContextTr>(ctx: &mut CTX) -> Result<U256, <CTX::Db as Database>::Error> {
+    let Some(rlp_bytes) = ctx.tx().rlp_bytes().cloned() else {
+        return Ok(U256::ZERO);
+    };
+    let spec = ctx.cfg().spec();
+    let block_number = ctx.block().number();
+    if ctx.chain().l2_block != block_number {
+        *ctx.chain_mut() = L1FeeInfo::try_fetch(ctx.db_mut(), block_number, spec)?;
+    }
+    Ok(ctx.chain().calculate_tx_l1_fee(&rlp_bytes, spec))
+}
+
+impl<EVM, ERROR, FRAME> Handler for L2Handler<EVM, ERROR, FRAME>
+where
+    EVM: EvmTr<Context: L2ContextTr, Frame = FRAME>,
+    ERROR: EvmTrError<EVM>,
+    FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+{
+    type Evm = EVM;
+    type Error = ERROR;
+    type HaltReason = HaltReason;
+
+    fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        if evm.ctx().tx().tx_type() == TransactionType::Eip4844 as u8 {
+            return Err(InvalidTransaction::Eip4844NotSupported.into());
+        }
+        self.mainnet.validate_env(evm)
+    }
+
+    fn validate_against_state_and_deduct_caller(
+        &self,
+        evm: &mut Self::Evm,
+    ) -> Result<(), Self::Error> {
+        self.mainnet.validate_against_state_and_deduct_caller(evm)?;
+
+        let ctx = evm.ctx();
+        let l1_fee = tx_l1_fee(ctx)?;
+        if l1_fee.is_zero() {
+            return Ok(());
+        }
+
+        let caller = ctx.tx().caller();
+        let value = ctx.tx().value();
+        let is_balance_check_disabled = ctx.cfg().is_balance_check_disabled();
+        let journal = ctx.journal_mut();
+        let caller_account = journal.load_account(caller)?.data;
+        let old_balance = caller_account.info.balance;
+
+        // Value is transferred later, so the balance still has to cover it.
+        let required = l1_fee.saturating_add(value);
+        if old_balance < required && !is_balance_check_disabled {
+            return Err(InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(required),
+                balance: Box::new(old_balance),
+            }
+            .into());
+        }
+        caller_account.info.balance = old_balance.saturating_sub(l1_fee).max(value);
+        journal.caller_accounting_journal_entry(caller, old_balance, false);
+        Ok(())
+    }
+
+    fn reward_beneficiary(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &mut FrameResult,
+    ) -> Result<(), Self::Error> {
+        self.mainnet.reward_beneficiary(evm, exec_result)?;
+
+        let ctx = evm.ctx();
+        let l1_fee = tx_l1_fee(ctx)?;
+        ctx.journal_mut().balance_incr(L1_FEE_VAULT, l1_fee)?;
+        Ok(())
+    }
+}
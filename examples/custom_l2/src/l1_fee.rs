@@ -0,0 +1,105 @@
+//! L1 data fee read from the gas price oracle predeploy.
+use crate::L2SpecId;
+use revm::{
+    primitives::{address, Address, U256},
+    Database,
+};
+
+/// Address of the L1 gas price oracle predeploy.
+pub const L1_GAS_PRICE_ORACLE_ADDRESS: Address =
+    address!("0x5300000000000000000000000000000000000002");
+
+/// Address of the vault that receives L1 fees.
+pub const L1_FEE_VAULT: Address = address!("0x5300000000000000000000000000000000000005");
+
+/// Storage slot of the L1 base fee.
+pub const L1_BASE_FEE_SLOT: U256 = U256::from_limbs([1, 0, 0, 0]);
+/// Storage slot of the fixed L1 gas overhead.
+pub const L1_OVERHEAD_SLOT: U256 = U256::from_limbs([2, 0, 0, 0]);
+/// Storage slot of the L1 fee scalar.
+pub const L1_SCALAR_SLOT: U256 = U256::from_limbs([3, 0, 0, 0]);
+/// Storage slot of the L1 blob base fee, used since Curie.
+pub const L1_BLOB_BASE_FEE_SLOT: U256 = U256::from_limbs([5, 0, 0, 0]);
+/// Storage slot of the commit scalar, used since Curie.
+pub const COMMIT_SCALAR_SLOT: U256 = U256::from_limbs([6, 0, 0, 0]);
+/// Storage slot of the blob scalar, used since Curie.
+pub const BLOB_SCALAR_SLOT: U256 = U256::from_limbs([7, 0, 0, 0]);
+
+/// Scalars are fixed point numbers with 9 decimals.
+pub const PRECISION: U256 = U256::from_limbs([1_000_000_000, 0, 0, 0]);
+
+/// L1 gas charged for the signature, which is not part of the L1 gas of unsigned data.
+const TX_SIGNATURE_L1_GAS: u64 = 4 * 16;
+
+/// L1 fee parameters of the current L2 block, stored as the chain field of the context.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct L1FeeInfo {
+    /// L2 block the parameters were fetched for.
+    pub l2_block: U256,
+    /// Base fee of the L1 block.
+    pub l1_base_fee: U256,
+    /// Fixed L1 gas overhead added to every transaction.
+    pub l1_overhead: U256,
+    /// Scalar applied to the L1 gas cost.
+    pub l1_scalar: U256,
+    /// Blob base fee of the L1 block.
+    pub l1_blob_base_fee: U256,
+    /// Scalar applied to the L1 base fee for the commit transaction cost.
+    pub commit_scalar: U256,
+    /// Scalar applied to the L1 blob base fee per byte of transaction data.
+    pub blob_scalar: U256,
+}
+
+impl L1FeeInfo {
+    /// Reads the fee parameters from the L1 gas price oracle.
+    pub fn try_fetch<DB: Database>(
+        db: &mut DB,
+        l2_block: U256,
+        spec: L2SpecId,
+    ) -> Result<Self, DB::Error> {
+        let mut read = |slot| db.storage(L1_GAS_PRICE_ORACLE_ADDRESS, slot);
+        let mut info = Self {
+            l2_block,
+            l1_base_fee: read(L1_BASE_FEE_SLOT)?,
+            l1_overhead: read(L1_OVERHEAD_SLOT)?,
+            l1_scalar: read(L1_SCALAR_SLOT)?,
+            ..Default::default()
+        };
+        if spec.is_enabled_in(L2SpecId::CURIE) {
+            info.l1_blob_base_fee = read(L1_BLOB_BASE_FEE_SLOT)?;
+            info.commit_scalar = read(COMMIT_SCALAR_SLOT)?;
+            info.blob_scalar = read(BLOB_SCALAR_SLOT)?;
+        }
+        Ok(info)
+    }
+
+    /// Calculates the L1 fee of the RLP encoded transaction.
+    ///
+    /// * Bernoulli: `(l1_gas(tx) + overhead) * l1_base_fee * l1_scalar / PRECISION`
+    /// * Curie: `(commit_scalar * l1_base_fee + blob_scalar * l1_blob_base_fee * len(tx)) / PRECISION`
+    pub fn calculate_tx_l1_fee(&self, rlp_bytes: &[u8], spec: L2SpecId) -> U256 {
+        if spec.is_enabled_in(L2SpecId::CURIE) {
+            let data_fee = self
+                .blob_scalar
+                .saturating_mul(self.l1_blob_base_fee)
+                .saturating_mul(U256::from(rlp_bytes.len()));
+            return self
+                .commit_scalar
+                .saturating_mul(self.l1_base_fee)
+                .saturating_add(data_fee)
+                / PRECISION;
+        }
+        U256::from(data_l1_gas(rlp_bytes) + TX_SIGNATURE_L1_GAS)
+            .saturating_add(self.l1_overhead)
+            .saturating_mul(self.l1_base_fee)
+            .saturating_mul(self.l1_scalar)
+            / PRECISION
+    }
+}
+
+/// Returns the L1 calldata gas of the data, 4 gas per zero byte and 16 gas per non-zero byte.
+pub fn data_l1_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|byte| if *byte == 0 { 4 } else { 16 })
+        .sum()
+}
@@ -0,0 +1,29 @@
+#![doc = include_str!("../README.md")]
+
+/// Execution traits implemented for [`L2Evm`].
+pub mod api;
+
+/// Rollup EVM wrapping the standard EVM components with the rollup precompiles.
+pub mod evm;
+
+/// Rollup handler charging the L1 fee.
+pub mod handler;
+
+/// L1 fee parameters and formulas.
+pub mod l1_fee;
+
+/// Precompile sets of the rollup hardforks.
+pub mod precompiles;
+
+/// Rollup hardforks and their mapping to Ethereum hardforks.
+pub mod spec;
+
+/// Rollup transaction type.
+pub mod transaction;
+
+pub use evm::*;
+pub use handler::*;
+pub use l1_fee::*;
+pub use precompiles::L2Precompiles;
+pub use spec::L2SpecId;
+pub use transaction::{L2Transaction, L2TxTr};
@@ -0,0 +1,67 @@
+//! Example of a rollup EVM with its own L1 fee, precompiles and hardforks.
+#![doc = include_str!("../README.md")]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use example_custom_l2::{
+    L1FeeInfo, L2Evm, L2SpecId, L2Transaction, L1_BASE_FEE_SLOT, L1_FEE_VAULT,
+    L1_GAS_PRICE_ORACLE_ADDRESS, L1_SCALAR_SLOT,
+};
+use revm::{
+    context::{BlockEnv, CfgEnv, TxEnv},
+    database::{CacheDB, EmptyDB},
+    primitives::{address, Bytes, TxKind, U256},
+    state::AccountInfo,
+    Context, ExecuteEvm, MainContext,
+};
+
+/// Runs a transfer on the rollup and prints the L1 fee paid to the vault.
+pub fn main() {
+    let caller = address!("0x1000000000000000000000000000000000000001");
+    let mut db = CacheDB::<EmptyDB>::default();
+    db.insert_account_info(
+        caller,
+        AccountInfo::from_balance(U256::from(1_000_000_000_000_000_000u128)),
+    );
+    // L1 base fee of 10 gwei with a scalar of 1.0.
+    db.insert_account_storage(
+        L1_GAS_PRICE_ORACLE_ADDRESS,
+        L1_BASE_FEE_SLOT,
+        U256::from(10_000_000_000u64),
+    )
+    .unwrap();
+    db.insert_account_storage(
+        L1_GAS_PRICE_ORACLE_ADDRESS,
+        L1_SCALAR_SLOT,
+        U256::from(1_000_000_000u64),
+    )
+    .unwrap();
+
+    let ctx = Context::mainnet()
+        .with_db(db)
+        .with_tx(L2Transaction::default())
+        .with_block(BlockEnv {
+            number: U256::from(1),
+            ..Default::default()
+        })
+        .with_cfg(CfgEnv::new_with_spec(L2SpecId::BERNOULLI))
+        .with_chain(L1FeeInfo::default());
+    let mut evm = L2Evm::new(ctx, ());
+
+    let tx = L2Transaction::new(
+        TxEnv::builder()
+            .caller(caller)
+            .kind(TxKind::Call(address!(
+                "0x2000000000000000000000000000000000000002"
+            )))
+            .value(U256::from(1))
+            .gas_limit(21_000)
+            .build()
+            .unwrap(),
+    )
+    .with_rlp_bytes(Bytes::from(vec![0xf8; 100]));
+
+    let result = evm.transact(tx).unwrap();
+    assert!(result.result.is_success());
+    let l1_fee = result.state[&L1_FEE_VAULT].info.balance;
+    println!("L1 fee paid: {l1_fee} wei");
+}
@@ -0,0 +1,105 @@
+//! Precompile sets of the rollup hardforks.
+use crate::L2SpecId;
+use revm::{
+    context::Cfg,
+    context_interface::ContextTr,
+    handler::{EthPrecompiles, PrecompileProvider},
+    interpreter::{InputsImpl, InterpreterResult},
+    precompile::{blake2, hash, secp256r1, Precompiles},
+    primitives::{hardfork::SpecId, Address, OnceLock},
+};
+
+/// Rollup precompile provider.
+#[derive(Debug, Clone)]
+pub struct L2Precompiles {
+    /// Inner precompile provider is same as Ethereums.
+    inner: EthPrecompiles,
+    /// Spec id of the precompile provider.
+    spec: L2SpecId,
+}
+
+impl L2Precompiles {
+    /// Create a new precompile provider with the given spec.
+    pub fn new_with_spec(spec: L2SpecId) -> Self {
+        let precompiles = match spec {
+            L2SpecId::BERNOULLI => bernoulli(),
+            L2SpecId::CURIE => curie(),
+        };
+        Self {
+            inner: EthPrecompiles {
+                precompiles,
+                spec: SpecId::default(),
+            },
+            spec,
+        }
+    }
+
+    /// Precompiles getter.
+    pub fn precompiles(&self) -> &'static Precompiles {
+        self.inner.precompiles
+    }
+}
+
+/// Returns precompiles for Bernoulli spec.
+///
+/// Berlin precompiles without RIPEMD-160 and BLAKE2F, which are expensive to prove.
+pub fn bernoulli() -> &'static Precompiles {
+    static INSTANCE: OnceLock<Precompiles> = OnceLock::new();
+    INSTANCE.get_or_init(|| {
+        let mut disabled = Precompiles::default();
+        disabled.extend([hash::RIPEMD160, blake2::FUN]);
+        Precompiles::berlin().difference(&disabled)
+    })
+}
+
+/// Returns precompiles for Curie spec.
+pub fn curie() -> &'static Precompiles {
+    static INSTANCE: OnceLock<Precompiles> = OnceLock::new();
+    INSTANCE.get_or_init(|| {
+        let mut precompiles = bernoulli().clone();
+        // RIP-7212: secp256r1 P256verify
+        precompiles.extend([secp256r1::P256VERIFY]);
+        precompiles
+    })
+}
+
+impl<CTX> PrecompileProvider<CTX> for L2Precompiles
+where
+    CTX: ContextTr<Cfg: Cfg<Spec = L2SpecId>>,
+{
+    type Output = InterpreterResult;
+
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        if spec == self.spec {
+            return false;
+        }
+        *self = Self::new_with_spec(spec);
+        true
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        inputs: &InputsImpl,
+        is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<Self::Output>, String> {
+        self.inner
+            .run(context, address, inputs, is_static, gas_limit)
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        self.inner.warm_addresses()
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.inner.contains(address)
+    }
+}
+
+impl Default for L2Precompiles {
+    fn default() -> Self {
+        Self::new_with_spec(L2SpecId::default())
+    }
+}
@@ -0,0 +1,68 @@
+//! Contains the [`L2SpecId`] type and its mapping to Ethereum [`SpecId`].
+use core::str::FromStr;
+use revm::primitives::hardfork::{SpecId, UnknownHardfork};
+
+/// Hardforks of the rollup.
+///
+/// Rollup hardforks are ordered and each maps to the Ethereum hardfork whose
+/// instruction set and gas schedule it follows.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[allow(non_camel_case_types)]
+pub enum L2SpecId {
+    /// Launch hardfork, L1 fee is derived from the L1 gas of the transaction.
+    BERNOULLI = 100,
+    /// L1 fee is derived from L1 blob data cost, adds the P256 precompile.
+    #[default]
+    CURIE,
+}
+
+impl L2SpecId {
+    /// Converts the [`L2SpecId`] into a [`SpecId`].
+    pub const fn into_eth_spec(self) -> SpecId {
+        match self {
+            Self::BERNOULLI => SpecId::SHANGHAI,
+            Self::CURIE => SpecId::CANCUN,
+        }
+    }
+
+    /// Checks if the [`L2SpecId`] is enabled in the other [`L2SpecId`].
+    pub const fn is_enabled_in(self, other: L2SpecId) -> bool {
+        other as u8 <= self as u8
+    }
+}
+
+impl From<L2SpecId> for SpecId {
+    fn from(spec: L2SpecId) -> Self {
+        spec.into_eth_spec()
+    }
+}
+
+impl FromStr for L2SpecId {
+    type Err = UnknownHardfork;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            name::BERNOULLI => Ok(L2SpecId::BERNOULLI),
+            name::CURIE => Ok(L2SpecId::CURIE),
+            _ => Err(UnknownHardfork),
+        }
+    }
+}
+
+impl From<L2SpecId> for &'static str {
+    fn from(spec_id: L2SpecId) -> Self {
+        match spec_id {
+            L2SpecId::BERNOULLI => name::BERNOULLI,
+            L2SpecId::CURIE => name::CURIE,
+        }
+    }
+}
+
+/// String identifiers for rollup hardforks.
+pub mod name {
+    /// Bernoulli spec name.
+    pub const BERNOULLI: &str = "Bernoulli";
+    /// Curie spec name.
+    pub const CURIE: &str = "Curie";
+}
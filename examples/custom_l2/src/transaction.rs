@@ -0,0 +1,124 @@
+//! Rollup transaction carrying the RLP encoding used for the L1 fee.
+use revm::{
+    context::TxEnv,
+    context_interface::transaction::Transaction,
+    primitives::{Address, Bytes, TxKind, B256, U256},
+};
+
+/// Rollup transaction trait.
+pub trait L2TxTr: Transaction {
+    /// RLP encoded signed transaction, used to calculate the L1 fee.
+    ///
+    /// Transactions without the encoding, like system calls, are not charged the L1 fee.
+    fn rlp_bytes(&self) -> Option<&Bytes>;
+}
+
+/// Rollup transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct L2Transaction<T: Transaction = TxEnv> {
+    /// Base transaction fields.
+    pub base: T,
+    /// RLP encoded signed transaction.
+    pub rlp_bytes: Option<Bytes>,
+}
+
+impl<T: Transaction> L2Transaction<T> {
+    /// Create a new rollup transaction without the RLP encoding.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            rlp_bytes: None,
+        }
+    }
+
+    /// Sets the RLP encoding of the signed transaction.
+    pub fn with_rlp_bytes(mut self, rlp_bytes: Bytes) -> Self {
+        self.rlp_bytes = Some(rlp_bytes);
+        self
+    }
+}
+
+impl<T: Transaction> Transaction for L2Transaction<T> {
+    type AccessListItem<'a>
+        = T::AccessListItem<'a>
+    where
+        T: 'a;
+    type Authorization<'a>
+        = T::Authorization<'a>
+    where
+        T: 'a;
+
+    fn tx_type(&self) -> u8 {
+        self.base.tx_type()
+    }
+
+    fn caller(&self) -> Address {
+        self.base.caller()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.base.gas_limit()
+    }
+
+    fn value(&self) -> U256 {
+        self.base.value()
+    }
+
+    fn input(&self) -> &Bytes {
+        self.base.input()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.base.nonce()
+    }
+
+    fn kind(&self) -> TxKind {
+        self.base.kind()
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.base.chain_id()
+    }
+
+    fn access_list(&self) -> Option<impl Iterator<Item = Self::AccessListItem<'_>>> {
+        self.base.access_list()
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.base.max_priority_fee_per_gas()
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.base.max_fee_per_gas()
+    }
+
+    fn gas_price(&self) -> u128 {
+        self.base.gas_price()
+    }
+
+    fn blob_versioned_hashes(&self) -> &[B256] {
+        self.base.blob_versioned_hashes()
+    }
+
+    fn max_fee_per_blob_gas(&self) -> u128 {
+        self.base.max_fee_per_blob_gas()
+    }
+
+    fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        self.base.effective_gas_price(base_fee)
+    }
+
+    fn authorization_list_len(&self) -> usize {
+        self.base.authorization_list_len()
+    }
+
+    fn authorization_list(&self) -> impl Iterator<Item = Self::Authorization<'_>> {
+        self.base.authorization_list()
+    }
+}
+
+impl<T: Transaction> L2TxTr for L2Transaction<T> {
+    fn rlp_bytes(&self) -> Option<&Bytes> {
+        self.rlp_bytes.as_ref()
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    evm::ExampleEvm, handler::ExampleContextTr, ExampleHaltReason, ExampleHandler, ExampleTxError,
+};
+use revm::{
+    context::{result::ExecResultAndState, ContextSetters},
+    context_interface::{
+        result::{EVMError, ExecutionResult},
+        ContextTr, Database, JournalTr,
+    },
+    handler::{EvmTr, Handler},
+    inspector::{InspectCommitEvm, InspectEvm, Inspector, InspectorHandler, JournalExt},
+    interpreter::interpreter::EthInterpreter,
+    state::EvmState,
+    DatabaseCommit, ExecuteCommitEvm, ExecuteEvm,
+};
+
+/// Type alias for the error type of the ExampleEvm.
+type ExampleError<CTX> = EVMError<<<CTX as ContextTr>::Db as Database>::Error, ExampleTxError>;
+
+// Trait that allows to replay and transact the transaction.
+impl<CTX, INSP> ExecuteEvm for ExampleEvm<CTX, INSP>
+where
+    CTX: ExampleContextTr + ContextSetters,
+{
+    type State = EvmState;
+    type ExecutionResult = ExecutionResult<ExampleHaltReason>;
+    type Error = ExampleError<CTX>;
+
+    type Tx = <CTX as ContextTr>::Tx;
+
+    type Block = <CTX as ContextTr>::Block;
+
+    fn set_block(&mut self, block: Self::Block) {
+        self.0.ctx.set_block(block);
+    }
+
+    fn transact_one(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
+        self.0.ctx.set_tx(tx);
+        let mut handler = ExampleHandler::default();
+        handler.run(self)
+    }
+
+    fn finalize(&mut self) -> Self::State {
+        self.ctx().journal_mut().finalize()
+    }
+
+    fn replay(
+        &mut self,
+    ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
+        let mut handler = ExampleHandler::default();
+        handler.run(self).map(|result| {
+            let state = self.finalize();
+            ExecResultAndState::new(result, state)
+        })
+    }
+}
+
+// Trait allows replay_commit and transact_commit functionality.
+impl<CTX, INSP> ExecuteCommitEvm for ExampleEvm<CTX, INSP>
+where
+    CTX: ExampleContextTr<Db: DatabaseCommit> + ContextSetters,
+{
+    fn commit(&mut self, state: Self::State) {
+        self.ctx().db_mut().commit(state);
+    }
+}
+
+// Inspection trait.
+impl<CTX, INSP> InspectEvm for ExampleEvm<CTX, INSP>
+where
+    CTX: ExampleContextTr<Journal: JournalExt> + ContextSetters,
+    INSP: Inspector<CTX, EthInterpreter>,
+{
+    type Inspector = INSP;
+
+    fn set_inspector(&mut self, inspector: Self::Inspector) {
+        self.0.inspector = inspector;
+    }
+
+    fn inspect_one_tx(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
+        self.0.ctx.set_tx(tx);
+        let mut handler = ExampleHandler::default();
+        handler.inspect_run(self)
+    }
+}
+
+// Inspect
+impl<CTX, INSP> InspectCommitEvm for ExampleEvm<CTX, INSP>
+where
+    CTX: ExampleContextTr<Db: DatabaseCommit, Journal: JournalExt> + ContextSetters,
+    INSP: Inspector<CTX, EthInterpreter>,
+{
+}
@@ -0,0 +1,144 @@
+use crate::{ExampleChainInfo, ExamplePrecompiles, ExampleSpecId, ExampleTransaction};
+use revm::{
+    context::{BlockEnv, Cfg, CfgEnv, ContextError, ContextSetters, ContextTr, Evm, FrameStack},
+    handler::{
+        evm::FrameTr, instructions::EthInstructions, EthFrame, EvmTr, FrameInitOrResult,
+        ItemOrResult,
+    },
+    inspector::{InspectorEvmTr, JournalExt},
+    interpreter::interpreter::EthInterpreter,
+    Context, Database, Inspector, Journal,
+};
+
+/// Type alias for the default context of the chain.
+///
+/// The chain field of the context holds the L1 block hashes known to the chain.
+pub type ExampleContext<DB> =
+    Context<BlockEnv, ExampleTransaction, CfgEnv<ExampleSpecId>, DB, Journal<DB>, ExampleChainInfo>;
+
+/// Chain variant of the EVM.
+///
+/// Uses Ethereum instructions and frames with the chain precompile set. Wrapping [`Evm`]
+/// allows implementing execution traits with the rollup handler.
+#[derive(Debug)]
+pub struct ExampleEvm<CTX, INSP = ()>(
+    pub  Evm<
+        CTX,
+        INSP,
+        EthInstructions<EthInterpreter, CTX>,
+        ExamplePrecompiles,
+        EthFrame<EthInterpreter>,
+    >,
+);
+
+impl<CTX: ContextTr<Cfg: Cfg<Spec = ExampleSpecId>, Chain = ExampleChainInfo>, INSP>
+    ExampleEvm<CTX, INSP>
+{
+    /// Creates a new chain EVM with precompiles of the context spec.
+    pub fn new(ctx: CTX, inspector: INSP) -> Self {
+        let spec = ctx.cfg().spec();
+        Self(Evm {
+            ctx,
+            inspector,
+            instruction: EthInstructions::new_mainnet(),
+            precompiles: ExamplePrecompiles::new_with_spec(spec),
+            frame_stack: FrameStack::new(),
+        })
+    }
+}
+
+impl<CTX, INSP> EvmTr for ExampleEvm<CTX, INSP>
+where
+    CTX: ContextTr<Cfg: Cfg<Spec = ExampleSpecId>, Chain = ExampleChainInfo>,
+{
+    type Context = CTX;
+    type Instructions = EthInstructions<EthInterpreter, CTX>;
+    type Precompiles = ExamplePrecompiles;
+    type Frame = EthFrame<EthInterpreter>;
+
+    fn ctx(&mut self) -> &mut Self::Context {
+        &mut self.0.ctx
+    }
+
+    fn ctx_ref(&self) -> &Self::Context {
+        self.0.ctx_ref()
+    }
+
+    fn ctx_instructions(&mut self) -> (&mut Self::Context, &mut Self::Instructions) {
+        self.0.ctx_instructions()
+    }
+
+    fn ctx_precompiles(&mut self) -> (&mut Self::Context, &mut Self::Precompiles) {
+        self.0.ctx_precompiles()
+    }
+
+    fn frame_stack(&mut self) -> &mut FrameStack<Self::Frame> {
+        self.0.frame_stack()
+    }
+
+    fn frame_init(
+        &mut self,
+        frame_input: <Self::Frame as FrameTr>::FrameInit,
+    ) -> Result<
+        ItemOrResult<&mut Self::Frame, <Self::Frame as FrameTr>::FrameResult>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_init(frame_input)
+    }
+
+    fn frame_run(
+        &mut self,
+    ) -> Result<
+        FrameInitOrResult<Self::Frame>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_run()
+    }
+
+    fn frame_return_result(
+        &mut self,
+        frame_result: <Self::Frame as FrameTr>::FrameResult,
+    ) -> Result<
+        Option<<Self::Frame as FrameTr>::FrameResult>,
+        ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
+    > {
+        self.0.frame_return_result(frame_result)
+    }
+}
+
+impl<CTX, INSP> InspectorEvmTr for ExampleEvm<CTX, INSP>
+where
+    CTX: ContextSetters<
+        Cfg: Cfg<Spec = ExampleSpecId>,
+        Chain = ExampleChainInfo,
+        Journal: JournalExt,
+    >,
+    INSP: Inspector<CTX, EthInterpreter>,
+{
+    type Inspector = INSP;
+
+    fn inspector(&mut self) -> &mut Self::Inspector {
+        self.0.inspector()
+    }
+
+    fn ctx_inspector(&mut self) -> (&mut Self::Context, &mut Self::Inspector) {
+        self.0.ctx_inspector()
+    }
+
+    fn ctx_inspector_frame(
+        &mut self,
+    ) -> (&mut Self::Context, &mut Self::Inspector, &mut Self::Frame) {
+        self.0.ctx_inspector_frame()
+    }
+
+    fn ctx_inspector_frame_instructions(
+        &mut self,
+    ) -> (
+        &mut Self::Context,
+        &mut Self::Inspector,
+        &mut Self::Frame,
+        &mut Self::Instructions,
+    ) {
+        self.0.ctx_inspector_frame_instructions()
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    ExampleChainInfo, ExampleHaltReason, ExampleSpecId, ExampleTxError, ExampleTxTr,
+    L1_BLOCK_HASH_ADDRESS, SPONSORED_TX_TYPE,
+};
+use revm::{
+    context::{
+        result::{ExecutionResult, FromStringError, HaltReason},
+        Cfg, ContextError, LocalContextTr,
+    },
+    context_interface::{Block, ContextTr, JournalTr, Transaction},
+    handler::{
+        evm::FrameTr, handler::EvmTrError, post_execution, validation, EthFrame, EvmTr,
+        FrameResult, Handler, MainnetHandler,
+    },
+    inspector::{BalanceCredits, GasSettlement, Inspector, InspectorEvmTr, InspectorHandler},
+    interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, Gas},
+    primitives::{TxKind, U256},
+    state::EvmState,
+};
+
+/// Context of the chain, the chain field holds the L1 block hashes.
+pub trait ExampleContextTr:
+    ContextTr<
+    Journal: JournalTr<State = EvmState>,
+    Tx: ExampleTxTr,
+    Cfg: Cfg<Spec = ExampleSpecId>,
+    Chain = ExampleChainInfo,
+>
+{
+}
+
+impl<T> ExampleContextTr for T where
+    T: ContextTr<
+        Journal: JournalTr<State = EvmState>,
+        Tx: ExampleTxTr,
+        Cfg: Cfg<Spec = ExampleSpecId>,
+        Chain = ExampleChainInfo,
+    >
+{
+}
+
+/// Chain handler.
+///
+/// Extends the mainnet handler by:
+/// * validating sponsored transactions and rejecting them before
+///   [`ExampleSpecId::SPONSORSHIP`],
+/// * moving the prepaid fee from the sponsor to the caller before execution and the unspent
+///   part back after it,
+/// * reporting [`ExampleHaltReason::UnknownL1Block`] when a transaction calling the L1 block hash
+///   precompile halts.
+#[derive(Debug)]
+pub struct ExampleHandler<EVM, ERROR, FRAME> {
+    /// Mainnet handler used for the parts that are not changed.
+    pub mainnet: MainnetHandler<EVM, ERROR, FRAME>,
+}
+
+impl<EVM, ERROR, FRAME> Default for ExampleHandler<EVM, ERROR, FRAME> {
+    fn default() -> Self {
+        Self {
+            mainnet: MainnetHandler::default(),
+        }
+    }
+}
+
+/// Returns the fee prepaid by the sponsor, `gas_limit * max_fee_per_gas`.
+fn sponsor_prefund(tx: &impl Transaction) -> U256 {
+    U256::from(tx.gas_limit()).saturating_mul(U256::from(tx.max_fee_per_gas()))
+}
+
+/// Returns the part of the prepaid fee that was not spent by the transaction.
+fn sponsor_refund<CTX: ContextTr>(ctx: &CTX, gas: &Gas) -> U256 {
+    let basefee = ctx.block().basefee() as u128;
+    let fee = ctx.tx().effective_gas_price(basefee) * gas.used() as u128;
+    sponsor_prefund(ctx.tx()).saturating_sub(U256::from(fee))
+}
+
+impl<EVM, ERROR, FRAME> Handler for ExampleHandler<EVM, ERROR, FRAME>
+where
+    EVM: EvmTr<Context: ExampleContextTr, Frame = FRAME>,
+    ERROR: EvmTrError<EVM> + From<ExampleTxError> + FromStringError,
+    FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+{
+    type Evm = EVM;
+    type Error = ERROR;
+    type HaltReason = ExampleHaltReason;
+
+    fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        let ctx = evm.ctx();
+        if ctx.tx().tx_type() == SPONSORED_TX_TYPE {
+            if !ctx.cfg().spec().is_enabled_in(ExampleSpecId::SPONSORSHIP) {
+                return Err(ExampleTxError::SponsoredTxNotActive.into());
+            }
+            // Custom transaction types are not validated by the mainnet handler.
+            validation::validate_priority_fee_tx(
+                ctx.tx().max_fee_per_gas(),
+                ctx.tx().max_priority_fee_per_gas().unwrap_or_default(),
+                Some(ctx.block().basefee() as u128),
+                ctx.cfg().is_priority_fee_check_disabled(),
+            )
+            .map_err(ExampleTxError::Base)?;
+        }
+        self.mainnet.validate_env(evm)
+    }
+
+    fn validate_against_state_and_deduct_caller(
+        &self,
+        evm: &mut Self::Evm,
+    ) -> Result<(), Self::Error> {
+        let ctx = evm.ctx();
+        if let Some(sponsor) = ctx.tx().sponsor() {
+            let caller = ctx.tx().caller();
+            let prefund = sponsor_prefund(ctx.tx());
+            let balance = ctx.journal_mut().load_account(sponsor)?.data.info.balance;
+            if balance < prefund {
+                return Err(ExampleTxError::SponsorLackOfFund {
+                    fee: Box::new(prefund),
+                    balance: Box::new(balance),
+                }
+                .into());
+            }
+            // Caller pays the fee from the prefund as in a regular transaction.
+            if let Some(error) = ctx.journal_mut().transfer(sponsor, caller, prefund)? {
+                return Err(ERROR::from_string(format!(
+                    "sponsor prefund failed: {error:?}"
+                )));
+            }
+        }
+        self.mainnet.validate_against_state_and_deduct_caller(evm)
+    }
+
+    fn reimburse_caller(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &mut FrameResult,
+    ) -> Result<(), Self::Error> {
+        self.mainnet.reimburse_caller(evm, exec_result)?;
+
+        let ctx = evm.ctx();
+        if let Some(sponsor) = ctx.tx().sponsor() {
+            let caller = ctx.tx().caller();
+            let refund = sponsor_refund(ctx, exec_result.gas());
+            if let Some(error) = ctx.journal_mut().transfer(caller, sponsor, refund)? {
+                return Err(ERROR::from_string(format!(
+                    "sponsor refund failed: {error:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn execution_result(
+        &mut self,
+        evm: &mut Self::Evm,
+        result: FrameResult,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        match core::mem::replace(evm.ctx().error(), Ok(())) {
+            Err(ContextError::Db(e)) => return Err(e.into()),
+            Err(ContextError::Custom(e)) => return Err(Self::Error::from_string(e)),
+            Ok(_) => (),
+        }
+
+        let calls_l1_block_hash = evm.ctx().tx().kind() == TxKind::Call(L1_BLOCK_HASH_ADDRESS);
+        let exec_result =
+            post_execution::output(evm.ctx(), result).map_haltreason(|reason| match reason {
+                HaltReason::PrecompileError if calls_l1_block_hash => {
+                    ExampleHaltReason::UnknownL1Block
+                }
+                reason => ExampleHaltReason::Base(reason),
+            });
+
+        evm.ctx().journal_mut().commit_tx();
+        evm.ctx().local_mut().clear();
+        evm.frame_stack().clear();
+
+        Ok(exec_result)
+    }
+}
+
+impl<EVM, ERROR> InspectorHandler for ExampleHandler<EVM, ERROR, EthFrame<EthInterpreter>>
+where
+    EVM: InspectorEvmTr<
+        Context: ExampleContextTr,
+        Frame = EthFrame<EthInterpreter>,
+        Inspector: Inspector<<<Self as Handler>::Evm as EvmTr>::Context, EthInterpreter>,
+    >,
+    ERROR: EvmTrError<EVM> + From<ExampleTxError> + FromStringError,
+{
+    type IT = EthInterpreter;
+
+    fn gas_settlement(
+        &self,
+        evm: &mut Self::Evm,
+        exec_result: &FrameResult,
+        caller_credits: &BalanceCredits,
+        fee_credits: &BalanceCredits,
+    ) -> Result<GasSettlement, Self::Error> {
+        let mut settlement =
+            self.mainnet
+                .gas_settlement(evm, exec_result, caller_credits, fee_credits)?;
+        // Unspent prepaid fee is returned to the sponsor.
+        if let Some(sponsor) = evm.ctx_ref().tx().sponsor() {
+            settlement.caller = sponsor;
+            settlement.caller_refund = caller_credits.amount(sponsor);
+        }
+        Ok(settlement)
+    }
+}
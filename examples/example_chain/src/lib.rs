@@ -0,0 +1,29 @@
+#![doc = include_str!("../README.md")]
+
+/// Execution and inspection traits implemented for [`ExampleEvm`].
+pub mod api;
+
+/// Chain EVM wrapping the standard EVM components with the chain precompiles.
+pub mod evm;
+
+/// Chain handler charging the sponsor and reporting chain halt reasons.
+pub mod handler;
+
+/// Precompiles of the chain and the chain context they read.
+pub mod precompiles;
+
+/// Transaction errors and halt reasons of the chain.
+pub mod result;
+
+/// Chain hardforks and their mapping to Ethereum hardforks.
+pub mod spec;
+
+/// Sponsored transaction type.
+pub mod transaction;
+
+pub use evm::*;
+pub use handler::*;
+pub use precompiles::{ExampleChainInfo, ExamplePrecompiles, L1_BLOCK_HASH_ADDRESS};
+pub use result::{ExampleHaltReason, ExampleTxError};
+pub use spec::ExampleSpecId;
+pub use transaction::{ExampleTransaction, ExampleTxTr, SPONSORED_TX_TYPE};
@@ -0,0 +1,56 @@
+//! Example of a chain EVM using all extension points together.
+#![doc = include_str!("../README.md")]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use example_chain::{
+    ExampleChainInfo, ExampleEvm, ExampleSpecId, ExampleTransaction, L1_BLOCK_HASH_ADDRESS,
+};
+use revm::{
+    context::{BlockEnv, CfgEnv, TxEnv},
+    database::{CacheDB, EmptyDB},
+    primitives::{address, b256, TxKind, U256},
+    state::AccountInfo,
+    Context, ExecuteEvm, MainContext,
+};
+
+/// Reads an L1 block hash in a sponsored transaction and prints the fee paid by the sponsor.
+pub fn main() {
+    let caller = address!("0x1000000000000000000000000000000000000001");
+    let sponsor = address!("0x2000000000000000000000000000000000000002");
+    let sponsor_balance = U256::from(1_000_000_000_000_000_000u128);
+    let mut db = CacheDB::<EmptyDB>::default();
+    db.insert_account_info(sponsor, AccountInfo::from_balance(sponsor_balance));
+
+    let l1_block_hash = b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+    let ctx = Context::mainnet()
+        .with_db(db)
+        .with_tx(ExampleTransaction::default())
+        .with_block(BlockEnv {
+            basefee: 1_000_000_000,
+            ..Default::default()
+        })
+        .with_cfg(CfgEnv::new_with_spec(ExampleSpecId::SPONSORSHIP))
+        .with_chain(ExampleChainInfo::default().with_l1_block_hash(7, l1_block_hash));
+    let mut evm = ExampleEvm::new(ctx, ());
+
+    let tx = ExampleTransaction::new(
+        TxEnv::builder()
+            .caller(caller)
+            .kind(TxKind::Call(L1_BLOCK_HASH_ADDRESS))
+            .data(U256::from(7).to_be_bytes_vec().into())
+            .gas_limit(30_000)
+            .gas_price(2_000_000_000)
+            .gas_priority_fee(Some(1_000_000_000))
+            .build()
+            .unwrap(),
+    )
+    .with_sponsor(sponsor);
+
+    let result = evm.transact(tx).unwrap();
+    assert_eq!(
+        result.result.output().unwrap().as_ref(),
+        l1_block_hash.as_slice()
+    );
+    let fee = sponsor_balance - result.state[&sponsor].info.balance;
+    println!("L1 block hash: {l1_block_hash}, fee paid by the sponsor: {fee} wei");
+}
@@ -0,0 +1,146 @@
+//! Precompiles of the chain and the chain context they read.
+use crate::ExampleSpecId;
+use revm::{
+    context::Cfg,
+    context_interface::{ContextTr, LocalContextTr},
+    handler::{EthPrecompiles, PrecompileProvider},
+    interpreter::{CallInput, Gas, InputsImpl, InstructionResult, InterpreterResult},
+    precompile::{PrecompileSpecId, Precompiles},
+    primitives::{address, Address, Bytes, B256, U256},
+};
+use std::collections::BTreeMap;
+
+/// Address of the precompile returning L1 block hashes.
+pub const L1_BLOCK_HASH_ADDRESS: Address = address!("0x0000000000000000000000000000000000000200");
+
+/// Gas cost of the L1 block hash precompile.
+pub const L1_BLOCK_HASH_GAS: u64 = 200;
+
+/// Chain specific data of the context, set by the node for every block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleChainInfo {
+    /// Hashes of L1 blocks known to the chain, by L1 block number.
+    pub l1_block_hashes: BTreeMap<u64, B256>,
+}
+
+impl ExampleChainInfo {
+    /// Sets the hash of the L1 block.
+    pub fn with_l1_block_hash(mut self, number: u64, hash: B256) -> Self {
+        self.l1_block_hashes.insert(number, hash);
+        self
+    }
+}
+
+/// Chain precompile provider.
+///
+/// Ethereum precompiles of the mapped hardfork with the L1 block hash precompile.
+#[derive(Debug, Clone)]
+pub struct ExamplePrecompiles {
+    /// Ethereum precompiles.
+    inner: EthPrecompiles,
+    /// Spec id of the precompile provider.
+    spec: ExampleSpecId,
+}
+
+impl ExamplePrecompiles {
+    /// Create a new precompile provider with the given spec.
+    pub fn new_with_spec(spec: ExampleSpecId) -> Self {
+        let eth_spec = spec.into_eth_spec();
+        Self {
+            inner: EthPrecompiles {
+                precompiles: Precompiles::new(PrecompileSpecId::from_spec_id(eth_spec)),
+                spec: eth_spec,
+            },
+            spec,
+        }
+    }
+}
+
+impl Default for ExamplePrecompiles {
+    fn default() -> Self {
+        Self::new_with_spec(ExampleSpecId::default())
+    }
+}
+
+impl<CTX> PrecompileProvider<CTX> for ExamplePrecompiles
+where
+    CTX: ContextTr<Cfg: Cfg<Spec = ExampleSpecId>, Chain = ExampleChainInfo>,
+{
+    type Output = InterpreterResult;
+
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        if spec == self.spec {
+            return false;
+        }
+        *self = Self::new_with_spec(spec);
+        true
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        inputs: &InputsImpl,
+        is_static: bool,
+        gas_limit: u64,
+    ) -> Result<Option<Self::Output>, String> {
+        if *address == L1_BLOCK_HASH_ADDRESS {
+            return Ok(Some(l1_block_hash(context, inputs, gas_limit)));
+        }
+        self.inner
+            .run(context, address, inputs, is_static, gas_limit)
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        let mut addresses = vec![L1_BLOCK_HASH_ADDRESS];
+        addresses.extend(self.inner.warm_addresses());
+        Box::new(addresses.into_iter())
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        *address == L1_BLOCK_HASH_ADDRESS || self.inner.contains(address)
+    }
+}
+
+/// Returns the hash of the L1 block whose number is the 32 byte input.
+///
+/// Halts with [`InstructionResult::PrecompileError`] if the block is not known, the handler
+/// reports it as [`ExampleHaltReason::UnknownL1Block`](crate::ExampleHaltReason::UnknownL1Block)
+/// when it ends the transaction.
+fn l1_block_hash<CTX: ContextTr<Chain = ExampleChainInfo>>(
+    context: &mut CTX,
+    inputs: &InputsImpl,
+    gas_limit: u64,
+) -> InterpreterResult {
+    let input = match &inputs.input {
+        CallInput::SharedBuffer(range) => context
+            .local()
+            .shared_memory_buffer_slice(range.clone())
+            .map(|slice| slice.to_vec())
+            .unwrap_or_default(),
+        CallInput::Bytes(bytes) => bytes.to_vec(),
+    };
+
+    let mut result = InterpreterResult {
+        result: InstructionResult::Return,
+        gas: Gas::new(gas_limit),
+        output: Bytes::new(),
+    };
+    if !result.gas.record_cost(L1_BLOCK_HASH_GAS) {
+        result.result = InstructionResult::PrecompileOOG;
+        return result;
+    }
+
+    let hash = (input.len() == 32)
+        .then(|| U256::from_be_slice(&input))
+        .and_then(|number| u64::try_from(number).ok())
+        .and_then(|number| context.chain().l1_block_hashes.get(&number));
+    match hash {
+        Some(hash) => result.output = Bytes::copy_from_slice(hash.as_slice()),
+        None => {
+            result.result = InstructionResult::PrecompileError;
+            result.gas.spend_all();
+        }
+    }
+    result
+}
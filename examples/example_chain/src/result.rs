@@ -0,0 +1,90 @@
+//! Transaction errors and halt reasons of the chain.
+use core::fmt;
+use revm::{
+    context_interface::{
+        result::{EVMError, HaltReason, InvalidTransaction},
+        transaction::TransactionError,
+    },
+    primitives::U256,
+};
+
+/// Transaction validation error of the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExampleTxError {
+    /// Base transaction error.
+    Base(InvalidTransaction),
+    /// Sponsored transactions are not enabled in the current hardfork.
+    SponsoredTxNotActive,
+    /// Sponsor can't prepay the fee of the transaction.
+    SponsorLackOfFund {
+        /// Prepaid fee.
+        fee: Box<U256>,
+        /// Balance of the sponsor.
+        balance: Box<U256>,
+    },
+}
+
+impl TransactionError for ExampleTxError {
+    fn code(&self) -> u32 {
+        match self {
+            Self::Base(error) => error.code(),
+            Self::SponsoredTxNotActive => 1601,
+            Self::SponsorLackOfFund { .. } => 1602,
+        }
+    }
+}
+
+impl fmt::Display for ExampleTxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Base(error) => error.fmt(f),
+            Self::SponsoredTxNotActive => {
+                write!(f, "sponsored transactions are not active in this hardfork")
+            }
+            Self::SponsorLackOfFund { fee, balance } => {
+                write!(f, "sponsor can't prepay fee {fee}, balance is {balance}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ExampleTxError {}
+
+impl From<InvalidTransaction> for ExampleTxError {
+    fn from(value: InvalidTransaction) -> Self {
+        Self::Base(value)
+    }
+}
+
+impl<DBError> From<ExampleTxError> for EVMError<DBError, ExampleTxError> {
+    fn from(value: ExampleTxError) -> Self {
+        Self::Transaction(value)
+    }
+}
+
+/// Halt reason of the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExampleHaltReason {
+    /// Base halt reason.
+    Base(HaltReason),
+    /// Transaction called the L1 block hash precompile with a block that is not known.
+    UnknownL1Block,
+}
+
+impl ExampleHaltReason {
+    /// Returns stable numeric code of the halt reason.
+    ///
+    /// Base halt reasons keep their [`HaltReason::code`].
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Base(reason) => reason.code(),
+            Self::UnknownL1Block => 3601,
+        }
+    }
+}
+
+impl From<HaltReason> for ExampleHaltReason {
+    fn from(value: HaltReason) -> Self {
+        Self::Base(value)
+    }
+}
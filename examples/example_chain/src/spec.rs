@@ -0,0 +1,68 @@
+//! Contains the [`ExampleSpecId`] type and its mapping to Ethereum [`SpecId`].
+use core::str::FromStr;
+use revm::primitives::hardfork::{SpecId, UnknownHardfork};
+
+/// Hardforks of the chain.
+///
+/// Chain hardforks are ordered and each maps to the Ethereum hardfork whose
+/// instruction set and gas schedule it follows.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[allow(non_camel_case_types)]
+pub enum ExampleSpecId {
+    /// Launch hardfork, adds the L1 block hash precompile.
+    GENESIS = 100,
+    /// Enables sponsored transactions.
+    #[default]
+    SPONSORSHIP,
+}
+
+impl ExampleSpecId {
+    /// Converts the [`ExampleSpecId`] into a [`SpecId`].
+    pub const fn into_eth_spec(self) -> SpecId {
+        match self {
+            Self::GENESIS => SpecId::CANCUN,
+            Self::SPONSORSHIP => SpecId::PRAGUE,
+        }
+    }
+
+    /// Checks if the [`ExampleSpecId`] is enabled in the other [`ExampleSpecId`].
+    pub const fn is_enabled_in(self, other: ExampleSpecId) -> bool {
+        other as u8 <= self as u8
+    }
+}
+
+impl From<ExampleSpecId> for SpecId {
+    fn from(spec: ExampleSpecId) -> Self {
+        spec.into_eth_spec()
+    }
+}
+
+impl FromStr for ExampleSpecId {
+    type Err = UnknownHardfork;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            name::GENESIS => Ok(ExampleSpecId::GENESIS),
+            name::SPONSORSHIP => Ok(ExampleSpecId::SPONSORSHIP),
+            _ => Err(UnknownHardfork),
+        }
+    }
+}
+
+impl From<ExampleSpecId> for &'static str {
+    fn from(spec_id: ExampleSpecId) -> Self {
+        match spec_id {
+            ExampleSpecId::GENESIS => name::GENESIS,
+            ExampleSpecId::SPONSORSHIP => name::SPONSORSHIP,
+        }
+    }
+}
+
+/// String identifiers for chain hardforks.
+pub mod name {
+    /// Genesis spec name.
+    pub const GENESIS: &str = "Genesis";
+    /// Sponsorship spec name.
+    pub const SPONSORSHIP: &str = "Sponsorship";
+}
@@ -0,0 +1,133 @@
+//! Sponsored transaction whose fee is paid by a sponsor.
+use revm::{
+    context::TxEnv,
+    context_interface::transaction::Transaction,
+    primitives::{Address, Bytes, TxKind, B256, U256},
+};
+
+/// Type of the sponsored transaction.
+pub const SPONSORED_TX_TYPE: u8 = 0x7C;
+
+/// Chain transaction trait.
+pub trait ExampleTxTr: Transaction {
+    /// Account that pays the fee of the transaction, `None` if the caller pays it.
+    fn sponsor(&self) -> Option<Address>;
+}
+
+/// Chain transaction.
+///
+/// Transactions with a sponsor have the [`SPONSORED_TX_TYPE`] type. The sponsor prepays
+/// `gas_limit * max_fee_per_gas` and gets back the part that was not spent, the caller only pays
+/// the transferred value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleTransaction<T: Transaction = TxEnv> {
+    /// Base transaction fields.
+    pub base: T,
+    /// Account paying the fee.
+    pub sponsor: Option<Address>,
+}
+
+impl<T: Transaction> ExampleTransaction<T> {
+    /// Create a new transaction paid by the caller.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            sponsor: None,
+        }
+    }
+
+    /// Sets the sponsor paying the fee.
+    pub fn with_sponsor(mut self, sponsor: Address) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+}
+
+impl<T: Transaction> Transaction for ExampleTransaction<T> {
+    type AccessListItem<'a>
+        = T::AccessListItem<'a>
+    where
+        T: 'a;
+    type Authorization<'a>
+        = T::Authorization<'a>
+    where
+        T: 'a;
+
+    fn tx_type(&self) -> u8 {
+        if self.sponsor.is_some() {
+            SPONSORED_TX_TYPE
+        } else {
+            self.base.tx_type()
+        }
+    }
+
+    fn caller(&self) -> Address {
+        self.base.caller()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.base.gas_limit()
+    }
+
+    fn value(&self) -> U256 {
+        self.base.value()
+    }
+
+    fn input(&self) -> &Bytes {
+        self.base.input()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.base.nonce()
+    }
+
+    fn kind(&self) -> TxKind {
+        self.base.kind()
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.base.chain_id()
+    }
+
+    fn access_list(&self) -> Option<impl Iterator<Item = Self::AccessListItem<'_>>> {
+        self.base.access_list()
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.base.max_priority_fee_per_gas()
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.base.max_fee_per_gas()
+    }
+
+    fn gas_price(&self) -> u128 {
+        self.base.gas_price()
+    }
+
+    fn blob_versioned_hashes(&self) -> &[B256] {
+        self.base.blob_versioned_hashes()
+    }
+
+    fn max_fee_per_blob_gas(&self) -> u128 {
+        self.base.max_fee_per_blob_gas()
+    }
+
+    fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        self.base.effective_gas_price(base_fee)
+    }
+
+    fn authorization_list_len(&self) -> usize {
+        self.base.authorization_list_len()
+    }
+
+    fn authorization_list(&self) -> impl Iterator<Item = Self::Authorization<'_>> {
+        self.base.authorization_list()
+    }
+}
+
+impl<T: Transaction> ExampleTxTr for ExampleTransaction<T> {
+    fn sponsor(&self) -> Option<Address> {
+        self.sponsor
+    }
+}
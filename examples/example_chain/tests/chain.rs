@@ -0,0 +1,183 @@
+//! End-to-end tests of the example chain.
+use example_chain::{
+    ExampleChainInfo, ExampleContext, ExampleEvm, ExampleHaltReason, ExampleSpecId,
+    ExampleTransaction, ExampleTxError, L1_BLOCK_HASH_ADDRESS,
+};
+use revm::{
+    context::{
+        result::{EVMError, ExecutionResult, HaltReason},
+        BlockEnv, CfgEnv, TxEnv,
+    },
+    database::{CacheDB, EmptyDB},
+    inspector::{GasSettlement, InspectEvm, Inspector},
+    interpreter::interpreter::EthInterpreter,
+    primitives::{address, b256, Address, TxKind, B256, U256},
+    state::AccountInfo,
+    Context, ExecuteEvm, MainContext,
+};
+
+const CALLER: Address = address!("0x1000000000000000000000000000000000000001");
+const SPONSOR: Address = address!("0x2000000000000000000000000000000000000002");
+const RECEIVER: Address = address!("0x3000000000000000000000000000000000000003");
+const BENEFICIARY: Address = address!("0x4000000000000000000000000000000000000004");
+const L1_HASH: B256 = b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+const BASEFEE: u64 = 10;
+const BALANCE: u64 = 1_000_000_000;
+
+fn context(spec: ExampleSpecId, sponsor_balance: u64) -> ExampleContext<CacheDB<EmptyDB>> {
+    let mut db = CacheDB::<EmptyDB>::default();
+    db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(BALANCE)));
+    db.insert_account_info(
+        SPONSOR,
+        AccountInfo::from_balance(U256::from(sponsor_balance)),
+    );
+    Context::mainnet()
+        .with_db(db)
+        .with_tx(ExampleTransaction::default())
+        .with_block(BlockEnv {
+            basefee: BASEFEE,
+            beneficiary: BENEFICIARY,
+            ..Default::default()
+        })
+        .with_cfg(CfgEnv::new_with_spec(spec))
+        .with_chain(ExampleChainInfo::default().with_l1_block_hash(7, L1_HASH))
+}
+
+fn tx(kind: TxKind, data: Vec<u8>, sponsor: Option<Address>) -> ExampleTransaction {
+    let tx = ExampleTransaction::new(
+        TxEnv::builder()
+            .caller(CALLER)
+            .kind(kind)
+            .data(data.into())
+            .value(U256::from(5))
+            .gas_limit(50_000)
+            .gas_price(20)
+            .gas_priority_fee(Some(3))
+            .build()
+            .unwrap(),
+    );
+    match sponsor {
+        Some(sponsor) => tx.with_sponsor(sponsor),
+        None => tx,
+    }
+}
+
+#[test]
+fn sponsor_pays_fee() {
+    let mut evm = ExampleEvm::new(context(ExampleSpecId::SPONSORSHIP, BALANCE), ());
+    let output = evm
+        .transact(tx(TxKind::Call(RECEIVER), vec![], Some(SPONSOR)))
+        .unwrap();
+    assert!(output.result.is_success());
+    let gas_used = output.result.gas_used();
+
+    // Caller only pays the transferred value.
+    assert_eq!(output.state[&CALLER].info.balance, U256::from(BALANCE - 5));
+    assert_eq!(output.state[&CALLER].info.nonce, 1);
+    assert_eq!(output.state[&RECEIVER].info.balance, U256::from(5));
+    assert_eq!(
+        output.state[&SPONSOR].info.balance,
+        U256::from(BALANCE - (BASEFEE + 3) * gas_used)
+    );
+    assert_eq!(
+        output.state[&BENEFICIARY].info.balance,
+        U256::from(3 * gas_used)
+    );
+}
+
+#[test]
+fn sponsored_tx_validation() {
+    let mut evm = ExampleEvm::new(context(ExampleSpecId::GENESIS, BALANCE), ());
+    assert_eq!(
+        evm.transact(tx(TxKind::Call(RECEIVER), vec![], Some(SPONSOR)))
+            .unwrap_err(),
+        EVMError::Transaction(ExampleTxError::SponsoredTxNotActive)
+    );
+    // Transactions paid by the caller are valid in every hardfork.
+    assert!(evm
+        .transact(tx(TxKind::Call(RECEIVER), vec![], None))
+        .unwrap()
+        .result
+        .is_success());
+
+    let mut evm = ExampleEvm::new(context(ExampleSpecId::SPONSORSHIP, 1_000), ());
+    let error = evm
+        .transact(tx(TxKind::Call(RECEIVER), vec![], Some(SPONSOR)))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        EVMError::Transaction(ExampleTxError::SponsorLackOfFund { .. })
+    ));
+    assert_eq!(error.code(), 1602);
+}
+
+#[test]
+fn l1_block_hash_precompile() {
+    let mut evm = ExampleEvm::new(context(ExampleSpecId::GENESIS, BALANCE), ());
+    let kind = TxKind::Call(L1_BLOCK_HASH_ADDRESS);
+
+    let output = evm
+        .transact(tx(kind, U256::from(7).to_be_bytes_vec(), None))
+        .unwrap();
+    assert_eq!(output.result.output().unwrap().as_ref(), L1_HASH.as_slice());
+
+    let output = evm
+        .transact(tx(kind, U256::from(8).to_be_bytes_vec(), None))
+        .unwrap();
+    let ExecutionResult::Halt { reason, gas_used } = output.result else {
+        panic!("transaction should halt");
+    };
+    assert_eq!(reason, ExampleHaltReason::UnknownL1Block);
+    assert_eq!(reason.code(), 3601);
+    assert_eq!(gas_used, 50_000);
+
+    // Failures of Ethereum precompiles keep the base halt reason, the point is not on the curve.
+    let output = evm
+        .transact(tx(
+            TxKind::Call(address!("0x0000000000000000000000000000000000000006")),
+            vec![1; 64],
+            None,
+        ))
+        .unwrap();
+    assert!(matches!(
+        output.result,
+        ExecutionResult::Halt {
+            reason: ExampleHaltReason::Base(HaltReason::PrecompileError),
+            ..
+        }
+    ));
+}
+
+#[derive(Default)]
+struct SettlementInspector {
+    settlement: Option<GasSettlement>,
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for SettlementInspector {
+    fn gas_settlement(&mut self, _context: &mut CTX, settlement: &GasSettlement) {
+        self.settlement = Some(settlement.clone());
+    }
+}
+
+#[test]
+fn inspect_sponsored_tx() {
+    let mut evm = ExampleEvm::new(
+        context(ExampleSpecId::SPONSORSHIP, BALANCE),
+        SettlementInspector::default(),
+    );
+    let output = evm
+        .inspect_tx(tx(TxKind::Call(RECEIVER), vec![], Some(SPONSOR)))
+        .unwrap();
+    assert!(output.result.is_success());
+
+    let settlement = evm.0.inspector.settlement.take().unwrap();
+    assert_eq!(settlement.caller, SPONSOR);
+    assert_eq!(
+        U256::from(BALANCE) - settlement.total_fee(),
+        output.state[&SPONSOR].info.balance
+    );
+    assert_eq!(
+        settlement.caller_refund + settlement.total_fee(),
+        U256::from(50_000 * 20)
+    );
+}